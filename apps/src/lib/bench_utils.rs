@@ -1,6 +1,7 @@
 //! Library code for benchmarks provides a wrapper of the ledger's shell
 //! `BenchShell` and helper functions to generate transactions.
 
+use std::collections::HashMap;
 use std::fs::{File, OpenOptions};
 use std::io::{Read, Write};
 use std::ops::{Deref, DerefMut};
@@ -251,6 +252,7 @@ impl Default for BenchShell {
                 voting_start_epoch,
                 voting_end_epoch: voting_start_epoch + 3_u64,
                 grace_epoch: voting_start_epoch + 9_u64,
+                deposit: Amount::native_whole(500),
             },
             None,
             Some(vec![content_section]),
@@ -639,7 +641,7 @@ impl ShieldedUtils for BenchShieldedUtils {
         // Fill the supplied context with the deserialized object
         *ctx = ShieldedContext {
             utils: ctx.utils.clone(),
-            ..ShieldedContext::deserialize(&mut &bytes[..])?
+            ..ShieldedContext::deserialize_versioned(&bytes)?
         };
         Ok(())
     }
@@ -661,10 +663,7 @@ impl ShieldedUtils for BenchShieldedUtils {
                 .write(true)
                 .create_new(true)
                 .open(tmp_path.clone())?;
-            let mut bytes = Vec::new();
-            ctx.serialize(&mut bytes)
-                .expect("cannot serialize shielded context");
-            ctx_file.write_all(&bytes[..])?;
+            ctx_file.write_all(&ctx.serialize_versioned())?;
         }
         // Atomically update the old shielded context file with new data.
         // Atomicity is required to prevent other client instances from reading
@@ -708,6 +707,7 @@ impl Client for BenchShell {
             vp_wasm_cache: self.vp_wasm_cache.read_only(),
             tx_wasm_cache: self.tx_wasm_cache.read_only(),
             storage_read_past_height_limit: None,
+            event_index: false,
         };
 
         if request.path == "/shell/dry_run_tx" {
@@ -759,12 +759,14 @@ impl Default for BenchShieldedCtx {
         chain_ctx.wallet.gen_store_spending_key(
             ALBERT_SPENDING_KEY.to_string(),
             None,
+            None,
             true,
             &mut OsRng,
         );
         chain_ctx.wallet.gen_store_spending_key(
             BERTHA_SPENDING_KEY.to_string(),
             None,
+            None,
             true,
             &mut OsRng,
         );
@@ -832,8 +834,10 @@ impl BenchShieldedCtx {
         async_runtime
             .block_on(self.shielded.fetch(
                 &self.shell,
+                &StdIo,
                 &[spending_key.into()],
                 &[],
+                &HashMap::new(),
             ))
             .unwrap();
         let native_token = self.shell.wl_storage.storage.native_token.clone();
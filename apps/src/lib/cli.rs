@@ -162,19 +162,22 @@ pub mod cmds {
     pub enum NamadaRelayer {
         EthBridgePool(EthBridgePool),
         ValidatorSet(ValidatorSet),
+        Relay(RelayDaemon),
     }
 
     impl Cmd for NamadaRelayer {
         fn add_sub(app: App) -> App {
             app.subcommand(EthBridgePool::def())
                 .subcommand(ValidatorSet::def())
+                .subcommand(RelayDaemon::def())
         }
 
         fn parse(matches: &ArgMatches) -> Option<Self> {
             let eth_bridge_pool =
                 SubCmd::parse(matches).map(Self::EthBridgePool);
             let validator_set = SubCmd::parse(matches).map(Self::ValidatorSet);
-            eth_bridge_pool.or(validator_set)
+            let relay = SubCmd::parse(matches).map(Self::Relay);
+            eth_bridge_pool.or(validator_set).or(relay)
         }
     }
 
@@ -217,11 +220,14 @@ pub mod cmds {
                 .subcommand(TxTransfer::def().display_order(1))
                 .subcommand(TxIbcTransfer::def().display_order(1))
                 .subcommand(TxUpdateAccount::def().display_order(1))
+                .subcommand(TxSpendingPolicy::def().display_order(1))
                 .subcommand(TxInitAccount::def().display_order(1))
                 .subcommand(TxRevealPk::def().display_order(1))
                 // Governance transactions
                 .subcommand(TxInitProposal::def().display_order(1))
                 .subcommand(TxVoteProposal::def().display_order(1))
+                .subcommand(TxDepositProposal::def().display_order(1))
+                .subcommand(TxDelegateVote::def().display_order(1))
                 // PoS transactions
                 .subcommand(TxBecomeValidator::def().display_order(2))
                 .subcommand(TxInitValidator::def().display_order(2))
@@ -238,6 +244,7 @@ pub mod cmds {
                 .subcommand(TxMetadataChange::def().display_order(2))
                 // Ethereum bridge transactions
                 .subcommand(AddToEthBridgePool::def().display_order(3))
+                .subcommand(CancelEthBridgePoolTransfer::def().display_order(3))
                 // PGF transactions
                 .subcommand(TxUpdateStewardCommission::def().display_order(4))
                 .subcommand(TxResignSteward::def().display_order(4))
@@ -259,6 +266,9 @@ pub mod cmds {
                 .subcommand(QueryProposal::def().display_order(5))
                 .subcommand(QueryProposalResult::def().display_order(5))
                 .subcommand(QueryProtocolParameters::def().display_order(5))
+                .subcommand(QueryPendingUpgrade::def().display_order(5))
+                .subcommand(QueryIbcRateLimits::def().display_order(5))
+                .subcommand(QueryErc20Whitelist::def().display_order(5))
                 .subcommand(QueryPgf::def().display_order(5))
                 .subcommand(QueryValidatorState::def().display_order(5))
                 .subcommand(QueryCommissionRate::def().display_order(5))
@@ -266,6 +276,9 @@ pub mod cmds {
                 .subcommand(QueryMetaData::def().display_order(5))
                 // Actions
                 .subcommand(SignTx::def().display_order(6))
+                .subcommand(CreateSigningRequest::def().display_order(6))
+                .subcommand(SignSigningRequest::def().display_order(6))
+                .subcommand(SubmitSigningRequest::def().display_order(6))
                 .subcommand(GenIbcShieldedTransafer::def().display_order(6))
                 // Utils
                 .subcommand(Utils::def().display_order(7))
@@ -278,6 +291,8 @@ pub mod cmds {
             let tx_ibc_transfer = Self::parse_with_ctx(matches, TxIbcTransfer);
             let tx_update_account =
                 Self::parse_with_ctx(matches, TxUpdateAccount);
+            let tx_spending_policy =
+                Self::parse_with_ctx(matches, TxSpendingPolicy);
             let tx_init_account = Self::parse_with_ctx(matches, TxInitAccount);
             let tx_become_validator =
                 Self::parse_with_ctx(matches, TxBecomeValidator);
@@ -294,6 +309,10 @@ pub mod cmds {
                 Self::parse_with_ctx(matches, TxInitProposal);
             let tx_vote_proposal =
                 Self::parse_with_ctx(matches, TxVoteProposal);
+            let tx_deposit_proposal =
+                Self::parse_with_ctx(matches, TxDepositProposal);
+            let tx_delegate_vote =
+                Self::parse_with_ctx(matches, TxDelegateVote);
             let tx_update_steward_commission =
                 Self::parse_with_ctx(matches, TxUpdateStewardCommission);
             let tx_resign_steward =
@@ -334,6 +353,12 @@ pub mod cmds {
                 Self::parse_with_ctx(matches, QueryProposalResult);
             let query_protocol_parameters =
                 Self::parse_with_ctx(matches, QueryProtocolParameters);
+            let query_pending_upgrade =
+                Self::parse_with_ctx(matches, QueryPendingUpgrade);
+            let query_ibc_rate_limits =
+                Self::parse_with_ctx(matches, QueryIbcRateLimits);
+            let query_erc20_whitelist =
+                Self::parse_with_ctx(matches, QueryErc20Whitelist);
             let query_pgf = Self::parse_with_ctx(matches, QueryPgf);
             let query_validator_state =
                 Self::parse_with_ctx(matches, QueryValidatorState);
@@ -342,7 +367,15 @@ pub mod cmds {
             let query_metadata = Self::parse_with_ctx(matches, QueryMetaData);
             let add_to_eth_bridge_pool =
                 Self::parse_with_ctx(matches, AddToEthBridgePool);
+            let cancel_eth_bridge_pool_transfer =
+                Self::parse_with_ctx(matches, CancelEthBridgePoolTransfer);
             let sign_tx = Self::parse_with_ctx(matches, SignTx);
+            let create_signing_request =
+                Self::parse_with_ctx(matches, CreateSigningRequest);
+            let sign_signing_request =
+                Self::parse_with_ctx(matches, SignSigningRequest);
+            let submit_signing_request =
+                Self::parse_with_ctx(matches, SubmitSigningRequest);
             let gen_ibc_shielded =
                 Self::parse_with_ctx(matches, GenIbcShieldedTransafer);
             let utils = SubCmd::parse(matches).map(Self::WithoutContext);
@@ -350,10 +383,13 @@ pub mod cmds {
                 .or(tx_transfer)
                 .or(tx_ibc_transfer)
                 .or(tx_update_account)
+                .or(tx_spending_policy)
                 .or(tx_init_account)
                 .or(tx_reveal_pk)
                 .or(tx_init_proposal)
                 .or(tx_vote_proposal)
+                .or(tx_deposit_proposal)
+                .or(tx_delegate_vote)
                 .or(tx_become_validator)
                 .or(tx_init_validator)
                 .or(tx_commission_rate_change)
@@ -368,6 +404,7 @@ pub mod cmds {
                 .or(redelegate)
                 .or(claim_rewards)
                 .or(add_to_eth_bridge_pool)
+                .or(cancel_eth_bridge_pool_transfer)
                 .or(tx_update_steward_commission)
                 .or(tx_resign_steward)
                 .or(query_epoch)
@@ -387,12 +424,18 @@ pub mod cmds {
                 .or(query_proposal)
                 .or(query_proposal_result)
                 .or(query_protocol_parameters)
+                .or(query_pending_upgrade)
+                .or(query_ibc_rate_limits)
+                .or(query_erc20_whitelist)
                 .or(query_pgf)
                 .or(query_validator_state)
                 .or(query_commission)
                 .or(query_metadata)
                 .or(query_account)
                 .or(sign_tx)
+                .or(create_signing_request)
+                .or(sign_signing_request)
+                .or(submit_signing_request)
                 .or(gen_ibc_shielded)
                 .or(utils)
         }
@@ -436,6 +479,7 @@ pub mod cmds {
         TxIbcTransfer(TxIbcTransfer),
         QueryResult(QueryResult),
         TxUpdateAccount(TxUpdateAccount),
+        TxSpendingPolicy(TxSpendingPolicy),
         TxInitAccount(TxInitAccount),
         TxBecomeValidator(TxBecomeValidator),
         TxInitValidator(TxInitValidator),
@@ -447,6 +491,8 @@ pub mod cmds {
         TxReactivateValidator(TxReactivateValidator),
         TxInitProposal(TxInitProposal),
         TxVoteProposal(TxVoteProposal),
+        TxDepositProposal(TxDepositProposal),
+        TxDelegateVote(TxDelegateVote),
         TxRevealPk(TxRevealPk),
         Bond(Bond),
         Unbond(Unbond),
@@ -454,6 +500,7 @@ pub mod cmds {
         ClaimRewards(ClaimRewards),
         Redelegate(Redelegate),
         AddToEthBridgePool(AddToEthBridgePool),
+        CancelEthBridgePoolTransfer(CancelEthBridgePoolTransfer),
         TxUpdateStewardCommission(TxUpdateStewardCommission),
         TxResignSteward(TxResignSteward),
         QueryEpoch(QueryEpoch),
@@ -474,10 +521,16 @@ pub mod cmds {
         QueryProposal(QueryProposal),
         QueryProposalResult(QueryProposalResult),
         QueryProtocolParameters(QueryProtocolParameters),
+        QueryPendingUpgrade(QueryPendingUpgrade),
+        QueryIbcRateLimits(QueryIbcRateLimits),
+        QueryErc20Whitelist(QueryErc20Whitelist),
         QueryPgf(QueryPgf),
         QueryValidatorState(QueryValidatorState),
         QueryRewards(QueryRewards),
         SignTx(SignTx),
+        CreateSigningRequest(CreateSigningRequest),
+        SignSigningRequest(SignSigningRequest),
+        SubmitSigningRequest(SubmitSigningRequest),
         GenIbcShieldedTransafer(GenIbcShieldedTransafer),
     }
 
@@ -1083,6 +1136,85 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct QueryPendingUpgrade(
+        pub args::QueryPendingUpgrade<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryPendingUpgrade {
+        const CMD: &'static str = "query-pending-upgrade";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryPendingUpgrade(args::QueryPendingUpgrade::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the upgrade scheduled by an accepted upgrade \
+                     proposal.",
+                )
+                .add_args::<args::QueryPendingUpgrade<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryIbcRateLimits(pub args::QueryIbcRateLimits<args::CliTypes>);
+
+    impl SubCmd for QueryIbcRateLimits {
+        const CMD: &'static str = "query-ibc-rate-limits";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryIbcRateLimits(args::QueryIbcRateLimits::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the rate limits of the IBC token transfers with \
+                     their current usage.",
+                )
+                .add_args::<args::QueryIbcRateLimits<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct QueryErc20Whitelist(
+        pub args::QueryErc20Whitelist<args::CliTypes>,
+    );
+
+    impl SubCmd for QueryErc20Whitelist {
+        const CMD: &'static str = "query-erc20-whitelist";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                QueryErc20Whitelist(args::QueryErc20Whitelist::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Query the ERC20 tokens whitelisted by the Ethereum \
+                     bridge with the used amount of their caps.",
+                )
+                .add_args::<args::QueryErc20Whitelist<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryPgf(pub args::QueryPgf<args::CliTypes>);
 
@@ -1594,6 +1726,77 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct CreateSigningRequest(
+        pub args::CreateSigningRequest<args::CliTypes>,
+    );
+
+    impl SubCmd for CreateSigningRequest {
+        const CMD: &'static str = "create-signing-request";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                CreateSigningRequest(args::CreateSigningRequest::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Create a request for the signatures of the keys of a \
+                     multisignature account over a serialized transaction.",
+                )
+                .add_args::<args::CreateSigningRequest<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct SignSigningRequest(pub args::SignSigningRequest<args::CliTypes>);
+
+    impl SubCmd for SignSigningRequest {
+        const CMD: &'static str = "sign-request";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                SignSigningRequest(args::SignSigningRequest::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Append the signatures of the keys found in the wallet to \
+                     a signing request. This command does not connect to a \
+                     node.",
+                )
+                .add_args::<args::SignSigningRequest<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct SubmitSigningRequest(
+        pub args::SubmitSigningRequest<args::CliTypes>,
+    );
+
+    impl SubCmd for SubmitSigningRequest {
+        const CMD: &'static str = "submit-signing-request";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                SubmitSigningRequest(args::SubmitSigningRequest::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Check that the signatures of signing requests meet the \
+                     threshold of the account and submit the transaction.",
+                )
+                .add_args::<args::SubmitSigningRequest<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct QueryValidatorState(
         pub args::QueryValidatorState<args::CliTypes>,
@@ -1829,6 +2032,34 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct TxSpendingPolicy(
+        pub args::UpdateSpendingPolicy<args::CliTypes>,
+    );
+
+    impl SubCmd for TxSpendingPolicy {
+        const CMD: &'static str = "spending-policy";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxSpendingPolicy(args::UpdateSpendingPolicy::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Set or clear the spending policy of an account, or \
+                     schedule, execute or cancel a withdrawal above its \
+                     threshold.",
+                )
+                .add_args::<args::UpdateSpendingPolicy<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxResignSteward(pub args::ResignSteward<args::CliTypes>);
 
@@ -1948,6 +2179,56 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct TxDepositProposal(pub args::DepositProposal<args::CliTypes>);
+
+    impl SubCmd for TxDepositProposal {
+        const CMD: &'static str = "deposit-proposal";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxDepositProposal(args::DepositProposal::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Top up the deposit of a proposal until it reaches the \
+                     minimum required for voting to start.",
+                )
+                .add_args::<args::DepositProposal<args::CliTypes>>()
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct TxDelegateVote(pub args::DelegateVote<args::CliTypes>);
+
+    impl SubCmd for TxDelegateVote {
+        const CMD: &'static str = "delegate-vote";
+
+        fn parse(matches: &ArgMatches) -> Option<Self>
+        where
+            Self: Sized,
+        {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                TxDelegateVote(args::DelegateVote::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Delegate the governance voting power of an address to a \
+                     representative, or revoke the delegation.",
+                )
+                .add_args::<args::DelegateVote<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct TxRevealPk(pub args::RevealPk<args::CliTypes>);
 
@@ -1996,7 +2277,10 @@ pub mod cmds {
 
         fn def() -> App {
             App::new(Self::CMD)
-                .about("Generate shielded transfer for IBC.")
+                .about(
+                    "Generate shielded transfer for IBC. A token received by \
+                     a payment address without it is shielded natively.",
+                )
                 .add_args::<args::GenIbcShieldedTransafer<args::CliTypes>>()
         }
     }
@@ -2352,6 +2636,8 @@ pub mod cmds {
         /// Get a recommendation on a batch of transfers
         /// to relay.
         RecommendBatch(RecommendBatch),
+        /// Raise the gas fees of a transfer in the pool.
+        BumpFee(BumpBridgePoolFee),
     }
 
     /// Ethereum Bridge pool commands not requiring [`super::Context`].
@@ -2376,6 +2662,7 @@ pub mod cmds {
     impl Cmd for EthBridgePool {
         fn add_sub(app: App) -> App {
             app.subcommand(RecommendBatch::def().display_order(1))
+                .subcommand(BumpBridgePoolFee::def().display_order(1))
                 .subcommand(ConstructProof::def().display_order(1))
                 .subcommand(RelayProof::def().display_order(1))
                 .subcommand(QueryEthBridgePool::def().display_order(1))
@@ -2388,6 +2675,7 @@ pub mod cmds {
             use EthBridgePoolWithoutCtx::*;
 
             let recommend = Self::parse_with_ctx(matches, RecommendBatch);
+            let bump_fee = Self::parse_with_ctx(matches, BumpFee);
             let construct_proof =
                 Self::parse_without_ctx(matches, ConstructProof);
             let relay_proof = Self::parse_without_ctx(matches, RelayProof);
@@ -2397,6 +2685,7 @@ pub mod cmds {
 
             construct_proof
                 .or(recommend)
+                .or(bump_fee)
                 .or(relay_proof)
                 .or(query_pool)
                 .or(query_signed)
@@ -2439,6 +2728,7 @@ pub mod cmds {
                 .subcommand_required(true)
                 .subcommand(ConstructProof::def().display_order(1))
                 .subcommand(RecommendBatch::def().display_order(1))
+                .subcommand(BumpBridgePoolFee::def().display_order(1))
                 .subcommand(RelayProof::def().display_order(1))
                 .subcommand(QueryEthBridgePool::def().display_order(1))
                 .subcommand(QuerySignedBridgePool::def().display_order(1))
@@ -2466,6 +2756,31 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct CancelEthBridgePoolTransfer(
+        pub args::CancelBridgePoolTransfer<args::CliTypes>,
+    );
+
+    impl SubCmd for CancelEthBridgePoolTransfer {
+        const CMD: &'static str = "cancel-erc20-transfer";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                Self(args::CancelBridgePoolTransfer::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Cancel a transfer in the Ethereum Bridge pool, or set \
+                     the epoch from which it is refunded.",
+                )
+                .arg_required_else_help(true)
+                .add_args::<args::CancelBridgePoolTransfer<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct ConstructProof(pub args::BridgePoolProof<args::CliTypes>);
 
@@ -2512,6 +2827,32 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct BumpBridgePoolFee(
+        pub args::BumpBridgePoolTransferFee<args::CliTypes>,
+    );
+
+    impl SubCmd for BumpBridgePoolFee {
+        const CMD: &'static str = "bump-fee";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches.subcommand_matches(Self::CMD).map(|matches| {
+                Self(args::BumpBridgePoolTransferFee::parse(matches))
+            })
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Raise the gas fees of a transfer in the Ethereum bridge \
+                     pool, keeping its hash. The additional fees are paid in \
+                     the token of its gas fees by their payer.",
+                )
+                .arg_required_else_help(true)
+                .add_args::<args::BumpBridgePoolTransferFee<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct RecommendBatch(pub args::RecommendBatch<args::CliTypes>);
 
@@ -2742,6 +3083,29 @@ pub mod cmds {
         }
     }
 
+    #[derive(Clone, Debug)]
+    pub struct RelayDaemon(pub args::RelayDaemon<args::CliTypes>);
+
+    impl SubCmd for RelayDaemon {
+        const CMD: &'static str = "relay";
+
+        fn parse(matches: &ArgMatches) -> Option<Self> {
+            matches
+                .subcommand_matches(Self::CMD)
+                .map(|matches| Self(args::RelayDaemon::parse(matches)))
+        }
+
+        fn def() -> App {
+            App::new(Self::CMD)
+                .about(
+                    "Run a daemon that relays validator set updates and \
+                     profitable batches of transfers in the Bridge pool to \
+                     Namada's Ethereum bridge smart contracts.",
+                )
+                .add_args::<args::RelayDaemon<args::CliTypes>>()
+        }
+    }
+
     #[derive(Clone, Debug)]
     pub struct PkToTmAddress(pub args::PkToTmAddress);
 
@@ -2789,7 +3153,7 @@ pub mod cmds {
 }
 
 pub mod args {
-    use std::collections::HashMap;
+    use std::collections::{BTreeSet, HashMap};
     use std::convert::TryFrom;
     use std::env;
     use std::net::SocketAddr;
@@ -2797,13 +3161,14 @@ pub mod args {
     use std::str::FromStr;
 
     use namada::ibc::core::host::types::identifiers::{ChannelId, PortId};
+    use namada::types::account::{AccountKeyOptions, KeyRole};
     use namada::types::address::{Address, EstablishedAddress};
     use namada::types::chain::{ChainId, ChainIdPrefix};
     use namada::types::dec::Dec;
     use namada::types::ethereum_events::EthAddress;
     use namada::types::keccak::KeccakHash;
     use namada::types::key::*;
-    use namada::types::masp::PaymentAddress;
+    use namada::types::masp::{KeyBirthday, PaymentAddress};
     use namada::types::storage::{self, BlockHeight, Epoch};
     use namada::types::time::DateTimeUtc;
     use namada::types::token;
@@ -2811,13 +3176,15 @@ pub mod args {
     use namada::types::transaction::GasLimit;
     pub use namada_sdk::args::*;
     pub use namada_sdk::tx::{
-        TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
+        TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_BUMP_FEE_WASM,
+        TX_BRIDGE_POOL_CANCEL_WASM, TX_BRIDGE_POOL_WASM,
         TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
         TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
-        TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
-        TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-        TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
-        TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
+        TX_DEACTIVATE_VALIDATOR_WASM, TX_DELEGATE_VOTE, TX_DEPOSIT_PROPOSAL,
+        TX_IBC_WASM, TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL,
+        TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM, TX_RESIGN_STEWARD,
+        TX_REVEAL_PK, TX_SPENDING_POLICY_WASM, TX_TRANSFER_WASM,
+        TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
         TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
         VP_USER_WASM,
     };
@@ -2838,6 +3205,7 @@ pub mod args {
     pub const ALIAS_MANY: ArgMulti<String, GlobPlus> = arg_multi("aliases");
     pub const ALLOW_DUPLICATE_IP: ArgFlag = flag("allow-duplicate-ip");
     pub const AMOUNT: Arg<token::DenominatedAmount> = arg("amount");
+    pub const AMOUNT_OPT: ArgOpt<token::DenominatedAmount> = AMOUNT.opt();
     pub const ARCHIVE_DIR: ArgOpt<PathBuf> = arg_opt("archive-dir");
     pub const BALANCE_OWNER: ArgOpt<WalletBalanceOwner> = arg_opt("owner");
     pub const BASE_DIR: ArgDefault<PathBuf> = arg_default(
@@ -2847,6 +3215,7 @@ pub mod args {
             Err(_) => config::get_default_namada_folder(),
         }),
     );
+    pub const BIRTHDAY: ArgOpt<KeyBirthday> = arg_opt("birthday");
     pub const BLOCK_HEIGHT: Arg<BlockHeight> = arg("block-height");
     pub const BLOCK_HEIGHT_OPT: ArgOpt<BlockHeight> = arg_opt("height");
     pub const BRIDGE_POOL_GAS_AMOUNT: ArgDefault<token::DenominatedAmount> =
@@ -2867,11 +3236,15 @@ pub mod args {
             DefaultFn(|| "NAM".parse().unwrap()),
         );
     pub const BRIDGE_POOL_TARGET: Arg<EthAddress> = arg("target");
+    pub const BRIDGE_POOL_EXPIRY: ArgOpt<Epoch> = arg_opt("expiry");
+    pub const BRIDGE_POOL_TRANSFER_HASH: Arg<String> = arg("transfer-hash");
     pub const BROADCAST_ONLY: ArgFlag = flag("broadcast-only");
+    pub const CANCEL_WITHDRAWAL: ArgOpt<u64> = arg_opt("cancel-withdrawal");
     pub const CHAIN_ID: Arg<ChainId> = arg("chain-id");
     pub const CHAIN_ID_OPT: ArgOpt<ChainId> = CHAIN_ID.opt();
     pub const CHAIN_ID_PREFIX: Arg<ChainIdPrefix> = arg("chain-prefix");
     pub const CHANNEL_ID: Arg<ChannelId> = arg("channel-id");
+    pub const CLEAR_POLICY: ArgFlag = flag("clear-policy");
     pub const CODE_PATH: Arg<PathBuf> = arg("code-path");
     pub const CODE_PATH_OPT: ArgOpt<PathBuf> = CODE_PATH.opt();
     pub const COMMISSION_RATE: Arg<Dec> = arg("commission-rate");
@@ -2912,6 +3285,7 @@ pub mod args {
     );
     pub const ETH_SYNC: ArgFlag = flag("sync");
     pub const EXPIRATION_OPT: ArgOpt<DateTimeUtc> = arg_opt("expiration");
+    pub const EXECUTE_WITHDRAWAL: ArgOpt<u64> = arg_opt("execute-withdrawal");
     pub const EMAIL: Arg<String> = arg("email");
     pub const EMAIL_OPT: ArgOpt<String> = EMAIL.opt();
     pub const FEE_UNSHIELD_SPENDING_KEY: ArgOpt<WalletTransferSource> =
@@ -2949,6 +3323,8 @@ pub mod args {
     pub const HISTORIC: ArgFlag = flag("historic");
     pub const IBC_TRANSFER_MEMO_PATH: ArgOpt<PathBuf> = arg_opt("memo-path");
     pub const INPUT_OPT: ArgOpt<PathBuf> = arg_opt("input");
+    pub const KEY_ROLES: ArgMulti<String, GlobStar> = arg_multi("key-roles");
+    pub const KEY_WEIGHTS: ArgMulti<u8, GlobStar> = arg_multi("key-weights");
     pub const LEDGER_ADDRESS_ABOUT: &str =
         "Address of a ledger node as \"{scheme}://{host}:{port}\". If the \
          scheme is not supplied, it is assumed to be TCP.";
@@ -2964,6 +3340,8 @@ pub mod args {
     pub const MAX_COMMISSION_RATE_CHANGE: Arg<Dec> =
         arg("max-commission-rate-change");
     pub const MAX_ETH_GAS: ArgOpt<u64> = arg_opt("max_eth-gas");
+    pub const MIN_PROFIT: ArgDefault<i64> =
+        arg_default("min-profit", DefaultFn(|| 0));
     pub const MODE: ArgOpt<String> = arg_opt("mode");
     pub const NET_ADDRESS: Arg<SocketAddr> = arg("net-address");
     pub const NAMADA_START_TIME: ArgOpt<DateTimeUtc> = arg_opt("time");
@@ -2982,7 +3360,12 @@ pub mod args {
         DefaultFn(|| PortId::from_str("transfer").unwrap()),
     );
     pub const PRE_GENESIS: ArgFlag = flag("pre-genesis");
+    pub const PROPOSAL_DEPOSIT_OPT: ArgOpt<token::DenominatedAmount> =
+        arg_opt("deposit");
+    pub const PROPOSAL_EMERGENCY: ArgFlag = flag("emergency");
     pub const PROPOSAL_ETH: ArgFlag = flag("eth");
+    pub const PROPOSAL_PARAMETER_CHANGE: ArgFlag = flag("parameter-change");
+    pub const PROPOSAL_UPGRADE: ArgFlag = flag("upgrade");
     pub const PROPOSAL_PGF_STEWARD: ArgFlag = flag("pgf-stewards");
     pub const PROPOSAL_PGF_FUNDING: ArgFlag = flag("pgf-funding");
     pub const PROPOSAL_OFFLINE: ArgFlag = flag("offline");
@@ -3011,6 +3394,12 @@ pub mod args {
         RAW_PUBLIC_KEY_HASH.opt();
     pub const RECEIVER: Arg<String> = arg("receiver");
     pub const RELAYER: Arg<Address> = arg("relayer");
+    pub const RELAYER_STATE_PATH: ArgOpt<PathBuf> = arg_opt("state-path");
+    pub const REPRESENTATIVE_OPT: ArgOpt<WalletAddress> =
+        arg_opt("representative");
+    pub const REQUEST_PATH: Arg<PathBuf> = arg("request-path");
+    pub const REQUEST_PATHS: ArgMulti<PathBuf, GlobPlus> =
+        arg_multi("request-paths");
     pub const SAFE_MODE: ArgFlag = flag("safe-mode");
     pub const SCHEME: ArgDefault<SchemeType> =
         arg_default("scheme", DefaultFn(|| SchemeType::Ed25519));
@@ -3038,6 +3427,7 @@ pub mod args {
     pub const TRANSFER_SOURCE: Arg<WalletTransferSource> = arg("source");
     pub const TRANSFER_TARGET: Arg<WalletTransferTarget> = arg("target");
     pub const TRANSPARENT: ArgFlag = flag("transparent");
+    pub const WITHDRAWAL_TARGET: ArgOpt<WalletAddress> = arg_opt("target");
     pub const TX_HASH: Arg<String> = arg("tx-hash");
     pub const THRESHOLD: ArgOpt<u8> = arg_opt("threshold");
     pub const UNSAFE_DONT_ENCRYPT: ArgFlag = flag("unsafe-dont-encrypt");
@@ -3378,6 +3768,135 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<CancelBridgePoolTransfer<SdkTypes>>
+        for CancelBridgePoolTransfer<CliTypes>
+    {
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> CancelBridgePoolTransfer<SdkTypes> {
+            CancelBridgePoolTransfer::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                transfer_hash: self.transfer_hash,
+                expiry: self.expiry,
+                code_path: self.code_path,
+            }
+        }
+    }
+
+    impl Args for CancelBridgePoolTransfer<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let hash = BRIDGE_POOL_TRANSFER_HASH.parse(matches);
+            let transfer_hash = KeccakHash::try_from(hash.as_str())
+                .unwrap_or_else(|_| {
+                    tracing::info!(
+                        "Could not parse '{}' as a Keccak hash.",
+                        hash
+                    );
+                    safe_exit(1)
+                });
+            let expiry = BRIDGE_POOL_EXPIRY.parse(matches);
+            let code_path = PathBuf::from(TX_BRIDGE_POOL_CANCEL_WASM);
+            Self {
+                tx,
+                transfer_hash,
+                expiry,
+                code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(BRIDGE_POOL_TRANSFER_HASH.def().help(
+                    "The Keccak hash of the transfer in the Bridge pool.",
+                ))
+                .arg(BRIDGE_POOL_EXPIRY.def().help(
+                    "The epoch from which the transfer is refunded by the \
+                     protocol, unless it has been signed by the validators by \
+                     then. If unset, the transfer is cancelled right away.",
+                ))
+        }
+    }
+
+    impl CliToSdk<BumpBridgePoolTransferFee<SdkTypes>>
+        for BumpBridgePoolTransferFee<CliTypes>
+    {
+        fn to_sdk(
+            self,
+            ctx: &mut Context,
+        ) -> BumpBridgePoolTransferFee<SdkTypes> {
+            BumpBridgePoolTransferFee::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                transfer_hash: self.transfer_hash,
+                amount: self.amount,
+                code_path: self.code_path,
+            }
+        }
+    }
+
+    impl Args for BumpBridgePoolTransferFee<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let hash = BRIDGE_POOL_TRANSFER_HASH.parse(matches);
+            let transfer_hash = KeccakHash::try_from(hash.as_str())
+                .unwrap_or_else(|_| {
+                    tracing::info!(
+                        "Could not parse '{}' as a Keccak hash.",
+                        hash
+                    );
+                    safe_exit(1)
+                });
+            let amount = InputAmount::Unvalidated(AMOUNT.parse(matches));
+            let code_path = PathBuf::from(TX_BRIDGE_POOL_BUMP_FEE_WASM);
+            Self {
+                tx,
+                transfer_hash,
+                amount,
+                code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(BRIDGE_POOL_TRANSFER_HASH.def().help(
+                    "The Keccak hash of the transfer in the Bridge pool.",
+                ))
+                .arg(AMOUNT.def().help(
+                    "The amount of gas fees added to the transfer, in the \
+                     token of its gas fees.",
+                ))
+        }
+    }
+
+    /// Read a Bridge pool recommendations conversion rates table
+    /// from the given JSON file.
+    fn read_conversion_table(
+        chain_ctx: &ChainContext,
+        path: PathBuf,
+    ) -> HashMap<Address, BpConversionTableEntry> {
+        let file = std::io::BufReader::new(std::fs::File::open(path).expect(
+            "Failed to open the provided file to the conversion table",
+        ));
+        let table: HashMap<String, f64> = serde_json::from_reader(file)
+            .expect("Failed to parse conversion table");
+        table
+            .into_iter()
+            .map(|(token, conversion_rate)| {
+                let token_from_ctx = FromContext::<Address>::new(token);
+                let address = chain_ctx.get(&token_from_ctx);
+                let alias = token_from_ctx.raw;
+                (
+                    address,
+                    BpConversionTableEntry {
+                        alias,
+                        conversion_rate,
+                    },
+                )
+            })
+            .collect()
+    }
+
     impl CliToSdk<RecommendBatch<SdkTypes>> for RecommendBatch<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> RecommendBatch<SdkTypes> {
             let chain_ctx = ctx.borrow_chain_or_exit();
@@ -3385,33 +3904,10 @@ pub mod args {
                 query: self.query.to_sdk_ctxless(),
                 max_gas: self.max_gas,
                 gas: self.gas,
-                conversion_table: {
-                    let file = std::io::BufReader::new(
-                        std::fs::File::open(self.conversion_table).expect(
-                            "Failed to open the provided file to the \
-                             conversion table",
-                        ),
-                    );
-                    let table: HashMap<String, f64> =
-                        serde_json::from_reader(file)
-                            .expect("Failed to parse conversion table");
-                    table
-                        .into_iter()
-                        .map(|(token, conversion_rate)| {
-                            let token_from_ctx =
-                                FromContext::<Address>::new(token);
-                            let address = chain_ctx.get(&token_from_ctx);
-                            let alias = token_from_ctx.raw;
-                            (
-                                address,
-                                BpConversionTableEntry {
-                                    alias,
-                                    conversion_rate,
-                                },
-                            )
-                        })
-                        .collect()
-                },
+                conversion_table: read_conversion_table(
+                    chain_ctx,
+                    self.conversion_table,
+                ),
             }
         }
     }
@@ -3593,6 +4089,133 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<RelayDaemon<SdkTypes>> for RelayDaemon<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> RelayDaemon<SdkTypes> {
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            let state_path = self.state_path.unwrap_or_else(|| {
+                chain_ctx
+                    .config
+                    .ledger
+                    .chain_dir()
+                    .join("relayer_state.json")
+            });
+            RelayDaemon::<SdkTypes> {
+                query: self.query.to_sdk_ctxless(),
+                relayer: self.relayer,
+                conversion_table: read_conversion_table(
+                    chain_ctx,
+                    self.conversion_table,
+                ),
+                max_gas: self.max_gas,
+                min_profit: self.min_profit,
+                state_path: Some(state_path),
+                confirmations: self.confirmations,
+                eth_rpc_endpoint: (),
+                gas_price: self.gas_price,
+                eth_addr: self.eth_addr,
+                sync: self.sync,
+                retry_dur: self.retry_dur,
+                success_dur: self.success_dur,
+                safe_mode: self.safe_mode,
+            }
+        }
+    }
+
+    impl Args for RelayDaemon<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let safe_mode = SAFE_MODE.parse(matches);
+            let query = Query::parse(matches);
+            let relayer = RELAYER.parse(matches);
+            let conversion_table = CONVERSION_TABLE.parse(matches);
+            let max_gas = MAX_ETH_GAS.parse(matches);
+            let min_profit = MIN_PROFIT.parse(matches);
+            let state_path = RELAYER_STATE_PATH.parse(matches);
+            let gas_price = ETH_GAS_PRICE.parse(matches);
+            let eth_rpc_endpoint = ETH_RPC_ENDPOINT.parse(matches);
+            let eth_addr = ETH_ADDRESS_OPT.parse(matches);
+            let confirmations = ETH_CONFIRMATIONS.parse(matches);
+            let sync = ETH_SYNC.parse(matches);
+            let retry_dur =
+                DAEMON_MODE_RETRY_DUR.parse(matches).map(|dur| dur.0);
+            let success_dur =
+                DAEMON_MODE_SUCCESS_DUR.parse(matches).map(|dur| dur.0);
+            Self {
+                query,
+                relayer,
+                conversion_table,
+                max_gas,
+                min_profit,
+                state_path,
+                confirmations,
+                eth_rpc_endpoint,
+                gas_price,
+                eth_addr,
+                sync,
+                retry_dur,
+                success_dur,
+                safe_mode,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+                .arg(SAFE_MODE.def().help(
+                    "Safe mode overrides keyboard interrupt signals, to \
+                     ensure Ethereum transfers aren't canceled midway through.",
+                ))
+                .arg(
+                    RELAYER
+                        .def()
+                        .help("The rewards address for relaying proofs."),
+                )
+                .arg(CONVERSION_TABLE.def().help(
+                    "Path to a JSON object containing a mapping between token \
+                     aliases (or addresses) and their conversion rates in gwei",
+                ))
+                .arg(MAX_ETH_GAS.def().help(
+                    "The maximum amount Ethereum gas that can be spent \
+                     relaying a batch of transfers.",
+                ))
+                .arg(MIN_PROFIT.def().allow_negative_numbers(true).help(
+                    "The minimum net profit, in gwei, of a batch of transfers \
+                     for it to be relayed. A negative value allows relaying \
+                     batches at a loss of up to its magnitude. Defaults to 0.",
+                ))
+                .arg(RELAYER_STATE_PATH.def().help(
+                    "Path to the file where the relays in flight are \
+                     persisted across restarts. Defaults to \
+                     `relayer_state.json` in the chain directory.",
+                ))
+                .arg(DAEMON_MODE_RETRY_DUR.def().help(
+                    "The amount of time to sleep between iterations that \
+                     relayed nothing.",
+                ))
+                .arg(DAEMON_MODE_SUCCESS_DUR.def().help(
+                    "The amount of time to sleep between iterations that \
+                     relayed something successfully.",
+                ))
+                .arg(ETH_ADDRESS_OPT.def().help(
+                    "The address of the Ethereum wallet to pay the gas fees. \
+                     If unset, the default wallet is used.",
+                ))
+                .arg(
+                    ETH_GAS_PRICE.def().help(
+                        "The price of Ethereum gas, during the relay calls.",
+                    ),
+                )
+                .arg(ETH_RPC_ENDPOINT.def().help("The Ethereum RPC endpoint."))
+                .arg(
+                    ETH_CONFIRMATIONS
+                        .def()
+                        .help("The number of block confirmations on Ethereum."),
+                )
+                .arg(ETH_SYNC.def().help(
+                    "Synchronize with the network, or exit immediately, if \
+                     the Ethereum node has fallen behind.",
+                ))
+        }
+    }
+
     impl CliToSdkCtxless<BridgeValidatorSet<SdkTypes>>
         for BridgeValidatorSet<CliTypes>
     {
@@ -4300,6 +4923,7 @@ pub mod args {
                     .map(|pk| chain_ctx.get(pk))
                     .collect(),
                 threshold: self.threshold,
+                key_options: self.key_options,
             }
         }
     }
@@ -4312,6 +4936,17 @@ pub mod args {
             let tx_code_path = PathBuf::from(TX_UPDATE_ACCOUNT_WASM);
             let public_keys = PUBLIC_KEYS.parse(matches);
             let threshold = THRESHOLD.parse(matches);
+            let key_weights = KEY_WEIGHTS.parse(matches);
+            let key_roles = KEY_ROLES.parse(matches);
+            let key_options = (0..key_weights.len().max(key_roles.len()))
+                .map(|index| AccountKeyOptions {
+                    weight: key_weights.get(index).copied().unwrap_or(1),
+                    roles: key_roles
+                        .get(index)
+                        .map(|roles| parse_key_roles(roles))
+                        .unwrap_or_default(),
+                })
+                .collect();
             Self {
                 tx,
                 vp_code_path,
@@ -4319,6 +4954,7 @@ pub mod args {
                 tx_code_path,
                 public_keys,
                 threshold,
+                key_options,
             }
         }
 
@@ -4342,7 +4978,34 @@ pub mod args {
                      authorization. Must be less then the maximum number of \
                      public keys provided.",
                 ))
+                .arg(KEY_WEIGHTS.def().help(
+                    "A list of the weights of the account's public keys, in \
+                     the order of the keys. The signatures of a key count \
+                     towards the threshold with its weight. Defaults to 1.",
+                ))
+                .arg(KEY_ROLES.def().help(
+                    "A list of the roles of the account's public keys, in the \
+                     order of the keys. Each entry is either `any` or a \
+                     `+`-separated list of `governance` and `staking`. A key \
+                     with roles can only sign for the actions of its roles. \
+                     Defaults to `any`.",
+                ))
+        }
+    }
+
+    /// Parse a `+`-separated list of key roles, or `any` for no roles
+    fn parse_key_roles(raw: &str) -> BTreeSet<KeyRole> {
+        if raw == "any" {
+            return BTreeSet::new();
         }
+        raw.split('+')
+            .map(|role| {
+                role.parse().unwrap_or_else(|err| {
+                    eprintln!("{}", err);
+                    safe_exit(1)
+                })
+            })
+            .collect()
     }
 
     impl CliToSdk<Bond<SdkTypes>> for Bond<CliTypes> {
@@ -4482,12 +5145,140 @@ pub mod args {
 
         fn def(app: App) -> App {
             app.add_args::<Tx<CliTypes>>()
-                .arg(STEWARD.def().help("Steward address."))
-                .arg(DATA_PATH.def().help(
-                    "The path to the file that describes the commission \
-                     split. The file must contain a map from namada address \
-                     to a percentage. Percentages must sum to 1 or less.",
+                .arg(STEWARD.def().help("Steward address."))
+                .arg(DATA_PATH.def().help(
+                    "The path to the file that describes the commission \
+                     split. The file must contain a map from namada address \
+                     to a percentage. Percentages must sum to 1 or less.",
+                ))
+        }
+    }
+
+    impl CliToSdk<UpdateSpendingPolicy<SdkTypes>>
+        for UpdateSpendingPolicy<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> UpdateSpendingPolicy<SdkTypes> {
+            let action = match self.action {
+                SpendingPolicyAction::SetPolicy(path) => {
+                    SpendingPolicyAction::SetPolicy(path.map(|path| {
+                        std::fs::read(path)
+                            .expect("Expected a file at given path")
+                    }))
+                }
+                SpendingPolicyAction::ScheduleWithdrawal {
+                    token,
+                    target,
+                    amount,
+                } => {
+                    let chain_ctx = ctx.borrow_chain_or_exit();
+                    SpendingPolicyAction::ScheduleWithdrawal {
+                        token: chain_ctx.get(&token),
+                        target: chain_ctx.get(&target),
+                        amount,
+                    }
+                }
+                SpendingPolicyAction::ExecuteWithdrawal(id) => {
+                    SpendingPolicyAction::ExecuteWithdrawal(id)
+                }
+                SpendingPolicyAction::CancelWithdrawal(id) => {
+                    SpendingPolicyAction::CancelWithdrawal(id)
+                }
+            };
+            UpdateSpendingPolicy::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+                action,
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for UpdateSpendingPolicy<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let owner = OWNER.parse(matches);
+            let action = if let Some(id) = EXECUTE_WITHDRAWAL.parse(matches) {
+                SpendingPolicyAction::ExecuteWithdrawal(id)
+            } else if let Some(id) = CANCEL_WITHDRAWAL.parse(matches) {
+                SpendingPolicyAction::CancelWithdrawal(id)
+            } else if let Some(amount) = AMOUNT_OPT.parse(matches) {
+                SpendingPolicyAction::ScheduleWithdrawal {
+                    token: TOKEN_OPT
+                        .parse(matches)
+                        .expect("The token is required by the amount"),
+                    target: WITHDRAWAL_TARGET
+                        .parse(matches)
+                        .expect("The target is required by the amount"),
+                    amount: InputAmount::Unvalidated(amount),
+                }
+            } else {
+                SpendingPolicyAction::SetPolicy(DATA_PATH_OPT.parse(matches))
+            };
+            let tx_code_path = PathBuf::from(TX_SPENDING_POLICY_WASM);
+            Self {
+                tx,
+                owner,
+                action,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(OWNER.def().help("The address of the account."))
+                .arg(DATA_PATH_OPT.def().help(
+                    "The path to the JSON file of the new spending policy. \
+                     The policy takes effect after the delay of the current \
+                     one, if any.",
                 ))
+                .arg(
+                    CLEAR_POLICY
+                        .def()
+                        .help("Clear the spending policy of the account."),
+                )
+                .arg(
+                    AMOUNT_OPT
+                        .def()
+                        .help(
+                            "Schedule a withdrawal of this amount above the \
+                             threshold of the policy.",
+                        )
+                        .requires(TOKEN_OPT.name)
+                        .requires(WITHDRAWAL_TARGET.name),
+                )
+                .arg(
+                    TOKEN_OPT
+                        .def()
+                        .help("The token of the scheduled withdrawal.")
+                        .requires(AMOUNT_OPT.name),
+                )
+                .arg(
+                    WITHDRAWAL_TARGET
+                        .def()
+                        .help("The recipient of the scheduled withdrawal.")
+                        .requires(AMOUNT_OPT.name),
+                )
+                .arg(
+                    EXECUTE_WITHDRAWAL
+                        .def()
+                        .help("Execute the scheduled withdrawal with this id."),
+                )
+                .arg(
+                    CANCEL_WITHDRAWAL
+                        .def()
+                        .help("Cancel the scheduled withdrawal with this id."),
+                )
+                .group(
+                    ArgGroup::new("spending_policy_action")
+                        .args([
+                            DATA_PATH_OPT.name,
+                            CLEAR_POLICY.name,
+                            AMOUNT_OPT.name,
+                            EXECUTE_WITHDRAWAL.name,
+                            CANCEL_WITHDRAWAL.name,
+                        ])
+                        .required(true),
+                )
         }
     }
 
@@ -4586,6 +5377,10 @@ pub mod args {
                 is_offline: self.is_offline,
                 is_pgf_stewards: self.is_pgf_stewards,
                 is_pgf_funding: self.is_pgf_funding,
+                is_emergency: self.is_emergency,
+                is_parameter_change: self.is_parameter_change,
+                is_upgrade: self.is_upgrade,
+                deposit: self.deposit,
                 native_token: ctx.borrow_chain_or_exit().native_token.clone(),
                 tx_code_path: self.tx_code_path,
             }
@@ -4599,6 +5394,19 @@ pub mod args {
             let is_offline = PROPOSAL_OFFLINE.parse(matches);
             let is_pgf_stewards = PROPOSAL_PGF_STEWARD.parse(matches);
             let is_pgf_funding = PROPOSAL_PGF_FUNDING.parse(matches);
+            let is_emergency = PROPOSAL_EMERGENCY.parse(matches);
+            let is_parameter_change = PROPOSAL_PARAMETER_CHANGE.parse(matches);
+            let is_upgrade = PROPOSAL_UPGRADE.parse(matches);
+            let deposit = PROPOSAL_DEPOSIT_OPT.parse(matches).map(|deposit| {
+                deposit
+                    .canonical()
+                    .increase_precision(NATIVE_MAX_DECIMAL_PLACES.into())
+                    .unwrap_or_else(|e| {
+                        println!("Could not parse proposal deposit: {:?}", e);
+                        safe_exit(1);
+                    })
+                    .amount()
+            });
             let tx_code_path = PathBuf::from(TX_INIT_PROPOSAL);
 
             Self {
@@ -4609,6 +5417,10 @@ pub mod args {
                 is_offline,
                 is_pgf_stewards,
                 is_pgf_funding,
+                is_emergency,
+                is_parameter_change,
+                is_upgrade,
+                deposit,
             }
         }
 
@@ -4663,6 +5475,65 @@ pub mod args {
                             PROPOSAL_PGF_STEWARD.name,
                         ]),
                 )
+                .arg(
+                    PROPOSAL_EMERGENCY
+                        .def()
+                        .help(
+                            "Flag if the proposal is of type emergency. Used \
+                             for default proposals with a shorter voting \
+                             period and a higher threshold.",
+                        )
+                        .conflicts_with_all([
+                            PROPOSAL_OFFLINE.name,
+                            PROPOSAL_ETH.name,
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
+                        ]),
+                )
+                .arg(
+                    PROPOSAL_PARAMETER_CHANGE
+                        .def()
+                        .help(
+                            "Flag if the proposal is of type parameter \
+                             change. Used to set new values of the protocol \
+                             parameters without wasm code.",
+                        )
+                        .conflicts_with_all([
+                            PROPOSAL_OFFLINE.name,
+                            PROPOSAL_ETH.name,
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_EMERGENCY.name,
+                        ]),
+                )
+                .arg(
+                    PROPOSAL_UPGRADE
+                        .def()
+                        .help(
+                            "Flag if the proposal is of type upgrade. Used to \
+                             halt the chain at the given height until the \
+                             nodes run a binary including the upgrade.",
+                        )
+                        .conflicts_with_all([
+                            PROPOSAL_OFFLINE.name,
+                            PROPOSAL_ETH.name,
+                            PROPOSAL_PGF_STEWARD.name,
+                            PROPOSAL_PGF_FUNDING.name,
+                            PROPOSAL_EMERGENCY.name,
+                            PROPOSAL_PARAMETER_CHANGE.name,
+                        ]),
+                )
+                .arg(
+                    PROPOSAL_DEPOSIT_OPT
+                        .def()
+                        .help(
+                            "The initial deposit of the author. Other \
+                             accounts can top it up until it reaches the \
+                             minimum proposal fund. Defaults to the minimum \
+                             proposal fund.",
+                        )
+                        .conflicts_with(PROPOSAL_OFFLINE.name),
+                )
         }
     }
 
@@ -4715,11 +5586,11 @@ pub mod args {
                             DATA_PATH_OPT.name,
                         ]),
                 )
-                .arg(
-                    PROPOSAL_VOTE
-                        .def()
-                        .help("The vote for the proposal. Either yay or nay."),
-                )
+                .arg(PROPOSAL_VOTE.def().help(
+                    "The vote for the proposal. Either yay, nay, abstain or \
+                     no-with-veto. A no-with-veto vote counts as nay and asks \
+                     for the deposit of the proposal to be burned.",
+                ))
                 .arg(
                     PROPOSAL_OFFLINE
                         .def()
@@ -4740,6 +5611,100 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<DepositProposal<SdkTypes>> for DepositProposal<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> DepositProposal<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            DepositProposal::<SdkTypes> {
+                tx,
+                proposal_id: self.proposal_id,
+                depositor: chain_ctx.get(&self.depositor),
+                amount: self.amount,
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for DepositProposal<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let proposal_id = PROPOSAL_ID.parse(matches);
+            let depositor = ADDRESS.parse(matches);
+            let amount = AMOUNT
+                .parse(matches)
+                .canonical()
+                .increase_precision(NATIVE_MAX_DECIMAL_PLACES.into())
+                .unwrap_or_else(|e| {
+                    println!("Could not parse deposit amount: {:?}", e);
+                    safe_exit(1);
+                })
+                .amount();
+            let tx_code_path = PathBuf::from(TX_DEPOSIT_PROPOSAL);
+            Self {
+                tx,
+                proposal_id,
+                depositor,
+                amount,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(PROPOSAL_ID.def().help("The proposal identifier."))
+                .arg(ADDRESS.def().help("The address depositing the tokens."))
+                .arg(
+                    AMOUNT
+                        .def()
+                        .help("The amount of native tokens to deposit."),
+                )
+        }
+    }
+
+    impl CliToSdk<DelegateVote<SdkTypes>> for DelegateVote<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> DelegateVote<SdkTypes> {
+            let tx = self.tx.to_sdk(ctx);
+            let chain_ctx = ctx.borrow_chain_or_exit();
+            DelegateVote::<SdkTypes> {
+                tx,
+                delegator: chain_ctx.get(&self.delegator),
+                representative: self
+                    .representative
+                    .map(|representative| chain_ctx.get(&representative)),
+                tx_code_path: self.tx_code_path.to_path_buf(),
+            }
+        }
+    }
+
+    impl Args for DelegateVote<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let delegator = ADDRESS.parse(matches);
+            let representative = REPRESENTATIVE_OPT.parse(matches);
+            let tx_code_path = PathBuf::from(TX_DELEGATE_VOTE);
+            Self {
+                tx,
+                delegator,
+                representative,
+                tx_code_path,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(
+                    ADDRESS
+                        .def()
+                        .help("The address delegating its voting power."),
+                )
+                .arg(REPRESENTATIVE_OPT.def().help(
+                    "The representative voting on behalf of the address. \
+                     Without a representative, the current delegation is \
+                     revoked.",
+                ))
+        }
+    }
+
     impl CliToSdk<RevealPk<SdkTypes>> for RevealPk<CliTypes> {
         fn to_sdk(self, ctx: &mut Context) -> RevealPk<SdkTypes> {
             let tx = self.tx.to_sdk(ctx);
@@ -4885,6 +5850,72 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<QueryPendingUpgrade<SdkTypes>>
+        for QueryPendingUpgrade<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> QueryPendingUpgrade<SdkTypes> {
+            QueryPendingUpgrade::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+            }
+        }
+    }
+
+    impl Args for QueryPendingUpgrade<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+
+            Self { query }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+        }
+    }
+
+    impl CliToSdk<QueryIbcRateLimits<SdkTypes>>
+        for QueryIbcRateLimits<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> QueryIbcRateLimits<SdkTypes> {
+            QueryIbcRateLimits::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+            }
+        }
+    }
+
+    impl Args for QueryIbcRateLimits<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+
+            Self { query }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+        }
+    }
+
+    impl CliToSdk<QueryErc20Whitelist<SdkTypes>>
+        for QueryErc20Whitelist<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> QueryErc20Whitelist<SdkTypes> {
+            QueryErc20Whitelist::<SdkTypes> {
+                query: self.query.to_sdk(ctx),
+            }
+        }
+    }
+
+    impl Args for QueryErc20Whitelist<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let query = Query::parse(matches);
+
+            Self { query }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Query<CliTypes>>()
+        }
+    }
+
     impl Args for QueryPgf<CliTypes> {
         fn parse(matches: &ArgMatches) -> Self {
             let query = Query::parse(matches);
@@ -5520,6 +6551,95 @@ pub mod args {
         }
     }
 
+    impl CliToSdk<CreateSigningRequest<SdkTypes>>
+        for CreateSigningRequest<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> CreateSigningRequest<SdkTypes> {
+            CreateSigningRequest::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                tx_data: std::fs::read(self.tx_data).expect(""),
+                owner: ctx.borrow_chain_or_exit().get(&self.owner),
+            }
+        }
+    }
+
+    impl Args for CreateSigningRequest<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let tx_path = TX_PATH.parse(matches);
+            let owner = OWNER.parse(matches);
+            Self {
+                tx,
+                tx_data: tx_path,
+                owner,
+            }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>()
+                .arg(
+                    TX_PATH.def().help(
+                        "The path to the tx file with the serialized tx.",
+                    ),
+                )
+                .arg(
+                    OWNER
+                        .def()
+                        .help("The address of the multisignature account."),
+                )
+        }
+    }
+
+    impl CliToSdk<SignSigningRequest<SdkTypes>> for SignSigningRequest<CliTypes> {
+        fn to_sdk(self, ctx: &mut Context) -> SignSigningRequest<SdkTypes> {
+            SignSigningRequest::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                request_path: self.request_path,
+            }
+        }
+    }
+
+    impl Args for SignSigningRequest<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let request_path = REQUEST_PATH.parse(matches);
+            Self { tx, request_path }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>().arg(REQUEST_PATH.def().help(
+                "The path to the signing request file. The signatures are \
+                 appended to it in place.",
+            ))
+        }
+    }
+
+    impl CliToSdk<SubmitSigningRequest<SdkTypes>>
+        for SubmitSigningRequest<CliTypes>
+    {
+        fn to_sdk(self, ctx: &mut Context) -> SubmitSigningRequest<SdkTypes> {
+            SubmitSigningRequest::<SdkTypes> {
+                tx: self.tx.to_sdk(ctx),
+                request_paths: self.request_paths,
+            }
+        }
+    }
+
+    impl Args for SubmitSigningRequest<CliTypes> {
+        fn parse(matches: &ArgMatches) -> Self {
+            let tx = Tx::parse(matches);
+            let request_paths = REQUEST_PATHS.parse(matches);
+            Self { tx, request_paths }
+        }
+
+        fn def(app: App) -> App {
+            app.add_args::<Tx<CliTypes>>().arg(REQUEST_PATHS.def().help(
+                "The paths to the copies of the signing request. Their \
+                 signatures are merged before submission.",
+            ))
+        }
+    }
+
     impl CliToSdk<GenIbcShieldedTransafer<SdkTypes>>
         for GenIbcShieldedTransafer<CliTypes>
     {
@@ -6097,6 +7217,7 @@ pub mod args {
             let unsafe_dont_encrypt = UNSAFE_DONT_ENCRYPT.parse(matches);
             let use_device = USE_DEVICE.parse(matches);
             let derivation_path = HD_WALLET_DERIVATION_PATH.parse(matches);
+            let birthday = BIRTHDAY.parse(matches);
             Self {
                 scheme,
                 shielded,
@@ -6105,6 +7226,7 @@ pub mod args {
                 unsafe_dont_encrypt,
                 use_device,
                 derivation_path,
+                birthday,
             }
         }
 
@@ -6137,10 +7259,16 @@ pub mod args {
             .arg(HD_WALLET_DERIVATION_PATH.def().help(
                 "HD key derivation path. Use keyword `default` to refer to a \
                  scheme default path:\n- m/44'/60'/0'/0/0 for secp256k1 \
-                 scheme\n- m/44'/877'/0'/0'/0' for ed25519 scheme.\nFor \
-                 ed25519, all path indices will be promoted to hardened \
-                 indexes. If none is specified, the scheme default path is \
-                 used.",
+                 scheme\n- m/44'/877'/0'/0'/0' for ed25519 scheme\n- \
+                 m/32'/877'/0' for the shielded pool.\nFor ed25519, all path \
+                 indices will be promoted to hardened indexes. If none is \
+                 specified, the scheme default path is used.",
+            ))
+            .arg(BIRTHDAY.def().requires(SHIELDED.name).help(
+                "The birthday of the shielded key, i.e. the block height (or \
+                 the MASP transaction index prefixed with `masp-tx:`) from \
+                 which it may have received notes. The shielded history \
+                 preceding it is not scanned for this key.",
             ))
         }
     }
@@ -6154,6 +7282,7 @@ pub mod args {
             let alias_force = ALIAS_FORCE.parse(matches);
             let unsafe_dont_encrypt = UNSAFE_DONT_ENCRYPT.parse(matches);
             let derivation_path = HD_WALLET_DERIVATION_PATH.parse(matches);
+            let birthday = BIRTHDAY.parse(matches);
             Self {
                 scheme,
                 shielded,
@@ -6162,6 +7291,7 @@ pub mod args {
                 alias_force,
                 unsafe_dont_encrypt,
                 derivation_path,
+                birthday,
             }
         }
 
@@ -6202,6 +7332,12 @@ pub mod args {
                  indexes. If none is specified, the scheme default path is \
                  used.",
             ))
+            .arg(BIRTHDAY.def().requires(SHIELDED.name).help(
+                "The birthday of the shielded key, i.e. the block height (or \
+                 the MASP transaction index prefixed with `masp-tx:`) from \
+                 which it may have received notes. The shielded history \
+                 preceding it is not scanned for this key.",
+            ))
         }
     }
 
@@ -6335,11 +7471,13 @@ pub mod args {
             let alias_force = ALIAS_FORCE.parse(matches);
             let value = VALUE.parse(matches);
             let unsafe_dont_encrypt = UNSAFE_DONT_ENCRYPT.parse(matches);
+            let birthday = BIRTHDAY.parse(matches);
             Self {
                 alias,
                 alias_force,
                 value,
                 unsafe_dont_encrypt,
+                birthday,
             }
         }
 
@@ -6362,6 +7500,12 @@ pub mod args {
                 "UNSAFE: Do not encrypt the added keys. Do not use this for \
                  keys used in a live network.",
             ))
+            .arg(BIRTHDAY.def().help(
+                "The birthday of an added shielded key, i.e. the block height \
+                 (or the MASP transaction index prefixed with `masp-tx:`) \
+                 from which it may have received notes. The shielded history \
+                 preceding it is not scanned for this key.",
+            ))
         }
     }
 
@@ -6949,6 +8093,7 @@ pub enum NamadaRelayer {
     EthBridgePoolWithCtx(Box<(cmds::EthBridgePoolWithCtx, Context)>),
     EthBridgePoolWithoutCtx(cmds::EthBridgePoolWithoutCtx),
     ValidatorSet(cmds::ValidatorSet),
+    Relay(Box<(cmds::RelayDaemon, Context)>),
 }
 
 pub fn namada_relayer_cli() -> Result<NamadaRelayer> {
@@ -6971,6 +8116,11 @@ pub fn namada_relayer_cli() -> Result<NamadaRelayer> {
             cmds::NamadaRelayer::ValidatorSet(sub_cmd) => {
                 Ok(NamadaRelayer::ValidatorSet(sub_cmd))
             }
+            cmds::NamadaRelayer::Relay(sub_cmd) => {
+                let global_args = args::Global::parse(&matches);
+                let context = Context::new::<StdIo>(global_args)?;
+                Ok(NamadaRelayer::Relay(Box::new((sub_cmd, context))))
+            }
         },
         None => {
             let mut app = app;
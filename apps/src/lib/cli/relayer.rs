@@ -1,12 +1,13 @@
 use color_eyre::eyre::Result;
 use namada::types::io::Io;
-use namada_sdk::eth_bridge::{bridge_pool, validator_set};
+use namada_sdk::eth_bridge::{bridge_pool, relayer, validator_set};
 
 use crate::cli;
 use crate::cli::api::{CliApi, CliClient};
 use crate::cli::args::{CliToSdk, CliToSdkCtxless};
 use crate::cli::cmds::*;
 use crate::cli::utils::get_eth_rpc_client;
+use crate::client::tx;
 
 impl CliApi {
     pub async fn handle_relayer_command<C>(
@@ -34,6 +35,20 @@ impl CliApi {
                         let namada = ctx.to_sdk(client, io);
                         bridge_pool::recommend_batch(&namada, args).await?;
                     }
+                    EthBridgePoolWithCtx::BumpFee(BumpBridgePoolFee(
+                        mut args,
+                    )) => {
+                        let client = client.unwrap_or_else(|| {
+                            C::from_tendermint_address(
+                                &mut args.tx.ledger_address,
+                            )
+                        });
+                        client.wait_until_node_is_synced(&io).await?;
+                        let args = args.to_sdk(&mut ctx);
+                        let namada = ctx.to_sdk(client, io);
+                        tx::submit_bump_bridge_pool_fee_tx(&namada, args)
+                            .await?;
+                    }
                 }
             }
             cli::NamadaRelayer::EthBridgePoolWithoutCtx(sub) => match sub {
@@ -156,6 +171,18 @@ impl CliApi {
                     .await?;
                 }
             },
+            cli::NamadaRelayer::Relay(boxed) => {
+                let (RelayDaemon(mut args), mut ctx) = *boxed;
+                let client = client.unwrap_or_else(|| {
+                    C::from_tendermint_address(&mut args.query.ledger_address)
+                });
+                client.wait_until_node_is_synced(&io).await?;
+                let eth_client =
+                    get_eth_rpc_client(&args.eth_rpc_endpoint).await;
+                let args = args.to_sdk(&mut ctx);
+                let namada = ctx.to_sdk(client, io);
+                relayer::run_relayer_daemon(&namada, eth_client, args).await?;
+            }
         }
         Ok(())
     }
@@ -15,7 +15,9 @@ use masp_primitives::zip32::ExtendedFullViewingKey;
 use namada::types::address::{Address, DecodeError};
 use namada::types::io::Io;
 use namada::types::key::*;
-use namada::types::masp::{ExtendedSpendingKey, MaspValue, PaymentAddress};
+use namada::types::masp::{
+    ExtendedSpendingKey, KeyBirthday, MaspValue, PaymentAddress,
+};
 use namada_sdk::masp::find_valid_diversifier;
 use namada_sdk::wallet::{
     DecryptionError, DerivationPath, DerivationPathError, FindKeyError, Wallet,
@@ -108,6 +110,10 @@ fn shielded_keys_list(
             display_line!(io, &mut w_lock; "  Alias \"{}\" ({}):", alias, encrypted_status).unwrap();
             // Always print the corresponding viewing key
             display_line!(io, &mut w_lock; "    Viewing Key: {}", key).unwrap();
+            if let Some(birthday) = wallet.store().find_birthday(&alias) {
+                display_line!(io, &mut w_lock; "    Birthday: {}", birthday)
+                    .unwrap();
+            }
             // A subset of viewing keys will have corresponding spending keys.
             // Print those too if they are available and requested.
             if unsafe_show_secret {
@@ -177,14 +183,20 @@ fn spending_key_gen(
         alias,
         alias_force,
         unsafe_dont_encrypt,
+        birthday,
         ..
     }: args::KeyGen,
 ) {
     let mut wallet = load_wallet(ctx);
     let alias = alias.to_lowercase();
     let password = read_and_confirm_encryption_password(unsafe_dont_encrypt);
-    let (alias, _key) =
-        wallet.gen_store_spending_key(alias, password, alias_force, &mut OsRng);
+    let (alias, _key) = wallet.gen_store_spending_key(
+        alias,
+        password,
+        birthday,
+        alias_force,
+        &mut OsRng,
+    );
     wallet
         .save()
         .unwrap_or_else(|err| edisplay_line!(io, "{}", err));
@@ -240,13 +252,14 @@ fn shielded_key_address_add(
     alias_force: bool,
     masp_value: MaspValue,
     unsafe_dont_encrypt: bool,
+    birthday: Option<KeyBirthday>,
 ) {
     let alias = alias.to_lowercase();
     let mut wallet = load_wallet(ctx);
     let (alias, typ) = match masp_value {
         MaspValue::FullViewingKey(viewing_key) => {
             let alias = wallet
-                .insert_viewing_key(alias, viewing_key, alias_force)
+                .insert_viewing_key(alias, viewing_key, birthday, alias_force)
                 .unwrap_or_else(|| {
                     edisplay_line!(io, "Viewing key not added");
                     cli::safe_exit(1);
@@ -257,7 +270,13 @@ fn shielded_key_address_add(
             let password =
                 read_and_confirm_encryption_password(unsafe_dont_encrypt);
             let alias = wallet
-                .insert_spending_key(alias, spending_key, password, alias_force)
+                .insert_spending_key(
+                    alias,
+                    spending_key,
+                    password,
+                    birthday,
+                    alias_force,
+                )
                 .unwrap_or_else(|| {
                     edisplay_line!(io, "Spending key not added");
                     cli::safe_exit(1);
@@ -283,6 +302,55 @@ fn shielded_key_address_add(
     );
 }
 
+/// Restore a spending key from a mnemonic code and store it in the wallet.
+fn shielded_key_derive(
+    ctx: Context,
+    io: &impl Io,
+    args::KeyDerive {
+        alias,
+        alias_force,
+        unsafe_dont_encrypt,
+        derivation_path,
+        birthday,
+        ..
+    }: args::KeyDerive,
+) {
+    let mut wallet = load_wallet(ctx);
+    let derivation_path = if derivation_path.eq_ignore_ascii_case("DEFAULT") {
+        DerivationPath::default_for_shielded()
+    } else {
+        DerivationPath::from_str(&derivation_path).unwrap_or_else(|err| {
+            edisplay_line!(io, "{}", err);
+            cli::safe_exit(1)
+        })
+    };
+    let alias = alias.to_lowercase();
+    let encryption_password =
+        read_and_confirm_encryption_password(unsafe_dont_encrypt);
+    let (alias, _key) = wallet
+        .derive_spending_key_from_mnemonic_code(
+            alias,
+            alias_force,
+            derivation_path,
+            None,
+            encryption_password,
+            birthday,
+        )
+        .unwrap_or_else(|err| {
+            edisplay_line!(io, "{}", err);
+            display_line!(io, "No changes are persisted. Exiting.");
+            cli::safe_exit(1)
+        });
+    wallet
+        .save()
+        .unwrap_or_else(|err| edisplay_line!(io, "{}", err));
+    display_line!(
+        io,
+        "Successfully added a spending key with alias: \"{}\"",
+        alias
+    );
+}
+
 /// Decode the derivation path from the given string unless it is "default",
 /// in which case use the default derivation path for the given scheme.
 pub fn decode_derivation_path(
@@ -487,7 +555,7 @@ async fn key_derive(
     if !args_key_derive.shielded {
         transparent_key_and_address_derive(ctx, io, args_key_derive).await
     } else {
-        todo!()
+        shielded_key_derive(ctx, io, args_key_derive)
     }
 }
 
@@ -653,6 +721,7 @@ fn add_key_or_address(
     alias_force: bool,
     value: KeyAddrAddValue,
     unsafe_dont_encrypt: bool,
+    birthday: Option<KeyBirthday>,
 ) {
     match value {
         KeyAddrAddValue::TranspValue(TransparentValue::TranspSecretKey(sk)) => {
@@ -678,6 +747,7 @@ fn add_key_or_address(
             alias_force,
             masp_value,
             unsafe_dont_encrypt,
+            birthday,
         ),
     }
 }
@@ -691,7 +761,7 @@ fn key_address_add(
         alias_force,
         value,
         unsafe_dont_encrypt,
-        ..
+        birthday,
     }: args::KeyAddressAdd,
 ) {
     let value = KeyAddrAddValue::from_str(&value).unwrap_or_else(|err| {
@@ -699,7 +769,15 @@ fn key_address_add(
         display_line!(io, "No changes are persisted. Exiting.");
         cli::safe_exit(1)
     });
-    add_key_or_address(ctx, io, alias, alias_force, value, unsafe_dont_encrypt)
+    add_key_or_address(
+        ctx,
+        io,
+        alias,
+        alias_force,
+        value,
+        unsafe_dont_encrypt,
+        birthday,
+    )
 }
 
 /// Remove keys and addresses
@@ -1114,6 +1192,7 @@ fn key_import(
             alias_force,
             masp_value,
             unsafe_dont_encrypt,
+            None,
         );
     } else {
         display_line!(io, "Could not parse the data.");
@@ -25,7 +25,7 @@ use namada::core::ledger::governance::storage::proposal::{
     StoragePgfFunding, StorageProposal,
 };
 use namada::core::ledger::governance::utils::{
-    compute_proposal_result, ProposalVotes, TallyType, TallyVote, VotePower,
+    compute_proposal_result, ProposalVotes, TallyVote, VotePower,
 };
 use namada::core::ledger::pgf::parameters::PgfParameters;
 use namada::core::ledger::pgf::storage::steward::StewardDetail;
@@ -45,7 +45,7 @@ use namada::types::io::Io;
 use namada::types::key::*;
 use namada::types::masp::{BalanceOwner, ExtendedViewingKey, PaymentAddress};
 use namada::types::storage::{BlockHeight, BlockResults, Epoch, Key, KeySeg};
-use namada::types::token::{Change, MaspDenom};
+use namada::types::token::{Change, DenominatedAmount, MaspDenom};
 use namada::types::{storage, token};
 use namada_sdk::error::{is_pinned_error, Error, PinnedBalanceError};
 use namada_sdk::masp::{Conversions, MaspAmount, MaspChange};
@@ -131,9 +131,11 @@ pub async fn query_transfers(
     let transfers = shielded
         .query_tx_deltas(
             context.client(),
+            context.io(),
             &query_owner,
             &query_token,
             &wallet.get_viewing_keys(),
+            &wallet.get_viewing_key_birthdays(),
         )
         .await
         .unwrap();
@@ -729,6 +731,15 @@ async fn get_ibc_denom_alias(
 /// Query Proposals
 pub async fn query_proposal(context: &impl Namada, args: args::QueryProposal) {
     let current_epoch = query_and_print_epoch(context).await;
+    let governance_parameters =
+        query_governance_parameters(context.client()).await;
+    let stewards = query_pgf_stewards(context.client()).await;
+    let tally_type = |proposal: &StorageProposal| {
+        let is_author_steward = stewards
+            .iter()
+            .any(|steward| steward.address.eq(&proposal.author));
+        proposal.get_tally_type(is_author_steward, &governance_parameters)
+    };
 
     if let Some(id) = args.proposal_id {
         let proposal =
@@ -739,6 +750,12 @@ pub async fn query_proposal(context: &impl Namada, args: args::QueryProposal) {
                 "{}",
                 proposal.to_string_with_status(current_epoch)
             );
+            display_line!(
+                context.io(),
+                "{:4}Tally rule: {}",
+                "",
+                tally_type(&proposal)
+            );
         } else {
             edisplay_line!(context.io(), "No proposal found with id: {}", id);
         }
@@ -763,6 +780,12 @@ pub async fn query_proposal(context: &impl Namada, args: args::QueryProposal) {
                 .unwrap()
                 .expect("Proposal should be written to storage.");
             display_line!(context.io(), "{}", proposal);
+            display_line!(
+                context.io(),
+                "{:4}Tally rule: {}",
+                "",
+                tally_type(&proposal)
+            );
         }
     }
 }
@@ -797,6 +820,7 @@ pub async fn query_shielded_balance(
             .copied()
             .collect(),
     };
+    let birthdays = context.wallet().await.get_viewing_key_birthdays();
     {
         let mut shielded = context.shielded_mut().await;
         let _ = shielded.load().await;
@@ -804,7 +828,10 @@ pub async fn query_shielded_balance(
             .iter()
             .map(|fvk| ExtendedFullViewingKey::from(*fvk).fvk.vk)
             .collect();
-        shielded.fetch(context.client(), &[], &fvks).await.unwrap();
+        shielded
+            .fetch(context.client(), context.io(), &[], &fvks, &birthdays)
+            .await
+            .unwrap();
         // Save the update state so that future fetches can be short-circuited
         let _ = shielded.save().await;
     }
@@ -1135,7 +1162,12 @@ pub async fn query_proposal_result(
                         .await
                         .iter()
                         .any(|steward| steward.address.eq(&proposal.author));
-                    let tally_type = proposal.get_tally_type(is_author_steward);
+                    let governance_parameters =
+                        query_governance_parameters(context.client()).await;
+                    let tally_type = proposal.get_tally_type(
+                        is_author_steward,
+                        &governance_parameters,
+                    );
                     let total_voting_power = get_total_staked_tokens(
                         context.client(),
                         proposal.voting_end_epoch,
@@ -1153,6 +1185,7 @@ pub async fn query_proposal_result(
                         votes,
                         total_voting_power,
                         tally_type,
+                        governance_parameters.veto_threshold,
                     )
                 }
             };
@@ -1229,10 +1262,13 @@ pub async fn query_proposal_result(
         )
         .await;
 
+        let governance_parameters =
+            query_governance_parameters(context.client()).await;
         let proposal_result = compute_proposal_result(
             proposal_votes,
             total_voting_power,
-            TallyType::TwoThirds,
+            governance_parameters.default_proposal_tally,
+            governance_parameters.veto_threshold,
         );
 
         display_line!(
@@ -1253,8 +1289,26 @@ pub async fn query_account(context: &impl Namada, args: args::QueryAccount) {
         display_line!(context.io(), "Address: {}", account.address);
         display_line!(context.io(), "Threshold: {}", account.threshold);
         display_line!(context.io(), "Public keys:");
-        for (public_key, _) in account.public_keys_map.pk_to_idx {
-            display_line!(context.io(), "- {}", public_key);
+        for (public_key, index) in account.public_keys_map.pk_to_idx {
+            let options =
+                account.key_options.get(&index).cloned().unwrap_or_default();
+            let roles = if options.roles.is_empty() {
+                "any".to_string()
+            } else {
+                options
+                    .roles
+                    .iter()
+                    .map(|role| role.to_string())
+                    .collect::<Vec<_>>()
+                    .join("+")
+            };
+            display_line!(
+                context.io(),
+                "- {} (weight: {}, roles: {})",
+                public_key,
+                options.weight,
+                roles
+            );
         }
     } else {
         display_line!(context.io(), "No account exists for {}", args.owner);
@@ -1304,14 +1358,102 @@ pub async fn query_pgf(context: &impl Namada, _args: args::QueryPgf) {
                     context.io(),
                     "{:4}- {} for {}",
                     "",
-                    funding.detail.target,
-                    funding.detail.amount.to_string_native()
+                    funding.detail,
+                    funding.detail.amount().to_string_native()
                 );
             }
         }
     }
 }
 
+/// Query the upgrade scheduled by an accepted upgrade proposal
+pub async fn query_pending_upgrade(
+    context: &impl Namada,
+    _args: args::QueryPendingUpgrade,
+) {
+    let upgrade = rpc::query_pending_upgrade(context.client()).await.unwrap();
+    match upgrade {
+        Some(upgrade) => {
+            display_line!(context.io(), "Pending upgrade: {}", upgrade.name);
+            display_line!(
+                context.io(),
+                "{:4}Height: {} (nodes without a binary including the upgrade \
+                 halt at this height)",
+                "",
+                upgrade.height
+            );
+        }
+        None => {
+            display_line!(context.io(), "No upgrade is currently scheduled.")
+        }
+    }
+}
+
+/// Query the rate limits of the IBC token transfers with their current usage
+pub async fn query_ibc_rate_limits(
+    context: &impl Namada,
+    _args: args::QueryIbcRateLimits,
+) {
+    let rate_limits =
+        rpc::query_ibc_rate_limits(context.client()).await.unwrap();
+    if rate_limits.is_empty() {
+        display_line!(context.io(), "No IBC rate limit is currently set.");
+        return;
+    }
+    display_line!(context.io(), "IBC rate limits (in the smallest units):");
+    for rate_limit in rate_limits {
+        let usage = rpc::query_ibc_rate_limit_usage(
+            context.client(),
+            &rate_limit.channel_id,
+            &rate_limit.token,
+        )
+        .await
+        .unwrap();
+        if let Some((limit, total)) = usage {
+            display_line!(
+                context.io(),
+                "{:4}- {} on {} over {} epochs: inflow {}/{}, outflow {}/{}",
+                "",
+                rate_limit.token,
+                rate_limit.channel_id,
+                limit.window,
+                total.inflow,
+                limit.max_inflow,
+                total.outflow,
+                limit.max_outflow
+            );
+        }
+    }
+}
+
+/// Query the whitelisted ERC20 tokens of the Ethereum bridge with the used
+/// amount of their caps
+pub async fn query_erc20_whitelist(
+    context: &impl Namada,
+    _args: args::QueryErc20Whitelist,
+) {
+    let whitelist = rpc::query_erc20_whitelist(context.client()).await.unwrap();
+    if whitelist.is_empty() {
+        display_line!(context.io(), "No ERC20 token is currently whitelisted.");
+        return;
+    }
+    display_line!(context.io(), "Whitelisted ERC20 tokens:");
+    for (erc20, supply) in whitelist {
+        let Some(cap) = erc20.token_cap else {
+            continue;
+        };
+        display_line!(
+            context.io(),
+            "{:4}- {}: {}/{} minted ({} decimal places)",
+            "",
+            erc20.token_address,
+            DenominatedAmount::new(supply, cap.denom()),
+            cap,
+            cap.denom().0
+        );
+    }
+}
+
 pub async fn query_protocol_parameters(
     context: &impl Namada,
     _args: args::QueryProtocolParameters,
@@ -1355,6 +1497,48 @@ pub async fn query_protocol_parameters(
         "",
         governance_parameters.min_proposal_grace_epochs
     );
+    display_line!(
+        context.io(),
+        "{:4}Min. emergency proposal voting period: {}",
+        "",
+        governance_parameters.min_emergency_proposal_voting_period
+    );
+    display_line!(
+        context.io(),
+        "{:4}Default proposal tally: {}",
+        "",
+        governance_parameters.default_proposal_tally
+    );
+    display_line!(
+        context.io(),
+        "{:4}Emergency proposal tally: {}",
+        "",
+        governance_parameters.emergency_proposal_tally
+    );
+    display_line!(
+        context.io(),
+        "{:4}Pgf steward proposal tally: {}",
+        "",
+        governance_parameters.pgf_steward_proposal_tally
+    );
+    display_line!(
+        context.io(),
+        "{:4}Pgf funding proposal tally: {}",
+        "",
+        governance_parameters.pgf_funding_proposal_tally
+    );
+    display_line!(
+        context.io(),
+        "{:4}Pgf funding proposal tally (steward author): {}",
+        "",
+        governance_parameters.pgf_steward_funding_proposal_tally
+    );
+    display_line!(
+        context.io(),
+        "{:4}Veto threshold: {}",
+        "",
+        governance_parameters.veto_threshold
+    );
 
     let pgf_parameters = query_pgf_parameters(context.client()).await;
     display_line!(context.io(), "Public Goods Funding Parameters\n");
@@ -2752,10 +2936,39 @@ pub async fn compute_proposal_votes<
         }
     }
 
-    ProposalVotes {
+    let mut proposal_votes = ProposalVotes {
         validators_vote,
         validator_voting_power,
         delegators_vote,
         delegator_voting_power,
+    };
+
+    let represented_votes =
+        namada_sdk::rpc::query_proposal_represented_votes(client, proposal_id)
+            .await
+            .unwrap();
+    for represented_vote in represented_votes {
+        let delegator = represented_vote.delegator;
+        if is_validator(client, &delegator).await {
+            continue;
+        }
+        let mut voting_power = HashMap::default();
+        for validator in get_delegators_delegation_at(client, &delegator, epoch)
+            .await
+            .into_keys()
+        {
+            let delegator_stake =
+                get_bond_amount_at(client, &delegator, &validator, epoch)
+                    .await
+                    .unwrap_or_default();
+            voting_power.insert(validator, delegator_stake);
+        }
+        proposal_votes.add_represented_vote(
+            delegator,
+            represented_vote.data.into(),
+            voting_power,
+        );
     }
+
+    proposal_votes
 }
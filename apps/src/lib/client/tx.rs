@@ -11,7 +11,8 @@ use namada::core::ledger::governance::cli::offline::{
     OfflineProposal, OfflineSignedProposal, OfflineVote,
 };
 use namada::core::ledger::governance::cli::onchain::{
-    DefaultProposal, PgfFundingProposal, PgfStewardProposal, ProposalVote,
+    DefaultProposal, ParameterChangeProposal, PgfFundingProposal,
+    PgfStewardProposal, ProposalVote, UpgradeProposal,
 };
 use namada::core::ledger::storage::EPOCH_SWITCH_BLOCKS_DELAY;
 use namada::ibc::apps::transfer::types::Memo;
@@ -20,6 +21,7 @@ use namada::types::address::{Address, ImplicitAddress};
 use namada::types::dec::Dec;
 use namada::types::io::Io;
 use namada::types::key::{self, *};
+use namada::types::storage::BlockHeight;
 use namada::types::transaction::pos::{BecomeValidator, ConsensusKeyChange};
 use namada_sdk::rpc::{InnerTxResult, TxBroadcastData, TxResponse};
 use namada_sdk::wallet::alias::validator_consensus_key;
@@ -32,7 +34,7 @@ use super::rpc;
 use crate::cli::{args, safe_exit};
 use crate::client::rpc::query_wasm_code_hash;
 use crate::client::tx::signing::{
-    default_sign, init_validator_signing_data, SigningTxData,
+    default_sign, init_validator_signing_data, SigningRequest, SigningTxData,
 };
 use crate::client::tx::tx::ProcessTxResponse;
 use crate::config::TendermintMode;
@@ -251,6 +253,40 @@ pub async fn submit_bridge_pool_tx<N: Namada>(
     Ok(())
 }
 
+pub async fn submit_cancel_bridge_pool_tx<N: Namada>(
+    namada: &N,
+    args: args::CancelBridgePoolTransfer,
+) -> Result<(), error::Error> {
+    let (mut tx, signing_data) = args.clone().build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_bump_bridge_pool_fee_tx<N: Namada>(
+    namada: &N,
+    args: args::BumpBridgePoolTransferFee,
+) -> Result<(), error::Error> {
+    let (mut tx, signing_data) = args.clone().build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
 pub async fn submit_custom<N: Namada>(
     namada: &N,
     args: args::TxCustom,
@@ -293,6 +329,26 @@ where
     Ok(())
 }
 
+pub async fn submit_spending_policy<N: Namada>(
+    namada: &N,
+    args: args::UpdateSpendingPolicy,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
 pub async fn submit_init_account<N: Namada>(
     namada: &N,
     args: args::TxInitAccount,
@@ -992,6 +1048,9 @@ where
     let current_epoch = rpc::query_and_print_epoch(namada).await;
     let governance_parameters =
         rpc::query_governance_parameters(namada.client()).await;
+    let deposit = args
+        .deposit
+        .unwrap_or(governance_parameters.min_proposal_fund);
     let (mut tx_builder, signing_data) = if args.is_offline {
         let proposal = OfflineProposal::try_from(args.proposal_data.as_ref())
             .map_err(|e| {
@@ -1068,6 +1127,7 @@ where
                 &governance_parameters,
                 current_epoch,
                 author_balance,
+                deposit,
                 args.tx.force,
             )
             .map_err(|e| error::TxError::InvalidProposal(e.to_string()))?;
@@ -1076,6 +1136,64 @@ where
             .await?;
 
         tx::build_pgf_stewards_proposal(namada, &args, proposal).await?
+    } else if args.is_parameter_change {
+        let proposal =
+            ParameterChangeProposal::try_from(args.proposal_data.as_ref())
+                .map_err(|e| {
+                    error::TxError::FailedGovernaneProposalDeserialize(
+                        e.to_string(),
+                    )
+                })?;
+        let author_balance = rpc::get_token_balance(
+            namada.client(),
+            &namada.native_token(),
+            &proposal.proposal.author,
+        )
+        .await;
+        let proposal = proposal
+            .validate(
+                &governance_parameters,
+                current_epoch,
+                author_balance,
+                deposit,
+                args.tx.force,
+            )
+            .map_err(|e| error::TxError::InvalidProposal(e.to_string()))?;
+
+        submit_reveal_aux(namada, args.tx.clone(), &proposal.proposal.author)
+            .await?;
+
+        tx::build_parameter_change_proposal(namada, &args, proposal).await?
+    } else if args.is_upgrade {
+        let proposal = UpgradeProposal::try_from(args.proposal_data.as_ref())
+            .map_err(|e| {
+            error::TxError::FailedGovernaneProposalDeserialize(e.to_string())
+        })?;
+        let current_height = namada_sdk::rpc::query_block(namada.client())
+            .await?
+            .map(|block| block.height)
+            .unwrap_or(BlockHeight(0));
+        let author_balance = rpc::get_token_balance(
+            namada.client(),
+            &namada.native_token(),
+            &proposal.proposal.author,
+        )
+        .await;
+        let proposal = proposal
+            .validate(
+                &governance_parameters,
+                current_epoch,
+                current_height,
+                author_balance,
+                deposit,
+                args.tx.force,
+            )
+            .map_err(|e| error::TxError::InvalidProposal(e.to_string()))?;
+
+        submit_reveal_aux(namada, args.tx.clone(), &proposal.proposal.author)
+            .await?;
+
+        tx::build_upgrade_proposal(namada, &args, proposal).await?
     } else {
         let proposal = DefaultProposal::try_from(args.proposal_data.as_ref())
             .map_err(|e| {
@@ -1092,6 +1210,8 @@ where
                 &governance_parameters,
                 current_epoch,
                 author_balane,
+                deposit,
+                args.is_emergency,
                 args.tx.force,
             )
             .map_err(|e| error::TxError::InvalidProposal(e.to_string()))?;
@@ -1276,6 +1396,190 @@ where
     Ok(())
 }
 
+pub async fn create_signing_request<N: Namada>(
+    namada: &N,
+    args::CreateSigningRequest {
+        tx: tx_args,
+        tx_data,
+        owner,
+    }: args::CreateSigningRequest,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let tx = if let Ok(transaction) = Tx::deserialize(tx_data.as_ref()) {
+        transaction
+    } else {
+        edisplay_line!(namada.io(), "Couldn't decode the transaction.");
+        safe_exit(1)
+    };
+    let account = namada_sdk::rpc::get_account_info(namada.client(), &owner)
+        .await?
+        .ok_or_else(|| {
+            error::Error::from(error::TxError::InvalidAccount(owner.encode()))
+        })?;
+    let request = SigningRequest::new(&tx, &account);
+
+    let filename = format!("signing_request_{}.json", tx.header_hash());
+    let output_path = match &tx_args.output_folder {
+        Some(path) => path.join(filename),
+        None => filename.into(),
+    };
+    write_signing_request(&output_path, &request)?;
+    display_line!(
+        namada.io(),
+        "Signing request for {} out of {} keys with a threshold of {} \
+         serialized at {}",
+        owner,
+        request.signers.len(),
+        request.threshold,
+        output_path.display()
+    );
+    Ok(())
+}
+
+pub async fn sign_signing_request<N: Namada>(
+    namada: &N,
+    args::SignSigningRequest {
+        tx: tx_args,
+        request_path,
+    }: args::SignSigningRequest,
+) -> Result<(), error::Error> {
+    let mut request = read_signing_request(&request_path)?;
+
+    let secret_keys = {
+        let mut wallet = namada.wallet_mut().await;
+        request
+            .signers
+            .iter()
+            .filter(|signer| !request.is_signed_by(&signer.public_key))
+            .filter_map(|signer| {
+                signing::find_key_by_pk(
+                    &mut wallet,
+                    &tx_args,
+                    &signer.public_key,
+                )
+                .ok()
+            })
+            .collect::<Vec<common::SecretKey>>()
+    };
+    let signed = request.sign(&secret_keys)?;
+    if signed.is_empty() {
+        edisplay_line!(
+            namada.io(),
+            "No key of the signing request that hasn't signed yet was found \
+             in the wallet."
+        );
+        return Ok(());
+    }
+
+    write_signing_request(&request_path, &request)?;
+    for public_key in signed {
+        display_line!(
+            namada.io(),
+            "Signature for {} appended to {}",
+            public_key,
+            request_path.display()
+        );
+    }
+    Ok(())
+}
+
+pub async fn submit_signing_request<N: Namada>(
+    namada: &N,
+    args::SubmitSigningRequest {
+        tx: tx_args,
+        request_paths,
+    }: args::SubmitSigningRequest,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let mut paths = request_paths.iter();
+    let mut request = match paths.next() {
+        Some(path) => read_signing_request(path)?,
+        None => {
+            return Err(error::Error::Other(
+                "No signing request was given".to_string(),
+            ));
+        }
+    };
+    for path in paths {
+        request.merge(read_signing_request(path)?)?;
+    }
+
+    // Check the signatures against the current state of the account, which
+    // may have changed since the request was created
+    let account =
+        namada_sdk::rpc::get_account_info(namada.client(), &request.owner)
+            .await?
+            .ok_or_else(|| {
+                error::Error::from(error::TxError::InvalidAccount(
+                    request.owner.encode(),
+                ))
+            })?;
+    let weight = request.signed_weight(&account)?;
+    if weight < u32::from(account.threshold) {
+        return Err(error::Error::from(error::TxError::MissingSigningKeys(
+            account.threshold,
+            u8::try_from(weight).unwrap_or(u8::MAX),
+        )));
+    }
+
+    let encode = |value: &String| {
+        serde_json::to_vec(value).map_err(|err| {
+            error::Error::from(error::EncodingError::Serde(err.to_string()))
+        })
+    };
+    let signatures = request
+        .signatures
+        .iter()
+        .map(|signature| encode(&signature.serialize()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let args = args::TxCustom {
+        tx: args::Tx {
+            signatures,
+            ..tx_args
+        },
+        code_path: None,
+        data_path: None,
+        serialized_tx: Some(encode(&request.tx)?),
+        owner: request.owner,
+    };
+    submit_custom(namada, args).await
+}
+
+fn read_signing_request(
+    path: &std::path::Path,
+) -> Result<SigningRequest, error::Error> {
+    let file = File::open(path).map_err(|err| {
+        error::Error::Other(format!(
+            "Unable to open the signing request {}: {}",
+            path.display(),
+            err
+        ))
+    })?;
+    serde_json::from_reader(file).map_err(|err| {
+        error::Error::from(error::EncodingError::Serde(err.to_string()))
+    })
+}
+
+fn write_signing_request(
+    path: &std::path::Path,
+    request: &SigningRequest,
+) -> Result<(), error::Error> {
+    let file = File::create(path).map_err(|err| {
+        error::Error::Other(format!(
+            "Unable to write the signing request {}: {}",
+            path.display(),
+            err
+        ))
+    })?;
+    serde_json::to_writer_pretty(file, request).map_err(|err| {
+        error::Error::from(error::EncodingError::Serde(err.to_string()))
+    })
+}
+
 pub async fn submit_reveal_pk<N: Namada>(
     namada: &N,
     args: args::RevealPk,
@@ -1536,6 +1840,46 @@ where
     Ok(())
 }
 
+pub async fn submit_deposit_proposal<N: Namada>(
+    namada: &N,
+    args: args::DepositProposal,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
+pub async fn submit_delegate_vote<N: Namada>(
+    namada: &N,
+    args: args::DelegateVote,
+) -> Result<(), error::Error>
+where
+    <N::Client as namada::ledger::queries::Client>::Error: std::fmt::Display,
+{
+    let (mut tx, signing_data) = args.build(namada).await?;
+
+    if args.tx.dump_tx {
+        tx::dump_tx(namada.io(), &args.tx, tx);
+    } else {
+        sign(namada, &mut tx, &args.tx, signing_data).await?;
+
+        namada.submit(tx, &args.tx).await?;
+    }
+
+    Ok(())
+}
+
 /// Save accounts initialized from a tx into the wallet, if any.
 pub async fn save_initialized_accounts(
     namada: &impl Namada,
@@ -6,6 +6,10 @@ use serde::{Deserialize, Serialize};
 /// Default [Ethereum JSON-RPC](https://ethereum.org/en/developers/docs/apis/json-rpc/) endpoint used by the oracle
 pub const DEFAULT_ORACLE_RPC_ENDPOINT: &str = "http://127.0.0.1:8545";
 
+/// The default number of Ethereum JSON-RPC endpoints that must agree on an
+/// event before the oracle forwards it.
+pub const DEFAULT_ORACLE_RPC_QUORUM: usize = 1;
+
 /// The default maximum number of Ethereum events the channel between
 /// the oracle and the shell can hold.
 pub const ORACLE_CHANNEL_BUFFER_SIZE: usize = 1000;
@@ -14,7 +18,8 @@ pub const ORACLE_CHANNEL_BUFFER_SIZE: usize = 1000;
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum Mode {
     /// The oracle will listen to the Ethereum JSON-RPC endpoint as
-    /// specified in the `oracle_rpc_endpoint` setting.
+    /// specified in the `oracle_rpc_endpoint` setting, and to the ones in
+    /// the `additional_oracle_rpc_endpoints` setting.
     RemoteEndpoint,
     /// Instead of the oracle listening for events using an Ethereum
     /// JSON-RPC endpoint, an endpoint will be exposed by the ledger
@@ -33,6 +38,15 @@ pub struct Config {
     /// The Ethereum JSON-RPC endpoint that the Ethereum event oracle will use
     /// to listen for events from the Ethereum bridge smart contracts
     pub oracle_rpc_endpoint: String,
+    /// More Ethereum JSON-RPC endpoints queried by the oracle alongside
+    /// `oracle_rpc_endpoint`, ideally backed by independent fullnodes
+    #[serde(default)]
+    pub additional_oracle_rpc_endpoints: Vec<String>,
+    /// The number of endpoints that must agree on an Ethereum event before
+    /// the oracle forwards it to the ledger. It must not be greater than the
+    /// number of endpoints. The default is 1.
+    #[serde(default = "default_oracle_rpc_quorum")]
+    pub oracle_rpc_quorum: usize,
     /// The size of bounded channel between the Ethereum oracle and main
     /// ledger subprocesses. This is the number of Ethereum events that
     /// can be held in the channel. The default is 1000.
@@ -44,7 +58,30 @@ impl Default for Config {
         Self {
             mode: Mode::RemoteEndpoint,
             oracle_rpc_endpoint: DEFAULT_ORACLE_RPC_ENDPOINT.to_owned(),
+            additional_oracle_rpc_endpoints: vec![],
+            oracle_rpc_quorum: DEFAULT_ORACLE_RPC_QUORUM,
             channel_buffer_size: ORACLE_CHANNEL_BUFFER_SIZE,
         }
     }
 }
+
+impl Config {
+    /// Get all the Ethereum JSON-RPC endpoints used by the oracle.
+    pub fn oracle_rpc_endpoints(&self) -> Vec<String> {
+        std::iter::once(&self.oracle_rpc_endpoint)
+            .chain(&self.additional_oracle_rpc_endpoints)
+            .cloned()
+            .collect()
+    }
+
+    /// Check that the oracle quorum is between 1 and the number of Ethereum
+    /// JSON-RPC endpoints used by the oracle.
+    pub fn is_valid_oracle_quorum(&self) -> bool {
+        self.oracle_rpc_quorum > 0
+            && self.oracle_rpc_quorum <= self.oracle_rpc_endpoints().len()
+    }
+}
+
+const fn default_oracle_rpc_quorum() -> usize {
+    DEFAULT_ORACLE_RPC_QUORUM
+}
@@ -385,6 +385,13 @@ impl Finalized {
             max_proposal_period,
             max_proposal_content_size,
             min_proposal_grace_epochs,
+            min_emergency_proposal_voting_period,
+            default_proposal_tally,
+            emergency_proposal_tally,
+            pgf_steward_proposal_tally,
+            pgf_funding_proposal_tally,
+            pgf_steward_funding_proposal_tally,
+            veto_threshold,
         } = self.parameters.gov_params.clone();
         namada::core::ledger::governance::parameters::GovernanceParameters {
             min_proposal_fund: Amount::native_whole(min_proposal_fund),
@@ -393,6 +400,13 @@ impl Finalized {
             max_proposal_content_size,
             min_proposal_grace_epochs,
             min_proposal_voting_period,
+            min_emergency_proposal_voting_period,
+            default_proposal_tally,
+            emergency_proposal_tally,
+            pgf_steward_proposal_tally,
+            pgf_funding_proposal_tally,
+            pgf_steward_funding_proposal_tally,
+            veto_threshold,
         }
     }
 
@@ -5,6 +5,7 @@ use std::marker::PhantomData;
 use std::path::Path;
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use namada::core::ledger::governance::utils::TallyType;
 use namada::core::types::{ethereum_structs, token};
 use namada::eth_bridge::storage::parameters::{
     Contracts, Erc20WhitelistEntry, MinimumConfirmations,
@@ -431,6 +432,21 @@ pub struct GovernanceParams {
     pub max_proposal_content_size: u64,
     /// Minimum number of epoch between end and grace epoch
     pub min_proposal_grace_epochs: u64,
+    /// Minimum emergency proposal period length in epochs
+    pub min_emergency_proposal_voting_period: u64,
+    /// Tally rule of default proposals
+    pub default_proposal_tally: TallyType,
+    /// Tally rule of emergency proposals
+    pub emergency_proposal_tally: TallyType,
+    /// Tally rule of PGF stewards proposals
+    pub pgf_steward_proposal_tally: TallyType,
+    /// Tally rule of PGF funding proposals
+    pub pgf_funding_proposal_tally: TallyType,
+    /// Tally rule of PGF funding proposals authored by a steward
+    pub pgf_steward_funding_proposal_tally: TallyType,
+    /// Fraction of the votes that must be no-with-veto for a proposal to be
+    /// vetoed and its deposits burned
+    pub veto_threshold: Dec,
 }
 
 #[derive(
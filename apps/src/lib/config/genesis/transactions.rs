@@ -731,6 +731,7 @@ impl<T> Signed<T> {
             account_public_keys_map: Some(pks.iter().cloned().collect()),
             public_keys: pks.clone(),
             threshold,
+            key_options: Default::default(),
             fee_payer: get_sentinel_pubkey(),
         };
 
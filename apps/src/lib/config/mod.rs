@@ -35,6 +35,8 @@ pub const FILENAME: &str = "config.toml";
 pub const COMETBFT_DIR: &str = "cometbft";
 /// Chain-specific Namada DB. Nested in chain dirs.
 pub const DB_DIR: &str = "db";
+/// Chain-specific state sync snapshots. Nested in chain dirs.
+pub const SNAPSHOTS_DIR: &str = "snapshots";
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Config {
@@ -111,6 +113,18 @@ pub struct Shell {
     /// When set, will limit the how many block heights in the past can the
     /// storage be queried for reading values.
     pub storage_read_past_height_limit: Option<u64>,
+    /// When set, the events emitted by the ledger are persisted in an index
+    /// in the DB that can be queried by height, tx hash, type or attribute.
+    #[serde(default)]
+    pub event_index: bool,
+    /// When set, a snapshot of the state is taken at every multiple of this
+    /// number of blocks, to be served to the nodes joining the network with
+    /// state sync.
+    #[serde(default)]
+    pub snapshot_interval: Option<u64>,
+    /// The number of most recent snapshots that are kept.
+    #[serde(default = "default_snapshots_to_keep")]
+    pub snapshots_to_keep: u64,
     /// Use the [`Ledger::db_dir()`] method to read the value.
     db_dir: PathBuf,
     /// Use the [`Ledger::cometbft_dir()`] method to read the value.
@@ -143,6 +157,9 @@ impl Ledger {
                 tx_wasm_compilation_cache_bytes: None,
                 // Default corresponds to 1 hour of past blocks at 1 block/sec
                 storage_read_past_height_limit: Some(3600),
+                event_index: false,
+                snapshot_interval: None,
+                snapshots_to_keep: default_snapshots_to_keep(),
                 db_dir: DB_DIR.into(),
                 cometbft_dir: COMETBFT_DIR.into(),
                 action_at_height: None,
@@ -181,6 +198,15 @@ impl Shell {
             .join(chain_id.as_str())
             .join(&self.cometbft_dir)
     }
+
+    /// Get the directory path to the state sync snapshots
+    pub fn snapshots_dir(&self, chain_id: &ChainId) -> PathBuf {
+        self.base_dir.join(chain_id.as_str()).join(SNAPSHOTS_DIR)
+    }
+}
+
+const fn default_snapshots_to_keep() -> u64 {
+    2
 }
 
 #[derive(Error, Debug)]
@@ -200,6 +226,11 @@ pub enum Error {
          {{protocol}}/{{ip}}/tcp/{{port}}/p2p/{{peerid}}"
     )]
     BadBootstrapPeerFormat(String),
+    #[error(
+        "The Ethereum oracle quorum {quorum} must be between 1 and the number \
+         of RPC endpoints ({endpoints})"
+    )]
+    InvalidOracleQuorum { quorum: usize, endpoints: usize },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -283,7 +314,16 @@ impl Config {
                 )
             })
             .map_err(Error::ReadError)?;
-        config.try_into().map_err(Error::DeserializationError)
+        let config: Self =
+            config.try_into().map_err(Error::DeserializationError)?;
+        let eth_bridge = &config.ledger.ethereum_bridge;
+        if !eth_bridge.is_valid_oracle_quorum() {
+            return Err(Error::InvalidOracleQuorum {
+                quorum: eth_bridge.oracle_rpc_quorum,
+                endpoints: eth_bridge.oracle_rpc_endpoints().len(),
+            });
+        }
+        Ok(config)
     }
 
     /// Generate configuration and write it to a file.
@@ -845,11 +885,41 @@ namespace = "cometbft"
 
 #[cfg(test)]
 mod tests {
-    use super::DEFAULT_COMETBFT_CONFIG;
+    use super::*;
     use crate::facade::tendermint_config::TendermintConfig;
 
     #[test]
     fn test_default_cometbft_config() {
         assert!(TendermintConfig::parse_toml(DEFAULT_COMETBFT_CONFIG).is_ok());
     }
+
+    /// Test that a config whose Ethereum oracle quorum can't be reached by
+    /// its RPC endpoints is rejected when it's read.
+    #[test]
+    fn test_invalid_oracle_quorum() {
+        let base_dir = tempfile::tempdir().unwrap();
+        let chain_id = ChainId::default();
+        let mut config = Config::new(
+            base_dir.path(),
+            chain_id.clone(),
+            TendermintMode::Full,
+        );
+        config.ledger.ethereum_bridge.oracle_rpc_quorum = 2;
+        config.write(base_dir.path(), &chain_id, false).unwrap();
+        assert!(matches!(
+            Config::read(base_dir.path(), &chain_id, None),
+            Err(Error::InvalidOracleQuorum {
+                quorum: 2,
+                endpoints: 1,
+            })
+        ));
+
+        config
+            .ledger
+            .ethereum_bridge
+            .additional_oracle_rpc_endpoints =
+            vec!["http://127.0.0.1:8546".to_string()];
+        config.write(base_dir.path(), &chain_id, true).unwrap();
+        assert!(Config::read(base_dir.path(), &chain_id, None).is_ok());
+    }
 }
@@ -3,10 +3,12 @@ pub mod events;
 pub mod test_tools;
 
 use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use async_trait::async_trait;
 use ethabi::Address;
 use ethbridge_events::{event_codecs, EventKind};
+use futures::future::join_all;
 use itertools::Either;
 use namada::core::hints;
 use namada::core::types::ethereum_structs;
@@ -30,6 +32,17 @@ use crate::node::ledger::oracle::control::Command;
 const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
 const DEFAULT_CEILING: Duration = Duration::from_secs(30);
 
+/// The number of Ethereum events on which the oracle RPC endpoints have
+/// disagreed since the node started
+static RPC_DISAGREEMENTS: AtomicU64 = AtomicU64::new(0);
+
+/// Get the counter of the Ethereum events on which the oracle RPC endpoints
+/// have disagreed since the node started, i.e. the events dropped for lack of
+/// a quorum and the ones missing from some of the responding endpoints
+pub fn rpc_disagreements() -> u64 {
+    RPC_DISAGREEMENTS.load(Ordering::Relaxed)
+}
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("Ethereum node has fallen out of sync")]
@@ -46,6 +59,11 @@ pub enum Error {
     MoreConfirmations,
     #[error("The Ethereum oracle timed out")]
     Timeout,
+    #[error(
+        "Couldn't get a quorum of RPC endpoints to agree on an event ({0} \
+         from {1}) yet"
+    )]
+    EventQuorum(String, Address),
 }
 
 /// Convert values to [`ethabi`] Ethereum event logs.
@@ -184,8 +202,11 @@ impl RpcClient for Provider<Http> {
 /// and relay events relevant to Namada to the
 /// ledger process
 pub struct Oracle<C = Provider<Http>> {
-    /// The client that talks to the Ethereum fullnode
-    client: C,
+    /// The clients that talk to the Ethereum fullnodes, one per endpoint
+    clients: Vec<C>,
+    /// The number of endpoints that must agree on an event before it is
+    /// forwarded to the ledger process
+    quorum: usize,
     /// A channel for sending processed and confirmed
     /// events to the ledger process
     sender: BoundedSender<EthereumEvent>,
@@ -202,19 +223,33 @@ pub struct Oracle<C = Provider<Http>> {
 impl<C: RpcClient> Oracle<C> {
     /// Construct a new [`Oracle`]. Note that it can not do anything until it
     /// has been sent a configuration via the passed in `control` channel.
+    ///
+    /// Panics if the quorum is zero or greater than the number of endpoints,
+    /// which is checked when loading the node config.
     pub fn new(
-        client_or_url: Either<C, &str>,
+        clients_or_urls: Either<Vec<C>, &[String]>,
+        quorum: usize,
         sender: BoundedSender<EthereumEvent>,
         last_processed_block: last_processed_block::Sender,
         backoff: Duration,
         ceiling: Duration,
         control: control::Receiver,
     ) -> Self {
+        let clients: Vec<C> = match clients_or_urls {
+            Either::Left(clients) => clients,
+            Either::Right(urls) => {
+                urls.iter().map(|url| C::new_client(url)).collect()
+            }
+        };
+        assert!(
+            quorum > 0 && quorum <= clients.len(),
+            "The Ethereum oracle quorum must be between 1 and the number of \
+             RPC endpoints ({}), got {quorum}",
+            clients.len(),
+        );
         Self {
-            client: match client_or_url {
-                Either::Left(client) => client,
-                Either::Right(url) => C::new_client(url),
-            },
+            clients,
+            quorum,
             sender,
             backoff,
             ceiling,
@@ -242,6 +277,180 @@ impl<C: RpcClient> Oracle<C> {
         true
     }
 
+    /// Query the sync status of all the Ethereum endpoints. The returned
+    /// height is the latest one reached by at least a quorum of the synced
+    /// endpoints, such that the oracle fails over the endpoints that are
+    /// lagging behind or unresponsive. It is returned along with the height
+    /// of each endpoint, if it is synced.
+    async fn syncing(
+        &self,
+        last_processed_block: Option<&ethereum_structs::BlockHeight>,
+        backoff: Duration,
+        deadline: Instant,
+    ) -> Result<(SyncStatus, Vec<Option<Uint256>>), Error> {
+        let responses = join_all(self.clients.iter().map(|client| {
+            client.syncing(last_processed_block, backoff, deadline)
+        }))
+        .await;
+        let mut heights = vec![];
+        let mut first_error = None;
+        for (endpoint, response) in responses.into_iter().enumerate() {
+            match response {
+                Ok(SyncStatus::AtHeight(height)) => {
+                    heights.push(Some(height));
+                    continue;
+                }
+                Ok(SyncStatus::Syncing) => {
+                    tracing::debug!(endpoint, "Ethereum endpoint is syncing")
+                }
+                Err(error) => {
+                    tracing::debug!(
+                        endpoint,
+                        %error,
+                        "Couldn't get the sync status of Ethereum endpoint"
+                    );
+                    first_error.get_or_insert(error);
+                }
+            }
+            heights.push(None);
+        }
+        let synced = heights.iter().flatten().cloned().collect();
+        match quorum_height(synced, self.quorum) {
+            Some(height) => Ok((SyncStatus::AtHeight(height), heights)),
+            None => first_error.map_or(Ok((SyncStatus::Syncing, heights)), Err),
+        }
+    }
+
+    /// Query all the Ethereum endpoints for the events of a block, keeping
+    /// only the logs returned by at least a quorum of them. The logs on which
+    /// the endpoints disagree are reported and dropped. An error is returned
+    /// if less than a quorum of endpoints responded, or if a log could still
+    /// reach a quorum once the endpoints that failed to respond or whose
+    /// synced `heights` are below the block catch up, such that the block is
+    /// checked again.
+    async fn check_events_in_block(
+        &self,
+        block: &ethereum_structs::BlockHeight,
+        address: Address,
+        abi_signature: &str,
+        heights: &[Option<Uint256>],
+    ) -> Result<Vec<ethabi::RawLog>, Error> {
+        let responses = join_all(self.clients.iter().map(|client| {
+            client.check_events_in_block(block.clone(), address, abi_signature)
+        }))
+        .await;
+        let mut responding = vec![];
+        // the endpoints that may still return more logs for the block
+        let mut lagging = vec![];
+        let mut first_error = None;
+        // the logs with the endpoints that returned them
+        let mut votes: Vec<(ethabi::RawLog, Vec<usize>)> = vec![];
+        for (endpoint, response) in responses.into_iter().enumerate() {
+            let logs = match response {
+                Ok(logs) => logs,
+                Err(error) => {
+                    tracing::warn!(
+                        endpoint,
+                        %error,
+                        ?block,
+                        "Couldn't check for events with Ethereum endpoint"
+                    );
+                    lagging.push(endpoint);
+                    first_error.get_or_insert(error);
+                    continue;
+                }
+            };
+            responding.push(endpoint);
+            let synced_height = heights.get(endpoint).cloned().flatten();
+            if synced_height.as_ref() < Some(<&Uint256>::from(block)) {
+                lagging.push(endpoint);
+            }
+            for log in logs.into_iter().map(IntoEthAbiLog::into_ethabi_log) {
+                match votes.iter_mut().find(|(voted, _)| *voted == log) {
+                    Some((_, voters)) => {
+                        if !voters.contains(&endpoint) {
+                            voters.push(endpoint);
+                        }
+                    }
+                    None => votes.push((log, vec![endpoint])),
+                }
+            }
+        }
+        if responding.len() < self.quorum {
+            if let Some(error) = first_error {
+                return Err(error);
+            }
+        }
+        let may_reach_quorum = |voters: &Vec<usize>| {
+            let catching_up = lagging
+                .iter()
+                .filter(|endpoint| !voters.contains(*endpoint))
+                .count();
+            voters.len() < self.quorum
+                && voters.len() + catching_up >= self.quorum
+        };
+        if let Some((log, voters)) =
+            votes.iter().find(|(_, voters)| may_reach_quorum(voters))
+        {
+            tracing::warn!(
+                ?block,
+                ?address,
+                abi_signature,
+                endpoints = ?voters,
+                quorum = self.quorum,
+                ?lagging,
+                "Checking an Ethereum block again for an event that may still \
+                 reach a quorum of endpoints: {:#?}",
+                log
+            );
+            return Err(Error::EventQuorum(abi_signature.into(), address));
+        }
+        let mut agreed = vec![];
+        for (log, voters) in votes {
+            if voters.len() < responding.len() {
+                RPC_DISAGREEMENTS.fetch_add(1, Ordering::Relaxed);
+            }
+            if voters.len() >= self.quorum {
+                if voters.len() < responding.len() {
+                    let missing: Vec<_> = responding
+                        .iter()
+                        .filter(|endpoint| !voters.contains(*endpoint))
+                        .copied()
+                        .collect();
+                    tracing::warn!(
+                        ?block,
+                        ?address,
+                        abi_signature,
+                        ?missing,
+                        disagreements = rpc_disagreements(),
+                        "Ethereum endpoints did not return an event agreed on \
+                         by a quorum of endpoints"
+                    );
+                }
+                agreed.push(log);
+            } else {
+                tracing::warn!(
+                    ?block,
+                    ?address,
+                    abi_signature,
+                    endpoints = ?voters,
+                    quorum = self.quorum,
+                    disagreements = rpc_disagreements(),
+                    "Dropping an Ethereum event not agreed on by a quorum of \
+                     endpoints: {:#?}",
+                    log
+                );
+            }
+        }
+        Ok(agreed)
+    }
+
+    /// Check if any of the RPC clients may recover from the given
+    /// [`enum@Error`].
+    fn may_recover(&self, error: &Error) -> bool {
+        self.clients.iter().any(|client| client.may_recover(error))
+    }
+
     /// Check if a new config has been sent from the Shell.
     fn update_config(&mut self) -> Option<Config> {
         match self.control.try_recv() {
@@ -281,22 +490,27 @@ async fn await_initial_configuration(
 /// Set up an Oracle and run the process where the Oracle
 /// processes and forwards Ethereum events to the ledger
 pub fn run_oracle<C: RpcClient>(
-    url: impl AsRef<str>,
+    urls: Vec<String>,
+    quorum: usize,
     sender: BoundedSender<EthereumEvent>,
     control: control::Receiver,
     last_processed_block: last_processed_block::Sender,
     spawner: &mut AbortableSpawner,
 ) -> tokio::task::JoinHandle<()> {
-    let url = url.as_ref().to_owned();
     let blocking_handle = tokio::task::spawn_blocking(move || {
         let rt = tokio::runtime::Handle::current();
         rt.block_on(async move {
             LocalSet::new()
                 .run_until(async move {
-                    tracing::info!(?url, "Ethereum event oracle is starting");
+                    tracing::info!(
+                        ?urls,
+                        quorum,
+                        "Ethereum event oracle is starting"
+                    );
 
                     let oracle = Oracle::<C>::new(
-                        Either::Right(&url),
+                        Either::Right(&urls),
+                        quorum,
                         sender,
                         last_processed_block,
                         DEFAULT_BACKOFF,
@@ -306,7 +520,7 @@ pub fn run_oracle<C: RpcClient>(
                     run_oracle_aux(oracle).await;
 
                     tracing::info!(
-                        ?url,
+                        ?urls,
                         "Ethereum event oracle is no longer running"
                     );
                 })
@@ -371,7 +585,7 @@ pub(crate) async fn try_process_eth_events<C: RpcClient>(
         .await
         .map_or_else(
             |error| {
-                if oracle.client.may_recover(&error) {
+                if oracle.may_recover(&error) {
                     tracing::debug!(
                         %error,
                         block = ?next_block_to_process,
@@ -472,11 +686,10 @@ async fn process_events_in_block<C: RpcClient>(
     let last_processed_block = last_processed_block_ref.as_ref();
     let backoff = oracle.backoff;
     let deadline = Instant::now() + oracle.ceiling;
-    let latest_block = match oracle
-        .client
+    let (status, heights) = oracle
         .syncing(last_processed_block, backoff, deadline)
-        .await?
-    {
+        .await?;
+    let latest_block = match status {
         SyncStatus::AtHeight(height) => height,
         SyncStatus::Syncing => return Err(Error::FallenBehind),
     }
@@ -513,8 +726,7 @@ async fn process_events_in_block<C: RpcClient>(
         // fetch the events for matching the given signature
         let mut events = {
             let logs = oracle
-                .client
-                .check_events_in_block(block_to_process.clone(), addr, &sig)
+                .check_events_in_block(block_to_process, addr, &sig, &heights)
                 .await?;
             if !logs.is_empty() {
                 tracing::info!(
@@ -526,7 +738,6 @@ async fn process_events_in_block<C: RpcClient>(
                 )
             }
             logs.into_iter()
-                .map(IntoEthAbiLog::into_ethabi_log)
                 .filter_map(|log| {
                     match PendingEvent::decode(
                         codec,
@@ -580,6 +791,13 @@ async fn process_events_in_block<C: RpcClient>(
     Ok(())
 }
 
+/// Get the latest block height reached by at least `quorum` endpoints, given
+/// the heights of the synced endpoints.
+fn quorum_height(mut heights: Vec<Uint256>, quorum: usize) -> Option<Uint256> {
+    heights.sort_unstable_by(|a, b| b.cmp(a));
+    heights.into_iter().nth(quorum.checked_sub(1)?)
+}
+
 /// Check which events in the queue have reached their
 /// required number of confirmations and remove them
 /// from the queue of pending events
@@ -677,7 +895,8 @@ mod test_oracle {
         let controller = client.controller();
         TestPackage {
             oracle: TestOracle {
-                client,
+                clients: vec![client],
+                quorum: 1,
                 sender: eth_sender,
                 last_processed_block: last_processed_block_sender,
                 // backoff should be short for tests so that they run faster
@@ -692,6 +911,223 @@ mod test_oracle {
         }
     }
 
+    /// The data returned from setting up a test with several endpoints
+    struct TestEndpointsPackage {
+        oracle: TestOracle,
+        controllers: Vec<Web3Controller>,
+        eth_recv: tokio::sync::mpsc::Receiver<EthereumEvent>,
+        control_sender: control::Sender,
+    }
+
+    /// Set up an oracle with `n` mock web3 clients that we can control,
+    /// requiring `quorum` of them to agree on the events
+    fn setup_endpoints(n: usize, quorum: usize) -> TestEndpointsPackage {
+        let (_, controllers, clients) = Web3Client::setup_endpoints(n);
+        let (eth_sender, eth_receiver) = tokio::sync::mpsc::channel(1000);
+        let (last_processed_block_sender, _) = last_processed_block::channel();
+        let (control_sender, control_receiver) = control::channel();
+        TestEndpointsPackage {
+            oracle: TestOracle::new(
+                Either::Left(clients),
+                quorum,
+                eth_sender,
+                last_processed_block_sender,
+                Duration::from_millis(5),
+                DEFAULT_CEILING,
+                control_receiver,
+            ),
+            controllers,
+            eth_recv: eth_receiver,
+            control_sender,
+        }
+    }
+
+    /// Test the latest block height reached by a quorum of endpoints.
+    #[test]
+    fn test_quorum_height() {
+        let heights: Vec<Uint256> =
+            vec![10u32.into(), 12u32.into(), 11u32.into()];
+        assert_eq!(quorum_height(heights.clone(), 1), Some(12u32.into()));
+        assert_eq!(quorum_height(heights.clone(), 2), Some(11u32.into()));
+        assert_eq!(quorum_height(heights.clone(), 3), Some(10u32.into()));
+        assert_eq!(quorum_height(heights, 4), None);
+    }
+
+    /// Test that only the events returned by a quorum of endpoints are
+    /// forwarded to the ledger.
+    #[tokio::test]
+    async fn test_events_quorum() {
+        let TestEndpointsPackage {
+            oracle,
+            controllers,
+            mut eth_recv,
+            mut control_sender,
+        } = setup_endpoints(3, 2);
+        let min_confirmations = 100;
+        let config = Config {
+            min_confirmations: NonZeroU64::try_from(min_confirmations)
+                .expect("Test wasn't set up correctly"),
+            ..Config::default()
+        };
+        let oracle =
+            start_with_default_config(oracle, &mut control_sender, config)
+                .await;
+
+        let disagreements = rpc_disagreements();
+        // only the first endpoint returns the event with nonce 0, whereas
+        // the two others return the event with nonce 1
+        let mut seen = vec![];
+        for (i, controller) in controllers.iter().enumerate() {
+            let nonce: u64 = if i == 0 { 0 } else { 1 };
+            let (sender, recv) = channel();
+            seen.push(recv);
+            controller.apply_cmd(TestCmd::NewEvent {
+                event_type: event_signature::<TransferToChainFilter>(),
+                log: TransferToChainFilter {
+                    nonce: nonce.into(),
+                    transfers: vec![],
+                    confirmations: 100.into(),
+                }
+                .get_log(),
+                height: 100,
+                seen: sender,
+            });
+        }
+        for controller in &controllers {
+            controller.apply_cmd(TestCmd::NewHeight(Uint256::from(200u32)));
+        }
+
+        let event = eth_recv.recv().await.expect("Test failed");
+        if let EthereumEvent::TransfersToNamada { nonce, .. } = event {
+            assert_eq!(nonce, 1.into());
+        } else {
+            panic!("Test failed, {:?}", event);
+        }
+        // the event without a quorum was seen but dropped
+        for seen in seen {
+            seen.await.expect("Test failed");
+        }
+        // both events were not returned by all the endpoints
+        assert!(rpc_disagreements() >= disagreements + 2);
+        let mut time = std::time::Duration::from_secs(1);
+        while time > std::time::Duration::from_millis(10) {
+            assert!(eth_recv.try_recv().is_err());
+            time -= std::time::Duration::from_millis(10);
+        }
+        drop(eth_recv);
+        oracle.await.expect("Test failed");
+    }
+
+    /// Test that a block is checked again when an event lacks a quorum only
+    /// because of endpoints that failed to respond or are lagging behind,
+    /// and that the event is dropped once they all caught up.
+    #[tokio::test]
+    async fn test_events_quorum_retry() {
+        let TestEndpointsPackage {
+            oracle,
+            controllers,
+            ..
+        } = setup_endpoints(3, 2);
+        let block = ethereum_structs::BlockHeight::from(100);
+        let sig = event_signature::<TransferToChainFilter>();
+        // only the second endpoint returns the event
+        let add_event = || {
+            let (sender, recv) = channel();
+            controllers[1].apply_cmd(TestCmd::NewEvent {
+                event_type: sig.clone(),
+                log: TransferToChainFilter {
+                    nonce: 0.into(),
+                    transfers: vec![],
+                    confirmations: 100.into(),
+                }
+                .get_log(),
+                height: 100,
+                seen: sender,
+            });
+            recv
+        };
+        let synced = Some(Uint256::from(200u32));
+
+        // the first endpoint is down and may still return the event
+        controllers[0].apply_cmd(TestCmd::Offline);
+        let _seen = add_event();
+        let heights = vec![None, synced.clone(), synced.clone()];
+        let result = oracle
+            .check_events_in_block(&block, Address::default(), &sig, &heights)
+            .await;
+        assert!(matches!(result, Err(Error::EventQuorum(_, _))));
+
+        // the third endpoint has not reached the block yet
+        controllers[0].apply_cmd(TestCmd::Normal);
+        let _seen = add_event();
+        let heights =
+            vec![synced.clone(), synced.clone(), Some(Uint256::from(50u32))];
+        let result = oracle
+            .check_events_in_block(&block, Address::default(), &sig, &heights)
+            .await;
+        assert!(matches!(result, Err(Error::EventQuorum(_, _))));
+
+        // all the endpoints are synced past the block and disagree
+        let _seen = add_event();
+        let heights = vec![synced.clone(), synced.clone(), synced];
+        let result = oracle
+            .check_events_in_block(&block, Address::default(), &sig, &heights)
+            .await;
+        assert!(matches!(result, Ok(logs) if logs.is_empty()));
+    }
+
+    /// Test that the oracle keeps processing blocks when an endpoint goes
+    /// down, as long as a quorum of endpoints is still responding.
+    #[tokio::test]
+    async fn test_endpoint_failover() {
+        let TestEndpointsPackage {
+            oracle,
+            controllers,
+            mut eth_recv,
+            mut control_sender,
+        } = setup_endpoints(3, 2);
+        let oracle = start_with_default_config(
+            oracle,
+            &mut control_sender,
+            Config::default(),
+        )
+        .await;
+        controllers[0].apply_cmd(TestCmd::Offline);
+
+        let mut seen = vec![];
+        for controller in &controllers[1..] {
+            let (sender, recv) = channel();
+            seen.push(recv);
+            controller.apply_cmd(TestCmd::NewEvent {
+                event_type: event_signature::<TransferToChainFilter>(),
+                log: TransferToChainFilter {
+                    nonce: 0.into(),
+                    transfers: vec![],
+                    confirmations: 100.into(),
+                }
+                .get_log(),
+                height: 10,
+                seen: sender,
+            });
+        }
+        // the offline endpoint is still at height 0
+        for controller in &controllers[1..] {
+            controller.apply_cmd(TestCmd::NewHeight(Uint256::from(200u32)));
+        }
+
+        let event = timeout(std::time::Duration::from_secs(3), eth_recv.recv())
+            .await
+            .expect("Timed out waiting for the event")
+            .expect("Test failed");
+        if let EthereumEvent::TransfersToNamada { nonce, .. } = event {
+            assert_eq!(nonce, 0.into());
+        } else {
+            panic!("Test failed, {:?}", event);
+        }
+        drop(eth_recv);
+        oracle.await.expect("Test failed");
+    }
+
     /// Test that if the fullnode stops, the oracle
     /// shuts down, even if the web3 client is unresponsive
     #[tokio::test]
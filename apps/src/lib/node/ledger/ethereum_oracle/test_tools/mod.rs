@@ -86,6 +86,8 @@ pub mod mock_web3_client {
     pub enum TestCmd {
         Normal,
         Unresponsive,
+        /// Fail every request, as if the fullnode had gone down
+        Offline,
         NewHeight(Uint256),
         NewEvent {
             event_type: MockEventType,
@@ -110,8 +112,12 @@ pub mod mock_web3_client {
         pub fn apply_cmd(&self, cmd: TestCmd) {
             let mut oracle = self.0.lock().unwrap();
             match cmd {
-                TestCmd::Normal => oracle.active = true,
+                TestCmd::Normal => {
+                    oracle.active = true;
+                    oracle.online = true;
+                }
                 TestCmd::Unresponsive => oracle.active = false,
+                TestCmd::Offline => oracle.online = false,
                 TestCmd::NewHeight(height) => {
                     oracle.latest_block_height = height
                 }
@@ -138,6 +144,7 @@ pub mod mock_web3_client {
     /// testing purposes.
     pub struct Web3ClientInner {
         active: bool,
+        online: bool,
         latest_block_height: Uint256,
         events: Vec<(MockEventType, ethabi::RawLog, u32, Sender<()>)>,
         blocks_processed: UnboundedSender<Uint256>,
@@ -167,7 +174,7 @@ pub mod mock_web3_client {
         ) -> Result<Vec<Self::Log>, Error> {
             let block_to_check: Uint256 = block.into();
             let mut client = self.0.lock().unwrap();
-            if client.active {
+            if client.active && client.online {
                 let mut logs = vec![];
                 let mut events = vec![];
                 std::mem::swap(&mut client.events, &mut events);
@@ -205,8 +212,12 @@ pub mod mock_web3_client {
             _: Duration,
             _: Instant,
         ) -> Result<SyncStatus, Error> {
-            let height = self.0.lock().unwrap().latest_block_height.clone();
-            Ok(SyncStatus::AtHeight(height))
+            let client = self.0.lock().unwrap();
+            if client.online {
+                Ok(SyncStatus::AtHeight(client.latest_block_height.clone()))
+            } else {
+                Err(Error::Timeout)
+            }
         }
 
         #[inline(always)]
@@ -225,6 +236,7 @@ pub mod mock_web3_client {
                 block_processed_recv,
                 Self(Arc::new(Mutex::new(Web3ClientInner {
                     active: true,
+                    online: true,
                     latest_block_height: Default::default(),
                     events: vec![],
                     blocks_processed: block_processed_send,
@@ -233,6 +245,28 @@ pub mod mock_web3_client {
             )
         }
 
+        /// Return `n` independent clients, mocking as many Ethereum
+        /// endpoints, along with their controllers and the receivers of
+        /// their processed blocks
+        pub fn setup_endpoints(
+            n: usize,
+        ) -> (
+            Vec<UnboundedReceiver<Uint256>>,
+            Vec<Web3Controller>,
+            Vec<Self>,
+        ) {
+            let mut blocks_processed = Vec::with_capacity(n);
+            let mut controllers = Vec::with_capacity(n);
+            let mut clients = Vec::with_capacity(n);
+            for _ in 0..n {
+                let (recv, client) = Self::setup();
+                blocks_processed.push(recv);
+                controllers.push(client.controller());
+                clients.push(client);
+            }
+            (blocks_processed, controllers, clients)
+        }
+
         /// Get a new [`Web3Controller`] for the current oracle.
         pub fn controller(&self) -> Web3Controller {
             Web3Controller(Arc::clone(&self.0))
@@ -94,6 +94,8 @@ where
                 .expect("Failed tx hashes finalization")
         }
 
+        self.finalize_pending_upgrade(height)?;
+
         let pos_params =
             namada_proof_of_stake::storage::read_pos_params(&self.wl_storage)?;
 
@@ -144,6 +146,9 @@ where
             // the rewards in the current epoch.
             self.process_slashes();
             self.apply_inflation(current_epoch)?;
+            // The continuous PGF payments over IBC emit events outside of
+            // any tx
+            response.events.extend(self.take_protocol_ibc_events());
         }
 
         // Consensus set liveness check
@@ -294,13 +299,24 @@ where
 
                         match inner {
                             DecryptedTx::Decrypted => {
-                                if let Some(code_sec) = tx
-                                    .get_section(tx.code_sechash())
-                                    .and_then(|x| Section::code_sec(x.as_ref()))
-                                {
-                                    stats.increment_tx_type(
-                                        code_sec.code.hash().to_string(),
-                                    );
+                                let code_sechashes = match tx.batch() {
+                                    Some(batch) => batch
+                                        .txs
+                                        .iter()
+                                        .map(|inner_tx| inner_tx.code_hash)
+                                        .collect(),
+                                    None => vec![*tx.code_sechash()],
+                                };
+                                for code_sechash in code_sechashes {
+                                    if let Some(code_sec) =
+                                        tx.get_section(&code_sechash).and_then(
+                                            |x| Section::code_sec(x.as_ref()),
+                                        )
+                                    {
+                                        stats.increment_tx_type(
+                                            code_sec.code.hash().to_string(),
+                                        );
+                                    }
                                 }
                             }
                             DecryptedTx::Undecryptable => {
@@ -597,7 +613,7 @@ where
             native_block_proposer_address,
         )?;
 
-        self.event_log_mut().log_events(response.events.clone());
+        self.log_events(height, response.events.clone())?;
         tracing::debug!("End finalize_block {height} of epoch {current_epoch}");
 
         Ok(response)
@@ -681,11 +697,32 @@ where
         )?;
 
         // Pgf inflation
-        pgf_inflation::apply_inflation(&mut self.wl_storage)?;
+        pgf_inflation::apply_inflation(
+            &mut self.wl_storage,
+            namada::ledger::ibc::transfer_over_ibc,
+        )?;
 
         Ok(())
     }
 
+    /// Take the IBC events emitted by the protocol itself, e.g. by the PGF
+    /// payments over IBC, such that they are not discarded with the write log
+    /// of the next tx.
+    pub(super) fn take_protocol_ibc_events(&mut self) -> Vec<Event> {
+        let height = self.wl_storage.storage.block.height;
+        self.wl_storage
+            .write_log
+            .take_ibc_events()
+            .into_iter()
+            .map(|ibc_event| {
+                let mut event = Event::from(ibc_event);
+                // Add the height for IBC event query
+                event["height"] = height.to_string();
+                event
+            })
+            .collect()
+    }
+
     // Process the proposer and votes in the block to assign their PoS rewards.
     fn log_block_rewards(
         &mut self,
@@ -809,17 +846,24 @@ fn pos_votes_from_abci(
 /// are covered by the e2e tests.
 #[cfg(test)]
 mod test_finalize_block {
-    use std::collections::{BTreeMap, BTreeSet, HashMap};
+    use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
     use std::num::NonZeroU64;
     use std::str::FromStr;
 
     use data_encoding::HEXUPPER;
     use namada::core::ledger::eth_bridge::storage::wrapped_erc20s;
-    use namada::core::ledger::governance::storage::keys::get_proposal_execution_key;
-    use namada::core::ledger::governance::storage::proposal::ProposalType;
+    use namada::core::ledger::governance::storage::keys::{
+        get_proposal_execution_key, get_proposal_result_key,
+    };
+    use namada::core::ledger::governance::storage::proposal::{
+        AddRemove, ProposalType,
+    };
     use namada::core::ledger::governance::storage::vote::{
         StorageProposalVote, VoteType,
     };
+    use namada::core::ledger::governance::utils::{
+        ProposalResult, TallyResult,
+    };
     use namada::core::ledger::replay_protection;
     use namada::core::types::storage::KeySeg;
     use namada::eth_bridge::storage::bridge_pool::{
@@ -1577,6 +1621,98 @@ mod test_finalize_block {
         });
     }
 
+    /// Test that a proposal whose deposit did not reach the minimum is
+    /// rejected without being executed, and that its deposit is refunded.
+    #[test]
+    fn test_underfunded_proposal_not_executed() {
+        let (mut shell, _broadcaster, _, _eth_control) = setup();
+        let validator = shell.mode.get_validator_address().unwrap().clone();
+        let native_token = shell.wl_storage.storage.native_token.clone();
+        let min_proposal_fund =
+            storage_api::governance::get_parameters(&shell.wl_storage)
+                .unwrap()
+                .min_proposal_fund;
+        let author = namada::types::address::testing::established_address_1();
+        storage_api::token::credit_tokens(
+            &mut shell.wl_storage,
+            &native_token,
+            &author,
+            min_proposal_fund + min_proposal_fund,
+        )
+        .unwrap();
+
+        // Add two proposals to add a PGF steward, one with the minimum
+        // deposit and one with less, both voted yay by the only validator
+        let underfunded_deposit =
+            min_proposal_fund.checked_sub(Amount::from_u64(1)).unwrap();
+        let stewards = [
+            namada::types::address::testing::established_address_2(),
+            namada::types::address::testing::established_address_3(),
+        ];
+        for (proposal_id, deposit) in
+            [(0, min_proposal_fund), (1, underfunded_deposit)]
+        {
+            shell.proposal_data.insert(proposal_id);
+            let proposal = InitProposalData {
+                id: Some(proposal_id),
+                content: Hash::default(),
+                author: author.clone(),
+                voting_start_epoch: Epoch::default(),
+                voting_end_epoch: Epoch::default().next(),
+                grace_epoch: Epoch::default().next(),
+                r#type: ProposalType::PGFSteward(HashSet::from([
+                    AddRemove::Add(stewards[proposal_id as usize].clone()),
+                ])),
+                deposit,
+            };
+            storage_api::governance::init_proposal(
+                &mut shell.wl_storage,
+                proposal,
+                vec![],
+                None,
+            )
+            .unwrap();
+            let vote = VoteProposalData {
+                id: proposal_id,
+                vote: StorageProposalVote::Yay(VoteType::Default),
+                voter: validator.clone(),
+                delegations: vec![],
+            };
+            storage_api::governance::vote_proposal(&mut shell.wl_storage, vote)
+                .unwrap();
+        }
+
+        let mut response = shim::response::FinalizeBlock::default();
+        execute_governance_proposals(&mut shell.shell, &mut response).unwrap();
+
+        let tally_result = |proposal_id| {
+            let result: ProposalResult = shell
+                .wl_storage
+                .read(&get_proposal_result_key(proposal_id))
+                .unwrap()
+                .unwrap();
+            result.result
+        };
+        assert!(matches!(tally_result(0), TallyResult::Passed));
+        assert!(matches!(tally_result(1), TallyResult::Rejected));
+        assert!(
+            storage_api::pgf::is_steward(&shell.wl_storage, &stewards[0])
+                .unwrap()
+        );
+        assert!(
+            !storage_api::pgf::is_steward(&shell.wl_storage, &stewards[1])
+                .unwrap()
+        );
+        // Both deposits are refunded
+        let balance = storage_api::token::read_balance(
+            &shell.wl_storage,
+            &native_token,
+            &author,
+        )
+        .unwrap();
+        assert_eq!(balance, min_proposal_fund + min_proposal_fund);
+    }
+
     /// Test that the finalize block handler never commits changes directly to
     /// the DB.
     #[test]
@@ -1621,6 +1757,7 @@ mod test_finalize_block {
                 voting_end_epoch: Epoch::default().next(),
                 grace_epoch: Epoch::default().next(),
                 r#type: ProposalType::Default(None),
+                deposit: Amount::zero(),
             };
 
             storage_api::governance::init_proposal(
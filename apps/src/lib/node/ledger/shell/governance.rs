@@ -1,27 +1,25 @@
-use std::collections::HashMap;
-
+use namada::core::ledger::eth_bridge::storage::whitelist;
 use namada::core::ledger::governance::storage::keys as gov_storage;
 use namada::core::ledger::governance::storage::proposal::{
-    AddRemove, PGFAction, ProposalType, StoragePgfFunding,
+    AddRemove, PGFAction, PGFTarget, ParameterChange, ProposalType,
+    StoragePgfFunding, Upgrade,
 };
 use namada::core::ledger::governance::utils::{
-    compute_proposal_result, ProposalVotes, TallyResult, TallyType, TallyVote,
-    VotePower,
+    ProposalResult, TallyResult, VotePower,
 };
 use namada::core::ledger::governance::ADDRESS as gov_address;
+use namada::core::ledger::ibc::rate_limit;
 use namada::core::ledger::pgf::storage::keys as pgf_storage;
 use namada::core::ledger::pgf::storage::steward::StewardDetail;
 use namada::core::ledger::pgf::ADDRESS;
 use namada::core::ledger::storage_api::governance as gov_api;
 use namada::ledger::governance::utils::ProposalEvent;
-use namada::ledger::pos::BondId;
-use namada::ledger::protocol;
+use namada::ledger::parameters::storage as params_storage;
 use namada::ledger::storage::types::encode;
 use namada::ledger::storage::{DBIter, StorageHasher, DB};
 use namada::ledger::storage_api::{pgf, token, StorageWrite};
-use namada::proof_of_stake::bond_amount;
-use namada::proof_of_stake::parameters::PosParams;
-use namada::proof_of_stake::storage::read_total_stake;
+use namada::ledger::{ibc, protocol};
+use namada::proof_of_stake::storage::{read_total_stake, write_pos_params};
 use namada::proto::{Code, Data};
 use namada::types::address::Address;
 use namada::types::storage::Epoch;
@@ -66,24 +64,59 @@ where
         let total_voting_power =
             read_total_stake(&shell.wl_storage, &params, proposal_end_epoch)?;
 
-        let tally_type = TallyType::from(proposal_type.clone(), is_steward);
-        let votes = compute_proposal_votes(
+        let gov_params = gov_api::get_parameters(&shell.wl_storage)?;
+        let native_token = shell.wl_storage.storage.native_token.clone();
+
+        // A proposal whose deposit did not reach the minimum before its
+        // voting period could not be voted on, so it is rejected without a
+        // tally and its deposits are refunded
+        if funds < gov_params.min_proposal_fund {
+            let proposal_result = ProposalResult {
+                result: TallyResult::Rejected,
+                total_voting_power,
+                total_yay_power: VotePower::zero(),
+                total_nay_power: VotePower::zero(),
+                total_abstain_power: VotePower::zero(),
+                total_no_with_veto_power: VotePower::zero(),
+            };
+            let proposal_result_key = gov_storage::get_proposal_result_key(id);
+            shell
+                .wl_storage
+                .write(&proposal_result_key, proposal_result)?;
+            let proposal_event =
+                ProposalEvent::rejected_proposal_event(id).into();
+            response.events.push(proposal_event);
+            proposals_result.rejected.push(id);
+
+            tracing::info!(
+                "Governance proposal {} has been rejected, as its deposit of \
+                 {} is below the minimum of {}.",
+                id,
+                funds.to_string_native(),
+                gov_params.min_proposal_fund.to_string_native()
+            );
+
+            refund_proposal_deposits(&mut shell.wl_storage, &native_token, id)?;
+            continue;
+        }
+
+        let proposal_result = gov_api::tally_proposal(
             &shell.wl_storage,
             &params,
             id,
+            &proposal_type,
+            is_steward,
             proposal_end_epoch,
         )?;
-        let proposal_result =
-            compute_proposal_result(votes, total_voting_power, tally_type);
         let proposal_result_key = gov_storage::get_proposal_result_key(id);
         shell
             .wl_storage
             .write(&proposal_result_key, proposal_result)?;
 
-        let transfer_address = match proposal_result.result {
+        let burn_deposits = match proposal_result.result {
             TallyResult::Passed => {
                 let proposal_event = match proposal_type {
-                    ProposalType::Default(_) => {
+                    ProposalType::Default(_) | ProposalType::Emergency(_) => {
                         let proposal_code_key =
                             gov_storage::get_proposal_code_key(id);
                         let proposal_code =
@@ -94,8 +127,9 @@ where
                             proposal_code.clone(),
                         )?;
                         tracing::info!(
-                            "Governance proposal (default) {} has been \
-                             executed ({}) and passed.",
+                            "Governance proposal ({}) {} has been executed \
+                             ({}) and passed.",
+                            proposal_type,
                             id,
                             result
                         );
@@ -107,6 +141,39 @@ where
                         )
                         .into()
                     }
+                    ProposalType::ParameterChange(changes) => {
+                        let result = execute_parameter_change_proposal(
+                            &mut shell.wl_storage,
+                            id,
+                            changes,
+                        )?;
+                        tracing::info!(
+                            "Governance proposal (parameter change) {} has \
+                             been executed ({}) and passed.",
+                            id,
+                            result
+                        );
+
+                        ProposalEvent::parameter_change_proposal_event(
+                            id, result,
+                        )
+                        .into()
+                    }
+                    ProposalType::Upgrade(upgrade) => {
+                        let result = execute_upgrade_proposal(
+                            &mut shell.wl_storage,
+                            id,
+                            upgrade,
+                        )?;
+                        tracing::info!(
+                            "Governance proposal (upgrade) {} has been \
+                             executed ({}) and passed.",
+                            id,
+                            result
+                        );
+
+                        ProposalEvent::upgrade_proposal_event(id, result).into()
+                    }
                     ProposalType::PGFSteward(stewards) => {
                         let result = execute_pgf_steward_proposal(
                             &mut shell.wl_storage,
@@ -130,6 +197,12 @@ where
                             payments,
                             id,
                         )?;
+                        // The events of the retro payments over IBC must be
+                        // emitted before the write log is reused to execute
+                        // the next proposal
+                        response
+                            .events
+                            .extend(shell.take_protocol_ibc_events());
                         tracing::info!(
                             "Governance proposal (pgf funding) {} has been \
                              executed and passed.",
@@ -143,8 +216,7 @@ where
                 response.events.push(proposal_event);
                 proposals_result.passed.push(id);
 
-                let proposal_author_key = gov_storage::get_author_key(id);
-                shell.wl_storage.read::<Address>(&proposal_author_key)?
+                false
             }
             TallyResult::Rejected => {
                 if let ProposalType::PGFPayment(_) = proposal_type {
@@ -174,88 +246,60 @@ where
                     id
                 );
 
-                None
+                false
+            }
+            TallyResult::Vetoed => {
+                let proposal_event =
+                    ProposalEvent::vetoed_proposal_event(id).into();
+                response.events.push(proposal_event);
+                proposals_result.rejected.push(id);
+
+                tracing::info!(
+                    "Governance proposal {} has been vetoed and its deposits \
+                     are being burned.",
+                    id
+                );
+
+                true
             }
         };
 
-        let native_token = shell.wl_storage.storage.native_token.clone();
-        if let Some(address) = transfer_address {
-            token::transfer(
-                &mut shell.wl_storage,
-                &native_token,
-                &gov_address,
-                &address,
-                funds,
-            )?;
-        } else {
+        if burn_deposits {
             token::burn(
                 &mut shell.wl_storage,
                 &native_token,
                 &gov_address,
                 funds,
             )?;
+        } else {
+            refund_proposal_deposits(&mut shell.wl_storage, &native_token, id)?;
         }
     }
 
     Ok(proposals_result)
 }
 
-fn compute_proposal_votes<S>(
-    storage: &S,
-    params: &PosParams,
+/// Refund the deposits of a proposal, such that each backer gets back exactly
+/// what they deposited
+fn refund_proposal_deposits<S>(
+    storage: &mut S,
+    native_token: &Address,
     proposal_id: u64,
-    epoch: Epoch,
-) -> storage_api::Result<ProposalVotes>
+) -> storage_api::Result<()>
 where
-    S: StorageRead,
+    S: StorageRead + StorageWrite,
 {
-    let votes = gov_api::get_proposal_votes(storage, proposal_id)?;
-
-    let mut validators_vote: HashMap<Address, TallyVote> = HashMap::default();
-    let mut validator_voting_power: HashMap<Address, VotePower> =
-        HashMap::default();
-    let mut delegators_vote: HashMap<Address, TallyVote> = HashMap::default();
-    let mut delegator_voting_power: HashMap<
-        Address,
-        HashMap<Address, VotePower>,
-    > = HashMap::default();
-
-    for vote in votes {
-        if vote.is_validator() {
-            let validator = vote.validator.clone();
-            let vote_data = vote.data.clone();
-
-            let validator_stake =
-                read_total_stake(storage, params, epoch).unwrap_or_default();
-
-            validators_vote.insert(validator.clone(), vote_data.into());
-            validator_voting_power.insert(validator, validator_stake);
-        } else {
-            let validator = vote.validator.clone();
-            let delegator = vote.delegator.clone();
-            let vote_data = vote.data.clone();
-
-            let bond_id = BondId {
-                source: delegator.clone(),
-                validator: validator.clone(),
-            };
-            let delegator_stake =
-                bond_amount(storage, &bond_id, epoch).unwrap_or_default();
-
-            delegators_vote.insert(delegator.clone(), vote_data.into());
-            delegator_voting_power
-                .entry(delegator)
-                .or_default()
-                .insert(validator, delegator_stake);
-        }
+    let deposits = gov_api::get_proposal_deposits(storage, proposal_id)?;
+    for (depositor, amount) in deposits {
+        token::transfer(
+            storage,
+            native_token,
+            &gov_address,
+            &depositor,
+            amount,
+        )?;
     }
-
-    Ok(ProposalVotes {
-        validators_vote,
-        validator_voting_power,
-        delegators_vote,
-        delegator_voting_power,
-    })
+    Ok(())
 }
 
 fn execute_default_proposal<D, H>(
@@ -316,6 +360,256 @@ where
     }
 }
 
+/// Schedule the upgrade, unless its height has already been reached, in
+/// which case the nodes would have no chance to halt
+fn execute_upgrade_proposal<S>(
+    storage: &mut S,
+    id: u64,
+    upgrade: Upgrade,
+) -> Result<bool>
+where
+    S: StorageRead + StorageWrite,
+{
+    let current_height = storage.get_block_height()?;
+    if upgrade.height <= current_height {
+        tracing::warn!(
+            "The upgrade {} of proposal {} is scheduled at height {}, which \
+             has already been reached.",
+            upgrade.name,
+            id,
+            upgrade.height
+        );
+        return Ok(false);
+    }
+    if let Some(pending) = gov_api::get_pending_upgrade(storage)? {
+        tracing::warn!(
+            "Proposal {} replaces the pending upgrade {}.",
+            id,
+            pending
+        );
+    }
+    gov_api::write_pending_upgrade(storage, &upgrade)?;
+    tracing::info!(
+        "Upgrade {} is scheduled at height {}. Nodes without a binary \
+         including it will halt at this height.",
+        upgrade.name,
+        upgrade.height
+    );
+    Ok(true)
+}
+
+/// Apply the new values of the parameters, unless they are inconsistent with
+/// the values of the other parameters, in which case none is applied
+fn execute_parameter_change_proposal<S>(
+    storage: &mut S,
+    id: u64,
+    changes: Vec<ParameterChange>,
+) -> Result<bool>
+where
+    S: StorageRead + StorageWrite,
+{
+    let mut pos_params = read_pos_params(storage)?.owned;
+    let mut gov_params = gov_api::get_parameters(storage)?;
+    for change in &changes {
+        match change.clone() {
+            ParameterChange::BlockProposerReward(value) => {
+                pos_params.block_proposer_reward = value
+            }
+            ParameterChange::BlockVoteReward(value) => {
+                pos_params.block_vote_reward = value
+            }
+            ParameterChange::MaxInflationRate(value) => {
+                pos_params.max_inflation_rate = value
+            }
+            ParameterChange::TargetStakedRatio(value) => {
+                pos_params.target_staked_ratio = value
+            }
+            ParameterChange::DuplicateVoteMinSlashRate(value) => {
+                pos_params.duplicate_vote_min_slash_rate = value
+            }
+            ParameterChange::LightClientAttackMinSlashRate(value) => {
+                pos_params.light_client_attack_min_slash_rate = value
+            }
+            ParameterChange::CubicSlashingWindowLength(value) => {
+                pos_params.cubic_slashing_window_length = value
+            }
+            ParameterChange::ValidatorStakeThreshold(value) => {
+                pos_params.validator_stake_threshold = value
+            }
+            ParameterChange::LivenessWindowCheck(value) => {
+                pos_params.liveness_window_check = value
+            }
+            ParameterChange::LivenessThreshold(value) => {
+                pos_params.liveness_threshold = value
+            }
+            ParameterChange::RewardsGainP(value) => {
+                pos_params.rewards_gain_p = value
+            }
+            ParameterChange::RewardsGainD(value) => {
+                pos_params.rewards_gain_d = value
+            }
+            ParameterChange::MinProposalFund(value) => {
+                gov_params.min_proposal_fund = value
+            }
+            ParameterChange::MaxProposalCodeSize(value) => {
+                gov_params.max_proposal_code_size = value
+            }
+            ParameterChange::MinProposalVotingPeriod(value) => {
+                gov_params.min_proposal_voting_period = value
+            }
+            ParameterChange::MaxProposalPeriod(value) => {
+                gov_params.max_proposal_period = value
+            }
+            ParameterChange::MaxProposalContentSize(value) => {
+                gov_params.max_proposal_content_size = value
+            }
+            ParameterChange::MinProposalGraceEpochs(value) => {
+                gov_params.min_proposal_grace_epochs = value
+            }
+            ParameterChange::MinEmergencyProposalVotingPeriod(value) => {
+                gov_params.min_emergency_proposal_voting_period = value
+            }
+            ParameterChange::DefaultProposalTally(value) => {
+                gov_params.default_proposal_tally = value
+            }
+            ParameterChange::EmergencyProposalTally(value) => {
+                gov_params.emergency_proposal_tally = value
+            }
+            ParameterChange::PgfStewardProposalTally(value) => {
+                gov_params.pgf_steward_proposal_tally = value
+            }
+            ParameterChange::PgfFundingProposalTally(value) => {
+                gov_params.pgf_funding_proposal_tally = value
+            }
+            ParameterChange::PgfStewardFundingProposalTally(value) => {
+                gov_params.pgf_steward_funding_proposal_tally = value
+            }
+            ParameterChange::VetoThreshold(value) => {
+                gov_params.veto_threshold = value
+            }
+            // These are written below as they don't depend on each other
+            ParameterChange::MaxTxBytes(_)
+            | ParameterChange::MaxBlockGas(_)
+            | ParameterChange::EpochsPerYear(_)
+            | ParameterChange::MaxSignaturesPerTransaction(_)
+            | ParameterChange::FeeUnshieldingGasLimit(_)
+            | ParameterChange::FeeUnshieldingDescriptionsLimit(_)
+            | ParameterChange::MinimumGasPrice(_)
+            | ParameterChange::VpWhitelist(_)
+            | ParameterChange::TxWhitelist(_)
+            | ParameterChange::PgfInflationRate(_)
+            | ParameterChange::StewardsInflationRate(_)
+            | ParameterChange::IbcRateLimits(_)
+            | ParameterChange::Erc20Whitelist(_) => {}
+        }
+    }
+
+    let pos_errors = pos_params.validate();
+    if !pos_errors.is_empty() {
+        tracing::warn!(
+            "Parameter change proposal {} sets invalid PoS parameters: {:?}",
+            id,
+            pos_errors
+        );
+        return Ok(false);
+    }
+    let max_voting_period = gov_params
+        .min_proposal_voting_period
+        .max(gov_params.min_emergency_proposal_voting_period);
+    if max_voting_period > gov_params.max_proposal_period {
+        tracing::warn!(
+            "Parameter change proposal {} sets a minimum voting period \
+             greater than the maximum proposal period.",
+            id
+        );
+        return Ok(false);
+    }
+    // The whitelist may have changed, or the tokens may have been minted,
+    // since the proposal was submitted
+    for change in &changes {
+        if let ParameterChange::Erc20Whitelist(erc20s) = change {
+            for erc20 in erc20s {
+                if !whitelist::is_valid_whitelist_change(storage, erc20)? {
+                    tracing::warn!(
+                        "Parameter change proposal {} makes an invalid change \
+                         of the ERC20 whitelist: {}.",
+                        id,
+                        erc20
+                    );
+                    return Ok(false);
+                }
+            }
+        }
+    }
+
+    if changes.iter().any(ParameterChange::is_pos) {
+        write_pos_params(storage, &pos_params)?;
+    }
+    if changes.iter().any(ParameterChange::is_governance) {
+        gov_params.write_parameters(storage)?;
+    }
+    for change in changes {
+        match change {
+            ParameterChange::MaxTxBytes(value) => {
+                let key = params_storage::get_max_tx_bytes_key();
+                storage.write(&key, value)?
+            }
+            ParameterChange::MaxBlockGas(value) => {
+                let key = params_storage::get_max_block_gas_key();
+                storage.write(&key, value)?
+            }
+            ParameterChange::EpochsPerYear(value) => {
+                parameters::update_epochs_per_year_parameter(storage, &value)?
+            }
+            ParameterChange::MaxSignaturesPerTransaction(value) => {
+                parameters::update_max_signature_per_tx(storage, value)?
+            }
+            ParameterChange::FeeUnshieldingGasLimit(value) => {
+                let key = params_storage::get_fee_unshielding_gas_limit_key();
+                storage.write(&key, value)?
+            }
+            ParameterChange::FeeUnshieldingDescriptionsLimit(value) => {
+                let key =
+                    params_storage::get_fee_unshielding_descriptions_limit_key(
+                    );
+                storage.write(&key, value)?
+            }
+            ParameterChange::MinimumGasPrice(value) => {
+                let key = params_storage::get_gas_cost_key();
+                storage.write(&key, value)?
+            }
+            ParameterChange::VpWhitelist(value) => {
+                parameters::update_vp_whitelist_parameter(storage, value)?
+            }
+            ParameterChange::TxWhitelist(value) => {
+                parameters::update_tx_whitelist_parameter(storage, value)?
+            }
+            ParameterChange::PgfInflationRate(value) => {
+                let key = pgf_storage::get_pgf_inflation_rate_key();
+                storage.write(&key, value)?
+            }
+            ParameterChange::StewardsInflationRate(value) => {
+                let key = pgf_storage::get_steward_inflation_rate_key();
+                storage.write(&key, value)?
+            }
+            ParameterChange::IbcRateLimits(rate_limits) => {
+                for rate_limit in rate_limits {
+                    rate_limit::write_rate_limit(storage, &rate_limit)?
+                }
+            }
+            ParameterChange::Erc20Whitelist(erc20s) => {
+                for erc20 in erc20s {
+                    whitelist::write_whitelisted_erc20(storage, &erc20)?
+                }
+            }
+            // The PoS and governance parameters are written above
+            _ => {}
+        }
+    }
+
+    Ok(true)
+}
+
 fn execute_pgf_steward_proposal<S>(
     storage: &mut S,
     stewards: HashSet<AddRemove<Address>>,
@@ -341,14 +635,15 @@ where
     Ok(true)
 }
 
-fn execute_pgf_payment_proposal<S>(
-    storage: &mut S,
+fn execute_pgf_payment_proposal<D, H>(
+    storage: &mut WlStorage<D, H>,
     token: &Address,
     payments: Vec<PGFAction>,
     proposal_id: u64,
 ) -> Result<bool>
 where
-    S: StorageRead + StorageWrite,
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
 {
     for payment in payments {
         match payment {
@@ -356,49 +651,55 @@ where
                 AddRemove::Add(target) => {
                     pgf_storage::fundings_handle().insert(
                         storage,
-                        target.target.clone(),
+                        target.target(),
                         StoragePgfFunding::new(target.clone(), proposal_id),
                     )?;
                     tracing::info!(
                         "Execute ContinousPgf from proposal id {}: set {} to \
                          {}.",
                         proposal_id,
-                        target.amount.to_string_native(),
-                        target.target
+                        target.amount().to_string_native(),
+                        target
                     );
                 }
                 AddRemove::Remove(target) => {
                     pgf_storage::fundings_handle()
-                        .remove(storage, &target.target)?;
+                        .remove(storage, &target.target())?;
                     tracing::info!(
                         "Execute ContinousPgf from proposal id {}: set {} to \
                          {}.",
                         proposal_id,
-                        target.amount.to_string_native(),
-                        target.target
+                        target.amount().to_string_native(),
+                        target
                     );
                 }
             },
             PGFAction::Retro(target) => {
-                match token::transfer(
-                    storage,
-                    token,
-                    &ADDRESS,
-                    &target.target,
-                    target.amount,
-                ) {
+                let result = match &target {
+                    PGFTarget::Internal(internal) => token::transfer(
+                        storage,
+                        token,
+                        &ADDRESS,
+                        &internal.target,
+                        internal.amount,
+                    ),
+                    PGFTarget::Ibc(ibc_target) => ibc::transfer_over_ibc(
+                        storage, token, &ADDRESS, ibc_target,
+                    ),
+                };
+                match result {
                     Ok(()) => tracing::info!(
                         "Execute RetroPgf from proposal id {}: sent {} to {}.",
                         proposal_id,
-                        target.amount.to_string_native(),
-                        target.target
+                        target.amount().to_string_native(),
+                        target
                     ),
                     Err(e) => tracing::warn!(
                         "Error in RetroPgf transfer from proposal id {}, \
                          amount {} to {}: {}",
                         proposal_id,
-                        target.amount.to_string_native(),
-                        target.target,
+                        target.amount().to_string_native(),
+                        target,
                         e
                     ),
                 }
@@ -408,3 +709,99 @@ where
 
     Ok(true)
 }
+
+#[cfg(test)]
+mod test_governance {
+    use namada::core::ledger::eth_bridge::storage::whitelist::WhitelistedErc20;
+    use namada::core::ledger::eth_bridge::storage::{
+        native_erc20_key, wrapped_erc20s,
+    };
+    use namada::types::ethereum_events::testing::DAI_ERC20_ETH_ADDRESS;
+    use namada::types::ethereum_events::EthAddress;
+    use namada::types::token::{
+        minted_balance_key, Amount, DenominatedAmount,
+        NATIVE_MAX_DECIMAL_PLACES,
+    };
+
+    use super::*;
+    use crate::node::ledger::shell::test_utils;
+
+    /// Build a change of the ERC20 whitelist.
+    fn erc20_whitelist(
+        token_address: EthAddress,
+        cap: Option<(u64, u8)>,
+    ) -> Vec<ParameterChange> {
+        vec![ParameterChange::Erc20Whitelist(vec![WhitelistedErc20 {
+            token_address,
+            token_cap: cap.map(|(amount, denom)| {
+                DenominatedAmount::new(Amount::from_u64(amount), denom.into())
+            }),
+        }])]
+    }
+
+    /// Test that the execution of a proposal changing the denomination of an
+    /// ERC20 already minted in Namada, or removing the native ERC20 from the
+    /// whitelist, is rejected without changing the whitelist.
+    #[test]
+    fn test_execute_erc20_whitelist_change() {
+        let (mut shell, _recv, _, _) = test_utils::setup();
+        let storage = &mut shell.wl_storage;
+
+        let dai = Some((1_000, 18));
+        assert!(execute_parameter_change_proposal(
+            storage,
+            0,
+            erc20_whitelist(DAI_ERC20_ETH_ADDRESS, dai)
+        )
+        .unwrap());
+        let cap = |storage: &WlStorage<_, _>| {
+            whitelist::read_token_cap(storage, &DAI_ERC20_ETH_ADDRESS).unwrap()
+        };
+        let dai_cap = cap(storage);
+        assert_eq!(
+            dai_cap,
+            Some(DenominatedAmount::new(Amount::from_u64(1_000), 18.into()))
+        );
+
+        // the wrapped DAI is minted once the proposal is executed
+        let minted_key =
+            minted_balance_key(&wrapped_erc20s::token(&DAI_ERC20_ETH_ADDRESS));
+        storage.write(&minted_key, Amount::from_u64(1)).unwrap();
+        assert!(!execute_parameter_change_proposal(
+            storage,
+            1,
+            erc20_whitelist(DAI_ERC20_ETH_ADDRESS, Some((1_000, 6)))
+        )
+        .unwrap());
+        assert_eq!(cap(storage), dai_cap);
+
+        // the cap of a minted token can still change
+        assert!(execute_parameter_change_proposal(
+            storage,
+            2,
+            erc20_whitelist(DAI_ERC20_ETH_ADDRESS, Some((2_000, 18)))
+        )
+        .unwrap());
+
+        let wnam = EthAddress([1; 20]);
+        storage.write(&native_erc20_key(), wnam).unwrap();
+        assert!(!execute_parameter_change_proposal(
+            storage,
+            3,
+            erc20_whitelist(wnam, None)
+        )
+        .unwrap());
+        assert!(!execute_parameter_change_proposal(
+            storage,
+            4,
+            erc20_whitelist(wnam, Some((1_000, 18)))
+        )
+        .unwrap());
+        assert!(execute_parameter_change_proposal(
+            storage,
+            5,
+            erc20_whitelist(wnam, Some((1_000, NATIVE_MAX_DECIMAL_PLACES)))
+        )
+        .unwrap());
+    }
+}
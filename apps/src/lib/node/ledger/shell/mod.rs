@@ -13,10 +13,12 @@ pub use init_chain::InitChainValidation;
 pub mod prepare_proposal;
 pub mod process_proposal;
 pub(super) mod queries;
+mod snapshots;
 mod stats;
 #[cfg(any(test, feature = "testing"))]
 #[allow(dead_code)]
 pub mod testing;
+mod upgrade;
 pub mod utils;
 mod vote_extensions;
 
@@ -33,6 +35,7 @@ use masp_primitives::transaction::Transaction;
 use namada::core::hints;
 use namada::core::ledger::eth_bridge;
 pub use namada::core::types::transaction::ResultCode;
+use namada::ledger::events::index::index_entry;
 use namada::ledger::events::log::EventLog;
 use namada::ledger::events::Event;
 use namada::ledger::gas::{Gas, TxGasMeter};
@@ -51,7 +54,7 @@ use namada::ledger::storage::{
     EPOCH_SWITCH_BLOCKS_DELAY,
 };
 use namada::ledger::storage_api::tx::validate_tx_bytes;
-use namada::ledger::storage_api::{self, StorageRead};
+use namada::ledger::storage_api::{self, ResultExt, StorageRead};
 use namada::ledger::{parameters, pos, protocol};
 use namada::proof_of_stake::slashing::{process_slashes, slash};
 use namada::proof_of_stake::storage::read_pos_params;
@@ -359,6 +362,11 @@ where
     pub proposal_data: HashSet<u64>,
     /// Log of events emitted by `FinalizeBlock` ABCI calls.
     event_log: EventLog,
+    /// Taken from config `event_index`. When set, the events emitted by
+    /// `FinalizeBlock` ABCI calls are also persisted in the DB.
+    event_index: bool,
+    /// State sync snapshots
+    snapshots: snapshots::Snapshots,
 }
 
 /// Channels for communicating with an Ethereum oracle.
@@ -402,10 +410,12 @@ where
     ) -> Self {
         let chain_id = config.chain_id;
         let db_path = config.shell.db_dir(&chain_id);
+        let snapshots = snapshots::Snapshots::new(&config.shell, &chain_id);
         let base_dir = config.shell.base_dir;
         let mode = config.shell.tendermint_mode;
         let storage_read_past_height_limit =
             config.shell.storage_read_past_height_limit;
+        let event_index = config.shell.event_index;
         if !Path::new(&base_dir).is_dir() {
             std::fs::create_dir(&base_dir)
                 .expect("Creating directory for Namada should not fail");
@@ -527,7 +537,13 @@ where
             proposal_data: HashSet::new(),
             // TODO: config event log params
             event_log: EventLog::default(),
+            event_index,
+            snapshots,
         };
+        if let Err(reason) = shell.check_upgrade_on_startup() {
+            tracing::error!("{}", reason);
+            panic!("{}", reason);
+        }
         shell.update_eth_oracle(&Default::default());
         shell
     }
@@ -544,6 +560,31 @@ where
         &mut self.event_log
     }
 
+    /// Log the events emitted at the given height, persisting them in the
+    /// event index if it is enabled.
+    pub fn log_events(
+        &mut self,
+        height: BlockHeight,
+        events: Vec<Event>,
+    ) -> storage_api::Result<()> {
+        if self.event_index {
+            let entries: Vec<_> = events.iter().map(index_entry).collect();
+            let mut batch = D::batch();
+            self.wl_storage
+                .storage
+                .db
+                .write_indexed_events(&mut batch, height, &entries)
+                .into_storage_result()?;
+            self.wl_storage
+                .storage
+                .db
+                .exec_batch(batch)
+                .into_storage_result()?;
+        }
+        self.event_log.log_events(events);
+        Ok(())
+    }
+
     /// Iterate over the wrapper txs in order
     #[allow(dead_code)]
     fn iter_tx_queue(&mut self) -> impl Iterator<Item = &TxInQueue> {
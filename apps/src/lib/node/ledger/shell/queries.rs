@@ -24,6 +24,7 @@ where
             vp_wasm_cache: self.vp_wasm_cache.read_only(),
             tx_wasm_cache: self.tx_wasm_cache.read_only(),
             storage_read_past_height_limit: self.storage_read_past_height_limit,
+            event_index: self.event_index,
         };
 
         // Invoke the root RPC handler - returns borsh-encoded data on success
@@ -0,0 +1,414 @@
+//! Implementation of the state sync ABCI calls.
+//!
+//! Every `snapshot_interval` blocks, the shell creates a checkpoint of its DB
+//! right after the block is committed and writes a snapshot of the state at
+//! that height from it in the background. The snapshots are served to the
+//! nodes joining the network with state sync, which restore them into their
+//! empty DB.
+
+use std::fs;
+use std::thread::{self, JoinHandle};
+
+use namada::types::hash::Hash;
+
+use super::*;
+use crate::facade::tendermint::abci::types::Snapshot;
+use crate::facade::tendermint::v0_37::abci::response::ApplySnapshotChunkResult;
+use crate::node::ledger::storage::snapshot::{self, SnapshotMetadata};
+use crate::node::ledger::storage::PersistentDB;
+
+/// The state of the snapshots of the shell
+#[derive(Debug)]
+pub struct Snapshots {
+    /// The directory of the snapshots
+    dir: PathBuf,
+    /// Taken from config `snapshot_interval`. When set, a snapshot is taken
+    /// at every block height that is a multiple of it.
+    interval: Option<u64>,
+    /// Taken from config `snapshots_to_keep`
+    to_keep: u64,
+    /// The task writing the last snapshot
+    task: Option<JoinHandle<()>>,
+    /// The snapshot being restored, if any
+    restore: Option<Restore>,
+}
+
+/// A snapshot accepted for restoration
+#[derive(Debug)]
+struct Restore {
+    metadata: SnapshotMetadata,
+    /// The app hash of the snapshotted height, verified by the light client
+    app_hash: Vec<u8>,
+    /// The index of the next chunk to apply
+    next_chunk: u32,
+}
+
+impl Snapshots {
+    /// Initialize the snapshots state from the ledger config
+    pub fn new(config: &config::Shell, chain_id: &ChainId) -> Self {
+        Self {
+            dir: config.snapshots_dir(chain_id),
+            interval: config.snapshot_interval,
+            to_keep: config.snapshots_to_keep,
+            task: None,
+            restore: None,
+        }
+    }
+
+    /// The directory in which the chunks of the snapshot being restored are
+    /// received
+    fn restore_dir(&self) -> PathBuf {
+        self.dir.join("restore")
+    }
+}
+
+impl Shell {
+    /// Take a snapshot of the last committed block if its height is a
+    /// multiple of the configured interval. The snapshot is written in the
+    /// background from a checkpoint of the DB, and skipped if the previous
+    /// one is still being written.
+    pub fn take_snapshot_if_due(&mut self) {
+        let height = self.wl_storage.storage.get_last_block_height();
+        match self.snapshots.interval {
+            Some(interval) if interval > 0 && height.0 % interval == 0 => {}
+            _ => return,
+        }
+        if let Some(task) = self.snapshots.task.as_ref() {
+            if !task.is_finished() {
+                tracing::info!(
+                    "Skipping the snapshot at height {}, the previous one is \
+                     still being written",
+                    height
+                );
+                return;
+            }
+        }
+
+        let dir = self.snapshots.dir.clone();
+        let checkpoint_dir = dir.join(format!("checkpoint_{}", height));
+        let checkpoint = fs::create_dir_all(&dir)
+            .map_err(|e| e.to_string())
+            .and_then(|()| {
+                if checkpoint_dir.exists() {
+                    fs::remove_dir_all(&checkpoint_dir)
+                        .map_err(|e| e.to_string())?;
+                }
+                self.wl_storage
+                    .storage
+                    .db
+                    .checkpoint(&checkpoint_dir)
+                    .map_err(|e| e.to_string())
+            });
+        if let Err(e) = checkpoint {
+            tracing::error!(
+                "Failed to create a checkpoint of the DB at height {}: {}",
+                height,
+                e
+            );
+            return;
+        }
+
+        let chain_id = self.chain_id.clone();
+        let native_token = self.wl_storage.storage.native_token.clone();
+        let to_keep = self.snapshots.to_keep;
+        self.snapshots.task = Some(thread::spawn(move || {
+            let result = write_snapshot_from_checkpoint(
+                &dir,
+                &checkpoint_dir,
+                chain_id,
+                native_token,
+            );
+            if let Err(e) = fs::remove_dir_all(&checkpoint_dir) {
+                tracing::error!("Failed to remove a DB checkpoint: {}", e);
+            }
+            match result.and_then(|metadata| {
+                snapshot::prune_snapshots(&dir, to_keep)?;
+                Ok(metadata)
+            }) {
+                Ok(metadata) => tracing::info!(
+                    "Took a snapshot at height {} in {} chunks",
+                    metadata.height,
+                    metadata.chunk_hashes.len()
+                ),
+                Err(e) => tracing::error!(
+                    "Failed to take a snapshot at height {}: {}",
+                    height,
+                    e
+                ),
+            }
+        }));
+    }
+
+    /// List the available snapshots
+    pub fn list_snapshots(&self) -> response::ListSnapshots {
+        let snapshots = match snapshot::list_snapshots(&self.snapshots.dir) {
+            Ok(snapshots) => snapshots,
+            Err(e) => {
+                tracing::error!("Failed to list the snapshots: {}", e);
+                return Default::default();
+            }
+        };
+        response::ListSnapshots {
+            snapshots: snapshots
+                .into_iter()
+                .map(|metadata| Snapshot {
+                    height: metadata
+                        .height
+                        .0
+                        .try_into()
+                        .expect("Invalid block height"),
+                    format: snapshot::SNAPSHOT_FORMAT,
+                    chunks: metadata.chunk_hashes.len() as u32,
+                    hash: metadata.hash().0.to_vec().into(),
+                    metadata: metadata.serialize_to_vec().into(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Decide whether to restore the offered snapshot. Only snapshots of our
+    /// own format are accepted, and only when the node has no state yet.
+    pub fn offer_snapshot(
+        &mut self,
+        req: request::OfferSnapshot,
+    ) -> response::OfferSnapshot {
+        if self.wl_storage.storage.get_state().is_some() {
+            tracing::info!("Rejecting a snapshot, the node already has state");
+            return response::OfferSnapshot::Abort;
+        }
+        let offered = req.snapshot;
+        if offered.format != snapshot::SNAPSHOT_FORMAT {
+            return response::OfferSnapshot::RejectFormat;
+        }
+        let metadata = match SnapshotMetadata::try_from_slice(&offered.metadata)
+        {
+            Ok(metadata) => metadata,
+            Err(_) => return response::OfferSnapshot::Reject,
+        };
+        if metadata.height.0 != offered.height.value()
+            || metadata.chunk_hashes.len() != offered.chunks as usize
+            || metadata.hash().0.as_slice() != offered.hash.as_ref()
+        {
+            return response::OfferSnapshot::Reject;
+        }
+
+        let restore_dir = self.snapshots.restore_dir();
+        let reset = if restore_dir.exists() {
+            fs::remove_dir_all(&restore_dir)
+                .and_then(|()| fs::create_dir_all(&restore_dir))
+        } else {
+            fs::create_dir_all(&restore_dir)
+        };
+        if let Err(e) = reset {
+            tracing::error!("Failed to create the restore directory: {}", e);
+            return response::OfferSnapshot::Abort;
+        }
+        tracing::info!(
+            "Accepted a snapshot at height {} in {} chunks",
+            metadata.height,
+            metadata.chunk_hashes.len()
+        );
+        self.snapshots.restore = Some(Restore {
+            metadata,
+            app_hash: req.app_hash.as_bytes().to_vec(),
+            next_chunk: 0,
+        });
+        response::OfferSnapshot::Accept
+    }
+
+    /// Load a chunk of one of our snapshots
+    pub fn load_snapshot_chunk(
+        &self,
+        req: request::LoadSnapshotChunk,
+    ) -> response::LoadSnapshotChunk {
+        if req.format != snapshot::SNAPSHOT_FORMAT {
+            return Default::default();
+        }
+        match snapshot::load_chunk(
+            &self.snapshots.dir,
+            BlockHeight(req.height.value()),
+            req.chunk,
+        ) {
+            Ok(Some(chunk)) => response::LoadSnapshotChunk {
+                chunk: chunk.into(),
+            },
+            Ok(None) => Default::default(),
+            Err(e) => {
+                tracing::error!("Failed to load a snapshot chunk: {}", e);
+                Default::default()
+            }
+        }
+    }
+
+    /// Apply a chunk of the snapshot being restored. Once all the chunks are
+    /// received, the snapshot is restored in a separate DB and its Merkle
+    /// tree is checked against the app hash and the restored state before
+    /// the snapshot is restored in the DB of the node.
+    pub fn apply_snapshot_chunk(
+        &mut self,
+        req: request::ApplySnapshotChunk,
+    ) -> response::ApplySnapshotChunk {
+        let restore_dir = self.snapshots.restore_dir();
+        let restore = match self.snapshots.restore.as_mut() {
+            Some(restore) => restore,
+            None => {
+                return apply_chunk_response(ApplySnapshotChunkResult::Abort);
+            }
+        };
+        if req.index != restore.next_chunk {
+            return apply_chunk_response(ApplySnapshotChunkResult::Retry);
+        }
+        let expected_hash = restore.metadata.chunk_hashes[req.index as usize];
+        if Hash::sha256(&req.chunk) != expected_hash {
+            tracing::info!(
+                "Received an invalid snapshot chunk {} from {}",
+                req.index,
+                req.sender
+            );
+            return response::ApplySnapshotChunk {
+                result: ApplySnapshotChunkResult::Retry,
+                refetch_chunks: vec![req.index],
+                reject_senders: vec![req.sender],
+            };
+        }
+        if let Err(e) =
+            fs::write(restore_dir.join(req.index.to_string()), &req.chunk)
+        {
+            tracing::error!("Failed to write a snapshot chunk: {}", e);
+            return apply_chunk_response(ApplySnapshotChunkResult::Abort);
+        }
+        restore.next_chunk += 1;
+        if (restore.next_chunk as usize) < restore.metadata.chunk_hashes.len() {
+            return apply_chunk_response(ApplySnapshotChunkResult::Accept);
+        }
+
+        // All the chunks have been received
+        let restore = self
+            .snapshots
+            .restore
+            .take()
+            .expect("The snapshot being restored must be set");
+        let num_of_chunks = restore.metadata.chunk_hashes.len();
+        let result = verify_snapshot(
+            &restore,
+            &restore_dir,
+            self.chain_id.clone(),
+            self.wl_storage.storage.native_token.clone(),
+        )
+        .and_then(|()| {
+            self.wl_storage.storage.db.restore_snapshot(
+                &mut snapshot::read_chunks(&restore_dir, num_of_chunks),
+            )?;
+            Ok(self.wl_storage.storage.load_last_state()?)
+        });
+        if let Err(e) = fs::remove_dir_all(&restore_dir) {
+            tracing::error!("Failed to remove the restore directory: {}", e);
+        }
+        match result {
+            Ok(()) => {
+                tracing::info!(
+                    "Restored the snapshot at height {}",
+                    restore.metadata.height
+                );
+                self.update_eth_oracle(&Default::default());
+                apply_chunk_response(ApplySnapshotChunkResult::Accept)
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to restore the snapshot at height {}: {}",
+                    restore.metadata.height,
+                    e
+                );
+                apply_chunk_response(ApplySnapshotChunkResult::RejectSnapshot)
+            }
+        }
+    }
+}
+
+fn apply_chunk_response(
+    result: ApplySnapshotChunkResult,
+) -> response::ApplySnapshotChunk {
+    response::ApplySnapshotChunk {
+        result,
+        ..Default::default()
+    }
+}
+
+/// Write the snapshot of the last committed block of the DB checkpoint
+fn write_snapshot_from_checkpoint(
+    snapshots_dir: &Path,
+    checkpoint_dir: &Path,
+    chain_id: ChainId,
+    native_token: Address,
+) -> snapshot::Result<SnapshotMetadata> {
+    let mut storage = Storage::<PersistentDB, Sha256Hasher>::open(
+        checkpoint_dir,
+        chain_id,
+        native_token,
+        None,
+        None,
+    );
+    storage.load_last_state()?;
+    let height = storage.get_last_block_height();
+    let epoch = storage.block.epoch;
+    // The start of the epoch from which the Merkle tree is rebuilt, as in
+    // `Storage::get_merkle_tree`
+    let epoch_start_height =
+        match storage.block.pred_epochs.get_start_height_of_epoch(epoch) {
+            Some(height) if height == BlockHeight(0) => BlockHeight(1),
+            Some(height) => height,
+            None => BlockHeight(1),
+        };
+    snapshot::write_snapshot(snapshots_dir, height, |writer| {
+        storage.db.write_snapshot(
+            writer,
+            height,
+            epoch,
+            epoch_start_height,
+            storage.block.tree.stores(),
+        )
+    })
+}
+
+/// Restore the received snapshot in a separate DB and check that its state
+/// matches the app hash
+fn verify_snapshot(
+    restore: &Restore,
+    restore_dir: &Path,
+    chain_id: ChainId,
+    native_token: Address,
+) -> snapshot::Result<()> {
+    let num_of_chunks = restore.metadata.chunk_hashes.len();
+    let mut storage = Storage::<PersistentDB, Sha256Hasher>::open(
+        restore_dir.join("db"),
+        chain_id,
+        native_token,
+        None,
+        None,
+    );
+    storage.db.restore_snapshot(&mut snapshot::read_chunks(
+        restore_dir,
+        num_of_chunks,
+    ))?;
+    storage.load_last_state()?;
+
+    let height = storage.get_last_block_height();
+    if height != restore.metadata.height {
+        return Err(snapshot::Error::Invalid(format!(
+            "Expected the state at height {}, got {}",
+            restore.metadata.height, height
+        )));
+    }
+    if storage.merkle_root().0.as_slice() != restore.app_hash.as_slice() {
+        return Err(snapshot::Error::Invalid(format!(
+            "The Merkle root {} does not match the app hash",
+            storage.merkle_root()
+        )));
+    }
+    if !storage.verify_merkle_tree()? {
+        return Err(snapshot::Error::Invalid(
+            "The state does not match the Merkle tree".to_string(),
+        ));
+    }
+    Ok(())
+}
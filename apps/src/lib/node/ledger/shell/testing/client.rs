@@ -81,6 +81,9 @@ pub fn run(
                 cmds::NamadaRelayer::ValidatorSet(sub_cmd) => {
                     NamadaRelayer::ValidatorSet(sub_cmd)
                 }
+                cmds::NamadaRelayer::Relay(sub_cmd) => {
+                    NamadaRelayer::Relay(Box::new((sub_cmd, ctx)))
+                }
             };
             rt.block_on(CliApi::handle_relayer_command(
                 Some(node),
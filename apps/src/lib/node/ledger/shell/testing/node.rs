@@ -193,7 +193,8 @@ pub fn mock_services(cfg: MockServicesCfg) -> MockServicesPackage {
     let (control_sender, control_receiver) = control::channel();
     let eth_oracle_controller = eth_client.controller();
     let oracle = TestOracle::new(
-        Either::Left(eth_client),
+        Either::Left(vec![eth_client]),
+        1,
         eth_sender.clone(),
         last_processed_block_sender,
         Duration::from_millis(5),
@@ -595,6 +596,7 @@ impl<'a> Client for &'a MockNode {
             vp_wasm_cache: borrowed.vp_wasm_cache.read_only(),
             tx_wasm_cache: borrowed.tx_wasm_cache.read_only(),
             storage_read_past_height_limit: None,
+            event_index: borrowed.event_index,
         };
         if request.path == "/shell/dry_run_tx" {
             dry_run_tx(ctx, &request)
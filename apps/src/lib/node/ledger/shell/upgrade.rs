@@ -0,0 +1,142 @@
+//! Protocol upgrades scheduled by governance.
+//!
+//! An accepted upgrade proposal records the name and the height of the
+//! upgrade in governance storage. Nodes whose binary doesn't include the
+//! upgrade halt before processing the block at the upgrade height, while the
+//! binaries that include it refuse to start before that height. The pending
+//! upgrade is removed when the block at the upgrade height is finalized.
+
+use namada::core::ledger::governance::storage::proposal::Upgrade;
+use namada::core::ledger::storage_api::governance as gov_api;
+
+use super::*;
+
+/// The names of the upgrades included in this binary
+pub const SUPPORTED_UPGRADES: &[&str] = &[];
+
+impl<D, H> Shell<D, H>
+where
+    D: DB + for<'iter> DBIter<'iter> + Sync + 'static,
+    H: StorageHasher + Sync + 'static,
+{
+    /// Get the reason for which the node must halt before processing the
+    /// block at the given height, if any
+    pub fn upgrade_halt_reason(&self, height: BlockHeight) -> Option<String> {
+        let upgrade = gov_api::get_pending_upgrade(&self.wl_storage)
+            .expect("Reading the pending upgrade shouldn't fail")?;
+        halt_reason(&upgrade, height, SUPPORTED_UPGRADES)
+    }
+
+    /// Check that this binary can process the next block with regard to the
+    /// pending upgrade, if any
+    pub(super) fn check_upgrade_on_startup(
+        &self,
+    ) -> std::result::Result<(), String> {
+        let upgrade = match gov_api::get_pending_upgrade(&self.wl_storage)
+            .expect("Reading the pending upgrade shouldn't fail")
+        {
+            Some(upgrade) => upgrade,
+            None => return Ok(()),
+        };
+        let last_height = self.wl_storage.storage.get_last_block_height();
+        startup_check(&upgrade, last_height, SUPPORTED_UPGRADES)
+    }
+
+    /// Remove the pending upgrade once the block at its height is finalized
+    pub(super) fn finalize_pending_upgrade(
+        &mut self,
+        height: BlockHeight,
+    ) -> storage_api::Result<()> {
+        match gov_api::get_pending_upgrade(&self.wl_storage)? {
+            Some(upgrade) if upgrade.height <= height => {
+                tracing::info!(
+                    "Upgrade {} has been applied at height {}.",
+                    upgrade.name,
+                    height
+                );
+                gov_api::remove_pending_upgrade(&mut self.wl_storage)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A binary that doesn't include the upgrade must not process the blocks
+/// from the upgrade height on
+fn halt_reason(
+    upgrade: &Upgrade,
+    height: BlockHeight,
+    supported: &[&str],
+) -> Option<String> {
+    if height < upgrade.height || supported.contains(&upgrade.name.as_str()) {
+        return None;
+    }
+    Some(format!(
+        "Reached the height {} of the upgrade {}, halting the chain. Restart \
+         the node with a binary including the upgrade.",
+        upgrade.height, upgrade.name
+    ))
+}
+
+/// A binary that includes the upgrade must only process the blocks from the
+/// upgrade height on, and the others only the blocks before it
+fn startup_check(
+    upgrade: &Upgrade,
+    last_height: BlockHeight,
+    supported: &[&str],
+) -> std::result::Result<(), String> {
+    let next_height = last_height.next_height();
+    let is_supported = supported.contains(&upgrade.name.as_str());
+    if is_supported && next_height < upgrade.height {
+        Err(format!(
+            "This binary includes the upgrade {} scheduled at height {}, but \
+             the last committed block is at height {}. Keep running the \
+             previous binary until the node halts at the upgrade height.",
+            upgrade.name, upgrade.height, last_height
+        ))
+    } else if !is_supported && next_height >= upgrade.height {
+        Err(format!(
+            "The chain halted at height {} for the upgrade {}, which is not \
+             included in this binary. Restart the node with a binary \
+             including the upgrade.",
+            upgrade.height, upgrade.name
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upgrade() -> Upgrade {
+        Upgrade {
+            name: "v1".to_string(),
+            height: BlockHeight(10),
+        }
+    }
+
+    /// Test that only the binaries without the upgrade halt, from the
+    /// upgrade height on.
+    #[test]
+    fn test_upgrade_halt_reason() {
+        let upgrade = upgrade();
+        assert!(halt_reason(&upgrade, BlockHeight(9), &[]).is_none());
+        assert!(halt_reason(&upgrade, BlockHeight(10), &[]).is_some());
+        assert!(halt_reason(&upgrade, BlockHeight(11), &[]).is_some());
+        assert!(halt_reason(&upgrade, BlockHeight(10), &["v1"]).is_none());
+        assert!(halt_reason(&upgrade, BlockHeight(10), &["v0"]).is_some());
+    }
+
+    /// Test that the binaries including the upgrade only start once the
+    /// chain reached the upgrade height, and the others only before it.
+    #[test]
+    fn test_upgrade_startup_check() {
+        let upgrade = upgrade();
+        assert!(startup_check(&upgrade, BlockHeight(8), &[]).is_ok());
+        assert!(startup_check(&upgrade, BlockHeight(9), &[]).is_err());
+        assert!(startup_check(&upgrade, BlockHeight(8), &["v1"]).is_err());
+        assert!(startup_check(&upgrade, BlockHeight(9), &["v1"]).is_ok());
+    }
+}
@@ -92,10 +92,29 @@ impl AbcippShim {
         hash_tx(bytes.as_slice())
     }
 
+    /// Get the reason for which the node must halt before processing the
+    /// given request because of a pending upgrade, if any
+    fn upgrade_halt_reason(&self, req: &Req) -> Option<String> {
+        let height = match req {
+            Req::PrepareProposal(req) => req.height,
+            Req::ProcessProposal(req) => req.height,
+            Req::BeginBlock(req) => req.header.height,
+            _ => return None,
+        };
+        self.service.upgrade_halt_reason(BlockHeight(height.value()))
+    }
+
     /// Run the shell's blocking loop that receives messages from the
     /// [`AbciService`].
     pub fn run(mut self) {
         while let Ok((req, resp_sender)) = self.shell_recv.recv() {
+            if let Some(reason) = self.upgrade_halt_reason(&req) {
+                tracing::info!("{}", reason);
+                if resp_sender.send(Err(BoxError::from(reason))).is_err() {
+                    tracing::info!("ABCI response channel is closed")
+                }
+                continue;
+            }
             let resp = match req {
                 Req::ProcessProposal(proposal) => self
                     .service
@@ -2,6 +2,7 @@
 //! state in DB.
 
 mod rocksdb;
+pub mod snapshot;
 
 use std::fmt;
 
@@ -575,6 +576,75 @@ mod tests {
         Ok(())
     }
 
+    /// Test that a node restored from a state sync snapshot has the same
+    /// state as the snapshotted node and can keep on committing blocks.
+    #[test]
+    fn test_snapshot_restore() {
+        let db_path =
+            TempDir::new().expect("Unable to create a temporary DB directory");
+        let mut storage = PersistentStorage::open(
+            db_path.path(),
+            ChainId::default(),
+            address::nam(),
+            None,
+            None,
+        );
+        // Epoch 0 starts at height 1, the snapshot is taken at height 3
+        storage.block.pred_epochs.new_epoch(BlockHeight(1));
+        for height in 1..=3_u64 {
+            storage
+                .begin_block(BlockHash::default(), BlockHeight(height))
+                .expect("begin_block failed");
+            let key = ibc_key(format!("key{}", height)).unwrap();
+            storage
+                .write(&key, types::encode(&height))
+                .expect("write failed");
+            storage.commit_block(PersistentStorage::batch()).unwrap();
+        }
+
+        let mut chunk = vec![];
+        storage
+            .db
+            .write_snapshot(
+                &mut chunk,
+                BlockHeight(3),
+                storage.block.epoch,
+                BlockHeight(1),
+                storage.block.tree.stores(),
+            )
+            .expect("write_snapshot failed");
+
+        let restored_path =
+            TempDir::new().expect("Unable to create a temporary DB directory");
+        let mut restored = PersistentStorage::open(
+            restored_path.path(),
+            ChainId::default(),
+            address::nam(),
+            None,
+            None,
+        );
+        restored
+            .db
+            .restore_snapshot(&mut chunk.as_slice())
+            .expect("restore_snapshot failed");
+        restored.load_last_state().expect("load_last_state failed");
+        assert_eq!(restored.get_last_block_height(), BlockHeight(3));
+        assert_eq!(restored.merkle_root(), storage.merkle_root());
+        assert!(restored.verify_merkle_tree().unwrap());
+
+        // The tree is rebuilt from the diffs of the blocks after the snapshot
+        for storage in [&mut storage, &mut restored] {
+            storage
+                .begin_block(BlockHash::default(), BlockHeight(4))
+                .expect("begin_block failed");
+            let key = ibc_key("key4").unwrap();
+            storage.write(&key, types::encode(&4_u64)).unwrap();
+            storage.commit_block(PersistentStorage::batch()).unwrap();
+        }
+        let tree = restored.get_merkle_tree(BlockHeight(4), None).unwrap();
+        assert_eq!(tree.root(), storage.merkle_root());
+    }
+
     /// Test the restore of the merkle tree
     #[test]
     fn test_prune_merkle_tree_stores() {
@@ -37,9 +37,12 @@
 //! - `replay_protection`: hashes of processed tx
 //!     - `all`: the hashes included up to the last block
 //!     - `last`: the hashes included in the last block
+//! - `events`: the event index, if enabled
+//!     - `all/{h}/{i}`: the `i`-th event emitted at height `h`
+//!     - `tag/{tag}/{h}/{i}`: reference to the same event, for each of its tags
 
 use std::fs::File;
-use std::io::{BufWriter, Write};
+use std::io::{BufWriter, Read, Write};
 use std::path::Path;
 use std::str::FromStr;
 use std::sync::Mutex;
@@ -52,15 +55,15 @@ use namada::core::ledger::masp_conversions::ConversionState;
 use namada::core::types::ethereum_structs;
 use namada::eth_bridge::storage::proof::BridgePoolRootProof;
 use namada::ledger::eth_bridge::storage::bridge_pool;
-use namada::ledger::replay_protection;
 use namada::ledger::storage::merkle_tree::{
-    base_tree_key_prefix, subtree_key_prefix,
+    base_tree_key_prefix, subtree_key_prefix, MerkleTreeStoresWrite,
 };
 use namada::ledger::storage::types::PrefixIterator;
 use namada::ledger::storage::{
     types, BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, Error,
     MerkleTreeStoresRead, Result, StoreType, DB,
 };
+use namada::ledger::{event_index, replay_protection};
 use namada::types::ethereum_events::Uint;
 use namada::types::internal::TxQueue;
 use namada::types::storage::{
@@ -69,11 +72,13 @@ use namada::types::storage::{
 };
 use namada::types::time::DateTimeUtc;
 use rayon::prelude::*;
+use rocksdb::checkpoint::Checkpoint;
 use rocksdb::{
     BlockBasedOptions, ColumnFamily, ColumnFamilyDescriptor, Direction,
     FlushOptions, IteratorMode, Options, ReadOptions, WriteBatch,
 };
 
+use super::snapshot;
 use crate::config::utils::num_of_threads;
 
 // TODO the DB schema will probably need some kind of versioning
@@ -88,6 +93,10 @@ const DIFFS_CF: &str = "diffs";
 const STATE_CF: &str = "state";
 const BLOCK_CF: &str = "block";
 const REPLAY_PROTECTION_CF: &str = "replay_protection";
+const EVENTS_CF: &str = "events";
+
+/// The number of snapshot entries restored in a single write batch
+const RESTORE_BATCH_LEN: usize = 10_000;
 
 /// RocksDB handle
 #[derive(Debug)]
@@ -190,6 +199,14 @@ pub fn open(
         replay_protection_cf_opts,
     ));
 
+    // for the event index (append-only)
+    let mut events_cf_opts = Options::default();
+    events_cf_opts.set_compression_type(rocksdb::DBCompressionType::Zstd);
+    events_cf_opts.set_compression_options(0, 0, 0, 1024 * 1024);
+    events_cf_opts.set_compaction_style(rocksdb::DBCompactionStyle::Universal);
+    events_cf_opts.set_block_based_table_factory(&table_opts);
+    cfs.push(ColumnFamilyDescriptor::new(EVENTS_CF, events_cf_opts));
+
     rocksdb::DB::open_cf_descriptors(&db_opts, path, cfs)
         .map(RocksDB)
         .map_err(|e| Error::DBError(e.into_string()))
@@ -433,6 +450,133 @@ impl RocksDB {
         buf.flush().expect("Unable to write to output file");
     }
 
+    /// Create a checkpoint of the DB in the given directory, which must not
+    /// exist. The files of the checkpoint are hard links to those of the DB
+    /// when they are on the same filesystem.
+    pub fn checkpoint(&self, path: impl AsRef<Path>) -> Result<()> {
+        Checkpoint::new(&self.0)
+            .and_then(|checkpoint| checkpoint.create_checkpoint(path))
+            .map_err(|e| Error::DBError(e.into_string()))
+    }
+
+    /// Write the entries of a state sync snapshot of the last committed
+    /// block, given its Merkle tree stores. The DB only holds the stores of
+    /// the subtrees from the start of the epoch, so these are replaced by the
+    /// given ones. The given base tree store is also written as the one of
+    /// the start of the epoch, from which the tree is rebuilt with the diffs
+    /// of the following blocks.
+    pub fn write_snapshot(
+        &self,
+        writer: &mut dyn Write,
+        height: BlockHeight,
+        epoch: Epoch,
+        epoch_start_height: BlockHeight,
+        merkle_tree_stores: MerkleTreeStoresWrite,
+    ) -> snapshot::Result<()> {
+        for cf_name in [STATE_CF, SUBSPACE_CF, REPLAY_PROTECTION_CF] {
+            let cf = self.get_column_family(cf_name)?;
+            for entry in self.0.iterator_cf(cf, IteratorMode::Start) {
+                let (key, value) =
+                    entry.map_err(|e| Error::DBError(e.into_string()))?;
+                snapshot::write_entry(writer, cf_name, &key, &value)?;
+            }
+        }
+
+        // The block data at the height, except for its Merkle tree stores
+        let block_cf = self.get_column_family(BLOCK_CF)?;
+        let prefix = format!("{}/", height.raw());
+        let tree_prefix = format!("{}tree/", prefix);
+        let mut read_opts = ReadOptions::default();
+        read_opts.set_total_order_seek(false);
+        read_opts.set_iterate_upper_bound(format!(
+            "{}/",
+            height.next_height().raw()
+        ));
+        for entry in self.0.iterator_cf_opt(
+            block_cf,
+            read_opts,
+            IteratorMode::From(prefix.as_bytes(), Direction::Forward),
+        ) {
+            let (key, value) =
+                entry.map_err(|e| Error::DBError(e.into_string()))?;
+            if !key.starts_with(tree_prefix.as_bytes()) {
+                snapshot::write_entry(writer, BLOCK_CF, &key, &value)?;
+            }
+        }
+        let results_path = format!("results/{}", height.raw());
+        if let Some(results) = self
+            .0
+            .get_cf(block_cf, &results_path)
+            .map_err(|e| Error::DBError(e.into_string()))?
+        {
+            snapshot::write_entry(
+                writer,
+                BLOCK_CF,
+                results_path.as_bytes(),
+                &results,
+            )?;
+        }
+
+        // The Merkle tree stores at the height
+        for st in StoreType::iter() {
+            let key_prefixes = if *st == StoreType::Base {
+                let mut key_prefixes = vec![base_tree_key_prefix(height)];
+                if epoch_start_height != height {
+                    key_prefixes.push(base_tree_key_prefix(epoch_start_height));
+                }
+                key_prefixes
+            } else {
+                vec![subtree_key_prefix(st, epoch)]
+            };
+            let root = types::encode(merkle_tree_stores.root(st));
+            let store = merkle_tree_stores.store(st).encode();
+            for key_prefix in key_prefixes {
+                let root_key =
+                    key_prefix.clone().with_segment("root".to_owned());
+                snapshot::write_entry(
+                    writer,
+                    BLOCK_CF,
+                    root_key.to_string().as_bytes(),
+                    &root,
+                )?;
+                let store_key = key_prefix.with_segment("store".to_owned());
+                snapshot::write_entry(
+                    writer,
+                    BLOCK_CF,
+                    store_key.to_string().as_bytes(),
+                    &store,
+                )?;
+            }
+        }
+
+        snapshot::write_end(writer)
+    }
+
+    /// Write the entries of a state sync snapshot to the DB
+    pub fn restore_snapshot(
+        &mut self,
+        reader: &mut dyn Read,
+    ) -> snapshot::Result<()> {
+        let mut batch = WriteBatch::default();
+        while let Some(entry) = snapshot::read_entry(reader)? {
+            if ![STATE_CF, SUBSPACE_CF, REPLAY_PROTECTION_CF, BLOCK_CF]
+                .contains(&entry.column.as_str())
+            {
+                return Err(snapshot::Error::Invalid(format!(
+                    "Unexpected column family {}",
+                    entry.column
+                )));
+            }
+            let cf = self.get_column_family(&entry.column)?;
+            batch.put_cf(cf, entry.key, entry.value);
+            if batch.len() >= RESTORE_BATCH_LEN {
+                self.exec_batch(std::mem::take(&mut batch))?;
+            }
+        }
+        self.exec_batch(batch)?;
+        Ok(self.flush(true)?)
+    }
+
     /// Rollback to previous block. Given the inner working of tendermint
     /// rollback and of the key structure of Namada, calling rollback more than
     /// once without restarting the chain results in a single rollback.
@@ -1498,6 +1642,76 @@ impl DB for RocksDB {
 
         Ok(())
     }
+
+    fn write_indexed_events(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        events: &[(Vec<u8>, Vec<String>)],
+    ) -> Result<()> {
+        let events_cf = self.get_column_family(EVENTS_CF)?;
+
+        for (index, (event, tags)) in (0..).zip(events) {
+            for tag in tags {
+                batch.0.put_cf(
+                    events_cf,
+                    event_index::tag_key(tag, height, index),
+                    vec![],
+                );
+            }
+            batch.0.put_cf(
+                events_cf,
+                event_index::event_key(height, index),
+                event,
+            );
+        }
+
+        Ok(())
+    }
+
+    fn read_indexed_events(
+        &self,
+        tag: Option<&str>,
+        from: BlockHeight,
+        to: BlockHeight,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<(BlockHeight, Vec<u8>)>> {
+        let events_cf = self.get_column_family(EVENTS_CF)?;
+        let prefix = tag
+            .map(event_index::tag_prefix)
+            .unwrap_or_else(event_index::all_prefix);
+        let lower_bound = event_index::height_lower_bound(&prefix, from);
+        let iter = self.0.iterator_cf_opt(
+            events_cf,
+            make_iter_read_opts(Some(prefix)),
+            IteratorMode::From(lower_bound.as_bytes(), Direction::Forward),
+        );
+
+        let mut events = vec![];
+        for result in iter.skip(offset as usize) {
+            let (key, value) =
+                result.map_err(|e| Error::DBError(e.into_string()))?;
+            let key = String::from_utf8(key.to_vec())
+                .map_err(|e| Error::DBError(e.to_string()))?;
+            let (height, index) = event_index::parse_position(&key)
+                .ok_or_else(|| Error::UnknownKey { key: key.clone() })?;
+            if height > to || events.len() as u64 >= limit {
+                break;
+            }
+            let event = if tag.is_some() {
+                let event_key = event_index::event_key(height, index);
+                self.0
+                    .get_cf(events_cf, event_key.as_bytes())
+                    .map_err(|e| Error::DBError(e.into_string()))?
+                    .ok_or(Error::UnknownKey { key: event_key })?
+            } else {
+                value.to_vec()
+            };
+            events.push((height, event));
+        }
+        Ok(events)
+    }
 }
 
 impl<'iter> DBIter<'iter> for RocksDB {
@@ -2033,6 +2247,83 @@ mod test {
         assert_eq!(conversion_state, types::encode(&conversion_state_0));
     }
 
+    /// Test the height range, the pagination and the tag filter of the event
+    /// index.
+    #[test]
+    fn test_read_indexed_events() {
+        let dir = tempdir().unwrap();
+        let mut db = open(dir.path(), None).unwrap();
+
+        let tagged = |tags: &[&str], event: Vec<u8>| {
+            (
+                event,
+                tags.iter().map(|tag| tag.to_string()).collect::<Vec<_>>(),
+            )
+        };
+        let blocks = [
+            vec![
+                tagged(&["type:applied", "tx:x"], vec![1, 0]),
+                tagged(&["type:applied"], vec![1, 1]),
+            ],
+            vec![
+                tagged(&["type:applied"], vec![2, 0]),
+                tagged(&["tx:x"], vec![2, 1]),
+            ],
+            vec![tagged(&["type:applied", "tx:x"], vec![3, 0])],
+        ];
+        let mut batch = RocksDB::batch();
+        for (height, events) in (1..).zip(&blocks) {
+            db.write_indexed_events(&mut batch, BlockHeight(height), events)
+                .unwrap();
+        }
+        db.exec_batch(batch.0).unwrap();
+
+        let read = |tag, from, to, offset, limit| {
+            db.read_indexed_events(
+                tag,
+                BlockHeight(from),
+                BlockHeight(to),
+                offset,
+                limit,
+            )
+            .unwrap()
+        };
+        let event = |height, index: u8| {
+            (BlockHeight(height), vec![height as u8, index])
+        };
+
+        // all the events, in order of emission
+        assert_eq!(
+            read(None, 0, u64::MAX, 0, 100),
+            vec![
+                event(1, 0),
+                event(1, 1),
+                event(2, 0),
+                event(2, 1),
+                event(3, 0)
+            ]
+        );
+        // the range of heights is inclusive
+        assert_eq!(read(None, 2, 2, 0, 100), vec![event(2, 0), event(2, 1)]);
+        assert!(read(None, 4, u64::MAX, 0, 100).is_empty());
+        // pagination
+        assert_eq!(read(None, 2, 3, 1, 2), vec![event(2, 1), event(3, 0)]);
+        assert_eq!(read(None, 0, u64::MAX, 4, 100), vec![event(3, 0)]);
+        assert!(read(None, 0, u64::MAX, 5, 100).is_empty());
+        assert!(read(None, 0, u64::MAX, 0, 0).is_empty());
+        // tag filter
+        assert_eq!(
+            read(Some("tx:x"), 0, u64::MAX, 0, 100),
+            vec![event(1, 0), event(2, 1), event(3, 0)]
+        );
+        assert_eq!(read(Some("tx:x"), 2, 3, 1, 1), vec![event(3, 0)]);
+        assert_eq!(
+            read(Some("type:applied"), 1, 1, 0, 100),
+            vec![event(1, 0), event(1, 1)]
+        );
+        assert!(read(Some("tx:y"), 0, u64::MAX, 0, 100).is_empty());
+    }
+
     /// A test helper to write a block
     fn add_block_to_batch(
         db: &RocksDB,
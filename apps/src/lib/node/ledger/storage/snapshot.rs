@@ -0,0 +1,328 @@
+//! State sync snapshots.
+//!
+//! A snapshot of the state at some height holds the entries of the DB that a
+//! node needs to resume from that height: the whole `subspace`, `state` and
+//! `replay_protection` column families, and the block data and the Merkle
+//! tree stores at that height. The entries are encoded one after another,
+//! compressed with gzip and split into chunks of at most [`CHUNK_SIZE`]
+//! bytes.
+//!
+//! The snapshots directory holds:
+//! - `{h}`: the snapshot at height `h`
+//!   - `{i}`: the `i`-th chunk
+//!   - `metadata`: the hashes of the chunks, written last such that only
+//!     complete snapshots are listed
+
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use borsh_ext::BorshSerializeExt;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use namada::ledger::storage;
+use namada::types::hash::Hash;
+use namada::types::storage::BlockHeight;
+use thiserror::Error;
+
+/// The version of the format of the snapshots
+pub const SNAPSHOT_FORMAT: u32 = 1;
+
+/// The maximum size of a chunk of a snapshot, below the limit of CometBFT
+pub const CHUNK_SIZE: usize = 10 * 1024 * 1024;
+
+const METADATA_FILE: &str = "metadata";
+
+#[allow(missing_docs)]
+#[derive(Error, Debug)]
+pub enum Error {
+    #[error("Snapshot I/O error: {0}")]
+    Io(#[from] io::Error),
+    #[error("Snapshot storage error: {0}")]
+    Storage(#[from] storage::Error),
+    #[error("Invalid snapshot: {0}")]
+    Invalid(String),
+}
+
+/// Snapshot result
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// An entry of the DB in a snapshot
+#[derive(Debug, BorshSerialize, BorshDeserialize)]
+pub struct SnapshotEntry {
+    /// The name of the column family of the entry
+    pub column: String,
+    /// The raw key
+    pub key: Vec<u8>,
+    /// The raw value
+    pub value: Vec<u8>,
+}
+
+/// The metadata of a snapshot, exchanged with the other nodes along with it
+#[derive(Clone, Debug, PartialEq, Eq, BorshSerialize, BorshDeserialize)]
+pub struct SnapshotMetadata {
+    /// The height of the snapshotted state
+    pub height: BlockHeight,
+    /// The hashes of the chunks of the snapshot
+    pub chunk_hashes: Vec<Hash>,
+}
+
+impl SnapshotMetadata {
+    /// Get the hash of the snapshot, committing to all of its chunks
+    pub fn hash(&self) -> Hash {
+        Hash::sha256(self.serialize_to_vec())
+    }
+}
+
+/// Write the snapshot at the given height with the entries written by the
+/// given function, replacing any previous snapshot at the same height.
+pub fn write_snapshot(
+    snapshots_dir: &Path,
+    height: BlockHeight,
+    write_entries: impl FnOnce(&mut dyn Write) -> Result<()>,
+) -> Result<SnapshotMetadata> {
+    let dir = snapshot_dir(snapshots_dir, height);
+    if dir.exists() {
+        fs::remove_dir_all(&dir)?;
+    }
+    fs::create_dir_all(&dir)?;
+
+    let mut encoder = GzEncoder::new(
+        ChunkWriter {
+            dir: dir.clone(),
+            chunk: Vec::with_capacity(CHUNK_SIZE),
+            chunk_hashes: vec![],
+        },
+        Compression::default(),
+    );
+    write_entries(&mut encoder)?;
+    let chunk_hashes = encoder.finish()?.finish()?;
+
+    let metadata = SnapshotMetadata {
+        height,
+        chunk_hashes,
+    };
+    fs::write(dir.join(METADATA_FILE), metadata.serialize_to_vec())?;
+    Ok(metadata)
+}
+
+/// Write an entry of a snapshot
+pub fn write_entry<W: Write + ?Sized>(
+    mut writer: &mut W,
+    column: &str,
+    key: &[u8],
+    value: &[u8],
+) -> Result<()> {
+    Some(SnapshotEntry {
+        column: column.to_string(),
+        key: key.to_vec(),
+        value: value.to_vec(),
+    })
+    .serialize(&mut writer)
+    .map_err(Error::Io)
+}
+
+/// Mark the end of the entries of a snapshot
+pub fn write_end<W: Write + ?Sized>(mut writer: &mut W) -> Result<()> {
+    None::<SnapshotEntry>
+        .serialize(&mut writer)
+        .map_err(Error::Io)
+}
+
+/// Read the next entry of a snapshot, if any
+pub fn read_entry<R: Read + ?Sized>(
+    mut reader: &mut R,
+) -> Result<Option<SnapshotEntry>> {
+    Option::<SnapshotEntry>::deserialize_reader(&mut reader).map_err(Error::Io)
+}
+
+/// List the metadata of the complete snapshots, by increasing height
+pub fn list_snapshots(snapshots_dir: &Path) -> Result<Vec<SnapshotMetadata>> {
+    let mut snapshots = vec![];
+    if !snapshots_dir.is_dir() {
+        return Ok(snapshots);
+    }
+    for entry in fs::read_dir(snapshots_dir)? {
+        let path = entry?.path();
+        let is_snapshot = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name.parse::<u64>().is_ok())
+            .unwrap_or_default();
+        let metadata_path = path.join(METADATA_FILE);
+        if is_snapshot && metadata_path.is_file() {
+            let bytes = fs::read(metadata_path)?;
+            snapshots.push(
+                SnapshotMetadata::try_from_slice(&bytes).map_err(Error::Io)?,
+            );
+        }
+    }
+    snapshots.sort_by_key(|metadata| metadata.height);
+    Ok(snapshots)
+}
+
+/// Load a chunk of the snapshot at the given height, if it exists
+pub fn load_chunk(
+    snapshots_dir: &Path,
+    height: BlockHeight,
+    index: u32,
+) -> Result<Option<Vec<u8>>> {
+    let path = snapshot_dir(snapshots_dir, height).join(index.to_string());
+    if path.is_file() {
+        Ok(Some(fs::read(path)?))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Remove all the snapshots but the given number of most recent ones
+pub fn prune_snapshots(snapshots_dir: &Path, to_keep: u64) -> Result<()> {
+    let snapshots = list_snapshots(snapshots_dir)?;
+    let to_remove = snapshots.len().saturating_sub(to_keep as usize);
+    for metadata in &snapshots[..to_remove] {
+        fs::remove_dir_all(snapshot_dir(snapshots_dir, metadata.height))?;
+    }
+    Ok(())
+}
+
+/// Read the entries of a snapshot from the chunk files in the given
+/// directory, named by their index
+pub fn read_chunks(
+    dir: &Path,
+    num_of_chunks: usize,
+) -> GzDecoder<ChunksReader> {
+    GzDecoder::new(ChunksReader {
+        paths: (0..num_of_chunks)
+            .rev()
+            .map(|index| dir.join(index.to_string()))
+            .collect(),
+        current: None,
+    })
+}
+
+fn snapshot_dir(snapshots_dir: &Path, height: BlockHeight) -> PathBuf {
+    snapshots_dir.join(height.0.to_string())
+}
+
+/// A writer splitting its input into chunk files, named by their index
+struct ChunkWriter {
+    dir: PathBuf,
+    chunk: Vec<u8>,
+    chunk_hashes: Vec<Hash>,
+}
+
+impl ChunkWriter {
+    fn write_chunk(&mut self) -> io::Result<()> {
+        let path = self.dir.join(self.chunk_hashes.len().to_string());
+        fs::write(path, &self.chunk)?;
+        self.chunk_hashes.push(Hash::sha256(&self.chunk));
+        self.chunk.clear();
+        Ok(())
+    }
+
+    /// Write the last chunk and return the hashes of all the chunks
+    fn finish(mut self) -> io::Result<Vec<Hash>> {
+        if !self.chunk.is_empty() || self.chunk_hashes.is_empty() {
+            self.write_chunk()?;
+        }
+        Ok(self.chunk_hashes)
+    }
+}
+
+impl Write for ChunkWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let len = buf.len().min(CHUNK_SIZE - self.chunk.len());
+        self.chunk.extend_from_slice(&buf[..len]);
+        if self.chunk.len() == CHUNK_SIZE {
+            self.write_chunk()?;
+        }
+        Ok(len)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A reader of the concatenation of chunk files
+pub struct ChunksReader {
+    /// The paths of the chunks left to open, in reverse order
+    paths: Vec<PathBuf>,
+    current: Option<File>,
+}
+
+impl Read for ChunksReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        loop {
+            if let Some(file) = self.current.as_mut() {
+                let len = file.read(buf)?;
+                if len > 0 || buf.is_empty() {
+                    return Ok(len);
+                }
+            }
+            match self.paths.pop() {
+                Some(path) => self.current = Some(File::open(path)?),
+                None => return Ok(0),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    /// Test that the entries of a snapshot are read back from its chunks and
+    /// that only the most recent snapshots are kept.
+    #[test]
+    fn test_snapshot_chunks() {
+        let dir = TempDir::new().unwrap();
+        // Incompressible values, such that the snapshot spans several chunks
+        let values: Vec<Vec<u8>> = (0..3)
+            .map(|_| (0..CHUNK_SIZE).map(|_| rand::random::<u8>()).collect())
+            .collect();
+        let metadata = write_snapshot(dir.path(), BlockHeight(10), |writer| {
+            for (i, value) in values.iter().enumerate() {
+                write_entry(
+                    writer,
+                    "subspace",
+                    i.to_string().as_bytes(),
+                    value,
+                )?;
+            }
+            write_end(writer)
+        })
+        .unwrap();
+        assert!(metadata.chunk_hashes.len() > 3);
+
+        let chunks_dir = dir.path().join("10");
+        for (index, hash) in metadata.chunk_hashes.iter().enumerate() {
+            let chunk = load_chunk(dir.path(), BlockHeight(10), index as u32)
+                .unwrap()
+                .unwrap();
+            assert_eq!(Hash::sha256(chunk), *hash);
+        }
+        let mut reader = read_chunks(&chunks_dir, metadata.chunk_hashes.len());
+        for (i, value) in values.iter().enumerate() {
+            let entry = read_entry(&mut reader).unwrap().unwrap();
+            assert_eq!(entry.key, i.to_string().as_bytes());
+            assert_eq!(&entry.value, value);
+        }
+        assert!(read_entry(&mut reader).unwrap().is_none());
+
+        for height in [20, 30] {
+            write_snapshot(dir.path(), BlockHeight(height), write_end).unwrap();
+        }
+        prune_snapshots(dir.path(), 2).unwrap();
+        let heights: Vec<_> = list_snapshots(dir.path())
+            .unwrap()
+            .into_iter()
+            .map(|metadata| metadata.height)
+            .collect();
+        assert_eq!(heights, vec![BlockHeight(20), BlockHeight(30)]);
+    }
+}
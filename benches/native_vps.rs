@@ -135,6 +135,7 @@ fn governance(c: &mut Criterion) {
                         voting_start_epoch,
                         voting_end_epoch: voting_start_epoch + 3_u64,
                         grace_epoch: voting_start_epoch + 9_u64,
+                        deposit: Amount::native_whole(500),
                     },
                     None,
                     Some(vec![content_section]),
@@ -189,6 +190,7 @@ fn governance(c: &mut Criterion) {
                         voting_start_epoch,
                         voting_end_epoch: voting_start_epoch + 3_u64,
                         grace_epoch: voting_start_epoch + 9_u64,
+                        deposit: Amount::native_whole(500),
                     },
                     None,
                     Some(vec![content_section, wasm_code_section]),
@@ -387,6 +387,7 @@ fn update_account(c: &mut Criterion) {
         )),
         public_keys: vec![defaults::albert_keypair().ref_to()],
         threshold: None,
+        key_options: vec![],
     };
     let vp = shell.generate_tx(
         TX_UPDATE_ACCOUNT_WASM,
@@ -471,6 +472,7 @@ fn init_proposal(c: &mut Criterion) {
                                     voting_start_epoch: 12.into(),
                                     voting_end_epoch: 15.into(),
                                     grace_epoch: 18.into(),
+                                    deposit: Amount::native_whole(500),
                                 },
                                 None,
                                 Some(vec![content_section]),
@@ -523,6 +525,7 @@ fn init_proposal(c: &mut Criterion) {
                                     voting_start_epoch: 12.into(),
                                     voting_end_epoch: 15.into(),
                                     grace_epoch: 18.into(),
+                                    deposit: Amount::native_whole(500),
                                 },
                                 None,
                                 Some(vec![content_section, wasm_code_section]),
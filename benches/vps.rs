@@ -86,6 +86,7 @@ fn vp_user(c: &mut Criterion) {
         )),
         public_keys: vec![defaults::albert_keypair().to_public()],
         threshold: None,
+        key_options: vec![],
     };
     let vp = shell.generate_tx(
         TX_UPDATE_ACCOUNT_WASM,
@@ -372,6 +373,7 @@ fn vp_validator(c: &mut Criterion) {
         )),
         public_keys: vec![defaults::validator_account_keypair().to_public()],
         threshold: None,
+        key_options: vec![],
     };
     let vp = shell.generate_tx(
         TX_UPDATE_ACCOUNT_WASM,
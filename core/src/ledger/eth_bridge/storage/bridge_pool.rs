@@ -32,6 +32,8 @@ pub const BRIDGE_POOL_ADDRESS: Address =
 struct Segments {
     signed_root: &'static str,
     bridge_pool_nonce: &'static str,
+    transfer_expiry: &'static str,
+    transfer_fee_bump: &'static str,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -76,6 +78,72 @@ pub fn get_nonce_key() -> Key {
     }
 }
 
+/// Get the storage key prefix of the expiries of the transfers in the pool
+pub fn get_expiry_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(BRIDGE_POOL_ADDRESS),
+            DbKeySeg::StringSeg(Segments::VALUES.transfer_expiry.into()),
+        ],
+    }
+}
+
+/// Get the storage key for the epoch from which a transfer in the pool
+/// is refunded by the protocol
+pub fn get_expiry_key(hash: &KeccakHash) -> Key {
+    get_expiry_prefix()
+        .push(hash)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if a key is for the expiry of a transfer in the pool, returning
+/// the hash of the transfer
+pub fn is_expiry_key(key: &Key) -> Option<KeccakHash> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(hash)]
+            if addr == &BRIDGE_POOL_ADDRESS
+                && prefix == Segments::VALUES.transfer_expiry =>
+        {
+            KeccakHash::try_from(hash.as_str()).ok()
+        }
+        _ => None,
+    }
+}
+
+/// Get the storage key prefix of the fee bumps of the transfers in the pool
+pub fn get_fee_bump_prefix() -> Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(BRIDGE_POOL_ADDRESS),
+            DbKeySeg::StringSeg(Segments::VALUES.transfer_fee_bump.into()),
+        ],
+    }
+}
+
+/// Get the storage key for the gas fees added on top of the [`GasFee`] of
+/// a transfer in the pool, which keep the hash of the transfer unchanged
+///
+/// [`GasFee`]: crate::types::eth_bridge_pool::GasFee
+pub fn get_fee_bump_key(hash: &KeccakHash) -> Key {
+    get_fee_bump_prefix()
+        .push(hash)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Check if a key is for the fee bump of a transfer in the pool, returning
+/// the hash of the transfer
+pub fn is_fee_bump_key(key: &Key) -> Option<KeccakHash> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(hash)]
+            if addr == &BRIDGE_POOL_ADDRESS
+                && prefix == Segments::VALUES.transfer_fee_bump =>
+        {
+            KeccakHash::try_from(hash.as_str()).ok()
+        }
+        _ => None,
+    }
+}
+
 /// Check if a key belongs to the bridge pools sub-storage
 pub fn is_bridge_pool_key(key: &Key) -> bool {
     matches!(&key.segments[0], DbKeySeg::AddressSeg(addr) if addr == &BRIDGE_POOL_ADDRESS)
@@ -691,6 +759,33 @@ mod test_bridge_pool_tree {
         assert!(BridgePoolTree::parse_key(&key).is_err());
     }
 
+    /// Test that the expiry keys of the transfers are parsed back into
+    /// their hashes and are not taken for pending transfers
+    #[test]
+    fn test_expiry_key() {
+        let hash = KeccakHash([1; 32]);
+        let key = get_expiry_key(&hash);
+        assert!(is_bridge_pool_key(&key));
+        assert!(!is_pending_transfer_key(&key));
+        assert_eq!(is_expiry_key(&key), Some(hash.clone()));
+        assert_eq!(is_expiry_key(&get_key_from_hash(&hash)), None);
+        assert!(!is_pending_transfer_key(&get_expiry_prefix()));
+    }
+
+    /// Test that the fee bump keys of the transfers are parsed back into
+    /// their hashes and are not taken for pending transfers or expiries
+    #[test]
+    fn test_fee_bump_key() {
+        let hash = KeccakHash([1; 32]);
+        let key = get_fee_bump_key(&hash);
+        assert!(is_bridge_pool_key(&key));
+        assert!(!is_pending_transfer_key(&key));
+        assert_eq!(is_fee_bump_key(&key), Some(hash.clone()));
+        assert_eq!(is_expiry_key(&key), None);
+        assert_eq!(is_fee_bump_key(&get_expiry_key(&hash)), None);
+        assert!(!is_pending_transfer_key(&get_fee_bump_prefix()));
+    }
+
     /// Test that [`contains_key`] works correctly
     #[test]
     fn test_contains_key() {
@@ -3,14 +3,22 @@
 //! These storage keys should only ever be written to by governance,
 //! or `InitChain`.
 
+use std::fmt::Display;
 use std::str::FromStr;
 
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
 use super::super::ADDRESS as BRIDGE_ADDRESS;
-use super::{prefix as ethbridge_key_prefix, wrapped_erc20s};
+use super::{native_erc20_key, prefix as ethbridge_key_prefix, wrapped_erc20s};
+use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
 use crate::types::ethereum_events::EthAddress;
 use crate::types::storage;
 use crate::types::storage::DbKeySeg;
-use crate::types::token::{denom_key, minted_balance_key};
+use crate::types::token::{
+    denom_key, minted_balance_key, Amount, DenominatedAmount, Denomination,
+    NATIVE_MAX_DECIMAL_PLACES,
+};
 
 mod segments {
     //! Storage key segments under the token whitelist.
@@ -60,11 +68,51 @@ pub struct Key {
     pub suffix: KeyType,
 }
 
-/// Return the whitelist storage key sub-space prefix.
-fn whitelist_prefix(asset: &EthAddress) -> storage::Key {
+/// A change of the whitelisting of an ERC20 token, set by governance.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct WhitelistedErc20 {
+    /// The address of the ERC20 token on Ethereum.
+    pub token_address: EthAddress,
+    /// The cap of the supply of the wrapped token in Namada, whose number of
+    /// decimal places is the denomination of the token, or `None` to remove
+    /// the token from the whitelist.
+    pub token_cap: Option<DenominatedAmount>,
+}
+
+impl Display for WhitelistedErc20 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.token_cap {
+            Some(cap) => write!(
+                f,
+                "{}: cap {} with {} decimal places",
+                self.token_address,
+                cap,
+                cap.denom().0
+            ),
+            None => write!(f, "{}: removed", self.token_address),
+        }
+    }
+}
+
+/// Return the storage key prefix of the whole whitelist.
+pub fn prefix() -> storage::Key {
     ethbridge_key_prefix()
         .push(&segments::MAIN_SEGMENT.to_owned())
         .expect("Should be able to push a storage key segment")
+}
+
+/// Return the whitelist storage key sub-space prefix.
+fn whitelist_prefix(asset: &EthAddress) -> storage::Key {
+    prefix()
         .push(&asset.to_canonical())
         .expect("Should be able to push a storage key segment")
 }
@@ -116,10 +164,176 @@ pub fn is_cap_or_whitelisted_key(key: &storage::Key) -> bool {
     }
 }
 
+/// Return the ERC20 asset if the given [`storage::Key`] is a whitelist key
+/// of type [`KeyType::Whitelisted`].
+pub fn is_whitelisted_key(key: &storage::Key) -> Option<EthAddress> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(s1),
+            DbKeySeg::StringSeg(s2),
+            DbKeySeg::StringSeg(s3),
+            DbKeySeg::StringSeg(s4),
+        ] => {
+            if s1 == &BRIDGE_ADDRESS
+                && s2 == segments::MAIN_SEGMENT
+                && s4 == segments::VALUES.whitelisted
+            {
+                EthAddress::from_str(s3).ok()
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Read the cap of an ERC20 token, with its denomination, if the token is
+/// whitelisted.
+pub fn read_token_cap<S>(
+    storage: &S,
+    asset: &EthAddress,
+) -> storage_api::Result<Option<DenominatedAmount>>
+where
+    S: StorageRead,
+{
+    let key = |suffix| {
+        storage::Key::from(Key {
+            asset: *asset,
+            suffix,
+        })
+    };
+    let whitelisted: bool =
+        storage.read(&key(KeyType::Whitelisted))?.unwrap_or(false);
+    if !whitelisted {
+        return Ok(None);
+    }
+    let cap: Amount = storage.read(&key(KeyType::Cap))?.unwrap_or_default();
+    let denom: Denomination =
+        storage.read(&key(KeyType::Denomination))?.ok_or_else(|| {
+            storage_api::Error::new_const(
+                "Missing the denomination of a whitelisted ERC20 token",
+            )
+        })?;
+    Ok(Some(DenominatedAmount::new(cap, denom)))
+}
+
+/// Read all the whitelisted ERC20 tokens with their caps.
+pub fn read_whitelist<S>(
+    storage: &S,
+) -> storage_api::Result<Vec<WhitelistedErc20>>
+where
+    S: StorageRead,
+{
+    let mut whitelist = vec![];
+    for result in storage_api::iter_prefix_bytes(storage, &prefix())? {
+        let (key, _) = result?;
+        let Some(token_address) = is_whitelisted_key(&key) else {
+            continue;
+        };
+        if let Some(token_cap) = read_token_cap(storage, &token_address)? {
+            whitelist.push(WhitelistedErc20 {
+                token_address,
+                token_cap: Some(token_cap),
+            });
+        }
+    }
+    Ok(whitelist)
+}
+
+/// Check that a change of the ERC20 whitelist can be applied. The native ERC20
+/// can't be removed from the whitelist, and must keep the denomination of the
+/// native token. The denomination of the other tokens can't change once their
+/// wrapped tokens or NUTs have been minted in Namada, as their balances would
+/// otherwise be rescaled.
+pub fn is_valid_whitelist_change<S>(
+    storage: &S,
+    change: &WhitelistedErc20,
+) -> storage_api::Result<bool>
+where
+    S: StorageRead,
+{
+    let asset = change.token_address;
+    let native_erc20: Option<EthAddress> = storage.read(&native_erc20_key())?;
+    let is_native_erc20 = native_erc20 == Some(asset);
+    let Some(cap) = &change.token_cap else {
+        if is_native_erc20 {
+            tracing::debug!(%asset, "The native ERC20 can't be removed");
+        }
+        return Ok(!is_native_erc20);
+    };
+    if is_native_erc20 {
+        let is_native_denom = cap.denom() == NATIVE_MAX_DECIMAL_PLACES.into();
+        if !is_native_denom {
+            tracing::debug!(
+                %asset,
+                denom = cap.denom().0,
+                "The native ERC20 must have {} decimal places",
+                NATIVE_MAX_DECIMAL_PLACES
+            );
+        }
+        return Ok(is_native_denom);
+    }
+
+    let denom_key = Key {
+        asset,
+        suffix: KeyType::Denomination,
+    }
+    .into();
+    let denom: Option<Denomination> = storage.read(&denom_key)?;
+    if denom.map_or(true, |denom| denom == cap.denom()) {
+        return Ok(true);
+    }
+    for token in [wrapped_erc20s::token(&asset), wrapped_erc20s::nut(&asset)] {
+        let supply: Amount = storage
+            .read(&minted_balance_key(&token))?
+            .unwrap_or_default();
+        if !supply.is_zero() {
+            tracing::debug!(
+                %asset,
+                "The denomination of an ERC20 minted in Namada can't change"
+            );
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Whitelist an ERC20 token with the given cap and denomination, or remove it
+/// from the whitelist. The denomination of a removed token is kept, as its
+/// wrapped tokens and NUTs may still be held in Namada.
+pub fn write_whitelisted_erc20<S>(
+    storage: &mut S,
+    erc20: &WhitelistedErc20,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key = |suffix| {
+        storage::Key::from(Key {
+            asset: erc20.token_address,
+            suffix,
+        })
+    };
+    match &erc20.token_cap {
+        Some(cap) => {
+            storage.write(&key(KeyType::Whitelisted), true)?;
+            storage.write(&key(KeyType::Cap), cap.amount())?;
+            storage.write(&key(KeyType::Denomination), cap.denom())
+        }
+        None => {
+            storage.delete(&key(KeyType::Whitelisted))?;
+            storage.delete(&key(KeyType::Cap))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::types::ethereum_events::testing::DAI_ERC20_ETH_ADDRESS;
+    use crate::ledger::storage::testing::TestWlStorage;
+    use crate::types::ethereum_events::testing::{
+        DAI_ERC20_ETH_ADDRESS, USDC_ERC20_ETH_ADDRESS,
+    };
 
     /// Test that storage key serialization yields the expected value.
     #[test]
@@ -163,4 +377,115 @@ mod tests {
         };
         assert!(!is_cap_or_whitelisted_key(&unexpected_key));
     }
+
+    /// Test that the whitelisted ERC20 tokens are read back with their caps
+    /// and denominations, and that removed tokens keep their denomination.
+    #[test]
+    fn test_write_read_whitelist() {
+        let mut storage = TestWlStorage::default();
+        let dai = WhitelistedErc20 {
+            token_address: DAI_ERC20_ETH_ADDRESS,
+            token_cap: Some(DenominatedAmount::new(
+                Amount::from_u64(1_000_000),
+                18.into(),
+            )),
+        };
+        let usdc = WhitelistedErc20 {
+            token_address: USDC_ERC20_ETH_ADDRESS,
+            token_cap: Some(DenominatedAmount::new(
+                Amount::from_u64(500),
+                6.into(),
+            )),
+        };
+        write_whitelisted_erc20(&mut storage, &dai).unwrap();
+        write_whitelisted_erc20(&mut storage, &usdc).unwrap();
+        let mut whitelist = read_whitelist(&storage).unwrap();
+        whitelist.sort_by_key(|erc20| erc20.token_address);
+        let mut expected = vec![dai.clone(), usdc.clone()];
+        expected.sort_by_key(|erc20| erc20.token_address);
+        assert_eq!(whitelist, expected);
+
+        write_whitelisted_erc20(
+            &mut storage,
+            &WhitelistedErc20 {
+                token_cap: None,
+                ..dai
+            },
+        )
+        .unwrap();
+        assert_eq!(read_whitelist(&storage).unwrap(), vec![usdc]);
+        assert_eq!(
+            read_token_cap(&storage, &DAI_ERC20_ETH_ADDRESS).unwrap(),
+            None
+        );
+        let denom_key: storage::Key = Key {
+            asset: DAI_ERC20_ETH_ADDRESS,
+            suffix: KeyType::Denomination,
+        }
+        .into();
+        let denom: Option<Denomination> = storage.read(&denom_key).unwrap();
+        assert_eq!(denom, Some(18.into()));
+    }
+
+    /// Test that the native ERC20 can't be removed from the whitelist nor
+    /// change its denomination, and that the denomination of other tokens
+    /// can only change until they are minted in Namada.
+    #[test]
+    fn test_is_valid_whitelist_change() {
+        let mut storage = TestWlStorage::default();
+        let wnam = EthAddress([1; 20]);
+        storage.write(&native_erc20_key(), wnam).unwrap();
+        let change = |token_address, cap: Option<(u64, u8)>| WhitelistedErc20 {
+            token_address,
+            token_cap: cap.map(|(amount, denom)| {
+                DenominatedAmount::new(Amount::from_u64(amount), denom.into())
+            }),
+        };
+
+        // the native ERC20
+        let native_denom = NATIVE_MAX_DECIMAL_PLACES;
+        assert!(is_valid_whitelist_change(
+            &storage,
+            &change(wnam, Some((1_000, native_denom)))
+        )
+        .unwrap());
+        assert!(!is_valid_whitelist_change(
+            &storage,
+            &change(wnam, Some((1_000, 18)))
+        )
+        .unwrap());
+        assert!(
+            !is_valid_whitelist_change(&storage, &change(wnam, None)).unwrap()
+        );
+
+        // a token yet to be minted
+        write_whitelisted_erc20(
+            &mut storage,
+            &change(DAI_ERC20_ETH_ADDRESS, Some((1_000, 18))),
+        )
+        .unwrap();
+        for dai in [
+            change(DAI_ERC20_ETH_ADDRESS, Some((1_000, 6))),
+            change(DAI_ERC20_ETH_ADDRESS, Some((2_000, 18))),
+            change(DAI_ERC20_ETH_ADDRESS, None),
+        ] {
+            assert!(is_valid_whitelist_change(&storage, &dai).unwrap());
+        }
+
+        // a minted token
+        let minted_key =
+            minted_balance_key(&wrapped_erc20s::nut(&DAI_ERC20_ETH_ADDRESS));
+        storage.write(&minted_key, Amount::from_u64(1)).unwrap();
+        assert!(!is_valid_whitelist_change(
+            &storage,
+            &change(DAI_ERC20_ETH_ADDRESS, Some((1_000, 6)))
+        )
+        .unwrap());
+        for dai in [
+            change(DAI_ERC20_ETH_ADDRESS, Some((2_000, 18))),
+            change(DAI_ERC20_ETH_ADDRESS, None),
+        ] {
+            assert!(is_valid_whitelist_change(&storage, &dai).unwrap());
+        }
+    }
 }
@@ -0,0 +1,75 @@
+//! Event index storage
+//!
+//! The events emitted at some block height are stored under
+//! `all/{height}/{index}`, and each of them is referenced from
+//! `tag/{tag}/{height}/{index}` for every tag it is indexed by. Heights and
+//! indices are zero-padded and tags are hex-encoded, such that the keys are
+//! ordered by height and tags cannot contain the keys' separator.
+
+use data_encoding::HEXLOWER;
+
+use crate::types::storage::BlockHeight;
+
+/// Get the prefix of the keys of all the events
+pub fn all_prefix() -> String {
+    "all/".to_string()
+}
+
+/// Get the prefix of the keys referencing the events with the given tag
+pub fn tag_prefix(tag: &str) -> String {
+    format!("tag/{}/", HEXLOWER.encode(tag.as_bytes()))
+}
+
+/// Get the key of the event with the given index emitted at the given height
+pub fn event_key(height: BlockHeight, index: u64) -> String {
+    format!("{}{}", all_prefix(), position_suffix(height, index))
+}
+
+/// Get the key referencing the event with the given index emitted at the given
+/// height from the given tag
+pub fn tag_key(tag: &str, height: BlockHeight, index: u64) -> String {
+    format!("{}{}", tag_prefix(tag), position_suffix(height, index))
+}
+
+/// Get the first key under the given prefix of the events emitted from the
+/// given height
+pub fn height_lower_bound(prefix: &str, height: BlockHeight) -> String {
+    format!("{}{:020}/", prefix, height.0)
+}
+
+/// Parse the height and the index of an event from the suffix of an event or
+/// tag key
+pub fn parse_position(key: &str) -> Option<(BlockHeight, u64)> {
+    let mut segments = key.rsplit('/');
+    let index = segments.next()?.parse().ok()?;
+    let height = segments.next()?.parse().ok()?;
+    Some((BlockHeight(height), index))
+}
+
+fn position_suffix(height: BlockHeight, index: u64) -> String {
+    format!("{:020}/{:010}", height.0, index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_keys_order() {
+        let keys = [
+            tag_key("type:applied", BlockHeight(9), 11),
+            tag_key("type:applied", BlockHeight(10), 2),
+            tag_key("type:applied", BlockHeight(10), 10),
+        ];
+        assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(parse_position(&keys[1]), Some((BlockHeight(10), 2)));
+        assert!(
+            height_lower_bound(&tag_prefix("type:applied"), BlockHeight(10))
+                <= keys[1]
+        );
+        assert_eq!(
+            parse_position(&event_key(BlockHeight(3), 4)),
+            Some((BlockHeight(3), 4))
+        );
+    }
+}
@@ -251,6 +251,11 @@ impl OfflineVote {
         self.vote.is_abstain()
     }
 
+    /// Check if the vote is no with veto
+    pub fn is_no_with_veto(&self) -> bool {
+        self.vote.is_no_with_veto()
+    }
+
     /// Check if two votes are equal
     pub fn is_same_side(&self, other: &Self) -> bool {
         self.vote.is_same_side(&other.vote)
@@ -327,23 +332,26 @@ fn compute_signatures_index(
         .collect::<BTreeSet<SignatureIndex>>()
 }
 
-/// Compute the total amount of signatures
+/// Compute the total weight of the valid signatures
 fn compute_total_valid_signatures(
     signatures: &BTreeSet<SignatureIndex>,
     account_public_keys_map: &AccountPublicKeysMap,
     hashed_data: &Hash,
 ) -> u8 {
     signatures.iter().fold(0_u8, |acc, signature_index| {
-        if account_public_keys_map
+        if let Some(index) = account_public_keys_map
             .get_index_from_public_key(&signature_index.pubkey)
-            .is_some()
         {
             let sig_check = common::SigScheme::verify_signature(
                 &signature_index.pubkey,
                 hashed_data,
                 &signature_index.signature,
             );
-            if sig_check.is_ok() { acc + 1 } else { acc }
+            if sig_check.is_ok() {
+                acc.saturating_add(account_public_keys_map.get_weight(index))
+            } else {
+                acc
+            }
         } else {
             acc
         }
@@ -5,14 +5,18 @@ use serde::{Deserialize, Serialize};
 
 use super::validation::{
     is_valid_author_balance, is_valid_content, is_valid_default_proposal_data,
-    is_valid_end_epoch, is_valid_grace_epoch, is_valid_pgf_funding_data,
-    is_valid_pgf_stewards_data, is_valid_proposal_period, is_valid_start_epoch,
+    is_valid_end_epoch, is_valid_grace_epoch, is_valid_parameter_changes,
+    is_valid_pgf_funding_data, is_valid_pgf_stewards_data,
+    is_valid_proposal_period, is_valid_start_epoch, is_valid_upgrade,
     ProposalValidation,
 };
 use crate::ledger::governance::parameters::GovernanceParameters;
+use crate::ledger::governance::storage::proposal::{
+    PGFTarget, ParameterChange, Upgrade,
+};
 use crate::ledger::storage_api::token;
 use crate::types::address::Address;
-use crate::types::storage::Epoch;
+use crate::types::storage::{BlockHeight, Epoch};
 
 #[derive(
     Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
@@ -45,28 +49,34 @@ pub struct DefaultProposal {
 }
 
 impl DefaultProposal {
-    /// Validate a default funding proposal
+    /// Validate a default funding proposal, with the shorter voting period
+    /// of emergency proposals if `is_emergency`. The author must be able to
+    /// pay for the initial `deposit`.
     pub fn validate(
         self,
         governance_parameters: &GovernanceParameters,
         current_epoch: Epoch,
         balance: token::Amount,
+        deposit: token::Amount,
+        is_emergency: bool,
         force: bool,
     ) -> Result<Self, ProposalValidation> {
         if force {
             return Ok(self);
         }
+        let min_voting_period =
+            governance_parameters.min_voting_period(is_emergency);
         is_valid_start_epoch(
             self.proposal.voting_start_epoch,
             current_epoch,
-            governance_parameters.min_proposal_voting_period,
+            min_voting_period,
         )?;
         is_valid_end_epoch(
             self.proposal.voting_start_epoch,
             self.proposal.voting_end_epoch,
             current_epoch,
-            governance_parameters.min_proposal_voting_period,
-            governance_parameters.min_proposal_voting_period,
+            min_voting_period,
+            min_voting_period,
             governance_parameters.max_proposal_period,
         )?;
         is_valid_grace_epoch(
@@ -79,10 +89,7 @@ impl DefaultProposal {
             self.proposal.grace_epoch,
             governance_parameters.max_proposal_period,
         )?;
-        is_valid_author_balance(
-            balance,
-            governance_parameters.min_proposal_fund,
-        )?;
+        is_valid_author_balance(balance, deposit)?;
         is_valid_content(
             &self.proposal.content,
             governance_parameters.max_proposal_content_size,
@@ -123,12 +130,14 @@ pub struct StewardsUpdate {
 }
 
 impl PgfStewardProposal {
-    /// Validate a Pgf stewards proposal
+    /// Validate a Pgf stewards proposal. The author must be able to pay for
+    /// the initial `deposit`.
     pub fn validate(
         self,
         governance_parameters: &GovernanceParameters,
         current_epoch: Epoch,
         balance: token::Amount,
+        deposit: token::Amount,
         force: bool,
     ) -> Result<Self, ProposalValidation> {
         if force {
@@ -157,10 +166,7 @@ impl PgfStewardProposal {
             self.proposal.grace_epoch,
             governance_parameters.max_proposal_period,
         )?;
-        is_valid_author_balance(
-            balance,
-            governance_parameters.min_proposal_fund,
-        )?;
+        is_valid_author_balance(balance, deposit)?;
         is_valid_content(
             &self.proposal.content,
             governance_parameters.max_proposal_content_size,
@@ -242,6 +248,141 @@ impl TryFrom<&[u8]> for PgfFundingProposal {
     }
 }
 
+/// Parameter change proposal
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct ParameterChangeProposal {
+    /// The proposal data
+    pub proposal: OnChainProposal,
+    /// The new values of the changed parameters
+    pub data: Vec<ParameterChange>,
+}
+
+impl ParameterChangeProposal {
+    /// Validate a parameter change proposal. The author must be able to pay
+    /// for the initial `deposit`.
+    pub fn validate(
+        self,
+        governance_parameters: &GovernanceParameters,
+        current_epoch: Epoch,
+        balance: token::Amount,
+        deposit: token::Amount,
+        force: bool,
+    ) -> Result<Self, ProposalValidation> {
+        if force {
+            return Ok(self);
+        }
+        is_valid_start_epoch(
+            self.proposal.voting_start_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+        )?;
+        is_valid_end_epoch(
+            self.proposal.voting_start_epoch,
+            self.proposal.voting_end_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_grace_epoch(
+            self.proposal.grace_epoch,
+            self.proposal.voting_end_epoch,
+            governance_parameters.min_proposal_grace_epochs,
+        )?;
+        is_valid_proposal_period(
+            self.proposal.voting_start_epoch,
+            self.proposal.grace_epoch,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_author_balance(balance, deposit)?;
+        is_valid_content(
+            &self.proposal.content,
+            governance_parameters.max_proposal_content_size,
+        )?;
+        is_valid_parameter_changes(&self.data)?;
+
+        Ok(self)
+    }
+}
+
+impl TryFrom<&[u8]> for ParameterChangeProposal {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(value)
+    }
+}
+
+/// Upgrade proposal
+#[derive(
+    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
+)]
+pub struct UpgradeProposal {
+    /// The proposal data
+    pub proposal: OnChainProposal,
+    /// The scheduled upgrade
+    pub data: Upgrade,
+}
+
+impl UpgradeProposal {
+    /// Validate an upgrade proposal. The author must be able to pay for the
+    /// initial `deposit`.
+    pub fn validate(
+        self,
+        governance_parameters: &GovernanceParameters,
+        current_epoch: Epoch,
+        current_height: BlockHeight,
+        balance: token::Amount,
+        deposit: token::Amount,
+        force: bool,
+    ) -> Result<Self, ProposalValidation> {
+        if force {
+            return Ok(self);
+        }
+        is_valid_start_epoch(
+            self.proposal.voting_start_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+        )?;
+        is_valid_end_epoch(
+            self.proposal.voting_start_epoch,
+            self.proposal.voting_end_epoch,
+            current_epoch,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.min_proposal_voting_period,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_grace_epoch(
+            self.proposal.grace_epoch,
+            self.proposal.voting_end_epoch,
+            governance_parameters.min_proposal_grace_epochs,
+        )?;
+        is_valid_proposal_period(
+            self.proposal.voting_start_epoch,
+            self.proposal.grace_epoch,
+            governance_parameters.max_proposal_period,
+        )?;
+        is_valid_author_balance(balance, deposit)?;
+        is_valid_content(
+            &self.proposal.content,
+            governance_parameters.max_proposal_content_size,
+        )?;
+        is_valid_upgrade(&self.data, current_height)?;
+
+        Ok(self)
+    }
+}
+
+impl TryFrom<&[u8]> for UpgradeProposal {
+    type Error = serde_json::Error;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        serde_json::from_slice(value)
+    }
+}
+
 /// Pgf stewards
 #[derive(
     Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
@@ -277,9 +418,9 @@ impl PgfAction {
 )]
 pub struct PgfFunding {
     /// Pgf continuous funding
-    pub continuous: Vec<PgfFundingTarget>,
+    pub continuous: Vec<PGFTarget>,
     /// pgf retro fundings
-    pub retro: Vec<PgfFundingTarget>,
+    pub retro: Vec<PGFTarget>,
 }
 
 /// Pgf continous funding
@@ -288,7 +429,7 @@ pub struct PgfFunding {
 )]
 pub struct PgfContinous {
     /// Pgf target
-    pub target: PgfFundingTarget,
+    pub target: PGFTarget,
     /// Pgf action
     pub action: PgfAction,
 }
@@ -299,18 +440,7 @@ pub struct PgfContinous {
 )]
 pub struct PgfRetro {
     /// Pgf retro target
-    pub target: PgfFundingTarget,
-}
-
-/// Pgf Target
-#[derive(
-    Debug, Clone, BorshSerialize, BorshDeserialize, Serialize, Deserialize,
-)]
-pub struct PgfFundingTarget {
-    /// Target amount
-    pub amount: token::Amount,
-    /// Target address
-    pub address: Address,
+    pub target: PGFTarget,
 }
 
 /// Represent an proposal vote
@@ -330,6 +460,8 @@ pub enum ProposalVote {
     Nay,
     /// Represent an abstain proposal vote
     Abstain,
+    /// Represent a nay proposal vote asking for the deposit to be burned
+    NoWithVeto,
 }
 
 impl TryFrom<String> for ProposalVote {
@@ -340,6 +472,7 @@ impl TryFrom<String> for ProposalVote {
             "yay" => Ok(ProposalVote::Yay),
             "nay" => Ok(ProposalVote::Nay),
             "abstain" => Ok(ProposalVote::Abstain),
+            "no-with-veto" => Ok(ProposalVote::NoWithVeto),
             _ => Err("invalid vote".to_string()),
         }
     }
@@ -361,6 +494,11 @@ impl ProposalVote {
         matches!(self, ProposalVote::Abstain)
     }
 
+    /// Check if the vote type is no with veto
+    pub fn is_no_with_veto(&self) -> bool {
+        matches!(self, ProposalVote::NoWithVeto)
+    }
+
     /// Check if two votes are equal
     pub fn is_same_side(&self, other: &Self) -> bool {
         std::mem::discriminant(self) == std::mem::discriminant(other)
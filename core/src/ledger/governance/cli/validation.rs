@@ -1,10 +1,11 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use thiserror::Error;
 
 use super::onchain::{PgfFunding, StewardsUpdate};
+use crate::ledger::governance::storage::proposal::{ParameterChange, Upgrade};
 use crate::types::address::Address;
-use crate::types::storage::Epoch;
+use crate::types::storage::{BlockHeight, Epoch};
 use crate::types::token;
 
 /// This enum raprresent a proposal data
@@ -38,11 +39,11 @@ pub enum ProposalValidation {
          epoch must be at most {1}, but found {0}"
     )]
     InvalidProposalPeriod(u64, u64),
-    /// The proposal author does not have enough balance to pay for proposal
-    /// fees
+    /// The proposal author does not have enough balance to pay for the
+    /// proposal deposit
     #[error(
-        "Invalid proposal minimum funds: the author address has {0} but \
-         minimum is {1}"
+        "Invalid proposal deposit: the author address has {0} but the deposit \
+         is {1}"
     )]
     InvalidBalance(String, String),
     /// The proposal content is too large
@@ -69,18 +70,39 @@ pub enum ProposalValidation {
     /// The pgf funding data is not valid
     #[error("invalid proposal extra data: cannot be empty.")]
     InvalidPgfFundingExtraData,
+    /// The pgf funding target is not valid
+    #[error(
+        "Invalid pgf funding target {0}: the receiver on the counterparty \
+         chain cannot be empty or contain a '/'."
+    )]
+    InvalidPgfFundingTarget(String),
+    /// The parameter change data is not valid
+    #[error("Invalid proposal extra data: cannot be empty.")]
+    InvalidParameterChangeExtraData,
+    /// The new value of a parameter is not valid
+    #[error("Invalid parameter change {0}: the value is out of range.")]
+    InvalidParameterChange(String),
+    /// A parameter is changed more than once
+    #[error("Invalid parameter change: {0} is changed more than once.")]
+    DuplicateParameterChange(String),
+    /// The upgrade has no name or is not scheduled in the future
+    #[error(
+        "Invalid upgrade: the name cannot be empty and the height {0} must be \
+         greater than the current height {1}"
+    )]
+    InvalidUpgrade(BlockHeight, BlockHeight),
 }
 
 pub fn is_valid_author_balance(
     author_balance: token::Amount,
-    min_proposal_fund: token::Amount,
+    deposit: token::Amount,
 ) -> Result<(), ProposalValidation> {
-    if author_balance.can_spend(&min_proposal_fund) {
+    if author_balance.can_spend(&deposit) {
         Ok(())
     } else {
         Err(ProposalValidation::InvalidBalance(
             author_balance.to_string_native(),
-            min_proposal_fund.to_string_native(),
+            deposit.to_string_native(),
         ))
     }
 }
@@ -244,9 +266,54 @@ pub fn is_valid_pgf_stewards_data(
 pub fn is_valid_pgf_funding_data(
     data: &PgfFunding,
 ) -> Result<(), ProposalValidation> {
-    if !data.continuous.is_empty() || !data.retro.is_empty() {
+    if data.continuous.is_empty() && data.retro.is_empty() {
+        return Err(ProposalValidation::InvalidPgfFundingExtraData);
+    }
+    match data
+        .continuous
+        .iter()
+        .chain(data.retro.iter())
+        .find(|target| !target.is_valid())
+    {
+        Some(target) => Err(ProposalValidation::InvalidPgfFundingTarget(
+            target.to_string(),
+        )),
+        None => Ok(()),
+    }
+}
+
+pub fn is_valid_parameter_changes(
+    changes: &[ParameterChange],
+) -> Result<(), ProposalValidation> {
+    if changes.is_empty() {
+        return Err(ProposalValidation::InvalidParameterChangeExtraData);
+    }
+    let mut names = BTreeSet::new();
+    for change in changes {
+        if !change.is_valid() {
+            return Err(ProposalValidation::InvalidParameterChange(
+                change.to_string(),
+            ));
+        }
+        if !names.insert(change.name()) {
+            return Err(ProposalValidation::DuplicateParameterChange(
+                change.name().to_string(),
+            ));
+        }
+    }
+    Ok(())
+}
+
+pub fn is_valid_upgrade(
+    upgrade: &Upgrade,
+    current_height: BlockHeight,
+) -> Result<(), ProposalValidation> {
+    if upgrade.is_valid(current_height) {
         Ok(())
     } else {
-        Err(ProposalValidation::InvalidPgfFundingExtraData)
+        Err(ProposalValidation::InvalidUpgrade(
+            upgrade.height,
+            current_height,
+        ))
     }
 }
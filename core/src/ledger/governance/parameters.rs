@@ -1,7 +1,10 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 
 use super::storage::keys as goverance_storage;
+use super::storage::proposal::ProposalType;
+use super::utils::TallyType;
 use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
+use crate::types::dec::Dec;
 use crate::types::token;
 
 #[derive(
@@ -29,6 +32,21 @@ pub struct GovernanceParameters {
     pub max_proposal_content_size: u64,
     /// Minimum epochs between end and grace epochs
     pub min_proposal_grace_epochs: u64,
+    /// Minimum emergency proposal voting period in epochs
+    pub min_emergency_proposal_voting_period: u64,
+    /// Tally rule of default proposals
+    pub default_proposal_tally: TallyType,
+    /// Tally rule of emergency proposals
+    pub emergency_proposal_tally: TallyType,
+    /// Tally rule of PGF stewards proposals
+    pub pgf_steward_proposal_tally: TallyType,
+    /// Tally rule of PGF funding proposals
+    pub pgf_funding_proposal_tally: TallyType,
+    /// Tally rule of PGF funding proposals authored by a steward
+    pub pgf_steward_funding_proposal_tally: TallyType,
+    /// Fraction of all the votes that must be no with veto to burn the
+    /// deposit of a proposal
+    pub veto_threshold: Dec,
 }
 
 impl Default for GovernanceParameters {
@@ -40,6 +58,26 @@ impl Default for GovernanceParameters {
             max_proposal_period: 27,
             max_proposal_content_size: 10_000,
             min_proposal_grace_epochs: 6,
+            min_emergency_proposal_voting_period: 1,
+            default_proposal_tally: TallyType::YayOverTotal {
+                threshold: Dec::two() / 3,
+            },
+            emergency_proposal_tally: TallyType::YayOverTotal {
+                threshold: Dec::new(8, 1).unwrap(),
+            },
+            pgf_steward_proposal_tally: TallyType::YayOverQuorum {
+                quorum: Dec::one() / 3,
+                threshold: Dec::new(5, 1).unwrap(),
+            },
+            pgf_funding_proposal_tally: TallyType::YayOverQuorum {
+                quorum: Dec::one() / 3,
+                threshold: Dec::new(5, 1).unwrap(),
+            },
+            pgf_steward_funding_proposal_tally: TallyType::NayVetoOverQuorum {
+                quorum: Dec::one() / 3,
+                threshold: Dec::new(5, 1).unwrap(),
+            },
+            veto_threshold: Dec::one() / 3,
         }
     }
 }
@@ -47,6 +85,21 @@ impl Default for GovernanceParameters {
 impl GovernanceParameters {
     /// Initialize governance parameters into storage
     pub fn init_storage<S>(&self, storage: &mut S) -> storage_api::Result<()>
+    where
+        S: StorageRead + StorageWrite,
+    {
+        self.write_parameters(storage)?;
+
+        let counter_key = goverance_storage::get_counter_key();
+        storage.write(&counter_key, u64::MIN)
+    }
+
+    /// Write the governance parameters into storage, leaving the proposals
+    /// untouched
+    pub fn write_parameters<S>(
+        &self,
+        storage: &mut S,
+    ) -> storage_api::Result<()>
     where
         S: StorageRead + StorageWrite,
     {
@@ -57,6 +110,13 @@ impl GovernanceParameters {
             max_proposal_period,
             max_proposal_content_size,
             min_proposal_grace_epochs,
+            min_emergency_proposal_voting_period,
+            default_proposal_tally,
+            emergency_proposal_tally,
+            pgf_steward_proposal_tally,
+            pgf_funding_proposal_tally,
+            pgf_steward_funding_proposal_tally,
+            veto_threshold,
         } = self;
 
         let min_proposal_fund_key =
@@ -88,7 +148,79 @@ impl GovernanceParameters {
         storage
             .write(&min_proposal_grace_epoch_key, min_proposal_grace_epochs)?;
 
-        let counter_key = goverance_storage::get_counter_key();
-        storage.write(&counter_key, u64::MIN)
+        let min_emergency_proposal_voting_period_key =
+            goverance_storage::get_min_emergency_proposal_voting_period_key();
+        storage.write(
+            &min_emergency_proposal_voting_period_key,
+            min_emergency_proposal_voting_period,
+        )?;
+
+        let default_proposal_tally_key =
+            goverance_storage::get_default_proposal_tally_key();
+        storage.write(&default_proposal_tally_key, default_proposal_tally)?;
+
+        let emergency_proposal_tally_key =
+            goverance_storage::get_emergency_proposal_tally_key();
+        storage
+            .write(&emergency_proposal_tally_key, emergency_proposal_tally)?;
+
+        let pgf_steward_proposal_tally_key =
+            goverance_storage::get_pgf_steward_proposal_tally_key();
+        storage.write(
+            &pgf_steward_proposal_tally_key,
+            pgf_steward_proposal_tally,
+        )?;
+
+        let pgf_funding_proposal_tally_key =
+            goverance_storage::get_pgf_funding_proposal_tally_key();
+        storage.write(
+            &pgf_funding_proposal_tally_key,
+            pgf_funding_proposal_tally,
+        )?;
+
+        let pgf_steward_funding_proposal_tally_key =
+            goverance_storage::get_pgf_steward_funding_proposal_tally_key();
+        storage.write(
+            &pgf_steward_funding_proposal_tally_key,
+            pgf_steward_funding_proposal_tally,
+        )?;
+
+        let veto_threshold_key = goverance_storage::get_veto_threshold_key();
+        storage.write(&veto_threshold_key, veto_threshold)
+    }
+
+    /// Get the tally rule of a proposal of the given type, depending on
+    /// whether its author is a PGF steward
+    pub fn tally_type(
+        &self,
+        proposal_type: &ProposalType,
+        is_steward: bool,
+    ) -> TallyType {
+        match (proposal_type, is_steward) {
+            (
+                ProposalType::Default(_)
+                | ProposalType::ParameterChange(_)
+                | ProposalType::Upgrade(_),
+                _,
+            ) => self.default_proposal_tally,
+            (ProposalType::Emergency(_), _) => self.emergency_proposal_tally,
+            (ProposalType::PGFSteward(_), _) => self.pgf_steward_proposal_tally,
+            (ProposalType::PGFPayment(_), true) => {
+                self.pgf_steward_funding_proposal_tally
+            }
+            (ProposalType::PGFPayment(_), false) => {
+                self.pgf_funding_proposal_tally
+            }
+        }
+    }
+
+    /// Get the minimum voting period in epochs of a proposal, which is also
+    /// the multiple of its start and end epochs
+    pub fn min_voting_period(&self, is_emergency: bool) -> u64 {
+        if is_emergency {
+            self.min_emergency_proposal_voting_period
+        } else {
+            self.min_proposal_voting_period
+        }
     }
 }
@@ -1,6 +1,10 @@
 use namada_macros::StorageKeys;
 
+use super::vote::VoteRecord;
 use crate::ledger::governance::ADDRESS;
+use crate::ledger::storage_api::collections::{
+    LazyCollection, LazySet, LazyVec,
+};
 use crate::types::address::Address;
 use crate::types::storage::{DbKeySeg, Key, KeySeg};
 
@@ -16,6 +20,7 @@ struct Keys {
     end_epoch: &'static str,
     grace_epoch: &'static str,
     funds: &'static str,
+    deposit: &'static str,
     proposal_code: &'static str,
     committing_epoch: &'static str,
     min_fund: &'static str,
@@ -24,9 +29,21 @@ struct Keys {
     max_period: &'static str,
     max_content: &'static str,
     min_grace_epoch: &'static str,
+    min_emergency_period: &'static str,
+    default_tally: &'static str,
+    emergency_tally: &'static str,
+    pgf_steward_tally: &'static str,
+    pgf_funding_tally: &'static str,
+    pgf_steward_funding_tally: &'static str,
+    veto_threshold: &'static str,
     counter: &'static str,
     pending: &'static str,
     result: &'static str,
+    representative: &'static str,
+    representative_vote: &'static str,
+    vote_history: &'static str,
+    voter_votes: &'static str,
+    upgrade: &'static str,
 }
 
 /// Check if key is inside governance address space
@@ -54,6 +71,66 @@ pub fn is_vote_key(key: &Key) -> bool {
     }
 }
 
+/// Check if a key is a sub-key of the vote history of a voter
+pub fn is_vote_history_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(id),
+            DbKeySeg::StringSeg(vote_history),
+            DbKeySeg::AddressSeg(_voter),
+            _,
+            ..,
+        ] if addr == &ADDRESS
+            && prefix == Keys::VALUES.proposal
+            && vote_history == Keys::VALUES.vote_history =>
+        {
+            id.parse::<u64>().is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// Check if a key is a key of the index of the proposals a voter voted on.
+/// If it is, returns the voter and the proposal id.
+pub fn is_voter_votes_key(key: &Key) -> Option<(&Address, u64)> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(voter),
+            DbKeySeg::StringSeg(id),
+        ] if addr == &ADDRESS && prefix == Keys::VALUES.voter_votes => {
+            <u64 as KeySeg>::parse(id.clone()).ok().map(|id| (voter, id))
+        }
+        _ => None,
+    }
+}
+
+/// Check if a key is a vote key of a representative
+pub fn is_representative_vote_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(id), DbKeySeg::StringSeg(representative_vote), DbKeySeg::AddressSeg(_representative)]
+            if addr == &ADDRESS
+                && prefix == Keys::VALUES.proposal
+                && representative_vote == Keys::VALUES.representative_vote =>
+        {
+            id.parse::<u64>().is_ok()
+        }
+        _ => false,
+    }
+}
+
+/// Check if a key is the key of the representative of a delegator
+pub fn is_representative_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+             DbKeySeg::AddressSeg(addr),
+             DbKeySeg::StringSeg(prefix),
+             DbKeySeg::AddressSeg(_delegator),
+         ] if addr == &ADDRESS && prefix == Keys::VALUES.representative)
+}
+
 /// Check if key is author key
 pub fn is_author_key(key: &Key) -> bool {
     match &key.segments[..] {
@@ -144,6 +221,20 @@ pub fn is_balance_key(key: &Key) -> bool {
     }
 }
 
+/// Check if key is the key of the deposit of a backer of a proposal
+pub fn is_proposal_deposit_key(key: &Key) -> bool {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(addr), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(id), DbKeySeg::StringSeg(deposit), DbKeySeg::AddressSeg(_depositor)]
+            if addr == &ADDRESS
+                && prefix == Keys::VALUES.proposal
+                && deposit == Keys::VALUES.deposit =>
+        {
+            id.parse::<u64>().is_ok()
+        }
+        _ => false,
+    }
+}
+
 /// Check if key is start epoch key
 pub fn is_start_epoch_key(key: &Key) -> bool {
     match &key.segments[..] {
@@ -270,6 +361,39 @@ pub fn is_min_grace_epoch_key(key: &Key) -> bool {
                     && min_grace_epoch_param == Keys::VALUES.min_grace_epoch)
 }
 
+/// Check if key is a min emergency proposal period param key
+pub fn is_min_emergency_proposal_voting_period_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+             DbKeySeg::AddressSeg(addr),
+             DbKeySeg::StringSeg(min_emergency_period_param),
+         ] if addr == &ADDRESS
+             && min_emergency_period_param == Keys::VALUES.min_emergency_period)
+}
+
+/// Check if key is the tally rule param key of a type of proposals
+pub fn is_proposal_tally_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+             DbKeySeg::AddressSeg(addr),
+             DbKeySeg::StringSeg(tally_param),
+         ] if addr == &ADDRESS
+             && [
+                 Keys::VALUES.default_tally,
+                 Keys::VALUES.emergency_tally,
+                 Keys::VALUES.pgf_steward_tally,
+                 Keys::VALUES.pgf_funding_tally,
+                 Keys::VALUES.pgf_steward_funding_tally,
+             ].contains(&tally_param.as_str()))
+}
+
+/// Check if key is the veto threshold param key
+pub fn is_veto_threshold_key(key: &Key) -> bool {
+    matches!(&key.segments[..], [
+             DbKeySeg::AddressSeg(addr),
+             DbKeySeg::StringSeg(veto_threshold_param),
+         ] if addr == &ADDRESS
+             && veto_threshold_param == Keys::VALUES.veto_threshold)
+}
+
 /// Check if key is parameter key
 pub fn is_parameter_key(key: &Key) -> bool {
     is_min_proposal_fund_key(key)
@@ -278,6 +402,9 @@ pub fn is_parameter_key(key: &Key) -> bool {
         || is_min_proposal_voting_period_key(key)
         || is_max_proposal_period_key(key)
         || is_min_grace_epoch_key(key)
+        || is_min_emergency_proposal_voting_period_key(key)
+        || is_proposal_tally_key(key)
+        || is_veto_threshold_key(key)
 }
 
 /// Check if key is start epoch or end epoch key
@@ -334,6 +461,55 @@ pub fn get_min_proposal_grace_epoch_key() -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get minimum emergency proposal period key
+pub fn get_min_emergency_proposal_voting_period_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.min_emergency_period.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get default proposal tally rule key
+pub fn get_default_proposal_tally_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.default_tally.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get emergency proposal tally rule key
+pub fn get_emergency_proposal_tally_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.emergency_tally.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get PGF stewards proposal tally rule key
+pub fn get_pgf_steward_proposal_tally_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.pgf_steward_tally.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get PGF funding proposal tally rule key
+pub fn get_pgf_funding_proposal_tally_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.pgf_funding_tally.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the tally rule key of PGF funding proposals authored by a steward
+pub fn get_pgf_steward_funding_proposal_tally_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.pgf_steward_funding_tally.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get veto threshold key
+pub fn get_veto_threshold_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.veto_threshold.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get key of proposal ids counter
 pub fn get_counter_key() -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -395,6 +571,22 @@ pub fn get_funds_key(id: u64) -> Key {
         .expect("Cannot obtain a storage key")
 }
 
+/// Get the prefix of the deposits of the backers of a proposal
+pub fn get_proposal_deposit_prefix_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.deposit.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the deposit of a backer of a proposal
+pub fn get_proposal_deposit_key(id: u64, depositor: &Address) -> Key {
+    get_proposal_deposit_prefix_key(id)
+        .push(depositor)
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get proposal grace epoch key
 pub fn get_grace_epoch_key(id: u64) -> Key {
     proposal_prefix()
@@ -451,6 +643,75 @@ pub fn get_vote_proposal_key(
         .expect("Cannot obtain a storage key")
 }
 
+/// Get the prefix of the votes of the representatives on a proposal
+pub fn get_proposal_representative_vote_prefix_key(id: u64) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.representative_vote.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the vote of a representative on a proposal
+pub fn get_representative_vote_key(id: u64, representative: &Address) -> Key {
+    get_proposal_representative_vote_prefix_key(id)
+        .push(representative)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the prefix of the history of the votes of a voter on a proposal
+pub fn get_vote_history_prefix_key(id: u64, voter: &Address) -> Key {
+    proposal_prefix()
+        .push(&id.to_string())
+        .expect("Cannot obtain a storage key")
+        .push(&Keys::VALUES.vote_history.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(voter)
+        .expect("Cannot obtain a storage key")
+}
+
+/// LazyVec handler for the history of the votes of a voter on a proposal,
+/// from the oldest to the current one
+pub fn vote_history_handle(id: u64, voter: &Address) -> LazyVec<VoteRecord> {
+    LazyVec::open(get_vote_history_prefix_key(id, voter))
+}
+
+/// Get the prefix of the ids of the proposals a voter voted on
+pub fn get_voter_votes_prefix_key(voter: &Address) -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.voter_votes.to_owned())
+        .expect("Cannot obtain a storage key")
+        .push(voter)
+        .expect("Cannot obtain a storage key")
+}
+
+/// LazySet handler for the ids of the proposals a voter voted on
+pub fn voter_votes_handle(voter: &Address) -> LazySet<u64> {
+    LazySet::open(get_voter_votes_prefix_key(voter))
+}
+
+/// Get the prefix of the representatives of all the delegators
+pub fn get_representative_prefix_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.representative.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the representative to which a delegator delegated its
+/// voting power
+pub fn get_representative_key(delegator: &Address) -> Key {
+    get_representative_prefix_key()
+        .push(delegator)
+        .expect("Cannot obtain a storage key")
+}
+
+/// Get the key of the upgrade scheduled by an accepted upgrade proposal
+pub fn get_pending_upgrade_key() -> Key {
+    Key::from(ADDRESS.to_db_key())
+        .push(&Keys::VALUES.upgrade.to_owned())
+        .expect("Cannot obtain a storage key")
+}
+
 /// Get the proposal execution key
 pub fn get_proposal_execution_key(id: u64) -> Key {
     Key::from(ADDRESS.to_db_key())
@@ -522,3 +783,47 @@ pub fn get_voter_address(key: &Key) -> Option<&Address> {
         None => None,
     }
 }
+
+/// Get the voter address from a vote history key
+pub fn get_vote_history_voter(key: &Key) -> Option<&Address> {
+    match key.get_at(4) {
+        Some(addr) => match addr {
+            DbKeySeg::AddressSeg(res) => Some(res),
+            DbKeySeg::StringSeg(_) => None,
+        },
+        None => None,
+    }
+}
+
+/// Get the representative address from a representative vote key
+pub fn get_representative_vote_address(key: &Key) -> Option<&Address> {
+    match key.get_at(4) {
+        Some(addr) => match addr {
+            DbKeySeg::AddressSeg(res) => Some(res),
+            DbKeySeg::StringSeg(_) => None,
+        },
+        None => None,
+    }
+}
+
+/// Get the depositor address from a proposal deposit key
+pub fn get_proposal_depositor_address(key: &Key) -> Option<&Address> {
+    match key.get_at(4) {
+        Some(addr) => match addr {
+            DbKeySeg::AddressSeg(res) => Some(res),
+            DbKeySeg::StringSeg(_) => None,
+        },
+        None => None,
+    }
+}
+
+/// Get the delegator address from a representative key
+pub fn get_represented_address(key: &Key) -> Option<&Address> {
+    match key.get_at(2) {
+        Some(addr) => match addr {
+            DbKeySeg::AddressSeg(res) => Some(res),
+            DbKeySeg::StringSeg(_) => None,
+        },
+        None => None,
+    }
+}
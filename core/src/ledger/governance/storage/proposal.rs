@@ -1,18 +1,24 @@
-use std::collections::{BTreeMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fmt::Display;
+use std::str::FromStr;
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
+use crate::ibc::core::host::types::identifiers::{ChannelId, PortId};
+use crate::ledger::eth_bridge::storage::whitelist::WhitelistedErc20;
 use crate::ledger::governance::cli::onchain::{
     PgfAction, PgfContinous, PgfRetro, PgfSteward, StewardsUpdate,
 };
+use crate::ledger::governance::parameters::GovernanceParameters;
 use crate::ledger::governance::utils::{ProposalStatus, TallyType};
+use crate::ledger::ibc::rate_limit::{IbcRateLimit, RateLimit};
 use crate::ledger::storage_api::token::Amount;
 use crate::types::address::Address;
+use crate::types::dec::Dec;
 use crate::types::hash::Hash;
-use crate::types::storage::Epoch;
+use crate::types::storage::{BlockHeight, Epoch, KEY_SEGMENT_SEPARATOR};
 
 #[allow(missing_docs)]
 #[derive(Debug, Error)]
@@ -69,6 +75,98 @@ pub enum AddRemove<T> {
 }
 
 /// The target of a PGF payment
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize, Ord, Eq, PartialOrd,
+)]
+pub enum PGFTarget {
+    /// Funding target on this chain
+    Internal(PGFInternalTarget),
+    /// Funding target on another chain, paid with an ICS-20 transfer
+    Ibc(PGFIbcTarget),
+}
+
+/// The tag prefixing the encoding of the IBC PGF targets. The internal
+/// targets keep the encoding of the former `PGFTarget` struct, which starts
+/// with the tag of an `Address` variant, so that the proposals and fundings
+/// already in storage are still decoded.
+const PGF_IBC_TARGET_TAG: u8 = u8::MAX;
+
+impl BorshSerialize for PGFTarget {
+    fn serialize<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        match self {
+            PGFTarget::Internal(t) => BorshSerialize::serialize(t, writer),
+            PGFTarget::Ibc(t) => {
+                BorshSerialize::serialize(&PGF_IBC_TARGET_TAG, writer)?;
+                BorshSerialize::serialize(t, writer)
+            }
+        }
+    }
+}
+
+impl BorshDeserialize for PGFTarget {
+    fn deserialize_reader<R: std::io::Read>(
+        reader: &mut R,
+    ) -> std::io::Result<Self> {
+        use std::io::Read;
+        let tag: u8 = BorshDeserialize::deserialize_reader(reader)?;
+        if tag == PGF_IBC_TARGET_TAG {
+            Ok(PGFTarget::Ibc(BorshDeserialize::deserialize_reader(
+                reader,
+            )?))
+        } else {
+            // Put the tag back, as it belongs to the target address
+            let mut reader = (&[tag][..]).chain(reader);
+            Ok(PGFTarget::Internal(BorshDeserialize::deserialize_reader(
+                &mut reader,
+            )?))
+        }
+    }
+}
+
+impl PGFTarget {
+    /// Returns the funding target as a string, which identifies the funding
+    pub fn target(&self) -> String {
+        match self {
+            PGFTarget::Internal(t) => t.target.to_string(),
+            PGFTarget::Ibc(t) => t.target.clone(),
+        }
+    }
+
+    /// Returns the funding amount
+    pub fn amount(&self) -> Amount {
+        match self {
+            PGFTarget::Internal(t) => t.amount,
+            PGFTarget::Ibc(t) => t.amount,
+        }
+    }
+
+    /// Check that the target can be used to identify the funding in storage
+    pub fn is_valid(&self) -> bool {
+        match self {
+            PGFTarget::Internal(_) => true,
+            PGFTarget::Ibc(t) => {
+                !t.target.is_empty()
+                    && !t.target.contains(KEY_SEGMENT_SEPARATOR)
+            }
+        }
+    }
+}
+
+impl Display for PGFTarget {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PGFTarget::Internal(t) => write!(f, "{}", t.target),
+            PGFTarget::Ibc(t) => {
+                write!(f, "{} on {}/{}", t.target, t.port_id, t.channel_id)
+            }
+        }
+    }
+}
+
+/// The target of a PGF payment on this chain
 #[derive(
     Debug,
     Clone,
@@ -81,13 +179,64 @@ pub enum AddRemove<T> {
     Eq,
     PartialOrd,
 )]
-pub struct PGFTarget {
+pub struct PGFInternalTarget {
     /// The target address
     pub target: Address,
     /// The amount of token to fund the target address
     pub amount: Amount,
 }
 
+/// The target of a PGF payment on an IBC counterparty chain
+#[derive(
+    Debug, Clone, PartialEq, Serialize, Deserialize, Ord, Eq, PartialOrd,
+)]
+pub struct PGFIbcTarget {
+    /// The target address on the counterparty chain
+    pub target: String,
+    /// The amount of token to fund the target address
+    pub amount: Amount,
+    /// Port ID to fund
+    pub port_id: PortId,
+    /// Channel ID to fund
+    pub channel_id: ChannelId,
+}
+
+impl BorshSerialize for PGFIbcTarget {
+    fn serialize<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.target, writer)?;
+        BorshSerialize::serialize(&self.amount, writer)?;
+        BorshSerialize::serialize(&self.port_id.to_string(), writer)?;
+        BorshSerialize::serialize(&self.channel_id.to_string(), writer)
+    }
+}
+
+impl BorshDeserialize for PGFIbcTarget {
+    fn deserialize_reader<R: std::io::Read>(
+        reader: &mut R,
+    ) -> std::io::Result<Self> {
+        use std::io::{Error, ErrorKind};
+        let target: String = BorshDeserialize::deserialize_reader(reader)?;
+        let amount: Amount = BorshDeserialize::deserialize_reader(reader)?;
+        let port_id: String = BorshDeserialize::deserialize_reader(reader)?;
+        let port_id = PortId::from_str(&port_id).map_err(|err| {
+            Error::new(ErrorKind::InvalidData, err.to_string())
+        })?;
+        let channel_id: String = BorshDeserialize::deserialize_reader(reader)?;
+        let channel_id = ChannelId::from_str(&channel_id).map_err(|err| {
+            Error::new(ErrorKind::InvalidData, err.to_string())
+        })?;
+        Ok(Self {
+            target,
+            amount,
+            port_id,
+            channel_id,
+        })
+    }
+}
+
 /// The actions that a PGF Steward can propose to execute
 #[derive(
     Debug,
@@ -105,6 +254,431 @@ pub enum PGFAction {
     Retro(PGFTarget),
 }
 
+impl PGFAction {
+    /// Get the target of the action
+    pub fn target(&self) -> &PGFTarget {
+        match self {
+            PGFAction::Continuous(AddRemove::Add(target))
+            | PGFAction::Continuous(AddRemove::Remove(target))
+            | PGFAction::Retro(target) => target,
+        }
+    }
+}
+
+/// A new value of a protocol parameter proposed to governance. The
+/// parameters on which the validator sets and the bonds depend, such as the
+/// pipeline and unbonding lengths, cannot be changed.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum ParameterChange {
+    /// Max payload size, in bytes, for a mempool tx
+    MaxTxBytes(u32),
+    /// Max gas for block
+    MaxBlockGas(u64),
+    /// Expected number of epochs per year
+    EpochsPerYear(u64),
+    /// Maximum number of signature per transaction
+    MaxSignaturesPerTransaction(u8),
+    /// Fee unshielding gas limit
+    FeeUnshieldingGasLimit(u64),
+    /// Fee unshielding descriptions limit
+    FeeUnshieldingDescriptionsLimit(u64),
+    /// Cost per gas unit of every token allowed for fee payment
+    MinimumGasPrice(BTreeMap<Address, Amount>),
+    /// Whitelisted validity predicate hashes
+    VpWhitelist(Vec<String>),
+    /// Whitelisted tx hashes
+    TxWhitelist(Vec<String>),
+    /// PoS reward rate of the block proposer
+    BlockProposerReward(Dec),
+    /// PoS reward rate of the validators voting on a block
+    BlockVoteReward(Dec),
+    /// PoS maximum staking rewards rate per annum
+    MaxInflationRate(Dec),
+    /// PoS target ratio of staked tokens
+    TargetStakedRatio(Dec),
+    /// PoS minimum slash rate of a duplicate vote
+    DuplicateVoteMinSlashRate(Dec),
+    /// PoS minimum slash rate of a light client attack
+    LightClientAttackMinSlashRate(Dec),
+    /// PoS number of epochs considered by cubic slashing
+    CubicSlashingWindowLength(u64),
+    /// PoS minimum stake of the consensus and below-capacity validators
+    ValidatorStakeThreshold(Amount),
+    /// PoS length, in blocks, of the liveness window of the validators
+    LivenessWindowCheck(u64),
+    /// PoS minimum activity of the validators over the liveness window
+    LivenessThreshold(Dec),
+    /// PoS gain p
+    RewardsGainP(Dec),
+    /// PoS gain d
+    RewardsGainD(Dec),
+    /// Minimum amount of locked funds of a proposal
+    MinProposalFund(Amount),
+    /// Maximum kibibyte length for proposal code
+    MaxProposalCodeSize(u64),
+    /// Minimum proposal voting period in epochs
+    MinProposalVotingPeriod(u64),
+    /// Maximum proposal voting period in epochs
+    MaxProposalPeriod(u64),
+    /// Maximum number of characters for proposal content
+    MaxProposalContentSize(u64),
+    /// Minimum epochs between end and grace epochs
+    MinProposalGraceEpochs(u64),
+    /// Minimum emergency proposal voting period in epochs
+    MinEmergencyProposalVotingPeriod(u64),
+    /// Tally rule of default proposals
+    DefaultProposalTally(TallyType),
+    /// Tally rule of emergency proposals
+    EmergencyProposalTally(TallyType),
+    /// Tally rule of PGF stewards proposals
+    PgfStewardProposalTally(TallyType),
+    /// Tally rule of PGF funding proposals
+    PgfFundingProposalTally(TallyType),
+    /// Tally rule of PGF funding proposals authored by a steward
+    PgfStewardFundingProposalTally(TallyType),
+    /// Fraction of the votes that must be no with veto to veto a proposal
+    VetoThreshold(Dec),
+    /// The pgf funding inflation rate
+    PgfInflationRate(Dec),
+    /// The pgf stewards inflation rate
+    StewardsInflationRate(Dec),
+    /// Rate limits of the ICS-20 transfers of tokens through channels
+    IbcRateLimits(Vec<IbcRateLimit>),
+    /// ERC20 tokens added to or removed from the whitelist of the Ethereum
+    /// bridge, or whose cap or denomination is changed
+    Erc20Whitelist(Vec<WhitelistedErc20>),
+}
+
+impl ParameterChange {
+    /// Get the name of the changed parameter
+    pub fn name(&self) -> &'static str {
+        match self {
+            ParameterChange::MaxTxBytes(_) => "max_tx_bytes",
+            ParameterChange::MaxBlockGas(_) => "max_block_gas",
+            ParameterChange::EpochsPerYear(_) => "epochs_per_year",
+            ParameterChange::MaxSignaturesPerTransaction(_) => {
+                "max_signatures_per_transaction"
+            }
+            ParameterChange::FeeUnshieldingGasLimit(_) => {
+                "fee_unshielding_gas_limit"
+            }
+            ParameterChange::FeeUnshieldingDescriptionsLimit(_) => {
+                "fee_unshielding_descriptions_limit"
+            }
+            ParameterChange::MinimumGasPrice(_) => "minimum_gas_price",
+            ParameterChange::VpWhitelist(_) => "vp_whitelist",
+            ParameterChange::TxWhitelist(_) => "tx_whitelist",
+            ParameterChange::BlockProposerReward(_) => "block_proposer_reward",
+            ParameterChange::BlockVoteReward(_) => "block_vote_reward",
+            ParameterChange::MaxInflationRate(_) => "max_inflation_rate",
+            ParameterChange::TargetStakedRatio(_) => "target_staked_ratio",
+            ParameterChange::DuplicateVoteMinSlashRate(_) => {
+                "duplicate_vote_min_slash_rate"
+            }
+            ParameterChange::LightClientAttackMinSlashRate(_) => {
+                "light_client_attack_min_slash_rate"
+            }
+            ParameterChange::CubicSlashingWindowLength(_) => {
+                "cubic_slashing_window_length"
+            }
+            ParameterChange::ValidatorStakeThreshold(_) => {
+                "validator_stake_threshold"
+            }
+            ParameterChange::LivenessWindowCheck(_) => "liveness_window_check",
+            ParameterChange::LivenessThreshold(_) => "liveness_threshold",
+            ParameterChange::RewardsGainP(_) => "rewards_gain_p",
+            ParameterChange::RewardsGainD(_) => "rewards_gain_d",
+            ParameterChange::MinProposalFund(_) => "min_proposal_fund",
+            ParameterChange::MaxProposalCodeSize(_) => "max_proposal_code_size",
+            ParameterChange::MinProposalVotingPeriod(_) => {
+                "min_proposal_voting_period"
+            }
+            ParameterChange::MaxProposalPeriod(_) => "max_proposal_period",
+            ParameterChange::MaxProposalContentSize(_) => {
+                "max_proposal_content_size"
+            }
+            ParameterChange::MinProposalGraceEpochs(_) => {
+                "min_proposal_grace_epochs"
+            }
+            ParameterChange::MinEmergencyProposalVotingPeriod(_) => {
+                "min_emergency_proposal_voting_period"
+            }
+            ParameterChange::DefaultProposalTally(_) => {
+                "default_proposal_tally"
+            }
+            ParameterChange::EmergencyProposalTally(_) => {
+                "emergency_proposal_tally"
+            }
+            ParameterChange::PgfStewardProposalTally(_) => {
+                "pgf_steward_proposal_tally"
+            }
+            ParameterChange::PgfFundingProposalTally(_) => {
+                "pgf_funding_proposal_tally"
+            }
+            ParameterChange::PgfStewardFundingProposalTally(_) => {
+                "pgf_steward_funding_proposal_tally"
+            }
+            ParameterChange::VetoThreshold(_) => "veto_threshold",
+            ParameterChange::PgfInflationRate(_) => "pgf_inflation_rate",
+            ParameterChange::StewardsInflationRate(_) => {
+                "stewards_inflation_rate"
+            }
+            ParameterChange::IbcRateLimits(_) => "ibc_rate_limits",
+            ParameterChange::Erc20Whitelist(_) => "erc20_whitelist",
+        }
+    }
+
+    /// Check if the change sets a PoS parameter
+    pub fn is_pos(&self) -> bool {
+        matches!(
+            self,
+            ParameterChange::BlockProposerReward(_)
+                | ParameterChange::BlockVoteReward(_)
+                | ParameterChange::MaxInflationRate(_)
+                | ParameterChange::TargetStakedRatio(_)
+                | ParameterChange::DuplicateVoteMinSlashRate(_)
+                | ParameterChange::LightClientAttackMinSlashRate(_)
+                | ParameterChange::CubicSlashingWindowLength(_)
+                | ParameterChange::ValidatorStakeThreshold(_)
+                | ParameterChange::LivenessWindowCheck(_)
+                | ParameterChange::LivenessThreshold(_)
+                | ParameterChange::RewardsGainP(_)
+                | ParameterChange::RewardsGainD(_)
+        )
+    }
+
+    /// Check if the change sets a governance parameter
+    pub fn is_governance(&self) -> bool {
+        matches!(
+            self,
+            ParameterChange::MinProposalFund(_)
+                | ParameterChange::MaxProposalCodeSize(_)
+                | ParameterChange::MinProposalVotingPeriod(_)
+                | ParameterChange::MaxProposalPeriod(_)
+                | ParameterChange::MaxProposalContentSize(_)
+                | ParameterChange::MinProposalGraceEpochs(_)
+                | ParameterChange::MinEmergencyProposalVotingPeriod(_)
+                | ParameterChange::DefaultProposalTally(_)
+                | ParameterChange::EmergencyProposalTally(_)
+                | ParameterChange::PgfStewardProposalTally(_)
+                | ParameterChange::PgfFundingProposalTally(_)
+                | ParameterChange::PgfStewardFundingProposalTally(_)
+                | ParameterChange::VetoThreshold(_)
+        )
+    }
+
+    /// Check that the new value of the parameter is in its range. The
+    /// consistency with the other parameters is checked when the change is
+    /// applied.
+    pub fn is_valid(&self) -> bool {
+        let is_fraction =
+            |value: &Dec| !value.is_negative() && *value <= Dec::one();
+        let is_valid_tally = |tally: &TallyType| match tally {
+            TallyType::YayOverTotal { threshold } => is_fraction(threshold),
+            TallyType::YayOverQuorum { quorum, threshold }
+            | TallyType::NayVetoOverQuorum { quorum, threshold } => {
+                is_fraction(quorum) && is_fraction(threshold)
+            }
+        };
+        match self {
+            ParameterChange::MaxTxBytes(value) => *value > 0,
+            ParameterChange::MaxSignaturesPerTransaction(value) => *value > 0,
+            ParameterChange::MaxBlockGas(value)
+            | ParameterChange::EpochsPerYear(value)
+            | ParameterChange::LivenessWindowCheck(value)
+            | ParameterChange::MinProposalVotingPeriod(value)
+            | ParameterChange::MaxProposalPeriod(value)
+            | ParameterChange::MinEmergencyProposalVotingPeriod(value) => {
+                *value > 0
+            }
+            ParameterChange::BlockProposerReward(value)
+            | ParameterChange::BlockVoteReward(value)
+            | ParameterChange::MaxInflationRate(value)
+            | ParameterChange::TargetStakedRatio(value)
+            | ParameterChange::DuplicateVoteMinSlashRate(value)
+            | ParameterChange::LightClientAttackMinSlashRate(value)
+            | ParameterChange::LivenessThreshold(value)
+            | ParameterChange::VetoThreshold(value)
+            | ParameterChange::PgfInflationRate(value)
+            | ParameterChange::StewardsInflationRate(value) => {
+                is_fraction(value)
+            }
+            ParameterChange::RewardsGainP(value)
+            | ParameterChange::RewardsGainD(value) => !value.is_negative(),
+            ParameterChange::DefaultProposalTally(tally)
+            | ParameterChange::EmergencyProposalTally(tally)
+            | ParameterChange::PgfStewardProposalTally(tally)
+            | ParameterChange::PgfFundingProposalTally(tally)
+            | ParameterChange::PgfStewardFundingProposalTally(tally) => {
+                is_valid_tally(tally)
+            }
+            ParameterChange::FeeUnshieldingGasLimit(_)
+            | ParameterChange::FeeUnshieldingDescriptionsLimit(_)
+            | ParameterChange::MinimumGasPrice(_)
+            | ParameterChange::VpWhitelist(_)
+            | ParameterChange::TxWhitelist(_)
+            | ParameterChange::CubicSlashingWindowLength(_)
+            | ParameterChange::ValidatorStakeThreshold(_)
+            | ParameterChange::MinProposalFund(_)
+            | ParameterChange::MaxProposalCodeSize(_)
+            | ParameterChange::MaxProposalContentSize(_)
+            | ParameterChange::MinProposalGraceEpochs(_) => true,
+            ParameterChange::IbcRateLimits(rate_limits) => {
+                // Each limit is changed at most once
+                let limited = rate_limits
+                    .iter()
+                    .map(|rate_limit| {
+                        (rate_limit.channel_id.to_string(), &rate_limit.token)
+                    })
+                    .collect::<BTreeSet<_>>();
+                !rate_limits.is_empty()
+                    && limited.len() == rate_limits.len()
+                    && rate_limits.iter().all(|rate_limit| {
+                        rate_limit
+                            .limit
+                            .as_ref()
+                            .map_or(true, RateLimit::is_valid)
+                    })
+            }
+            ParameterChange::Erc20Whitelist(whitelist) => {
+                // Each token is changed at most once
+                let tokens = whitelist
+                    .iter()
+                    .map(|erc20| erc20.token_address)
+                    .collect::<BTreeSet<_>>();
+                !whitelist.is_empty() && tokens.len() == whitelist.len()
+            }
+        }
+    }
+}
+
+impl Display for ParameterChange {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: ", self.name())?;
+        match self {
+            ParameterChange::MaxTxBytes(value) => write!(f, "{}", value),
+            ParameterChange::MaxSignaturesPerTransaction(value) => {
+                write!(f, "{}", value)
+            }
+            ParameterChange::MaxBlockGas(value)
+            | ParameterChange::EpochsPerYear(value)
+            | ParameterChange::FeeUnshieldingGasLimit(value)
+            | ParameterChange::FeeUnshieldingDescriptionsLimit(value)
+            | ParameterChange::CubicSlashingWindowLength(value)
+            | ParameterChange::LivenessWindowCheck(value)
+            | ParameterChange::MaxProposalCodeSize(value)
+            | ParameterChange::MinProposalVotingPeriod(value)
+            | ParameterChange::MaxProposalPeriod(value)
+            | ParameterChange::MaxProposalContentSize(value)
+            | ParameterChange::MinProposalGraceEpochs(value)
+            | ParameterChange::MinEmergencyProposalVotingPeriod(value) => {
+                write!(f, "{}", value)
+            }
+            ParameterChange::MinimumGasPrice(prices) => write!(
+                f,
+                "{}",
+                prices
+                    .iter()
+                    .map(|(token, price)| format!(
+                        "{} per gas of {}",
+                        price, token
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ParameterChange::VpWhitelist(hashes)
+            | ParameterChange::TxWhitelist(hashes) => {
+                write!(f, "[{}]", hashes.join(", "))
+            }
+            ParameterChange::BlockProposerReward(value)
+            | ParameterChange::BlockVoteReward(value)
+            | ParameterChange::MaxInflationRate(value)
+            | ParameterChange::TargetStakedRatio(value)
+            | ParameterChange::DuplicateVoteMinSlashRate(value)
+            | ParameterChange::LightClientAttackMinSlashRate(value)
+            | ParameterChange::LivenessThreshold(value)
+            | ParameterChange::RewardsGainP(value)
+            | ParameterChange::RewardsGainD(value)
+            | ParameterChange::VetoThreshold(value)
+            | ParameterChange::PgfInflationRate(value)
+            | ParameterChange::StewardsInflationRate(value) => {
+                write!(f, "{}", value)
+            }
+            ParameterChange::ValidatorStakeThreshold(amount)
+            | ParameterChange::MinProposalFund(amount) => {
+                write!(f, "{}", amount.to_string_native())
+            }
+            ParameterChange::DefaultProposalTally(tally)
+            | ParameterChange::EmergencyProposalTally(tally)
+            | ParameterChange::PgfStewardProposalTally(tally)
+            | ParameterChange::PgfFundingProposalTally(tally)
+            | ParameterChange::PgfStewardFundingProposalTally(tally) => {
+                write!(f, "{}", tally)
+            }
+            ParameterChange::IbcRateLimits(rate_limits) => write!(
+                f,
+                "{}",
+                rate_limits
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+            ParameterChange::Erc20Whitelist(whitelist) => write!(
+                f,
+                "{}",
+                whitelist
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        }
+    }
+}
+
+/// A protocol upgrade scheduled by governance. Nodes halt at the upgrade
+/// height until they are restarted with a binary including the upgrade.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct Upgrade {
+    /// The name of the upgrade, known to the binaries that include it
+    pub name: String,
+    /// The height of the first block produced by the upgraded binaries
+    pub height: BlockHeight,
+}
+
+impl Upgrade {
+    /// Check that the upgrade has a name and is scheduled after the given
+    /// block height
+    pub fn is_valid(&self, current_height: BlockHeight) -> bool {
+        !self.name.trim().is_empty() && self.height > current_height
+    }
+}
+
+impl Display for Upgrade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} at height {}", self.name, self.height)
+    }
+}
+
 /// The type of a Proposal
 #[derive(
     Debug,
@@ -122,6 +696,13 @@ pub enum ProposalType {
     PGFSteward(HashSet<AddRemove<Address>>),
     /// PGF funding proposal
     PGFPayment(Vec<PGFAction>),
+    /// Emergency governance proposal with the optional wasm code, with a
+    /// shorter voting period and its own tally rule
+    Emergency(Option<Hash>),
+    /// Parameter change proposal, applied without wasm code
+    ParameterChange(Vec<ParameterChange>),
+    /// Upgrade proposal, halting the chain at the given height
+    Upgrade(Upgrade),
 }
 
 impl ProposalType {
@@ -129,6 +710,21 @@ impl ProposalType {
     pub fn is_default(&self) -> bool {
         matches!(self, ProposalType::Default(_))
     }
+
+    /// Check if the proposal type is emergency
+    pub fn is_emergency(&self) -> bool {
+        matches!(self, ProposalType::Emergency(_))
+    }
+
+    /// Check if the proposal type is parameter change
+    pub fn is_parameter_change(&self) -> bool {
+        matches!(self, ProposalType::ParameterChange(_))
+    }
+
+    /// Check if the proposal type is upgrade
+    pub fn is_upgrade(&self) -> bool {
+        matches!(self, ProposalType::Upgrade(_))
+    }
 }
 
 impl Display for ProposalType {
@@ -137,6 +733,9 @@ impl Display for ProposalType {
             ProposalType::Default(_) => write!(f, "Default"),
             ProposalType::PGFSteward(_) => write!(f, "Pgf steward"),
             ProposalType::PGFPayment(_) => write!(f, "Pgf funding"),
+            ProposalType::Emergency(_) => write!(f, "Emergency"),
+            ProposalType::ParameterChange(_) => write!(f, "Parameter change"),
+            ProposalType::Upgrade(_) => write!(f, "Upgrade"),
         }
     }
 }
@@ -174,16 +773,10 @@ impl TryFrom<PgfContinous> for PGFAction {
     fn try_from(value: PgfContinous) -> Result<Self, Self::Error> {
         match value.action {
             PgfAction::Add => {
-                Ok(PGFAction::Continuous(AddRemove::Add(PGFTarget {
-                    target: value.target.address,
-                    amount: value.target.amount,
-                })))
+                Ok(PGFAction::Continuous(AddRemove::Add(value.target)))
             }
             PgfAction::Remove => {
-                Ok(PGFAction::Continuous(AddRemove::Remove(PGFTarget {
-                    target: value.target.address,
-                    amount: value.target.amount,
-                })))
+                Ok(PGFAction::Continuous(AddRemove::Remove(value.target)))
             }
         }
     }
@@ -193,10 +786,7 @@ impl TryFrom<PgfRetro> for PGFAction {
     type Error = ProposalTypeError;
 
     fn try_from(value: PgfRetro) -> Result<Self, Self::Error> {
-        Ok(PGFAction::Retro(PGFTarget {
-            target: value.target.address,
-            amount: value.target.amount,
-        }))
+        Ok(PGFAction::Retro(value.target))
     }
 }
 
@@ -238,8 +828,12 @@ impl StorageProposal {
     }
 
     /// Return the type of tally for the proposal
-    pub fn get_tally_type(&self, is_steward: bool) -> TallyType {
-        TallyType::from(self.r#type.clone(), is_steward)
+    pub fn get_tally_type(
+        &self,
+        is_steward: bool,
+        parameters: &GovernanceParameters,
+    ) -> TallyType {
+        parameters.tally_type(&self.r#type, is_steward)
     }
 
     /// Return the status of a proposal
@@ -257,7 +851,7 @@ impl StorageProposal {
 
     /// Serialize a proposal to string
     pub fn to_string_with_status(&self, current_epoch: Epoch) -> String {
-        format!(
+        let mut proposal = format!(
             "Proposal Id: {}
         {:2}Type: {}
         {:2}Author: {}
@@ -282,7 +876,17 @@ impl StorageProposal {
             self.grace_epoch,
             "",
             self.get_status(current_epoch)
-        )
+        );
+        if let ProposalType::ParameterChange(changes) = &self.r#type {
+            proposal.push_str(&format!("{:2}Parameter changes:\n", ""));
+            for change in changes {
+                proposal.push_str(&format!("        {:4}{}\n", "", change));
+            }
+        }
+        if let ProposalType::Upgrade(upgrade) = &self.r#type {
+            proposal.push_str(&format!("{:2}Upgrade: {}\n", "", upgrade));
+        }
+        proposal
     }
 }
 
@@ -315,11 +919,13 @@ impl Display for StorageProposal {
 #[cfg(any(test, feature = "testing"))]
 /// Testing helpers and and strategies for governance proposals
 pub mod testing {
-    use proptest::prelude::Strategy;
-    use proptest::{collection, option, prop_compose};
+    use proptest::prelude::{any, Strategy};
+    use proptest::{collection, option, prop_compose, prop_oneof};
 
     use super::*;
+    use crate::ledger::ibc::testing::{arb_ibc_channel_id, arb_ibc_port_id};
     use crate::types::address::testing::arb_non_internal_address;
+    use crate::types::dec::testing::arb_dec;
     use crate::types::hash::testing::arb_hash;
     use crate::types::token::testing::arb_amount;
 
@@ -336,18 +942,43 @@ pub mod testing {
     }
 
     prop_compose! {
-        /// Generate an arbitrary PGF target
-        pub fn arb_pgf_target()(
+        /// Generate an arbitrary PGF internal target
+        pub fn arb_pgf_internal_target()(
             target in arb_non_internal_address(),
             amount in arb_amount(),
-        ) -> PGFTarget {
-            PGFTarget {
+        ) -> PGFInternalTarget {
+            PGFInternalTarget {
+                target,
+                amount,
+            }
+        }
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary PGF IBC target
+        pub fn arb_pgf_ibc_target()(
+            target in "[a-zA-Z0-9_]{1,64}",
+            amount in arb_amount(),
+            port_id in arb_ibc_port_id(),
+            channel_id in arb_ibc_channel_id(),
+        ) -> PGFIbcTarget {
+            PGFIbcTarget {
                 target,
                 amount,
+                port_id,
+                channel_id,
             }
         }
     }
 
+    /// Generate an arbitrary PGF target
+    pub fn arb_pgf_target() -> impl Strategy<Value = PGFTarget> {
+        arb_pgf_internal_target()
+            .prop_map(PGFTarget::Internal)
+            .boxed()
+            .prop_union(arb_pgf_ibc_target().prop_map(PGFTarget::Ibc).boxed())
+    }
+
     /// Generate an arbitrary PGF action
     pub fn arb_pgf_action() -> impl Strategy<Value = PGFAction> {
         arb_add_remove(arb_pgf_target())
@@ -356,6 +987,33 @@ pub mod testing {
             .prop_union(arb_pgf_target().prop_map(PGFAction::Retro).boxed())
     }
 
+    /// Generate an arbitrary parameter change
+    pub fn arb_parameter_change() -> impl Strategy<Value = ParameterChange> {
+        prop_oneof![
+            any::<u64>().prop_map(ParameterChange::MaxBlockGas),
+            any::<u64>().prop_map(ParameterChange::EpochsPerYear),
+            arb_dec().prop_map(ParameterChange::MaxInflationRate),
+            arb_amount().prop_map(ParameterChange::ValidatorStakeThreshold),
+            arb_amount().prop_map(ParameterChange::MinProposalFund),
+            any::<u64>().prop_map(ParameterChange::MaxProposalPeriod),
+            arb_dec().prop_map(ParameterChange::VetoThreshold),
+            arb_dec().prop_map(ParameterChange::PgfInflationRate),
+        ]
+    }
+
+    prop_compose! {
+        /// Generate an arbitrary upgrade
+        pub fn arb_upgrade()(
+            name in "[a-z0-9-]{1,20}",
+            height in any::<u64>(),
+        ) -> Upgrade {
+            Upgrade {
+                name,
+                height: BlockHeight(height),
+            }
+        }
+    }
+
     /// Generate an arbitrary proposal type
     pub fn arb_proposal_type() -> impl Strategy<Value = ProposalType> {
         option::of(arb_hash())
@@ -372,5 +1030,171 @@ pub mod testing {
             .or(collection::vec(arb_pgf_action(), 0..10)
                 .prop_map(ProposalType::PGFPayment)
                 .boxed())
+            .or(option::of(arb_hash())
+                .prop_map(ProposalType::Emergency)
+                .boxed())
+            .or(collection::vec(arb_parameter_change(), 0..10)
+                .prop_map(ProposalType::ParameterChange)
+                .boxed())
+            .or(arb_upgrade().prop_map(ProposalType::Upgrade).boxed())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh_ext::BorshSerializeExt;
+    use proptest::prelude::*;
+
+    use super::testing::{arb_pgf_internal_target, arb_pgf_target};
+    use super::*;
+
+    proptest! {
+        /// Test that the PGF targets, including the IBC ones whose encoding
+        /// is implemented by hand, are decoded back from their encoding.
+        #[test]
+        fn test_pgf_target_borsh_roundtrip(target in arb_pgf_target()) {
+            let bytes = target.serialize_to_vec();
+            prop_assert_eq!(PGFTarget::try_from_slice(&bytes).unwrap(), target);
+        }
+
+        /// Test that the internal PGF targets keep the encoding of the former
+        /// `PGFTarget` struct.
+        #[test]
+        fn test_pgf_internal_target_borsh_layout(
+            target in arb_pgf_internal_target(),
+        ) {
+            #[derive(BorshSerialize)]
+            struct FormerPGFTarget {
+                target: Address,
+                amount: Amount,
+            }
+            let former = FormerPGFTarget {
+                target: target.target.clone(),
+                amount: target.amount,
+            }
+            .serialize_to_vec();
+            let target = PGFTarget::Internal(target);
+            prop_assert_eq!(&target.serialize_to_vec(), &former);
+            prop_assert_eq!(
+                PGFTarget::try_from_slice(&former).unwrap(),
+                target
+            );
+        }
+    }
+
+    /// Test that the parameter changes of a proposal are parsed from their
+    /// JSON encoding and that their values are checked.
+    #[test]
+    fn test_parameter_changes_from_json() {
+        let changes: Vec<ParameterChange> = serde_json::from_str(
+            r#"[
+                {"max_block_gas": 20000000},
+                {"veto_threshold": "0.5"},
+                {"default_proposal_tally": {
+                    "rule": "YayOverTotal", "threshold": "0.7"
+                }}
+            ]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            changes,
+            vec![
+                ParameterChange::MaxBlockGas(20_000_000),
+                ParameterChange::VetoThreshold(Dec::new(5, 1).unwrap()),
+                ParameterChange::DefaultProposalTally(
+                    TallyType::YayOverTotal {
+                        threshold: Dec::new(7, 1).unwrap(),
+                    }
+                ),
+            ]
+        );
+        assert!(changes.iter().all(ParameterChange::is_valid));
+        assert_eq!(changes[1].to_string(), "veto_threshold: 0.5");
+
+        assert!(!ParameterChange::MaxBlockGas(0).is_valid());
+        assert!(!ParameterChange::IbcRateLimits(vec![]).is_valid());
+        assert!(!ParameterChange::VetoThreshold(Dec::two()).is_valid());
+        assert!(!ParameterChange::EmergencyProposalTally(
+            TallyType::YayOverQuorum {
+                quorum: Dec::one(),
+                threshold: -Dec::one(),
+            }
+        )
+        .is_valid());
+    }
+
+    /// Test that the IBC rate limits of a proposal are parsed from their JSON
+    /// encoding and that each limit is changed at most once.
+    #[test]
+    fn test_ibc_rate_limits_from_json() {
+        let token = crate::types::address::nam();
+        let changes: Vec<ParameterChange> = serde_json::from_str(&format!(
+            r#"[{{"ibc_rate_limits": [
+                {{"channel_id": "channel-0", "token": "{token}", "limit": {{
+                    "max_inflow": "1000", "max_outflow": "500", "window": 2
+                }}}},
+                {{"channel_id": "channel-1", "token": "{token}", "limit": null}}
+            ]}}]"#
+        ))
+        .unwrap();
+        let rate_limits = match &changes[..] {
+            [ParameterChange::IbcRateLimits(rate_limits)] => rate_limits,
+            _ => panic!("Unexpected parameter changes: {:?}", changes),
+        };
+        assert_eq!(
+            rate_limits[0].limit,
+            Some(RateLimit {
+                max_inflow: Amount::from_u64(1000),
+                max_outflow: Amount::from_u64(500),
+                window: 2,
+            })
+        );
+        assert_eq!(rate_limits[1].limit, None);
+        assert!(changes[0].is_valid());
+
+        let mut duplicated = rate_limits.clone();
+        duplicated[1].channel_id = duplicated[0].channel_id.clone();
+        assert!(!ParameterChange::IbcRateLimits(duplicated).is_valid());
+    }
+
+    /// Test that the ERC20 whitelist changes of a proposal are parsed from
+    /// their JSON encoding and that each token is changed at most once.
+    #[test]
+    fn test_erc20_whitelist_from_json() {
+        use crate::types::ethereum_events::testing::{
+            DAI_ERC20_ETH_ADDRESS, USDC_ERC20_ETH_ADDRESS,
+        };
+        use crate::types::token::DenominatedAmount;
+
+        let changes: Vec<ParameterChange> = serde_json::from_str(&format!(
+            r#"[{{"erc20_whitelist": [
+                {{"token_address": "{DAI_ERC20_ETH_ADDRESS}",
+                  "token_cap": "1000.000000000000000000"}},
+                {{"token_address": "{USDC_ERC20_ETH_ADDRESS}",
+                  "token_cap": null}}
+            ]}}]"#
+        ))
+        .unwrap();
+        let whitelist = match &changes[..] {
+            [ParameterChange::Erc20Whitelist(whitelist)] => whitelist,
+            _ => panic!("Unexpected parameter changes: {:?}", changes),
+        };
+        let cap: DenominatedAmount = "1000.000000000000000000".parse().unwrap();
+        assert_eq!(cap.denom().0, 18);
+        assert_eq!(whitelist[0].token_cap, Some(cap));
+        assert_eq!(whitelist[1].token_cap, None);
+        assert!(changes[0].is_valid());
+        assert_eq!(
+            changes[0].to_string(),
+            format!(
+                "erc20_whitelist: {DAI_ERC20_ETH_ADDRESS}: cap 1000 with 18 \
+                 decimal places, {USDC_ERC20_ETH_ADDRESS}: removed"
+            )
+        );
+
+        let mut duplicated = whitelist.clone();
+        duplicated[1].token_address = duplicated[0].token_address;
+        assert!(!ParameterChange::Erc20Whitelist(duplicated).is_valid());
+        assert!(!ParameterChange::Erc20Whitelist(vec![]).is_valid());
     }
 }
@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 
 use super::super::cli::onchain::ProposalVote;
 use super::proposal::ProposalType;
+use crate::types::address::Address;
+use crate::types::storage::{BlockHeight, Epoch};
 
 /// The type of a governance vote with the optional associated Memo
 #[derive(
@@ -44,6 +46,8 @@ pub enum StorageProposalVote {
     Nay,
     /// Abstain
     Abstain,
+    /// No, asking for the deposit of the proposal to be burned
+    NoWithVeto,
 }
 
 impl StorageProposalVote {
@@ -62,6 +66,11 @@ impl StorageProposalVote {
         matches!(self, StorageProposalVote::Abstain)
     }
 
+    /// Check if a vote is no with veto
+    pub fn is_no_with_veto(&self) -> bool {
+        matches!(self, StorageProposalVote::NoWithVeto)
+    }
+
     /// Check if two votes are equal
     pub fn is_same_side(&self, other: &Self) -> bool {
         std::mem::discriminant(self) == std::mem::discriminant(other)
@@ -82,6 +91,7 @@ impl StorageProposalVote {
             StorageProposalVote::Yay(vote_type) => proposal_type.eq(vote_type),
             StorageProposalVote::Nay => true,
             StorageProposalVote::Abstain => true,
+            StorageProposalVote::NoWithVeto => true,
         }
     }
 
@@ -91,29 +101,63 @@ impl StorageProposalVote {
         proposal_type: &ProposalType,
     ) -> Option<Self> {
         match (proposal_vote, proposal_type) {
-            (ProposalVote::Yay, ProposalType::Default(_)) => {
-                Some(StorageProposalVote::Yay(VoteType::Default))
-            }
+            (
+                ProposalVote::Yay,
+                ProposalType::Default(_)
+                | ProposalType::Emergency(_)
+                | ProposalType::ParameterChange(_)
+                | ProposalType::Upgrade(_),
+            ) => Some(StorageProposalVote::Yay(VoteType::Default)),
             (ProposalVote::Yay, ProposalType::PGFSteward(_)) => {
                 Some(StorageProposalVote::Yay(VoteType::PGFSteward))
             }
             (ProposalVote::Yay, ProposalType::PGFPayment(_)) => {
                 Some(StorageProposalVote::Yay(VoteType::PGFPayment))
             }
-            (ProposalVote::Nay, ProposalType::Default(_)) => {
-                Some(StorageProposalVote::Nay)
-            }
+            (
+                ProposalVote::Nay,
+                ProposalType::Default(_)
+                | ProposalType::Emergency(_)
+                | ProposalType::ParameterChange(_)
+                | ProposalType::Upgrade(_),
+            ) => Some(StorageProposalVote::Nay),
             (ProposalVote::Nay, ProposalType::PGFSteward(_)) => {
                 Some(StorageProposalVote::Nay)
             }
             (ProposalVote::Nay, ProposalType::PGFPayment(_)) => {
                 Some(StorageProposalVote::Nay)
             }
+            (ProposalVote::NoWithVeto, _) => {
+                Some(StorageProposalVote::NoWithVeto)
+            }
             _ => None,
         }
     }
 }
 
+/// A vote cast by a voter on a proposal, kept in the history of its votes
+/// such that the changes of a vote can be followed
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct VoteRecord {
+    /// The vote
+    pub vote: StorageProposalVote,
+    /// The validators of the delegations the vote applies to
+    pub delegations: Vec<Address>,
+    /// The epoch in which the vote was cast
+    pub epoch: Epoch,
+    /// The height of the block in which the vote was cast
+    pub height: BlockHeight,
+}
+
 impl Display for StorageProposalVote {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -125,6 +169,7 @@ impl Display for StorageProposalVote {
 
             StorageProposalVote::Nay => write!(f, "nay"),
             StorageProposalVote::Abstain => write!(f, "abstain"),
+            StorageProposalVote::NoWithVeto => write!(f, "no-with-veto"),
         }
     }
 }
@@ -132,7 +177,10 @@ impl Display for StorageProposalVote {
 impl PartialEq<VoteType> for ProposalType {
     fn eq(&self, other: &VoteType) -> bool {
         match self {
-            Self::Default(_) => {
+            Self::Default(_)
+            | Self::Emergency(_)
+            | Self::ParameterChange(_)
+            | Self::Upgrade(_) => {
                 matches!(other, VoteType::Default)
             }
             Self::PGFSteward(_) => {
@@ -172,5 +220,6 @@ pub mod testing {
             .boxed()
             .prop_union(Just(StorageProposalVote::Nay).boxed())
             .or(Just(StorageProposalVote::Abstain).boxed())
+            .or(Just(StorageProposalVote::NoWithVeto).boxed())
     }
 }
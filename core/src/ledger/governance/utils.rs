@@ -2,11 +2,12 @@ use std::collections::HashMap;
 use std::fmt::Display;
 
 use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
 
 use super::cli::offline::OfflineVote;
-use super::storage::proposal::ProposalType;
 use super::storage::vote::StorageProposalVote;
 use crate::types::address::Address;
+use crate::types::dec::Dec;
 use crate::types::storage::Epoch;
 use crate::types::token;
 
@@ -51,35 +52,123 @@ impl Vote {
     }
 }
 
-/// Represent a tally type
+/// Structure representing the vote of a representative, counted for a
+/// delegator that delegated its voting power to it
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct RepresentedVote {
+    /// Field holding the address of the delegator
+    pub delegator: Address,
+    /// Field holding the address of the representative
+    pub representative: Address,
+    /// Field holding vote data
+    pub data: StorageProposalVote,
+}
+
+/// Structure representing a delegator whose vote, cast directly or by its
+/// representative, overrides the vote of one of its validators for the stake
+/// it delegated to it
+#[derive(Debug, Clone, BorshSerialize, BorshDeserialize)]
+pub struct VoteOverride {
+    /// Field holding the address of the delegator
+    pub delegator: Address,
+    /// Field holding the address of the validator
+    pub validator: Address,
+    /// Field holding the address of the representative, if the vote is the
+    /// one of the representative of the delegator
+    pub representative: Option<Address>,
+    /// Field holding the vote counted for the delegated stake
+    pub data: StorageProposalVote,
+    /// Field holding the vote of the validator, if it voted
+    pub validator_data: Option<StorageProposalVote>,
+}
+
+/// The rule by which the votes of a proposal are tallied. The quorums and
+/// thresholds are fractions of the voting power.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+#[serde(tag = "rule")]
 pub enum TallyType {
-    /// Represent a tally type for proposal requiring 2/3 of the total voting
-    /// power to be yay
-    TwoThirds,
-    /// Represent a tally type for proposal requiring 1/2 of yay votes over at
-    /// least 1/3 of the voting power
-    OneHalfOverOneThird,
-    /// Represent a tally type for proposal requiring less than 1/2 of nay
-    /// votes over at least 1/3 of the voting power
-    LessOneHalfOverOneThirdNay,
+    /// The proposal passes if the yay votes reach the threshold of the total
+    /// voting power
+    YayOverTotal {
+        /// Fraction of the total voting power that must vote yay
+        threshold: Dec,
+    },
+    /// The proposal passes if the votes reach the quorum of the total voting
+    /// power and the yay votes reach the threshold of the non-abstained
+    /// votes
+    YayOverQuorum {
+        /// Fraction of the total voting power that must vote
+        quorum: Dec,
+        /// Fraction of the non-abstained votes that must be yay
+        threshold: Dec,
+    },
+    /// The proposal passes unless the votes reach the quorum of the total
+    /// voting power and the nay votes reach the threshold of the
+    /// non-abstained votes
+    NayVetoOverQuorum {
+        /// Fraction of the total voting power that must vote to reject
+        quorum: Dec,
+        /// Fraction of the non-abstained votes that must be nay to reject
+        threshold: Dec,
+    },
 }
 
-impl TallyType {
-    /// Compute the type of tally for a proposal
-    pub fn from(proposal_type: ProposalType, is_steward: bool) -> Self {
-        match (proposal_type, is_steward) {
-            (ProposalType::Default(_), _) => TallyType::TwoThirds,
-            (ProposalType::PGFSteward(_), _) => TallyType::OneHalfOverOneThird,
-            (ProposalType::PGFPayment(_), true) => {
-                TallyType::LessOneHalfOverOneThirdNay
-            }
-            (ProposalType::PGFPayment(_), false) => {
-                TallyType::OneHalfOverOneThird
-            }
+impl Display for TallyType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TallyType::YayOverTotal { threshold } => write!(
+                f,
+                "passes with yay votes of at least {} of the total voting \
+                 power",
+                threshold
+            ),
+            TallyType::YayOverQuorum { quorum, threshold } => write!(
+                f,
+                "passes with yay votes of at least {} of the non-abstained \
+                 votes and a quorum of {} of the total voting power",
+                threshold, quorum
+            ),
+            TallyType::NayVetoOverQuorum { quorum, threshold } => write!(
+                f,
+                "passes unless nay votes reach {} of the non-abstained votes \
+                 with a quorum of {} of the total voting power",
+                threshold, quorum
+            ),
         }
     }
 }
 
+impl TallyType {
+    /// Check if the votes reach the participation required by the tally: the
+    /// quorum of the tally types that have one, or the yay threshold of the
+    /// total voting power otherwise
+    pub fn has_quorum(
+        &self,
+        total_votes: VotePower,
+        total_voting_power: VotePower,
+    ) -> bool {
+        let quorum = match self {
+            TallyType::YayOverTotal { threshold } => threshold,
+            TallyType::YayOverQuorum { quorum, .. }
+            | TallyType::NayVetoOverQuorum { quorum, .. } => quorum,
+        };
+        total_votes >= total_voting_power.mul_ceil(*quorum)
+    }
+}
+
 /// The result of a proposal
 #[derive(Copy, Clone, BorshSerialize, BorshDeserialize)]
 pub enum TallyResult {
@@ -87,6 +176,8 @@ pub enum TallyResult {
     Passed,
     /// Proposal was rejected
     Rejected,
+    /// Proposal was rejected by enough no with veto votes to burn its deposit
+    Vetoed,
 }
 
 impl Display for TallyResult {
@@ -94,6 +185,7 @@ impl Display for TallyResult {
         match self {
             TallyResult::Passed => write!(f, "passed"),
             TallyResult::Rejected => write!(f, "rejected"),
+            TallyResult::Vetoed => write!(f, "vetoed"),
         }
     }
 }
@@ -107,29 +199,26 @@ impl TallyResult {
         abstain_voting_power: VotePower,
         total_voting_power: VotePower,
     ) -> Self {
+        let total_votes =
+            yay_voting_power + nay_voting_power + abstain_voting_power;
+        let non_abstained_votes = yay_voting_power + nay_voting_power;
         let passed = match tally_type {
-            TallyType::TwoThirds => {
-                yay_voting_power >= total_voting_power * 2 / 3
+            TallyType::YayOverTotal { threshold } => {
+                yay_voting_power >= total_voting_power.mul_ceil(*threshold)
             }
-            TallyType::OneHalfOverOneThird => {
-                let at_least_one_third_voted =
-                    yay_voting_power + nay_voting_power + abstain_voting_power
-                        >= total_voting_power / 3;
-
-                // At least half of non-abstained votes are yay
-                let at_last_half_voted_yay =
-                    yay_voting_power >= nay_voting_power;
-                at_least_one_third_voted && at_last_half_voted_yay
+            TallyType::YayOverQuorum { threshold, .. } => {
+                let has_quorum =
+                    tally_type.has_quorum(total_votes, total_voting_power);
+                let has_yay_threshold = yay_voting_power
+                    >= non_abstained_votes.mul_ceil(*threshold);
+                has_quorum && has_yay_threshold
             }
-            TallyType::LessOneHalfOverOneThirdNay => {
-                let less_one_third_voted =
-                    yay_voting_power + nay_voting_power + abstain_voting_power
-                        < total_voting_power / 3;
-
-                // More than half of non-abstained votes are yay
-                let more_than_half_voted_yay =
-                    yay_voting_power > nay_voting_power;
-                less_one_third_voted || more_than_half_voted_yay
+            TallyType::NayVetoOverQuorum { threshold, .. } => {
+                let has_quorum =
+                    tally_type.has_quorum(total_votes, total_voting_power);
+                let has_nay_threshold = nay_voting_power
+                    >= non_abstained_votes.mul_ceil(*threshold);
+                !(has_quorum && has_nay_threshold)
             }
         };
 
@@ -146,10 +235,13 @@ pub struct ProposalResult {
     pub total_voting_power: VotePower,
     /// The total voting power from yay votes
     pub total_yay_power: VotePower,
-    /// The total voting power from nay votes
+    /// The total voting power from nay votes, including the no with veto
+    /// ones
     pub total_nay_power: VotePower,
     /// The total voting power from abstained votes
     pub total_abstain_power: VotePower,
+    /// The total voting power from no with veto votes
+    pub total_no_with_veto_power: VotePower,
 }
 
 impl Display for ProposalResult {
@@ -234,6 +326,14 @@ impl TallyVote {
         }
     }
 
+    /// Check if a vote is no with veto
+    pub fn is_no_with_veto(&self) -> bool {
+        match self {
+            TallyVote::OnChain(vote) => vote.is_no_with_veto(),
+            TallyVote::Offline(vote) => vote.is_no_with_veto(),
+        }
+    }
+
     /// Check if two votes are equal, returns an error if the variants of the
     /// two instances are different
     pub fn is_same_side(
@@ -254,6 +354,7 @@ impl TallyVote {
 
 /// Proposal structure holding votes information necessary to compute the
 /// outcome
+#[derive(Default)]
 pub struct ProposalVotes {
     /// Map from validator address to vote
     pub validators_vote: HashMap<Address, TallyVote>,
@@ -265,26 +366,80 @@ pub struct ProposalVotes {
     pub delegator_voting_power: HashMap<Address, HashMap<Address, VotePower>>,
 }
 
-/// Compute the result of a proposal
+impl ProposalVotes {
+    /// Count the vote of a representative as the vote of a delegator that
+    /// delegated its voting power to it, with the given voting power for each
+    /// of its validators. The vote is ignored if the delegator voted directly.
+    pub fn add_represented_vote(
+        &mut self,
+        delegator: Address,
+        vote: TallyVote,
+        voting_power: HashMap<Address, VotePower>,
+    ) {
+        if self.delegators_vote.contains_key(&delegator) {
+            return;
+        }
+        self.delegators_vote.insert(delegator.clone(), vote);
+        self.delegator_voting_power.insert(delegator, voting_power);
+    }
+}
+
+/// The voting power accumulated by each side of a proposal
+#[derive(Default)]
+struct VotingPowers {
+    yay: VotePower,
+    nay: VotePower,
+    abstain: VotePower,
+    no_with_veto: VotePower,
+}
+
+impl VotingPowers {
+    /// Get the voting power accumulated by the side of the given vote
+    fn side_mut(&mut self, vote: &TallyVote) -> Option<&mut VotePower> {
+        if vote.is_yay() {
+            Some(&mut self.yay)
+        } else if vote.is_nay() {
+            Some(&mut self.nay)
+        } else if vote.is_abstain() {
+            Some(&mut self.abstain)
+        } else if vote.is_no_with_veto() {
+            Some(&mut self.no_with_veto)
+        } else {
+            None
+        }
+    }
+
+    /// Add voting power to the side of the given vote
+    fn add(&mut self, vote: &TallyVote, voting_power: VotePower) {
+        if let Some(side) = self.side_mut(vote) {
+            *side += voting_power;
+        }
+    }
+
+    /// Remove voting power from the side of the given vote
+    fn sub(&mut self, vote: &TallyVote, voting_power: VotePower) {
+        if let Some(side) = self.side_mut(vote) {
+            *side -= voting_power;
+        }
+    }
+}
+
+/// Compute the result of a proposal. No with veto votes count as nay votes
+/// and the proposal is vetoed if the votes reach the participation required
+/// by the tally and the no with veto ones reach the veto threshold of all the
+/// votes.
 pub fn compute_proposal_result(
     votes: ProposalVotes,
     total_voting_power: VotePower,
     tally_at: TallyType,
+    veto_threshold: Dec,
 ) -> ProposalResult {
-    let mut yay_voting_power = VotePower::default();
-    let mut nay_voting_power = VotePower::default();
-    let mut abstain_voting_power = VotePower::default();
+    let mut powers = VotingPowers::default();
 
     for (address, vote_power) in votes.validator_voting_power {
         let vote_type = votes.validators_vote.get(&address);
         if let Some(vote) = vote_type {
-            if vote.is_yay() {
-                yay_voting_power += vote_power;
-            } else if vote.is_nay() {
-                nay_voting_power += vote_power;
-            } else if vote.is_abstain() {
-                abstain_voting_power += vote_power;
-            }
+            powers.add(vote, vote_power);
         }
     }
 
@@ -314,57 +469,44 @@ pub fn compute_proposal_result(
                                 total_yay_power: VotePower::default(),
                                 total_nay_power: VotePower::default(),
                                 total_abstain_power: VotePower::default(),
+                                total_no_with_veto_power: VotePower::default(),
                             };
                         }
                     };
                 if !validator_vote_is_same_side {
-                    if delegator_vote.is_yay() {
-                        yay_voting_power += voting_power;
-                        if validator_vote.is_nay() {
-                            nay_voting_power -= voting_power;
-                        } else if validator_vote.is_abstain() {
-                            abstain_voting_power -= voting_power;
-                        }
-                    } else if delegator_vote.is_nay() {
-                        nay_voting_power += voting_power;
-                        if validator_vote.is_yay() {
-                            yay_voting_power -= voting_power;
-                        } else if validator_vote.is_abstain() {
-                            abstain_voting_power -= voting_power;
-                        }
-                    } else if delegator_vote.is_abstain() {
-                        abstain_voting_power += voting_power;
-                        if validator_vote.is_yay() {
-                            yay_voting_power -= voting_power;
-                        } else if validator_vote.is_nay() {
-                            nay_voting_power -= voting_power;
-                        }
-                    }
+                    powers.add(delegator_vote, voting_power);
+                    powers.sub(validator_vote, voting_power);
                 }
-            } else if delegator_vote.is_yay() {
-                yay_voting_power += voting_power;
-            } else if delegator_vote.is_nay() {
-                nay_voting_power += voting_power;
-            } else if delegator_vote.is_abstain() {
-                abstain_voting_power += voting_power;
+            } else {
+                powers.add(delegator_vote, voting_power);
             }
         }
     }
 
-    let tally_result = TallyResult::new(
-        &tally_at,
-        yay_voting_power,
-        nay_voting_power,
-        abstain_voting_power,
-        total_voting_power,
-    );
+    let nay_voting_power = powers.nay + powers.no_with_veto;
+    let total_votes = powers.yay + nay_voting_power + powers.abstain;
+    let is_vetoed = !powers.no_with_veto.is_zero()
+        && tally_at.has_quorum(total_votes, total_voting_power)
+        && powers.no_with_veto >= total_votes.mul_ceil(veto_threshold);
+    let tally_result = if is_vetoed {
+        TallyResult::Vetoed
+    } else {
+        TallyResult::new(
+            &tally_at,
+            powers.yay,
+            nay_voting_power,
+            powers.abstain,
+            total_voting_power,
+        )
+    };
 
     ProposalResult {
         result: tally_result,
         total_voting_power,
-        total_yay_power: yay_voting_power,
+        total_yay_power: powers.yay,
         total_nay_power: nay_voting_power,
-        total_abstain_power: abstain_voting_power,
+        total_abstain_power: powers.abstain,
+        total_no_with_veto_power: powers.no_with_veto,
     }
 }
 
@@ -383,3 +525,255 @@ pub fn is_valid_validator_voting_period(
         current_epoch <= voting_start_epoch + two_third_duration
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::governance::storage::vote::VoteType;
+    use crate::types::address::testing::{
+        established_address_1, established_address_2, established_address_3,
+    };
+
+    /// Test that the vote of a representative overrides the vote of the
+    /// validator of a delegator, unless the delegator voted directly.
+    #[test]
+    fn test_represented_votes() {
+        let validator = established_address_1();
+        let represented = established_address_2();
+        let direct_voter = established_address_3();
+
+        let mut votes = ProposalVotes {
+            validators_vote: HashMap::from([(
+                validator.clone(),
+                StorageProposalVote::Nay.into(),
+            )]),
+            validator_voting_power: HashMap::from([(
+                validator.clone(),
+                VotePower::from_u64(100),
+            )]),
+            delegators_vote: HashMap::from([(
+                direct_voter.clone(),
+                StorageProposalVote::Nay.into(),
+            )]),
+            delegator_voting_power: HashMap::from([(
+                direct_voter.clone(),
+                HashMap::from([(validator.clone(), VotePower::from_u64(20))]),
+            )]),
+        };
+        for delegator in [represented, direct_voter] {
+            votes.add_represented_vote(
+                delegator,
+                StorageProposalVote::Yay(VoteType::Default).into(),
+                HashMap::from([(validator.clone(), VotePower::from_u64(30))]),
+            );
+        }
+
+        let result = compute_proposal_result(
+            votes,
+            VotePower::from_u64(100),
+            TallyType::YayOverTotal {
+                threshold: Dec::two() / 3,
+            },
+            Dec::one() / 3,
+        );
+        assert_eq!(result.total_yay_power, VotePower::from_u64(30));
+        assert_eq!(result.total_nay_power, VotePower::from_u64(70));
+        assert!(matches!(result.result, TallyResult::Rejected));
+    }
+
+    /// Test that the tally rules pass or reject proposals according to their
+    /// quorum and threshold.
+    #[test]
+    fn test_tally_types() {
+        let total = VotePower::from_u64(90);
+        let one_third = Dec::one() / 3;
+        let one_half = Dec::new(5, 1).unwrap();
+        let is_passed =
+            |tally_type: TallyType, yay: u64, nay: u64, abstain: u64| {
+                matches!(
+                    TallyResult::new(
+                        &tally_type,
+                        VotePower::from_u64(yay),
+                        VotePower::from_u64(nay),
+                        VotePower::from_u64(abstain),
+                        total,
+                    ),
+                    TallyResult::Passed
+                )
+            };
+
+        let yay_over_total = TallyType::YayOverTotal {
+            threshold: Dec::two() / 3,
+        };
+        assert!(is_passed(yay_over_total, 60, 0, 0));
+        assert!(!is_passed(yay_over_total, 59, 0, 31));
+
+        let yay_over_quorum = TallyType::YayOverQuorum {
+            quorum: one_third,
+            threshold: one_half,
+        };
+        assert!(is_passed(yay_over_quorum, 10, 10, 10));
+        assert!(!is_passed(yay_over_quorum, 10, 11, 10));
+        assert!(!is_passed(yay_over_quorum, 10, 0, 19));
+
+        let nay_veto_over_quorum = TallyType::NayVetoOverQuorum {
+            quorum: one_third,
+            threshold: one_half,
+        };
+        assert!(is_passed(nay_veto_over_quorum, 0, 29, 0));
+        assert!(is_passed(nay_veto_over_quorum, 16, 15, 0));
+        assert!(!is_passed(nay_veto_over_quorum, 15, 15, 0));
+    }
+
+    /// Test that the fractions of the voting power are rounded up, such that
+    /// a proposal needs at least two thirds of the voting power to pass.
+    #[test]
+    fn test_tally_threshold_rounding() {
+        let total = VotePower::from_u64(100);
+        let tally_type = TallyType::YayOverTotal {
+            threshold: Dec::two() / 3,
+        };
+        let tally = |yay: u64| {
+            TallyResult::new(
+                &tally_type,
+                VotePower::from_u64(yay),
+                VotePower::zero(),
+                VotePower::zero(),
+                total,
+            )
+        };
+        // 66 is below two thirds of 100, even though the former threshold of
+        // `total * 2 / 3` was rounded down to it
+        assert!(matches!(tally(66), TallyResult::Rejected));
+        assert!(matches!(tally(67), TallyResult::Passed));
+
+        let tally_type = TallyType::YayOverQuorum {
+            quorum: Dec::one() / 3,
+            threshold: Dec::new(5, 1).unwrap(),
+        };
+        let tally = |yay: u64| {
+            TallyResult::new(
+                &tally_type,
+                VotePower::from_u64(yay),
+                VotePower::zero(),
+                VotePower::zero(),
+                total,
+            )
+        };
+        assert!(matches!(tally(33), TallyResult::Rejected));
+        assert!(matches!(tally(34), TallyResult::Passed));
+    }
+
+    /// Test that no with veto votes count as nay votes and veto the proposal
+    /// once they reach the veto threshold of all the votes.
+    #[test]
+    fn test_no_with_veto_votes() {
+        let validator_1 = established_address_1();
+        let validator_2 = established_address_2();
+        let delegator = established_address_3();
+        let votes = |delegator_vote: StorageProposalVote| ProposalVotes {
+            validators_vote: HashMap::from([
+                (
+                    validator_1.clone(),
+                    StorageProposalVote::Yay(VoteType::Default).into(),
+                ),
+                (validator_2.clone(), StorageProposalVote::NoWithVeto.into()),
+            ]),
+            validator_voting_power: HashMap::from([
+                (validator_1.clone(), VotePower::from_u64(70)),
+                (validator_2.clone(), VotePower::from_u64(30)),
+            ]),
+            delegators_vote: HashMap::from([(
+                delegator.clone(),
+                delegator_vote.into(),
+            )]),
+            delegator_voting_power: HashMap::from([(
+                delegator.clone(),
+                HashMap::from([(validator_1.clone(), VotePower::from_u64(10))]),
+            )]),
+        };
+        let tally_type = TallyType::YayOverTotal {
+            threshold: Dec::new(6, 1).unwrap(),
+        };
+        let veto_threshold = Dec::one() / 3;
+
+        let result = compute_proposal_result(
+            votes(StorageProposalVote::Yay(VoteType::Default)),
+            VotePower::from_u64(100),
+            tally_type,
+            veto_threshold,
+        );
+        assert_eq!(result.total_nay_power, VotePower::from_u64(30));
+        assert_eq!(result.total_no_with_veto_power, VotePower::from_u64(30));
+        assert!(matches!(result.result, TallyResult::Passed));
+
+        let result = compute_proposal_result(
+            votes(StorageProposalVote::NoWithVeto),
+            VotePower::from_u64(100),
+            tally_type,
+            veto_threshold,
+        );
+        assert_eq!(result.total_yay_power, VotePower::from_u64(60));
+        assert_eq!(result.total_nay_power, VotePower::from_u64(40));
+        assert_eq!(result.total_no_with_veto_power, VotePower::from_u64(40));
+        assert!(matches!(result.result, TallyResult::Vetoed));
+    }
+
+    /// Test that no with veto votes don't veto a proposal whose votes don't
+    /// reach the participation required by the tally.
+    #[test]
+    fn test_no_with_veto_votes_low_turnout() {
+        let validator_1 = established_address_1();
+        let validator_2 = established_address_2();
+        let votes = || ProposalVotes {
+            validators_vote: HashMap::from([
+                (
+                    validator_1.clone(),
+                    StorageProposalVote::Yay(VoteType::Default).into(),
+                ),
+                (validator_2.clone(), StorageProposalVote::NoWithVeto.into()),
+            ]),
+            validator_voting_power: HashMap::from([
+                (validator_1.clone(), VotePower::from_u64(10)),
+                (validator_2.clone(), VotePower::from_u64(20)),
+            ]),
+            delegators_vote: HashMap::new(),
+            delegator_voting_power: HashMap::new(),
+        };
+        let veto_threshold = Dec::one() / 3;
+
+        for tally_type in [
+            TallyType::YayOverTotal {
+                threshold: Dec::two() / 3,
+            },
+            TallyType::YayOverQuorum {
+                quorum: Dec::one() / 3,
+                threshold: Dec::new(5, 1).unwrap(),
+            },
+        ] {
+            let result = compute_proposal_result(
+                votes(),
+                VotePower::from_u64(100),
+                tally_type,
+                veto_threshold,
+            );
+            assert_eq!(
+                result.total_no_with_veto_power,
+                VotePower::from_u64(20)
+            );
+            assert!(matches!(result.result, TallyResult::Rejected));
+        }
+
+        // The same votes veto the proposal once they reach the quorum
+        let result = compute_proposal_result(
+            votes(),
+            VotePower::from_u64(90),
+            TallyType::YayOverQuorum {
+                quorum: Dec::one() / 3,
+                threshold: Dec::new(5, 1).unwrap(),
+            },
+            veto_threshold,
+        );
+        assert!(matches!(result.result, TallyResult::Vetoed));
+    }
+}
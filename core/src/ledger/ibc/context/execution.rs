@@ -5,7 +5,7 @@ use super::common::IbcCommonContext;
 use super::IbcContext;
 use crate::ibc::core::channel::types::channel::ChannelEnd;
 use crate::ibc::core::channel::types::commitment::{
-    AcknowledgementCommitment, PacketCommitment,
+    compute_ack_commitment, AcknowledgementCommitment, PacketCommitment,
 };
 use crate::ibc::core::channel::types::packet::Receipt;
 use crate::ibc::core::client::context::ClientExecutionContext;
@@ -23,6 +23,7 @@ use crate::ibc::core::host::types::path::{
 };
 use crate::ibc::core::host::ExecutionContext;
 use crate::ibc::primitives::Timestamp;
+use crate::ledger::ibc::forward::{is_ack_pending, success_ack};
 use crate::ledger::ibc::storage;
 
 impl<C> ClientExecutionContext for IbcContext<C>
@@ -192,6 +193,18 @@ where
         path: &AckPath,
         ack_commitment: AcknowledgementCommitment,
     ) -> Result<(), ContextError> {
+        // The acknowledgement of a forwarded packet is written once the
+        // forward is resolved
+        if ack_commitment == compute_ack_commitment(&success_ack())
+            && is_ack_pending(
+                &*self.inner.borrow(),
+                &path.port_id,
+                &path.channel_id,
+                path.sequence,
+            )?
+        {
+            return Ok(());
+        }
         self.inner.borrow_mut().store_packet_ack(
             &path.port_id,
             &path.channel_id,
@@ -257,6 +270,18 @@ where
     }
 
     fn emit_ibc_event(&mut self, event: IbcEvent) -> Result<(), ContextError> {
+        if let IbcEvent::WriteAcknowledgement(write_ack) = &event {
+            if *write_ack.acknowledgement() == success_ack()
+                && is_ack_pending(
+                    &*self.inner.borrow(),
+                    write_ack.port_id_on_b(),
+                    write_ack.chan_id_on_b(),
+                    *write_ack.seq_on_a(),
+                )?
+            {
+                return Ok(());
+            }
+        }
         let event = event.try_into().expect("The event should be converted");
         self.inner
             .borrow_mut()
@@ -14,6 +14,7 @@ use crate::ibc::core::handler::types::error::ContextError;
 use crate::ibc::core::host::types::identifiers::{ChannelId, PortId};
 use crate::ledger::ibc::storage;
 use crate::ledger::storage_api::token::read_denom;
+use crate::ledger::storage_api::StorageRead;
 use crate::types::address::{Address, InternalAddress};
 use crate::types::token;
 use crate::types::uint::Uint;
@@ -24,7 +25,7 @@ pub struct TokenTransferContext<C>
 where
     C: IbcCommonContext,
 {
-    inner: Rc<RefCell<C>>,
+    pub(crate) inner: Rc<RefCell<C>>,
 }
 
 impl<C> TokenTransferContext<C>
@@ -38,37 +39,48 @@ where
 
     /// Get the token address and the amount from PrefixedCoin. If the base
     /// denom is not an address, it returns `IbcToken`
-    fn get_token_amount(
+    pub(crate) fn get_token_amount(
         &self,
         coin: &PrefixedCoin,
     ) -> Result<(Address, token::DenominatedAmount), TokenTransferError> {
-        let token = match Address::decode(coin.denom.base_denom.as_str()) {
-            Ok(token_addr) if coin.denom.trace_path.is_empty() => token_addr,
-            _ => storage::ibc_token(coin.denom.to_string()),
-        };
-
-        // Convert IBC amount to Namada amount for the token
-        let denom = read_denom(&*self.inner.borrow(), &token)
-            .map_err(ContextError::from)?
-            .unwrap_or(token::Denomination(0));
-        let uint_amount = Uint(primitive_types::U256::from(coin.amount).0);
-        let amount =
-            token::Amount::from_uint(uint_amount, denom).map_err(|e| {
-                TokenTransferError::ContextError(
-                    ChannelError::Other {
-                        description: format!(
-                            "The IBC amount is invalid: Coin {coin}, Error {e}",
-                        ),
-                    }
-                    .into(),
-                )
-            })?;
-        let amount = token::DenominatedAmount::new(amount, denom);
-
-        Ok((token, amount))
+        get_token_amount(&*self.inner.borrow(), coin)
     }
 }
 
+/// Get the token address and the amount from PrefixedCoin. If the base denom is
+/// not an address, it returns `IbcToken`
+pub(crate) fn get_token_amount<S>(
+    storage: &S,
+    coin: &PrefixedCoin,
+) -> Result<(Address, token::DenominatedAmount), TokenTransferError>
+where
+    S: StorageRead,
+{
+    let token = match Address::decode(coin.denom.base_denom.as_str()) {
+        Ok(token_addr) if coin.denom.trace_path.is_empty() => token_addr,
+        _ => storage::ibc_token(coin.denom.to_string()),
+    };
+
+    // Convert IBC amount to Namada amount for the token
+    let denom = read_denom(storage, &token)
+        .map_err(ContextError::from)?
+        .unwrap_or(token::Denomination(0));
+    let uint_amount = Uint(primitive_types::U256::from(coin.amount).0);
+    let amount = token::Amount::from_uint(uint_amount, denom).map_err(|e| {
+        TokenTransferError::ContextError(
+            ChannelError::Other {
+                description: format!(
+                    "The IBC amount is invalid: Coin {coin}, Error {e}",
+                ),
+            }
+            .into(),
+        )
+    })?;
+    let amount = token::DenominatedAmount::new(amount, denom);
+
+    Ok((token, amount))
+}
+
 impl<C> TokenTransferValidationContext for TokenTransferContext<C>
 where
     C: IbcCommonContext,
@@ -18,8 +18,11 @@ use crate::ibc::apps::transfer::module::{
     on_timeout_packet_validate,
 };
 use crate::ibc::apps::transfer::types::error::TokenTransferError;
+use crate::ibc::apps::transfer::types::packet::PacketData;
 use crate::ibc::apps::transfer::types::MODULE_ID_STR;
-use crate::ibc::core::channel::types::acknowledgement::Acknowledgement;
+use crate::ibc::core::channel::types::acknowledgement::{
+    Acknowledgement, AcknowledgementStatus, StatusValue,
+};
 use crate::ibc::core::channel::types::channel::{Counterparty, Order};
 use crate::ibc::core::channel::types::error::{ChannelError, PacketError};
 use crate::ibc::core::channel::types::packet::Packet;
@@ -30,6 +33,8 @@ use crate::ibc::core::host::types::identifiers::{
 use crate::ibc::core::router::module::Module;
 use crate::ibc::core::router::types::module::{ModuleExtras, ModuleId};
 use crate::ibc::primitives::Signer;
+use crate::ledger::ibc::forward::{parse_forward_memo, set_ack_pending};
+use crate::types::address::{Address, InternalAddress};
 
 /// IBC module wrapper for getting the reference of the module
 pub trait ModuleWrapper: Module {
@@ -263,7 +268,49 @@ where
         packet: &Packet,
         _relayer: &Signer,
     ) -> (ModuleExtras, Acknowledgement) {
-        on_recv_packet_execute(&mut self.ctx, packet)
+        let mut data: PacketData = match serde_json::from_slice(&packet.data) {
+            Ok(data) => data,
+            // The error acknowledgement is made by the transfer module
+            Err(_) => return on_recv_packet_execute(&mut self.ctx, packet),
+        };
+        match parse_forward_memo(data.memo.as_ref()) {
+            None => on_recv_packet_execute(&mut self.ctx, packet),
+            Some(Err(e)) => {
+                // Reject the packet to refund the sender
+                let status = StatusValue::new(e)
+                    .expect("The error message shouldn't be empty");
+                (
+                    ModuleExtras::empty(),
+                    AcknowledgementStatus::error(status).into(),
+                )
+            }
+            Some(Ok(_)) => {
+                // The acknowledgement is written once the forward is resolved.
+                // It's set before receiving the token so that the token can't
+                // be received without being forwarded.
+                if let Err(e) = set_ack_pending(
+                    &mut *self.ctx.inner.borrow_mut(),
+                    &packet.port_id_on_b,
+                    &packet.chan_id_on_b,
+                    packet.seq_on_a,
+                ) {
+                    let status = StatusValue::new(e.to_string())
+                        .expect("The error message shouldn't be empty");
+                    return (
+                        ModuleExtras::empty(),
+                        AcknowledgementStatus::error(status).into(),
+                    );
+                }
+                // The token is received by the IBC account to be forwarded
+                // after the execution
+                data.receiver =
+                    Address::Internal(InternalAddress::Ibc).to_string().into();
+                let mut packet = packet.clone();
+                packet.data = serde_json::to_vec(&data)
+                    .expect("Encoding the packet data shouldn't fail");
+                on_recv_packet_execute(&mut self.ctx, &packet)
+            }
+        }
     }
 
     fn on_acknowledgement_packet_validate(
@@ -0,0 +1,360 @@
+//! Forwarding of the ICS-20 transfers over multiple hops
+//!
+//! An incoming packet whose memo holds a `forward` instruction, following the
+//! packet-forward middleware convention, is received by the IBC account and
+//! re-sent in the same transaction over the given channel to the given
+//! receiver, with the `next` instruction as its memo. The acknowledgement of
+//! the incoming packet is written asynchronously, once the forwarded packet is
+//! acknowledged or times out. When the forward fails, the receipt of the token
+//! is reverted and an error acknowledgement is written, such that the sender
+//! is refunded by the previous chain. The forwards are not retried.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::Deserialize;
+
+use super::storage;
+use crate::ibc::apps::transfer::types::ack_success_b64;
+use crate::ibc::core::channel::types::acknowledgement::{
+    Acknowledgement, AcknowledgementStatus, StatusValue,
+};
+use crate::ibc::core::channel::types::packet::Packet;
+use crate::ibc::core::host::types::identifiers::{ChannelId, PortId, Sequence};
+use crate::ibc::primitives::proto::Protobuf;
+use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
+
+/// The timeout of a forwarded packet when the instruction doesn't set it
+pub const DEFAULT_FORWARD_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// The instruction to forward the token of an incoming packet to the next hop
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForwardInstruction {
+    /// The receiver on the next chain
+    pub receiver: String,
+    /// The port over which the token is forwarded
+    pub port_id: PortId,
+    /// The channel over which the token is forwarded
+    pub channel_id: ChannelId,
+    /// The timeout of the forwarded packet, from the time of the block
+    pub timeout: Duration,
+    /// The memo of the forwarded packet, e.g. the instruction of the next hop
+    pub next: Option<serde_json::Value>,
+}
+
+impl ForwardInstruction {
+    /// Get the memo of the forwarded packet
+    pub fn forward_memo(&self) -> String {
+        memo_string(self.next.as_ref())
+    }
+}
+
+fn memo_string(memo: Option<&serde_json::Value>) -> String {
+    match memo {
+        None | Some(serde_json::Value::Null) => String::new(),
+        Some(serde_json::Value::String(memo)) => memo.clone(),
+        Some(memo) => memo.to_string(),
+    }
+}
+
+#[derive(Deserialize)]
+struct RawForwardInstruction {
+    receiver: String,
+    port: PortId,
+    channel: ChannelId,
+    #[serde(default)]
+    timeout: Option<RawTimeout>,
+    #[serde(default)]
+    next: Option<serde_json::Value>,
+}
+
+/// A timeout given in nanoseconds or as a duration string, e.g. "10m"
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawTimeout {
+    Nanos(u64),
+    Duration(String),
+}
+
+/// Parse the forward instruction of a packet memo. Returns `None` if the memo
+/// has no forward instruction and an error if the instruction is malformed.
+pub fn parse_forward_memo(
+    memo: &str,
+) -> Option<Result<ForwardInstruction, String>> {
+    let memo: serde_json::Value = serde_json::from_str(memo).ok()?;
+    let forward = memo.get("forward")?.clone();
+    Some(parse_forward_instruction(forward))
+}
+
+fn parse_forward_instruction(
+    forward: serde_json::Value,
+) -> Result<ForwardInstruction, String> {
+    let raw: RawForwardInstruction = serde_json::from_value(forward)
+        .map_err(|e| format!("Invalid forward instruction: {e}"))?;
+    if raw.receiver.is_empty() {
+        return Err("The forward receiver is empty".to_string());
+    }
+    let timeout = match raw.timeout {
+        None => DEFAULT_FORWARD_TIMEOUT,
+        Some(RawTimeout::Nanos(nanos)) => Duration::from_nanos(nanos),
+        Some(RawTimeout::Duration(duration)) => parse_duration(&duration)
+            .ok_or_else(|| format!("Invalid forward timeout: {duration}"))?,
+    };
+    if timeout.is_zero() {
+        return Err("The forward timeout is zero".to_string());
+    }
+    Ok(ForwardInstruction {
+        receiver: raw.receiver,
+        port_id: raw.port,
+        channel_id: raw.channel,
+        timeout,
+        next: raw.next,
+    })
+}
+
+/// Parse a duration made of integers with units, e.g. "1h30m" or "600s"
+fn parse_duration(duration: &str) -> Option<Duration> {
+    let mut total = Duration::ZERO;
+    let mut rest = duration.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    while !rest.is_empty() {
+        let digits = rest.find(|c: char| !c.is_ascii_digit())?;
+        let value: u64 = rest[..digits].parse().ok()?;
+        rest = &rest[digits..];
+        let unit_len = rest
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let unit = match &rest[..unit_len] {
+            "ns" => Duration::from_nanos(1),
+            "us" | "µs" => Duration::from_micros(1),
+            "ms" => Duration::from_millis(1),
+            "s" => Duration::from_secs(1),
+            "m" => Duration::from_secs(60),
+            "h" => Duration::from_secs(3600),
+            _ => return None,
+        };
+        rest = &rest[unit_len..];
+        total = total.checked_add(unit.checked_mul(value.try_into().ok()?)?)?;
+    }
+    Some(total)
+}
+
+/// A packet forwarded by this chain, waiting for its acknowledgement
+#[derive(Debug, Clone, PartialEq)]
+pub struct ForwardedPacket {
+    /// The incoming packet whose token has been forwarded, to be acknowledged
+    /// once the forward is resolved
+    pub packet: Packet,
+}
+
+impl BorshSerialize for ForwardedPacket {
+    fn serialize<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.packet.clone().encode_vec(), writer)
+    }
+}
+
+impl BorshDeserialize for ForwardedPacket {
+    fn deserialize_reader<R: std::io::Read>(
+        reader: &mut R,
+    ) -> std::io::Result<Self> {
+        use std::io::{Error, ErrorKind};
+        let packet: Vec<u8> = BorshDeserialize::deserialize_reader(reader)?;
+        let packet = Packet::decode_vec(&packet).map_err(|err| {
+            Error::new(ErrorKind::InvalidData, err.to_string())
+        })?;
+        Ok(Self { packet })
+    }
+}
+
+/// Read the packet forwarded with the given sequence, if it is waiting for
+/// its acknowledgement
+pub fn read_forwarded_packet<S>(
+    storage: &S,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> storage_api::Result<Option<ForwardedPacket>>
+where
+    S: StorageRead,
+{
+    let key = storage::forwarded_packet_key(port_id, channel_id, sequence);
+    storage.read(&key)
+}
+
+/// Write the packet forwarded with the given sequence
+pub fn write_forwarded_packet<S>(
+    storage: &mut S,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+    forwarded: &ForwardedPacket,
+) -> storage_api::Result<()>
+where
+    S: StorageWrite,
+{
+    let key = storage::forwarded_packet_key(port_id, channel_id, sequence);
+    storage.write(&key, forwarded)
+}
+
+/// Remove the packet forwarded with the given sequence once it has been
+/// acknowledged or timed out
+pub fn remove_forwarded_packet<S>(
+    storage: &mut S,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> storage_api::Result<()>
+where
+    S: StorageWrite,
+{
+    let key = storage::forwarded_packet_key(port_id, channel_id, sequence);
+    storage.delete(&key)
+}
+
+/// The acknowledgement of a received transfer, which is postponed when the
+/// transfer is forwarded
+pub fn success_ack() -> Acknowledgement {
+    AcknowledgementStatus::success(ack_success_b64()).into()
+}
+
+/// The acknowledgement of a failed forward, to refund the sender
+pub fn error_ack(reason: String) -> Acknowledgement {
+    let status =
+        StatusValue::new(reason).expect("The error message shouldn't be empty");
+    AcknowledgementStatus::error(status).into()
+}
+
+/// Check if the acknowledgement of the incoming packet with the given
+/// sequence waits for the resolution of its forward
+pub fn is_ack_pending<S>(
+    storage: &S,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> storage_api::Result<bool>
+where
+    S: StorageRead,
+{
+    let key = storage::forward_ack_key(port_id, channel_id, sequence);
+    storage.has_key(&key)
+}
+
+/// Set the acknowledgement of the incoming packet with the given sequence to
+/// be written once its forward is resolved
+pub fn set_ack_pending<S>(
+    storage: &mut S,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> storage_api::Result<()>
+where
+    S: StorageWrite,
+{
+    let key = storage::forward_ack_key(port_id, channel_id, sequence);
+    storage.write(&key, ())
+}
+
+/// Remove the pending acknowledgement of the incoming packet with the given
+/// sequence when it is written
+pub fn remove_ack_pending<S>(
+    storage: &mut S,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> storage_api::Result<()>
+where
+    S: StorageWrite,
+{
+    let key = storage::forward_ack_key(port_id, channel_id, sequence);
+    storage.delete(&key)
+}
+
+#[cfg(test)]
+mod tests {
+    use borsh_ext::BorshSerializeExt;
+
+    use super::*;
+
+    /// Test that the forward instructions of the packet-forward middleware
+    /// convention are parsed, including the nested ones.
+    #[test]
+    fn test_parse_forward_memo() {
+        assert!(parse_forward_memo("").is_none());
+        assert!(parse_forward_memo("some memo").is_none());
+        assert!(parse_forward_memo(r#"{"wasm": {}}"#).is_none());
+
+        let memo = r#"{"forward": {
+            "receiver": "cosmos1receiver",
+            "port": "transfer",
+            "channel": "channel-1",
+            "timeout": "1h30m",
+            "next": {"forward": {
+                "receiver": "osmo1receiver",
+                "port": "transfer",
+                "channel": "channel-7"
+            }}
+        }}"#;
+        let forward = parse_forward_memo(memo).unwrap().unwrap();
+        assert_eq!(forward.receiver, "cosmos1receiver");
+        assert_eq!(forward.port_id, PortId::transfer());
+        assert_eq!(forward.channel_id, ChannelId::new(1));
+        assert_eq!(forward.timeout, Duration::from_secs(5400));
+
+        // The next hop gets the next instruction
+        let next = parse_forward_memo(&forward.forward_memo())
+            .unwrap()
+            .unwrap();
+        assert_eq!(next.receiver, "osmo1receiver");
+        assert_eq!(next.channel_id, ChannelId::new(7));
+        assert_eq!(next.timeout, DEFAULT_FORWARD_TIMEOUT);
+        assert!(next.next.is_none());
+        assert!(next.forward_memo().is_empty());
+    }
+
+    /// Test that a forwarded packet is encoded with its protobuf encoding.
+    #[test]
+    fn test_forwarded_packet_borsh_roundtrip() {
+        use crate::ibc::core::channel::types::timeout::TimeoutHeight;
+        use crate::ibc::primitives::Timestamp;
+
+        let forwarded = ForwardedPacket {
+            packet: Packet {
+                seq_on_a: Sequence::from(3),
+                port_id_on_a: PortId::transfer(),
+                chan_id_on_a: ChannelId::new(42),
+                port_id_on_b: PortId::transfer(),
+                chan_id_on_b: ChannelId::new(0),
+                data: br#"{"memo": "data"}"#.to_vec(),
+                timeout_height_on_b: TimeoutHeight::Never,
+                timeout_timestamp_on_b: Timestamp::from_nanoseconds(1).unwrap(),
+            },
+        };
+        let bytes = forwarded.serialize_to_vec();
+        assert_eq!(ForwardedPacket::try_from_slice(&bytes).unwrap(), forwarded);
+    }
+
+    /// Test that the malformed forward instructions are rejected.
+    #[test]
+    fn test_parse_invalid_forward_memo() {
+        let invalid = [
+            r#"{"forward": {"port": "transfer", "channel": "channel-1"}}"#,
+            r#"{"forward": {"receiver": "", "port": "transfer",
+                "channel": "channel-1"}}"#,
+            r#"{"forward": {"receiver": "a", "port": "transfer",
+                "channel": "channel-1", "timeout": "10 minutes"}}"#,
+            r#"{"forward": {"receiver": "a", "port": "transfer",
+                "channel": "channel-1", "timeout": 0}}"#,
+        ];
+        for memo in invalid {
+            assert!(parse_forward_memo(memo).unwrap().is_err(), "{memo}");
+        }
+        assert_eq!(parse_duration("250ms"), Some(Duration::from_millis(250)));
+        assert_eq!(parse_duration("10"), None);
+    }
+}
@@ -1,47 +1,68 @@
 //! IBC library code
 
 pub mod context;
+pub mod forward;
+pub mod rate_limit;
 pub mod storage;
 
 use std::cell::RefCell;
 use std::fmt::Debug;
 use std::rc::Rc;
 use std::str::FromStr;
+use std::time::Duration;
 
 use borsh::BorshDeserialize;
 pub use context::common::IbcCommonContext;
 use context::router::IbcRouter;
 pub use context::storage::{IbcStorageContext, ProofSpec};
+use context::token_transfer::get_token_amount;
 pub use context::token_transfer::TokenTransferContext;
 pub use context::transfer_mod::{ModuleWrapper, TransferModule};
 use context::IbcContext;
 pub use context::ValidationParams;
+use forward::{parse_forward_memo, ForwardedPacket};
 use prost::Message;
+use rate_limit::Flow;
 use thiserror::Error;
 
+use crate::ibc::apps::transfer::context::TokenTransferExecutionContext;
 use crate::ibc::apps::transfer::handler::{
     send_transfer_execute, send_transfer_validate,
 };
 use crate::ibc::apps::transfer::types::error::TokenTransferError;
 use crate::ibc::apps::transfer::types::msgs::transfer::MsgTransfer;
+use crate::ibc::apps::transfer::types::packet::PacketData;
 use crate::ibc::apps::transfer::types::{
-    is_receiver_chain_source, PrefixedDenom, TracePrefix,
+    is_receiver_chain_source, PrefixedCoin, PrefixedDenom, TracePrefix,
 };
+use crate::ibc::core::channel::types::acknowledgement::{
+    Acknowledgement, AcknowledgementStatus,
+};
+use crate::ibc::core::channel::types::commitment::compute_ack_commitment;
+use crate::ibc::core::channel::types::events::WriteAcknowledgement;
 use crate::ibc::core::channel::types::msgs::PacketMsg;
+use crate::ibc::core::channel::types::packet::Packet;
+use crate::ibc::core::channel::types::timeout::TimeoutHeight;
 use crate::ibc::core::entrypoint::{execute, validate};
 use crate::ibc::core::handler::types::error::ContextError;
+use crate::ibc::core::handler::types::events::IbcEvent as RawIbcEvent;
 use crate::ibc::core::handler::types::msgs::MsgEnvelope;
 use crate::ibc::core::host::types::error::IdentifierError;
 use crate::ibc::core::host::types::identifiers::{ChannelId, PortId};
+use crate::ibc::core::host::types::path::{AckPath, ChannelEndPath};
+use crate::ibc::core::host::{ExecutionContext, ValidationContext};
 use crate::ibc::core::router::types::error::RouterError;
 use crate::ibc::core::router::types::module::ModuleId;
 use crate::ibc::primitives::proto::Any;
-use crate::types::address::{Address, MASP};
+use crate::ledger::masp_utils;
+use crate::ledger::storage_api::{self, StorageRead};
+use crate::types::address::{Address, InternalAddress, MASP};
 use crate::types::ibc::{
-    get_shielded_transfer, is_ibc_denom, MsgShieldedTransfer,
-    EVENT_TYPE_DENOM_TRACE, EVENT_TYPE_PACKET,
+    get_shielded_transfer, is_ibc_denom, IbcEvent, IbcShieldedTransfer,
+    MsgShieldedTransfer, EVENT_TYPE_DENOM_TRACE, EVENT_TYPE_PACKET,
 };
 use crate::types::masp::PaymentAddress;
+use crate::types::token::Transfer;
 
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
@@ -60,6 +81,10 @@ pub enum Error {
     ChainId(IdentifierError),
     #[error("Handling MASP transaction error: {0}")]
     MaspTx(String),
+    #[error("Recording the IBC flow error: {0}")]
+    RateLimit(String),
+    #[error("Forwarding the IBC packet error: {0}")]
+    Forward(String),
 }
 
 /// IBC actions to handle IBC operations
@@ -110,7 +135,8 @@ where
                     &mut token_transfer_ctx,
                     msg.clone(),
                 )
-                .map_err(Error::TokenTransfer)
+                .map_err(Error::TokenTransfer)?;
+                self.record_flow(&message)
             }
             IbcMessage::ShieldedTransfer(msg) => {
                 let mut token_transfer_ctx =
@@ -121,6 +147,7 @@ where
                     msg.message.clone(),
                 )
                 .map_err(Error::TokenTransfer)?;
+                self.record_flow(&message)?;
                 self.handle_masp_tx(message)
             }
             IbcMessage::Envelope(envelope) => {
@@ -129,12 +156,377 @@ where
                 // the current ibc-rs execution doesn't store the denom for the
                 // token hash when transfer with MsgRecvPacket
                 self.store_denom(envelope)?;
+                self.record_flow(&message)?;
+                self.handle_forward(envelope)?;
                 // For receiving the token to a shielded address
                 self.handle_masp_tx(message)
             }
         }
     }
 
+    /// Record the flow of the transferred token for its rate limit on the
+    /// channel, if any
+    fn record_flow(&mut self, message: &IbcMessage) -> Result<(), Error> {
+        let (channel_id, coin, flow) = match message {
+            IbcMessage::Transfer(msg) => (
+                msg.chan_id_on_a.clone(),
+                msg.packet_data.token.clone(),
+                Flow::Outflow,
+            ),
+            IbcMessage::ShieldedTransfer(msg) => (
+                msg.message.chan_id_on_a.clone(),
+                msg.message.packet_data.token.clone(),
+                Flow::Outflow,
+            ),
+            IbcMessage::Envelope(MsgEnvelope::Packet(PacketMsg::Recv(msg))) => {
+                if !self.is_packet_received()? {
+                    return Ok(());
+                }
+                match received_packet_data(&msg.packet) {
+                    Some(data) => (
+                        msg.packet.chan_id_on_b.clone(),
+                        data.token,
+                        Flow::Inflow,
+                    ),
+                    // Not a token transfer
+                    None => return Ok(()),
+                }
+            }
+            _ => return Ok(()),
+        };
+        let (token, amount) = TokenTransferContext::new(self.ctx.inner.clone())
+            .get_token_amount(&coin)
+            .map_err(Error::TokenTransfer)?;
+        rate_limit::record_flow(
+            &mut *self.ctx.inner.borrow_mut(),
+            &channel_id,
+            &token,
+            flow,
+            amount.amount(),
+        )
+        .map_err(|e| Error::RateLimit(e.to_string()))
+    }
+
+    /// Forward the received token or write the acknowledgement of the packet
+    /// whose token has been forwarded if needed
+    fn handle_forward(&mut self, envelope: &MsgEnvelope) -> Result<(), Error> {
+        match envelope {
+            MsgEnvelope::Packet(PacketMsg::Recv(msg)) => {
+                self.forward_packet(&msg.packet)
+            }
+            MsgEnvelope::Packet(PacketMsg::Ack(msg)) => {
+                let is_successful =
+                    serde_json::from_slice::<AcknowledgementStatus>(
+                        msg.acknowledgement.as_ref(),
+                    )
+                    .map_or(false, |ack| ack.is_successful());
+                let result = if is_successful {
+                    Ok(msg.acknowledgement.clone())
+                } else {
+                    Err(format!(
+                        "The forwarded packet failed: {}",
+                        String::from_utf8_lossy(msg.acknowledgement.as_ref())
+                    ))
+                };
+                self.complete_forward(&msg.packet, result)
+            }
+            MsgEnvelope::Packet(PacketMsg::Timeout(msg)) => self
+                .complete_forward(
+                    &msg.packet,
+                    Err("The forwarded packet timed out".to_string()),
+                ),
+            MsgEnvelope::Packet(PacketMsg::TimeoutOnClose(msg)) => self
+                .complete_forward(
+                    &msg.packet,
+                    Err("The forwarded packet timed out".to_string()),
+                ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Send the received token to the next hop according to the forward
+    /// instruction in the packet memo. The acknowledgement of the packet is
+    /// written once the forward is resolved, or right away with an error if
+    /// the token can't be sent.
+    fn forward_packet(&mut self, packet: &Packet) -> Result<(), Error> {
+        let is_ack_pending = forward::is_ack_pending(
+            &*self.ctx.inner.borrow(),
+            &packet.port_id_on_b,
+            &packet.chan_id_on_b,
+            packet.seq_on_a,
+        )
+        .map_err(|e| Error::Forward(e.to_string()))?;
+        if !is_ack_pending {
+            return Ok(());
+        }
+        if !self.is_packet_received()? {
+            // The error acknowledgement has been written by the module
+            return forward::remove_ack_pending(
+                &mut *self.ctx.inner.borrow_mut(),
+                &packet.port_id_on_b,
+                &packet.chan_id_on_b,
+                packet.seq_on_a,
+            )
+            .map_err(|e| Error::Forward(e.to_string()));
+        }
+        let (data, instruction) = received_packet_data(packet)
+            .and_then(|data| {
+                let instruction = parse_forward_memo(data.memo.as_ref())?;
+                Some((data, instruction.ok()?))
+            })
+            .ok_or_else(|| {
+                Error::Forward(
+                    "The forward instruction of the received packet is invalid"
+                        .to_string(),
+                )
+            })?;
+        let memo = instruction.forward_memo();
+        let msg = self
+            .make_transfer(
+                instruction.port_id,
+                instruction.channel_id,
+                data.token,
+                instruction.receiver,
+                memo,
+                instruction.timeout,
+            )
+            .and_then(|msg| self.validate_forward(&msg).map(|()| msg));
+        let msg = match msg {
+            Ok(msg) => msg,
+            Err(e) => {
+                // Refund the sender through the previous chain
+                self.revert_receive(packet)?;
+                return self.write_forward_ack(
+                    packet,
+                    forward::error_ack(format!("Forwarding failed: {e}")),
+                );
+            }
+        };
+        let sequence = self
+            .ctx
+            .inner
+            .borrow()
+            .get_next_sequence_send(&msg.port_id_on_a, &msg.chan_id_on_a)
+            .map_err(|e| Error::Context(Box::new(e)))?;
+        forward::write_forwarded_packet(
+            &mut *self.ctx.inner.borrow_mut(),
+            &msg.port_id_on_a,
+            &msg.chan_id_on_a,
+            sequence,
+            &ForwardedPacket {
+                packet: packet.clone(),
+            },
+        )
+        .map_err(|e| Error::Forward(e.to_string()))?;
+        self.send_transfer(msg)
+    }
+
+    /// Write the acknowledgement of the incoming packet whose token has been
+    /// forwarded with the acknowledged or timed out packet, if any. The
+    /// receipt of the token is reverted when the forward failed.
+    fn complete_forward(
+        &mut self,
+        packet: &Packet,
+        result: Result<Acknowledgement, String>,
+    ) -> Result<(), Error> {
+        let forwarded = forward::read_forwarded_packet(
+            &*self.ctx.inner.borrow(),
+            &packet.port_id_on_a,
+            &packet.chan_id_on_a,
+            packet.seq_on_a,
+        )
+        .map_err(|e| Error::Forward(e.to_string()))?;
+        let forwarded = match forwarded {
+            Some(forwarded) => forwarded,
+            None => return Ok(()),
+        };
+        forward::remove_forwarded_packet(
+            &mut *self.ctx.inner.borrow_mut(),
+            &packet.port_id_on_a,
+            &packet.chan_id_on_a,
+            packet.seq_on_a,
+        )
+        .map_err(|e| Error::Forward(e.to_string()))?;
+        match result {
+            // Pass the acknowledgement of the next hop through
+            Ok(ack) => self.write_forward_ack(&forwarded.packet, ack),
+            Err(reason) => {
+                // The token has been refunded to the IBC account
+                self.revert_receive(&forwarded.packet)?;
+                self.write_forward_ack(
+                    &forwarded.packet,
+                    forward::error_ack(reason),
+                )
+            }
+        }
+    }
+
+    /// Make a transfer of the token held by the IBC account
+    fn make_transfer(
+        &self,
+        port_id: PortId,
+        channel_id: ChannelId,
+        token: PrefixedCoin,
+        receiver: String,
+        memo: String,
+        timeout: Duration,
+    ) -> Result<MsgTransfer, Error> {
+        let timestamp = self
+            .ctx
+            .inner
+            .borrow()
+            .host_timestamp()
+            .map_err(|e| Error::Context(Box::new(e)))?;
+        let timeout_timestamp_on_b = (timestamp + timeout).map_err(|e| {
+            Error::Forward(format!("Invalid timeout timestamp: {e}"))
+        })?;
+        Ok(MsgTransfer {
+            port_id_on_a: port_id,
+            chan_id_on_a: channel_id,
+            packet_data: PacketData {
+                token,
+                sender: Address::Internal(InternalAddress::Ibc)
+                    .to_string()
+                    .into(),
+                receiver: receiver.into(),
+                memo: memo.into(),
+            },
+            timeout_height_on_b: TimeoutHeight::Never,
+            timeout_timestamp_on_b,
+        })
+    }
+
+    /// Check that the token held by the IBC account can be sent, including
+    /// the rate limit of its outflow
+    fn validate_forward(&self, msg: &MsgTransfer) -> Result<(), Error> {
+        let token_transfer_ctx =
+            TokenTransferContext::new(self.ctx.inner.clone());
+        send_transfer_validate(&self.ctx, &token_transfer_ctx, msg.clone())
+            .map_err(Error::TokenTransfer)?;
+        let (token, amount) = token_transfer_ctx
+            .get_token_amount(&msg.packet_data.token)
+            .map_err(Error::TokenTransfer)?;
+        let usage = rate_limit::current_usage(
+            &*self.ctx.inner.borrow(),
+            &msg.chan_id_on_a,
+            &token,
+        )
+        .map_err(|e| Error::RateLimit(e.to_string()))?;
+        if let Some((limit, total)) = usage {
+            let outflow = total.outflow.checked_add(amount.amount());
+            if outflow.map_or(true, |outflow| outflow > limit.max_outflow) {
+                return Err(Error::RateLimit(format!(
+                    "The outflow of {token} through {} exceeds the limit",
+                    msg.chan_id_on_a
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Send a token from the IBC account in the same transaction
+    fn send_transfer(&mut self, msg: MsgTransfer) -> Result<(), Error> {
+        let mut token_transfer_ctx =
+            TokenTransferContext::new(self.ctx.inner.clone());
+        send_transfer_execute(
+            &mut self.ctx,
+            &mut token_transfer_ctx,
+            msg.clone(),
+        )
+        .map_err(Error::TokenTransfer)?;
+        self.record_flow(&IbcMessage::Transfer(msg))
+    }
+
+    /// Take the token of the received packet back from the IBC account, so
+    /// that the sender can be refunded by the previous chain
+    fn revert_receive(&mut self, packet: &Packet) -> Result<(), Error> {
+        // The source chain is checked on the denom as sent by the counterparty
+        // chain, before its trace prefix is adjusted on this chain
+        let raw_data: PacketData = serde_json::from_slice(&packet.data)
+            .map_err(|e| {
+                Error::Forward(format!("Decoding the packet data failed: {e}"))
+            })?;
+        if is_receiver_chain_source(
+            packet.port_id_on_a.clone(),
+            packet.chan_id_on_a.clone(),
+            &raw_data.token.denom,
+        ) {
+            // The token has been unescrowed to the IBC account, which is the
+            // escrow account itself
+            return Ok(());
+        }
+        // The received token has been minted to the IBC account with the
+        // trace prefix of this chain
+        let data = received_packet_data(packet).ok_or_else(|| {
+            Error::Forward("Decoding the packet data failed".to_string())
+        })?;
+        TokenTransferContext::new(self.ctx.inner.clone())
+            .burn_coins_execute(
+                &Address::Internal(InternalAddress::Ibc),
+                &data.token,
+            )
+            .map_err(Error::TokenTransfer)
+    }
+
+    /// Write the acknowledgement of the incoming packet whose forward has
+    /// been resolved
+    fn write_forward_ack(
+        &mut self,
+        packet: &Packet,
+        ack: Acknowledgement,
+    ) -> Result<(), Error> {
+        forward::remove_ack_pending(
+            &mut *self.ctx.inner.borrow_mut(),
+            &packet.port_id_on_b,
+            &packet.chan_id_on_b,
+            packet.seq_on_a,
+        )
+        .map_err(|e| Error::Forward(e.to_string()))?;
+        let ack_path = AckPath::new(
+            &packet.port_id_on_b,
+            &packet.chan_id_on_b,
+            packet.seq_on_a,
+        );
+        self.ctx
+            .store_packet_acknowledgement(
+                &ack_path,
+                compute_ack_commitment(&ack),
+            )
+            .map_err(|e| Error::Context(Box::new(e)))?;
+        let chan_end_path =
+            ChannelEndPath::new(&packet.port_id_on_b, &packet.chan_id_on_b);
+        let conn_id = self
+            .ctx
+            .channel_end(&chan_end_path)
+            .map_err(|e| Error::Context(Box::new(e)))?
+            .connection_hops
+            .first()
+            .cloned()
+            .ok_or_else(|| {
+                Error::Forward("The channel has no connection".to_string())
+            })?;
+        let event = WriteAcknowledgement::new(packet.clone(), ack, conn_id);
+        self.ctx
+            .emit_ibc_event(RawIbcEvent::WriteAcknowledgement(event))
+            .map_err(|e| Error::Context(Box::new(e)))
+    }
+
+    /// Check if the token of the received packet has been credited
+    fn is_packet_received(&self) -> Result<bool, Error> {
+        let event = self
+            .ctx
+            .inner
+            .borrow()
+            .get_ibc_events(EVENT_TYPE_PACKET)
+            .map_err(|_| {
+                Error::RateLimit("Reading the IBC event failed".to_string())
+            })?;
+        // The receiving event should be only one in the single IBC transaction
+        Ok(event.first().map_or(false, |event| {
+            event.attributes.get("success") == Some(&"true".to_string())
+        }))
+    }
+
     /// Store the denom when transfer with MsgRecvPacket
     fn store_denom(&mut self, envelope: &MsgEnvelope) -> Result<(), Error> {
         if let MsgEnvelope::Packet(PacketMsg::Recv(_)) = envelope {
@@ -256,7 +648,7 @@ where
     /// Handle the MASP transaction if needed
     fn handle_masp_tx(&mut self, message: IbcMessage) -> Result<(), Error> {
         let shielded_transfer = match message {
-            IbcMessage::Envelope(MsgEnvelope::Packet(PacketMsg::Recv(_))) => {
+            IbcMessage::Envelope(MsgEnvelope::Packet(PacketMsg::Recv(msg))) => {
                 let event = self
                     .ctx
                     .inner
@@ -270,8 +662,13 @@ where
                 // The receiving event should be only one in the single IBC
                 // transaction
                 match event.first() {
-                    Some(event) => get_shielded_transfer(event)
-                        .map_err(|e| Error::MaspTx(e.to_string()))?,
+                    Some(event) => received_shielded_transfer(
+                        &*self.ctx.inner.borrow(),
+                        &msg.packet,
+                        event,
+                    )
+                    .map_err(|e| Error::MaspTx(e.to_string()))?
+                    .map(|(shielded_transfer, _)| shielded_transfer),
                     None => return Ok(()),
                 }
             }
@@ -316,6 +713,99 @@ fn decode_message(tx_data: &[u8]) -> Result<IbcMessage, Error> {
     Err(Error::DecodingData)
 }
 
+/// Get the packet of the receiving message in the IBC transaction data
+pub fn decode_recv_packet(tx_data: &[u8]) -> Option<Packet> {
+    match decode_message(tx_data).ok()? {
+        IbcMessage::Envelope(MsgEnvelope::Packet(PacketMsg::Recv(msg))) => {
+            Some(msg.packet)
+        }
+        _ => None,
+    }
+}
+
+/// Get the shielded transfer of the token received with the packet. It is the
+/// one in the packet memo, or the one shielding the token natively when the
+/// receiver is a payment address and the memo has no shielded transfer. The
+/// returned flag is set when the shielded transfer has been built natively.
+pub fn received_shielded_transfer<S>(
+    storage: &S,
+    packet: &Packet,
+    event: &IbcEvent,
+) -> storage_api::Result<Option<(IbcShieldedTransfer, bool)>>
+where
+    S: StorageRead,
+{
+    if let Some(shielded_transfer) = get_shielded_transfer(event)
+        .map_err(|e| storage_api::Error::new(e.to_string()))?
+    {
+        return Ok(Some((shielded_transfer, false)));
+    }
+    if event.event_type != EVENT_TYPE_PACKET
+        || event.attributes.get("success") != Some(&"true".to_string())
+    {
+        return Ok(None);
+    }
+    // The receiver of the event is the IBC account when the packet has been
+    // forwarded
+    let target = match event
+        .attributes
+        .get("receiver")
+        .and_then(|receiver| PaymentAddress::from_str(receiver).ok())
+    {
+        Some(target) => target,
+        None => return Ok(None),
+    };
+    let data = match received_packet_data(packet) {
+        Some(data) => data,
+        None => return Ok(None),
+    };
+    let (token, amount) = get_token_amount(storage, &data.token)
+        .map_err(|e| storage_api::Error::new(e.to_string()))?;
+    // The packet can be received only once on this chain
+    let seed = format!(
+        "{}/{}/{}",
+        packet.port_id_on_b, packet.chan_id_on_b, packet.seq_on_a
+    );
+    let masp_tx = masp_utils::native_shielding_tx(
+        seed.as_bytes(),
+        &target,
+        &token,
+        amount.amount(),
+        storage.get_block_epoch()?,
+    )?;
+    let transfer = Transfer {
+        source: Address::Internal(InternalAddress::Ibc),
+        target: MASP,
+        token,
+        amount,
+        key: target.is_pinned().then(|| target.hash()),
+        shielded: None,
+    };
+    Ok(Some((IbcShieldedTransfer { transfer, masp_tx }, true)))
+}
+
+/// Get the data of a received token transfer packet with the token denom on
+/// this chain
+fn received_packet_data(packet: &Packet) -> Option<PacketData> {
+    let mut data: PacketData = serde_json::from_slice(&packet.data).ok()?;
+    if is_receiver_chain_source(
+        packet.port_id_on_a.clone(),
+        packet.chan_id_on_a.clone(),
+        &data.token.denom,
+    ) {
+        data.token.denom.remove_trace_prefix(&TracePrefix::new(
+            packet.port_id_on_a.clone(),
+            packet.chan_id_on_a.clone(),
+        ));
+    } else {
+        data.token.denom.add_trace_prefix(TracePrefix::new(
+            packet.port_id_on_b.clone(),
+            packet.chan_id_on_b.clone(),
+        ));
+    }
+    Some(data)
+}
+
 /// Get the IbcToken from the source/destination ports and channels
 pub fn received_ibc_token(
     ibc_denom: &PrefixedDenom,
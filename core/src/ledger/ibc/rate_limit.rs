@@ -0,0 +1,396 @@
+//! Rate limits of the ICS-20 transfers
+//!
+//! Governance can limit the amount of a token flowing in and out of the chain
+//! through a channel over a sliding window of epochs. The flows of a rate
+//! limited token are recorded per epoch when the transfers are executed and
+//! the IBC VP rejects the transactions making them exceed the limit. The
+//! amounts are in the smallest unit of the token. The refunds of the timed out
+//! or failed transfers are not deducted from the outflows.
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Deserialize, Serialize};
+
+use super::storage;
+use crate::ibc::core::host::types::identifiers::ChannelId;
+use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
+use crate::types::address::Address;
+use crate::types::storage::Epoch;
+use crate::types::token::Amount;
+
+/// The direction of an ICS-20 transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Flow {
+    /// A token received from the counterparty chain
+    Inflow,
+    /// A token sent to the counterparty chain
+    Outflow,
+}
+
+impl Display for Flow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Flow::Inflow => write!(f, "inflow"),
+            Flow::Outflow => write!(f, "outflow"),
+        }
+    }
+}
+
+/// The maximum amounts of a token flowing through a channel over a window of
+/// epochs
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct RateLimit {
+    /// The maximum amount received over the window
+    pub max_inflow: Amount,
+    /// The maximum amount sent over the window
+    pub max_outflow: Amount,
+    /// The number of epochs of the window, including the current one
+    pub window: u64,
+}
+
+impl RateLimit {
+    /// Check that the window includes at least the current epoch
+    pub fn is_valid(&self) -> bool {
+        self.window > 0
+    }
+
+    /// Get the first flow whose total amount over the window exceeds the
+    /// limit, if any
+    pub fn exceeded_flow(&self, total: &FlowAmounts) -> Option<Flow> {
+        if total.inflow > self.max_inflow {
+            Some(Flow::Inflow)
+        } else if total.outflow > self.max_outflow {
+            Some(Flow::Outflow)
+        } else {
+            None
+        }
+    }
+
+    /// Get the first epoch of the window ending at the given epoch
+    fn window_start(&self, epoch: Epoch) -> Epoch {
+        epoch.sub_or_default(Epoch(self.window.saturating_sub(1)))
+    }
+}
+
+impl Display for RateLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "inflow {}, outflow {} over {} epochs",
+            self.max_inflow, self.max_outflow, self.window
+        )
+    }
+}
+
+/// The amounts of a token received and sent through a channel
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct FlowAmounts {
+    /// The amount received
+    pub inflow: Amount,
+    /// The amount sent
+    pub outflow: Amount,
+}
+
+impl FlowAmounts {
+    fn checked_add(&self, other: &FlowAmounts) -> Option<Self> {
+        Some(Self {
+            inflow: self.inflow.checked_add(other.inflow)?,
+            outflow: self.outflow.checked_add(other.outflow)?,
+        })
+    }
+}
+
+/// The flows of a rate limited token through a channel in each epoch of the
+/// window
+#[derive(
+    Debug, Clone, Default, PartialEq, Eq, BorshSerialize, BorshDeserialize,
+)]
+pub struct FlowHistory {
+    epochs: BTreeMap<Epoch, FlowAmounts>,
+}
+
+impl FlowHistory {
+    /// Add an amount to the flows of the given epoch, dropping the epochs
+    /// before the window of the limit. Returns `None` on overflow.
+    pub fn record(
+        &mut self,
+        limit: &RateLimit,
+        epoch: Epoch,
+        flow: Flow,
+        amount: Amount,
+    ) -> Option<()> {
+        let window_start = limit.window_start(epoch);
+        self.epochs.retain(|recorded, _| *recorded >= window_start);
+        let amounts = self.epochs.entry(epoch).or_default();
+        match flow {
+            Flow::Inflow => {
+                amounts.inflow = amounts.inflow.checked_add(amount)?
+            }
+            Flow::Outflow => {
+                amounts.outflow = amounts.outflow.checked_add(amount)?
+            }
+        }
+        Some(())
+    }
+
+    /// Get the total flows over the window of the limit ending at the given
+    /// epoch. Returns `None` on overflow.
+    pub fn total(
+        &self,
+        limit: &RateLimit,
+        epoch: Epoch,
+    ) -> Option<FlowAmounts> {
+        self.epochs
+            .range(limit.window_start(epoch)..=epoch)
+            .try_fold(FlowAmounts::default(), |total, (_, amounts)| {
+                total.checked_add(amounts)
+            })
+    }
+}
+
+/// A change of the rate limit of a token through a channel, set by governance
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IbcRateLimit {
+    /// The channel through which the token is limited
+    pub channel_id: ChannelId,
+    /// The limited token
+    pub token: Address,
+    /// The new limit, or `None` to remove the limit
+    pub limit: Option<RateLimit>,
+}
+
+impl BorshSerialize for IbcRateLimit {
+    fn serialize<W: std::io::Write>(
+        &self,
+        writer: &mut W,
+    ) -> std::io::Result<()> {
+        BorshSerialize::serialize(&self.channel_id.to_string(), writer)?;
+        BorshSerialize::serialize(&self.token, writer)?;
+        BorshSerialize::serialize(&self.limit, writer)
+    }
+}
+
+impl BorshDeserialize for IbcRateLimit {
+    fn deserialize_reader<R: std::io::Read>(
+        reader: &mut R,
+    ) -> std::io::Result<Self> {
+        use std::io::{Error, ErrorKind};
+        let channel_id: String = BorshDeserialize::deserialize_reader(reader)?;
+        let channel_id = ChannelId::from_str(&channel_id).map_err(|err| {
+            Error::new(ErrorKind::InvalidData, err.to_string())
+        })?;
+        let token: Address = BorshDeserialize::deserialize_reader(reader)?;
+        let limit: Option<RateLimit> =
+            BorshDeserialize::deserialize_reader(reader)?;
+        Ok(Self {
+            channel_id,
+            token,
+            limit,
+        })
+    }
+}
+
+impl Display for IbcRateLimit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.limit {
+            Some(limit) => {
+                write!(f, "{} on {}: {}", self.token, self.channel_id, limit)
+            }
+            None => write!(f, "{} on {}: removed", self.token, self.channel_id),
+        }
+    }
+}
+
+/// Read the rate limit of a token through a channel
+pub fn read_rate_limit<S>(
+    storage: &S,
+    channel_id: &ChannelId,
+    token: &Address,
+) -> storage_api::Result<Option<RateLimit>>
+where
+    S: StorageRead,
+{
+    storage.read(&storage::rate_limit_key(channel_id, token))
+}
+
+/// Read all the rate limits
+pub fn read_rate_limits<S>(
+    storage: &S,
+) -> storage_api::Result<Vec<IbcRateLimit>>
+where
+    S: StorageRead,
+{
+    storage_api::iter_prefix::<RateLimit>(
+        storage,
+        &storage::rate_limit_prefix(),
+    )?
+    .filter_map(|result| match result {
+        Ok((key, limit)) => {
+            let (channel_id, token) = storage::is_rate_limit_key(&key)?;
+            Some(Ok(IbcRateLimit {
+                channel_id,
+                token,
+                limit: Some(limit),
+            }))
+        }
+        Err(err) => Some(Err(err)),
+    })
+    .collect()
+}
+
+/// Set or remove the rate limit of a token through a channel. The recorded
+/// flows are kept when the limit is changed.
+pub fn write_rate_limit<S>(
+    storage: &mut S,
+    rate_limit: &IbcRateLimit,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let key =
+        storage::rate_limit_key(&rate_limit.channel_id, &rate_limit.token);
+    match &rate_limit.limit {
+        Some(limit) => storage.write(&key, limit),
+        None => {
+            storage.delete(&key)?;
+            storage.delete(&storage::rate_limit_flows_key(
+                &rate_limit.channel_id,
+                &rate_limit.token,
+            ))
+        }
+    }
+}
+
+/// Record a flow of a token through a channel if the token is rate limited on
+/// the channel
+pub fn record_flow<S>(
+    storage: &mut S,
+    channel_id: &ChannelId,
+    token: &Address,
+    flow: Flow,
+    amount: Amount,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let limit = match read_rate_limit(storage, channel_id, token)? {
+        Some(limit) => limit,
+        None => return Ok(()),
+    };
+    let key = storage::rate_limit_flows_key(channel_id, token);
+    let mut history: FlowHistory = storage.read(&key)?.unwrap_or_default();
+    let epoch = storage.get_block_epoch()?;
+    history.record(&limit, epoch, flow, amount).ok_or_else(|| {
+        storage_api::Error::new_const("The IBC flow amount overflowed")
+    })?;
+    storage.write(&key, history)
+}
+
+/// Get the rate limit of a token through a channel with the total flows over
+/// its window ending at the current epoch
+pub fn current_usage<S>(
+    storage: &S,
+    channel_id: &ChannelId,
+    token: &Address,
+) -> storage_api::Result<Option<(RateLimit, FlowAmounts)>>
+where
+    S: StorageRead,
+{
+    let limit = match read_rate_limit(storage, channel_id, token)? {
+        Some(limit) => limit,
+        None => return Ok(None),
+    };
+    let history: FlowHistory = storage
+        .read(&storage::rate_limit_flows_key(channel_id, token))?
+        .unwrap_or_default();
+    let epoch = storage.get_block_epoch()?;
+    let total = history.total(&limit, epoch).ok_or_else(|| {
+        storage_api::Error::new_const("The IBC flow amount overflowed")
+    })?;
+    Ok(Some((limit, total)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limit() -> RateLimit {
+        RateLimit {
+            max_inflow: Amount::from_u64(100),
+            max_outflow: Amount::from_u64(50),
+            window: 3,
+        }
+    }
+
+    /// Test that only the flows of the epochs in the window are counted and
+    /// kept in the history.
+    #[test]
+    fn test_flow_history_window() {
+        let limit = limit();
+        let mut history = FlowHistory::default();
+        for epoch in 0..4 {
+            history
+                .record(
+                    &limit,
+                    Epoch(epoch),
+                    Flow::Inflow,
+                    Amount::from_u64(30),
+                )
+                .unwrap();
+        }
+        history
+            .record(&limit, Epoch(3), Flow::Outflow, Amount::from_u64(10))
+            .unwrap();
+
+        // Epoch 0 is out of the window of epoch 3
+        assert_eq!(history.epochs.len(), 3);
+        let total = history.total(&limit, Epoch(3)).unwrap();
+        assert_eq!(
+            total,
+            FlowAmounts {
+                inflow: Amount::from_u64(120),
+                outflow: Amount::from_u64(10),
+            }
+        );
+        assert_eq!(limit.exceeded_flow(&total), Some(Flow::Inflow));
+
+        // Only epoch 3 is left in the window of epoch 5
+        let total = history.total(&limit, Epoch(5)).unwrap();
+        assert_eq!(total.inflow, Amount::from_u64(30));
+        assert_eq!(limit.exceeded_flow(&total), None);
+    }
+
+    /// Test that the outflow limit is checked independently of the inflow.
+    #[test]
+    fn test_rate_limit_exceeded_outflow() {
+        let limit = limit();
+        let total = FlowAmounts {
+            inflow: Amount::from_u64(100),
+            outflow: Amount::from_u64(51),
+        };
+        assert_eq!(limit.exceeded_flow(&total), Some(Flow::Outflow));
+        assert!(!RateLimit { window: 0, ..limit }.is_valid());
+    }
+}
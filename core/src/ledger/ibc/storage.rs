@@ -22,6 +22,10 @@ const CLIENTS_COUNTER: &str = "clients/counter";
 const CONNECTIONS_COUNTER: &str = "connections/counter";
 const CHANNELS_COUNTER: &str = "channelEnds/counter";
 const DENOM: &str = "ibc_denom";
+const RATE_LIMIT: &str = "rate_limit";
+const RATE_LIMIT_FLOWS: &str = "rate_limit_flows";
+const FORWARD: &str = "forward";
+const FORWARD_ACK: &str = "forward_ack";
 
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
@@ -181,6 +185,35 @@ pub fn receipt_key(
         .expect("Creating a key for the receipt shouldn't fail")
 }
 
+/// Returns a key for the packet forwarded by this chain with the given
+/// sequence, waiting for its acknowledgement
+pub fn forwarded_packet_key(
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> Key {
+    let path = format!(
+        "{FORWARD}/ports/{port_id}/channels/{channel_id}/sequences/{sequence}"
+    );
+    ibc_key(path)
+        .expect("Creating a key for the forwarded packet shouldn't fail")
+}
+
+/// Returns a key marking the acknowledgement of the incoming packet with the
+/// given sequence as pending until its forward is resolved
+pub fn forward_ack_key(
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    sequence: Sequence,
+) -> Key {
+    let path = format!(
+        "{FORWARD_ACK}/ports/{port_id}/channels/{channel_id}/sequences/\
+         {sequence}"
+    );
+    ibc_key(path)
+        .expect("Creating a key for the pending acknowledgement shouldn't fail")
+}
+
 /// Returns a key for the ack
 pub fn ack_key(
     port_id: &PortId,
@@ -395,6 +428,66 @@ pub fn ibc_denom_key(
         .expect("Cannot obtain a storage key")
 }
 
+/// The storage key prefix of the rate limits of the ICS-20 transfers
+pub fn rate_limit_prefix() -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&RATE_LIMIT.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// The storage key of the rate limit of a token through a channel
+pub fn rate_limit_key(channel_id: &ChannelId, token: &Address) -> Key {
+    rate_limit_prefix()
+        .push(&channel_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&token.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// The storage key of the flows of a token through a channel, recorded while
+/// the token is rate limited on the channel
+pub fn rate_limit_flows_key(channel_id: &ChannelId, token: &Address) -> Key {
+    Key::from(Address::Internal(InternalAddress::Ibc).to_db_key())
+        .push(&RATE_LIMIT_FLOWS.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&channel_id.to_string().to_db_key())
+        .expect("Cannot obtain a storage key")
+        .push(&token.to_db_key())
+        .expect("Cannot obtain a storage key")
+}
+
+/// Returns the channel ID and the token if the given key is the rate limit
+/// key
+pub fn is_rate_limit_key(key: &Key) -> Option<(ChannelId, Address)> {
+    rate_limit_channel_token(key, RATE_LIMIT)
+}
+
+/// Returns the channel ID and the token if the given key is the key of the
+/// flows of a rate limited token
+pub fn is_rate_limit_flows_key(key: &Key) -> Option<(ChannelId, Address)> {
+    rate_limit_channel_token(key, RATE_LIMIT_FLOWS)
+}
+
+fn rate_limit_channel_token(
+    key: &Key,
+    expected_prefix: &str,
+) -> Option<(ChannelId, Address)> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(addr),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::StringSeg(channel_id),
+            DbKeySeg::AddressSeg(token),
+        ] if addr == &Address::Internal(InternalAddress::Ibc)
+            && prefix == expected_prefix =>
+        {
+            let channel_id = ChannelId::from_str(channel_id).ok()?;
+            Some((channel_id, token.clone()))
+        }
+        _ => None,
+    }
+}
+
 /// Hash the denom
 #[inline]
 pub fn calc_hash(denom: impl AsRef<str>) -> String {
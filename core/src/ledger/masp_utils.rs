@@ -1,17 +1,35 @@
 //! MASP utilities
 
+#[cfg(feature = "mainnet")]
+use masp_primitives::consensus::MainNetwork as Network;
+#[cfg(not(feature = "mainnet"))]
+use masp_primitives::consensus::TestNetwork as Network;
+use masp_primitives::consensus::{BlockHeight as MaspBlockHeight, BranchId};
+use masp_primitives::group::GroupEncoding;
+use masp_primitives::jubjub;
+use masp_primitives::memo::MemoBytes;
 use masp_primitives::merkle_tree::CommitmentTree;
-use masp_primitives::sapling::Node;
-use masp_primitives::transaction::Transaction;
+use masp_primitives::sapling::note_encryption::sapling_note_encryption;
+use masp_primitives::sapling::redjubjub::Signature;
+use masp_primitives::sapling::{Node, Rseed};
+use masp_primitives::transaction::components::sapling::{
+    Authorized, Bundle, OutputDescription, GROTH_PROOF_SIZE,
+};
+use masp_primitives::transaction::components::I128Sum;
+use masp_primitives::transaction::{Transaction, TransactionData, TxVersion};
+use rand_core::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
 
+use super::masp_conversions::encode_asset_type;
 use super::storage_api::{StorageRead, StorageWrite};
 use crate::ledger::storage_api::{Error, Result};
-use crate::types::address::MASP;
+use crate::types::address::{Address, MASP};
 use crate::types::hash::Hash;
+use crate::types::masp::PaymentAddress;
 use crate::types::storage::{BlockHeight, Epoch, Key, KeySeg, TxIndex};
 use crate::types::token::{
-    Transfer, HEAD_TX_KEY, MASP_NOTE_COMMITMENT_TREE_KEY, MASP_NULLIFIERS_KEY,
-    PIN_KEY_PREFIX, TX_KEY_PREFIX,
+    Amount, MaspDenom, Transfer, HEAD_TX_KEY, MASP_NOTE_COMMITMENT_TREE_KEY,
+    MASP_NULLIFIERS_KEY, PIN_KEY_PREFIX, TX_KEY_PREFIX,
 };
 
 // Writes the nullifiers of the provided masp transaction to storage
@@ -111,3 +129,187 @@ pub fn handle_masp_tx(
 
     Ok(())
 }
+
+/// Random number generator seeded by the data of a transaction, such that all
+/// the nodes build the same natively shielded notes
+struct SeededRng {
+    seed: [u8; 32],
+    counter: u64,
+}
+
+impl SeededRng {
+    fn new(seed: &[u8]) -> Self {
+        Self {
+            seed: Sha256::digest(seed).into(),
+            counter: 0,
+        }
+    }
+
+    fn next_block(&mut self) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        hasher.update(self.seed);
+        hasher.update(self.counter.to_le_bytes());
+        self.counter += 1;
+        hasher.finalize().into()
+    }
+}
+
+impl RngCore for SeededRng {
+    fn next_u32(&mut self) -> u32 {
+        rand_core::impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        rand_core::impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(32) {
+            let block = self.next_block();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(
+        &mut self,
+        dest: &mut [u8],
+    ) -> std::result::Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+impl CryptoRng for SeededRng {}
+
+/// Build the MASP transaction shielding the given amount of a token to a
+/// payment address natively, i.e. without a transaction made by the receiver.
+/// The notes are derived from the seed, which must be unique to the
+/// transaction, so that every node builds the same transaction. It has no
+/// proofs nor binding signature since the note commitments are computed by
+/// the protocol itself.
+pub fn native_shielding_tx(
+    seed: &[u8],
+    target: &PaymentAddress,
+    token: &Address,
+    amount: Amount,
+    epoch: Epoch,
+) -> Result<Transaction> {
+    let mut rng = SeededRng::new(seed);
+    let target: masp_primitives::sapling::PaymentAddress = (*target).into();
+    let mut shielded_outputs = vec![];
+    let mut value_balance = I128Sum::zero();
+    for denom in MaspDenom::iter() {
+        let value = denom.denominate(&amount);
+        // At least one note is made even for a zero amount
+        if value == 0 && denom != MaspDenom::Zero {
+            continue;
+        }
+        let asset_type = encode_asset_type(token.clone(), denom, epoch);
+        let mut rseed = [0u8; 32];
+        rng.fill_bytes(&mut rseed);
+        let note = target
+            .create_note(asset_type, value, Rseed::AfterZip212(rseed))
+            .ok_or(Error::SimpleMessage(
+                "Invalid payment address for the shielded note",
+            ))?;
+        let mut rcv = [0u8; 64];
+        rng.fill_bytes(&mut rcv);
+        let cv: jubjub::ExtendedPoint = asset_type
+            .value_commitment(value, jubjub::Fr::from_bytes_wide(&rcv))
+            .commitment()
+            .into();
+        let cmu = note.cmu();
+        let encryptor = sapling_note_encryption::<Network>(
+            None,
+            note,
+            target,
+            MemoBytes::empty(),
+        );
+        shielded_outputs.push(OutputDescription {
+            cv,
+            cmu,
+            ephemeral_key: encryptor.epk().to_bytes().into(),
+            enc_ciphertext: encryptor.encrypt_note_plaintext(),
+            out_ciphertext: encryptor
+                .encrypt_outgoing_plaintext(&cv, &cmu, &mut rng),
+            zkproof: [0u8; GROTH_PROOF_SIZE],
+        });
+        value_balance -= I128Sum::from_nonnegative(asset_type, value as i128)
+            .map_err(|()| {
+            Error::SimpleMessage("Invalid value of the shielded note")
+        })?;
+    }
+    let binding_sig = Signature::read(&[0u8; 64][..])
+        .expect("Reading an empty signature shouldn't fail");
+    let bundle = Bundle {
+        shielded_spends: vec![],
+        shielded_converts: vec![],
+        shielded_outputs,
+        value_balance,
+        authorization: Authorized { binding_sig },
+    };
+    TransactionData::from_parts(
+        TxVersion::MASPv5,
+        BranchId::MASP,
+        0,
+        MaspBlockHeight::from_u32(0),
+        None,
+        Some(bundle),
+    )
+    .freeze()
+    .map_err(Error::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use masp_primitives::sapling::note_encryption::{
+        try_sapling_note_decryption, PreparedIncomingViewingKey,
+    };
+    use masp_primitives::zip32::{ExtendedFullViewingKey, ExtendedSpendingKey};
+
+    use super::*;
+    use crate::types::address::nam;
+
+    /// Test that the natively shielded notes are the same for the same seed
+    /// and that they can be found by the receiver.
+    #[test]
+    fn test_native_shielding_tx() {
+        let xsk = ExtendedSpendingKey::master(&[0u8; 32]);
+        let vk = ExtendedFullViewingKey::from(&xsk).fvk.vk;
+        let (_, payment_address) = xsk.default_address();
+        let target = PaymentAddress::from(payment_address);
+        let amount = Amount::from_u64(123);
+        let epoch = Epoch(1);
+
+        let tx = native_shielding_tx(b"seed", &target, &nam(), amount, epoch)
+            .unwrap();
+        assert_eq!(
+            tx,
+            native_shielding_tx(b"seed", &target, &nam(), amount, epoch)
+                .unwrap()
+        );
+        assert_ne!(
+            tx,
+            native_shielding_tx(b"other", &target, &nam(), amount, epoch)
+                .unwrap()
+        );
+
+        let bundle = tx.sapling_bundle().unwrap();
+        assert_eq!(bundle.shielded_outputs.len(), 1);
+        let ivk = PreparedIncomingViewingKey::new(&vk.ivk());
+        let (note, to, _) = try_sapling_note_decryption(
+            &Network,
+            1.into(),
+            &ivk,
+            &bundle.shielded_outputs[0],
+        )
+        .unwrap();
+        assert_eq!(to, payment_address);
+        assert_eq!(note.value, 123);
+        assert_eq!(
+            note.asset_type,
+            encode_asset_type(nam(), MaspDenom::Zero, epoch)
+        );
+        assert_eq!(note.cmu(), bundle.shielded_outputs[0].cmu);
+    }
+}
@@ -1,18 +1,26 @@
 //! PGF lib code.
 
+use crate::ledger::governance::storage::proposal::{PGFIbcTarget, PGFTarget};
 use crate::ledger::parameters::storage as params_storage;
 use crate::ledger::storage_api::pgf::{
     get_parameters, get_payments, get_stewards,
 };
 use crate::ledger::storage_api::token::credit_tokens;
 use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
+use crate::types::address::Address;
 use crate::types::dec::Dec;
 use crate::types::token;
 
-/// Apply the PGF inflation.
-pub fn apply_inflation<S>(storage: &mut S) -> storage_api::Result<()>
+/// Apply the PGF inflation. The fundings of targets on other chains are paid
+/// with the given function, which transfers the token from the PGF address
+/// over IBC.
+pub fn apply_inflation<S, F>(
+    storage: &mut S,
+    transfer_over_ibc: F,
+) -> storage_api::Result<()>
 where
     S: StorageRead + StorageWrite,
+    F: Fn(&mut S, &Address, &Address, &PGFIbcTarget) -> storage_api::Result<()>,
 {
     let pgf_parameters = get_parameters(storage)?;
     let staking_token = storage.get_native_token()?;
@@ -46,25 +54,32 @@ where
     pgf_fundings.sort_by(|a, b| a.id.cmp(&b.id));
 
     for funding in pgf_fundings {
-        if storage_api::token::transfer(
-            storage,
-            &staking_token,
-            &super::ADDRESS,
-            &funding.detail.target,
-            funding.detail.amount,
-        )
-        .is_ok()
-        {
+        let result = match &funding.detail {
+            PGFTarget::Internal(target) => storage_api::token::transfer(
+                storage,
+                &staking_token,
+                &super::ADDRESS,
+                &target.target,
+                target.amount,
+            ),
+            PGFTarget::Ibc(target) => transfer_over_ibc(
+                storage,
+                &staking_token,
+                &super::ADDRESS,
+                target,
+            ),
+        };
+        if result.is_ok() {
             tracing::info!(
                 "Paying {} tokens for {} project.",
-                funding.detail.amount.to_string_native(),
-                &funding.detail.target,
+                funding.detail.amount().to_string_native(),
+                &funding.detail,
             );
         } else {
             tracing::warn!(
                 "Failed to pay {} tokens for {} project.",
-                funding.detail.amount.to_string_native(),
-                &funding.detail.target,
+                funding.detail.amount().to_string_native(),
+                &funding.detail,
             );
         }
     }
@@ -62,14 +62,15 @@ pub fn fundings_key_prefix() -> Key {
     }
 }
 
-/// LazyMap handler for the pgf fundings substorage
-pub fn fundings_handle() -> LazyMap<Address, StoragePgfFunding> {
+/// LazyMap handler for the pgf fundings substorage, keyed by the funding
+/// target
+pub fn fundings_handle() -> LazyMap<String, StoragePgfFunding> {
     LazyMap::open(fundings_key_prefix())
 }
 
 /// Check if the given storage key is a pgf funding key.
 pub fn is_fundings_key(key: &Key) -> bool {
-    matches!(&key.segments[..], [DbKeySeg::AddressSeg(pgf), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(data), DbKeySeg::AddressSeg(_)] if pgf.eq(&ADDRESS)
+    matches!(&key.segments[..], [DbKeySeg::AddressSeg(pgf), DbKeySeg::StringSeg(prefix), DbKeySeg::StringSeg(data), _] if pgf.eq(&ADDRESS)
                && prefix.as_str() == Keys::VALUES.fundings
                 && data.as_str() == lazy_map::DATA_SUBKEY)
 }
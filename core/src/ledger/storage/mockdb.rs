@@ -17,8 +17,8 @@ use super::{
     BlockStateRead, BlockStateWrite, DBIter, DBWriteBatch, Error, Result, DB,
 };
 use crate::ledger::masp_conversions::ConversionState;
-use crate::ledger::replay_protection;
 use crate::ledger::storage::types::{self, KVBytes, PrefixIterator};
+use crate::ledger::{event_index, replay_protection};
 use crate::types::ethereum_events::Uint;
 use crate::types::ethereum_structs;
 use crate::types::hash::Hash;
@@ -626,6 +626,72 @@ impl DB for MockDB {
 
         Ok(())
     }
+
+    fn write_indexed_events(
+        &mut self,
+        _batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        events: &[(Vec<u8>, Vec<String>)],
+    ) -> Result<()> {
+        let mut db = self.0.borrow_mut();
+        for (index, (event, tags)) in (0..).zip(events) {
+            for tag in tags {
+                db.insert(
+                    format!(
+                        "event_index/{}",
+                        event_index::tag_key(tag, height, index)
+                    ),
+                    vec![],
+                );
+            }
+            db.insert(
+                format!(
+                    "event_index/{}",
+                    event_index::event_key(height, index)
+                ),
+                event.clone(),
+            );
+        }
+        Ok(())
+    }
+
+    fn read_indexed_events(
+        &self,
+        tag: Option<&str>,
+        from: BlockHeight,
+        to: BlockHeight,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<(BlockHeight, Vec<u8>)>> {
+        let prefix = format!(
+            "event_index/{}",
+            tag.map(event_index::tag_prefix)
+                .unwrap_or_else(event_index::all_prefix)
+        );
+        let db = self.0.borrow();
+        let mut events = vec![];
+        for (key, _) in db
+            .range(event_index::height_lower_bound(&prefix, from)..)
+            .take_while(|(key, _)| key.starts_with(&prefix))
+            .skip(offset as usize)
+        {
+            let (height, index) = event_index::parse_position(key)
+                .ok_or_else(|| Error::UnknownKey { key: key.clone() })?;
+            if height > to || events.len() as u64 >= limit {
+                break;
+            }
+            let event_key = format!(
+                "event_index/{}",
+                event_index::event_key(height, index)
+            );
+            let event = db
+                .get(&event_key)
+                .cloned()
+                .ok_or(Error::UnknownKey { key: event_key })?;
+            events.push((height, event));
+        }
+        Ok(events)
+    }
 }
 
 impl<'iter> DBIter<'iter> for MockDB {
@@ -767,3 +833,84 @@ fn unknown_key_error(key: &str) -> Result<()> {
         key: key.to_owned(),
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test the height range, the pagination and the tag filter of the event
+    /// index.
+    #[test]
+    fn test_read_indexed_events() {
+        let mut db = MockDB::default();
+
+        let tagged = |tags: &[&str], event: Vec<u8>| {
+            (
+                event,
+                tags.iter().map(|tag| tag.to_string()).collect::<Vec<_>>(),
+            )
+        };
+        let blocks = [
+            vec![
+                tagged(&["type:applied", "tx:x"], vec![1, 0]),
+                tagged(&["type:applied"], vec![1, 1]),
+            ],
+            vec![
+                tagged(&["type:applied"], vec![2, 0]),
+                tagged(&["tx:x"], vec![2, 1]),
+            ],
+            vec![tagged(&["type:applied", "tx:x"], vec![3, 0])],
+        ];
+        let mut batch = MockDB::batch();
+        for (height, events) in (1..).zip(&blocks) {
+            db.write_indexed_events(&mut batch, BlockHeight(height), events)
+                .unwrap();
+        }
+        db.exec_batch(batch).unwrap();
+
+        let read = |tag, from, to, offset, limit| {
+            db.read_indexed_events(
+                tag,
+                BlockHeight(from),
+                BlockHeight(to),
+                offset,
+                limit,
+            )
+            .unwrap()
+        };
+        let event = |height, index: u8| {
+            (BlockHeight(height), vec![height as u8, index])
+        };
+
+        // all the events, in order of emission
+        assert_eq!(
+            read(None, 0, u64::MAX, 0, 100),
+            vec![
+                event(1, 0),
+                event(1, 1),
+                event(2, 0),
+                event(2, 1),
+                event(3, 0)
+            ]
+        );
+        // the range of heights is inclusive
+        assert_eq!(read(None, 2, 2, 0, 100), vec![event(2, 0), event(2, 1)]);
+        assert!(read(None, 4, u64::MAX, 0, 100).is_empty());
+        // pagination
+        assert_eq!(read(None, 2, 3, 1, 2), vec![event(2, 1), event(3, 0)]);
+        assert_eq!(read(None, 0, u64::MAX, 4, 100), vec![event(3, 0)]);
+        assert!(read(None, 0, u64::MAX, 5, 100).is_empty());
+        assert!(read(None, 0, u64::MAX, 0, 0).is_empty());
+        // tag filter
+        assert_eq!(
+            read(Some("tx:x"), 0, u64::MAX, 0, 100),
+            vec![event(1, 0), event(2, 1), event(3, 0)]
+        );
+        assert_eq!(read(Some("tx:x"), 2, 3, 1, 1), vec![event(3, 0)]);
+        assert_eq!(
+            read(Some("type:applied"), 1, 1, 0, 100),
+            vec![event(1, 0), event(1, 1)]
+        );
+        assert!(read(Some("tx:y"), 0, u64::MAX, 0, 100).is_empty());
+    }
+}
@@ -377,6 +377,28 @@ pub trait DB: std::fmt::Debug {
         batch: &mut Self::WriteBatch,
         key: &Key,
     ) -> Result<()>;
+
+    /// Write the events emitted at the given height to the event index. Each
+    /// event is given encoded, along with the tags it can be looked up by.
+    fn write_indexed_events(
+        &mut self,
+        batch: &mut Self::WriteBatch,
+        height: BlockHeight,
+        events: &[(Vec<u8>, Vec<String>)],
+    ) -> Result<()>;
+
+    /// Read the encoded events with the given tag, or all of them if no tag is
+    /// given, emitted within the given inclusive range of heights. The first
+    /// `offset` matching events are skipped and at most `limit` events are
+    /// returned, in order of emission.
+    fn read_indexed_events(
+        &self,
+        tag: Option<&str>,
+        from: BlockHeight,
+        to: BlockHeight,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<(BlockHeight, Vec<u8>)>>;
 }
 
 /// A database prefix iterator.
@@ -781,6 +803,23 @@ where
         self.get_merkle_tree(height, None)
     }
 
+    /// Check that the current Merkle tree commits to the values in the
+    /// subspace, by rebuilding a tree from the subspace alone. The heights of
+    /// the pending transfers of the Ethereum bridge pool are not in the
+    /// subspace, so they are taken from the current tree.
+    pub fn verify_merkle_tree(&self) -> Result<bool> {
+        let mut tree = MerkleTree::<H>::default();
+        for (key, value, _gas) in self.db.iter_prefix(None) {
+            let key = Key::parse(key).map_err(Error::KeyError)?;
+            if is_pending_transfer_key(&key) {
+                tree.update(&key, self.block.tree.get(&key)?)?;
+            } else {
+                tree.update(&key, value)?;
+            }
+        }
+        Ok(tree.root() == self.block.tree.root())
+    }
+
     /// Rebuild Merkle tree with diffs in the DB.
     /// Base tree and the specified `store_type` subtree is rebuilt.
     /// If `store_type` isn't given, full Merkle tree is restored.
@@ -1584,4 +1623,24 @@ mod tests {
             assert_eq!(wl_storage.storage.block.epoch, epoch_before.next());
         }
     }
+
+    /// Test that the Merkle tree is verified against the values of the
+    /// subspace.
+    #[test]
+    fn test_verify_merkle_tree() {
+        let mut storage = TestStorage::default();
+        let key = Key::parse("testing/key").unwrap();
+        let other_key = Key::parse("testing/other").unwrap();
+        storage.write(&key, [1_u8]).unwrap();
+        storage.write(&other_key, [2_u8]).unwrap();
+        storage.delete(&other_key).unwrap();
+        assert!(storage.verify_merkle_tree().unwrap());
+
+        // A value that isn't committed to by the tree
+        storage
+            .db
+            .write_subspace_val(BlockHeight(1), &key, [2_u8])
+            .unwrap();
+        assert!(!storage.verify_merkle_tree().unwrap());
+    }
 }
@@ -484,14 +484,17 @@ impl WriteLog {
     pub fn drop_tx(&mut self) {
         self.tx_precommit_write_log.clear();
         self.tx_write_log.clear();
+        self.take_ibc_events();
     }
 
     /// Drop the current transaction's write log but keep the precommit one.
     /// This is useful only when a part of a transaction failed but it can still
     /// be valid and we want to keep the changes applied before the failed
-    /// section.
+    /// section. The IBC events that were not taken yet are dropped along with
+    /// the failed part.
     pub fn drop_tx_keep_precommit(&mut self) {
         self.tx_write_log.clear();
+        self.take_ibc_events();
     }
 
     /// Commit the current block's write log to the storage. Starts a new block
@@ -1042,6 +1045,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_drop_tx_ibc_events() {
+        let mut write_log = WriteLog::default();
+        let key =
+            storage::Key::parse("key").expect("cannot parse the key string");
+        let event = |event_type: &str| IbcEvent {
+            event_type: event_type.to_string(),
+            attributes: HashMap::new(),
+        };
+
+        // the events of a precommitted part are taken with it
+        write_log.write(&key, vec![1]).unwrap();
+        write_log.emit_ibc_event(event("first"));
+        assert_eq!(write_log.take_ibc_events().len(), 1);
+        write_log.precommit_tx();
+
+        // the events of a failed part are dropped with it
+        write_log.write(&key, vec![2]).unwrap();
+        write_log.emit_ibc_event(event("second"));
+        write_log.drop_tx_keep_precommit();
+        assert!(write_log.get_ibc_events().is_empty());
+        match write_log.read(&key).0 {
+            Some(StorageModification::Write { value }) => {
+                assert_eq!(*value, vec![1])
+            }
+            _ => panic!("unexpected result"),
+        }
+
+        write_log.emit_ibc_event(event("third"));
+        write_log.drop_tx();
+        assert!(write_log.get_ibc_events().is_empty());
+        assert!(write_log.read(&key).0.is_none());
+    }
+
     prop_compose! {
         fn arb_verifiers_changed_key_tx_all_key()
             (verifiers_from_tx in testing::arb_verifiers_from_tx())
@@ -1,10 +1,16 @@
 //! Cryptographic signature keys storage API
 
+use std::collections::BTreeMap;
+
 use super::*;
-use crate::types::account::AccountPublicKeysMap;
+use crate::types::account::{
+    AccountKeyOptions, AccountPublicKeysMap, KeyRole, PendingWithdrawal,
+    SpendingPolicy,
+};
 use crate::types::address::Address;
 use crate::types::key::*;
-use crate::types::storage::Key;
+use crate::types::storage::{Epoch, Key};
+use crate::types::token;
 
 /// Init the subspace of a new account
 pub fn init_account_storage<S>(
@@ -52,7 +58,8 @@ where
     Ok(public_keys)
 }
 
-/// Get the public key index map associated with an account
+/// Get the public key index map associated with an account, with the weights
+/// of the keys
 pub fn public_keys_index_map<S>(
     storage: &S,
     owner: &Address,
@@ -61,8 +68,42 @@ where
     S: StorageRead,
 {
     let public_keys = public_keys(storage, owner)?;
+    let mut public_keys_map = AccountPublicKeysMap::from_iter(public_keys);
+    public_keys_map.idx_to_weight = key_options(storage, owner)?
+        .into_iter()
+        .map(|(index, options)| (index, options.weight))
+        .collect();
+
+    Ok(public_keys_map)
+}
+
+/// Get the public key index map of the keys of an account that can sign for
+/// the given role, or for any action if no role is given
+pub fn public_keys_index_map_for_role<S>(
+    storage: &S,
+    owner: &Address,
+    role: Option<KeyRole>,
+) -> Result<AccountPublicKeysMap>
+where
+    S: StorageRead,
+{
+    let public_keys = public_keys(storage, owner)?;
+    let key_options = key_options(storage, owner)?;
 
-    Ok(AccountPublicKeysMap::from_iter(public_keys))
+    Ok(AccountPublicKeysMap::from_iter(public_keys)
+        .with_key_options(&key_options, role))
+}
+
+/// Get the options of the public keys associated with an account, by key
+/// index
+pub fn key_options<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<BTreeMap<u8, AccountKeyOptions>>
+where
+    S: StorageRead,
+{
+    key_options_handle(owner).iter(storage)?.collect()
 }
 
 /// Check if a user account exists in storage
@@ -94,6 +135,75 @@ where
     Ok(())
 }
 
+/// Set the options of the public key at a specific index
+pub fn set_key_options_at<S>(
+    storage: &mut S,
+    owner: &Address,
+    options: AccountKeyOptions,
+    index: u8,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    if options == AccountKeyOptions::default() {
+        key_options_handle(owner).remove(storage, &index)?;
+    } else {
+        key_options_handle(owner).insert(storage, index, options)?;
+    }
+    Ok(())
+}
+
+/// Set the options of the public keys of an account, by key index. Fails if
+/// options are given at an index without a public key or with a zero weight.
+pub fn set_key_options<S>(
+    storage: &mut S,
+    owner: &Address,
+    key_options: Vec<AccountKeyOptions>,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    if key_options.len() as u64 > pks_handle(owner).len(storage)? {
+        return Err(Error::new_const(
+            "Key options are given at an index without a public key",
+        ));
+    }
+    if key_options.iter().any(|options| options.weight == 0) {
+        return Err(Error::new_const("The weight of a key cannot be zero"));
+    }
+    for (index, options) in key_options.into_iter().enumerate() {
+        set_key_options_at(storage, owner, options, index as u8)?;
+    }
+    Ok(())
+}
+
+/// Check that the options of the public keys of an account are all at the
+/// index of a public key and have a non-zero weight
+pub fn are_key_options_valid<S>(storage: &S, owner: &Address) -> Result<bool>
+where
+    S: StorageRead,
+{
+    let num_public_keys = pks_handle(owner).len(storage)?;
+    Ok(key_options(storage, owner)?.iter().all(|(index, options)| {
+        u64::from(*index) < num_public_keys && options.weight > 0
+    }))
+}
+
+/// Clear the options of the public keys of an account
+pub fn clear_key_options<S>(storage: &mut S, owner: &Address) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    let indices = key_options_handle(owner)
+        .iter(storage)?
+        .map(|entry| entry.map(|(index, _)| index))
+        .collect::<Result<Vec<u8>>>()?;
+    for index in indices {
+        key_options_handle(owner).remove(storage, &index)?;
+    }
+    Ok(())
+}
+
 /// Clear the public keys account subtorage space
 pub fn clear_public_keys<S>(storage: &mut S, owner: &Address) -> Result<()>
 where
@@ -105,3 +215,274 @@ where
     }
     Ok(())
 }
+
+/// Get the spending policy in effect for an account in the current epoch
+pub fn spending_policy<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<Option<SpendingPolicy>>
+where
+    S: StorageRead,
+{
+    let current_epoch = storage.get_block_epoch()?;
+    match pending_spending_policy(storage, owner)? {
+        Some((epoch, policy)) if epoch <= current_epoch => Ok(policy),
+        _ => storage.read(&spending_policy_key(owner)),
+    }
+}
+
+/// Get a change of the spending policy of an account, together with the epoch
+/// from which it takes effect
+pub fn pending_spending_policy<S>(
+    storage: &S,
+    owner: &Address,
+) -> Result<Option<(Epoch, Option<SpendingPolicy>)>>
+where
+    S: StorageRead,
+{
+    storage.read(&pending_spending_policy_key(owner))
+}
+
+/// Set or clear the spending policy of an account. If a policy with a non-zero
+/// delay is in effect, the change is only applied after its delay.
+pub fn set_spending_policy<S>(
+    storage: &mut S,
+    owner: &Address,
+    policy: Option<SpendingPolicy>,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    let current_epoch = storage.get_block_epoch()?;
+    let policy_key = spending_policy_key(owner);
+    let pending_key = pending_spending_policy_key(owner);
+
+    // Apply a pending change that has already taken effect
+    let current = match pending_spending_policy(storage, owner)? {
+        Some((epoch, pending)) if epoch <= current_epoch => {
+            match &pending {
+                Some(pending) => storage.write(&policy_key, pending)?,
+                None => storage.delete(&policy_key)?,
+            }
+            storage.delete(&pending_key)?;
+            pending
+        }
+        _ => storage.read::<SpendingPolicy>(&policy_key)?,
+    };
+
+    match current {
+        Some(current) if current.delay > 0 => {
+            storage.write(&pending_key, (current_epoch + current.delay, policy))
+        }
+        _ => {
+            match policy {
+                Some(policy) => storage.write(&policy_key, policy)?,
+                None => storage.delete(&policy_key)?,
+            }
+            storage.delete(&pending_key)
+        }
+    }
+}
+
+/// Get the amount of a token debited from an account in the current epoch
+pub fn spent_in_epoch<S>(
+    storage: &S,
+    owner: &Address,
+    token: &Address,
+) -> Result<token::Amount>
+where
+    S: StorageRead,
+{
+    let current_epoch = storage.get_block_epoch()?;
+    let spent: Option<(Epoch, token::Amount)> =
+        storage.read(&spent_key(owner, token))?;
+    Ok(match spent {
+        Some((epoch, amount)) if epoch == current_epoch => amount,
+        _ => token::Amount::zero(),
+    })
+}
+
+/// Add a debit of a token to the amount spent by an account in the current
+/// epoch, if its spending policy limits the token
+pub fn record_spending<S>(
+    storage: &mut S,
+    owner: &Address,
+    token: &Address,
+    amount: token::Amount,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    let is_limited = spending_policy(storage, owner)?
+        .map(|policy| policy.epoch_limits.contains_key(token))
+        .unwrap_or_default();
+    if !is_limited {
+        return Ok(());
+    }
+    let current_epoch = storage.get_block_epoch()?;
+    let spent = spent_in_epoch(storage, owner, token)?
+        .checked_add(amount)
+        .ok_or_else(|| Error::new_const("The spent amount would overflow"))?;
+    storage.write(&spent_key(owner, token), (current_epoch, spent))
+}
+
+/// Schedule a withdrawal of an account's tokens above the threshold of its
+/// spending policy. Returns the id of the withdrawal.
+pub fn schedule_withdrawal<S>(
+    storage: &mut S,
+    owner: &Address,
+    token: &Address,
+    target: &Address,
+    amount: token::Amount,
+) -> Result<u64>
+where
+    S: StorageWrite + StorageRead,
+{
+    let policy = spending_policy(storage, owner)?.ok_or_else(|| {
+        Error::new_const("The account does not have a spending policy")
+    })?;
+    if !policy.is_allowed_recipient(target) {
+        return Err(Error::new_const(
+            "The target is not an allowed recipient of the spending policy",
+        ));
+    }
+    let current_epoch = storage.get_block_epoch()?;
+    let counter_key = withdrawals_counter_key(owner);
+    let id: u64 = storage.read(&counter_key)?.unwrap_or_default();
+    storage.write(&counter_key, id + 1)?;
+    pending_withdrawals_handle(owner).insert(
+        storage,
+        id,
+        PendingWithdrawal {
+            token: token.clone(),
+            target: target.clone(),
+            amount,
+            executable_from: current_epoch + policy.delay,
+        },
+    )?;
+    Ok(id)
+}
+
+/// Execute a scheduled withdrawal whose delay has expired
+pub fn execute_withdrawal<S>(
+    storage: &mut S,
+    owner: &Address,
+    id: u64,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    let handle = pending_withdrawals_handle(owner);
+    let withdrawal = handle.get(storage, &id)?.ok_or_else(|| {
+        Error::new_const("The pending withdrawal does not exist")
+    })?;
+    if withdrawal.executable_from > storage.get_block_epoch()? {
+        return Err(Error::new_const(
+            "The pending withdrawal cannot be executed yet",
+        ));
+    }
+    handle.remove(storage, &id)?;
+    // Scheduled withdrawals don't count towards the epoch limits
+    super::token::transfer_unrecorded(
+        storage,
+        &withdrawal.token,
+        owner,
+        &withdrawal.target,
+        withdrawal.amount,
+    )
+}
+
+/// Cancel a scheduled withdrawal
+pub fn cancel_withdrawal<S>(
+    storage: &mut S,
+    owner: &Address,
+    id: u64,
+) -> Result<()>
+where
+    S: StorageWrite + StorageRead,
+{
+    pending_withdrawals_handle(owner)
+        .remove(storage, &id)?
+        .ok_or_else(|| {
+            Error::new_const("The pending withdrawal does not exist")
+        })?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ledger::storage::testing::TestWlStorage;
+    use crate::ledger::storage_api::token::{
+        credit_tokens, read_balance, transfer,
+    };
+    use crate::types::address::{nam, testing};
+
+    fn policy(delay: u64) -> SpendingPolicy {
+        SpendingPolicy {
+            epoch_limits: [(nam(), token::Amount::native_whole(10))].into(),
+            delay,
+            ..Default::default()
+        }
+    }
+
+    /// Test that changes of a policy with a delay only take effect after it.
+    #[test]
+    fn test_set_spending_policy_delay() {
+        let mut storage = TestWlStorage::default();
+        let owner = testing::established_address_1();
+
+        // Without a policy in effect, the new one applies immediately
+        set_spending_policy(&mut storage, &owner, Some(policy(2))).unwrap();
+        assert_eq!(spending_policy(&storage, &owner).unwrap(), Some(policy(2)));
+
+        set_spending_policy(&mut storage, &owner, None).unwrap();
+        assert_eq!(spending_policy(&storage, &owner).unwrap(), Some(policy(2)));
+        assert_eq!(
+            pending_spending_policy(&storage, &owner).unwrap(),
+            Some((Epoch(2), None))
+        );
+
+        storage.storage.block.epoch = Epoch(2);
+        assert_eq!(spending_policy(&storage, &owner).unwrap(), None);
+
+        // The pending change is applied before setting a new policy
+        set_spending_policy(&mut storage, &owner, Some(policy(0))).unwrap();
+        assert_eq!(spending_policy(&storage, &owner).unwrap(), Some(policy(0)));
+        assert_eq!(pending_spending_policy(&storage, &owner).unwrap(), None);
+    }
+
+    /// Test that only the debits of limited tokens are recorded, and that
+    /// scheduled withdrawals are not.
+    #[test]
+    fn test_record_spending_and_withdrawals() {
+        let mut storage = TestWlStorage::default();
+        let owner = testing::established_address_1();
+        let target = testing::established_address_2();
+        let other_token = testing::established_address_3();
+        let amount = token::Amount::native_whole(3);
+        credit_tokens(&mut storage, &nam(), &owner, amount * 3).unwrap();
+        set_spending_policy(&mut storage, &owner, Some(policy(1))).unwrap();
+
+        transfer(&mut storage, &nam(), &owner, &target, amount).unwrap();
+        record_spending(&mut storage, &owner, &other_token, amount).unwrap();
+        assert_eq!(spent_in_epoch(&storage, &owner, &nam()).unwrap(), amount);
+        assert!(spent_in_epoch(&storage, &owner, &other_token)
+            .unwrap()
+            .is_zero());
+
+        let id =
+            schedule_withdrawal(&mut storage, &owner, &nam(), &target, amount)
+                .unwrap();
+        assert!(execute_withdrawal(&mut storage, &owner, id).is_err());
+        storage.storage.block.epoch = Epoch(1);
+        assert!(spent_in_epoch(&storage, &owner, &nam()).unwrap().is_zero());
+        execute_withdrawal(&mut storage, &owner, id).unwrap();
+        assert!(spent_in_epoch(&storage, &owner, &nam()).unwrap().is_zero());
+        assert_eq!(
+            read_balance(&storage, &nam(), &target).unwrap(),
+            amount * 2
+        );
+        assert!(cancel_withdrawal(&mut storage, &owner, id).is_err());
+    }
+}
@@ -1,6 +1,6 @@
 //! Governance
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
 use borsh::BorshDeserialize;
 
@@ -8,16 +8,22 @@ use super::token;
 use crate::ledger::governance::parameters::GovernanceParameters;
 use crate::ledger::governance::storage::keys as governance_keys;
 use crate::ledger::governance::storage::proposal::{
-    ProposalType, StorageProposal,
+    ProposalType, StorageProposal, Upgrade,
+};
+use crate::ledger::governance::storage::vote::{
+    StorageProposalVote, VoteRecord,
+};
+use crate::ledger::governance::utils::{
+    compute_proposal_result, ProposalResult, ProposalVotes, RepresentedVote,
+    TallyType, Vote, VoteOverride,
 };
-use crate::ledger::governance::storage::vote::StorageProposalVote;
-use crate::ledger::governance::utils::Vote;
 use crate::ledger::governance::ADDRESS as governance_address;
 use crate::ledger::storage_api::{self, StorageRead, StorageWrite};
 use crate::types::address::Address;
+use crate::types::dec::Dec;
 use crate::types::storage::Epoch;
 use crate::types::transaction::governance::{
-    InitProposalData, VoteProposalData,
+    DepositProposalData, InitProposalData, VoteDelegationData, VoteProposalData,
 };
 
 /// A proposal creation transaction.
@@ -45,9 +51,14 @@ where
 
     let proposal_type_key = governance_keys::get_proposal_type_key(proposal_id);
     match data.r#type {
-        ProposalType::Default(Some(_)) => {
+        ProposalType::Default(Some(_)) | ProposalType::Emergency(Some(_)) => {
             // Remove wasm code and write it under a different subkey
-            storage.write(&proposal_type_key, ProposalType::Default(None))?;
+            let proposal_type = if data.r#type.is_emergency() {
+                ProposalType::Emergency(None)
+            } else {
+                ProposalType::Default(None)
+            };
+            storage.write(&proposal_type_key, proposal_type)?;
             let proposal_code_key =
                 governance_keys::get_proposal_code_key(proposal_id);
             let proposal_code = code.clone().ok_or(
@@ -69,7 +80,9 @@ where
     let grace_epoch_key = governance_keys::get_grace_epoch_key(proposal_id);
     storage.write(&grace_epoch_key, data.grace_epoch)?;
 
-    if let ProposalType::Default(Some(_)) = data.r#type {
+    if let ProposalType::Default(Some(_)) | ProposalType::Emergency(Some(_)) =
+        data.r#type
+    {
         let proposal_code_key =
             governance_keys::get_proposal_code_key(proposal_id);
         let proposal_code =
@@ -79,12 +92,12 @@ where
 
     storage.write(&counter_key, proposal_id + 1)?;
 
-    let min_proposal_funds_key = governance_keys::get_min_proposal_fund_key();
-    let min_proposal_funds: token::Amount =
-        storage.read(&min_proposal_funds_key)?.unwrap();
-
     let funds_key = governance_keys::get_funds_key(proposal_id);
-    storage.write(&funds_key, min_proposal_funds)?;
+    storage.write(&funds_key, data.deposit)?;
+
+    let deposit_key =
+        governance_keys::get_proposal_deposit_key(proposal_id, &data.author);
+    storage.write(&deposit_key, data.deposit)?;
 
     // this key must always be written for each proposal
     let committing_proposals_key =
@@ -99,10 +112,60 @@ where
         &storage.get_native_token()?,
         &data.author,
         &governance_address,
-        min_proposal_funds,
+        data.deposit,
+    )
+}
+
+/// A proposal deposit transaction.
+pub fn deposit_proposal<S>(
+    storage: &mut S,
+    data: DepositProposalData,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let funds_key = governance_keys::get_funds_key(data.id);
+    let funds: token::Amount = storage.read(&funds_key)?.ok_or_else(|| {
+        storage_api::Error::new_const("The proposal does not exist")
+    })?;
+    storage.write(&funds_key, funds + data.amount)?;
+
+    let deposit_key =
+        governance_keys::get_proposal_deposit_key(data.id, &data.depositor);
+    let deposit: token::Amount =
+        storage.read(&deposit_key)?.unwrap_or_default();
+    storage.write(&deposit_key, deposit + data.amount)?;
+
+    token::transfer(
+        storage,
+        &storage.get_native_token()?,
+        &data.depositor,
+        &governance_address,
+        data.amount,
     )
 }
 
+/// Query the deposits of all the backers of a proposal
+pub fn get_proposal_deposits<S>(
+    storage: &S,
+    proposal_id: u64,
+) -> storage_api::Result<BTreeMap<Address, token::Amount>>
+where
+    S: StorageRead,
+{
+    let prefix = governance_keys::get_proposal_deposit_prefix_key(proposal_id);
+    let mut deposits = BTreeMap::new();
+    for entry in storage_api::iter_prefix::<token::Amount>(storage, &prefix)? {
+        let (key, deposit) = entry?;
+        if let Some(depositor) =
+            governance_keys::get_proposal_depositor_address(&key)
+        {
+            deposits.insert(depositor.to_owned(), deposit);
+        }
+    }
+    Ok(deposits)
+}
+
 /// A proposal vote transaction.
 pub fn vote_proposal<S>(
     storage: &mut S,
@@ -111,17 +174,206 @@ pub fn vote_proposal<S>(
 where
     S: StorageRead + StorageWrite,
 {
-    for delegation in data.delegations {
+    let history = governance_keys::vote_history_handle(data.id, &data.voter);
+
+    // A new vote replaces the previous one, which is kept in the history.
+    // The votes that the previous one cast for delegations that the new one
+    // doesn't cover anymore must not be tallied.
+    if let Some(previous) = get_current_vote(storage, data.id, &data.voter)? {
+        for delegation in previous
+            .delegations
+            .iter()
+            .filter(|delegation| !data.delegations.contains(delegation))
+        {
+            let vote_key = governance_keys::get_vote_proposal_key(
+                data.id,
+                data.voter.clone(),
+                delegation.clone(),
+            );
+            storage.delete(&vote_key)?;
+        }
+        if !previous.delegations.contains(&data.voter)
+            && data.delegations.contains(&data.voter)
+        {
+            let representative_vote_key =
+                governance_keys::get_representative_vote_key(
+                    data.id,
+                    &data.voter,
+                );
+            storage.delete(&representative_vote_key)?;
+        }
+    }
+
+    // Validators vote with their own stake, the vote of any other address
+    // also counts for the delegators that delegated their voting power to it
+    let is_validator_vote = data.delegations.contains(&data.voter);
+    for delegation in &data.delegations {
         let vote_key = governance_keys::get_vote_proposal_key(
             data.id,
             data.voter.clone(),
-            delegation,
+            delegation.clone(),
         );
         storage.write(&vote_key, data.vote.clone())?;
     }
+    if !is_validator_vote {
+        let representative_vote_key =
+            governance_keys::get_representative_vote_key(data.id, &data.voter);
+        storage.write(&representative_vote_key, data.vote.clone())?;
+    }
+
+    let record = VoteRecord {
+        vote: data.vote,
+        delegations: data.delegations,
+        epoch: storage.get_block_epoch()?,
+        height: storage.get_block_height()?,
+    };
+    history.push(storage, record)?;
+    governance_keys::voter_votes_handle(&data.voter)
+        .insert(storage, data.id)?;
     Ok(())
 }
 
+/// Read the history of the votes of a voter on a proposal, from the oldest
+/// to the current one
+pub fn get_vote_history<S>(
+    storage: &S,
+    proposal_id: u64,
+    voter: &Address,
+) -> storage_api::Result<Vec<VoteRecord>>
+where
+    S: StorageRead,
+{
+    governance_keys::vote_history_handle(proposal_id, voter)
+        .iter(storage)?
+        .collect()
+}
+
+/// Read the current vote of a voter on a proposal, i.e. the last one of its
+/// history, if any
+pub fn get_current_vote<S>(
+    storage: &S,
+    proposal_id: u64,
+    voter: &Address,
+) -> storage_api::Result<Option<VoteRecord>>
+where
+    S: StorageRead,
+{
+    let history = governance_keys::vote_history_handle(proposal_id, voter);
+    match history.len(storage)? {
+        0 => Ok(None),
+        len => history.get(storage, len - 1),
+    }
+}
+
+/// Query the current vote of a voter on each of the proposals it voted on, by
+/// proposal id
+pub fn get_voter_votes<S>(
+    storage: &S,
+    voter: &Address,
+) -> storage_api::Result<BTreeMap<u64, VoteRecord>>
+where
+    S: StorageRead,
+{
+    let mut votes = BTreeMap::new();
+    for proposal_id in
+        governance_keys::voter_votes_handle(voter).iter(storage)?
+    {
+        let proposal_id = proposal_id?;
+        if let Some(vote) = get_current_vote(storage, proposal_id, voter)? {
+            votes.insert(proposal_id, vote);
+        }
+    }
+    Ok(votes)
+}
+
+/// A vote delegation transaction.
+pub fn delegate_vote<S>(
+    storage: &mut S,
+    data: VoteDelegationData,
+) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    let representative_key =
+        governance_keys::get_representative_key(&data.delegator);
+    match data.representative {
+        Some(representative) => {
+            storage.write(&representative_key, representative)
+        }
+        None => storage.delete(&representative_key),
+    }
+}
+
+/// Read the representative to which a delegator delegated its voting power,
+/// if any
+pub fn get_representative<S>(
+    storage: &S,
+    delegator: &Address,
+) -> storage_api::Result<Option<Address>>
+where
+    S: StorageRead,
+{
+    storage.read(&governance_keys::get_representative_key(delegator))
+}
+
+/// Query all the delegators that delegated their voting power to the given
+/// representative
+pub fn get_represented_delegators<S>(
+    storage: &S,
+    representative: &Address,
+) -> storage_api::Result<Vec<Address>>
+where
+    S: StorageRead,
+{
+    let prefix = governance_keys::get_representative_prefix_key();
+    let mut delegators = vec![];
+    for entry in storage_api::iter_prefix::<Address>(storage, &prefix)? {
+        let (key, delegator_representative) = entry?;
+        if &delegator_representative != representative {
+            continue;
+        }
+        if let Some(delegator) = governance_keys::get_represented_address(&key)
+        {
+            delegators.push(delegator.to_owned());
+        }
+    }
+    Ok(delegators)
+}
+
+/// Query the votes of the representatives on a proposal for all the
+/// delegators that delegated their voting power to them. A delegator is
+/// listed even if it also voted directly, in which case its direct vote takes
+/// precedence.
+pub fn get_represented_votes<S>(
+    storage: &S,
+    proposal_id: u64,
+) -> storage_api::Result<Vec<RepresentedVote>>
+where
+    S: StorageRead,
+{
+    let prefix = governance_keys::get_representative_prefix_key();
+    let mut votes = vec![];
+    for entry in storage_api::iter_prefix::<Address>(storage, &prefix)? {
+        let (key, representative) = entry?;
+        let delegator = match governance_keys::get_represented_address(&key) {
+            Some(delegator) => delegator.to_owned(),
+            None => continue,
+        };
+        let vote_key = governance_keys::get_representative_vote_key(
+            proposal_id,
+            &representative,
+        );
+        if let Some(data) = storage.read(&vote_key)? {
+            votes.push(RepresentedVote {
+                delegator,
+                representative,
+                data,
+            });
+        }
+    }
+    Ok(votes)
+}
+
 /// Read a proposal by id from storage
 pub fn get_proposal_by_id<S>(
     storage: &S,
@@ -247,6 +499,34 @@ where
 
     let max_proposal_period: u64 = get_max_proposal_period(storage)?;
 
+    let key = governance_keys::get_min_emergency_proposal_voting_period_key();
+    let min_emergency_proposal_voting_period: u64 =
+        storage.read(&key)?.expect("Parameter should be defined.");
+
+    let key = governance_keys::get_default_proposal_tally_key();
+    let default_proposal_tally: TallyType =
+        storage.read(&key)?.expect("Parameter should be defined.");
+
+    let key = governance_keys::get_emergency_proposal_tally_key();
+    let emergency_proposal_tally: TallyType =
+        storage.read(&key)?.expect("Parameter should be defined.");
+
+    let key = governance_keys::get_pgf_steward_proposal_tally_key();
+    let pgf_steward_proposal_tally: TallyType =
+        storage.read(&key)?.expect("Parameter should be defined.");
+
+    let key = governance_keys::get_pgf_funding_proposal_tally_key();
+    let pgf_funding_proposal_tally: TallyType =
+        storage.read(&key)?.expect("Parameter should be defined.");
+
+    let key = governance_keys::get_pgf_steward_funding_proposal_tally_key();
+    let pgf_steward_funding_proposal_tally: TallyType =
+        storage.read(&key)?.expect("Parameter should be defined.");
+
+    let key = governance_keys::get_veto_threshold_key();
+    let veto_threshold: Dec =
+        storage.read(&key)?.expect("Parameter should be defined.");
+
     Ok(GovernanceParameters {
         min_proposal_fund,
         max_proposal_code_size,
@@ -254,6 +534,13 @@ where
         max_proposal_period,
         max_proposal_content_size,
         min_proposal_grace_epochs,
+        min_emergency_proposal_voting_period,
+        default_proposal_tally,
+        emergency_proposal_tally,
+        pgf_steward_proposal_tally,
+        pgf_funding_proposal_tally,
+        pgf_steward_funding_proposal_tally,
+        veto_threshold,
     })
 }
 
@@ -267,3 +554,207 @@ where
         storage.read(&key)?.expect("Parameter should be defined.");
     Ok(max_proposal_period)
 }
+
+/// Read the upgrade scheduled by an accepted upgrade proposal, if any
+pub fn get_pending_upgrade<S>(
+    storage: &S,
+) -> storage_api::Result<Option<Upgrade>>
+where
+    S: StorageRead,
+{
+    storage.read(&governance_keys::get_pending_upgrade_key())
+}
+
+/// Schedule an upgrade, replacing the previously scheduled one
+pub fn write_pending_upgrade<S>(
+    storage: &mut S,
+    upgrade: &Upgrade,
+) -> storage_api::Result<()>
+where
+    S: StorageWrite,
+{
+    storage.write(&governance_keys::get_pending_upgrade_key(), upgrade)
+}
+
+/// Remove the scheduled upgrade, once it has been applied
+pub fn remove_pending_upgrade<S>(storage: &mut S) -> storage_api::Result<()>
+where
+    S: StorageWrite,
+{
+    storage.delete(&governance_keys::get_pending_upgrade_key())
+}
+
+/// The reads of the proof-of-stake state that are needed to tally the votes
+/// of a proposal. Governance doesn't depend on the proof-of-stake system,
+/// which implements this trait for its parameters.
+pub trait StakeReader {
+    /// Check if the given address is a validator
+    fn is_validator<S>(
+        &self,
+        storage: &S,
+        address: &Address,
+    ) -> storage_api::Result<bool>
+    where
+        S: StorageRead;
+
+    /// Find the validators to which the given owner delegated
+    fn delegation_validators<S>(
+        &self,
+        storage: &S,
+        owner: &Address,
+    ) -> storage_api::Result<HashSet<Address>>
+    where
+        S: StorageRead;
+
+    /// Read the stake of a validator at the given epoch
+    fn validator_stake<S>(
+        &self,
+        storage: &S,
+        validator: &Address,
+        epoch: Epoch,
+    ) -> storage_api::Result<token::Amount>
+    where
+        S: StorageRead;
+
+    /// Read the amount bonded by a delegator to a validator at the given
+    /// epoch
+    fn bond_amount<S>(
+        &self,
+        storage: &S,
+        delegator: &Address,
+        validator: &Address,
+        epoch: Epoch,
+    ) -> storage_api::Result<token::Amount>
+    where
+        S: StorageRead;
+
+    /// Read the total stake at the given epoch
+    fn total_stake<S>(
+        &self,
+        storage: &S,
+        epoch: Epoch,
+    ) -> storage_api::Result<token::Amount>
+    where
+        S: StorageRead;
+}
+
+/// Get the votes counted for the bonds of the delegators, cast directly or by
+/// their representatives, along with the votes of their validators
+pub fn get_vote_overrides<S>(
+    storage: &S,
+    stake: &impl StakeReader,
+    proposal_id: u64,
+) -> storage_api::Result<Vec<VoteOverride>>
+where
+    S: StorageRead,
+{
+    let votes = get_proposal_votes(storage, proposal_id)?;
+    let validator_votes: HashMap<Address, StorageProposalVote> = votes
+        .iter()
+        .filter(|vote| vote.is_validator())
+        .map(|vote| (vote.validator.clone(), vote.data.clone()))
+        .collect();
+
+    let mut delegator_votes = vec![];
+    let mut direct_voters = HashSet::new();
+    for vote in votes.into_iter().filter(|vote| !vote.is_validator()) {
+        direct_voters.insert(vote.delegator.clone());
+        delegator_votes.push(VoteOverride {
+            validator_data: validator_votes.get(&vote.validator).cloned(),
+            delegator: vote.delegator,
+            validator: vote.validator,
+            representative: None,
+            data: vote.data,
+        });
+    }
+
+    for represented_vote in get_represented_votes(storage, proposal_id)? {
+        let RepresentedVote {
+            delegator,
+            representative,
+            data,
+        } = represented_vote;
+        // Direct votes take precedence and validators vote with their own
+        // stake only
+        if direct_voters.contains(&delegator)
+            || stake.is_validator(storage, &delegator)?
+        {
+            continue;
+        }
+        for validator in stake.delegation_validators(storage, &delegator)? {
+            delegator_votes.push(VoteOverride {
+                validator_data: validator_votes.get(&validator).cloned(),
+                delegator: delegator.clone(),
+                validator,
+                representative: Some(representative.clone()),
+                data: data.clone(),
+            });
+        }
+    }
+    Ok(delegator_votes)
+}
+
+/// Compute the votes of a proposal with the stake at the given epoch
+pub fn compute_proposal_votes<S>(
+    storage: &S,
+    stake: &impl StakeReader,
+    proposal_id: u64,
+    epoch: Epoch,
+) -> storage_api::Result<ProposalVotes>
+where
+    S: StorageRead,
+{
+    let mut proposal_votes = ProposalVotes::default();
+    for vote in get_proposal_votes(storage, proposal_id)? {
+        if vote.is_validator() {
+            let validator_stake =
+                stake.validator_stake(storage, &vote.validator, epoch)?;
+            proposal_votes
+                .validators_vote
+                .insert(vote.validator.clone(), vote.data.into());
+            proposal_votes
+                .validator_voting_power
+                .insert(vote.validator, validator_stake);
+        }
+    }
+    for vote in get_vote_overrides(storage, stake, proposal_id)? {
+        let delegator_stake = stake
+            .bond_amount(storage, &vote.delegator, &vote.validator, epoch)
+            .unwrap_or_default();
+        proposal_votes
+            .delegators_vote
+            .insert(vote.delegator.clone(), vote.data.into());
+        proposal_votes
+            .delegator_voting_power
+            .entry(vote.delegator)
+            .or_default()
+            .insert(vote.validator, delegator_stake);
+    }
+    Ok(proposal_votes)
+}
+
+/// Tally the votes of a proposal with the stake at the given epoch. This is
+/// the tally that the ledger applies at the end of the voting period.
+pub fn tally_proposal<S>(
+    storage: &S,
+    stake: &impl StakeReader,
+    proposal_id: u64,
+    proposal_type: &ProposalType,
+    is_steward: bool,
+    epoch: Epoch,
+) -> storage_api::Result<ProposalResult>
+where
+    S: StorageRead,
+{
+    let gov_params = get_parameters(storage)?;
+    let tally_type = gov_params.tally_type(proposal_type, is_steward);
+
+    let total_voting_power = stake.total_stake(storage, epoch)?;
+    let votes = compute_proposal_votes(storage, stake, proposal_id, epoch)?;
+    Ok(compute_proposal_result(
+        votes,
+        total_voting_power,
+        tally_type,
+        gov_params.veto_threshold,
+    ))
+}
@@ -98,6 +98,27 @@ pub fn transfer<S>(
     dest: &Address,
     amount: token::Amount,
 ) -> storage_api::Result<()>
+where
+    S: StorageRead + StorageWrite,
+{
+    if amount.is_zero() {
+        return Ok(());
+    }
+    if src != dest {
+        storage_api::account::record_spending(storage, src, token, amount)?;
+    }
+    transfer_unrecorded(storage, token, src, dest, amount)
+}
+
+/// Transfer `token` from `src` to `dest` without counting the debit towards
+/// the epoch limits of the spending policy of `src`.
+pub(super) fn transfer_unrecorded<S>(
+    storage: &mut S,
+    token: &Address,
+    src: &Address,
+    dest: &Address,
+    amount: token::Amount,
+) -> storage_api::Result<()>
 where
     S: StorageRead + StorageWrite,
 {
@@ -178,6 +199,12 @@ where
             balance
         }
     };
+    storage_api::account::record_spending(
+        storage,
+        source,
+        token,
+        amount_to_burn,
+    )?;
 
     let total_supply = read_total_supply(&*storage, source)?;
     let new_total_supply =
@@ -4,13 +4,12 @@
 use borsh::BorshDeserialize;
 use masp_primitives::transaction::Transaction;
 
-use super::storage_api::{self, OptionExt, ResultExt, StorageRead};
+use super::ibc::{decode_recv_packet, received_shielded_transfer};
+use super::storage_api::{self, OptionExt, StorageRead};
 use crate::proto::Tx;
 use crate::types::address::Address;
 use crate::types::hash::Hash;
-use crate::types::ibc::{
-    get_shielded_transfer, IbcEvent, MsgShieldedTransfer, EVENT_TYPE_PACKET,
-};
+use crate::types::ibc::{IbcEvent, MsgShieldedTransfer, EVENT_TYPE_PACKET};
 use crate::types::storage::{
     BlockHash, BlockHeight, Epoch, Header, Key, TxIndex,
 };
@@ -108,11 +107,12 @@ where
     /// Get a tx hash
     fn get_tx_code_hash(&self) -> Result<Option<Hash>, storage_api::Error>;
 
-    /// Get the shielded action including the transfer and the masp tx
+    /// Get the shielded action including the transfer and the masp tx, and
+    /// whether the masp tx has been built natively without proofs
     fn get_shielded_action(
-        &self,
+        &'view self,
         tx_data: &Tx,
-    ) -> Result<(Transfer, Transaction), storage_api::Error> {
+    ) -> Result<(Transfer, Transaction, bool), storage_api::Error> {
         let signed = tx_data;
         let data = signed.data().ok_or_err_msg("No transaction data")?;
         if let Ok(transfer) = Transfer::try_from_slice(&data) {
@@ -123,13 +123,14 @@ where
                 .get_section(&shielded_hash)
                 .and_then(|x| x.as_ref().masp_tx())
                 .ok_or_err_msg("unable to find shielded section")?;
-            return Ok((transfer, masp_tx));
+            return Ok((transfer, masp_tx, false));
         }
 
         if let Ok(message) = MsgShieldedTransfer::try_from_slice(&data) {
             return Ok((
                 message.shielded_transfer.transfer,
                 message.shielded_transfer.masp_tx,
+                false,
             ));
         }
 
@@ -141,9 +142,15 @@ where
                 "No IBC event for the shielded action",
             )
         })?;
-        get_shielded_transfer(event)
-            .into_storage_result()?
-            .map(|shielded| (shielded.transfer, shielded.masp_tx))
+        let packet = decode_recv_packet(&data).ok_or_else(|| {
+            storage_api::Error::new_const(
+                "No received IBC packet for the shielded action",
+            )
+        })?;
+        received_shielded_transfer(&self.post(), &packet, event)?
+            .map(|(shielded, is_native)| {
+                (shielded.transfer, shielded.masp_tx, is_native)
+            })
             .ok_or_else(|| {
                 storage_api::Error::new_const(
                     "No shielded transfer in the IBC event",
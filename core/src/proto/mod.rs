@@ -4,10 +4,11 @@ pub mod generated;
 mod types;
 
 pub use types::{
-    standalone_signature, verify_standalone_sig, Code, Commitment,
-    CompressedSignature, Data, Error, Header, MaspBuilder, Section,
-    SerializeWithBorsh, Signable, SignableEthMessage, Signature,
-    SignatureIndex, Signed, Signer, Tx, TxError,
+    standalone_signature, verify_standalone_sig, BatchMode,
+    BatchedTxCommitments, Code, Commitment, CompressedSignature, Data, Error,
+    Header, MaspBuilder, Section, SerializeWithBorsh, Signable,
+    SignableEthMessage, Signature, SignatureIndex, Signed, Signer, Tx, TxBatch,
+    TxError,
 };
 
 #[cfg(test)]
@@ -30,4 +31,31 @@ mod tests {
         let tx_from_bytes = Tx::decode(&tx_from_hex[..]).unwrap();
         assert_eq!(tx, tx_from_bytes);
     }
+
+    #[test]
+    fn batch_round_trip() {
+        use crate::types::chain::ChainId;
+
+        let mut txs = vec![];
+        for i in 0..3u8 {
+            let mut tx = super::Tx::new(ChainId::default(), None);
+            tx.set_code(Code::new(vec![0], None));
+            tx.set_data(Data::new(vec![i]));
+            txs.push(tx);
+        }
+        let batch =
+            super::Tx::new_batch(txs.clone(), BatchMode::Atomic).unwrap();
+
+        // The common code section is only included once
+        assert_eq!(batch.sections.len(), 4);
+        let inner_txs = batch.batch_txs();
+        assert_eq!(inner_txs.len(), txs.len());
+        for (inner_tx, tx) in inner_txs.iter().zip(&txs) {
+            assert_eq!(inner_tx.code(), tx.code());
+            assert_eq!(inner_tx.data(), tx.data());
+            // Signatures over the batch are valid for every inner tx
+            assert_eq!(inner_tx.raw_header_hash(), batch.raw_header_hash());
+        }
+        assert!(super::Tx::new_batch(vec![], BatchMode::Independent).is_none());
+    }
 }
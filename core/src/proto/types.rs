@@ -952,6 +952,64 @@ impl Section {
     }
 }
 
+/// The manner in which the inner transactions of a batch are applied
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub enum BatchMode {
+    /// Either all the inner transactions are applied or none of them are
+    Atomic,
+    /// Every inner transaction is applied or rejected on its own
+    Independent,
+}
+
+/// The code and data section hashes of an inner transaction of a batch
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct BatchedTxCommitments {
+    /// The SHA-256 hash of the inner transaction's code section
+    pub code_hash: crate::types::hash::Hash,
+    /// The SHA-256 hash of the inner transaction's data section
+    pub data_hash: crate::types::hash::Hash,
+}
+
+/// An ordered list of inner transactions to be applied under a single wrapper
+#[derive(
+    Clone,
+    Debug,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub struct TxBatch {
+    /// How the inner transactions are applied
+    pub mode: BatchMode,
+    /// The commitments to the inner transactions, in order of execution
+    pub txs: Vec<BatchedTxCommitments>,
+}
+
 /// A Namada transaction header indicating where transaction subcomponents can
 /// be found
 #[derive(
@@ -976,6 +1034,9 @@ pub struct Header {
     pub data_hash: crate::types::hash::Hash,
     /// The type of this transaction
     pub tx_type: TxType,
+    /// The inner transactions of a batch. If present, the code and data
+    /// hashes above designate the inner transaction being applied.
+    pub batch: Option<TxBatch>,
 }
 
 impl Header {
@@ -988,6 +1049,7 @@ impl Header {
             timestamp: DateTimeUtc::now(),
             code_hash: crate::types::hash::Hash::default(),
             data_hash: crate::types::hash::Hash::default(),
+            batch: None,
         }
     }
 
@@ -1128,10 +1190,24 @@ impl Tx {
 
     /// Gets the hash of the decrypted transaction's header
     pub fn raw_header_hash(&self) -> crate::types::hash::Hash {
+        Section::Header(self.raw_header()).get_hash()
+    }
+
+    /// Get the decrypted transaction's header. For batches, the code and data
+    /// hashes are those of the first inner transaction so that all the inner
+    /// transactions share the same header.
+    fn raw_header(&self) -> Header {
         let mut raw_header = self.header();
         raw_header.tx_type = TxType::Raw;
-
-        Section::Header(raw_header).get_hash()
+        if let Some(first) = raw_header
+            .batch
+            .as_ref()
+            .and_then(|batch| batch.txs.first())
+        {
+            raw_header.code_hash = first.code_hash;
+            raw_header.data_hash = first.data_hash;
+        }
+        raw_header
     }
 
     /// Get hashes of all the sections in this transaction
@@ -1157,9 +1233,7 @@ impl Tx {
         if self.header_hash() == *hash {
             return Some(Cow::Owned(Section::Header(self.header.clone())));
         } else if self.raw_header_hash() == *hash {
-            let mut header = self.header();
-            header.tx_type = TxType::Raw;
-            return Some(Cow::Owned(Section::Header(header)));
+            return Some(Cow::Owned(Section::Header(self.raw_header())));
         }
         for section in &self.sections {
             if section.get_hash() == *hash {
@@ -1235,6 +1309,71 @@ impl Tx {
         }
     }
 
+    /// Get the inner transactions of this batch, if this is one
+    pub fn batch(&self) -> Option<&TxBatch> {
+        self.header.batch.as_ref()
+    }
+
+    /// Get the inner transactions of this batch as standalone transactions
+    /// whose code and data hashes designate the respective inner transaction.
+    /// All of them share the raw header hash of this transaction.
+    pub fn batch_txs(&self) -> Vec<Tx> {
+        self.header
+            .batch
+            .iter()
+            .flat_map(|batch| batch.txs.iter())
+            .map(|commitments| {
+                let mut inner_tx = self.clone();
+                inner_tx.set_code_sechash(commitments.code_hash);
+                inner_tx.set_data_sechash(commitments.data_hash);
+                inner_tx
+            })
+            .collect()
+    }
+
+    /// Combine the given transactions into a single batch applying their inner
+    /// transactions in the given order. The header of the first transaction
+    /// is kept and the sections of all of them are merged. Signatures are
+    /// dropped since they do not cover the batch. Returns `None` if no
+    /// transactions are given.
+    pub fn new_batch(txs: Vec<Tx>, mode: BatchMode) -> Option<Self> {
+        let mut txs = txs.into_iter();
+        let mut batch_tx = txs.next()?;
+        let mut commitments = batch_tx.batch_commitments();
+        batch_tx
+            .sections
+            .retain(|section| !matches!(section, Section::Signature(_)));
+        let mut sechashes: HashSet<_> =
+            batch_tx.sections.iter().map(Section::get_hash).collect();
+        for tx in txs {
+            commitments.extend(tx.batch_commitments());
+            for section in tx.sections {
+                if !matches!(section, Section::Signature(_))
+                    && sechashes.insert(section.get_hash())
+                {
+                    batch_tx.sections.push(section);
+                }
+            }
+        }
+        batch_tx.header.batch = Some(TxBatch {
+            mode,
+            txs: commitments,
+        });
+        Some(batch_tx)
+    }
+
+    /// Get the commitments to the inner transactions of this transaction,
+    /// flattening it if it is already a batch
+    fn batch_commitments(&self) -> Vec<BatchedTxCommitments> {
+        match &self.header.batch {
+            Some(batch) => batch.txs.clone(),
+            None => vec![BatchedTxCommitments {
+                code_hash: self.header.code_hash,
+                data_hash: self.header.data_hash,
+            }],
+        }
+    }
+
     /// Convert this transaction into protobufs
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut bytes = vec![];
@@ -1306,7 +1445,9 @@ impl Tx {
                         witnesses.push(signatures);
                     }
                     // Short-circuit these checks if the threshold is exceeded
-                    if verified_pks.len() >= threshold.into() {
+                    if public_keys_index_map.total_weight(&verified_pks)
+                        >= threshold.into()
+                    {
                         return Ok(witnesses);
                     }
                 }
@@ -1314,7 +1455,7 @@ impl Tx {
         }
         Err(Error::InvalidSectionSignature(format!(
             "signature threshold not met: ({} < {})",
-            verified_pks.len(),
+            public_keys_index_map.total_weight(&verified_pks),
             threshold
         )))
     }
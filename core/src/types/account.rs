@@ -1,12 +1,16 @@
 //! Helper structures to manage accounts
 
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt::Display;
+use std::str::FromStr;
 
-use borsh::{BorshDeserialize, BorshSerialize};
+use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
 use super::address::Address;
 use super::key::{common, RefTo};
+use super::storage::Epoch;
+use super::token;
 use crate::hints;
 
 #[derive(
@@ -20,6 +24,10 @@ pub struct Account {
     pub threshold: u8,
     /// The address corresponding to the account owner
     pub address: Address,
+    /// The weights and roles of the public keys, by index. Keys without
+    /// options have the default ones.
+    #[serde(default)]
+    pub key_options: BTreeMap<u8, AccountKeyOptions>,
 }
 
 impl Account {
@@ -55,6 +63,10 @@ pub struct AccountPublicKeysMap {
     pub pk_to_idx: HashMap<common::PublicKey, u8>,
     /// Hashmap from index key to public key
     pub idx_to_pk: HashMap<u8, common::PublicKey>,
+    /// Hashmap from index key to the weight of the key's signatures. Keys
+    /// without a weight have a weight of 1.
+    #[serde(default)]
+    pub idx_to_weight: HashMap<u8, u8>,
 }
 
 impl FromIterator<common::PublicKey> for AccountPublicKeysMap {
@@ -75,6 +87,7 @@ impl FromIterator<common::PublicKey> for AccountPublicKeysMap {
         Self {
             pk_to_idx,
             idx_to_pk,
+            idx_to_weight: HashMap::new(),
         }
     }
 }
@@ -96,6 +109,47 @@ impl AccountPublicKeysMap {
         self.pk_to_idx.get(public_key).cloned()
     }
 
+    /// Retrieve the weight of the public key at the given index
+    pub fn get_weight(&self, index: u8) -> u8 {
+        self.idx_to_weight.get(&index).copied().unwrap_or(1)
+    }
+
+    /// Sum the weights of the public keys at the given indices
+    pub fn total_weight<'a>(
+        &self,
+        indices: impl IntoIterator<Item = &'a u8>,
+    ) -> u32 {
+        indices
+            .into_iter()
+            .map(|index| u32::from(self.get_weight(*index)))
+            .sum()
+    }
+
+    /// Apply the weights of the given key options, and keep only the public
+    /// keys that are allowed to sign for the given role. Without a role, only
+    /// the keys with no roles are kept.
+    pub fn with_key_options(
+        mut self,
+        key_options: &BTreeMap<u8, AccountKeyOptions>,
+        role: Option<KeyRole>,
+    ) -> Self {
+        self.idx_to_weight = key_options
+            .iter()
+            .map(|(index, options)| (*index, options.weight))
+            .collect();
+        let default_options = AccountKeyOptions::default();
+        self.idx_to_pk.retain(|index, _| {
+            key_options
+                .get(index)
+                .unwrap_or(&default_options)
+                .is_allowed_for(role)
+        });
+        let idx_to_pk = &self.idx_to_pk;
+        self.pk_to_idx
+            .retain(|_, index| idx_to_pk.contains_key(index));
+        self
+    }
+
     /// Index the given set of secret keys
     pub fn index_secret_keys(
         &self,
@@ -110,3 +164,190 @@ impl AccountPublicKeysMap {
             .collect()
     }
 }
+
+#[derive(
+    Debug,
+    Clone,
+    Default,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+/// A policy restricting the debits of an account's tokens, enforced by the
+/// user VP
+pub struct SpendingPolicy {
+    /// The maximum amount of each token that can be debited per epoch.
+    /// Tokens without a limit can be debited freely.
+    pub epoch_limits: BTreeMap<Address, token::Amount>,
+    /// If not empty, the only addresses that can be credited with the
+    /// account's tokens
+    pub allowed_recipients: BTreeSet<Address>,
+    /// Debits of a token above this amount have to be scheduled as a
+    /// [`PendingWithdrawal`] first
+    pub withdrawal_thresholds: BTreeMap<Address, token::Amount>,
+    /// The number of epochs after which scheduled withdrawals and changes of
+    /// the policy itself can be applied
+    pub delay: u64,
+}
+
+impl SpendingPolicy {
+    /// Check if the given address can be credited with the account's tokens
+    pub fn is_allowed_recipient(&self, recipient: &Address) -> bool {
+        self.allowed_recipients.is_empty()
+            || self.allowed_recipients.contains(recipient)
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+/// A withdrawal above the threshold of a [`SpendingPolicy`], waiting for the
+/// policy's delay to expire
+pub struct PendingWithdrawal {
+    /// The withdrawn token
+    pub token: Address,
+    /// The recipient of the withdrawal
+    pub target: Address,
+    /// The withdrawn amount
+    pub amount: token::Amount,
+    /// The first epoch in which the withdrawal can be executed
+    pub executable_from: Epoch,
+}
+
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+/// A class of actions that the keys of an account can be restricted to
+pub enum KeyRole {
+    /// Voting on governance proposals
+    Governance,
+    /// Bonding, unbonding, redelegating and withdrawing tokens, and
+    /// claiming rewards
+    Staking,
+}
+
+impl Display for KeyRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KeyRole::Governance => write!(f, "governance"),
+            KeyRole::Staking => write!(f, "staking"),
+        }
+    }
+}
+
+impl FromStr for KeyRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "governance" => Ok(KeyRole::Governance),
+            "staking" => Ok(KeyRole::Staking),
+            _ => Err(format!("Unknown key role: {}", s)),
+        }
+    }
+}
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+/// The weight and the roles of a public key of an account
+pub struct AccountKeyOptions {
+    /// The weight of the key's signatures towards the account threshold
+    pub weight: u8,
+    /// The roles the key is restricted to. A key without roles can sign for
+    /// any action.
+    pub roles: BTreeSet<KeyRole>,
+}
+
+impl Default for AccountKeyOptions {
+    fn default() -> Self {
+        Self {
+            weight: 1,
+            roles: BTreeSet::new(),
+        }
+    }
+}
+
+impl AccountKeyOptions {
+    /// Check if the key can sign for an action of the given role, or for any
+    /// action if no role is given
+    pub fn is_allowed_for(&self, role: Option<KeyRole>) -> bool {
+        self.roles.is_empty()
+            || role
+                .map(|role| self.roles.contains(&role))
+                .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::key::testing::{keypair_1, keypair_2};
+
+    /// Test that the key options set the weights of the keys and restrict the
+    /// keys to their roles.
+    #[test]
+    fn test_public_keys_map_with_key_options() {
+        let public_keys = [keypair_1().ref_to(), keypair_2().ref_to()];
+        let key_options = BTreeMap::from([(
+            1,
+            AccountKeyOptions {
+                weight: 3,
+                roles: [KeyRole::Governance].into(),
+            },
+        )]);
+        let map = AccountPublicKeysMap::from_iter(public_keys.clone());
+        assert_eq!(map.total_weight(&[0, 1]), 2);
+
+        let general = map.clone().with_key_options(&key_options, None);
+        assert_eq!(general.get_index_from_public_key(&public_keys[1]), None);
+        assert_eq!(general.get_public_key_from_index(1), None);
+        assert_eq!(general.total_weight(&[0]), 1);
+
+        let governance = map
+            .clone()
+            .with_key_options(&key_options, Some(KeyRole::Governance));
+        assert_eq!(
+            governance.get_index_from_public_key(&public_keys[1]),
+            Some(1)
+        );
+        assert_eq!(governance.total_weight(&[0, 1]), 4);
+
+        let staking =
+            map.with_key_options(&key_options, Some(KeyRole::Staking));
+        assert_eq!(staking.get_index_from_public_key(&public_keys[0]), Some(0));
+        assert_eq!(staking.get_index_from_public_key(&public_keys[1]), None);
+    }
+}
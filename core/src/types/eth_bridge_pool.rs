@@ -15,7 +15,8 @@ use crate::types::ethereum_events::{
     EthAddress, TransferToEthereum as TransferToEthereumEvent,
 };
 use crate::types::hash::Hash as HashDigest;
-use crate::types::storage::{DbKeySeg, Key};
+use crate::types::keccak::KeccakHash;
+use crate::types::storage::{DbKeySeg, Epoch, Key};
 use crate::types::token::Amount;
 
 /// A version used in our Ethereuem smart contracts
@@ -302,6 +303,53 @@ pub struct GasFee {
     pub token: Address,
 }
 
+/// The data of a transaction made by the sender of a transfer in the
+/// Bridge pool to cancel it, or to set the epoch from which it is refunded
+/// by the protocol. A transfer can only be refunded while it is not part of
+/// a signed Bridge pool root that can still be relayed.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct CancelPendingTransfer {
+    /// The hash of the pending transfer.
+    pub transfer_hash: KeccakHash,
+    /// The epoch from which the protocol refunds the transfer, if it
+    /// hasn't been relayed by then. If `None`, the transfer is refunded
+    /// immediately.
+    pub expiry: Option<Epoch>,
+}
+
+/// The data of a transaction made by the payer of the gas fees of a transfer
+/// in the Bridge pool to raise them, e.g. when the transfer is no longer
+/// profitable to relay. The hash of the transfer is kept, as the additional
+/// fees are stored apart from its [`GasFee`], and they are paid in the same
+/// token to the relayer of the transfer.
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    Serialize,
+    Deserialize,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+)]
+pub struct BumpPendingTransferFee {
+    /// The hash of the pending transfer.
+    pub transfer_hash: KeccakHash,
+    /// The amount of gas fees added to the transfer.
+    pub amount: Amount,
+}
+
 #[cfg(any(test, feature = "testing"))]
 /// Testing helpers and strategies for the Ethereum bridge pool
 pub mod testing {
@@ -219,7 +219,13 @@ impl TryFrom<Memo> for IbcShieldedTransfer {
     }
 }
 
-/// Get the shielded transfer from the memo
+/// Get the shielded transfer from the memo. The memo of a token received by a
+/// payment address without a shielded transfer is ignored since the token is
+/// shielded natively: a memo which isn't HEX-encoded can't be a shielded
+/// transfer and is the plain memo of the sender. A HEX-encoded memo which
+/// can't be decoded is an error rather than being shielded natively, so that
+/// the packet isn't received with a shielded transfer other than the one
+/// expected by the sender.
 pub fn get_shielded_transfer(
     event: &IbcEvent,
 ) -> Result<Option<IbcShieldedTransfer>> {
@@ -239,9 +245,57 @@ pub fn get_shielded_transfer(
         return Ok(None);
     }
 
-    event
-        .attributes
-        .get("memo")
-        .map(|memo| IbcShieldedTransfer::try_from(Memo::from(memo.clone())))
-        .transpose()
+    match event.attributes.get("memo") {
+        Some(memo) if is_hex_memo(memo) => {
+            IbcShieldedTransfer::try_from(Memo::from(memo.clone())).map(Some)
+        }
+        _ => Ok(None),
+    }
+}
+
+fn is_hex_memo(memo: &str) -> bool {
+    !memo.is_empty() && HEXUPPER.decode(memo.as_bytes()).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use masp_primitives::zip32::ExtendedSpendingKey;
+
+    use super::*;
+
+    fn received_event(memo: Option<&str>) -> IbcEvent {
+        let xsk = ExtendedSpendingKey::master(&[0u8; 32]);
+        let (_, payment_address) = xsk.default_address();
+        let receiver = PaymentAddress::from(payment_address).to_string();
+        let mut attributes = HashMap::from([
+            ("success".to_string(), "true".to_string()),
+            ("receiver".to_string(), receiver),
+        ]);
+        if let Some(memo) = memo {
+            attributes.insert("memo".to_string(), memo.to_string());
+        }
+        IbcEvent {
+            event_type: EVENT_TYPE_PACKET.to_string(),
+            attributes,
+        }
+    }
+
+    /// Test that a plain memo is ignored for the native shielding and that a
+    /// malformed shielded transfer in the memo is rejected.
+    #[test]
+    fn test_get_shielded_transfer_from_memo() {
+        assert!(get_shielded_transfer(&received_event(None))
+            .unwrap()
+            .is_none());
+        for memo in ["", "a plain memo", "abcd"] {
+            let event = received_event(Some(memo));
+            assert!(get_shielded_transfer(&event).unwrap().is_none(), "{memo}");
+        }
+
+        let malformed = received_event(Some("ABCD"));
+        assert!(matches!(
+            get_shielded_transfer(&malformed),
+            Err(Error::DecodingShieldedTransfer(_))
+        ));
+    }
 }
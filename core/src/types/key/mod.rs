@@ -19,6 +19,7 @@ use serde::Serialize;
 use sha2::{Digest, Sha256};
 use thiserror::Error;
 
+use super::account::{AccountKeyOptions, PendingWithdrawal};
 use super::address::Address;
 use super::storage::{self, DbKeySeg, Key};
 use crate::ledger::storage::{Sha256Hasher, StorageHasher};
@@ -31,6 +32,12 @@ struct Keys {
     public_keys: &'static str,
     threshold: &'static str,
     protocol_public_keys: &'static str,
+    key_options: &'static str,
+    spending_policy: &'static str,
+    pending_spending_policy: &'static str,
+    spent: &'static str,
+    pending_withdrawals: &'static str,
+    withdrawals_counter: &'static str,
 }
 
 /// Obtain a storage key for user's public key.
@@ -66,6 +73,34 @@ pub fn is_pks_key(key: &Key) -> Option<&Address> {
     }
 }
 
+/// Obtain a storage key prefix for the options of the user's public keys.
+pub fn key_options_prefix(owner: &Address) -> storage::Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.key_options.to_string()),
+        ],
+    }
+}
+
+/// LazyMap handler for the options of the user's public keys, by key index
+pub fn key_options_handle(owner: &Address) -> LazyMap<u8, AccountKeyOptions> {
+    LazyMap::open(key_options_prefix(owner))
+}
+
+/// Check if the given storage key is a key of the options of the user's public
+/// keys. If it is, returns the owner.
+pub fn is_key_options_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(prefix), ..]
+            if prefix.as_str() == Keys::VALUES.key_options =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
 /// Check if the given storage key is a threshold key.
 pub fn is_threshold_key(key: &Key) -> Option<&Address> {
     match &key.segments[..] {
@@ -110,6 +145,102 @@ pub fn is_protocol_pk_key(key: &Key) -> Option<&Address> {
     }
 }
 
+/// Obtain the storage key for the spending policy of an account
+pub fn spending_policy_key(owner: &Address) -> storage::Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.spending_policy.to_string()),
+        ],
+    }
+}
+
+/// Obtain the storage key for a change of the spending policy of an account,
+/// waiting for the delay of the current policy to expire
+pub fn pending_spending_policy_key(owner: &Address) -> storage::Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(
+                Keys::VALUES.pending_spending_policy.to_string(),
+            ),
+        ],
+    }
+}
+
+/// Obtain the storage key for the amount of a token debited from an account
+/// in the last epoch in which it was debited
+pub fn spent_key(owner: &Address, token: &Address) -> storage::Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.spent.to_string()),
+            DbKeySeg::AddressSeg(token.to_owned()),
+        ],
+    }
+}
+
+/// Check if the given storage key is a spent amount key. If it is, returns
+/// the owner and the token.
+pub fn is_spent_key(key: &Key) -> Option<(&Address, &Address)> {
+    match &key.segments[..] {
+        [
+            DbKeySeg::AddressSeg(owner),
+            DbKeySeg::StringSeg(prefix),
+            DbKeySeg::AddressSeg(token),
+        ] if prefix.as_str() == Keys::VALUES.spent => Some((owner, token)),
+        _ => None,
+    }
+}
+
+/// Obtain a storage key prefix for the pending withdrawals of an account
+pub fn pending_withdrawals_prefix(owner: &Address) -> storage::Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.pending_withdrawals.to_string()),
+        ],
+    }
+}
+
+/// LazyMap handler for the pending withdrawals of an account, by id
+pub fn pending_withdrawals_handle(
+    owner: &Address,
+) -> LazyMap<u64, PendingWithdrawal> {
+    LazyMap::open(pending_withdrawals_prefix(owner))
+}
+
+/// Obtain the storage key for the id of the next pending withdrawal of an
+/// account
+pub fn withdrawals_counter_key(owner: &Address) -> storage::Key {
+    Key {
+        segments: vec![
+            DbKeySeg::AddressSeg(owner.to_owned()),
+            DbKeySeg::StringSeg(Keys::VALUES.withdrawals_counter.to_string()),
+        ],
+    }
+}
+
+/// Check if the given storage key belongs to the spending policy subspace of
+/// an account. If it does, returns the owner.
+pub fn is_spending_policy_key(key: &Key) -> Option<&Address> {
+    match &key.segments[..] {
+        [DbKeySeg::AddressSeg(owner), DbKeySeg::StringSeg(prefix), ..]
+            if [
+                Keys::VALUES.spending_policy,
+                Keys::VALUES.pending_spending_policy,
+                Keys::VALUES.spent,
+                Keys::VALUES.pending_withdrawals,
+                Keys::VALUES.withdrawals_counter,
+            ]
+            .contains(&prefix.as_str()) =>
+        {
+            Some(owner)
+        }
+        _ => None,
+    }
+}
+
 /// Represents an error in signature verification
 #[allow(missing_docs)]
 #[derive(Error, Debug)]
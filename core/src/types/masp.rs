@@ -9,6 +9,7 @@ use sha2::{Digest, Sha256};
 
 use crate::impl_display_and_from_str_via_format;
 use crate::types::address::{Address, DecodeError, HASH_HEX_LEN, MASP};
+use crate::types::storage::BlockHeight;
 use crate::types::string_encoding::{
     self, MASP_EXT_FULL_VIEWING_KEY_HRP, MASP_EXT_SPENDING_KEY_HRP,
     MASP_PAYMENT_ADDRESS_HRP,
@@ -437,6 +438,77 @@ impl Display for BalanceOwner {
     }
 }
 
+/// The prefix of a string encoded [`KeyBirthday::MaspTxIndex`]
+const MASP_TX_BIRTHDAY_PREFIX: &str = "masp-tx:";
+
+/// The point in the history of the shielded pool before which a shielded key
+/// cannot have received any notes
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    BorshSerialize,
+    BorshDeserialize,
+)]
+pub enum KeyBirthday {
+    /// The height of the first block that may contain notes for the key
+    Height(BlockHeight),
+    /// The index of the first MASP transaction that may contain notes for
+    /// the key
+    MaspTxIndex(u64),
+}
+
+impl Display for KeyBirthday {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Height(height) => write!(f, "{}", height),
+            Self::MaspTxIndex(idx) => {
+                write!(f, "{}{}", MASP_TX_BIRTHDAY_PREFIX, idx)
+            }
+        }
+    }
+}
+
+impl FromStr for KeyBirthday {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix(MASP_TX_BIRTHDAY_PREFIX) {
+            Some(idx) => Ok(Self::MaspTxIndex(idx.parse()?)),
+            None => Ok(Self::Height(s.parse()?)),
+        }
+    }
+}
+
+impl serde::Serialize for KeyBirthday {
+    fn serialize<S>(
+        &self,
+        serializer: S,
+    ) -> std::result::Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let encoded = self.to_string();
+        serde::Serialize::serialize(&encoded, serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for KeyBirthday {
+    fn deserialize<D>(deserializer: D) -> std::result::Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        use serde::de::Error;
+        let encoded: String = serde::Deserialize::deserialize(deserializer)?;
+        Self::from_str(&encoded).map_err(D::Error::custom)
+    }
+}
+
 /// Represents any MASP value
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
@@ -1,9 +1,11 @@
 use borsh::{BorshDeserialize, BorshSchema, BorshSerialize};
 use serde::{Deserialize, Serialize};
 
+use crate::types::account::{AccountKeyOptions, SpendingPolicy};
 use crate::types::address::Address;
 use crate::types::hash::Hash;
 use crate::types::key::common;
+use crate::types::token;
 
 /// A tx data type to initialize a new established account
 #[derive(
@@ -49,15 +51,67 @@ pub struct UpdateAccount {
     pub public_keys: Vec<common::PublicKey>,
     /// The account signature threshold
     pub threshold: Option<u8>,
+    /// The weights and roles of the public keys, by key index. If the public
+    /// keys are replaced, the options of the keys without an entry are reset
+    /// to the default ones.
+    pub key_options: Vec<AccountKeyOptions>,
+}
+
+/// A tx data type to manage the spending policy of an account
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    BorshSchema,
+    Serialize,
+    Deserialize,
+)]
+pub enum UpdateSpendingPolicy {
+    /// Set or clear the policy of the account. The change takes effect after
+    /// the delay of the policy currently in effect, if any.
+    SetPolicy {
+        /// The address of the account
+        owner: Address,
+        /// The new policy
+        policy: Option<SpendingPolicy>,
+    },
+    /// Schedule a withdrawal above the threshold of a policy
+    ScheduleWithdrawal {
+        /// The address of the account
+        owner: Address,
+        /// The withdrawn token
+        token: Address,
+        /// The recipient of the withdrawal
+        target: Address,
+        /// The withdrawn amount
+        amount: token::Amount,
+    },
+    /// Execute a scheduled withdrawal whose delay has expired
+    ExecuteWithdrawal {
+        /// The address of the account
+        owner: Address,
+        /// The id of the withdrawal
+        id: u64,
+    },
+    /// Cancel a scheduled withdrawal
+    CancelWithdrawal {
+        /// The address of the account
+        owner: Address,
+        /// The id of the withdrawal
+        id: u64,
+    },
 }
 
 #[cfg(any(test, feature = "testing"))]
 /// Tests and strategies for accounts
 pub mod tests {
     use proptest::prelude::Just;
-    use proptest::{collection, option, prop_compose};
+    use proptest::{collection, option, prop_compose, prop_oneof};
 
     use super::*;
+    use crate::types::account::KeyRole;
     use crate::types::address::testing::arb_non_internal_address;
     use crate::types::hash::testing::arb_hash;
     use crate::types::key::testing::arb_common_pk;
@@ -79,6 +133,19 @@ pub mod tests {
         }
     }
 
+    prop_compose! {
+        /// Generate arbitrary options of a public key
+        pub fn arb_key_options()(
+            weight in 1..=3u8,
+            roles in collection::btree_set(
+                prop_oneof![Just(KeyRole::Governance), Just(KeyRole::Staking)],
+                0..=2,
+            ),
+        ) -> AccountKeyOptions {
+            AccountKeyOptions { weight, roles }
+        }
+    }
+
     prop_compose! {
         /// Generate an arbitrary account update
         pub fn arb_update_account()(
@@ -87,6 +154,10 @@ pub mod tests {
             addr in arb_non_internal_address(),
             vp_code_hash in option::of(arb_hash()),
             threshold in option::of(0..=public_keys.len() as u8),
+            key_options in collection::vec(
+                arb_key_options(),
+                0..=public_keys.len(),
+            ),
             public_keys in Just(public_keys),
         ) -> UpdateAccount {
             UpdateAccount {
@@ -94,6 +165,7 @@ pub mod tests {
                 vp_code_hash,
                 public_keys,
                 threshold,
+                key_options,
             }
         }
     }
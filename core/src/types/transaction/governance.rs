@@ -5,15 +5,17 @@ use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::ledger::governance::cli::onchain::{
-    DefaultProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, ParameterChangeProposal, PgfFundingProposal,
+    PgfStewardProposal, UpgradeProposal,
 };
 use crate::ledger::governance::storage::proposal::{
-    AddRemove, PGFAction, PGFTarget, ProposalType,
+    AddRemove, PGFAction, ProposalType,
 };
 use crate::ledger::governance::storage::vote::StorageProposalVote;
 use crate::types::address::Address;
 use crate::types::hash::Hash;
 use crate::types::storage::Epoch;
+use crate::types::token;
 
 #[allow(missing_docs)]
 #[derive(Debug, Error)]
@@ -47,13 +49,16 @@ pub struct InitProposalData {
     pub voting_end_epoch: Epoch,
     /// The epoch from which this changes are executed
     pub grace_epoch: Epoch,
+    /// The initial deposit of the author, which can be topped up by other
+    /// accounts until it reaches the minimum proposal fund
+    pub deposit: token::Amount,
 }
 
 impl InitProposalData {
     /// Get the hash of the corresponding extra data section
     pub fn get_section_code_hash(&self) -> Option<Hash> {
         match self.r#type {
-            ProposalType::Default(hash) => hash,
+            ProposalType::Default(hash) | ProposalType::Emergency(hash) => hash,
             _ => None,
         }
     }
@@ -80,6 +85,44 @@ pub struct VoteProposalData {
     pub delegations: Vec<Address>,
 }
 
+/// A tx data type to top up the deposit of a proposal
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct DepositProposalData {
+    /// The proposal id
+    pub id: u64,
+    /// The address depositing the tokens
+    pub depositor: Address,
+    /// The amount of native tokens to deposit
+    pub amount: token::Amount,
+}
+
+/// A tx data type to delegate the voting power of an address to a
+/// representative, or to revoke the delegation
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    BorshSerialize,
+    BorshDeserialize,
+    Serialize,
+    Deserialize,
+)]
+pub struct VoteDelegationData {
+    /// The address delegating its voting power
+    pub delegator: Address,
+    /// The representative voting on behalf of the delegator, or `None` to
+    /// revoke the delegation
+    pub representative: Option<Address>,
+}
+
 impl TryFrom<DefaultProposal> for InitProposalData {
     type Error = ProposalError;
 
@@ -92,6 +135,7 @@ impl TryFrom<DefaultProposal> for InitProposalData {
             voting_start_epoch: value.proposal.voting_start_epoch,
             voting_end_epoch: value.proposal.voting_end_epoch,
             grace_epoch: value.proposal.grace_epoch,
+            deposit: token::Amount::zero(),
         })
     }
 }
@@ -111,6 +155,7 @@ impl TryFrom<PgfStewardProposal> for InitProposalData {
             voting_start_epoch: value.proposal.voting_start_epoch,
             voting_end_epoch: value.proposal.voting_end_epoch,
             grace_epoch: value.proposal.grace_epoch,
+            deposit: token::Amount::zero(),
         })
     }
 }
@@ -124,12 +169,8 @@ impl TryFrom<PgfFundingProposal> for InitProposalData {
             .continuous
             .iter()
             .cloned()
-            .map(|funding| {
-                let target = PGFTarget {
-                    target: funding.address,
-                    amount: funding.amount,
-                };
-                if funding.amount.is_zero() {
+            .map(|target| {
+                if target.amount().is_zero() {
                     PGFAction::Continuous(AddRemove::Remove(target))
                 } else {
                     PGFAction::Continuous(AddRemove::Add(target))
@@ -142,13 +183,7 @@ impl TryFrom<PgfFundingProposal> for InitProposalData {
             .retro
             .iter()
             .cloned()
-            .map(|funding| {
-                let target = PGFTarget {
-                    target: funding.address,
-                    amount: funding.amount,
-                };
-                PGFAction::Retro(target)
-            })
+            .map(PGFAction::Retro)
             .collect::<Vec<PGFAction>>();
 
         let extra_data = [continuous_fundings, retro_fundings].concat();
@@ -161,6 +196,41 @@ impl TryFrom<PgfFundingProposal> for InitProposalData {
             voting_start_epoch: value.proposal.voting_start_epoch,
             voting_end_epoch: value.proposal.voting_end_epoch,
             grace_epoch: value.proposal.grace_epoch,
+            deposit: token::Amount::zero(),
+        })
+    }
+}
+
+impl TryFrom<ParameterChangeProposal> for InitProposalData {
+    type Error = ProposalError;
+
+    fn try_from(value: ParameterChangeProposal) -> Result<Self, Self::Error> {
+        Ok(InitProposalData {
+            id: value.proposal.id,
+            content: Hash::default(),
+            author: value.proposal.author,
+            r#type: ProposalType::ParameterChange(value.data),
+            voting_start_epoch: value.proposal.voting_start_epoch,
+            voting_end_epoch: value.proposal.voting_end_epoch,
+            grace_epoch: value.proposal.grace_epoch,
+            deposit: token::Amount::zero(),
+        })
+    }
+}
+
+impl TryFrom<UpgradeProposal> for InitProposalData {
+    type Error = ProposalError;
+
+    fn try_from(value: UpgradeProposal) -> Result<Self, Self::Error> {
+        Ok(InitProposalData {
+            id: value.proposal.id,
+            content: Hash::default(),
+            author: value.proposal.author,
+            r#type: ProposalType::Upgrade(value.data),
+            voting_start_epoch: value.proposal.voting_start_epoch,
+            voting_end_epoch: value.proposal.voting_end_epoch,
+            grace_epoch: value.proposal.grace_epoch,
+            deposit: token::Amount::zero(),
         })
     }
 }
@@ -176,6 +246,7 @@ pub mod tests {
     use crate::types::address::testing::arb_non_internal_address;
     use crate::types::hash::testing::arb_hash;
     use crate::types::storage::testing::arb_epoch;
+    use crate::types::token::testing::arb_amount;
 
     prop_compose! {
         /// Generate a proposal initialization
@@ -187,6 +258,7 @@ pub mod tests {
             voting_start_epoch in arb_epoch(),
             voting_end_epoch in arb_epoch(),
             grace_epoch in arb_epoch(),
+            deposit in arb_amount(),
         ) -> InitProposalData {
             InitProposalData {
                 id,
@@ -196,6 +268,7 @@ pub mod tests {
                 voting_start_epoch,
                 voting_end_epoch,
                 grace_epoch,
+                deposit,
             }
         }
     }
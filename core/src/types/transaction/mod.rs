@@ -187,6 +187,11 @@ pub struct TxResult {
     pub ibc_events: BTreeSet<IbcEvent>,
     /// Ethereum bridge events emitted by the transaction
     pub eth_bridge_events: BTreeSet<EthBridgeEvent>,
+    /// The results of the inner transactions of a batch, in order of
+    /// execution. Inner transactions that failed to run are reported with
+    /// their error. Empty if the transaction is not a batch.
+    #[serde(default)]
+    pub batch_results: Vec<Result<TxResult, String>>,
 }
 
 impl TxResult {
@@ -234,7 +239,14 @@ impl fmt::Display for TxResult {
                 self.gas_used,
                 iterable_to_string("Changed keys", self.changed_keys.iter()),
                 self.vps_result,
-            )
+            )?;
+            for (idx, result) in self.batch_results.iter().enumerate() {
+                match result {
+                    Ok(result) => write!(f, " Inner tx {}: {:#}", idx, result)?,
+                    Err(err) => write!(f, " Inner tx {} failed: {}", idx, err)?,
+                }
+            }
+            Ok(())
         } else {
             write!(f, "{}", serde_json::to_string(self).unwrap())
         }
@@ -7,7 +7,8 @@ use borsh::BorshDeserialize;
 use eyre::{Result, WrapErr};
 use namada_core::hints;
 use namada_core::ledger::eth_bridge::storage::bridge_pool::{
-    get_nonce_key, is_pending_transfer_key, BRIDGE_POOL_ADDRESS,
+    get_expiry_key, get_expiry_prefix, get_fee_bump_key, get_key_from_hash,
+    get_nonce_key, is_expiry_key, is_pending_transfer_key, BRIDGE_POOL_ADDRESS,
 };
 use namada_core::ledger::eth_bridge::storage::{
     self as bridge_storage, wrapped_erc20s,
@@ -16,7 +17,7 @@ use namada_core::ledger::eth_bridge::ADDRESS as BRIDGE_ADDRESS;
 use namada_core::ledger::parameters::read_epoch_duration_parameter;
 use namada_core::ledger::storage::traits::StorageHasher;
 use namada_core::ledger::storage::{DBIter, WlStorage, DB};
-use namada_core::ledger::storage_api::{StorageRead, StorageWrite};
+use namada_core::ledger::storage_api::{self, StorageRead, StorageWrite};
 use namada_core::types::address::Address;
 use namada_core::types::eth_abi::Encode;
 use namada_core::types::eth_bridge_pool::{
@@ -27,13 +28,17 @@ use namada_core::types::ethereum_events::{
     TransfersToNamada,
 };
 use namada_core::types::ethereum_structs::EthBridgeEvent;
-use namada_core::types::storage::{BlockHeight, Key, KeySeg};
+use namada_core::types::keccak::KeccakHash;
+use namada_core::types::storage::{BlockHeight, Epoch, Key, KeySeg};
 use namada_core::types::token;
 use namada_core::types::token::{balance_key, minted_balance_key};
 
 use crate::protocol::transactions::update;
 use crate::storage::eth_bridge_queries::{EthAssetMint, EthBridgeQueries};
 use crate::storage::parameters::read_native_erc20_address;
+use crate::storage::vp::bridge_pool::{
+    is_transfer_signed, read_transfer_fee_bump, transfer_inserted_height,
+};
 
 /// Updates storage based on the given confirmed `event`. For example, for a
 /// confirmed [`EthereumEvent::TransfersToNamada`], mint the corresponding
@@ -357,15 +362,25 @@ where
             balance_key(&pending_transfer.gas_fee.token, &BRIDGE_POOL_ADDRESS);
         let relayer_rewards_key =
             balance_key(&pending_transfer.gas_fee.token, relayer);
+        let gas_fees = take_transfer_gas_fees(
+            wl_storage,
+            &pending_transfer,
+            &mut changed_keys,
+        )?;
         // give the relayer the gas fee for this transfer.
         update::amount(wl_storage, &relayer_rewards_key, |balance| {
-            balance.receive(&pending_transfer.gas_fee.amount);
+            balance.receive(&gas_fees);
         })?;
         // the gas fee is removed from escrow.
         update::amount(wl_storage, &pool_balance_key, |balance| {
-            balance.spend(&pending_transfer.gas_fee.amount);
+            balance.spend(&gas_fees);
         })?;
         wl_storage.delete(&key)?;
+        delete_transfer_expiry(
+            wl_storage,
+            &pending_transfer.keccak256(),
+            &mut changed_keys,
+        )?;
         _ = pending_keys.remove(&key);
         _ = changed_keys.insert(key);
         _ = changed_keys.insert(pool_balance_key);
@@ -442,6 +457,11 @@ where
     // Delete the key from the bridge pool
     wl_storage.delete(&key)?;
     _ = changed_keys.insert(key);
+    delete_transfer_expiry(
+        wl_storage,
+        &transfer.keccak256(),
+        &mut changed_keys,
+    )?;
 
     // Emit expiration event
     _ = tx_events.insert(EthBridgeEvent::new_bridge_pool_expired(
@@ -451,6 +471,101 @@ where
     Ok((changed_keys, tx_events))
 }
 
+/// Delete the expiry of a transfer leaving the Bridge pool, if it has one.
+fn delete_transfer_expiry<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    hash: &KeccakHash,
+    changed_keys: &mut BTreeSet<Key>,
+) -> Result<()>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let expiry_key = get_expiry_key(hash);
+    if wl_storage.has_key(&expiry_key)? {
+        wl_storage.delete(&expiry_key)?;
+        _ = changed_keys.insert(expiry_key);
+    }
+    Ok(())
+}
+
+/// Delete the fee bump of a transfer leaving the Bridge pool, if it has one,
+/// and return the total gas fees escrowed for the transfer.
+fn take_transfer_gas_fees<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    transfer: &PendingTransfer,
+    changed_keys: &mut BTreeSet<Key>,
+) -> Result<token::Amount>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let hash = transfer.keccak256();
+    let fee_bump = read_transfer_fee_bump(wl_storage, &hash)?;
+    let fee_bump_key = get_fee_bump_key(&hash);
+    if wl_storage.has_key(&fee_bump_key)? {
+        wl_storage.delete(&fee_bump_key)?;
+        _ = changed_keys.insert(fee_bump_key);
+    }
+    transfer
+        .gas_fee
+        .amount
+        .checked_add(fee_bump)
+        .ok_or_else(|| eyre::eyre!("Overflowed adding the fee bump of {hash}"))
+}
+
+/// Refund the transfers in the Bridge pool whose expiry, set by their
+/// sender, has been reached. The transfers which are part of a signed
+/// Bridge pool root that can still be relayed are only refunded once that
+/// root becomes outdated.
+pub(super) fn refund_expired_transfers<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+) -> Result<(BTreeSet<Key>, BTreeSet<EthBridgeEvent>)>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let mut changed_keys = BTreeSet::default();
+    let mut tx_events = BTreeSet::default();
+
+    let current_epoch = wl_storage.get_block_epoch()?;
+    let expiries: Vec<(Key, Epoch)> =
+        storage_api::iter_prefix(wl_storage, &get_expiry_prefix())?
+            .collect::<storage_api::Result<_>>()?;
+    for (expiry_key, expiry) in expiries {
+        if expiry > current_epoch {
+            continue;
+        }
+        let Some(hash) = is_expiry_key(&expiry_key) else {
+            continue;
+        };
+        let key = get_key_from_hash(&hash);
+        if !wl_storage.has_key(&key)? {
+            hints::cold();
+            tracing::warn!(%hash, "Found the expiry of a missing transfer");
+            wl_storage.delete(&expiry_key)?;
+            _ = changed_keys.insert(expiry_key);
+            continue;
+        }
+        let inserted_height =
+            transfer_inserted_height(&wl_storage.storage, &key);
+        if is_transfer_signed(wl_storage, inserted_height)? {
+            tracing::debug!(
+                %hash,
+                "Delaying the refund of an expired transfer which can \
+                 still be relayed"
+            );
+            continue;
+        }
+        tracing::debug!(%hash, "Refunding an expired Bridge pool transfer");
+        let (mut keys, mut new_tx_events) = refund_transfer(wl_storage, key)?;
+        changed_keys.append(&mut keys);
+        tx_events.append(&mut new_tx_events);
+    }
+
+    Ok((changed_keys, tx_events))
+}
+
 fn refund_transfer_fees<D, H>(
     wl_storage: &mut WlStorage<D, H>,
     transfer: &PendingTransfer,
@@ -465,11 +580,13 @@ where
         balance_key(&transfer.gas_fee.token, &transfer.gas_fee.payer);
     let pool_balance_key =
         balance_key(&transfer.gas_fee.token, &BRIDGE_POOL_ADDRESS);
+    let gas_fees =
+        take_transfer_gas_fees(wl_storage, transfer, &mut changed_keys)?;
     update::amount(wl_storage, &payer_balance_key, |balance| {
-        balance.receive(&transfer.gas_fee.amount);
+        balance.receive(&gas_fees);
     })?;
     update::amount(wl_storage, &pool_balance_key, |balance| {
-        balance.spend(&transfer.gas_fee.amount);
+        balance.spend(&gas_fees);
     })?;
 
     tracing::debug!(?transfer, "Refunded Bridge pool transfer fees");
@@ -584,7 +701,9 @@ mod tests {
     use assert_matches::assert_matches;
     use borsh_ext::BorshSerializeExt;
     use eyre::Result;
-    use namada_core::ledger::eth_bridge::storage::bridge_pool::get_pending_key;
+    use namada_core::ledger::eth_bridge::storage::bridge_pool::{
+        get_pending_key, get_signed_root_key,
+    };
     use namada_core::ledger::parameters::{
         update_epoch_parameter, EpochDuration,
     };
@@ -602,6 +721,7 @@ mod tests {
     use namada_core::types::{address, eth_bridge_pool};
 
     use super::*;
+    use crate::storage::proof::BridgePoolRootProof;
     use crate::test_utils::{self, stored_keys_count};
 
     fn init_storage(wl_storage: &mut TestWlStorage) {
@@ -1130,6 +1250,52 @@ mod tests {
         assert_eq!(bp_erc_balance_post, Amount::from(0));
     }
 
+    #[test]
+    /// Test that the relayer of a transfer whose fees were bumped is paid
+    /// the additional fees, and that the fee bump is removed from storage.
+    fn test_act_on_transfers_to_eth_with_fee_bump() {
+        let mut wl_storage = TestWlStorage::default();
+        test_utils::bootstrap_ethereum_bridge(&mut wl_storage);
+        wl_storage.commit_block().expect("Test failed");
+        init_storage(&mut wl_storage);
+        let pending_transfers = init_bridge_pool(&mut wl_storage);
+        init_balance(&mut wl_storage, &pending_transfers);
+        let bumped = &pending_transfers[0];
+        let fee_bump_key = get_fee_bump_key(&bumped.keccak256());
+        wl_storage
+            .write(&fee_bump_key, Amount::from(2))
+            .expect("Test failed");
+        let pool_balance_key = balance_key(&nam(), &BRIDGE_POOL_ADDRESS);
+        update::amount(&mut wl_storage, &pool_balance_key, |balance| {
+            balance.receive(&Amount::from(2));
+        })
+        .expect("Test failed");
+
+        let relayer = gen_established_address("random");
+        let event = EthereumEvent::TransfersToEthereum {
+            nonce: arbitrary_nonce(),
+            transfers: pending_transfers
+                .iter()
+                .map(TransferToEthereum::from)
+                .collect(),
+            relayer: relayer.clone(),
+        };
+        let (changed_keys, _) = act_on(&mut wl_storage, event).unwrap();
+
+        assert!(changed_keys.contains(&fee_bump_key));
+        assert!(!wl_storage.has_key(&fee_bump_key).expect("Test failed"));
+        let relayer_balance: Amount = wl_storage
+            .read(&balance_key(&nam(), &relayer))
+            .expect("Test failed")
+            .expect("Test failed");
+        assert_eq!(relayer_balance, Amount::from(4));
+        let pool_balance: Amount = wl_storage
+            .read(&pool_balance_key)
+            .expect("Test failed")
+            .expect("Test failed");
+        assert_eq!(pool_balance, Amount::zero());
+    }
+
     #[test]
     /// Test that the transfers time out in the bridge pool then the refund when
     /// we act on a TransfersToEthereum
@@ -1247,6 +1413,90 @@ mod tests {
         }
     }
 
+    /// Test that the transfers whose expiry has been reached are refunded,
+    /// unless they are part of a signed root which can still be relayed
+    #[test]
+    fn test_refund_expired_transfers() {
+        let mut wl_storage = TestWlStorage::default();
+        test_utils::bootstrap_ethereum_bridge(&mut wl_storage);
+        wl_storage.commit_block().expect("Test failed");
+        init_storage(&mut wl_storage);
+        let pending_transfers = init_bridge_pool(&mut wl_storage);
+        init_balance(&mut wl_storage, &pending_transfers);
+        wl_storage
+            .storage
+            .commit_block(MockDBWriteBatch)
+            .expect("Test failed");
+        let expired = &pending_transfers[0];
+        let not_expired = &pending_transfers[1];
+        let expired_key = get_expiry_key(&expired.keccak256());
+        wl_storage
+            .write(&expired_key, Epoch(0))
+            .expect("Test failed");
+        wl_storage
+            .write(&get_expiry_key(&not_expired.keccak256()), Epoch(5))
+            .expect("Test failed");
+        // the fees of the expired transfer were bumped
+        let fee_bump_key = get_fee_bump_key(&expired.keccak256());
+        wl_storage
+            .write(&fee_bump_key, Amount::from(2))
+            .expect("Test failed");
+        update::amount(
+            &mut wl_storage,
+            &balance_key(&expired.gas_fee.token, &BRIDGE_POOL_ADDRESS),
+            |balance| {
+                balance.receive(&Amount::from(2));
+            },
+        )
+        .expect("Test failed");
+
+        // the transfers are in a root signed with the current nonce
+        let nonce = wl_storage.ethbridge_queries().get_bridge_pool_nonce();
+        let root = BridgePoolRootProof::new((KeccakHash([0; 32]), nonce));
+        wl_storage
+            .write(
+                &get_signed_root_key(),
+                (root, wl_storage.storage.block.height),
+            )
+            .expect("Test failed");
+        let (changed_keys, tx_events) =
+            refund_expired_transfers(&mut wl_storage).expect("Test failed");
+        assert!(changed_keys.is_empty());
+        assert!(tx_events.is_empty());
+
+        // the signed root is outdated after a relay
+        increment_bp_nonce(&get_nonce_key(), &mut wl_storage)
+            .expect("Test failed");
+        let (changed_keys, tx_events) =
+            refund_expired_transfers(&mut wl_storage).expect("Test failed");
+        assert!(changed_keys.contains(&get_pending_key(expired)));
+        assert!(changed_keys.contains(&expired_key));
+        assert_eq!(
+            tx_events,
+            BTreeSet::from([EthBridgeEvent::new_bridge_pool_expired(
+                expired.keccak256()
+            )])
+        );
+        assert!(!wl_storage
+            .has_key(&get_pending_key(expired))
+            .expect("Test failed"));
+        assert!(!wl_storage.has_key(&expired_key).expect("Test failed"));
+        assert!(changed_keys.contains(&fee_bump_key));
+        assert!(!wl_storage.has_key(&fee_bump_key).expect("Test failed"));
+        assert!(wl_storage
+            .has_key(&get_pending_key(not_expired))
+            .expect("Test failed"));
+        let payer_key = balance_key(
+            &expired.gas_fee.token,
+            &address::testing::established_address_2(),
+        );
+        let payer_balance: Amount = wl_storage
+            .read(&payer_key)
+            .expect("Test failed")
+            .expect("Test failed");
+        assert_eq!(payer_balance, expired.gas_fee.amount + Amount::from(2));
+    }
+
     #[test]
     fn test_redeem_native_token() -> Result<()> {
         let mut wl_storage = TestWlStorage::default();
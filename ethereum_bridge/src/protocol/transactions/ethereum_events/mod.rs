@@ -53,9 +53,13 @@ where
     H: 'static + StorageHasher + Sync,
 {
     let mut changed_keys = timeout_events(wl_storage)?;
+    let (mut refunded_keys, mut eth_bridge_events) =
+        events::refund_expired_transfers(wl_storage)?;
+    changed_keys.append(&mut refunded_keys);
     if events.is_empty() {
         return Ok(TxResult {
             changed_keys,
+            eth_bridge_events,
             ..Default::default()
         });
     }
@@ -78,9 +82,10 @@ where
 
     let voting_powers = utils::get_voting_powers(wl_storage, &updates)?;
 
-    let (mut apply_updates_keys, eth_bridge_events) =
+    let (mut apply_updates_keys, mut new_eth_bridge_events) =
         apply_updates(wl_storage, updates, voting_powers)?;
     changed_keys.append(&mut apply_updates_keys);
+    eth_bridge_events.append(&mut new_eth_bridge_events);
 
     Ok(TxResult {
         changed_keys,
@@ -1,11 +1,18 @@
+use borsh::BorshDeserialize;
 use namada_core::ledger::eth_bridge::storage::bridge_pool::{
-    get_nonce_key, BRIDGE_POOL_ADDRESS,
+    get_fee_bump_key, get_nonce_key, get_signed_root_key, BRIDGE_POOL_ADDRESS,
 };
-use namada_core::ledger::storage::{DBIter, StorageHasher, WlStorage, DB};
-use namada_core::ledger::storage_api::StorageWrite;
+use namada_core::ledger::storage::{
+    DBIter, Storage, StorageHasher, WlStorage, DB,
+};
+use namada_core::ledger::storage_api::{self, StorageRead, StorageWrite};
 use namada_core::types::ethereum_events::Uint;
+use namada_core::types::keccak::KeccakHash;
+use namada_core::types::storage::{BlockHeight, Key};
 use namada_core::types::token::{balance_key, Amount};
 
+use crate::storage::proof::BridgePoolRootProof;
+
 /// Initialize the storage owned by the Bridge Pool VP.
 ///
 /// This means that the amount of escrowed gas fees is
@@ -24,3 +31,62 @@ where
         .write(&get_nonce_key(), Uint::from(0))
         .expect("Initializing the Bridge pool nonce shouldn't fail.");
 }
+
+/// Get the height at which the pending transfer with the given key was added
+/// to the Bridge pool. The transfers added in the current block are not in
+/// the Merkle tree yet, so the current height is returned for them.
+pub fn transfer_inserted_height<D, H>(
+    storage: &Storage<D, H>,
+    key: &Key,
+) -> BlockHeight
+where
+    D: DB + for<'iter> DBIter<'iter>,
+    H: StorageHasher,
+{
+    storage
+        .block
+        .tree
+        .get(key)
+        .ok()
+        .and_then(|bytes| BlockHeight::try_from_slice(&bytes).ok())
+        .unwrap_or(storage.block.height)
+}
+
+/// Check if a transfer added to the Bridge pool at the given height is part
+/// of the latest signed Bridge pool root, while that root can still be
+/// relayed to Ethereum.
+///
+/// A signed root can only be relayed if its nonce matches the current
+/// nonce of the Bridge pool, which is incremented on every relay. Transfers
+/// for which this returns `false` can be safely refunded.
+pub fn is_transfer_signed<S>(
+    storage: &S,
+    inserted_height: BlockHeight,
+) -> storage_api::Result<bool>
+where
+    S: StorageRead,
+{
+    let Some((root, signed_height)) = storage
+        .read::<(BridgePoolRootProof, BlockHeight)>(&get_signed_root_key())?
+    else {
+        return Ok(false);
+    };
+    let nonce: Uint = storage.read(&get_nonce_key())?.ok_or_else(|| {
+        storage_api::Error::new_const("The Bridge pool nonce is missing")
+    })?;
+    Ok(root.data.1 == nonce && signed_height >= inserted_height)
+}
+
+/// Read the gas fees added on top of the [`GasFee`] of the transfer with
+/// the given hash, which are nil if its fees were never bumped.
+///
+/// [`GasFee`]: namada_core::types::eth_bridge_pool::GasFee
+pub fn read_transfer_fee_bump<S>(
+    storage: &S,
+    hash: &KeccakHash,
+) -> storage_api::Result<Amount>
+where
+    S: StorageRead,
+{
+    Ok(storage.read(&get_fee_bump_key(hash))?.unwrap_or_default())
+}
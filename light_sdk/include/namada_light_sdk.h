@@ -0,0 +1,185 @@
+#ifndef NAMADA_LIGHT_SDK_H
+#define NAMADA_LIGHT_SDK_H
+
+/* Generated with cbindgen from light_sdk/src/ffi, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// The outcome of a call to the C interface
+typedef enum NamadaStatus {
+  // The call succeeded
+  NAMADA_STATUS_OK = 0,
+  // A required pointer argument was null
+  NAMADA_STATUS_NULL_POINTER = 1,
+  // A string argument was not valid UTF-8 or could not be parsed
+  NAMADA_STATUS_INVALID_STRING = 2,
+  // The transaction kind is unknown
+  NAMADA_STATUS_INVALID_KIND = 3,
+  // A byte buffer argument could not be decoded
+  NAMADA_STATUS_DECODING_ERROR = 4,
+  // The call panicked
+  NAMADA_STATUS_PANIC = 5,
+} NamadaStatus;
+
+// The kinds of transactions that can be built with [`namada_tx_build`],
+// along with the type whose Borsh encoding must be passed as their data
+enum NamadaTxKind {
+  // `token::Transfer`
+  NAMADA_TX_KIND_TRANSFER = 0,
+  // `transaction::account::InitAccount`
+  NAMADA_TX_KIND_INIT_ACCOUNT = 1,
+  // `key::common::PublicKey`
+  NAMADA_TX_KIND_REVEAL_PK = 2,
+  // `transaction::account::UpdateAccount`
+  NAMADA_TX_KIND_UPDATE_ACCOUNT = 3,
+  // `transaction::pos::Bond`
+  NAMADA_TX_KIND_BOND = 4,
+  // `transaction::pos::Unbond`
+  NAMADA_TX_KIND_UNBOND = 5,
+  // `transaction::pos::BecomeValidator`
+  NAMADA_TX_KIND_BECOME_VALIDATOR = 6,
+  // `address::Address` of the validator
+  NAMADA_TX_KIND_UNJAIL_VALIDATOR = 7,
+  // `address::Address` of the validator
+  NAMADA_TX_KIND_DEACTIVATE_VALIDATOR = 8,
+  // `address::Address` of the validator
+  NAMADA_TX_KIND_REACTIVATE_VALIDATOR = 9,
+  // `transaction::pos::ClaimRewards`
+  NAMADA_TX_KIND_CLAIM_REWARDS = 10,
+  // `transaction::pos::MetaDataChange`
+  NAMADA_TX_KIND_CHANGE_META_DATA = 11,
+  // `transaction::pos::ConsensusKeyChange`
+  NAMADA_TX_KIND_CHANGE_CONSENSUS_KEY = 12,
+  // `transaction::pos::CommissionChange`
+  NAMADA_TX_KIND_CHANGE_COMMISSION = 13,
+  // `transaction::pos::Withdraw`
+  NAMADA_TX_KIND_WITHDRAW = 14,
+  // `transaction::pos::Redelegation`
+  NAMADA_TX_KIND_REDELEGATE = 15,
+  // `transaction::governance::InitProposalData`
+  NAMADA_TX_KIND_INIT_PROPOSAL = 16,
+  // `transaction::governance::VoteProposalData`
+  NAMADA_TX_KIND_VOTE_PROPOSAL = 17,
+  // `address::Address` of the steward
+  NAMADA_TX_KIND_RESIGN_STEWARD = 18,
+  // `transaction::pgf::UpdateStewardCommission`
+  NAMADA_TX_KIND_UPDATE_STEWARD_COMMISSION = 19,
+  // `eth_bridge_pool::PendingTransfer`
+  NAMADA_TX_KIND_BRIDGE_TRANSFER = 20,
+  // The Protobuf encoding of an IBC `MsgTransfer`
+  NAMADA_TX_KIND_IBC_TRANSFER = 21,
+};
+typedef uint32_t NamadaTxKind;
+
+// An opaque handle to a transaction, to be freed with [`namada_tx_free`]
+typedef struct NamadaTx NamadaTx;
+
+// A byte buffer allocated by this library, to be freed with
+// [`namada_buffer_free`]
+typedef struct NamadaBuffer {
+  // Pointer to the first byte
+  uint8_t *data;
+  // Number of bytes
+  size_t len;
+} NamadaBuffer;
+
+// Build a raw transaction of the given [`NamadaTxKind`] from the encoding of
+// its data. The expiration is an optional RFC 3339 timestamp.
+//
+// # Safety
+//
+// `data` must point to `data_len` readable bytes, `code_hash` to 32 readable
+// bytes, `chain_id` and `expiration` (if not null) to null-terminated
+// strings, and `out` to writable memory.
+NamadaStatus namada_tx_build(uint32_t kind,
+                             const uint8_t *data,
+                             size_t data_len,
+                             const char *chain_id,
+                             const uint8_t *code_hash,
+                             const char *expiration,
+                             NamadaTx **out);
+
+// Get the hashes to sign for a raw transaction, concatenated
+//
+// # Safety
+//
+// `tx` must be a live handle and `out` must point to writable memory.
+NamadaStatus namada_tx_sign_bytes(const NamadaTx *tx, NamadaBuffer *out);
+
+// Attach a signature over the hashes of [`namada_tx_sign_bytes`] to a raw
+// transaction, given the encodings of the signer's `key::common::PublicKey`
+// and of the `key::common::Signature`
+//
+// # Safety
+//
+// `tx` must be a live handle, and `public_key` and `signature` must point to
+// `public_key_len` and `signature_len` readable bytes.
+NamadaStatus namada_tx_attach_signature(NamadaTx *tx,
+                                        const uint8_t *public_key,
+                                        size_t public_key_len,
+                                        const uint8_t *signature,
+                                        size_t signature_len);
+
+// Wrap a raw transaction, given the encodings of the `transaction::Fee` and
+// of the fee payer's `key::common::PublicKey`
+//
+// # Safety
+//
+// `tx` must be a live handle, and `fee` and `fee_payer` must point to
+// `fee_len` and `fee_payer_len` readable bytes.
+NamadaStatus namada_tx_wrap(NamadaTx *tx,
+                            const uint8_t *fee,
+                            size_t fee_len,
+                            const uint8_t *fee_payer,
+                            size_t fee_payer_len,
+                            uint64_t gas_limit);
+
+// Get the hashes to sign for a wrapper transaction, concatenated. This
+// drops the sections that must not be signed by the fee payer.
+//
+// # Safety
+//
+// `tx` must be a live handle and `out` must point to writable memory.
+NamadaStatus namada_wrapper_sign_bytes(NamadaTx *tx, NamadaBuffer *out);
+
+// Attach the fee payer's signature over the hashes of
+// [`namada_wrapper_sign_bytes`] to a wrapper transaction, given the
+// encodings of the `key::common::PublicKey` and of the
+// `key::common::Signature`
+//
+// # Safety
+//
+// `tx` must be a live handle, and `public_key` and `signature` must point to
+// `public_key_len` and `signature_len` readable bytes.
+NamadaStatus namada_wrapper_attach_signature(NamadaTx *tx,
+                                             const uint8_t *public_key,
+                                             size_t public_key_len,
+                                             const uint8_t *signature,
+                                             size_t signature_len);
+
+// Serialize a transaction to the bytes to be broadcast
+//
+// # Safety
+//
+// `tx` must be a live handle and `out` must point to writable memory.
+NamadaStatus namada_tx_to_bytes(const NamadaTx *tx, NamadaBuffer *out);
+
+// Free a transaction handle
+//
+// # Safety
+//
+// `tx` must be null or a live handle, which must not be used afterwards.
+void namada_tx_free(NamadaTx *tx);
+
+// Free a buffer returned by this library
+//
+// # Safety
+//
+// The buffer must have been returned by this library and not freed already.
+void namada_buffer_free(NamadaBuffer buffer);
+
+#endif /* NAMADA_LIGHT_SDK_H */
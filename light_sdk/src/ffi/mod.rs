@@ -0,0 +1,929 @@
+//! C-compatible interface to the transaction builders of this crate, for
+//! wrapping the SDK in other languages.
+//!
+//! Transactions are exposed as opaque [`NamadaTx`] handles, and all the
+//! arguments that are not plain integers or strings are passed as byte
+//! buffers holding the Borsh encoding of the corresponding Rust type. Every
+//! fallible function returns a [`NamadaStatus`] and writes its result through
+//! an out-pointer. The C header is generated with `make
+//! gen-light-sdk-header`.
+
+use std::ffi::{c_char, CStr};
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+
+use borsh::BorshDeserialize;
+use namada_core::ibc::apps::transfer::types::msgs::transfer::MsgTransfer;
+use namada_core::ibc::primitives::proto::Protobuf;
+use namada_core::proto::Tx;
+use namada_core::types::address::Address;
+use namada_core::types::chain::ChainId;
+use namada_core::types::eth_bridge_pool::PendingTransfer;
+use namada_core::types::hash::{Hash, HASH_LENGTH};
+use namada_core::types::key::common;
+use namada_core::types::time::DateTimeUtc;
+use namada_core::types::token;
+use namada_core::types::transaction::{
+    account, governance, pgf, pos, Fee, GasLimit,
+};
+
+use crate::transaction::wrapper::Wrapper;
+use crate::transaction::{self, GlobalArgs};
+
+/// The outcome of a call to the C interface
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NamadaStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// A required pointer argument was null
+    NullPointer = 1,
+    /// A string argument was not valid UTF-8 or could not be parsed
+    InvalidString = 2,
+    /// The transaction kind is unknown
+    InvalidKind = 3,
+    /// A byte buffer argument could not be decoded
+    DecodingError = 4,
+    /// The call panicked
+    Panic = 5,
+}
+
+/// The kinds of transactions that can be built with [`namada_tx_build`],
+/// along with the type whose Borsh encoding must be passed as their data
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NamadaTxKind {
+    /// `token::Transfer`
+    Transfer = 0,
+    /// `transaction::account::InitAccount`
+    InitAccount = 1,
+    /// `key::common::PublicKey`
+    RevealPk = 2,
+    /// `transaction::account::UpdateAccount`
+    UpdateAccount = 3,
+    /// `transaction::pos::Bond`
+    Bond = 4,
+    /// `transaction::pos::Unbond`
+    Unbond = 5,
+    /// `transaction::pos::BecomeValidator`
+    BecomeValidator = 6,
+    /// `address::Address` of the validator
+    UnjailValidator = 7,
+    /// `address::Address` of the validator
+    DeactivateValidator = 8,
+    /// `address::Address` of the validator
+    ReactivateValidator = 9,
+    /// `transaction::pos::ClaimRewards`
+    ClaimRewards = 10,
+    /// `transaction::pos::MetaDataChange`
+    ChangeMetaData = 11,
+    /// `transaction::pos::ConsensusKeyChange`
+    ChangeConsensusKey = 12,
+    /// `transaction::pos::CommissionChange`
+    ChangeCommission = 13,
+    /// `transaction::pos::Withdraw`
+    Withdraw = 14,
+    /// `transaction::pos::Redelegation`
+    Redelegate = 15,
+    /// `transaction::governance::InitProposalData`
+    InitProposal = 16,
+    /// `transaction::governance::VoteProposalData`
+    VoteProposal = 17,
+    /// `address::Address` of the steward
+    ResignSteward = 18,
+    /// `transaction::pgf::UpdateStewardCommission`
+    UpdateStewardCommission = 19,
+    /// `eth_bridge_pool::PendingTransfer`
+    BridgeTransfer = 20,
+    /// The Protobuf encoding of an IBC `MsgTransfer`
+    IbcTransfer = 21,
+}
+
+impl TryFrom<u32> for NamadaTxKind {
+    type Error = NamadaStatus;
+
+    fn try_from(kind: u32) -> Result<Self, Self::Error> {
+        use NamadaTxKind::*;
+
+        [
+            Transfer,
+            InitAccount,
+            RevealPk,
+            UpdateAccount,
+            Bond,
+            Unbond,
+            BecomeValidator,
+            UnjailValidator,
+            DeactivateValidator,
+            ReactivateValidator,
+            ClaimRewards,
+            ChangeMetaData,
+            ChangeConsensusKey,
+            ChangeCommission,
+            Withdraw,
+            Redelegate,
+            InitProposal,
+            VoteProposal,
+            ResignSteward,
+            UpdateStewardCommission,
+            BridgeTransfer,
+            IbcTransfer,
+        ]
+        .into_iter()
+        .find(|candidate| *candidate as u32 == kind)
+        .ok_or(NamadaStatus::InvalidKind)
+    }
+}
+
+/// An opaque handle to a transaction, to be freed with [`namada_tx_free`]
+pub struct NamadaTx(Tx);
+
+/// A byte buffer allocated by this library, to be freed with
+/// [`namada_buffer_free`]
+#[repr(C)]
+#[derive(Debug)]
+pub struct NamadaBuffer {
+    /// Pointer to the first byte
+    pub data: *mut u8,
+    /// Number of bytes
+    pub len: usize,
+}
+
+impl From<Vec<u8>> for NamadaBuffer {
+    fn from(bytes: Vec<u8>) -> Self {
+        let len = bytes.len();
+        let data = Box::into_raw(bytes.into_boxed_slice()) as *mut u8;
+        Self { data, len }
+    }
+}
+
+/// Build a raw transaction of the given [`NamadaTxKind`] from the encoding of
+/// its data. The expiration is an optional RFC 3339 timestamp.
+///
+/// # Safety
+///
+/// `data` must point to `data_len` readable bytes, `code_hash` to 32 readable
+/// bytes, `chain_id` and `expiration` (if not null) to null-terminated
+/// strings, and `out` to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_build(
+    kind: u32,
+    data: *const u8,
+    data_len: usize,
+    chain_id: *const c_char,
+    code_hash: *const u8,
+    expiration: *const c_char,
+    out: *mut *mut NamadaTx,
+) -> NamadaStatus {
+    guard(|| {
+        let kind = NamadaTxKind::try_from(kind)?;
+        let data = bytes(data, data_len)?;
+        let code_hash = bytes(code_hash, HASH_LENGTH)?;
+        let expiration = if expiration.is_null() {
+            None
+        } else {
+            Some(
+                DateTimeUtc::from_str(string(expiration)?)
+                    .map_err(|_| NamadaStatus::InvalidString)?,
+            )
+        };
+        let args = GlobalArgs {
+            expiration,
+            code_hash: Hash(code_hash.try_into().unwrap()),
+            chain_id: ChainId(string(chain_id)?.to_string()),
+        };
+        let tx = build_tx(kind, data, args)?;
+        write(out, Box::into_raw(Box::new(NamadaTx(tx))))
+    })
+}
+
+/// Get the hashes to sign for a raw transaction, concatenated
+///
+/// # Safety
+///
+/// `tx` must be a live handle and `out` must point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_sign_bytes(
+    tx: *const NamadaTx,
+    out: *mut NamadaBuffer,
+) -> NamadaStatus {
+    guard(|| {
+        let tx = handle(tx)?;
+        write(out, concat_hashes(transaction::get_sign_bytes(&tx.0)))
+    })
+}
+
+/// Attach a signature over the hashes of [`namada_tx_sign_bytes`] to a raw
+/// transaction, given the encodings of the signer's `key::common::PublicKey`
+/// and of the `key::common::Signature`
+///
+/// # Safety
+///
+/// `tx` must be a live handle, and `public_key` and `signature` must point to
+/// `public_key_len` and `signature_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_attach_signature(
+    tx: *mut NamadaTx,
+    public_key: *const u8,
+    public_key_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+) -> NamadaStatus {
+    guard(|| {
+        let tx = handle_mut(tx)?;
+        let public_key: common::PublicKey =
+            decode(bytes(public_key, public_key_len)?)?;
+        let signature: common::Signature =
+            decode(bytes(signature, signature_len)?)?;
+        tx.0 = transaction::attach_raw_signatures(
+            tx.0.clone(),
+            public_key,
+            signature,
+        );
+        Ok(())
+    })
+}
+
+/// Wrap a raw transaction, given the encodings of the `transaction::Fee` and
+/// of the fee payer's `key::common::PublicKey`
+///
+/// # Safety
+///
+/// `tx` must be a live handle, and `fee` and `fee_payer` must point to
+/// `fee_len` and `fee_payer_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_wrap(
+    tx: *mut NamadaTx,
+    fee: *const u8,
+    fee_len: usize,
+    fee_payer: *const u8,
+    fee_payer_len: usize,
+    gas_limit: u64,
+) -> NamadaStatus {
+    guard(|| {
+        let tx = handle_mut(tx)?;
+        let fee: Fee = decode(bytes(fee, fee_len)?)?;
+        let fee_payer: common::PublicKey =
+            decode(bytes(fee_payer, fee_payer_len)?)?;
+        tx.0 = Wrapper::new(
+            tx.0.clone(),
+            fee,
+            fee_payer,
+            GasLimit::from(gas_limit),
+            None,
+        )
+        .payload();
+        Ok(())
+    })
+}
+
+/// Get the hashes to sign for a wrapper transaction, concatenated. This
+/// drops the sections that must not be signed by the fee payer.
+///
+/// # Safety
+///
+/// `tx` must be a live handle and `out` must point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn namada_wrapper_sign_bytes(
+    tx: *mut NamadaTx,
+    out: *mut NamadaBuffer,
+) -> NamadaStatus {
+    guard(|| {
+        let tx = handle_mut(tx)?;
+        let (wrapper, hashes) = Wrapper(tx.0.clone()).get_sign_bytes();
+        tx.0 = wrapper.payload();
+        write(out, concat_hashes(hashes))
+    })
+}
+
+/// Attach the fee payer's signature over the hashes of
+/// [`namada_wrapper_sign_bytes`] to a wrapper transaction, given the
+/// encodings of the `key::common::PublicKey` and of the
+/// `key::common::Signature`
+///
+/// # Safety
+///
+/// `tx` must be a live handle, and `public_key` and `signature` must point to
+/// `public_key_len` and `signature_len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn namada_wrapper_attach_signature(
+    tx: *mut NamadaTx,
+    public_key: *const u8,
+    public_key_len: usize,
+    signature: *const u8,
+    signature_len: usize,
+) -> NamadaStatus {
+    guard(|| {
+        let tx = handle_mut(tx)?;
+        let public_key: common::PublicKey =
+            decode(bytes(public_key, public_key_len)?)?;
+        let signature: common::Signature =
+            decode(bytes(signature, signature_len)?)?;
+        tx.0 = Wrapper(tx.0.clone())
+            .attach_signature(public_key, signature)
+            .payload();
+        Ok(())
+    })
+}
+
+/// Serialize a transaction to the bytes to be broadcast
+///
+/// # Safety
+///
+/// `tx` must be a live handle and `out` must point to writable memory.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_to_bytes(
+    tx: *const NamadaTx,
+    out: *mut NamadaBuffer,
+) -> NamadaStatus {
+    guard(|| {
+        let tx = handle(tx)?;
+        write(out, NamadaBuffer::from(tx.0.to_bytes()))
+    })
+}
+
+/// Free a transaction handle
+///
+/// # Safety
+///
+/// `tx` must be null or a live handle, which must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn namada_tx_free(tx: *mut NamadaTx) {
+    if !tx.is_null() {
+        drop(Box::from_raw(tx));
+    }
+}
+
+/// Free a buffer returned by this library
+///
+/// # Safety
+///
+/// The buffer must have been returned by this library and not freed already.
+#[no_mangle]
+pub unsafe extern "C" fn namada_buffer_free(buffer: NamadaBuffer) {
+    if !buffer.data.is_null() {
+        drop(Box::from_raw(std::ptr::slice_from_raw_parts_mut(
+            buffer.data,
+            buffer.len,
+        )));
+    }
+}
+
+fn build_tx(
+    kind: NamadaTxKind,
+    data: &[u8],
+    args: GlobalArgs,
+) -> Result<Tx, NamadaStatus> {
+    use crate::transaction::{
+        account as acc, bridge, governance as gov, ibc, pgf as pgf_tx,
+        pos as pos_tx, transfer,
+    };
+
+    let tx = match kind {
+        NamadaTxKind::Transfer => {
+            let token::Transfer {
+                source,
+                target,
+                token,
+                amount,
+                key,
+                shielded,
+            } = decode(data)?;
+            transfer::Transfer::new(
+                source, target, token, amount, key, shielded, args,
+            )
+            .payload()
+        }
+        NamadaTxKind::InitAccount => {
+            let account::InitAccount {
+                public_keys,
+                vp_code_hash,
+                threshold,
+            } = decode(data)?;
+            acc::InitAccount::new(public_keys, vp_code_hash, threshold, args)
+                .payload()
+        }
+        NamadaTxKind::RevealPk => {
+            acc::RevealPk::new(decode(data)?, args).payload()
+        }
+        NamadaTxKind::UpdateAccount => {
+            let account::UpdateAccount {
+                addr,
+                vp_code_hash,
+                public_keys,
+                threshold,
+                key_options,
+            } = decode(data)?;
+            acc::UpdateAccount::new(
+                addr,
+                vp_code_hash,
+                public_keys,
+                threshold,
+                key_options,
+                args,
+            )
+            .payload()
+        }
+        NamadaTxKind::Bond => {
+            let pos::Bond {
+                validator,
+                amount,
+                source,
+            } = decode(data)?;
+            pos_tx::Bond::new(validator, amount, source, args).payload()
+        }
+        NamadaTxKind::Unbond => {
+            let pos::Unbond {
+                validator,
+                amount,
+                source,
+            } = decode(data)?;
+            pos_tx::Unbond::new(validator, amount, source, args).payload()
+        }
+        NamadaTxKind::BecomeValidator => {
+            let pos::BecomeValidator {
+                address,
+                consensus_key,
+                eth_cold_key,
+                eth_hot_key,
+                protocol_key,
+                commission_rate,
+                max_commission_rate_change,
+                email,
+                description,
+                website,
+                discord_handle,
+            } = decode(data)?;
+            pos_tx::BecomeValidator::new(
+                address,
+                consensus_key,
+                eth_cold_key,
+                eth_hot_key,
+                protocol_key,
+                commission_rate,
+                max_commission_rate_change,
+                email,
+                description,
+                website,
+                discord_handle,
+                args,
+            )
+            .payload()
+        }
+        NamadaTxKind::UnjailValidator => {
+            let address: Address = decode(data)?;
+            pos_tx::UnjailValidator::new(address, args).payload()
+        }
+        NamadaTxKind::DeactivateValidator => {
+            let address: Address = decode(data)?;
+            pos_tx::DeactivateValidator::new(address, args).payload()
+        }
+        NamadaTxKind::ReactivateValidator => {
+            let address: Address = decode(data)?;
+            pos_tx::ReactivateValidator::new(address, args).payload()
+        }
+        NamadaTxKind::ClaimRewards => {
+            let pos::ClaimRewards { validator, source } = decode(data)?;
+            pos_tx::ClaimRewards::new(validator, source, args).payload()
+        }
+        NamadaTxKind::ChangeMetaData => {
+            let pos::MetaDataChange {
+                validator,
+                email,
+                description,
+                website,
+                discord_handle,
+                commission_rate,
+            } = decode(data)?;
+            pos_tx::ChangeMetaData::new(
+                validator,
+                email,
+                description,
+                website,
+                discord_handle,
+                commission_rate,
+                args,
+            )
+            .payload()
+        }
+        NamadaTxKind::ChangeConsensusKey => {
+            let pos::ConsensusKeyChange {
+                validator,
+                consensus_key,
+            } = decode(data)?;
+            pos_tx::ChangeConsensusKey::new(validator, consensus_key, args)
+                .payload()
+        }
+        NamadaTxKind::ChangeCommission => {
+            let pos::CommissionChange {
+                validator,
+                new_rate,
+            } = decode(data)?;
+            pos_tx::ChangeCommission::new(validator, new_rate, args).payload()
+        }
+        NamadaTxKind::Withdraw => {
+            let pos::Withdraw { validator, source } = decode(data)?;
+            pos_tx::Withdraw::new(validator, source, args).payload()
+        }
+        NamadaTxKind::Redelegate => {
+            let pos::Redelegation {
+                src_validator,
+                dest_validator,
+                owner,
+                amount,
+            } = decode(data)?;
+            pos_tx::Redelegate::new(
+                src_validator,
+                dest_validator,
+                owner,
+                amount,
+                args,
+            )
+            .payload()
+        }
+        NamadaTxKind::InitProposal => {
+            let governance::InitProposalData {
+                id,
+                content,
+                author,
+                r#type,
+                voting_start_epoch,
+                voting_end_epoch,
+                grace_epoch,
+                deposit,
+            } = decode(data)?;
+            gov::InitProposal::new(
+                id,
+                content,
+                author,
+                r#type,
+                voting_start_epoch,
+                voting_end_epoch,
+                grace_epoch,
+                deposit,
+                args,
+            )
+            .payload()
+        }
+        NamadaTxKind::VoteProposal => {
+            let governance::VoteProposalData {
+                id,
+                vote,
+                voter,
+                delegations,
+            } = decode(data)?;
+            gov::VoteProposal::new(id, vote, voter, delegations, args).payload()
+        }
+        NamadaTxKind::ResignSteward => {
+            let steward: Address = decode(data)?;
+            pgf_tx::ResignSteward::new(steward, args).payload()
+        }
+        NamadaTxKind::UpdateStewardCommission => {
+            let pgf::UpdateStewardCommission {
+                steward,
+                commission,
+            } = decode(data)?;
+            pgf_tx::UpdateStewardCommission::new(steward, commission, args)
+                .payload()
+        }
+        NamadaTxKind::BridgeTransfer => {
+            let PendingTransfer { transfer, gas_fee } = decode(data)?;
+            bridge::BridgeTransfer::new(transfer, gas_fee, args).payload()
+        }
+        NamadaTxKind::IbcTransfer => {
+            let message = MsgTransfer::decode_vec(data)
+                .map_err(|_| NamadaStatus::DecodingError)?;
+            ibc::IbcTransfer::new(message, args).payload()
+        }
+    };
+    Ok(tx)
+}
+
+/// Run the body of a call, catching panics so that they do not unwind across
+/// the FFI boundary
+fn guard(call: impl FnOnce() -> Result<(), NamadaStatus>) -> NamadaStatus {
+    match panic::catch_unwind(AssertUnwindSafe(call)) {
+        Ok(Ok(())) => NamadaStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => NamadaStatus::Panic,
+    }
+}
+
+fn decode<T: BorshDeserialize>(bytes: &[u8]) -> Result<T, NamadaStatus> {
+    T::try_from_slice(bytes).map_err(|_| NamadaStatus::DecodingError)
+}
+
+fn concat_hashes(hashes: Vec<Hash>) -> NamadaBuffer {
+    hashes
+        .into_iter()
+        .flat_map(|hash| hash.0)
+        .collect::<Vec<u8>>()
+        .into()
+}
+
+unsafe fn bytes<'a>(
+    data: *const u8,
+    len: usize,
+) -> Result<&'a [u8], NamadaStatus> {
+    if data.is_null() {
+        if len == 0 {
+            Ok(&[])
+        } else {
+            Err(NamadaStatus::NullPointer)
+        }
+    } else {
+        Ok(std::slice::from_raw_parts(data, len))
+    }
+}
+
+unsafe fn string<'a>(string: *const c_char) -> Result<&'a str, NamadaStatus> {
+    if string.is_null() {
+        return Err(NamadaStatus::NullPointer);
+    }
+    CStr::from_ptr(string)
+        .to_str()
+        .map_err(|_| NamadaStatus::InvalidString)
+}
+
+unsafe fn handle<'a>(
+    tx: *const NamadaTx,
+) -> Result<&'a NamadaTx, NamadaStatus> {
+    tx.as_ref().ok_or(NamadaStatus::NullPointer)
+}
+
+unsafe fn handle_mut<'a>(
+    tx: *mut NamadaTx,
+) -> Result<&'a mut NamadaTx, NamadaStatus> {
+    tx.as_mut().ok_or(NamadaStatus::NullPointer)
+}
+
+unsafe fn write<T>(out: *mut T, value: T) -> Result<(), NamadaStatus> {
+    if out.is_null() {
+        return Err(NamadaStatus::NullPointer);
+    }
+    out.write(value);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ffi::CString;
+    use std::ptr;
+
+    use borsh_ext::BorshSerializeExt;
+    use namada_core::types::address::nam;
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada_core::types::key::testing::keypair_1;
+    use namada_core::types::key::{RefTo, SigScheme};
+    use namada_core::types::token::{Amount, DenominatedAmount};
+
+    use super::*;
+
+    const CHAIN_ID: &str = "localnet.000000000000000000000";
+    const EXPIRATION: &str = "2030-01-01T00:00:00Z";
+
+    fn global_args() -> GlobalArgs {
+        GlobalArgs {
+            expiration: Some(DateTimeUtc::from_str(EXPIRATION).unwrap()),
+            code_hash: Hash([7; HASH_LENGTH]),
+            chain_id: ChainId(CHAIN_ID.to_string()),
+        }
+    }
+
+    fn build_through_ffi(kind: NamadaTxKind, data: &[u8]) -> *mut NamadaTx {
+        let chain_id = CString::new(CHAIN_ID).unwrap();
+        let expiration = CString::new(EXPIRATION).unwrap();
+        let code_hash = [7; HASH_LENGTH];
+        let mut tx = ptr::null_mut();
+        let status = unsafe {
+            namada_tx_build(
+                kind as u32,
+                data.as_ptr(),
+                data.len(),
+                chain_id.as_ptr(),
+                code_hash.as_ptr(),
+                expiration.as_ptr(),
+                &mut tx,
+            )
+        };
+        assert_eq!(status, NamadaStatus::Ok);
+        tx
+    }
+
+    fn take_buffer(buffer: NamadaBuffer) -> Vec<u8> {
+        let bytes =
+            unsafe { std::slice::from_raw_parts(buffer.data, buffer.len) }
+                .to_vec();
+        unsafe { namada_buffer_free(buffer) };
+        bytes
+    }
+
+    fn empty_buffer() -> NamadaBuffer {
+        NamadaBuffer {
+            data: ptr::null_mut(),
+            len: 0,
+        }
+    }
+
+    /// Test that a transfer signed and wrapped through the C interface is
+    /// byte-identical to the one built in Rust.
+    #[test]
+    fn test_transfer_round_trip() {
+        let keypair = keypair_1();
+        let public_key = keypair.ref_to();
+        let transfer = token::Transfer {
+            source: established_address_1(),
+            target: established_address_2(),
+            token: nam(),
+            amount: DenominatedAmount::native(Amount::from(100)),
+            key: None,
+            shielded: None,
+        };
+        let fee = Fee {
+            amount_per_gas_unit: DenominatedAmount::native(Amount::from(1)),
+            token: nam(),
+        };
+
+        // Build the tx in Rust
+        let tx = transaction::transfer::Transfer::new(
+            transfer.source.clone(),
+            transfer.target.clone(),
+            transfer.token.clone(),
+            transfer.amount,
+            None,
+            None,
+            global_args(),
+        );
+        let raw_sig = common::SigScheme::sign(&keypair, tx.get_sign_bytes()[0]);
+        let tx = tx.attach_signatures(public_key.clone(), raw_sig.clone());
+        let (wrapper, wrapper_hashes) = Wrapper::new(
+            tx.payload(),
+            fee.clone(),
+            public_key.clone(),
+            GasLimit::from(20_000),
+            None,
+        )
+        .get_sign_bytes();
+        let wrapper_sig = common::SigScheme::sign(&keypair, wrapper_hashes[0]);
+        let expected = wrapper
+            .attach_signature(public_key.clone(), wrapper_sig.clone())
+            .to_bytes();
+
+        // Build the same tx through the C interface
+        let ffi_tx = build_through_ffi(
+            NamadaTxKind::Transfer,
+            &transfer.serialize_to_vec(),
+        );
+        let public_key = public_key.serialize_to_vec();
+        let raw_sig = raw_sig.serialize_to_vec();
+        let wrapper_sig = wrapper_sig.serialize_to_vec();
+        let fee = fee.serialize_to_vec();
+        let mut out = empty_buffer();
+        let bytes = unsafe {
+            assert_eq!(
+                namada_tx_sign_bytes(ffi_tx, &mut out),
+                NamadaStatus::Ok
+            );
+            assert_eq!(take_buffer(out).len(), HASH_LENGTH);
+            assert_eq!(
+                namada_tx_attach_signature(
+                    ffi_tx,
+                    public_key.as_ptr(),
+                    public_key.len(),
+                    raw_sig.as_ptr(),
+                    raw_sig.len(),
+                ),
+                NamadaStatus::Ok
+            );
+            assert_eq!(
+                namada_tx_wrap(
+                    ffi_tx,
+                    fee.as_ptr(),
+                    fee.len(),
+                    public_key.as_ptr(),
+                    public_key.len(),
+                    20_000,
+                ),
+                NamadaStatus::Ok
+            );
+            let mut out = empty_buffer();
+            assert_eq!(
+                namada_wrapper_sign_bytes(ffi_tx, &mut out),
+                NamadaStatus::Ok
+            );
+            let hashes = take_buffer(out);
+            assert_eq!(
+                hashes,
+                wrapper_hashes
+                    .iter()
+                    .flat_map(|hash| hash.0)
+                    .collect::<Vec<u8>>()
+            );
+            assert_eq!(
+                namada_wrapper_attach_signature(
+                    ffi_tx,
+                    public_key.as_ptr(),
+                    public_key.len(),
+                    wrapper_sig.as_ptr(),
+                    wrapper_sig.len(),
+                ),
+                NamadaStatus::Ok
+            );
+            let mut out = empty_buffer();
+            assert_eq!(namada_tx_to_bytes(ffi_tx, &mut out), NamadaStatus::Ok);
+            namada_tx_free(ffi_tx);
+            take_buffer(out)
+        };
+
+        assert_eq!(bytes, expected);
+    }
+
+    /// Test that raw txs of other kinds built through the C interface are
+    /// byte-identical to the ones built in Rust.
+    #[test]
+    fn test_build_round_trip() {
+        let bond = pos::Bond {
+            validator: established_address_1(),
+            amount: Amount::from(10),
+            source: Some(established_address_2()),
+        };
+        let cases = [
+            (
+                NamadaTxKind::Bond,
+                bond.serialize_to_vec(),
+                transaction::pos::Bond::new(
+                    bond.validator.clone(),
+                    bond.amount,
+                    bond.source.clone(),
+                    global_args(),
+                )
+                .to_bytes(),
+            ),
+            (
+                NamadaTxKind::RevealPk,
+                keypair_1().ref_to().serialize_to_vec(),
+                transaction::account::RevealPk::new(
+                    keypair_1().ref_to(),
+                    global_args(),
+                )
+                .to_bytes(),
+            ),
+            (
+                NamadaTxKind::ResignSteward,
+                established_address_1().serialize_to_vec(),
+                transaction::pgf::ResignSteward::new(
+                    established_address_1(),
+                    global_args(),
+                )
+                .to_bytes(),
+            ),
+        ];
+
+        for (kind, data, expected) in cases {
+            let tx = build_through_ffi(kind, &data);
+            let mut out = empty_buffer();
+            let bytes = unsafe {
+                assert_eq!(namada_tx_to_bytes(tx, &mut out), NamadaStatus::Ok);
+                namada_tx_free(tx);
+                take_buffer(out)
+            };
+            assert_eq!(bytes, expected);
+        }
+    }
+
+    /// Test that invalid arguments are reported through the status codes.
+    #[test]
+    fn test_invalid_arguments() {
+        let chain_id = CString::new(CHAIN_ID).unwrap();
+        let code_hash = [7; HASH_LENGTH];
+        let data = [1, 2, 3];
+        let mut tx = ptr::null_mut();
+        let build = |kind: u32, chain_id: *const c_char, tx| unsafe {
+            namada_tx_build(
+                kind,
+                data.as_ptr(),
+                data.len(),
+                chain_id,
+                code_hash.as_ptr(),
+                ptr::null(),
+                tx,
+            )
+        };
+
+        assert_eq!(
+            build(1000, chain_id.as_ptr(), &mut tx),
+            NamadaStatus::InvalidKind
+        );
+        assert_eq!(
+            build(NamadaTxKind::Bond as u32, ptr::null(), &mut tx),
+            NamadaStatus::NullPointer
+        );
+        assert_eq!(
+            build(NamadaTxKind::Bond as u32, chain_id.as_ptr(), &mut tx),
+            NamadaStatus::DecodingError
+        );
+        assert!(tx.is_null());
+    }
+}
@@ -13,8 +13,13 @@
 //! - transaction: contains functions to construct all the transactions
 //!   currently supported by the protocol
 //! - reading: exposes queries to retrieve data from a Namada node
-//! - writing: TO BE DONE
+//! - writing: broadcasts signed transactions to a Namada node and waits for
+//!   their results
+//!
+//! The transaction builders are also exposed through a C interface in the
+//! `ffi` module, whose header is `include/namada_light_sdk.h`.
 
+pub mod ffi;
 pub mod reading;
 pub mod transaction;
 pub mod writing;
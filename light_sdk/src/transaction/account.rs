@@ -1,4 +1,5 @@
 use namada_core::proto::Tx;
+use namada_core::types::account::AccountKeyOptions;
 use namada_core::types::address::Address;
 use namada_core::types::hash::Hash;
 use namada_core::types::key::common;
@@ -55,6 +56,11 @@ impl InitAccount {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
 
 /// Transaction to reveal a public key to the ledger to validate signatures of
@@ -91,6 +97,11 @@ impl RevealPk {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
 
 /// Transaction to update the parameters of an established account
@@ -103,6 +114,7 @@ impl UpdateAccount {
         vp_code_hash: Option<Hash>,
         public_keys: Vec<common::PublicKey>,
         threshold: Option<u8>,
+        key_options: Vec<AccountKeyOptions>,
         args: GlobalArgs,
     ) -> Self {
         let update_account =
@@ -111,6 +123,7 @@ impl UpdateAccount {
                 vp_code_hash,
                 public_keys,
                 threshold,
+                key_options,
             };
 
         Self(transaction::build_tx(
@@ -140,4 +153,9 @@ impl UpdateAccount {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
@@ -5,6 +5,7 @@ use namada_core::types::address::Address;
 use namada_core::types::hash::Hash;
 use namada_core::types::key::common;
 use namada_core::types::storage::Epoch;
+use namada_core::types::token;
 
 use super::GlobalArgs;
 use crate::transaction;
@@ -26,6 +27,7 @@ impl InitProposal {
         voting_start_epoch: Epoch,
         voting_end_epoch: Epoch,
         grace_epoch: Epoch,
+        deposit: token::Amount,
         args: GlobalArgs,
     ) -> Self {
         let init_proposal =
@@ -37,6 +39,7 @@ impl InitProposal {
                 voting_start_epoch,
                 voting_end_epoch,
                 grace_epoch,
+                deposit,
             };
 
         Self(transaction::build_tx(
@@ -66,6 +69,11 @@ impl InitProposal {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
 
 /// Transaction to vote on a governance proposal
@@ -115,4 +123,9 @@ impl VoteProposal {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
@@ -57,4 +57,9 @@ impl IbcTransfer {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
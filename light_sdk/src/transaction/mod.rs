@@ -42,11 +42,11 @@ pub(in crate::transaction) fn build_tx(
     inner_tx
 }
 
-pub(in crate::transaction) fn get_sign_bytes(tx: &Tx) -> Vec<Hash> {
+pub(crate) fn get_sign_bytes(tx: &Tx) -> Vec<Hash> {
     vec![tx.raw_header_hash()]
 }
 
-pub(in crate::transaction) fn attach_raw_signatures(
+pub(crate) fn attach_raw_signatures(
     mut tx: Tx,
     signer: common::PublicKey,
     signature: common::Signature,
@@ -45,6 +45,11 @@ impl ResignSteward {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
 
 /// Transaction to update a pgf steward's commission rate
@@ -91,4 +96,9 @@ impl UpdateStewardCommission {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
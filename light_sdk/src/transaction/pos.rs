@@ -67,6 +67,11 @@ impl Bond {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
 
 /// An unbond transaction
@@ -113,6 +118,11 @@ impl Unbond {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
 
 /// Transaction to initialize a new PoS validator
@@ -177,6 +187,11 @@ impl BecomeValidator {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
 
 /// Transaction to unjail a PoS validator
@@ -212,6 +227,11 @@ impl UnjailValidator {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
 
 /// Transaction to deactivate a validator
@@ -247,6 +267,11 @@ impl DeactivateValidator {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
 
 /// Transaction to reactivate a previously deactivated validator
@@ -282,6 +307,11 @@ impl ReactivateValidator {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
 
 /// Transaction to claim PoS rewards
@@ -326,6 +356,11 @@ impl ClaimRewards {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
 
 /// Transaction to change the validator's metadata
@@ -379,6 +414,11 @@ impl ChangeMetaData {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
 
 /// Transaction to modify the validator's consensus key
@@ -424,6 +464,11 @@ impl ChangeConsensusKey {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
 
 /// Transaction to modify the validator's commission rate
@@ -465,6 +510,11 @@ impl ChangeCommission {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
 
 /// Transaction to withdraw previously unstaked funds
@@ -509,6 +559,11 @@ impl Withdraw {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
 
 /// Transaction to redelegate
@@ -557,4 +612,9 @@ impl Redelegate {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
@@ -5,7 +5,7 @@ use namada_core::types::storage::Epoch;
 use namada_core::types::transaction::{Fee, GasLimit};
 
 #[allow(missing_docs)]
-pub struct Wrapper(Tx);
+pub struct Wrapper(pub(crate) Tx);
 
 impl Wrapper {
     /// Takes a transaction and a signature and wraps them in a wrapper
@@ -61,4 +61,9 @@ impl Wrapper {
     pub fn to_bytes(&self) -> Vec<u8> {
         self.0.to_bytes()
     }
+
+    /// Gets the inner transaction
+    pub fn payload(self) -> Tx {
+        self.0
+    }
 }
@@ -1,3 +1,351 @@
-pub mod blocking {}
+//! Broadcast signed transactions to a Namada node and wait for their results.
+//!
+//! The [`blocking`] module runs the requests on its own runtime, while the
+//! [`unblocking`] module exposes the same functions as futures.
 
-pub mod unblocking {}
+use std::ops::ControlFlow;
+use std::str::FromStr;
+
+use namada_core::proto::Tx;
+use namada_core::types::transaction::ResultCode;
+use namada_sdk::control_flow::time;
+use namada_sdk::error::{EncodingError, Error, TxError};
+use namada_sdk::queries::Client;
+use namada_sdk::rpc::{self, InnerTxResult, TxEventQuery, TxResponse};
+use namada_sdk::tendermint_rpc::endpoint::broadcast::tx_sync::Response;
+use namada_sdk::tendermint_rpc::error::Error as RpcError;
+use tendermint_config::net::Address as TendermintAddress;
+use tendermint_rpc::HttpClient;
+
+/// Maximum time to wait for the results of a submitted transaction, in
+/// seconds
+pub const DEFAULT_EVENTS_MAX_WAIT_TIME_SECONDS: u64 = 60;
+
+/// The results of a transaction that made it on chain
+#[derive(Debug)]
+pub struct TxResults {
+    /// The result of the wrapper transaction
+    pub wrapper: TxResponse,
+    /// The result of the inner transaction, absent if the wrapper was
+    /// rejected
+    pub inner: Option<TxResponse>,
+}
+
+impl TxResults {
+    /// Check that the inner transaction was applied and accepted by all the
+    /// VPs
+    pub fn is_applied_and_valid(&self) -> bool {
+        match &self.inner {
+            Some(inner) => {
+                inner.code == ResultCode::Ok
+                    && matches!(
+                        inner.inner_tx_result(),
+                        InnerTxResult::Success(_)
+                    )
+            }
+            None => false,
+        }
+    }
+}
+
+pub mod blocking {
+    use tokio::runtime::Runtime;
+
+    use super::*;
+
+    /// Broadcast a signed transaction to the mempool of the node, without
+    /// waiting for it to be applied
+    pub fn broadcast_tx(
+        tendermint_addr: &str,
+        tx: &Tx,
+    ) -> Result<Response, Error> {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(unblocking::broadcast_tx(tendermint_addr, tx))
+    }
+
+    /// Broadcast a signed transaction and wait for the results of the wrapper
+    /// and of the inner transaction, for at most `max_wait_secs` seconds
+    pub fn submit_tx(
+        tendermint_addr: &str,
+        tx: &Tx,
+        max_wait_secs: u64,
+    ) -> Result<TxResults, Error> {
+        let rt = Runtime::new().unwrap();
+        rt.block_on(unblocking::submit_tx(tendermint_addr, tx, max_wait_secs))
+    }
+}
+
+pub mod unblocking {
+    use super::*;
+
+    /// Broadcast a signed transaction to the mempool of the node, without
+    /// waiting for it to be applied
+    pub async fn broadcast_tx(
+        tendermint_addr: &str,
+        tx: &Tx,
+    ) -> Result<Response, Error> {
+        let client = new_client(tendermint_addr)?;
+        broadcast(&client, tx).await
+    }
+
+    /// Broadcast a signed transaction and wait for the results of the wrapper
+    /// and of the inner transaction, for at most `max_wait_secs` seconds
+    pub async fn submit_tx(
+        tendermint_addr: &str,
+        tx: &Tx,
+        max_wait_secs: u64,
+    ) -> Result<TxResults, Error> {
+        let client = new_client(tendermint_addr)?;
+        submit(&client, tx, max_wait_secs).await
+    }
+}
+
+fn new_client(tendermint_addr: &str) -> Result<HttpClient, Error> {
+    HttpClient::new(
+        TendermintAddress::from_str(tendermint_addr)
+            .map_err(|e| Error::Other(e.to_string()))?,
+    )
+    .map_err(|e| Error::Other(e.to_string()))
+}
+
+async fn broadcast<C: Client + Sync>(
+    client: &C,
+    tx: &Tx,
+) -> Result<Response, Error> {
+    let response = client
+        .broadcast_tx_sync(tx.to_bytes())
+        .await
+        .map_err(|err| Error::from(TxError::TxBroadcast(err)))?;
+
+    if response.code == 0.into() {
+        Ok(response)
+    } else {
+        Err(Error::from(TxError::TxBroadcast(RpcError::server(
+            serde_json::to_string(&response).map_err(|err| {
+                Error::from(EncodingError::Serde(err.to_string()))
+            })?,
+        ))))
+    }
+}
+
+async fn submit<C: Client + Sync>(
+    client: &C,
+    tx: &Tx,
+    max_wait_secs: u64,
+) -> Result<TxResults, Error> {
+    // The wrapper and the inner tx are looked up by different hashes
+    let wrapper_hash = tx.header_hash().to_string();
+    let inner_hash = tx.raw_header_hash().to_string();
+
+    broadcast(client, tx).await?;
+
+    let deadline =
+        time::Instant::now() + time::Duration::from_secs(max_wait_secs);
+    let wrapper = wait_for_response(
+        client,
+        TxEventQuery::Accepted(wrapper_hash.as_str()),
+        deadline,
+    )
+    .await?;
+    let inner = if wrapper.code == ResultCode::Ok {
+        Some(
+            wait_for_response(
+                client,
+                TxEventQuery::Applied(inner_hash.as_str()),
+                deadline,
+            )
+            .await?,
+        )
+    } else {
+        None
+    };
+
+    Ok(TxResults { wrapper, inner })
+}
+
+/// Poll the node for the event matching the query until the deadline. Failed
+/// queries are retried, as the node may be catching up.
+async fn wait_for_response<C: Client + Sync>(
+    client: &C,
+    query: TxEventQuery<'_>,
+    deadline: time::Instant,
+) -> Result<TxResponse, Error> {
+    let event = time::Sleep {
+        strategy: time::LinearBackoff {
+            delta: time::Duration::from_secs(1),
+        },
+    }
+    .timeout(deadline, || async {
+        match rpc::query_tx_events(client, query).await {
+            Ok(Some(event)) => ControlFlow::Break(event),
+            Ok(None) | Err(_) => ControlFlow::Continue(()),
+        }
+    })
+    .await
+    .map_err(|_| match query {
+        TxEventQuery::Accepted(_) => Error::Tx(TxError::AcceptTimeout),
+        TxEventQuery::Applied(_) => Error::Tx(TxError::AppliedTimeout),
+    })?;
+    TxResponse::try_from(event).map_err(Error::Other)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use data_encoding::{BASE64, HEXUPPER};
+    use namada_core::types::chain::ChainId;
+    use namada_core::types::transaction::TxResult;
+    use namada_sdk::events::{Event, EventLevel, EventType};
+    use namada_sdk::tendermint_rpc::client::{MockClient, MockRequestMatcher};
+    use namada_sdk::tendermint_rpc::{Method, Request, Response as _};
+    use tokio::runtime::Runtime;
+
+    use super::*;
+
+    /// A mock RPC endpoint accepting every tx with the given code, and
+    /// answering the `accepted` and `applied` queries with the given events
+    struct MockRpc {
+        check_tx_code: u32,
+        accepted: Option<Event>,
+        applied: Option<Event>,
+    }
+
+    impl MockRequestMatcher for MockRpc {
+        fn response_for<R>(
+            &self,
+            request: R,
+        ) -> Option<Result<R::Response, RpcError>>
+        where
+            R: Request,
+        {
+            let method = request.method();
+            let request = request.into_json();
+            let result = match method {
+                Method::BroadcastTxSync => format!(
+                    r#"{{"code":{},"data":"","log":"","hash":"{}"}}"#,
+                    self.check_tx_code,
+                    HEXUPPER.encode(&[0; 32]),
+                ),
+                Method::AbciQuery => {
+                    let event = if request.contains("/shell/accepted/") {
+                        &self.accepted
+                    } else {
+                        &self.applied
+                    };
+                    let value = borsh::to_vec(event).unwrap();
+                    format!(
+                        r#"{{"response":{{"code":0,"log":"","info":"","index":"0","key":"","value":"{}","proofOps":null,"height":"1","codespace":""}}}}"#,
+                        BASE64.encode(&value),
+                    )
+                }
+                _ => return None,
+            };
+            Some(R::Response::from_string(format!(
+                r#"{{"jsonrpc":"2.0","id":"","result":{}}}"#,
+                result
+            )))
+        }
+    }
+
+    fn tx_event(
+        event_type: EventType,
+        hash: String,
+        code: ResultCode,
+    ) -> Event {
+        let mut event = Event {
+            event_type,
+            level: EventLevel::Tx,
+            attributes: HashMap::new(),
+        };
+        event["hash"] = hash;
+        event["height"] = "1".to_string();
+        event["info"] = "".to_string();
+        event["log"] = "".to_string();
+        event["code"] = code.to_string();
+        event["gas_used"] = "0".to_string();
+        event
+    }
+
+    /// Test that the results of both the wrapper and the inner tx are
+    /// returned once they are on chain.
+    #[test]
+    fn test_submit_tx() {
+        let tx = Tx::new(ChainId::default(), None);
+        let mut applied = tx_event(
+            EventType::Applied,
+            tx.raw_header_hash().to_string(),
+            ResultCode::Ok,
+        );
+        applied["inner_tx"] = TxResult::default().to_string();
+        let (client, _driver) = MockClient::new(MockRpc {
+            check_tx_code: 0,
+            accepted: Some(tx_event(
+                EventType::Accepted,
+                tx.header_hash().to_string(),
+                ResultCode::Ok,
+            )),
+            applied: Some(applied),
+        });
+
+        let results = Runtime::new()
+            .unwrap()
+            .block_on(submit(&client, &tx, 1))
+            .unwrap();
+        assert_eq!(results.wrapper.hash, tx.header_hash().to_string());
+        let inner = results.inner.as_ref().unwrap();
+        assert_eq!(inner.hash, tx.raw_header_hash().to_string());
+        assert!(results.is_applied_and_valid());
+    }
+
+    /// Test that the inner tx is not waited for if the wrapper is rejected.
+    #[test]
+    fn test_submit_tx_wrapper_rejected() {
+        let tx = Tx::new(ChainId::default(), None);
+        let (client, _driver) = MockClient::new(MockRpc {
+            check_tx_code: 0,
+            accepted: Some(tx_event(
+                EventType::Accepted,
+                tx.header_hash().to_string(),
+                ResultCode::FeeError,
+            )),
+            applied: None,
+        });
+
+        let results = Runtime::new()
+            .unwrap()
+            .block_on(submit(&client, &tx, 1))
+            .unwrap();
+        assert_eq!(results.wrapper.code, ResultCode::FeeError);
+        assert!(results.inner.is_none());
+        assert!(!results.is_applied_and_valid());
+    }
+
+    /// Test that a tx rejected from the mempool or whose results never show
+    /// up is reported as an error.
+    #[test]
+    fn test_submit_tx_errors() {
+        let tx = Tx::new(ChainId::default(), None);
+        let rt = Runtime::new().unwrap();
+
+        let (client, _driver) = MockClient::new(MockRpc {
+            check_tx_code: 1,
+            accepted: None,
+            applied: None,
+        });
+        assert!(matches!(
+            rt.block_on(submit(&client, &tx, 1)),
+            Err(Error::Tx(TxError::TxBroadcast(_)))
+        ));
+
+        let (client, _driver) = MockClient::new(MockRpc {
+            check_tx_code: 0,
+            accepted: None,
+            applied: None,
+        });
+        assert!(matches!(
+            rt.block_on(submit(&client, &tx, 0)),
+            Err(Error::Tx(TxError::AcceptTimeout))
+        ));
+    }
+}
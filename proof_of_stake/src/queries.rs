@@ -7,6 +7,7 @@ use borsh::BorshDeserialize;
 use namada_core::ledger::storage_api::collections::lazy_map::{
     NestedSubKey, SubKey,
 };
+use namada_core::ledger::storage_api::governance::StakeReader;
 use namada_core::ledger::storage_api::{self, StorageRead};
 use namada_core::types::address::Address;
 use namada_core::types::dec::Dec;
@@ -14,7 +15,10 @@ use namada_core::types::storage::Epoch;
 use namada_core::types::token;
 
 use crate::slashing::{find_validator_slashes, get_slashed_amount};
-use crate::storage::{bond_handle, read_pos_params, unbond_handle};
+use crate::storage::{
+    bond_handle, read_pos_params, read_total_stake, read_validator_stake,
+    unbond_handle,
+};
 use crate::types::{
     BondDetails, BondId, BondsAndUnbondsDetail, BondsAndUnbondsDetails, Slash,
     UnbondDetails,
@@ -455,3 +459,67 @@ fn make_unbond_details(
         slashed_amount,
     }
 }
+
+impl StakeReader for PosParams {
+    fn is_validator<S>(
+        &self,
+        storage: &S,
+        address: &Address,
+    ) -> storage_api::Result<bool>
+    where
+        S: StorageRead,
+    {
+        crate::is_validator(storage, address)
+    }
+
+    fn delegation_validators<S>(
+        &self,
+        storage: &S,
+        owner: &Address,
+    ) -> storage_api::Result<HashSet<Address>>
+    where
+        S: StorageRead,
+    {
+        find_delegation_validators(storage, owner)
+    }
+
+    fn validator_stake<S>(
+        &self,
+        storage: &S,
+        validator: &Address,
+        epoch: Epoch,
+    ) -> storage_api::Result<token::Amount>
+    where
+        S: StorageRead,
+    {
+        read_validator_stake(storage, self, validator, epoch)
+    }
+
+    fn bond_amount<S>(
+        &self,
+        storage: &S,
+        delegator: &Address,
+        validator: &Address,
+        epoch: Epoch,
+    ) -> storage_api::Result<token::Amount>
+    where
+        S: StorageRead,
+    {
+        let bond_id = BondId {
+            source: delegator.clone(),
+            validator: validator.clone(),
+        };
+        crate::bond_amount(storage, &bond_id, epoch)
+    }
+
+    fn total_stake<S>(
+        &self,
+        storage: &S,
+        epoch: Epoch,
+    ) -> storage_api::Result<token::Amount>
+    where
+        S: StorageRead,
+    {
+        read_total_stake(storage, self, epoch)
+    }
+}
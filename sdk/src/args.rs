@@ -5,15 +5,17 @@ use std::path::PathBuf;
 use std::time::Duration as StdDuration;
 
 use namada_core::ledger::governance::cli::onchain::{
-    DefaultProposal, PgfFundingProposal, PgfStewardProposal,
+    DefaultProposal, ParameterChangeProposal, PgfFundingProposal,
+    PgfStewardProposal, UpgradeProposal,
 };
+use namada_core::types::account::AccountKeyOptions;
 use namada_core::types::address::Address;
 use namada_core::types::chain::ChainId;
 use namada_core::types::dec::Dec;
 use namada_core::types::ethereum_events::EthAddress;
 use namada_core::types::keccak::KeccakHash;
 use namada_core::types::key::{common, SchemeType};
-use namada_core::types::masp::PaymentAddress;
+use namada_core::types::masp::{KeyBirthday, PaymentAddress};
 use namada_core::types::storage::Epoch;
 use namada_core::types::time::DateTimeUtc;
 use namada_core::types::transaction::GasLimit;
@@ -418,6 +420,15 @@ pub struct InitProposal<C: NamadaTypes = SdkTypes> {
     pub is_pgf_stewards: bool,
     /// Flag if proposal is of type Pgf funding
     pub is_pgf_funding: bool,
+    /// Flag if proposal is of type emergency
+    pub is_emergency: bool,
+    /// Flag if proposal is of type parameter change
+    pub is_parameter_change: bool,
+    /// Flag if proposal is of type upgrade
+    pub is_upgrade: bool,
+    /// The initial deposit of the author, the minimum proposal fund if not
+    /// set
+    pub deposit: Option<token::Amount>,
     /// Path to the tx WASM file
     pub tx_code_path: PathBuf,
 }
@@ -472,6 +483,35 @@ impl<C: NamadaTypes> InitProposal<C> {
         }
     }
 
+    /// Flag if proposal is of type emergency
+    pub fn is_emergency(self, is_emergency: bool) -> Self {
+        Self {
+            is_emergency,
+            ..self
+        }
+    }
+
+    /// Flag if proposal is of type parameter change
+    pub fn is_parameter_change(self, is_parameter_change: bool) -> Self {
+        Self {
+            is_parameter_change,
+            ..self
+        }
+    }
+
+    /// Flag if proposal is of type upgrade
+    pub fn is_upgrade(self, is_upgrade: bool) -> Self {
+        Self { is_upgrade, ..self }
+    }
+
+    /// The initial deposit of the author
+    pub fn deposit(self, deposit: token::Amount) -> Self {
+        Self {
+            deposit: Some(deposit),
+            ..self
+        }
+    }
+
     /// Path to the tx WASM file
     pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
         Self {
@@ -490,6 +530,9 @@ impl InitProposal {
         let current_epoch = rpc::query_epoch(context.client()).await?;
         let governance_parameters =
             rpc::query_governance_parameters(context.client()).await;
+        let deposit = self
+            .deposit
+            .unwrap_or(governance_parameters.min_proposal_fund);
 
         if self.is_pgf_funding {
             let proposal = PgfFundingProposal::try_from(
@@ -527,6 +570,7 @@ impl InitProposal {
                     &governance_parameters,
                     current_epoch,
                     author_balance,
+                    deposit,
                     self.tx.force,
                 )
                 .map_err(|e| {
@@ -534,6 +578,69 @@ impl InitProposal {
                 })?;
 
             tx::build_pgf_stewards_proposal(context, self, proposal).await
+        } else if self.is_parameter_change {
+            let proposal = ParameterChangeProposal::try_from(
+                self.proposal_data.as_ref(),
+            )
+            .map_err(|e| {
+                crate::error::TxError::FailedGovernaneProposalDeserialize(
+                    e.to_string(),
+                )
+            })?;
+            let nam_address = context.native_token();
+            let author_balance = rpc::get_token_balance(
+                context.client(),
+                &nam_address,
+                &proposal.proposal.author,
+            )
+            .await?;
+            let proposal = proposal
+                .validate(
+                    &governance_parameters,
+                    current_epoch,
+                    author_balance,
+                    deposit,
+                    self.tx.force,
+                )
+                .map_err(|e| {
+                    crate::error::TxError::InvalidProposal(e.to_string())
+                })?;
+
+            tx::build_parameter_change_proposal(context, self, proposal).await
+        } else if self.is_upgrade {
+            let proposal = UpgradeProposal::try_from(
+                self.proposal_data.as_ref(),
+            )
+            .map_err(|e| {
+                crate::error::TxError::FailedGovernaneProposalDeserialize(
+                    e.to_string(),
+                )
+            })?;
+            let current_height = rpc::query_block(context.client())
+                .await?
+                .map(|block| block.height)
+                .unwrap_or(storage::BlockHeight(0));
+            let nam_address = context.native_token();
+            let author_balance = rpc::get_token_balance(
+                context.client(),
+                &nam_address,
+                &proposal.proposal.author,
+            )
+            .await?;
+            let proposal = proposal
+                .validate(
+                    &governance_parameters,
+                    current_epoch,
+                    current_height,
+                    author_balance,
+                    deposit,
+                    self.tx.force,
+                )
+                .map_err(|e| {
+                    crate::error::TxError::InvalidProposal(e.to_string())
+                })?;
+
+            tx::build_upgrade_proposal(context, self, proposal).await
         } else {
             let proposal = DefaultProposal::try_from(
                 self.proposal_data.as_ref(),
@@ -555,6 +662,8 @@ impl InitProposal {
                     &governance_parameters,
                     current_epoch,
                     author_balance,
+                    deposit,
+                    self.is_emergency,
                     self.tx.force,
                 )
                 .map_err(|e| {
@@ -565,6 +674,72 @@ impl InitProposal {
     }
 }
 
+/// Transaction to top up the deposit of a proposal
+#[derive(Clone, Debug)]
+pub struct DepositProposal<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Proposal id
+    pub proposal_id: u64,
+    /// The address depositing the tokens
+    pub depositor: C::Address,
+    /// The amount of native tokens to deposit
+    pub amount: token::Amount,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for DepositProposal<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        DepositProposal {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> DepositProposal<C> {
+    /// Proposal id
+    pub fn proposal_id(self, proposal_id: u64) -> Self {
+        Self {
+            proposal_id,
+            ..self
+        }
+    }
+
+    /// The address depositing the tokens
+    pub fn depositor(self, depositor: C::Address) -> Self {
+        Self { depositor, ..self }
+    }
+
+    /// The amount of native tokens to deposit
+    pub fn amount(self, amount: token::Amount) -> Self {
+        Self { amount, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl DepositProposal {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(crate::proto::Tx, SigningTxData)> {
+        let current_epoch = rpc::query_epoch(context.client()).await?;
+        tx::build_deposit_proposal(context, self, current_epoch).await
+    }
+}
+
 /// Transaction to vote on a proposal
 #[derive(Clone, Debug)]
 pub struct VoteProposal<C: NamadaTypes = SdkTypes> {
@@ -648,6 +823,65 @@ impl VoteProposal {
     }
 }
 
+#[derive(Clone, Debug)]
+/// Vote delegation args
+pub struct DelegateVote<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// The address delegating its voting power
+    pub delegator: C::Address,
+    /// The representative voting on behalf of the delegator, or `None` to
+    /// revoke the delegation
+    pub representative: Option<C::Address>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for DelegateVote<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        DelegateVote {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> DelegateVote<C> {
+    /// The address delegating its voting power
+    pub fn delegator(self, delegator: C::Address) -> Self {
+        Self { delegator, ..self }
+    }
+
+    /// The representative voting on behalf of the delegator
+    pub fn representative(self, representative: C::Address) -> Self {
+        Self {
+            representative: Some(representative),
+            ..self
+        }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl DelegateVote {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(crate::proto::Tx, SigningTxData)> {
+        tx::build_delegate_vote(context, self).await
+    }
+}
+
 /// Transaction to initialize a new account
 #[derive(Clone, Debug)]
 pub struct TxInitAccount<C: NamadaTypes = SdkTypes> {
@@ -810,6 +1044,8 @@ pub struct TxUpdateAccount<C: NamadaTypes = SdkTypes> {
     pub public_keys: Vec<C::PublicKey>,
     /// The account threshold
     pub threshold: Option<u8>,
+    /// The weights and roles of the public keys, by key index
+    pub key_options: Vec<AccountKeyOptions>,
 }
 
 impl<C: NamadaTypes> TxBuilder<C> for TxUpdateAccount<C> {
@@ -861,6 +1097,14 @@ impl<C: NamadaTypes> TxUpdateAccount<C> {
             ..self
         }
     }
+
+    /// The weights and roles of the public keys, by key index
+    pub fn key_options(self, key_options: Vec<AccountKeyOptions>) -> Self {
+        Self {
+            key_options,
+            ..self
+        }
+    }
 }
 
 impl TxUpdateAccount {
@@ -1145,6 +1389,27 @@ pub struct QueryProtocolParameters<C: NamadaTypes = SdkTypes> {
     pub query: Query<C>,
 }
 
+/// Query the upgrade scheduled by governance
+#[derive(Clone, Debug)]
+pub struct QueryPendingUpgrade<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+}
+
+/// Query the rate limits of the ICS-20 transfers
+#[derive(Clone, Debug)]
+pub struct QueryIbcRateLimits<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+}
+
+/// Query the ERC20 whitelist of the Ethereum bridge
+#[derive(Clone, Debug)]
+pub struct QueryErc20Whitelist<C: NamadaTypes = SdkTypes> {
+    /// Common query args
+    pub query: Query<C>,
+}
+
 /// Query pgf data
 #[derive(Clone, Debug)]
 pub struct QueryPgf<C: NamadaTypes = SdkTypes> {
@@ -1554,6 +1819,81 @@ impl UpdateStewardCommission {
     }
 }
 
+#[derive(Clone, Debug)]
+/// A change to the spending policy of an account
+pub enum SpendingPolicyAction<C: NamadaTypes = SdkTypes> {
+    /// Set the policy to the given JSON encoded one, or clear it
+    SetPolicy(Option<C::Data>),
+    /// Schedule a withdrawal above the threshold of the policy
+    ScheduleWithdrawal {
+        /// The withdrawn token
+        token: C::Address,
+        /// The recipient of the withdrawal
+        target: C::Address,
+        /// The withdrawn amount
+        amount: InputAmount,
+    },
+    /// Execute the scheduled withdrawal with the given id
+    ExecuteWithdrawal(u64),
+    /// Cancel the scheduled withdrawal with the given id
+    CancelWithdrawal(u64),
+}
+
+#[derive(Clone, Debug)]
+/// Spending policy update args
+pub struct UpdateSpendingPolicy<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Address of the account whose policy is updated
+    pub owner: C::Address,
+    /// The change to the policy
+    pub action: SpendingPolicyAction<C>,
+    /// Path to the TX WASM code file
+    pub tx_code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for UpdateSpendingPolicy<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        UpdateSpendingPolicy {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> UpdateSpendingPolicy<C> {
+    /// Address of the account whose policy is updated
+    pub fn owner(self, owner: C::Address) -> Self {
+        Self { owner, ..self }
+    }
+
+    /// The change to the policy
+    pub fn action(self, action: SpendingPolicyAction<C>) -> Self {
+        Self { action, ..self }
+    }
+
+    /// Path to the TX WASM code file
+    pub fn tx_code_path(self, tx_code_path: PathBuf) -> Self {
+        Self {
+            tx_code_path,
+            ..self
+        }
+    }
+}
+
+impl UpdateSpendingPolicy {
+    /// Build a transaction from this builder
+    pub async fn build(
+        &self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(crate::proto::Tx, SigningTxData)> {
+        tx::build_spending_policy(context, self).await
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Commission rate change args
 pub struct ResignSteward<C: NamadaTypes = SdkTypes> {
@@ -1757,6 +2097,35 @@ pub struct SignTx<C: NamadaTypes = SdkTypes> {
     pub owner: C::Address,
 }
 
+#[derive(Clone, Debug)]
+/// Create a request for the signatures of a multisignature account's keys
+pub struct CreateSigningRequest<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Transaction data
+    pub tx_data: C::Data,
+    /// The account address
+    pub owner: C::Address,
+}
+
+#[derive(Clone, Debug)]
+/// Append signatures to a signing request offline
+pub struct SignSigningRequest<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Path to the signing request
+    pub request_path: PathBuf,
+}
+
+#[derive(Clone, Debug)]
+/// Submit the transaction of a signing request with the collected signatures
+pub struct SubmitSigningRequest<C: NamadaTypes = SdkTypes> {
+    /// Common tx arguments
+    pub tx: Tx<C>,
+    /// Paths to the copies of the signing request, whose signatures are merged
+    pub request_paths: Vec<PathBuf>,
+}
+
 /// Query PoS commission rate
 #[derive(Clone, Debug)]
 pub struct QueryCommissionRate<C: NamadaTypes = SdkTypes> {
@@ -2042,6 +2411,8 @@ pub struct KeyGen {
     pub unsafe_dont_encrypt: bool,
     /// BIP44 / ZIP32 derivation path
     pub derivation_path: String,
+    /// Birthday of the shielded key
+    pub birthday: Option<KeyBirthday>,
 }
 
 /// Wallet restore key and implicit address arguments
@@ -2057,10 +2428,12 @@ pub struct KeyDerive {
     pub alias_force: bool,
     /// Don't encrypt the keypair
     pub unsafe_dont_encrypt: bool,
-    /// BIP44 derivation path
+    /// BIP44 / ZIP32 derivation path
     pub derivation_path: String,
     /// Use device to generate key and address
     pub use_device: bool,
+    /// Birthday of the shielded key
+    pub birthday: Option<KeyBirthday>,
 }
 
 /// Wallet list arguments
@@ -2133,6 +2506,8 @@ pub struct KeyAddressAdd {
     pub value: String,
     /// Don't encrypt the key
     pub unsafe_dont_encrypt: bool,
+    /// Birthday of the shielded key
+    pub birthday: Option<KeyBirthday>,
 }
 
 /// Wallet key / address remove arguments
@@ -2278,6 +2653,127 @@ impl EthereumBridgePool {
     }
 }
 
+/// A request of the sender of a transfer in the Ethereum bridge pool to
+/// cancel it, or to set the epoch from which it gets refunded.
+#[derive(Clone, Debug)]
+pub struct CancelBridgePoolTransfer<C: NamadaTypes = SdkTypes> {
+    /// The args for building a tx to the bridge pool
+    pub tx: Tx<C>,
+    /// The hash of the pending transfer
+    pub transfer_hash: KeccakHash,
+    /// The epoch from which the transfer is refunded by the protocol.
+    ///
+    /// If unset, the transfer is cancelled right away.
+    pub expiry: Option<Epoch>,
+    /// Path to the tx WASM code file
+    pub code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for CancelBridgePoolTransfer<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        CancelBridgePoolTransfer {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> CancelBridgePoolTransfer<C> {
+    /// The hash of the pending transfer
+    pub fn transfer_hash(self, transfer_hash: KeccakHash) -> Self {
+        Self {
+            transfer_hash,
+            ..self
+        }
+    }
+
+    /// The epoch from which the transfer is refunded by the protocol.
+    ///
+    /// If unset, the transfer is cancelled right away.
+    pub fn expiry(self, expiry: Epoch) -> Self {
+        Self {
+            expiry: Some(expiry),
+            ..self
+        }
+    }
+
+    /// Path to the tx WASM code file
+    pub fn code_path(self, code_path: PathBuf) -> Self {
+        Self { code_path, ..self }
+    }
+}
+
+impl CancelBridgePoolTransfer {
+    /// Build a transaction from this builder
+    pub async fn build(
+        self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(crate::proto::Tx, SigningTxData)> {
+        bridge_pool::build_cancel_bridge_pool_tx(context, self).await
+    }
+}
+
+/// A request of the payer of the gas fees of a transfer in the Ethereum
+/// bridge pool to raise them.
+#[derive(Clone, Debug)]
+pub struct BumpBridgePoolTransferFee<C: NamadaTypes = SdkTypes> {
+    /// The args for building a tx to the bridge pool
+    pub tx: Tx<C>,
+    /// The hash of the pending transfer
+    pub transfer_hash: KeccakHash,
+    /// The amount of gas fees added to the transfer, in the token
+    /// of its gas fees
+    pub amount: InputAmount,
+    /// Path to the tx WASM code file
+    pub code_path: PathBuf,
+}
+
+impl<C: NamadaTypes> TxBuilder<C> for BumpBridgePoolTransferFee<C> {
+    fn tx<F>(self, func: F) -> Self
+    where
+        F: FnOnce(Tx<C>) -> Tx<C>,
+    {
+        BumpBridgePoolTransferFee {
+            tx: func(self.tx),
+            ..self
+        }
+    }
+}
+
+impl<C: NamadaTypes> BumpBridgePoolTransferFee<C> {
+    /// The hash of the pending transfer
+    pub fn transfer_hash(self, transfer_hash: KeccakHash) -> Self {
+        Self {
+            transfer_hash,
+            ..self
+        }
+    }
+
+    /// The amount of gas fees added to the transfer, in the token
+    /// of its gas fees
+    pub fn amount(self, amount: InputAmount) -> Self {
+        Self { amount, ..self }
+    }
+
+    /// Path to the tx WASM code file
+    pub fn code_path(self, code_path: PathBuf) -> Self {
+        Self { code_path, ..self }
+    }
+}
+
+impl BumpBridgePoolTransferFee {
+    /// Build a transaction from this builder
+    pub async fn build(
+        self,
+        context: &impl Namada,
+    ) -> crate::error::Result<(crate::proto::Tx, SigningTxData)> {
+        bridge_pool::build_bump_bridge_pool_fee_tx(context, self).await
+    }
+}
+
 /// Bridge pool proof arguments.
 #[derive(Debug, Clone)]
 pub struct BridgePoolProof<C: NamadaTypes = SdkTypes> {
@@ -2389,6 +2885,49 @@ pub struct ValidatorSetUpdateRelay<C: NamadaTypes = SdkTypes> {
     pub safe_mode: bool,
 }
 
+/// Relayer daemon arguments.
+#[derive(Debug, Clone)]
+pub struct RelayDaemon<C: NamadaTypes = SdkTypes> {
+    /// The query parameters.
+    pub query: Query<C>,
+    /// The Namada address for receiving fees for relaying
+    pub relayer: Address,
+    /// Bridge pool recommendations conversion rates table.
+    pub conversion_table: C::BpConversionTable,
+    /// The maximum amount of Ethereum gas to spend relaying
+    /// a batch of transfers.
+    pub max_gas: Option<u64>,
+    /// The minimum net profit, in gwei, of a batch of transfers
+    /// for it to be relayed. A negative value allows relaying
+    /// batches at a loss of up to its magnitude.
+    pub min_profit: i64,
+    /// The path to the file where the relays in flight are
+    /// persisted across restarts. If unset, they are only
+    /// kept in memory.
+    pub state_path: Option<PathBuf>,
+    /// The number of block confirmations on Ethereum.
+    pub confirmations: u64,
+    /// The Ethereum RPC endpoint.
+    pub eth_rpc_endpoint: C::EthereumAddress,
+    /// The price of Ethereum gas, during the relay calls.
+    pub gas_price: Option<u64>,
+    /// The address of the Ethereum wallet to pay the gas fees.
+    /// If unset, the default wallet is used.
+    pub eth_addr: Option<EthAddress>,
+    /// Synchronize with the network, or exit immediately,
+    /// if the Ethereum node has fallen behind.
+    pub sync: bool,
+    /// The amount of time to sleep between iterations of the
+    /// daemon that relayed nothing.
+    pub retry_dur: Option<StdDuration>,
+    /// The amount of time to sleep between iterations of the
+    /// daemon that relayed something successfully.
+    pub success_dur: Option<StdDuration>,
+    /// Safe mode overrides keyboard interrupt signals, to ensure
+    /// Ethereum transfers aren't canceled midway through.
+    pub safe_mode: bool,
+}
+
 /// IBC shielded transfer generation arguments
 #[derive(Clone, Debug)]
 pub struct GenIbcShieldedTransafer<C: NamadaTypes = SdkTypes> {
@@ -174,6 +174,9 @@ pub enum TxError {
     /// The address is not a valid steward
     #[error("The address {0} is not a valid steward.")]
     InvalidSteward(Address),
+    /// Invalid spending policy
+    #[error("Invalid spending policy: {0}")]
+    InvalidSpendingPolicy(String),
     /// Rate of epoch change too large for current epoch
     #[error(
         "New rate, {0}, is too large of a change with respect to the \
@@ -267,6 +270,12 @@ pub enum TxError {
     /// The proposal can't be found
     #[error("Proposal {0} can't be found")]
     ProposalDoesNotExist(u64),
+    /// The deposit can't be made on the proposal
+    #[error("Invalid deposit on proposal {0}: {1}")]
+    InvalidProposalDeposit(u64, String),
+    /// The voting power of the address can't be delegated
+    #[error("Invalid vote delegation: {0}")]
+    InvalidVoteDelegation(String),
     /// Updating an VP of an implicit account
     #[error(
         "A validity predicate of an implicit address cannot be directly \
@@ -299,6 +308,9 @@ pub enum TxError {
     /// Invalid owner account
     #[error("The source account {0} is not valid or doesn't exist.")]
     InvalidAccount(String),
+    /// Invalid options of the keys of an account
+    #[error("Invalid key options: {0}")]
+    InvalidKeyOptions(String),
     /// The redelegation amount is larger than the remaining bond amount
     #[error(
         "The redelegation amount is larger than the remaining bond amount. \
@@ -385,6 +397,9 @@ pub enum EthereumBridgeError {
     /// Transfer already in pool error.
     #[error("An identical transfer is already present in the Bridge pool")]
     TransferAlreadyInPool,
+    /// Error reading or writing the state of the relayer daemon.
+    #[error("Failed to access the state of the relayer: {0}")]
+    RelayerState(String),
 }
 
 /// Checks if the given error is an invalid viewing key
@@ -9,12 +9,15 @@ use borsh_ext::BorshSerializeExt;
 use ethbridge_bridge_contract::Bridge;
 use ethers::providers::Middleware;
 use futures::future::FutureExt;
-use namada_core::ledger::eth_bridge::storage::bridge_pool::get_pending_key;
+use namada_core::ledger::eth_bridge::storage::bridge_pool::{
+    get_key_from_hash, get_pending_key,
+};
 use namada_core::ledger::eth_bridge::storage::wrapped_erc20s;
 use namada_core::types::address::{Address, InternalAddress};
 use namada_core::types::eth_abi::Encode;
 use namada_core::types::eth_bridge_pool::{
-    GasFee, PendingTransfer, TransferToEthereum, TransferToEthereumKind,
+    BumpPendingTransferFee, CancelPendingTransfer, GasFee, PendingTransfer,
+    TransferToEthereum, TransferToEthereumKind,
 };
 use namada_core::types::ethereum_events::EthAddress;
 use namada_core::types::keccak::KeccakHash;
@@ -37,7 +40,9 @@ use crate::queries::{
     Client, GenBridgePoolProofReq, GenBridgePoolProofRsp, TransferToErcArgs,
     TransferToEthereumStatus, RPC,
 };
-use crate::rpc::{query_storage_value, query_wasm_code_hash, validate_amount};
+use crate::rpc::{
+    query_epoch, query_storage_value, query_wasm_code_hash, validate_amount,
+};
 use crate::signing::aux_signing_data;
 use crate::tx::prepare_tx;
 use crate::{
@@ -110,6 +115,171 @@ pub async fn build_bridge_pool_tx(
     Ok((tx, signing_data))
 }
 
+/// Craft a transaction that cancels a transfer in the Ethereum bridge pool,
+/// or sets the epoch from which it is refunded by the protocol.
+pub async fn build_cancel_bridge_pool_tx(
+    context: &impl Namada,
+    args::CancelBridgePoolTransfer {
+        tx: tx_args,
+        transfer_hash,
+        expiry,
+        code_path,
+    }: args::CancelBridgePoolTransfer,
+) -> Result<(Tx, SigningTxData), Error> {
+    let transfer: PendingTransfer = query_storage_value(
+        context.client(),
+        &get_key_from_hash(&transfer_hash),
+    )
+    .await
+    .map_err(|_| {
+        Error::Other(format!(
+            "The transfer {transfer_hash} is not in the Bridge pool"
+        ))
+    })?;
+    if let Some(expiry) = expiry {
+        let current_epoch = query_epoch(context.client()).await?;
+        if expiry <= current_epoch {
+            edisplay_line!(
+                context.io(),
+                "The expiry {expiry} of the transfer must be after the \
+                 current epoch {current_epoch}."
+            );
+            if !tx_args.force {
+                return Err(Error::Other(
+                    "The expiry of the transfer must be in the future".into(),
+                ));
+            }
+        }
+    }
+    let sender = transfer.transfer.sender;
+
+    let (tx_code_hash, signing_data) = futures::try_join!(
+        query_wasm_code_hash(context, code_path.to_string_lossy()),
+        aux_signing_data(
+            context,
+            &tx_args,
+            // token owner
+            Some(sender.clone()),
+            // tx signer
+            Some(sender),
+        ),
+    )?;
+
+    let chain_id = tx_args
+        .chain_id
+        .clone()
+        .ok_or_else(|| Error::Other("No chain id available".into()))?;
+
+    let mut tx = Tx::new(chain_id, tx_args.expiration);
+    tx.add_code_from_hash(
+        tx_code_hash,
+        Some(code_path.to_string_lossy().into_owned()),
+    )
+    .add_data(CancelPendingTransfer {
+        transfer_hash,
+        expiry,
+    });
+
+    prepare_tx(
+        context,
+        &tx_args,
+        &mut tx,
+        signing_data.fee_payer.clone(),
+        None,
+    )
+    .await?;
+
+    Ok((tx, signing_data))
+}
+
+/// Craft a transaction that raises the gas fees of a transfer in the
+/// Ethereum bridge pool, keeping its hash.
+pub async fn build_bump_bridge_pool_fee_tx(
+    context: &impl Namada,
+    args::BumpBridgePoolTransferFee {
+        tx: tx_args,
+        transfer_hash,
+        amount,
+        code_path,
+    }: args::BumpBridgePoolTransferFee,
+) -> Result<(Tx, SigningTxData), Error> {
+    let transfer: PendingTransfer = query_storage_value(
+        context.client(),
+        &get_key_from_hash(&transfer_hash),
+    )
+    .await
+    .map_err(|_| {
+        Error::Other(format!(
+            "The transfer {transfer_hash} is not in the Bridge pool"
+        ))
+    })?;
+    let GasFee { token, payer, .. } = transfer.gas_fee;
+    let denominated = validate_amount(context, amount, &token, tx_args.force)
+        .await
+        .map_err(|e| {
+            Error::Other(format!(
+                "Failed to validate Bridge pool fee bump: {e}"
+            ))
+        })?;
+    let amount = denominated.amount();
+    if amount.is_zero() {
+        return Err(Error::Other(
+            "The gas fees of the transfer must be bumped by a positive amount"
+                .into(),
+        ));
+    }
+    if !tx_args.force {
+        let balance: Amount =
+            query_storage_value(context.client(), &balance_key(&token, &payer))
+                .await?;
+        if balance.checked_sub(amount).is_none() {
+            return Err(Error::Tx(TxError::NegativeBalanceAfterTransfer(
+                Box::new(payer),
+                denominated.to_string(),
+                Box::new(token),
+            )));
+        }
+    }
+
+    let (tx_code_hash, signing_data) = futures::try_join!(
+        query_wasm_code_hash(context, code_path.to_string_lossy()),
+        aux_signing_data(
+            context,
+            &tx_args,
+            // token owner
+            Some(payer.clone()),
+            // tx signer
+            Some(payer),
+        ),
+    )?;
+
+    let chain_id = tx_args
+        .chain_id
+        .clone()
+        .ok_or_else(|| Error::Other("No chain id available".into()))?;
+
+    let mut tx = Tx::new(chain_id, tx_args.expiration);
+    tx.add_code_from_hash(
+        tx_code_hash,
+        Some(code_path.to_string_lossy().into_owned()),
+    )
+    .add_data(BumpPendingTransferFee {
+        transfer_hash,
+        amount,
+    });
+
+    prepare_tx(
+        context,
+        &tx_args,
+        &mut tx,
+        signing_data.fee_payer.clone(),
+        None,
+    )
+    .await?;
+
+    Ok((tx, signing_data))
+}
+
 /// Perform client validation checks on a Bridge pool transfer.
 #[allow(clippy::too_many_arguments)]
 async fn validate_bridge_pool_tx(
@@ -804,18 +974,18 @@ mod recommendations {
     /// Batch of recommended transfers to Ethereum that generate
     /// a profit after a relay operation.
     #[derive(Debug, Eq, PartialEq)]
-    struct RecommendedBatch {
+    pub(crate) struct RecommendedBatch {
         /// Hashes of the recommended transfers to be relayed.
-        transfer_hashes: Vec<String>,
+        pub(crate) transfer_hashes: Vec<String>,
         /// Estimate of the total fees, measured in gwei, that will be paid
         /// on Ethereum.
-        ethereum_gas_fees: Uint,
+        pub(crate) ethereum_gas_fees: Uint,
         /// Net profitt in gwei, based on the conversion rates provided
         /// to the algorithm.
-        net_profit: I256,
+        pub(crate) net_profit: I256,
         /// Gas fees paid by the transfers considered for relaying,
         /// paid in various token types.
-        bridge_pool_gas_fees: HashMap<String, Uint>,
+        pub(crate) bridge_pool_gas_fees: HashMap<String, Uint>,
     }
 
     /// Recommend the most economical batch of transfers to relay based
@@ -825,6 +995,60 @@ mod recommendations {
         context: &impl Namada,
         args: args::RecommendBatch,
     ) -> Result<(), Error> {
+        find_recommended_batch(
+            context,
+            &args.conversion_table,
+            args.max_gas,
+            args.gas,
+        )
+        .await?
+        .map(
+            |RecommendedBatch {
+                 transfer_hashes,
+                 ethereum_gas_fees,
+                 net_profit,
+                 bridge_pool_gas_fees,
+             }| {
+                display_line!(
+                    context.io(),
+                    "Recommended batch: {transfer_hashes:#?}"
+                );
+                display_line!(
+                    context.io(),
+                    "Estimated Ethereum transaction gas (in gwei): \
+                     {ethereum_gas_fees}",
+                );
+                display_line!(
+                    context.io(),
+                    "Estimated net profit (in gwei): {net_profit}"
+                );
+                display_line!(
+                    context.io(),
+                    "Total fees: {bridge_pool_gas_fees:#?}"
+                );
+            },
+        )
+        .unwrap_or_else(|| {
+            display_line!(
+                context.io(),
+                "Unable to find a recommendation satisfying the input \
+                 parameters."
+            );
+        });
+
+        Ok(())
+    }
+
+    /// Find the most economical batch of transfers to relay, given
+    /// the conversion rates of the gas fee tokens to gwei, the maximum
+    /// Ethereum gas to spend and the maximum net cost the relayer is
+    /// willing to pay (in gwei).
+    pub(crate) async fn find_recommended_batch(
+        context: &impl Namada,
+        conversion_table: &HashMap<Address, args::BpConversionTableEntry>,
+        max_gas: Option<u64>,
+        max_cost: Option<u64>,
+    ) -> Result<Option<RecommendedBatch>, Error> {
         // get transfers that can already been relayed but are awaiting a quorum
         // of backing votes.
         let in_progress = RPC
@@ -926,61 +1150,40 @@ mod recommendations {
             * signature_checks(voting_powers, &bp_root.signatures)
             + valset_fee() * valset_size;
 
+        // the relayers are paid the bumped fees of the transfers as well
+        let fee_bumps = RPC
+            .shell()
+            .eth_bridge()
+            .read_bridge_pool_fee_bumps(context.client())
+            .await
+            .map_err(|e| {
+                Error::EthereumBridge(EthereumBridgeError::ReadBridgePool(
+                    e.to_string(),
+                ))
+            })?;
+        let mut signed_pool =
+            query_signed_bridge_pool(context.client(), context.io()).await?;
+        add_fee_bumps(&mut signed_pool, &fee_bumps);
+
         // we don't recommend transfers that have already been relayed
         let eligible = generate_eligible(
             context.io(),
-            &args.conversion_table,
+            conversion_table,
             &in_progress,
-            query_signed_bridge_pool(context.client(), context.io()).await?,
+            signed_pool,
         )?;
 
-        let max_gas =
-            args.max_gas.map(Uint::from_u64).unwrap_or(uint::MAX_VALUE);
-        let max_cost = args.gas.map(I256::from).unwrap_or_default();
+        let max_gas = max_gas.map(Uint::from_u64).unwrap_or(uint::MAX_VALUE);
+        let max_cost = max_cost.map(I256::from).unwrap_or_default();
 
         generate_recommendations(
             context.io(),
             eligible,
-            &args.conversion_table,
+            conversion_table,
             validator_gas,
             max_gas,
             max_cost,
-        )?
-        .map(
-            |RecommendedBatch {
-                 transfer_hashes,
-                 ethereum_gas_fees,
-                 net_profit,
-                 bridge_pool_gas_fees,
-             }| {
-                display_line!(
-                    context.io(),
-                    "Recommended batch: {transfer_hashes:#?}"
-                );
-                display_line!(
-                    context.io(),
-                    "Estimated Ethereum transaction gas (in gwei): \
-                     {ethereum_gas_fees}",
-                );
-                display_line!(
-                    context.io(),
-                    "Estimated net profit (in gwei): {net_profit}"
-                );
-                display_line!(
-                    context.io(),
-                    "Total fees: {bridge_pool_gas_fees:#?}"
-                );
-            },
         )
-        .unwrap_or_else(|| {
-            display_line!(
-                context.io(),
-                "Unable to find a recommendation satisfying the input \
-                 parameters."
-            );
-        });
-
-        Ok(())
     }
 
     /// Given an ordered list of signatures, figure out the size of the first
@@ -1020,6 +1223,19 @@ mod recommendations {
         )
     }
 
+    /// Add the bumped fees of the transfers to their gas fees. The
+    /// transfers are still indexed by their original hashes.
+    fn add_fee_bumps(
+        pool: &mut HashMap<String, PendingTransfer>,
+        fee_bumps: &HashMap<KeccakHash, Amount>,
+    ) {
+        for (hash, fee_bump) in fee_bumps {
+            if let Some(transfer) = pool.get_mut(&hash.to_string()) {
+                transfer.gas_fee.amount += *fee_bump;
+            }
+        }
+    }
+
     /// Generate eligible recommendations.
     fn generate_eligible<IO: Io>(
         io: &IO,
@@ -1355,6 +1571,36 @@ mod recommendations {
             });
         }
 
+        /// Test that the bumped fees of a transfer lower its cost,
+        /// while it is still recommended by its original hash.
+        #[test]
+        fn test_generate_eligible_with_fee_bump() {
+            test_generate_eligible_aux(|mut ctx| {
+                ctx.add_eth_to_conversion_table();
+                let hash = ctx.pending.keccak256();
+                ctx.signed_pool
+                    .insert(hash.to_string(), ctx.pending.clone());
+                let fee_bump = Amount::from(1_000_000_000_u64);
+                add_fee_bumps(
+                    ctx.signed_pool,
+                    &HashMap::from([
+                        (hash.clone(), fee_bump),
+                        // not in the signed pool
+                        (KeccakHash([0; 32]), fee_bump),
+                    ]),
+                );
+                let mut bumped = ctx.pending.clone();
+                bumped.gas_fee.amount += fee_bump;
+                ctx.expected_eligible.push(EligibleRecommendation {
+                    transfer_hash: hash.to_string(),
+                    cost: transfer_fee()
+                        - I256::try_from(bumped.gas_fee.amount)
+                            .expect("Test failed"),
+                    pending_transfer: bumped,
+                });
+            });
+        }
+
         /// Test that a transfer is not recommended if its gas
         /// token is not found in the conversion table.
         #[test]
@@ -1611,3 +1857,4 @@ mod recommendations {
 }
 
 pub use recommendations::recommend_batch;
+pub(crate) use recommendations::{find_recommended_batch, RecommendedBatch};
@@ -0,0 +1,1158 @@
+//! Relayer daemon SDK functionality.
+//!
+//! The daemon watches the signed Bridge pool roots and the validator
+//! set updates in Namada, and relays them to Ethereum as soon as they
+//! become available. Batches of transfers are picked with the same
+//! algorithm as [`super::bridge_pool::recommend_batch`], and are only
+//! relayed if they satisfy the profitability policy of the relayer.
+//!
+//! Relays submitted to Ethereum are persisted to disk, such that a
+//! restarted daemon does not relay the same batch or validator set
+//! update twice, while the original relay is still pending. A relay
+//! is persisted before it is sent, along with the nonce of the
+//! Ethereum account of the relayer. If the daemon stops before the
+//! hash of the relay is persisted, the account nonce tells whether
+//! the relay was sent, in which case the daemon waits on it, or not,
+//! in which case it is relayed again with the same account nonce,
+//! such that it can never be duplicated. The account nonce can only
+//! be pinned if the sender of the relay is known, i.e. if an Ethereum
+//! address was given to the daemon or the Ethereum client signs with
+//! a default account; otherwise, the relay is sent again, and a
+//! duplicate is rejected by the nonce checks of the Bridge contract,
+//! at the cost of its gas fees.
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::future::Future;
+use std::path::Path;
+use std::sync::Arc;
+use std::task::Poll;
+
+use borsh_ext::BorshSerializeExt;
+use ethbridge_bridge_contract::Bridge;
+use ethers::providers::Middleware;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{Address, BlockNumber, TxHash, U256};
+use futures::future::{self, FutureExt};
+use itertools::Either;
+use namada_core::types::keccak::KeccakHash;
+use namada_core::types::storage::Epoch;
+use namada_core::types::uint::I256;
+use serde::{Deserialize, Serialize};
+
+use super::bridge_pool::{find_recommended_batch, RecommendedBatch};
+use super::validator_set::{validator_set_update_call, GetStatus};
+use super::{block_on_eth_sync, eth_sync_or, eth_sync_or_exit, BlockOnEthSync};
+use crate::control_flow::install_shutdown_signal;
+use crate::control_flow::time::{self, Duration, Instant};
+use crate::error::{EncodingError, Error, EthereumBridgeError, QueryError};
+use crate::eth_bridge::ethers::abi::AbiDecode;
+use crate::queries::{Client, GenBridgePoolProofReq, TransferToErcArgs, RPC};
+use crate::{args, Namada};
+
+/// A batch of transfers relayed from the Bridge pool.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchRelay {
+    /// The Bridge pool nonce the batch was relayed at.
+    pub nonce: U256,
+    /// The hashes of the relayed transfers.
+    pub transfers: Vec<KeccakHash>,
+    /// The hash of the Ethereum transaction of the relay, unknown
+    /// until the relay has been sent.
+    pub tx_hash: Option<TxHash>,
+    /// The nonce of the Ethereum account the relay was sent from,
+    /// if the sender of the relay is known.
+    #[serde(default)]
+    pub eth_nonce: Option<U256>,
+}
+
+/// A relayed validator set update.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidatorSetRelay {
+    /// The epoch of the relayed validator set.
+    pub epoch: Epoch,
+    /// The hash of the Ethereum transaction of the relay, unknown
+    /// until the relay has been sent.
+    pub tx_hash: Option<TxHash>,
+    /// The nonce of the Ethereum account the relay was sent from,
+    /// if the sender of the relay is known.
+    #[serde(default)]
+    pub eth_nonce: Option<U256>,
+}
+
+/// The state of the relayer daemon, persisted across restarts.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelayerState {
+    /// The last batch of transfers relayed by the daemon.
+    pub bridge_pool: Option<BatchRelay>,
+    /// The last validator set update relayed by the daemon.
+    pub validator_set: Option<ValidatorSetRelay>,
+}
+
+impl RelayerState {
+    /// Load the state of the daemon from the given path. If no state
+    /// has been persisted yet, an empty state is returned.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let bytes = match std::fs::read(path) {
+            Ok(bytes) => bytes,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(Self::default());
+            }
+            Err(err) => {
+                return Err(Error::EthereumBridge(
+                    EthereumBridgeError::RelayerState(format!(
+                        "Failed to read {}: {err}",
+                        path.display()
+                    )),
+                ));
+            }
+        };
+        serde_json::from_slice(&bytes).map_err(|err| {
+            Error::EthereumBridge(EthereumBridgeError::RelayerState(format!(
+                "Failed to decode {}: {err}",
+                path.display()
+            )))
+        })
+    }
+
+    /// Persist the state of the daemon to the given path.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let bytes = serde_json::to_vec_pretty(self)
+            .map_err(|err| EncodingError::Serde(err.to_string()))?;
+        // write to a temporary file first, such that the state
+        // is not corrupted if the daemon is killed midway through
+        let tmp_path = path.with_extension("tmp");
+        std::fs::write(&tmp_path, bytes)
+            .and_then(|()| std::fs::rename(&tmp_path, path))
+            .map_err(|err| {
+                Error::EthereumBridge(EthereumBridgeError::RelayerState(
+                    format!("Failed to write {}: {err}", path.display()),
+                ))
+            })
+    }
+}
+
+/// The status of a relay submitted to Ethereum.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum RelayStatus {
+    /// The relay has yet to be included in a block.
+    Pending,
+    /// The relay was included in a block, and succeeded.
+    Succeeded,
+    /// The relay was included in a block, but reverted.
+    Failed,
+    /// The Ethereum node no longer knows about the relay.
+    Dropped,
+}
+
+impl RelayStatus {
+    /// Check if a relay is still waiting to be included in a block.
+    #[inline]
+    fn is_pending(self) -> bool {
+        matches!(self, RelayStatus::Pending)
+    }
+}
+
+/// Query the status of a relay submitted to Ethereum.
+async fn relay_status<E>(
+    eth_client: &E,
+    tx_hash: TxHash,
+) -> Result<RelayStatus, Error>
+where
+    E: Middleware,
+{
+    let receipt =
+        eth_client
+            .get_transaction_receipt(tx_hash)
+            .await
+            .map_err(|err| {
+                Error::EthereumBridge(EthereumBridgeError::Rpc(err.to_string()))
+            })?;
+    if let Some(receipt) = receipt {
+        return Ok(if receipt.is_successful() {
+            RelayStatus::Succeeded
+        } else {
+            RelayStatus::Failed
+        });
+    }
+    let transaction =
+        eth_client.get_transaction(tx_hash).await.map_err(|err| {
+            Error::EthereumBridge(EthereumBridgeError::Rpc(err.to_string()))
+        })?;
+    Ok(if transaction.is_some() {
+        RelayStatus::Pending
+    } else {
+        RelayStatus::Dropped
+    })
+}
+
+/// What the daemon should do about a batch or a validator set update,
+/// given the relay of it persisted earlier, if any.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum NextRelay {
+    /// Wait on a previous relay, which is still pending.
+    Wait,
+    /// Relay to Ethereum, reusing the given nonce of the Ethereum
+    /// account of the relayer, if any.
+    Relay { eth_nonce: Option<U256> },
+}
+
+/// Query the nonce of an Ethereum account, as of the given block.
+async fn account_nonce<E>(
+    eth_client: &E,
+    account: Address,
+    block: BlockNumber,
+) -> Result<U256, Error>
+where
+    E: Middleware,
+{
+    eth_client
+        .get_transaction_count(account, Some(block.into()))
+        .await
+        .map_err(|err| {
+            Error::EthereumBridge(EthereumBridgeError::Rpc(err.to_string()))
+        })
+}
+
+/// Decide what to do about a previous relay of the same batch or
+/// validator set update, possibly submitted by an earlier run of
+/// the daemon.
+async fn next_relay<E>(
+    eth_client: &E,
+    sender: Option<Address>,
+    tx_hash: Option<TxHash>,
+    eth_nonce: Option<U256>,
+) -> Result<NextRelay, Error>
+where
+    E: Middleware,
+{
+    if let Some(tx_hash) = tx_hash {
+        let status = relay_status(eth_client, tx_hash).await?;
+        if status.is_pending() {
+            tracing::info!(?tx_hash, "Waiting on a previous relay");
+            return Ok(NextRelay::Wait);
+        }
+        tracing::warn!(
+            ?tx_hash,
+            ?status,
+            "A previous relay did not go through, relaying it again"
+        );
+        return Ok(NextRelay::Relay { eth_nonce: None });
+    }
+
+    // the daemon stopped before persisting the hash of the
+    // relay, which may or may not have been sent
+    let (Some(sender), Some(eth_nonce)) = (sender, eth_nonce) else {
+        tracing::warn!(
+            "A previous relay may have been sent before its hash was \
+             persisted, relaying it again"
+        );
+        return Ok(NextRelay::Relay { eth_nonce: None });
+    };
+    let mined_nonce =
+        account_nonce(eth_client, sender, BlockNumber::Latest).await?;
+    if mined_nonce > eth_nonce {
+        // had the relay succeeded, it would no longer be needed
+        tracing::warn!(
+            %eth_nonce,
+            "The account nonce of a previous relay was used by a mined \
+             transaction, relaying it again"
+        );
+        return Ok(NextRelay::Relay { eth_nonce: None });
+    }
+    let pending_nonce =
+        account_nonce(eth_client, sender, BlockNumber::Pending).await?;
+    if pending_nonce > eth_nonce {
+        tracing::info!(%eth_nonce, "Waiting on a previous relay");
+        return Ok(NextRelay::Wait);
+    }
+    tracing::warn!(
+        %eth_nonce,
+        "A previous relay was not sent, relaying it again"
+    );
+    Ok(NextRelay::Relay {
+        eth_nonce: Some(eth_nonce),
+    })
+}
+
+/// Decide what to do about the Bridge pool batch at the given nonce
+/// of the Bridge contract. Relays persisted at an older nonce are
+/// ignored, since their batch has already been accepted.
+async fn next_batch_relay<E>(
+    eth_client: &E,
+    sender: Option<Address>,
+    state: &RelayerState,
+    contract_nonce: U256,
+) -> Result<NextRelay, Error>
+where
+    E: Middleware,
+{
+    match state
+        .bridge_pool
+        .as_ref()
+        .filter(|relay| relay.nonce == contract_nonce)
+    {
+        Some(relay) => {
+            tracing::debug!(
+                nonce = %relay.nonce,
+                "Found a previous relay of a Bridge pool batch"
+            );
+            next_relay(eth_client, sender, relay.tx_hash, relay.eth_nonce).await
+        }
+        None => Ok(NextRelay::Relay { eth_nonce: None }),
+    }
+}
+
+/// Decide what to do about the validator set update of the given
+/// epoch. Relays persisted for another epoch are ignored.
+async fn next_validator_set_relay<E>(
+    eth_client: &E,
+    sender: Option<Address>,
+    state: &RelayerState,
+    epoch: Epoch,
+) -> Result<NextRelay, Error>
+where
+    E: Middleware,
+{
+    match state
+        .validator_set
+        .as_ref()
+        .filter(|relay| relay.epoch == epoch)
+    {
+        Some(relay) => {
+            tracing::debug!(
+                epoch = ?relay.epoch,
+                "Found a previous relay of the validator set update"
+            );
+            next_relay(eth_client, sender, relay.tx_hash, relay.eth_nonce).await
+        }
+        None => Ok(NextRelay::Relay { eth_nonce: None }),
+    }
+}
+
+/// The Ethereum account relays are sent from, if known.
+fn relay_sender<E>(eth_client: &E, args: &args::RelayDaemon) -> Option<Address>
+where
+    E: Middleware,
+{
+    args.eth_addr
+        .map(Into::into)
+        .or_else(|| eth_client.default_sender())
+}
+
+/// Pin the nonce of the Ethereum account of the relayer in a relay,
+/// reusing the given nonce if any, or the next one of the account
+/// otherwise.
+///
+/// Returns the pinned nonce, or `None` if the sender of the relay
+/// is not known, in which case the nonce is left to the Ethereum
+/// node to fill in.
+async fn pin_relay_nonce<E>(
+    eth_client: &E,
+    sender: Option<Address>,
+    tx: &mut TypedTransaction,
+    eth_nonce: Option<U256>,
+) -> Result<Option<U256>, Error>
+where
+    E: Middleware,
+{
+    let Some(sender) = sender else {
+        return Ok(None);
+    };
+    let eth_nonce = match eth_nonce {
+        Some(eth_nonce) => eth_nonce,
+        None => account_nonce(eth_client, sender, BlockNumber::Pending).await?,
+    };
+    tx.set_nonce(eth_nonce);
+    Ok(Some(eth_nonce))
+}
+
+/// Pick the batch of transfers to relay out of the recommended one,
+/// if it satisfies the profitability policy of the relayer.
+fn relayable_batch(
+    recommendation: Option<RecommendedBatch>,
+    min_profit: i64,
+) -> Option<RecommendedBatch> {
+    let Some(batch) = recommendation else {
+        tracing::debug!("No batch of transfers is worth relaying");
+        return None;
+    };
+    if !satisfies_policy(&batch, min_profit) {
+        tracing::info!(
+            net_profit = %batch.net_profit,
+            min_profit,
+            "Skipping a batch of transfers below the minimum profit"
+        );
+        return None;
+    }
+    Some(batch)
+}
+
+/// Check whether a batch of transfers satisfies the profitability
+/// policy of the relayer.
+fn satisfies_policy(batch: &RecommendedBatch, min_profit: i64) -> bool {
+    batch.net_profit >= I256::from(min_profit)
+}
+
+/// The maximum net cost of a relay, in gwei, that the relayer
+/// is willing to pay, given the minimum net profit it expects.
+fn max_relay_cost(min_profit: i64) -> Option<u64> {
+    (min_profit < 0).then(|| min_profit.unsigned_abs())
+}
+
+/// Run the relayer daemon, which relays signed Bridge pool roots
+/// and validator set updates to Ethereum, until a shutdown signal
+/// is received.
+pub async fn run_relayer_daemon<E>(
+    context: &impl Namada,
+    eth_client: Arc<E>,
+    args: args::RelayDaemon,
+) -> Result<(), Error>
+where
+    E: Middleware,
+    E::Error: std::fmt::Debug + std::fmt::Display,
+{
+    let mut shutdown_receiver = args.safe_mode.then(install_shutdown_signal);
+
+    if args.sync {
+        block_on_eth_sync(
+            &*eth_client,
+            context.io(),
+            BlockOnEthSync {
+                deadline: Instant::now() + Duration::from_secs(60),
+                delta_sleep: Duration::from_secs(1),
+            },
+        )
+        .await?;
+    } else {
+        eth_sync_or_exit(&*eth_client, context.io()).await?;
+    }
+
+    relayer_daemon_loop(context, eth_client, &args, &mut shutdown_receiver)
+        .await
+}
+
+async fn relayer_daemon_loop<E, F>(
+    context: &impl Namada,
+    eth_client: Arc<E>,
+    args: &args::RelayDaemon,
+    shutdown_receiver: &mut Option<F>,
+) -> Result<(), Error>
+where
+    E: Middleware,
+    E::Error: std::fmt::Debug + std::fmt::Display,
+    F: Future<Output = ()> + Unpin,
+{
+    const DEFAULT_RETRY_DURATION: Duration = Duration::from_secs(1);
+    const DEFAULT_SUCCESS_DURATION: Duration = Duration::from_secs(10);
+
+    let retry_duration = args.retry_dur.unwrap_or(DEFAULT_RETRY_DURATION);
+    let success_duration = args.success_dur.unwrap_or(DEFAULT_SUCCESS_DURATION);
+
+    let mut state = match &args.state_path {
+        Some(path) => RelayerState::load(path)?,
+        None => RelayerState::default(),
+    };
+    let mut last_relay_succeeded = false;
+
+    tracing::info!(?state, "The relayer daemon has started");
+
+    loop {
+        let should_exit = if let Some(fut) = shutdown_receiver.as_mut() {
+            let fut = future::poll_fn(|cx| match fut.poll_unpin(cx) {
+                Poll::Pending => Poll::Ready(false),
+                Poll::Ready(_) => Poll::Ready(true),
+            });
+            futures::pin_mut!(fut);
+            fut.as_mut().await
+        } else {
+            false
+        };
+
+        if should_exit {
+            tracing::info!("The relayer daemon is shutting down");
+            return Ok(());
+        }
+
+        let sleep_for = if last_relay_succeeded {
+            success_duration
+        } else {
+            retry_duration
+        };
+
+        tracing::debug!(?sleep_for, "Sleeping");
+        time::sleep(sleep_for).await;
+
+        let is_synchronized = matches!(
+            eth_sync_or(&*eth_client, context.io(), || ()).await,
+            Ok(Either::Right(()))
+        );
+        if !is_synchronized {
+            tracing::debug!("The Ethereum node is synchronizing");
+            last_relay_succeeded = false;
+            continue;
+        }
+
+        // validator set updates are relayed first, since Bridge pool
+        // proofs are signed by the latest validator set in Namada
+        let valset_result = relay_validator_set_update(
+            context.client(),
+            &eth_client,
+            args,
+            &mut state,
+        )
+        .await;
+        let bp_result =
+            relay_bridge_pool_batch(context, &eth_client, args, &mut state)
+                .await;
+
+        last_relay_succeeded = false;
+        for result in [valset_result, bp_result] {
+            match result {
+                Ok(relayed) => last_relay_succeeded |= relayed,
+                // we can't keep track of the relays in flight
+                // without persisting them, so we must exit
+                Err(
+                    err @ Error::EthereumBridge(
+                        EthereumBridgeError::RelayerState(_),
+                    ),
+                ) => return Err(err),
+                Err(err) => {
+                    tracing::error!(%err, "An error occurred during the relay");
+                }
+            }
+        }
+    }
+}
+
+/// Persist the state of the daemon, if a path was provided.
+fn persist_state(
+    args: &args::RelayDaemon,
+    state: &RelayerState,
+) -> Result<(), Error> {
+    args.state_path
+        .as_ref()
+        .map_or(Ok(()), |path| state.save(path))
+}
+
+/// Fetch the Bridge contract from Namada.
+async fn get_bridge_contract<C, E>(
+    client: &C,
+    eth_client: &Arc<E>,
+) -> Result<Bridge<E>, Error>
+where
+    C: Client + Sync,
+    E: Middleware,
+{
+    let bridge_contract = RPC
+        .shell()
+        .eth_bridge()
+        .read_bridge_contract(client)
+        .await
+        .map_err(|err| {
+            Error::EthereumBridge(EthereumBridgeError::RetrieveContract(
+                err.to_string(),
+            ))
+        })?;
+    Ok(Bridge::new(bridge_contract.address, Arc::clone(eth_client)))
+}
+
+/// Relay the next validator set update to Ethereum, if Namada has
+/// advanced past the epoch of the validator set in the Bridge contract.
+///
+/// Returns whether a validator set update was successfully relayed.
+async fn relay_validator_set_update<C, E>(
+    client: &C,
+    eth_client: &Arc<E>,
+    args: &args::RelayDaemon,
+    state: &mut RelayerState,
+) -> Result<bool, Error>
+where
+    C: Client + Sync,
+    E: Middleware,
+    E::Error: std::fmt::Debug + std::fmt::Display,
+{
+    // we could be racing against governance updates,
+    // so it is best to always fetch the latest Bridge
+    // contract address
+    let bridge = get_bridge_contract(client, eth_client).await?;
+    let bridge_epoch = bridge
+        .validator_set_nonce()
+        .call()
+        .await
+        .map(|nonce| Epoch(nonce.as_u64()))
+        .map_err(|err| {
+            Error::EthereumBridge(EthereumBridgeError::ContractCall(
+                err.to_string(),
+            ))
+        })?;
+    let namada_epoch =
+        RPC.shell().epoch(client).await.map_err(|err| {
+            Error::Query(QueryError::General(err.to_string()))
+        })?;
+
+    // NB: a namada epoch should always be one behind the nonce
+    // in the bridge contract, for the latter to be considered
+    // up to date
+    let epoch_to_relay = bridge_epoch.next();
+    match bridge_epoch.cmp(&namada_epoch.next()) {
+        Ordering::Equal => {
+            tracing::debug!(
+                ?namada_epoch,
+                ?bridge_epoch,
+                "Nothing to do, since the validator set in the Bridge \
+                 contract is up to date"
+            );
+            return Ok(false);
+        }
+        Ordering::Greater => {
+            tracing::error!(
+                ?namada_epoch,
+                ?bridge_epoch,
+                "The Bridge contract is ahead of Namada!"
+            );
+            return Ok(false);
+        }
+        Ordering::Less => {}
+    }
+
+    let sender = relay_sender(&**eth_client, args);
+    let eth_nonce = match next_validator_set_relay(
+        &**eth_client,
+        sender,
+        state,
+        epoch_to_relay,
+    )
+    .await?
+    {
+        NextRelay::Wait => return Ok(false),
+        NextRelay::Relay { eth_nonce } => eth_nonce,
+    };
+
+    let (_, mut relay_op) = validator_set_update_call(
+        client,
+        Arc::clone(eth_client),
+        epoch_to_relay,
+    )
+    .await?;
+    if let Some(gas_price) = args.gas_price {
+        relay_op.tx.set_gas_price(gas_price);
+    }
+    if let Some(eth_addr) = args.eth_addr {
+        relay_op.tx.set_from(eth_addr.into());
+    }
+    let eth_nonce =
+        pin_relay_nonce(&**eth_client, sender, &mut relay_op.tx, eth_nonce)
+            .await?;
+
+    // persist the relay before sending it, in case the
+    // daemon stops before its hash is persisted
+    state.validator_set = Some(ValidatorSetRelay {
+        epoch: epoch_to_relay,
+        tx_hash: None,
+        eth_nonce,
+    });
+    persist_state(args, state)?;
+
+    let pending_tx = relay_op.send().await.map_err(|err| {
+        Error::EthereumBridge(EthereumBridgeError::ContractCall(
+            err.to_string(),
+        ))
+    })?;
+    let tx_hash = pending_tx.tx_hash();
+    if let Some(relay) = state.validator_set.as_mut() {
+        relay.tx_hash = Some(tx_hash);
+    }
+    persist_state(args, state)?;
+    tracing::info!(
+        epoch = ?epoch_to_relay,
+        ?tx_hash,
+        "Relayed a validator set update"
+    );
+
+    let receipt = pending_tx
+        .confirmations(args.confirmations as usize)
+        .await
+        .map_err(|err| {
+            Error::EthereumBridge(EthereumBridgeError::Rpc(err.to_string()))
+        })?;
+    let succeeded = receipt.is_successful();
+    if succeeded {
+        tracing::info!(?receipt, "Updated the validator set");
+    } else {
+        tracing::error!(?receipt, "The validator set update failed");
+    }
+    Ok(succeeded)
+}
+
+/// Relay the most profitable batch of transfers in the Bridge pool
+/// to Ethereum, if it satisfies the profitability policy of the
+/// relayer.
+///
+/// Returns whether a batch was successfully relayed.
+async fn relay_bridge_pool_batch<E>(
+    context: &impl Namada,
+    eth_client: &Arc<E>,
+    args: &args::RelayDaemon,
+    state: &mut RelayerState,
+) -> Result<bool, Error>
+where
+    E: Middleware,
+    E::Error: std::fmt::Debug + std::fmt::Display,
+{
+    let bridge = get_bridge_contract(context.client(), eth_client).await?;
+    // NOTE: this operation costs no gas on Ethereum
+    let contract_nonce = bridge
+        .transfer_to_erc_20_nonce()
+        .call()
+        .await
+        .map_err(|err| {
+            Error::EthereumBridge(EthereumBridgeError::ContractCall(
+                err.to_string(),
+            ))
+        })?;
+
+    let sender = relay_sender(&**eth_client, args);
+    let eth_nonce =
+        match next_batch_relay(&**eth_client, sender, state, contract_nonce)
+            .await?
+        {
+            NextRelay::Wait => return Ok(false),
+            NextRelay::Relay { eth_nonce } => eth_nonce,
+        };
+
+    let recommendation = find_recommended_batch(
+        context,
+        &args.conversion_table,
+        args.max_gas,
+        max_relay_cost(args.min_profit),
+    )
+    .await;
+    let recommendation = match recommendation {
+        Err(Error::EthereumBridge(EthereumBridgeError::InvalidBpNonce)) => {
+            tracing::debug!(
+                "Waiting for the latest Bridge pool root to be signed"
+            );
+            return Ok(false);
+        }
+        result => result?,
+    };
+    let Some(batch) = relayable_batch(recommendation, args.min_profit) else {
+        return Ok(false);
+    };
+
+    let transfers = batch
+        .transfer_hashes
+        .iter()
+        .map(|hash| KeccakHash::try_from(hash.as_str()))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| EncodingError::Decoding(err.to_string()))?;
+    let abi_encoded_args = RPC
+        .shell()
+        .eth_bridge()
+        .generate_bridge_pool_proof(
+            context.client(),
+            Some(
+                GenBridgePoolProofReq {
+                    transfers: Cow::Borrowed(&transfers),
+                    relayer: Cow::Borrowed(&args.relayer),
+                    with_appendix: false,
+                }
+                .serialize_to_vec(),
+            ),
+            None,
+            false,
+        )
+        .await
+        .map_err(|err| {
+            Error::EthereumBridge(EthereumBridgeError::GenBridgePoolProof(
+                err.to_string(),
+            ))
+        })?
+        .data
+        .abi_encoded_args;
+    let (validator_set, signatures, bp_proof): TransferToErcArgs =
+        AbiDecode::decode(&abi_encoded_args)
+            .map_err(|err| EncodingError::Decoding(err.to_string()))?;
+
+    if bp_proof.batch_nonce != contract_nonce {
+        tracing::debug!(
+            namada_nonce = %bp_proof.batch_nonce,
+            %contract_nonce,
+            "The Bridge pool nonce in Namada does not match the one in the \
+             Bridge contract"
+        );
+        return Ok(false);
+    }
+
+    let mut relay_op =
+        bridge.transfer_to_erc(validator_set, signatures, bp_proof);
+    if let Some(gas_price) = args.gas_price {
+        relay_op.tx.set_gas_price(gas_price);
+    }
+    if let Some(eth_addr) = args.eth_addr {
+        relay_op.tx.set_from(eth_addr.into());
+    }
+    let eth_nonce =
+        pin_relay_nonce(&**eth_client, sender, &mut relay_op.tx, eth_nonce)
+            .await?;
+
+    // persist the relay before sending it, in case the
+    // daemon stops before its hash is persisted
+    state.bridge_pool = Some(BatchRelay {
+        nonce: contract_nonce,
+        transfers,
+        tx_hash: None,
+        eth_nonce,
+    });
+    persist_state(args, state)?;
+
+    let pending_tx = relay_op.send().await.map_err(|err| {
+        Error::EthereumBridge(EthereumBridgeError::ContractCall(
+            err.to_string(),
+        ))
+    })?;
+    let tx_hash = pending_tx.tx_hash();
+    if let Some(relay) = state.bridge_pool.as_mut() {
+        relay.tx_hash = Some(tx_hash);
+    }
+    persist_state(args, state)?;
+    tracing::info!(
+        nonce = %contract_nonce,
+        transfers = ?batch.transfer_hashes,
+        ethereum_gas_fees = %batch.ethereum_gas_fees,
+        net_profit = %batch.net_profit,
+        bridge_pool_gas_fees = ?batch.bridge_pool_gas_fees,
+        ?tx_hash,
+        "Relayed a batch of transfers"
+    );
+
+    let receipt = pending_tx
+        .confirmations(args.confirmations as usize)
+        .await
+        .map_err(|err| {
+            Error::EthereumBridge(EthereumBridgeError::Rpc(err.to_string()))
+        })?;
+    let succeeded = receipt.is_successful();
+    if succeeded {
+        tracing::info!(?receipt, "The batch of transfers was relayed");
+    } else {
+        tracing::error!(?receipt, "The relay of the batch of transfers failed");
+    }
+    Ok(succeeded)
+}
+
+#[cfg(test)]
+mod tests {
+    use ethers::providers::{MockProvider, Provider};
+    use ethers::types::{Transaction, TransactionReceipt};
+    use namada_core::types::uint::Uint;
+
+    use super::*;
+
+    /// Build a batch with the given net profit.
+    fn batch_with_profit(net_profit: i64) -> RecommendedBatch {
+        RecommendedBatch {
+            transfer_hashes: vec![],
+            ethereum_gas_fees: Uint::from_u64(0),
+            net_profit: I256::from(net_profit),
+            bridge_pool_gas_fees: Default::default(),
+        }
+    }
+
+    /// Test that batches are only relayed if they yield
+    /// at least the minimum profit.
+    #[test]
+    fn test_profitability_policy() {
+        assert!(satisfies_policy(&batch_with_profit(0), 0));
+        assert!(satisfies_policy(&batch_with_profit(10), 10));
+        assert!(!satisfies_policy(&batch_with_profit(9), 10));
+        assert!(satisfies_policy(&batch_with_profit(-5), -10));
+        assert!(!satisfies_policy(&batch_with_profit(-11), -10));
+
+        assert_eq!(max_relay_cost(10), None);
+        assert_eq!(max_relay_cost(0), None);
+        assert_eq!(max_relay_cost(-10), Some(10));
+    }
+
+    /// Test that the state of the daemon survives a restart.
+    #[test]
+    fn test_relayer_state_roundtrip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("relayer_state.json");
+
+        // nothing has been persisted yet
+        assert_eq!(RelayerState::load(&path).unwrap(), RelayerState::default());
+
+        let state = RelayerState {
+            bridge_pool: Some(BatchRelay {
+                nonce: 3.into(),
+                transfers: vec![KeccakHash([1; 32]), KeccakHash([2; 32])],
+                tx_hash: Some(TxHash::repeat_byte(3)),
+                eth_nonce: Some(6.into()),
+            }),
+            validator_set: Some(ValidatorSetRelay {
+                epoch: Epoch(4),
+                tx_hash: None,
+                eth_nonce: None,
+            }),
+        };
+        state.save(&path).unwrap();
+        assert_eq!(RelayerState::load(&path).unwrap(), state);
+        assert!(!path.with_extension("tmp").exists());
+    }
+
+    /// Test fetching the status of relays from an Ethereum node.
+    #[tokio::test]
+    async fn test_relay_status() {
+        let (eth_client, mock) = Provider::mocked();
+        let tx_hash = TxHash::repeat_byte(1);
+
+        // NB: the mock provider replies in LIFO order
+        let receipt = |status: u64| TransactionReceipt {
+            transaction_hash: tx_hash,
+            status: Some(status.into()),
+            ..Default::default()
+        };
+        mock.push::<Option<TransactionReceipt>, _>(Some(receipt(1)))
+            .unwrap();
+        assert_eq!(
+            relay_status(&eth_client, tx_hash).await.unwrap(),
+            RelayStatus::Succeeded
+        );
+
+        mock.push::<Option<TransactionReceipt>, _>(Some(receipt(0)))
+            .unwrap();
+        assert_eq!(
+            relay_status(&eth_client, tx_hash).await.unwrap(),
+            RelayStatus::Failed
+        );
+
+        let transaction = Transaction {
+            hash: tx_hash,
+            ..Default::default()
+        };
+        mock.push::<Option<Transaction>, _>(Some(transaction))
+            .unwrap();
+        mock.push::<Option<TransactionReceipt>, _>(None).unwrap();
+        assert_eq!(
+            relay_status(&eth_client, tx_hash).await.unwrap(),
+            RelayStatus::Pending
+        );
+
+        mock.push::<Option<Transaction>, _>(None).unwrap();
+        mock.push::<Option<TransactionReceipt>, _>(None).unwrap();
+        assert_eq!(
+            relay_status(&eth_client, tx_hash).await.unwrap(),
+            RelayStatus::Dropped
+        );
+
+        // the daemon never gives up on relays pending in the mempool
+        assert!(RelayStatus::Pending.is_pending());
+        assert!(!RelayStatus::Dropped.is_pending());
+    }
+
+    /// Push the replies of the Ethereum node to a status query
+    /// of a relay, pending or dropped from the mempool.
+    fn push_relay_status(mock: &MockProvider, tx_hash: TxHash, pending: bool) {
+        let transaction = pending.then(|| Transaction {
+            hash: tx_hash,
+            ..Default::default()
+        });
+        mock.push::<Option<Transaction>, _>(transaction).unwrap();
+        mock.push::<Option<TransactionReceipt>, _>(None).unwrap();
+    }
+
+    /// Build a state with a relay of the batch at the given nonce.
+    fn state_with_batch_relay(
+        nonce: u64,
+        tx_hash: Option<TxHash>,
+        eth_nonce: Option<u64>,
+    ) -> RelayerState {
+        RelayerState {
+            bridge_pool: Some(BatchRelay {
+                nonce: nonce.into(),
+                transfers: vec![KeccakHash([1; 32])],
+                tx_hash,
+                eth_nonce: eth_nonce.map(U256::from),
+            }),
+            validator_set: None,
+        }
+    }
+
+    /// Test that the daemon waits on a relay still pending
+    /// from an earlier run, rather than relaying it again.
+    #[tokio::test]
+    async fn test_skip_pending_relay() {
+        let (eth_client, mock) = Provider::mocked();
+        let tx_hash = TxHash::repeat_byte(1);
+        let sender = Some(Address::repeat_byte(2));
+
+        let state = state_with_batch_relay(3, Some(tx_hash), Some(7));
+        push_relay_status(&mock, tx_hash, true);
+        assert_eq!(
+            next_batch_relay(&eth_client, sender, &state, 3.into())
+                .await
+                .unwrap(),
+            NextRelay::Wait
+        );
+
+        let state = RelayerState {
+            bridge_pool: None,
+            validator_set: Some(ValidatorSetRelay {
+                epoch: Epoch(4),
+                tx_hash: Some(tx_hash),
+                eth_nonce: None,
+            }),
+        };
+        push_relay_status(&mock, tx_hash, true);
+        assert_eq!(
+            next_validator_set_relay(&eth_client, None, &state, Epoch(4))
+                .await
+                .unwrap(),
+            NextRelay::Wait
+        );
+
+        // the daemon stopped before persisting the hash of a relay
+        // which is pending in the mempool of the Ethereum node
+        let state = state_with_batch_relay(3, None, Some(7));
+        mock.push::<U256, _>(U256::from(8)).unwrap();
+        mock.push::<U256, _>(U256::from(7)).unwrap();
+        assert_eq!(
+            next_batch_relay(&eth_client, sender, &state, 3.into())
+                .await
+                .unwrap(),
+            NextRelay::Wait
+        );
+    }
+
+    /// Test that relays dropped from the mempool of the Ethereum
+    /// node are relayed again, with a fresh account nonce.
+    #[tokio::test]
+    async fn test_relay_dropped_again() {
+        let (eth_client, mock) = Provider::mocked();
+        let tx_hash = TxHash::repeat_byte(1);
+        let sender = Some(Address::repeat_byte(2));
+
+        let state = state_with_batch_relay(3, Some(tx_hash), Some(7));
+        push_relay_status(&mock, tx_hash, false);
+        assert_eq!(
+            next_batch_relay(&eth_client, sender, &state, 3.into())
+                .await
+                .unwrap(),
+            NextRelay::Relay { eth_nonce: None }
+        );
+
+        // the account nonce of a relay whose hash was never
+        // persisted was used by a mined transaction
+        let state = state_with_batch_relay(3, None, Some(7));
+        mock.push::<U256, _>(U256::from(8)).unwrap();
+        assert_eq!(
+            next_batch_relay(&eth_client, sender, &state, 3.into())
+                .await
+                .unwrap(),
+            NextRelay::Relay { eth_nonce: None }
+        );
+    }
+
+    /// Test that only relays of the batch at the current nonce of the
+    /// Bridge contract are checked, and that relays which were never
+    /// sent are relayed again with the same account nonce.
+    #[tokio::test]
+    async fn test_relay_nonce_check() {
+        // NB: the mock provider fails every query without a reply
+        let (eth_client, mock) = Provider::mocked();
+        let sender = Some(Address::repeat_byte(2));
+
+        // the batch persisted at an older nonce has been accepted
+        let state =
+            state_with_batch_relay(2, Some(TxHash::repeat_byte(1)), Some(7));
+        assert_eq!(
+            next_batch_relay(&eth_client, sender, &state, 3.into())
+                .await
+                .unwrap(),
+            NextRelay::Relay { eth_nonce: None }
+        );
+        assert_eq!(
+            next_batch_relay(
+                &eth_client,
+                sender,
+                &RelayerState::default(),
+                3.into()
+            )
+            .await
+            .unwrap(),
+            NextRelay::Relay { eth_nonce: None }
+        );
+
+        // the daemon stopped before sending the relay
+        let state = state_with_batch_relay(3, None, Some(7));
+        mock.push::<U256, _>(U256::from(7)).unwrap();
+        mock.push::<U256, _>(U256::from(7)).unwrap();
+        assert_eq!(
+            next_batch_relay(&eth_client, sender, &state, 3.into())
+                .await
+                .unwrap(),
+            NextRelay::Relay {
+                eth_nonce: Some(7.into())
+            }
+        );
+
+        // without a known sender, there is no account nonce to check
+        let state = state_with_batch_relay(3, None, None);
+        assert_eq!(
+            next_batch_relay(&eth_client, None, &state, 3.into())
+                .await
+                .unwrap(),
+            NextRelay::Relay { eth_nonce: None }
+        );
+    }
+
+    /// Test pinning the nonce of the Ethereum account of the relayer.
+    #[tokio::test]
+    async fn test_pin_relay_nonce() {
+        let (eth_client, mock) = Provider::mocked();
+        let sender = Some(Address::repeat_byte(2));
+
+        // the sender is unknown, so the nonce is left to the node
+        let mut tx = TypedTransaction::default();
+        assert_eq!(
+            pin_relay_nonce(&eth_client, None, &mut tx, Some(7.into()))
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(tx.nonce(), None);
+
+        // the nonce of a previous relay is reused
+        assert_eq!(
+            pin_relay_nonce(&eth_client, sender, &mut tx, Some(7.into()))
+                .await
+                .unwrap(),
+            Some(7.into())
+        );
+        assert_eq!(tx.nonce(), Some(&7.into()));
+
+        // otherwise, the next nonce of the sender is used
+        let mut tx = TypedTransaction::default();
+        mock.push::<U256, _>(U256::from(9)).unwrap();
+        assert_eq!(
+            pin_relay_nonce(&eth_client, sender, &mut tx, None)
+                .await
+                .unwrap(),
+            Some(9.into())
+        );
+        assert_eq!(tx.nonce(), Some(&9.into()));
+    }
+
+    /// Test that batches below the minimum profit are not relayed.
+    #[test]
+    fn test_skip_unprofitable_batch() {
+        assert_eq!(relayable_batch(None, 0), None);
+        assert_eq!(relayable_batch(Some(batch_with_profit(9)), 10), None);
+        assert_eq!(
+            relayable_batch(Some(batch_with_profit(10)), 10),
+            Some(batch_with_profit(10))
+        );
+    }
+}
@@ -8,6 +8,7 @@ use std::task::Poll;
 
 use data_encoding::HEXLOWER;
 use ethbridge_bridge_contract::Bridge;
+use ethers::contract::ContractCall;
 use ethers::providers::Middleware;
 use futures::future::{self, FutureExt};
 use namada_core::hints;
@@ -123,7 +124,7 @@ impl Error {
 }
 
 /// Get the status of a relay result.
-trait GetStatus {
+pub(crate) trait GetStatus {
     /// Return whether a relay result is successful or not.
     fn is_successful(&self) -> bool;
 }
@@ -676,6 +677,58 @@ where
         )));
     }
 
+    let (bridge, mut relay_op) =
+        validator_set_update_call(nam_client, eth_client, epoch_to_relay)
+            .await
+            .map_err(|err| R::try_recover(err))?;
+
+    if let Err(result) = R::should_relay(epoch_to_relay, &bridge).await {
+        action(result);
+        return Err(Error::NoContext);
+    }
+
+    if let Some(gas) = args.gas {
+        relay_op.tx.set_gas(gas);
+    }
+    if let Some(gas_price) = args.gas_price {
+        relay_op.tx.set_gas_price(gas_price);
+    }
+    if let Some(eth_addr) = args.eth_addr {
+        relay_op.tx.set_from(eth_addr.into());
+    }
+
+    let pending_tx = relay_op.send().await.map_err(|e| {
+        Error::critical(EthereumBridgeError::ContractCall(e.to_string()))
+    })?;
+    let transf_result = pending_tx
+        .confirmations(args.confirmations as usize)
+        .await
+        .map_err(|e| {
+            Error::critical(EthereumBridgeError::Rpc(e.to_string()))
+        })?;
+
+    let transf_result: R::RelayResult = transf_result.into();
+    let status = if transf_result.is_successful() {
+        Ok(())
+    } else {
+        Err(Error::NoContext)
+    };
+
+    action(transf_result);
+    status
+}
+
+/// Build a call to the Bridge contract, relaying the validator set
+/// update signed off for the given epoch.
+pub(crate) async fn validator_set_update_call<C, E>(
+    nam_client: &C,
+    eth_client: Arc<E>,
+    epoch_to_relay: Epoch,
+) -> Result<(Bridge<E>, ContractCall<E, ()>), SdkError>
+where
+    C: Client + Sync,
+    E: Middleware,
+{
     let shell = RPC.shell().eth_bridge();
     let encoded_proof_fut = shell
         .read_valset_upd_proof(nam_client, &epoch_to_relay)
@@ -710,13 +763,11 @@ where
             })
         });
 
-    let (encoded_proof, validator_set_args, bridge_contract) =
-        futures::try_join!(
-            encoded_proof_fut,
-            validator_set_args_fut,
-            bridge_address_fut
-        )
-        .map_err(|err| R::try_recover(err))?;
+    let (encoded_proof, validator_set_args, bridge_contract) = futures::try_join!(
+        encoded_proof_fut,
+        validator_set_args_fut,
+        bridge_address_fut
+    )?;
 
     let (bridge_hash, gov_hash, signatures): (
         [u8; 32],
@@ -725,47 +776,13 @@ where
     ) = abi_decode_struct(encoded_proof);
 
     let bridge = Bridge::new(bridge_contract.address, eth_client);
-
-    if let Err(result) = R::should_relay(epoch_to_relay, &bridge).await {
-        action(result);
-        return Err(Error::NoContext);
-    }
-
-    let mut relay_op = bridge.update_validator_set(
+    let relay_op = bridge.update_validator_set(
         validator_set_args.into(),
         bridge_hash,
         gov_hash,
         signatures,
     );
-    if let Some(gas) = args.gas {
-        relay_op.tx.set_gas(gas);
-    }
-    if let Some(gas_price) = args.gas_price {
-        relay_op.tx.set_gas_price(gas_price);
-    }
-    if let Some(eth_addr) = args.eth_addr {
-        relay_op.tx.set_from(eth_addr.into());
-    }
-
-    let pending_tx = relay_op.send().await.map_err(|e| {
-        Error::critical(EthereumBridgeError::ContractCall(e.to_string()))
-    })?;
-    let transf_result = pending_tx
-        .confirmations(args.confirmations as usize)
-        .await
-        .map_err(|e| {
-            Error::critical(EthereumBridgeError::Rpc(e.to_string()))
-        })?;
-
-    let transf_result: R::RelayResult = transf_result.into();
-    let status = if transf_result.is_successful() {
-        Ok(())
-    } else {
-        Err(Error::NoContext)
-    };
-
-    action(transf_result);
-    status
+    Ok((bridge, relay_op))
 }
 
 // NOTE: there's a bug (or feature?!) in ethers, where
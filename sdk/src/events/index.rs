@@ -0,0 +1,129 @@
+//! Tags by which events are looked up in the persistent event index of the
+//! ledger.
+//!
+//! Every event is indexed by its type and by each of its attributes whose
+//! value is at most [`MAX_INDEXED_ATTRIBUTE_LEN`] bytes long. Transaction
+//! events are thus also indexed by their transaction hash.
+
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use borsh_ext::BorshSerializeExt;
+use data_encoding::HEXLOWER;
+use namada_core::types::hash::Hash;
+
+use crate::error::{EncodingError, Error};
+use crate::events::{Event, EventType};
+
+/// Maximum length of the attribute values that are indexed. Longer values,
+/// such as the results of inner transactions, are only stored.
+pub const MAX_INDEXED_ATTRIBUTE_LEN: usize = 128;
+
+/// Maximum number of events returned by a single query of the event index.
+pub const MAX_EVENTS_PER_QUERY: u64 = 1000;
+
+/// A filter selecting events from the event index
+#[derive(Clone, Debug, Eq, PartialEq, BorshSerialize, BorshDeserialize)]
+pub enum EventFilter {
+    /// Events with the given transaction hash
+    TxHash(Hash),
+    /// Events of the given type
+    EventType(EventType),
+    /// Events with the given attribute key and value
+    Attribute(String, String),
+}
+
+impl EventFilter {
+    /// Get the tag of the events matched by this filter in the event index
+    pub fn tag(&self) -> String {
+        match self {
+            Self::TxHash(hash) => attribute_tag("hash", &hash.to_string()),
+            Self::EventType(event_type) => format!("type:{}", event_type),
+            Self::Attribute(key, value) => attribute_tag(key, value),
+        }
+    }
+}
+
+/// The hex encoding of the filter is used in query paths, as attribute values
+/// can contain path separators
+impl Display for EventFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", HEXLOWER.encode(&self.serialize_to_vec()))
+    }
+}
+
+impl FromStr for EventFilter {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = HEXLOWER
+            .decode(s.as_bytes())
+            .map_err(|err| EncodingError::Decoding(err.to_string()))?;
+        Self::try_from_slice(&bytes)
+            .map_err(|err| EncodingError::Decoding(err.to_string()).into())
+    }
+}
+
+/// Get the encoded event and the tags by which it must be indexed
+pub fn index_entry(event: &Event) -> (Vec<u8>, Vec<String>) {
+    let tags =
+        std::iter::once(EventFilter::EventType(event.event_type.clone()).tag())
+            .chain(
+                event
+                    .attributes
+                    .iter()
+                    .filter(|(_, value)| {
+                        value.len() <= MAX_INDEXED_ATTRIBUTE_LEN
+                    })
+                    .map(|(key, value)| attribute_tag(key, value)),
+            )
+            .collect();
+    (event.serialize_to_vec(), tags)
+}
+
+/// The key is length-prefixed so that no two distinct attributes share a tag
+fn attribute_tag(key: &str, value: &str) -> String {
+    format!("attr:{}:{}={}", key.len(), key, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::events::EventLevel;
+
+    const HASH: &str =
+        "DEADBEEFDEADBEEFDEADBEEFDEADBEEFDEADBEEFDEADBEEFDEADBEEFDEADBEEF";
+
+    /// Test that events are indexed by their tx hash, type and short
+    /// attributes.
+    #[test]
+    fn test_index_entry_tags() {
+        let event = Event {
+            event_type: EventType::Applied,
+            level: EventLevel::Tx,
+            attributes: HashMap::from([
+                ("hash".to_string(), HASH.to_string()),
+                ("height".to_string(), "10".to_string()),
+                ("inner_tx".to_string(), "x".repeat(1000)),
+            ]),
+        };
+        let (encoded, tags) = index_entry(&event);
+
+        assert_eq!(Event::try_from_slice(&encoded).unwrap(), event);
+        assert_eq!(tags.len(), 3);
+        for filter in [
+            EventFilter::TxHash(Hash::try_from(HASH).unwrap()),
+            EventFilter::EventType(EventType::Applied),
+            EventFilter::Attribute("height".to_string(), "10".to_string()),
+        ] {
+            assert!(tags.contains(&filter.tag()));
+            assert_eq!(
+                filter.to_string().parse::<EventFilter>().unwrap(),
+                filter
+            );
+        }
+    }
+}
@@ -1,4 +1,5 @@
 //! Logic to do with events emitted by the ledger.
+pub mod index;
 pub mod log;
 
 use std::collections::HashMap;
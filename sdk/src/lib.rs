@@ -39,6 +39,7 @@ use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
 use namada_core::types::address::Address;
 use namada_core::types::dec::Dec;
 use namada_core::types::ethereum_events::EthAddress;
+use namada_core::types::keccak::KeccakHash;
 use namada_core::types::key::*;
 use namada_core::types::masp::{TransferSource, TransferTarget};
 use namada_core::types::token;
@@ -55,12 +56,14 @@ use crate::signing::SigningTxData;
 use crate::token::{DenominatedAmount, NATIVE_MAX_DECIMAL_PLACES};
 use crate::tx::{
     ProcessTxResponse, TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM,
+    TX_BRIDGE_POOL_BUMP_FEE_WASM, TX_BRIDGE_POOL_CANCEL_WASM,
     TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
     TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
-    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM,
-    TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM,
-    TX_REDELEGATE_WASM, TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM,
-    TX_UNBOND_WASM, TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
+    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_DELEGATE_VOTE,
+    TX_DEPOSIT_PROPOSAL, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL,
+    TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM, TX_RESIGN_STEWARD,
+    TX_REVEAL_PK, TX_SPENDING_POLICY_WASM, TX_TRANSFER_WASM, TX_UNBOND_WASM,
+    TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
     TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
     VP_USER_WASM,
 };
@@ -273,6 +276,10 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             is_offline: false,
             is_pgf_stewards: false,
             is_pgf_funding: false,
+            is_emergency: false,
+            is_parameter_change: false,
+            is_upgrade: false,
+            deposit: None,
             tx_code_path: PathBuf::from(TX_INIT_PROPOSAL),
             tx: self.tx_builder(),
         }
@@ -285,6 +292,7 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
             vp_code_path: None,
             public_keys: vec![],
             threshold: None,
+            key_options: vec![],
             tx_code_path: PathBuf::from(TX_UPDATE_ACCOUNT_WASM),
             tx: self.tx_builder(),
         }
@@ -307,6 +315,33 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a DepositProposal builder from the given minimum set of arguments
+    fn new_deposit_proposal(
+        &self,
+        proposal_id: u64,
+        depositor: Address,
+        amount: token::Amount,
+    ) -> args::DepositProposal {
+        args::DepositProposal {
+            proposal_id,
+            depositor,
+            amount,
+            tx_code_path: PathBuf::from(TX_DEPOSIT_PROPOSAL),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a DelegateVote builder from the given minimum set of arguments.
+    /// Without a representative, the builder revokes the delegation.
+    fn new_delegate_vote(&self, delegator: Address) -> args::DelegateVote {
+        args::DelegateVote {
+            delegator,
+            representative: None,
+            tx_code_path: PathBuf::from(TX_DELEGATE_VOTE),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a CommissionRateChange builder from the given minimum set of
     /// arguments
     fn new_change_commission_rate(
@@ -503,6 +538,35 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a builder cancelling a Bridge pool transfer from the given minimum
+    /// set of arguments
+    fn new_cancel_bridge_pool_transfer(
+        &self,
+        transfer_hash: KeccakHash,
+    ) -> args::CancelBridgePoolTransfer {
+        args::CancelBridgePoolTransfer {
+            transfer_hash,
+            expiry: None,
+            code_path: PathBuf::from(TX_BRIDGE_POOL_CANCEL_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
+    /// Make a builder bumping the gas fees of a Bridge pool transfer from the
+    /// given minimum set of arguments
+    fn new_bump_bridge_pool_fee(
+        &self,
+        transfer_hash: KeccakHash,
+        amount: InputAmount,
+    ) -> args::BumpBridgePoolTransferFee {
+        args::BumpBridgePoolTransferFee {
+            transfer_hash,
+            amount,
+            code_path: PathBuf::from(TX_BRIDGE_POOL_BUMP_FEE_WASM),
+            tx: self.tx_builder(),
+        }
+    }
+
     /// Make a ResignSteward builder from the given minimum set of arguments
     fn new_resign_steward(&self, steward: Address) -> args::ResignSteward {
         args::ResignSteward {
@@ -527,6 +591,21 @@ pub trait Namada: Sized + MaybeSync + MaybeSend {
         }
     }
 
+    /// Make a UpdateSpendingPolicy builder from the given minimum set of
+    /// arguments
+    fn new_spending_policy(
+        &self,
+        owner: Address,
+        action: args::SpendingPolicyAction,
+    ) -> args::UpdateSpendingPolicy {
+        args::UpdateSpendingPolicy {
+            owner,
+            action,
+            tx: self.tx_builder(),
+            tx_code_path: PathBuf::from(TX_SPENDING_POLICY_WASM),
+        }
+    }
+
     /// Make a TxCustom builder from the given minimum set of arguments
     fn new_custom(&self, owner: Address) -> args::TxCustom {
         args::TxCustom {
@@ -960,6 +1039,7 @@ pub mod testing {
                 data_hash,
                 code_hash,
                 tx_type,
+                batch: None,
             }
         }
     }
@@ -1061,7 +1141,7 @@ pub mod testing {
             let mut tx = Tx { header, sections: vec![] };
             let content_hash = tx.add_section(Section::ExtraData(content_extra_data)).get_hash();
             init_proposal.content = content_hash;
-            if let ProposalType::Default(Some(hash)) = &mut init_proposal.r#type {
+            if let ProposalType::Default(Some(hash)) | ProposalType::Emergency(Some(hash)) = &mut init_proposal.r#type {
                 let type_hash = tx.add_section(Section::ExtraData(type_extra_data)).get_hash();
                 *hash = type_hash;
             }
@@ -51,14 +51,15 @@ use masp_proofs::prover::LocalTxProver;
 use masp_proofs::sapling::SaplingVerificationContext;
 use namada_core::types::address::{Address, MASP};
 use namada_core::types::masp::{
-    BalanceOwner, ExtendedViewingKey, PaymentAddress, TransferSource,
-    TransferTarget,
+    BalanceOwner, ExtendedViewingKey, KeyBirthday, PaymentAddress,
+    TransferSource, TransferTarget,
 };
 use namada_core::types::storage::{BlockHeight, Epoch, Key, KeySeg, TxIndex};
 use namada_core::types::time::{DateTimeUtc, DurationSecs};
 use namada_core::types::token;
 use namada_core::types::token::{
-    Change, MaspDenom, Transfer, HEAD_TX_KEY, PIN_KEY_PREFIX, TX_KEY_PREFIX,
+    Change, MaspDenom, Transfer, HEAD_TX_KEY, MASP_NOTE_COMMITMENT_TREE_KEY,
+    PIN_KEY_PREFIX, TX_KEY_PREFIX,
 };
 use namada_core::types::transaction::WrapperTx;
 use rand_core::{CryptoRng, OsRng, RngCore};
@@ -107,6 +108,10 @@ pub const OUTPUT_NAME: &str = "masp-output.params";
 /// Convert circuit name
 pub const CONVERT_NAME: &str = "masp-convert.params";
 
+/// The number of shielded transactions that are queried concurrently and
+/// scanned between two saves of the shielded context
+pub const FETCH_BATCH_SIZE: u64 = 50;
+
 /// Shielded transfer
 #[derive(Clone, Debug, BorshSerialize, BorshDeserialize)]
 pub struct ShieldedTransfer {
@@ -626,6 +631,13 @@ pub struct ShieldedContext<U: ShieldedUtils> {
     pub asset_types: HashMap<AssetType, (Address, MaspDenom, Epoch)>,
     /// Maps note positions to their corresponding viewing keys
     pub vk_map: HashMap<usize, ViewingKey>,
+    /// Contexts of viewing keys that are still catching up to
+    /// `last_txidx`. These are persisted with this context so that an
+    /// interrupted sync can be resumed where it left off.
+    pub pending_ctxs: Vec<ShieldedContext<U>>,
+    /// Maps viewing keys to the index of the first MASP transaction that
+    /// may contain notes for them
+    pub birthdays: HashMap<ViewingKey, u64>,
 }
 
 /// Default implementation to ease construction of TxContexts. Derive cannot be
@@ -646,6 +658,87 @@ impl<U: ShieldedUtils + Default> Default for ShieldedContext<U> {
             delta_map: BTreeMap::default(),
             asset_types: HashMap::default(),
             vk_map: HashMap::default(),
+            pending_ctxs: Vec::default(),
+            birthdays: HashMap::default(),
+        }
+    }
+}
+
+/// The prefix of the versioned encodings of the shielded contexts. The
+/// unversioned encodings do not start with it, as it would decode to a last
+/// transaction index above 10^18.
+const SHIELDED_CONTEXT_MAGIC: &[u8] = b"NAMADA-SHIELDED-CONTEXT";
+
+/// The current version of the encoding of the shielded contexts
+const SHIELDED_CONTEXT_VERSION: u8 = 1;
+
+/// The encoding of the shielded contexts saved before it was versioned,
+/// without the pending contexts and the key birthdays
+#[derive(BorshDeserialize)]
+struct ShieldedContextV0 {
+    last_txidx: u64,
+    tree: CommitmentTree<Node>,
+    pos_map: HashMap<ViewingKey, BTreeSet<usize>>,
+    nf_map: HashMap<Nullifier, usize>,
+    note_map: HashMap<usize, Note>,
+    memo_map: HashMap<usize, MemoBytes>,
+    div_map: HashMap<usize, Diversifier>,
+    witness_map: HashMap<usize, IncrementalWitness<Node>>,
+    delta_map: BTreeMap<
+        (BlockHeight, TxIndex),
+        (Epoch, TransferDelta, TransactionDelta),
+    >,
+    spents: HashSet<usize>,
+    asset_types: HashMap<AssetType, (Address, MaspDenom, Epoch)>,
+    vk_map: HashMap<usize, ViewingKey>,
+}
+
+impl<U: ShieldedUtils> From<ShieldedContextV0> for ShieldedContext<U> {
+    fn from(ctx: ShieldedContextV0) -> Self {
+        Self {
+            last_txidx: ctx.last_txidx,
+            tree: ctx.tree,
+            pos_map: ctx.pos_map,
+            nf_map: ctx.nf_map,
+            note_map: ctx.note_map,
+            memo_map: ctx.memo_map,
+            div_map: ctx.div_map,
+            witness_map: ctx.witness_map,
+            delta_map: ctx.delta_map,
+            spents: ctx.spents,
+            asset_types: ctx.asset_types,
+            vk_map: ctx.vk_map,
+            ..Default::default()
+        }
+    }
+}
+
+impl<U: ShieldedUtils> ShieldedContext<U> {
+    /// Encode this context along with the version of the encoding, for the
+    /// [`ShieldedUtils`] to save it
+    pub fn serialize_versioned(&self) -> Vec<u8> {
+        let mut bytes = SHIELDED_CONTEXT_MAGIC.to_vec();
+        bytes.push(SHIELDED_CONTEXT_VERSION);
+        self.serialize(&mut bytes)
+            .expect("cannot serialize shielded context");
+        bytes
+    }
+
+    /// Decode a context saved by the [`ShieldedUtils`]. The contexts saved
+    /// before the encoding was versioned are migrated to the current one.
+    pub fn deserialize_versioned(bytes: &[u8]) -> std::io::Result<Self> {
+        let Some(versioned) = bytes.strip_prefix(SHIELDED_CONTEXT_MAGIC) else {
+            return ShieldedContextV0::deserialize(&mut &bytes[..])
+                .map(Into::into);
+        };
+        match versioned.split_first() {
+            Some((&SHIELDED_CONTEXT_VERSION, mut ctx)) => {
+                Self::deserialize(&mut ctx)
+            }
+            _ => Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "unsupported version of the shielded context",
+            )),
         }
     }
 }
@@ -678,6 +771,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         self.spents.extend(new_ctx.spents);
         self.asset_types.extend(new_ctx.asset_types);
         self.vk_map.extend(new_ctx.vk_map);
+        self.birthdays.extend(new_ctx.birthdays);
         // The deltas are the exception because different keys can reveal
         // different parts of the same transaction. Hence each delta needs to be
         // merged separately.
@@ -691,120 +785,305 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         }
     }
 
+    /// Get the number of MASP transactions that have been scanned for the
+    /// given viewing key. Returns `None` if the key is not known to this
+    /// context.
+    pub fn key_sync_progress(&self, vk: &ViewingKey) -> Option<u64> {
+        if self.pos_map.contains_key(vk) {
+            return Some(self.last_txidx);
+        }
+        self.pending_ctxs
+            .iter()
+            .find(|ctx| ctx.pos_map.contains_key(vk))
+            .map(|ctx| ctx.last_txidx)
+    }
+
+    /// Merge the pending contexts that have caught up with this context
+    fn merge_caught_up_ctxs(&mut self) {
+        let (caught_up, pending): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut self.pending_ctxs)
+                .into_iter()
+                .partition(|ctx| ctx.last_txidx == self.last_txidx);
+        self.pending_ctxs = pending;
+        for ctx in caught_up {
+            self.merge(ctx);
+        }
+    }
+
     /// Fetch the current state of the multi-asset shielded pool into a
-    /// ShieldedContext
+    /// ShieldedContext. Transactions are fetched in batches of
+    /// [`FETCH_BATCH_SIZE`] and the context is saved after every batch, so
+    /// that an interrupted fetch resumes from the last completed batch. New
+    /// keys with a birthday are only scanned from their birthday onward.
     pub async fn fetch<C: Client + Sync>(
         &mut self,
         client: &C,
+        io: &impl Io,
         sks: &[ExtendedSpendingKey],
         fvks: &[ViewingKey],
+        birthdays: &HashMap<ViewingKey, KeyBirthday>,
     ) -> Result<(), Error> {
         // First determine which of the keys requested to be fetched are new.
         // Necessary because old transactions will need to be scanned for new
         // keys.
         let mut unknown_keys = Vec::new();
-        for esk in sks {
-            let vk = to_viewing_key(esk).vk;
-            if !self.pos_map.contains_key(&vk) {
+        let requested_keys = sks
+            .iter()
+            .map(|esk| to_viewing_key(esk).vk)
+            .chain(fvks.iter().copied());
+        for vk in requested_keys {
+            if self.key_sync_progress(&vk).is_none()
+                && !unknown_keys.contains(&vk)
+            {
                 unknown_keys.push(vk);
             }
         }
-        for vk in fvks {
-            if !self.pos_map.contains_key(vk) {
-                unknown_keys.push(*vk);
-            }
-        }
+
+        // Query for the index of the last accepted transaction
+        let head_txidx = Self::query_head_txidx(client).await?;
 
         // If unknown keys are being used, we need to scan older transactions
-        // for any unspent notes
-        let (txs, mut tx_iter);
+        // for any unspent notes. Do this by constructing a shielded context
+        // only for the unknown keys, which is merged into the current context
+        // once it is level with it.
         if !unknown_keys.is_empty() {
-            // Load all transactions accepted until this point
-            txs = Self::fetch_shielded_transfers(client, 0).await?;
-            tx_iter = txs.iter();
-            // Do this by constructing a shielding context only for unknown keys
+            let mut key_birthdays = Vec::with_capacity(unknown_keys.len());
+            for vk in unknown_keys {
+                let birthday = match birthdays.get(&vk) {
+                    Some(birthday) => {
+                        Self::birthday_txidx(client, *birthday, head_txidx)
+                            .await?
+                    }
+                    None => 0,
+                };
+                key_birthdays.push((vk, birthday));
+            }
+            // A context without any keys does not need the transactions
+            // preceding the earliest birthday
+            if self.pos_map.is_empty() && self.pending_ctxs.is_empty() {
+                let earliest = key_birthdays
+                    .iter()
+                    .map(|(_vk, birthday)| *birthday)
+                    .min()
+                    .unwrap_or_default();
+                self.fast_forward_or_warn(client, io, earliest).await;
+            }
             let mut tx_ctx = Self {
                 utils: self.utils.clone(),
                 ..Default::default()
             };
-            for vk in unknown_keys {
-                tx_ctx.pos_map.entry(vk).or_insert_with(BTreeSet::new);
+            for &(vk, birthday) in &key_birthdays {
+                // Keys born after the transactions scanned so far do not need
+                // to catch up with this context
+                let ctx = if birthday >= self.last_txidx {
+                    &mut *self
+                } else {
+                    &mut tx_ctx
+                };
+                ctx.pos_map.entry(vk).or_insert_with(BTreeSet::new);
+                ctx.birthdays.insert(vk, birthday);
+            }
+            if !tx_ctx.pos_map.is_empty() {
+                let earliest = tx_ctx
+                    .birthdays
+                    .values()
+                    .copied()
+                    .min()
+                    .unwrap_or_default();
+                tx_ctx.fast_forward_or_warn(client, io, earliest).await;
+                self.pending_ctxs.push(tx_ctx);
             }
-            // Update this unknown shielded context until it is level with self
-            while tx_ctx.last_txidx != self.last_txidx {
-                if let Some(((height, idx), (epoch, tx, stx))) = tx_iter.next()
+        }
+        self.merge_caught_up_ctxs();
+
+        // Resume from the least advanced of the contexts
+        let mut next_txidx = self
+            .pending_ctxs
+            .iter()
+            .map(|ctx| ctx.last_txidx)
+            .fold(self.last_txidx, std::cmp::min);
+        while next_txidx < head_txidx {
+            let batch_end =
+                std::cmp::min(next_txidx + FETCH_BATCH_SIZE, head_txidx);
+            let txs = Self::fetch_shielded_transfer_range(
+                client, next_txidx, batch_end,
+            )
+            .await?;
+            for (txidx, (epoch, height, idx, tx, stx)) in &txs {
+                // Bring the lagging contexts closer to the current context
+                for ctx in self
+                    .pending_ctxs
+                    .iter_mut()
+                    .filter(|ctx| ctx.last_txidx == *txidx)
                 {
-                    tx_ctx
-                        .scan_tx(client, *height, *idx, *epoch, tx, stx)
+                    ctx.scan_tx(client, *height, *idx, *epoch, tx, stx).await?;
+                }
+                if self.last_txidx == *txidx {
+                    self.scan_tx(client, *height, *idx, *epoch, tx, stx)
                         .await?;
-                } else {
-                    break;
                 }
+                self.merge_caught_up_ctxs();
             }
-            // Merge the context data originating from the unknown keys into the
-            // current context
-            self.merge(tx_ctx);
-        } else {
-            // Load only transactions accepted from last_txid until this point
-            txs =
-                Self::fetch_shielded_transfers(client, self.last_txidx).await?;
-            tx_iter = txs.iter();
-        }
-        // Now that we possess the unspent notes corresponding to both old and
-        // new keys up until tx_pos, proceed to scan the new transactions.
-        for ((height, idx), (epoch, tx, stx)) in &mut tx_iter {
-            self.scan_tx(client, *height, *idx, *epoch, tx, stx).await?;
+            next_txidx = batch_end;
+            display_line!(
+                io,
+                "Scanned {} of {} shielded transactions",
+                next_txidx,
+                head_txidx
+            );
+            // Save the progress so that an interrupted fetch can be resumed
+            let _ = self.save().await;
         }
         Ok(())
     }
 
-    /// Obtain a chronologically-ordered list of all accepted shielded
-    /// transactions from the ledger. The ledger conceptually stores
-    /// transactions as a vector. More concretely, the HEAD_TX_KEY location
-    /// stores the index of the last accepted transaction and each transaction
-    /// is stored at a key derived from its index.
-    pub async fn fetch_shielded_transfers<C: Client + Sync>(
+    /// Query the number of shielded transactions accepted by the ledger
+    pub async fn query_head_txidx<C: Client + Sync>(
         client: &C,
-        last_txidx: u64,
-    ) -> Result<
-        BTreeMap<(BlockHeight, TxIndex), (Epoch, Transfer, Transaction)>,
-        Error,
-    > {
-        // The address of the MASP account
-        let masp_addr = MASP;
+    ) -> Result<u64, Error> {
         // Construct the key where last transaction pointer is stored
-        let head_tx_key = Key::from(masp_addr.to_db_key())
+        let head_tx_key = Key::from(MASP.to_db_key())
             .push(&HEAD_TX_KEY.to_owned())
             .map_err(|k| {
                 Error::Other(format!("Cannot obtain a storage key: {}", k))
             })?;
         // Query for the index of the last accepted transaction
-        let head_txidx = query_storage_value::<C, u64>(client, &head_tx_key)
+        Ok(query_storage_value::<C, u64>(client, &head_tx_key)
             .await
-            .unwrap_or(0);
-        let mut shielded_txs = BTreeMap::new();
-        // Fetch all the transactions we do not have yet
-        for i in last_txidx..head_txidx {
-            // Construct the key for where the current transaction is stored
-            let current_tx_key = Key::from(masp_addr.to_db_key())
-                .push(&(TX_KEY_PREFIX.to_owned() + &i.to_string()))
-                .map_err(|e| {
-                    Error::Other(format!("Cannot obtain a storage key {}", e))
-                })?;
-            // Obtain the current transaction
-            let (tx_epoch, tx_height, tx_index, current_tx, current_stx) =
-                query_storage_value::<
-                    C,
-                    (Epoch, BlockHeight, TxIndex, Transfer, Transaction),
-                >(client, &current_tx_key)
-                .await?;
-            // Collect the current transaction
-            shielded_txs.insert(
-                (tx_height, tx_index),
-                (tx_epoch, current_tx, current_stx),
+            .unwrap_or(0))
+    }
+
+    /// Find the index of the first MASP transaction that was accepted at or
+    /// after the given birthday
+    async fn birthday_txidx<C: Client + Sync>(
+        client: &C,
+        birthday: KeyBirthday,
+        head_txidx: u64,
+    ) -> Result<u64, Error> {
+        let height = match birthday {
+            KeyBirthday::Height(height) => height,
+            // The note commitment tree is only known at the end of each
+            // block, hence start from the first transaction of the block
+            KeyBirthday::MaspTxIndex(idx) if idx < head_txidx => {
+                let (_epoch, height, ..) =
+                    Self::fetch_shielded_transfer(client, idx).await?;
+                height
+            }
+            KeyBirthday::MaspTxIndex(_) => return Ok(head_txidx),
+        };
+        // Transactions are stored in the order in which they were accepted,
+        // hence their heights can be binary searched
+        let (mut low, mut high) = (0, head_txidx);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            let (_epoch, tx_height, ..) =
+                Self::fetch_shielded_transfer(client, mid).await?;
+            if tx_height < height {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        Ok(low)
+    }
+
+    /// Skip this context, which must not have any notes yet, ahead to the
+    /// transaction with the given index. The note commitment tree is queried
+    /// at the height of the preceding transaction, which must be lower than
+    /// the height of the given one.
+    async fn fast_forward<C: Client + Sync>(
+        &mut self,
+        client: &C,
+        txidx: u64,
+    ) -> Result<(), Error> {
+        if txidx <= self.last_txidx {
+            return Ok(());
+        }
+        let (_epoch, height, ..) =
+            Self::fetch_shielded_transfer(client, txidx - 1).await?;
+        let tree_key = Key::from(MASP.to_db_key())
+            .push(&MASP_NOTE_COMMITMENT_TREE_KEY.to_owned())
+            .map_err(|e| {
+                Error::Other(format!("Cannot obtain a storage key {}", e))
+            })?;
+        let (bytes, _proof) = rpc::query_storage_value_bytes(
+            client,
+            &tree_key,
+            Some(height),
+            false,
+        )
+        .await?;
+        let bytes = bytes.ok_or_else(|| {
+            Error::Other(format!(
+                "The note commitment tree is not available at height {}",
+                height
+            ))
+        })?;
+        self.tree = CommitmentTree::<Node>::try_from_slice(&bytes)
+            .map_err(|e| Error::Other(e.to_string()))?;
+        self.last_txidx = txidx;
+        Ok(())
+    }
+
+    /// Skip this context ahead to the transaction with the given index if the
+    /// ledger still has the note commitment tree at that point, otherwise
+    /// keep scanning from where this context is
+    async fn fast_forward_or_warn<C: Client + Sync>(
+        &mut self,
+        client: &C,
+        io: &impl Io,
+        txidx: u64,
+    ) {
+        if let Err(err) = self.fast_forward(client, txidx).await {
+            edisplay_line!(
+                io,
+                "Cannot skip the shielded transactions before the key \
+                 birthday, scanning from transaction {}: {}",
+                self.last_txidx,
+                err
             );
         }
-        Ok(shielded_txs)
+    }
+
+    /// Obtain the shielded transaction with the given index
+    pub async fn fetch_shielded_transfer<C: Client + Sync>(
+        client: &C,
+        txidx: u64,
+    ) -> Result<(Epoch, BlockHeight, TxIndex, Transfer, Transaction), Error>
+    {
+        // Construct the key for where the transaction is stored
+        let tx_key = Key::from(MASP.to_db_key())
+            .push(&(TX_KEY_PREFIX.to_owned() + &txidx.to_string()))
+            .map_err(|e| {
+                Error::Other(format!("Cannot obtain a storage key {}", e))
+            })?;
+        query_storage_value::<
+            C,
+            (Epoch, BlockHeight, TxIndex, Transfer, Transaction),
+        >(client, &tx_key)
+        .await
+    }
+
+    /// Obtain the shielded transactions with indices in the range
+    /// `from..to`, keyed by their index. The transactions are queried
+    /// concurrently.
+    pub async fn fetch_shielded_transfer_range<C: Client + Sync>(
+        client: &C,
+        from: u64,
+        to: u64,
+    ) -> Result<
+        BTreeMap<u64, (Epoch, BlockHeight, TxIndex, Transfer, Transaction)>,
+        Error,
+    > {
+        let queries = (from..to).map(|i| async move {
+            Self::fetch_shielded_transfer(client, i)
+                .await
+                .map(|current_tx| (i, current_tx))
+        });
+        Ok(futures::future::try_join_all(queries)
+            .await?
+            .into_iter()
+            .collect())
     }
 
     /// Applies the given transaction to the supplied context. More precisely,
@@ -826,6 +1105,18 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
     ) -> Result<(), Error> {
         // For tracking the account changes caused by this Transaction
         let mut transaction_delta = TransactionDelta::new();
+        // Prepare the incoming viewing keys once for all the outputs
+        let ivks: Vec<_> = self
+            .pos_map
+            .keys()
+            .filter(|vk| {
+                // Notes cannot be sent to keys before their birthday
+                self.birthdays
+                    .get(vk)
+                    .map_or(true, |birthday| *birthday <= self.last_txidx)
+            })
+            .map(|vk| (*vk, PreparedIncomingViewingKey::new(&vk.ivk())))
+            .collect();
         // Listen for notes sent to our viewing keys
         for so in shielded
             .sapling_bundle()
@@ -850,59 +1141,47 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             self.witness_map.insert(note_pos, witness);
             // Let's try to see if any of our viewing keys can decrypt latest
             // note
-            let mut pos_map = HashMap::new();
-            std::mem::swap(&mut pos_map, &mut self.pos_map);
-            for (vk, notes) in pos_map.iter_mut() {
-                let decres = try_sapling_note_decryption::<_, OutputDescription<<<Authorized as Authorization>::SaplingAuth as masp_primitives::transaction::components::sapling::Authorization>::Proof>>(
-                    &NETWORK,
-                    1.into(),
-                    &PreparedIncomingViewingKey::new(&vk.ivk()),
-                    so,
+            let decres = trial_decrypt_output(&ivks, so);
+            // So this current viewing key does decrypt this current note...
+            if let Some((vk, note, diversifier, memo)) = decres {
+                // Add this note to list of notes decrypted by this viewing
+                // key
+                self.pos_map.entry(vk).or_default().insert(note_pos);
+                // Compute the nullifier now to quickly recognize when spent
+                let nf = note.nf(
+                    &vk.nk,
+                    note_pos.try_into().map_err(|_| {
+                        Error::Other("Can not get nullifier".to_string())
+                    })?,
                 );
-                // So this current viewing key does decrypt this current note...
-                if let Some((note, pa, memo)) = decres {
-                    // Add this note to list of notes decrypted by this viewing
-                    // key
-                    notes.insert(note_pos);
-                    // Compute the nullifier now to quickly recognize when spent
-                    let nf = note.nf(
-                        &vk.nk,
-                        note_pos.try_into().map_err(|_| {
-                            Error::Other("Can not get nullifier".to_string())
-                        })?,
-                    );
-                    self.note_map.insert(note_pos, note);
-                    self.memo_map.insert(note_pos, memo);
-                    // The payment address' diversifier is required to spend
-                    // note
-                    self.div_map.insert(note_pos, *pa.diversifier());
-                    self.nf_map.insert(nf, note_pos);
-                    // Note the account changes
-                    let balance = transaction_delta
-                        .entry(*vk)
-                        .or_insert_with(MaspAmount::default);
-                    *balance += self
-                        .decode_all_amounts(
-                            client,
-                            I128Sum::from_nonnegative(
-                                note.asset_type,
-                                note.value as i128,
-                            )
-                            .map_err(|()| {
-                                Error::Other(
-                                    "found note with invalid value or asset \
-                                     type"
-                                        .to_string(),
-                                )
-                            })?,
+                self.note_map.insert(note_pos, note);
+                self.memo_map.insert(note_pos, memo);
+                // The payment address' diversifier is required to spend
+                // note
+                self.div_map.insert(note_pos, diversifier);
+                self.nf_map.insert(nf, note_pos);
+                // Note the account changes
+                let balance = transaction_delta
+                    .entry(vk)
+                    .or_insert_with(MaspAmount::default);
+                *balance += self
+                    .decode_all_amounts(
+                        client,
+                        I128Sum::from_nonnegative(
+                            note.asset_type,
+                            note.value as i128,
                         )
-                        .await;
+                        .map_err(|()| {
+                            Error::Other(
+                                "found note with invalid value or asset type"
+                                    .to_string(),
+                            )
+                        })?,
+                    )
+                    .await;
 
-                    self.vk_map.insert(note_pos, *vk);
-                    break;
-                }
+                self.vk_map.insert(note_pos, vk);
             }
-            std::mem::swap(&mut pos_map, &mut self.pos_map);
         }
         // Cancel out those of our notes that have been spent
         for ss in shielded
@@ -1554,12 +1833,19 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
         let spending_key = spending_key.map(|x| x.into());
         let spending_keys: Vec<_> = spending_key.into_iter().collect();
         {
+            let birthdays = context.wallet().await.get_viewing_key_birthdays();
             // Load the current shielded context given the spending key we
             // possess
             let mut shielded = context.shielded_mut().await;
             let _ = shielded.load().await;
             shielded
-                .fetch(context.client(), &spending_keys, &[])
+                .fetch(
+                    context.client(),
+                    context.io(),
+                    &spending_keys,
+                    &[],
+                    &birthdays,
+                )
                 .await?;
             // Save the update state so that future fetches can be
             // short-circuited
@@ -1909,9 +2195,11 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
     pub async fn query_tx_deltas<C: Client + Sync>(
         &mut self,
         client: &C,
+        io: &impl Io,
         query_owner: &Either<BalanceOwner, Vec<Address>>,
         query_token: &Option<Address>,
         viewing_keys: &HashMap<String, ExtendedViewingKey>,
+        birthdays: &HashMap<ViewingKey, KeyBirthday>,
     ) -> Result<
         BTreeMap<
             (BlockHeight, TxIndex),
@@ -1926,7 +2214,7 @@ impl<U: ShieldedUtils + MaybeSend + MaybeSync> ShieldedContext<U> {
             .values()
             .map(|fvk| ExtendedFullViewingKey::from(*fvk).fvk.vk)
             .collect();
-        self.fetch(client, &[], &fvks).await?;
+        self.fetch(client, io, &[], &fvks, birthdays).await?;
         // Save the update state so that future fetches can be short-circuited
         let _ = self.save().await;
         // Required for filtering out rejected transactions from Tendermint
@@ -2040,6 +2328,37 @@ fn extract_payload(
     Ok(())
 }
 
+/// A note decrypted by one of our viewing keys, along with the diversifier of
+/// its payment address and its memo
+type DecryptedNote = (ViewingKey, Note, Diversifier, MemoBytes);
+
+/// Try to decrypt the given shielded output with each of the given prepared
+/// incoming viewing keys. Returns the first successful decryption, if any.
+fn trial_decrypt_output(
+    ivks: &[(ViewingKey, PreparedIncomingViewingKey)],
+    so: &OutputDescription<<<Authorized as Authorization>::SaplingAuth as masp_primitives::transaction::components::sapling::Authorization>::Proof>,
+) -> Option<DecryptedNote> {
+    let try_decrypt = |(vk, ivk): &(ViewingKey, PreparedIncomingViewingKey)| {
+        try_sapling_note_decryption::<_, OutputDescription<<<Authorized as Authorization>::SaplingAuth as masp_primitives::transaction::components::sapling::Authorization>::Proof>>(
+            &NETWORK,
+            1.into(),
+            ivk,
+            so,
+        )
+        .map(|(note, pa, memo)| (*vk, note, *pa.diversifier(), memo))
+    };
+    // With many viewing keys, trial decryption dominates the cost of a scan
+    #[cfg(feature = "multicore")]
+    {
+        use rayon::prelude::*;
+        ivks.par_iter().find_map_first(try_decrypt)
+    }
+    #[cfg(not(feature = "multicore"))]
+    {
+        ivks.iter().find_map(try_decrypt)
+    }
+}
+
 /// Make asset type corresponding to given address and epoch
 pub fn make_asset_type(
     epoch: Option<Epoch>,
@@ -2080,7 +2399,197 @@ fn convert_amount(
     Ok((asset_types, amount))
 }
 
+#[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::io::NullIo;
+    use crate::queries::testing::TestClient;
+    use crate::queries::RPC;
+
+    /// Shielded utils that neither load nor save the contexts
+    #[derive(Debug, Clone, Default, BorshSerialize, BorshDeserialize)]
+    struct TestUtils;
+
+    #[cfg_attr(feature = "async-send", async_trait::async_trait)]
+    #[cfg_attr(not(feature = "async-send"), async_trait::async_trait(?Send))]
+    impl ShieldedUtils for TestUtils {
+        /// Load a prover from the MASP parameters of the node and the client
+        fn local_tx_prover(&self) -> LocalTxProver {
+            let params_dir = get_params_dir();
+            LocalTxProver::new(
+                &params_dir.join(SPEND_NAME),
+                &params_dir.join(OUTPUT_NAME),
+                &params_dir.join(CONVERT_NAME),
+            )
+        }
+
+        async fn load<U: ShieldedUtils + MaybeSend>(
+            &self,
+            _ctx: &mut ShieldedContext<U>,
+        ) -> std::io::Result<()> {
+            Ok(())
+        }
+
+        async fn save<U: ShieldedUtils + MaybeSync>(
+            &self,
+            _ctx: &ShieldedContext<U>,
+        ) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    fn viewing_key(seed: u8) -> ViewingKey {
+        to_viewing_key(&ExtendedSpendingKey::master(&[seed; 32])).vk
+    }
+
+    /// Commit a shielded transaction without any notes at each of the given
+    /// heights, along with the note commitment tree
+    fn store_masp_txs(
+        client: &mut TestClient<RPC>,
+        heights: &[u64],
+        tree: &CommitmentTree<Node>,
+    ) {
+        use masp_primitives::consensus::{
+            BlockHeight as MaspBlockHeight, BranchId,
+        };
+        use masp_primitives::transaction::TxVersion;
+        use namada_core::ledger::storage_api::StorageWrite;
+        use namada_core::types::address::nam;
+
+        let transfer = Transfer {
+            source: MASP,
+            target: MASP,
+            token: nam(),
+            amount: token::DenominatedAmount::native(token::Amount::zero()),
+            key: None,
+            shielded: None,
+        };
+        let shielded = TransactionData::from_parts(
+            TxVersion::MASPv5,
+            BranchId::MASP,
+            0,
+            MaspBlockHeight::from_u32(0),
+            None,
+            None,
+        )
+        .freeze()
+        .unwrap();
+        let masp_key =
+            |seg: String| Key::from(MASP.to_db_key()).push(&seg).unwrap();
+        for (idx, height) in heights.iter().enumerate() {
+            let record = (
+                Epoch(0),
+                BlockHeight(*height),
+                TxIndex(idx as u32),
+                transfer.clone(),
+                shielded.clone(),
+            );
+            client
+                .wl_storage
+                .write(
+                    &masp_key(TX_KEY_PREFIX.to_owned() + &idx.to_string()),
+                    record,
+                )
+                .unwrap();
+        }
+        client
+            .wl_storage
+            .write(&masp_key(HEAD_TX_KEY.to_owned()), heights.len() as u64)
+            .unwrap();
+        client
+            .wl_storage
+            .write(&masp_key(MASP_NOTE_COMMITMENT_TREE_KEY.to_owned()), tree)
+            .unwrap();
+        client.wl_storage.commit_block().unwrap();
+    }
+
+    /// Test that a context is decoded back from its versioned encoding,
+    /// along with its pending contexts and key birthdays.
+    #[test]
+    fn test_shielded_context_versioned_roundtrip() {
+        let mut pending = ShieldedContext::<TestUtils>::default();
+        pending.last_txidx = 3;
+        pending.pos_map.insert(viewing_key(1), BTreeSet::new());
+        let mut ctx = ShieldedContext::<TestUtils>::default();
+        ctx.last_txidx = 7;
+        ctx.pos_map.insert(viewing_key(0), BTreeSet::from([0, 2]));
+        ctx.birthdays.insert(viewing_key(0), 5);
+        ctx.pending_ctxs.push(pending);
+
+        let bytes = ctx.serialize_versioned();
+        assert!(bytes.starts_with(SHIELDED_CONTEXT_MAGIC));
+        let decoded =
+            ShieldedContext::<TestUtils>::deserialize_versioned(&bytes)
+                .unwrap();
+        assert_eq!(decoded.serialize_to_vec(), ctx.serialize_to_vec());
+        assert_eq!(decoded.key_sync_progress(&viewing_key(1)), Some(3));
+
+        // An unknown version is rejected
+        let mut bytes = bytes;
+        bytes[SHIELDED_CONTEXT_MAGIC.len()] = SHIELDED_CONTEXT_VERSION + 1;
+        assert!(ShieldedContext::<TestUtils>::deserialize_versioned(&bytes)
+            .is_err());
+    }
+
+    /// Test that a context saved before the encoding was versioned is
+    /// migrated without pending contexts nor key birthdays.
+    #[test]
+    fn test_shielded_context_legacy_migration() {
+        let mut ctx = ShieldedContext::<TestUtils>::default();
+        ctx.last_txidx = 7;
+        ctx.pos_map.insert(viewing_key(0), BTreeSet::from([0, 2]));
+        ctx.spents.insert(2);
+        // The legacy encoding is the current one without the trailing
+        // lengths of the empty pending contexts and birthdays
+        let bytes = ctx.serialize_to_vec();
+        let legacy = &bytes[..bytes.len() - 8];
+
+        let migrated =
+            ShieldedContext::<TestUtils>::deserialize_versioned(legacy)
+                .unwrap();
+        assert_eq!(migrated.serialize_to_vec(), bytes);
+        assert_eq!(migrated.last_txidx, 7);
+        assert!(migrated.pending_ctxs.is_empty());
+        assert!(migrated.birthdays.is_empty());
+    }
+
+    /// Test that the pending contexts are merged once they are level with
+    /// the context, and that the sync progress of their keys is reported.
+    #[test]
+    fn test_merge_caught_up_ctxs() {
+        let mut ctx = ShieldedContext::<TestUtils>::default();
+        ctx.last_txidx = 4;
+        ctx.pos_map.insert(viewing_key(0), BTreeSet::new());
+        for (seed, last_txidx) in [(1, 2), (2, 4)] {
+            let mut pending = ShieldedContext::<TestUtils>::default();
+            pending.last_txidx = last_txidx;
+            pending.pos_map.insert(viewing_key(seed), BTreeSet::new());
+            ctx.pending_ctxs.push(pending);
+        }
+        assert_eq!(ctx.key_sync_progress(&viewing_key(0)), Some(4));
+        assert_eq!(ctx.key_sync_progress(&viewing_key(1)), Some(2));
+        assert_eq!(ctx.key_sync_progress(&viewing_key(3)), None);
+
+        ctx.merge_caught_up_ctxs();
+        assert_eq!(ctx.pending_ctxs.len(), 1);
+        assert!(ctx.pos_map.contains_key(&viewing_key(2)));
+        assert_eq!(ctx.key_sync_progress(&viewing_key(1)), Some(2));
+    }
+
+    /// Test that new keys are added to a context that is level with the
+    /// chain without scanning any transaction.
+    #[tokio::test]
+    async fn test_fetch_new_keys_without_txs() {
+        let client = TestClient::new(RPC);
+        let mut ctx = ShieldedContext::<TestUtils>::default();
+        let esk = ExtendedSpendingKey::master(&[0; 32]);
+        ctx.fetch(&client, &NullIo, &[esk], &[], &HashMap::new())
+            .await
+            .unwrap();
+        assert_eq!(ctx.key_sync_progress(&viewing_key(0)), Some(0));
+        assert!(ctx.pending_ctxs.is_empty());
+    }
+
     /// quick and dirty test. will fail on size check
     #[test]
     #[should_panic(expected = "parameter file size is not correct")]
@@ -2189,6 +2698,69 @@ mod tests {
             &fake_params_paths[2].0,
         );
     }
+
+    /// Test that a transaction index birthday starts from the first
+    /// transaction of its block, whose preceding note commitment tree is
+    /// known.
+    #[tokio::test]
+    async fn test_birthday_txidx() {
+        let mut client = TestClient::new(RPC);
+        store_masp_txs(&mut client, &[1, 1, 2, 4], &CommitmentTree::empty());
+        let txidx = |birthday| {
+            ShieldedContext::<TestUtils>::birthday_txidx(&client, birthday, 4)
+        };
+        assert_eq!(txidx(KeyBirthday::MaspTxIndex(1)).await.unwrap(), 0);
+        assert_eq!(txidx(KeyBirthday::MaspTxIndex(2)).await.unwrap(), 2);
+        assert_eq!(txidx(KeyBirthday::MaspTxIndex(9)).await.unwrap(), 4);
+        assert_eq!(
+            txidx(KeyBirthday::Height(BlockHeight(3))).await.unwrap(),
+            3
+        );
+        assert_eq!(
+            txidx(KeyBirthday::Height(BlockHeight(5))).await.unwrap(),
+            4
+        );
+    }
+
+    /// Test that a context without any keys starts fetching at the birthday
+    /// of a new key, from the note commitment tree of the ledger.
+    #[tokio::test]
+    async fn test_fetch_from_birthday() {
+        let mut client = TestClient::new(RPC);
+        let mut tree = CommitmentTree::<Node>::empty();
+        tree.append(Node::new([1; 32])).unwrap();
+        store_masp_txs(&mut client, &[1, 1, 2, 4], &tree);
+        let mut ctx = ShieldedContext::<TestUtils>::default();
+        let esk = ExtendedSpendingKey::master(&[0; 32]);
+        let birthdays = HashMap::from([(
+            viewing_key(0),
+            KeyBirthday::Height(BlockHeight(2)),
+        )]);
+        ctx.fetch(&client, &NullIo, &[esk], &[], &birthdays)
+            .await
+            .unwrap();
+        assert_eq!(ctx.key_sync_progress(&viewing_key(0)), Some(4));
+        assert!(ctx.pending_ctxs.is_empty());
+        assert_eq!(ctx.birthdays.get(&viewing_key(0)), Some(&2));
+        // The transactions before the birthday were not scanned
+        assert_eq!(ctx.tree.size(), tree.size());
+
+        // A key born before the scanned transactions catches up from its own
+        // birthday in a pending context
+        let birthdays =
+            HashMap::from([(viewing_key(1), KeyBirthday::MaspTxIndex(3))]);
+        let mut synced = ShieldedContext::<TestUtils> {
+            last_txidx: 4,
+            ..Default::default()
+        };
+        synced.pos_map.insert(viewing_key(0), BTreeSet::new());
+        synced
+            .fetch(&client, &NullIo, &[], &[viewing_key(1)], &birthdays)
+            .await
+            .unwrap();
+        assert_eq!(synced.key_sync_progress(&viewing_key(1)), Some(4));
+        assert!(synced.pending_ctxs.is_empty());
+    }
 }
 
 #[cfg(feature = "std")]
@@ -2277,7 +2849,7 @@ pub mod fs {
             // Fill the supplied context with the deserialized object
             *ctx = ShieldedContext {
                 utils: ctx.utils.clone(),
-                ..ShieldedContext::<U>::deserialize(&mut &bytes[..])?
+                ..ShieldedContext::<U>::deserialize_versioned(&bytes)?
             };
             Ok(())
         }
@@ -2300,10 +2872,7 @@ pub mod fs {
                     .write(true)
                     .create_new(true)
                     .open(tmp_path.clone())?;
-                let mut bytes = Vec::new();
-                ctx.serialize(&mut bytes)
-                    .expect("cannot serialize shielded context");
-                ctx_file.write_all(&bytes[..])?;
+                ctx_file.write_all(&ctx.serialize_versioned())?;
             }
             // Atomically update the old shielded context file with new data.
             // Atomicity is required to prevent other client instances from
@@ -1029,6 +1029,7 @@ mod test {
             vp_wasm_cache: (),
             tx_wasm_cache: (),
             storage_read_past_height_limit: None,
+            event_index: true,
         };
         let result = TEST_RPC.handle(ctx, &request);
         assert!(result.is_err());
@@ -8,10 +8,13 @@ use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
 use namada_core::hints;
+use namada_core::ledger::ibc::rate_limit::{
+    self, FlowAmounts, IbcRateLimit, RateLimit,
+};
 use namada_core::ledger::storage::traits::StorageHasher;
 use namada_core::ledger::storage::{DBIter, LastBlock, DB};
 use namada_core::ledger::storage_api::{self, ResultExt, StorageRead};
-use namada_core::types::account::{Account, AccountPublicKeysMap};
+use namada_core::types::account::Account;
 use namada_core::types::address::Address;
 use namada_core::types::hash::Hash;
 use namada_core::types::storage::{
@@ -22,6 +25,7 @@ use namada_core::types::token::MaspDenom;
 use namada_core::types::transaction::TxResult;
 
 use self::eth_bridge::{EthBridge, ETH_BRIDGE};
+use crate::events::index::{EventFilter, MAX_EVENTS_PER_QUERY};
 use crate::events::log::dumb_queries;
 use crate::events::{Event, EventType};
 use crate::ibc::core::host::types::identifiers::{
@@ -97,6 +101,10 @@ router! {SHELL,
     // was the transaction applied?
     ( "applied" / [tx_hash: Hash] ) -> Option<Event> = applied,
 
+    // Events emitted within a range of heights, read from the event index
+    ( "events" / [from: BlockHeight] / [to: BlockHeight] / [offset: u64] / [limit: u64] / [filter: opt EventFilter] )
+        -> Vec<(BlockHeight, Event)> = indexed_events,
+
     // Query account subspace
     ( "account" / [owner: Address] ) -> Option<Account> = account,
 
@@ -108,6 +116,13 @@ router! {SHELL,
 
     // IBC packet event
     ( "ibc_packet" / [event_type: EventType] / [source_port: PortId] / [source_channel: ChannelId] / [destination_port: PortId] / [destination_channel: ChannelId] / [sequence: Sequence]) -> Option<Event> = ibc_packet,
+
+    // Rate limits of the ICS-20 transfers
+    ( "ibc_rate_limits" ) -> Vec<IbcRateLimit> = ibc_rate_limits,
+
+    // Rate limit of a token through a channel with its current usage
+    ( "ibc_rate_limit_usage" / [channel_id: ChannelId] / [token: Address] )
+        -> Option<(RateLimit, FlowAmounts)> = ibc_rate_limit_usage,
 }
 
 // Handlers:
@@ -426,12 +441,16 @@ where
     H: 'static + StorageHasher + Sync,
 {
     let matcher = dumb_queries::QueryMatcher::accepted(tx_hash);
-    Ok(ctx
+    let event = ctx
         .event_log
         .iter_with_matcher(matcher)
         .by_ref()
         .next()
-        .cloned())
+        .cloned();
+    match event {
+        Some(event) => Ok(Some(event)),
+        None => indexed_tx_event(&ctx, tx_hash, EventType::Accepted),
+    }
 }
 
 fn applied<D, H, V, T>(
@@ -443,12 +462,92 @@ where
     H: 'static + StorageHasher + Sync,
 {
     let matcher = dumb_queries::QueryMatcher::applied(tx_hash);
-    Ok(ctx
+    let event = ctx
         .event_log
         .iter_with_matcher(matcher)
         .by_ref()
         .next()
-        .cloned())
+        .cloned();
+    match event {
+        Some(event) => Ok(Some(event)),
+        None => indexed_tx_event(&ctx, tx_hash, EventType::Applied),
+    }
+}
+
+fn indexed_events<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    from: BlockHeight,
+    to: BlockHeight,
+    offset: u64,
+    limit: u64,
+    filter: Option<EventFilter>,
+) -> storage_api::Result<Vec<(BlockHeight, Event)>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    if !ctx.event_index {
+        return Err(storage_api::Error::new(std::io::Error::new(
+            std::io::ErrorKind::Unsupported,
+            "The event index is disabled on this node (configured via \
+             `shell.event_index`).",
+        )));
+    }
+    let tag = filter.as_ref().map(EventFilter::tag);
+    ctx.wl_storage
+        .storage
+        .db
+        .read_indexed_events(
+            tag.as_deref(),
+            from,
+            to,
+            offset,
+            limit.min(MAX_EVENTS_PER_QUERY),
+        )
+        .into_storage_result()?
+        .into_iter()
+        .map(|(height, event)| {
+            Event::try_from_slice(&event)
+                .into_storage_result()
+                .map(|event| (height, event))
+        })
+        .collect()
+}
+
+/// Look up the event of the given type of a tx in the event index, in case it
+/// has already been pruned from the in-memory event log.
+fn indexed_tx_event<D, H, V, T>(
+    ctx: &RequestCtx<'_, D, H, V, T>,
+    tx_hash: Hash,
+    event_type: EventType,
+) -> storage_api::Result<Option<Event>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    if !ctx.event_index {
+        return Ok(None);
+    }
+    let tag = EventFilter::TxHash(tx_hash).tag();
+    for (_, event) in ctx
+        .wl_storage
+        .storage
+        .db
+        .read_indexed_events(
+            Some(&tag),
+            BlockHeight(0),
+            BlockHeight(u64::MAX),
+            0,
+            MAX_EVENTS_PER_QUERY,
+        )
+        .into_storage_result()?
+    {
+        let event = Event::try_from_slice(&event).into_storage_result()?;
+        if event.event_type == event_type {
+            return Ok(Some(event));
+        }
+    }
+    Ok(None)
 }
 
 fn ibc_client_update<D, H, V, T>(
@@ -501,6 +600,28 @@ where
         .cloned())
 }
 
+fn ibc_rate_limits<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> storage_api::Result<Vec<IbcRateLimit>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    rate_limit::read_rate_limits(ctx.wl_storage)
+}
+
+fn ibc_rate_limit_usage<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    channel_id: ChannelId,
+    token: Address,
+) -> storage_api::Result<Option<(RateLimit, FlowAmounts)>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    rate_limit::current_usage(ctx.wl_storage, &channel_id, &token)
+}
+
 fn account<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
     owner: Address,
@@ -512,15 +633,20 @@ where
     let account_exists = storage_api::account::exists(ctx.wl_storage, &owner)?;
 
     if account_exists {
-        let public_keys =
-            storage_api::account::public_keys(ctx.wl_storage, &owner)?;
+        let public_keys_map = storage_api::account::public_keys_index_map(
+            ctx.wl_storage,
+            &owner,
+        )?;
         let threshold =
             storage_api::account::threshold(ctx.wl_storage, &owner)?;
+        let key_options =
+            storage_api::account::key_options(ctx.wl_storage, &owner)?;
 
         Ok(Some(Account {
-            public_keys_map: AccountPublicKeysMap::from_iter(public_keys),
+            public_keys_map,
             address: owner,
             threshold: threshold.unwrap_or(1),
+            key_options,
         }))
     } else {
         Ok(None)
@@ -543,9 +669,15 @@ where
 
 #[cfg(test)]
 mod test {
+    use borsh::BorshDeserialize;
+    use namada_core::ledger::storage::testing::TestWlStorage;
+    use namada_core::tendermint::block;
+    use namada_core::types::storage::BlockHeight;
     use namada_core::types::{address, token};
 
-    use crate::queries::RPC;
+    use crate::events::log::EventLog;
+    use crate::events::Event;
+    use crate::queries::{RequestCtx, RequestQuery, Router, RPC};
 
     #[test]
     fn test_shell_queries_router_paths() {
@@ -566,5 +698,50 @@ mod test {
 
         let path = RPC.shell().storage_has_key_path(&key);
         assert_eq!(format!("/shell/has_key/{}", key), path);
+
+        let path = RPC.shell().indexed_events_path(
+            &BlockHeight(1),
+            &BlockHeight(10),
+            &0,
+            &100,
+            &None,
+        );
+        assert_eq!("/shell/events/1/10/0/100", path);
+    }
+
+    /// Test that the event index cannot be queried when it's disabled.
+    #[test]
+    fn test_indexed_events_disabled() {
+        let wl_storage = TestWlStorage::default();
+        let event_log = EventLog::default();
+        let request = RequestQuery {
+            path: RPC.shell().indexed_events_path(
+                &BlockHeight(1),
+                &BlockHeight(10),
+                &0,
+                &100,
+                &None,
+            ),
+            data: Default::default(),
+            height: block::Height::from(0_u32),
+            prove: Default::default(),
+        };
+        let ctx = |event_index| RequestCtx {
+            wl_storage: &wl_storage,
+            event_log: &event_log,
+            vp_wasm_cache: (),
+            tx_wasm_cache: (),
+            storage_read_past_height_limit: None,
+            event_index,
+        };
+
+        let err = RPC.handle(ctx(false), &request).unwrap_err();
+        assert!(err.to_string().contains("event index is disabled"));
+
+        let response = RPC.handle(ctx(true), &request).unwrap();
+        let events =
+            Vec::<(BlockHeight, Event)>::try_from_slice(&response.data)
+                .unwrap();
+        assert!(events.is_empty());
     }
 }
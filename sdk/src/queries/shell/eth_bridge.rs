@@ -7,7 +7,12 @@ use std::str::FromStr;
 use borsh::{BorshDeserialize, BorshSerialize};
 use borsh_ext::BorshSerializeExt;
 use namada_core::hints;
-use namada_core::ledger::eth_bridge::storage::bridge_pool::get_key_from_hash;
+use namada_core::ledger::eth_bridge::storage::bridge_pool::{
+    get_fee_bump_prefix, get_key_from_hash, is_fee_bump_key,
+};
+use namada_core::ledger::eth_bridge::storage::whitelist::{
+    self, WhitelistedErc20,
+};
 use namada_core::ledger::storage::merkle_tree::StoreRef;
 use namada_core::ledger::storage::{DBIter, StorageHasher, StoreType, DB};
 use namada_core::ledger::storage_api::{
@@ -155,6 +160,11 @@ router! {ETH_BRIDGE,
     ( "pool" / "signed_contents" )
         -> Vec<PendingTransfer> = read_signed_ethereum_bridge_pool,
 
+    // Get the gas fees added on top of the gas fees of the
+    // transfers in the Ethereum bridge pool.
+    ( "pool" / "fee_bumps" )
+        -> HashMap<KeccakHash, Amount> = read_bridge_pool_fee_bumps,
+
     // Generate a merkle proof for the inclusion of requested
     // transfers in the Ethereum bridge pool
     ( "pool" / "proof" )
@@ -215,6 +225,11 @@ router! {ETH_BRIDGE,
     // ERC20 token in Namada.
     ( "erc20" / "flow_control" / [asset: EthAddress] )
         -> Erc20FlowControl = get_erc20_flow_control,
+
+    // Read the whitelisted ERC20 tokens with their caps and the
+    // total supply of their wrapped tokens in Namada.
+    ( "erc20" / "whitelist" )
+        -> Vec<(WhitelistedErc20, Amount)> = read_erc20_whitelist,
 }
 
 /// Given a list of keccak hashes, check whether they have been
@@ -359,6 +374,27 @@ where
     })
 }
 
+/// Read the whitelisted ERC20 tokens with their caps and the
+/// total supply of their wrapped tokens in Namada.
+fn read_erc20_whitelist<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> storage_api::Result<Vec<(WhitelistedErc20, Amount)>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let ethbridge_queries = ctx.wl_storage.ethbridge_queries();
+    Ok(whitelist::read_whitelist(ctx.wl_storage)?
+        .into_iter()
+        .map(|erc20| {
+            let supply = ethbridge_queries
+                .get_token_supply(&erc20.token_address)
+                .unwrap_or_default();
+            (erc20, supply)
+        })
+        .collect())
+}
+
 /// Helper function to read a smart contract from storage.
 fn read_contract<T, D, H, V, U>(
     key: &Key,
@@ -440,6 +476,25 @@ where
     Ok(read_ethereum_bridge_pool_at_height(height, ctx))
 }
 
+/// Read the gas fees added on top of the gas fees of the transfers
+/// in the Ethereum bridge pool, by the hashes of the transfers.
+fn read_bridge_pool_fee_bumps<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> storage_api::Result<HashMap<KeccakHash, Amount>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    storage_api::iter_prefix(ctx.wl_storage, &get_fee_bump_prefix())?
+        .filter_map(|result| match result {
+            Ok((key, fee_bump)) => {
+                is_fee_bump_key(&key).map(|hash| Ok((hash, fee_bump)))
+            }
+            Err(err) => Some(Err(err)),
+        })
+        .collect()
+}
+
 /// Read the Ethereum bridge pool contents at a specified height.
 fn read_ethereum_bridge_pool_at_height<D, H, V, T>(
     height: BlockHeight,
@@ -841,7 +896,7 @@ mod test_ethbridge_router {
 
     use assert_matches::assert_matches;
     use namada_core::ledger::eth_bridge::storage::bridge_pool::{
-        get_pending_key, get_signed_root_key, BridgePoolTree,
+        get_fee_bump_key, get_pending_key, get_signed_root_key, BridgePoolTree,
     };
     use namada_core::ledger::eth_bridge::storage::whitelist;
     use namada_core::ledger::storage::mockdb::MockDBWriteBatch;
@@ -854,6 +909,7 @@ mod test_ethbridge_router {
     };
     use namada_core::types::ethereum_events::EthAddress;
     use namada_core::types::storage::BlockHeight;
+    use namada_core::types::token::DenominatedAmount;
     use namada_core::types::vote_extensions::validator_set_update;
     use namada_core::types::vote_extensions::validator_set_update::{
         EthAddrBook, VotingPowersMapExt,
@@ -1102,6 +1158,27 @@ mod test_ethbridge_router {
         assert_eq!(pool, Vec::from([transfer]));
     }
 
+    /// Test that the fee bumps of the transfers in the bridge pool
+    /// are read by the hashes of the transfers
+    #[tokio::test]
+    async fn test_read_bridge_pool_fee_bumps() {
+        let mut client = TestClient::new(RPC);
+        let hash = KeccakHash([1; 32]);
+        client
+            .wl_storage
+            .write(&get_fee_bump_key(&hash), Amount::from(10))
+            .expect("Test failed");
+        client.wl_storage.commit_block().expect("Test failed");
+
+        let fee_bumps = RPC
+            .shell()
+            .eth_bridge()
+            .read_bridge_pool_fee_bumps(&client)
+            .await
+            .unwrap();
+        assert_eq!(fee_bumps, HashMap::from([(hash, Amount::from(10))]));
+    }
+
     /// Test that reading the bridge pool always gets
     /// the latest pool
     #[tokio::test]
@@ -1719,6 +1796,47 @@ mod test_ethbridge_router {
         );
     }
 
+    /// Test reading the whitelisted ERC20 tokens with the supply of their
+    /// wrapped tokens.
+    #[tokio::test]
+    async fn test_read_erc20_whitelist() {
+        const ERC20_TOKEN: EthAddress = EthAddress([0; 20]);
+
+        let mut client = TestClient::new(RPC);
+        test_utils::init_default_storage(&mut client.wl_storage);
+
+        // the whitelist starts empty
+        let result =
+            RPC.shell().eth_bridge().read_erc20_whitelist(&client).await;
+        assert_matches!(result, Ok(whitelist) if whitelist.is_empty());
+
+        // whitelist a token and mint some of it
+        let erc20 = WhitelistedErc20 {
+            token_address: ERC20_TOKEN,
+            token_cap: Some(DenominatedAmount::new(
+                Amount::from_u64(1000),
+                6.into(),
+            )),
+        };
+        whitelist::write_whitelisted_erc20(&mut client.wl_storage, &erc20)
+            .expect("Test failed");
+        let supply = Amount::from_u64(400);
+        let key = whitelist::Key {
+            asset: ERC20_TOKEN,
+            suffix: whitelist::KeyType::WrappedSupply,
+        }
+        .into();
+        client.wl_storage.write(&key, supply).expect("Test failed");
+
+        let result = RPC
+            .shell()
+            .eth_bridge()
+            .read_erc20_whitelist(&client)
+            .await
+            .expect("Test failed");
+        assert_eq!(result, vec![(erc20, supply)]);
+    }
+
     /// Test that querying the status of the Bridge pool
     /// returns the expected keccak hashes.
     #[tokio::test]
@@ -28,6 +28,9 @@ where
     /// limit the how many block heights in the past can the storage be
     /// queried for reading values.
     pub storage_read_past_height_limit: Option<u64>,
+    /// Taken from config `event_index`. When not set, the event index is not
+    /// written to and cannot be queried.
+    pub event_index: bool,
 }
 
 /// A `Router` handles parsing read-only query requests and dispatching them to
@@ -1,10 +1,20 @@
 // cd shared && cargo expand ledger::queries::vp::governance
 
+use std::collections::BTreeMap;
+
 use namada_core::ledger::governance::parameters::GovernanceParameters;
-use namada_core::ledger::governance::storage::proposal::StorageProposal;
-use namada_core::ledger::governance::utils::Vote;
+use namada_core::ledger::governance::storage::proposal::{
+    StorageProposal, Upgrade,
+};
+use namada_core::ledger::governance::storage::vote::VoteRecord;
+use namada_core::ledger::governance::utils::{
+    ProposalResult, RepresentedVote, Vote, VoteOverride,
+};
 use namada_core::ledger::storage::{DBIter, StorageHasher, DB};
-use namada_core::ledger::storage_api;
+use namada_core::ledger::storage_api::{self, StorageRead};
+use namada_core::types::address::Address;
+use namada_core::types::token;
+use namada_proof_of_stake::storage::read_pos_params;
 
 use crate::queries::types::RequestCtx;
 
@@ -12,7 +22,23 @@ use crate::queries::types::RequestCtx;
 router! {GOV,
     ( "proposal" / [id: u64 ] ) -> Option<StorageProposal> = proposal_id,
     ( "proposal" / [id: u64 ] / "votes" ) -> Vec<Vote> = proposal_id_votes,
+    ( "proposal" / [id: u64 ] / "represented_votes" )
+        -> Vec<RepresentedVote> = proposal_id_represented_votes,
+    ( "proposal" / [id: u64 ] / "deposits" )
+        -> BTreeMap<Address, token::Amount> = proposal_id_deposits,
+    ( "proposal" / [id: u64 ] / "vote_history" / [voter: Address] )
+        -> Vec<VoteRecord> = proposal_id_vote_history,
+    ( "proposal" / [id: u64 ] / "tally" )
+        -> Option<ProposalResult> = proposal_id_tally,
+    ( "proposal" / [id: u64 ] / "overrides" )
+        -> Vec<VoteOverride> = proposal_id_overrides,
+    ( "voter" / [voter: Address] / "votes" )
+        -> BTreeMap<u64, VoteRecord> = voter_votes,
+    ( "representative" / [delegator: Address] )
+        -> Option<Address> = representative,
+    ( "represented" / [representative: Address] ) -> Vec<Address> = represented,
     ( "parameters" ) -> GovernanceParameters = parameters,
+    ( "pending_upgrade" ) -> Option<Upgrade> = pending_upgrade,
 }
 
 /// Find if the given address belongs to a validator account.
@@ -39,6 +65,138 @@ where
     storage_api::governance::get_proposal_votes(ctx.wl_storage, id)
 }
 
+/// Get the votes of the representatives for the delegators that delegated
+/// their voting power to them.
+fn proposal_id_represented_votes<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> storage_api::Result<Vec<RepresentedVote>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    storage_api::governance::get_represented_votes(ctx.wl_storage, id)
+}
+
+/// Get the deposits of the backers of a proposal.
+fn proposal_id_deposits<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> storage_api::Result<BTreeMap<Address, token::Amount>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    storage_api::governance::get_proposal_deposits(ctx.wl_storage, id)
+}
+
+/// Get the history of the votes of a voter on a proposal.
+fn proposal_id_vote_history<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+    voter: Address,
+) -> storage_api::Result<Vec<VoteRecord>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    storage_api::governance::get_vote_history(ctx.wl_storage, id, &voter)
+}
+
+/// Tally the votes of a proposal with the stake of the current epoch. The
+/// validators vote with the stake delegated to them, but for the bonds of the
+/// delegators that voted directly or by their representatives.
+fn proposal_id_tally<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> storage_api::Result<Option<ProposalResult>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let proposal = match storage_api::governance::get_proposal_by_id(
+        ctx.wl_storage,
+        id,
+    )? {
+        Some(proposal) => proposal,
+        None => return Ok(None),
+    };
+    let epoch = ctx.wl_storage.get_block_epoch()?;
+    let is_steward =
+        storage_api::pgf::is_steward(ctx.wl_storage, &proposal.author)?;
+    let params = read_pos_params(ctx.wl_storage)?;
+    storage_api::governance::tally_proposal(
+        ctx.wl_storage,
+        &params,
+        id,
+        &proposal.r#type,
+        is_steward,
+        epoch,
+    )
+    .map(Some)
+}
+
+/// Get the delegators whose vote on a proposal differs from the vote of
+/// their validators, which then don't vote with their bonds.
+fn proposal_id_overrides<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    id: u64,
+) -> storage_api::Result<Vec<VoteOverride>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    let params = read_pos_params(ctx.wl_storage)?;
+    Ok(storage_api::governance::get_vote_overrides(
+        ctx.wl_storage,
+        &params,
+        id,
+    )?
+    .into_iter()
+    .filter(|vote| vote.validator_data.as_ref() != Some(&vote.data))
+    .collect())
+}
+
+/// Get the current vote of a voter on each of the proposals it voted on.
+fn voter_votes<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    voter: Address,
+) -> storage_api::Result<BTreeMap<u64, VoteRecord>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    storage_api::governance::get_voter_votes(ctx.wl_storage, &voter)
+}
+
+/// Get the representative to which the delegator delegated its voting power.
+fn representative<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    delegator: Address,
+) -> storage_api::Result<Option<Address>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    storage_api::governance::get_representative(ctx.wl_storage, &delegator)
+}
+
+/// Get the delegators that delegated their voting power to the
+/// representative.
+fn represented<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+    representative: Address,
+) -> storage_api::Result<Vec<Address>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    storage_api::governance::get_represented_delegators(
+        ctx.wl_storage,
+        &representative,
+    )
+}
+
 /// Get the governane parameters
 fn parameters<D, H, V, T>(
     ctx: RequestCtx<'_, D, H, V, T>,
@@ -49,3 +207,14 @@ where
 {
     storage_api::governance::get_parameters(ctx.wl_storage)
 }
+
+/// Get the upgrade scheduled by an accepted upgrade proposal, if any
+fn pending_upgrade<D, H, V, T>(
+    ctx: RequestCtx<'_, D, H, V, T>,
+) -> storage_api::Result<Option<Upgrade>>
+where
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+{
+    storage_api::governance::get_pending_upgrade(ctx.wl_storage)
+}
@@ -9,9 +9,18 @@ use borsh::BorshDeserialize;
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::merkle_tree::MerklePath;
 use masp_primitives::sapling::Node;
+use namada_core::ledger::eth_bridge::storage::whitelist::WhitelistedErc20;
 use namada_core::ledger::governance::parameters::GovernanceParameters;
-use namada_core::ledger::governance::storage::proposal::StorageProposal;
-use namada_core::ledger::governance::utils::Vote;
+use namada_core::ledger::governance::storage::proposal::{
+    StorageProposal, Upgrade,
+};
+use namada_core::ledger::governance::storage::vote::VoteRecord;
+use namada_core::ledger::governance::utils::{
+    ProposalResult, RepresentedVote, Vote, VoteOverride,
+};
+use namada_core::ledger::ibc::rate_limit::{
+    FlowAmounts, IbcRateLimit, RateLimit,
+};
 use namada_core::ledger::ibc::storage::{
     ibc_denom_key, ibc_denom_key_prefix, is_ibc_denom_key,
 };
@@ -38,6 +47,7 @@ use crate::args::InputAmount;
 use crate::control_flow::time;
 use crate::error::{EncodingError, Error, QueryError, TxError};
 use crate::events::Event;
+use crate::ibc::core::host::types::identifiers::ChannelId;
 use crate::internal_macros::echo_error;
 use crate::io::Io;
 use crate::proto::Tx;
@@ -292,6 +302,37 @@ pub async fn query_masp_reward_tokens<C: crate::queries::Client + Sync>(
     convert_response::<C, _>(RPC.shell().masp_reward_tokens(client).await)
 }
 
+/// Query the rate limits of the ICS-20 transfers
+pub async fn query_ibc_rate_limits<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<Vec<IbcRateLimit>, Error> {
+    convert_response::<C, _>(RPC.shell().ibc_rate_limits(client).await)
+}
+
+/// Query the rate limit of a token through a channel with the total flows
+/// over its window ending at the current epoch
+pub async fn query_ibc_rate_limit_usage<C: crate::queries::Client + Sync>(
+    client: &C,
+    channel_id: &ChannelId,
+    token: &Address,
+) -> Result<Option<(RateLimit, FlowAmounts)>, Error> {
+    convert_response::<C, _>(
+        RPC.shell()
+            .ibc_rate_limit_usage(client, channel_id, token)
+            .await,
+    )
+}
+
+/// Query the whitelisted ERC20 tokens of the Ethereum bridge with their caps
+/// and the total supply of their wrapped tokens in Namada
+pub async fn query_erc20_whitelist<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<Vec<(WhitelistedErc20, token::Amount)>, Error> {
+    convert_response::<C, _>(
+        RPC.shell().eth_bridge().read_erc20_whitelist(client).await,
+    )
+}
+
 /// Query a wasm code hash
 pub async fn query_wasm_code_hash(
     context: &impl Namada,
@@ -1000,6 +1041,112 @@ pub async fn query_proposal_votes<C: crate::queries::Client + Sync>(
     )
 }
 
+/// Get the votes of the representatives on a proposal for the delegators that
+/// delegated their voting power to them
+pub async fn query_proposal_represented_votes<
+    C: crate::queries::Client + Sync,
+>(
+    client: &C,
+    proposal_id: u64,
+) -> Result<Vec<RepresentedVote>, error::Error> {
+    convert_response::<C, Vec<RepresentedVote>>(
+        RPC.vp()
+            .gov()
+            .proposal_id_represented_votes(client, &proposal_id)
+            .await,
+    )
+}
+
+/// Get the history of the votes of a voter on a proposal
+pub async fn query_vote_history<C: crate::queries::Client + Sync>(
+    client: &C,
+    proposal_id: u64,
+    voter: &Address,
+) -> Result<Vec<VoteRecord>, error::Error> {
+    convert_response::<C, Vec<VoteRecord>>(
+        RPC.vp()
+            .gov()
+            .proposal_id_vote_history(client, &proposal_id, voter)
+            .await,
+    )
+}
+
+/// Get the latest votes of a voter, indexed by proposal id
+pub async fn query_voter_votes<C: crate::queries::Client + Sync>(
+    client: &C,
+    voter: &Address,
+) -> Result<BTreeMap<u64, VoteRecord>, error::Error> {
+    convert_response::<C, BTreeMap<u64, VoteRecord>>(
+        RPC.vp().gov().voter_votes(client, voter).await,
+    )
+}
+
+/// Get the current tally of a proposal, including the voting power that
+/// delegators inherit from their validator
+pub async fn query_proposal_tally<C: crate::queries::Client + Sync>(
+    client: &C,
+    proposal_id: u64,
+) -> Result<Option<ProposalResult>, error::Error> {
+    convert_response::<C, Option<ProposalResult>>(
+        RPC.vp().gov().proposal_id_tally(client, &proposal_id).await,
+    )
+}
+
+/// Get the votes of the delegators that differ from their validator's vote
+pub async fn query_vote_overrides<C: crate::queries::Client + Sync>(
+    client: &C,
+    proposal_id: u64,
+) -> Result<Vec<VoteOverride>, error::Error> {
+    convert_response::<C, Vec<VoteOverride>>(
+        RPC.vp()
+            .gov()
+            .proposal_id_overrides(client, &proposal_id)
+            .await,
+    )
+}
+
+/// Get the deposits of the backers of a proposal
+pub async fn query_proposal_deposits<C: crate::queries::Client + Sync>(
+    client: &C,
+    proposal_id: u64,
+) -> Result<BTreeMap<Address, token::Amount>, error::Error> {
+    convert_response::<C, BTreeMap<Address, token::Amount>>(
+        RPC.vp()
+            .gov()
+            .proposal_id_deposits(client, &proposal_id)
+            .await,
+    )
+}
+
+/// Get the representative to which a delegator delegated its voting power
+pub async fn query_vote_representative<C: crate::queries::Client + Sync>(
+    client: &C,
+    delegator: &Address,
+) -> Result<Option<Address>, error::Error> {
+    convert_response::<C, Option<Address>>(
+        RPC.vp().gov().representative(client, delegator).await,
+    )
+}
+
+/// Get the delegators that delegated their voting power to a representative
+pub async fn query_represented_delegators<C: crate::queries::Client + Sync>(
+    client: &C,
+    representative: &Address,
+) -> Result<Vec<Address>, error::Error> {
+    convert_response::<C, Vec<Address>>(
+        RPC.vp().gov().represented(client, representative).await,
+    )
+}
+
+/// Get the upgrade scheduled by an accepted upgrade proposal, if any
+pub async fn query_pending_upgrade<C: crate::queries::Client + Sync>(
+    client: &C,
+) -> Result<Option<Upgrade>, error::Error> {
+    convert_response::<C, Option<Upgrade>>(
+        RPC.vp().gov().pending_upgrade(client).await,
+    )
+}
+
 /// Get the bond amount at the given epoch
 pub async fn get_bond_amount_at<C: crate::queries::Client + Sync>(
     client: &C,
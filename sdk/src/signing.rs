@@ -4,7 +4,7 @@ use std::fmt::Display;
 
 use borsh::BorshDeserialize;
 use borsh_ext::BorshSerializeExt;
-use data_encoding::HEXLOWER;
+use data_encoding::{HEXLOWER, HEXUPPER};
 use itertools::Itertools;
 use masp_primitives::asset_type::AssetType;
 use masp_primitives::transaction::components::sapling::fees::{
@@ -12,10 +12,13 @@ use masp_primitives::transaction::components::sapling::fees::{
 };
 use namada_core::ledger::parameters::storage as parameter_storage;
 use namada_core::proto::SignatureIndex;
-use namada_core::types::account::AccountPublicKeysMap;
+use namada_core::types::account::{
+    Account, AccountKeyOptions, AccountPublicKeysMap, KeyRole,
+};
 use namada_core::types::address::{
     masp_tx_key, Address, ImplicitAddress, InternalAddress, MASP,
 };
+use namada_core::types::hash::Hash;
 use namada_core::types::key::*;
 use namada_core::types::masp::{ExtendedViewingKey, PaymentAddress};
 use namada_core::types::storage::Epoch;
@@ -23,9 +26,11 @@ use namada_core::types::token;
 use namada_core::types::token::Transfer;
 // use namada_core::types::storage::Key;
 use namada_core::types::token::{Amount, DenominatedAmount, MaspDenom};
-use namada_core::types::transaction::account::{InitAccount, UpdateAccount};
+use namada_core::types::transaction::account::{
+    InitAccount, UpdateAccount, UpdateSpendingPolicy,
+};
 use namada_core::types::transaction::governance::{
-    InitProposalData, VoteProposalData,
+    DepositProposalData, InitProposalData, VoteDelegationData, VoteProposalData,
 };
 use namada_core::types::transaction::pgf::UpdateStewardCommission;
 use namada_core::types::transaction::pos::BecomeValidator;
@@ -42,7 +47,9 @@ use crate::core::ledger::governance::storage::proposal::ProposalType;
 use crate::core::ledger::governance::storage::vote::{
     StorageProposalVote, VoteType,
 };
-use crate::core::types::eth_bridge_pool::PendingTransfer;
+use crate::core::types::eth_bridge_pool::{
+    BumpPendingTransferFee, CancelPendingTransfer, PendingTransfer,
+};
 use crate::error::{EncodingError, Error, TxError};
 use crate::ibc::apps::transfer::types::msgs::transfer::MsgTransfer;
 use crate::ibc::primitives::proto::Any;
@@ -51,12 +58,13 @@ use crate::masp::make_asset_type;
 use crate::proto::{MaspBuilder, Section, Tx};
 use crate::rpc::validate_amount;
 use crate::tx::{
-    TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_WASM,
-    TX_CHANGE_COMMISSION_WASM, TX_CHANGE_CONSENSUS_KEY_WASM,
-    TX_CHANGE_METADATA_WASM, TX_CLAIM_REWARDS_WASM,
-    TX_DEACTIVATE_VALIDATOR_WASM, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM,
-    TX_INIT_PROPOSAL, TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM,
-    TX_RESIGN_STEWARD, TX_REVEAL_PK, TX_TRANSFER_WASM, TX_UNBOND_WASM,
+    TX_BECOME_VALIDATOR_WASM, TX_BOND_WASM, TX_BRIDGE_POOL_BUMP_FEE_WASM,
+    TX_BRIDGE_POOL_CANCEL_WASM, TX_BRIDGE_POOL_WASM, TX_CHANGE_COMMISSION_WASM,
+    TX_CHANGE_CONSENSUS_KEY_WASM, TX_CHANGE_METADATA_WASM,
+    TX_CLAIM_REWARDS_WASM, TX_DEACTIVATE_VALIDATOR_WASM, TX_DELEGATE_VOTE,
+    TX_DEPOSIT_PROPOSAL, TX_IBC_WASM, TX_INIT_ACCOUNT_WASM, TX_INIT_PROPOSAL,
+    TX_REACTIVATE_VALIDATOR_WASM, TX_REDELEGATE_WASM, TX_RESIGN_STEWARD,
+    TX_REVEAL_PK, TX_SPENDING_POLICY_WASM, TX_TRANSFER_WASM, TX_UNBOND_WASM,
     TX_UNJAIL_VALIDATOR_WASM, TX_UPDATE_ACCOUNT_WASM,
     TX_UPDATE_STEWARD_COMMISSION, TX_VOTE_PROPOSAL, TX_WITHDRAW_WASM,
     VP_USER_WASM,
@@ -76,6 +84,8 @@ pub struct SigningTxData {
     pub threshold: u8,
     /// The public keys to index map associated to an account
     pub account_public_keys_map: Option<AccountPublicKeysMap>,
+    /// The weights and roles of the public keys of an account
+    pub key_options: BTreeMap<u8, AccountKeyOptions>,
     /// The public keys of the fee payer
     pub fee_payer: common::PublicKey,
 }
@@ -235,39 +245,80 @@ where
         tx.add_signatures(signatures);
     }
 
+    // Only the keys allowed to sign the tx for its role can be used, and no
+    // more keys are needed once the weight of the signatures meets the
+    // threshold of the owner
+    let account_public_keys_map =
+        signing_data
+            .account_public_keys_map
+            .map(|account_public_keys_map| {
+                account_public_keys_map.with_key_options(
+                    &signing_data.key_options,
+                    tx_key_role(tx),
+                )
+            });
+    let can_sign = |public_key: &common::PublicKey| match (
+        &signing_data.owner,
+        &account_public_keys_map,
+    ) {
+        (Some(_), Some(account_public_keys_map)) => account_public_keys_map
+            .get_index_from_public_key(public_key)
+            .is_some(),
+        _ => true,
+    };
+    let is_complete = |used_pubkeys: &HashSet<common::PublicKey>| match (
+        &signing_data.owner,
+        &account_public_keys_map,
+    ) {
+        (Some(_), Some(account_public_keys_map))
+            if signing_data.threshold > 0 =>
+        {
+            let signed_weight = account_public_keys_map.total_weight(
+                used_pubkeys.iter().filter_map(|public_key| {
+                    account_public_keys_map.pk_to_idx.get(public_key)
+                }),
+            );
+            signed_weight >= u32::from(signing_data.threshold)
+        }
+        _ => false,
+    };
+
     // Then try to sign the raw header with private keys in the software wallet
-    if let Some(account_public_keys_map) = signing_data.account_public_keys_map
-    {
+    if let Some(account_public_keys_map) = &account_public_keys_map {
         let mut wallet = wallet.write().await;
-        let signing_tx_keypairs = signing_data
-            .public_keys
-            .iter()
-            .filter_map(|public_key| {
-                if used_pubkeys.contains(public_key) {
-                    None
-                } else {
-                    match find_key_by_pk(&mut wallet, args, public_key) {
-                        Ok(secret_key) => {
-                            used_pubkeys.insert(public_key.clone());
-                            Some(secret_key)
-                        }
-                        Err(_) => None,
-                    }
-                }
-            })
-            .collect::<Vec<common::SecretKey>>();
+        let mut signing_tx_keypairs = vec![];
+        for public_key in &signing_data.public_keys {
+            if is_complete(&used_pubkeys) {
+                break;
+            }
+            if used_pubkeys.contains(public_key) || !can_sign(public_key) {
+                continue;
+            }
+            if let Ok(secret_key) =
+                find_key_by_pk(&mut wallet, args, public_key)
+            {
+                used_pubkeys.insert(public_key.clone());
+                signing_tx_keypairs.push(secret_key);
+            }
+        }
         if !signing_tx_keypairs.is_empty() {
             tx.sign_raw(
                 signing_tx_keypairs,
-                account_public_keys_map,
-                signing_data.owner,
+                account_public_keys_map.clone(),
+                signing_data.owner.clone(),
             );
         }
     }
 
     // Then try to sign the raw header using the hardware wallet
     for pubkey in signing_data.public_keys {
-        if !used_pubkeys.contains(&pubkey) && pubkey != signing_data.fee_payer {
+        if is_complete(&used_pubkeys) {
+            break;
+        }
+        if !used_pubkeys.contains(&pubkey)
+            && pubkey != signing_data.fee_payer
+            && can_sign(&pubkey)
+        {
             if let Ok(ntx) = sign(
                 tx.clone(),
                 pubkey.clone(),
@@ -307,6 +358,209 @@ where
     Ok(())
 }
 
+/// The role that the keys of an account must be allowed to sign for to
+/// authorize the given transaction. Transactions without a role can only be
+/// signed by the keys without any role.
+pub fn tx_key_role(tx: &Tx) -> Option<KeyRole> {
+    let tag = tx.get_section(tx.code_sechash())?.code_sec()?.tag?;
+    match tag.as_str() {
+        TX_BOND_WASM
+        | TX_UNBOND_WASM
+        | TX_REDELEGATE_WASM
+        | TX_WITHDRAW_WASM
+        | TX_CLAIM_REWARDS_WASM => Some(KeyRole::Staking),
+        TX_VOTE_PROPOSAL | TX_DELEGATE_VOTE => Some(KeyRole::Governance),
+        _ => None,
+    }
+}
+
+/// A key of a multisignature account whose signature is requested
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RequestedSigner {
+    /// The index of the public key in the account
+    pub index: u8,
+    /// The public key
+    pub public_key: common::PublicKey,
+    /// The weight of the key's signature
+    pub weight: u8,
+}
+
+/// A portable request for the signatures of the keys of a multisignature
+/// account over a transaction. It is handed over from one signer to the next,
+/// each of them appending their signatures offline, until the weight of the
+/// collected signatures meets the account threshold.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SigningRequest {
+    /// The hex encoded transaction
+    pub tx: String,
+    /// The hashes of the sections of the transaction to be signed
+    pub section_hashes: Vec<Hash>,
+    /// The account on behalf of which the transaction is signed
+    pub owner: Address,
+    /// The keys of the account
+    pub signers: Vec<RequestedSigner>,
+    /// The threshold of the account when the request was created
+    pub threshold: u8,
+    /// The signatures collected so far
+    pub signatures: Vec<SignatureIndex>,
+}
+
+impl SigningRequest {
+    /// Request the signatures of the keys of the given account over the
+    /// given transaction
+    pub fn new(tx: &Tx, account: &Account) -> Self {
+        let signers = account
+            .public_keys_map
+            .idx_to_pk
+            .iter()
+            .map(|(index, public_key)| RequestedSigner {
+                index: *index,
+                public_key: public_key.clone(),
+                weight: key_weight(account, *index),
+            })
+            .sorted_by_key(|signer| signer.index)
+            .collect();
+        Self {
+            tx: tx.serialize(),
+            section_hashes: vec![tx.raw_header_hash()],
+            owner: account.address.clone(),
+            signers,
+            threshold: account.threshold,
+            signatures: vec![],
+        }
+    }
+
+    /// Decode the requested transaction
+    pub fn tx(&self) -> Result<Tx, Error> {
+        let bytes = HEXUPPER
+            .decode(self.tx.as_bytes())
+            .map_err(|err| EncodingError::Decoding(err.to_string()))?;
+        Tx::try_from_slice(&bytes)
+            .map_err(|err| EncodingError::Decoding(err.to_string()).into())
+    }
+
+    /// Check whether the request already holds a signature of the given key
+    pub fn is_signed_by(&self, public_key: &common::PublicKey) -> bool {
+        self.signatures
+            .iter()
+            .any(|signature| signature.pubkey == *public_key)
+    }
+
+    /// Sign the transaction with the given secret keys. The keys that do not
+    /// belong to the account or that already signed are skipped. Return the
+    /// public keys of the added signatures.
+    pub fn sign(
+        &mut self,
+        secret_keys: &[common::SecretKey],
+    ) -> Result<Vec<common::PublicKey>, Error> {
+        let tx = self.tx()?;
+        let public_keys_map = AccountPublicKeysMap {
+            pk_to_idx: self
+                .signers
+                .iter()
+                .map(|signer| (signer.public_key.clone(), signer.index))
+                .collect(),
+            idx_to_pk: self
+                .signers
+                .iter()
+                .map(|signer| (signer.index, signer.public_key.clone()))
+                .collect(),
+            idx_to_weight: HashMap::new(),
+        };
+        let secret_keys = secret_keys
+            .iter()
+            .filter(|secret_key| !self.is_signed_by(&secret_key.ref_to()))
+            .cloned()
+            .collect::<Vec<_>>();
+        let signatures = tx.compute_section_signature(
+            &secret_keys,
+            &public_keys_map,
+            Some(self.owner.clone()),
+        );
+        let signed = signatures
+            .iter()
+            .map(|signature| signature.pubkey.clone())
+            .collect();
+        self.signatures.extend(signatures);
+        Ok(signed)
+    }
+
+    /// Add the signatures of another request over the same transaction
+    pub fn merge(&mut self, other: SigningRequest) -> Result<(), Error> {
+        if self.tx != other.tx || self.owner != other.owner {
+            return Err(Error::Other(
+                "The signing requests are for different transactions or \
+                 accounts"
+                    .to_string(),
+            ));
+        }
+        for signature in other.signatures {
+            if !self.is_signed_by(&signature.pubkey) {
+                self.signatures.push(signature);
+            }
+        }
+        Ok(())
+    }
+
+    /// Sum the weights of the valid signatures of the request, according to
+    /// the given state of the account. The key roles are not checked here,
+    /// as they depend on the transaction and are enforced by the VPs.
+    pub fn signed_weight(&self, account: &Account) -> Result<u32, Error> {
+        let tx = self.tx()?;
+        if self.section_hashes != [tx.raw_header_hash()] {
+            return Err(Error::Other(
+                "The section hashes of the signing request do not match its \
+                 transaction"
+                    .to_string(),
+            ));
+        }
+        // The signatures are made over the raw hash of the signature section
+        // that will hold them
+        let signed_hash = crate::proto::Signature {
+            targets: self.section_hashes.clone(),
+            signer: crate::proto::Signer::PubKeys(vec![]),
+            signatures: BTreeMap::new(),
+        }
+        .get_raw_hash();
+        let signed_indices = self
+            .signatures
+            .iter()
+            .filter_map(|signature| {
+                let (owner, index) = signature.index.as_ref()?;
+                let valid = *owner == account.address
+                    && account.get_public_key_from_index(*index).as_ref()
+                        == Some(&signature.pubkey)
+                    && common::SigScheme::verify_signature(
+                        &signature.pubkey,
+                        &signed_hash,
+                        &signature.signature,
+                    )
+                    .is_ok();
+                valid.then_some(*index)
+            })
+            .collect::<HashSet<u8>>();
+        Ok(signed_indices
+            .into_iter()
+            .map(|index| u32::from(key_weight(account, index)))
+            .sum())
+    }
+
+    /// Check whether the valid signatures of the request meet the threshold
+    /// of the given account
+    pub fn is_complete(&self, account: &Account) -> Result<bool, Error> {
+        Ok(self.signed_weight(account)? >= u32::from(account.threshold))
+    }
+}
+
+fn key_weight(account: &Account, index: u8) -> u8 {
+    account
+        .key_options
+        .get(&index)
+        .map_or(AccountKeyOptions::default().weight, |options| {
+            options.weight
+        })
+}
+
 /// Return the necessary data regarding an account to be able to generate a
 /// multisignature section
 pub async fn aux_signing_data(
@@ -321,12 +575,16 @@ pub async fn aux_signing_data(
         vec![]
     };
 
-    let (account_public_keys_map, threshold) = match &owner {
+    let (account_public_keys_map, threshold, key_options) = match &owner {
         Some(owner @ Address::Established(_)) => {
             let account =
                 rpc::get_account_info(context.client(), owner).await?;
             if let Some(account) = account {
-                (Some(account.public_keys_map), account.threshold)
+                (
+                    Some(account.public_keys_map),
+                    account.threshold,
+                    account.key_options,
+                )
             } else {
                 return Err(Error::from(TxError::InvalidAccount(
                     owner.encode(),
@@ -336,16 +594,17 @@ pub async fn aux_signing_data(
         Some(Address::Implicit(_)) => (
             Some(AccountPublicKeysMap::from_iter(public_keys.clone())),
             1u8,
+            BTreeMap::new(),
         ),
         Some(owner @ Address::Internal(internal)) => match internal {
-            InternalAddress::Masp => (None, 0u8),
+            InternalAddress::Masp => (None, 0u8, BTreeMap::new()),
             _ => {
                 return Err(Error::from(TxError::InvalidAccount(
                     owner.encode(),
                 )));
             }
         },
-        None => (None, 0u8),
+        None => (None, 0u8, BTreeMap::new()),
     };
 
     let fee_payer = if args.disposable_signing_key {
@@ -374,6 +633,7 @@ pub async fn aux_signing_data(
         public_keys,
         threshold,
         account_public_keys_map,
+        key_options,
         fee_payer,
     })
 }
@@ -419,6 +679,7 @@ pub async fn init_validator_signing_data(
         public_keys,
         threshold: 0,
         account_public_keys_map,
+        key_options: BTreeMap::new(),
         fee_payer,
     })
 }
@@ -893,6 +1154,31 @@ fn to_ledger_decimal(amount: &str) -> String {
     }
 }
 
+/// Format the options of the public keys of an account for the Ledger, by
+/// key index
+fn format_key_options(key_options: &[AccountKeyOptions]) -> Vec<String> {
+    key_options
+        .iter()
+        .enumerate()
+        .map(|(index, options)| {
+            let roles = if options.roles.is_empty() {
+                "any".to_string()
+            } else {
+                options
+                    .roles
+                    .iter()
+                    .map(KeyRole::to_string)
+                    .collect::<Vec<_>>()
+                    .join("+")
+            };
+            format!(
+                "Key {} : weight {}, roles {}",
+                index, options.weight, roles
+            )
+        })
+        .collect()
+}
+
 /// A ProposalVote wrapper that prints the spending cap with Ledger decimal
 /// formatting.
 struct LedgerProposalVote<'a>(&'a StorageProposalVote);
@@ -910,6 +1196,7 @@ impl<'a> Display for LedgerProposalVote<'a> {
 
             StorageProposalVote::Nay => write!(f, "nay"),
             StorageProposalVote::Abstain => write!(f, "abstain"),
+            StorageProposalVote::NoWithVeto => write!(f, "no-with-veto"),
         }
     }
 }
@@ -934,6 +1221,14 @@ impl<'a> Display for LedgerProposalType<'a> {
             }
             ProposalType::PGFSteward(_) => write!(f, "PGF Steward"),
             ProposalType::PGFPayment(_) => write!(f, "PGF Payment"),
+            ProposalType::Emergency(None) => write!(f, "Emergency"),
+            ProposalType::Emergency(Some(hash)) => write!(
+                f,
+                "Emergency {}",
+                LedgerProposalType(&ProposalType::Default(Some(*hash)), self.1)
+            ),
+            ProposalType::ParameterChange(_) => write!(f, "Parameter change"),
+            ProposalType::Upgrade(upgrade) => write!(f, "Upgrade {}", upgrade),
         }
     }
 }
@@ -1113,6 +1408,12 @@ pub async fn to_ledger_vector(
                 init_proposal_data.voting_end_epoch
             ),
             format!("Grace epoch : {}", init_proposal_data.grace_epoch),
+            format!(
+                "Deposit : NAM {}",
+                to_ledger_decimal(
+                    &init_proposal_data.deposit.to_string_native()
+                )
+            ),
             format!("Content : {}", HEXLOWER.encode(&extra.0)),
         ]);
 
@@ -1134,8 +1435,25 @@ pub async fn to_ledger_vector(
                 init_proposal_data.voting_end_epoch
             ),
             format!("Grace epoch : {}", init_proposal_data.grace_epoch),
+            format!(
+                "Deposit : NAM {}",
+                to_ledger_decimal(
+                    &init_proposal_data.deposit.to_string_native()
+                )
+            ),
             format!("Content : {}", HEXLOWER.encode(&extra.0)),
         ]);
+
+        if let ProposalType::ParameterChange(changes) =
+            &init_proposal_data.r#type
+        {
+            let changes = changes
+                .iter()
+                .map(|change| format!("Parameter change : {}", change))
+                .collect::<Vec<_>>();
+            tv.output.extend(changes.clone());
+            tv.output_expert.extend(changes);
+        }
     } else if code_sec.tag == Some(TX_VOTE_PROPOSAL.to_string()) {
         let vote_proposal = VoteProposalData::try_from_slice(
             &tx.data()
@@ -1166,6 +1484,61 @@ pub async fn to_ledger_vector(
             tv.output_expert
                 .push(format!("Delegation : {}", delegation));
         }
+    } else if code_sec.tag == Some(TX_DEPOSIT_PROPOSAL.to_string()) {
+        let deposit_proposal = DepositProposalData::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Deposit_Proposal_0".to_string();
+
+        tv.output.extend(vec![
+            format!("Type : Deposit proposal"),
+            format!("ID : {}", deposit_proposal.id),
+            format!("Depositor : {}", deposit_proposal.depositor),
+            format!(
+                "Amount : NAM {}",
+                to_ledger_decimal(&deposit_proposal.amount.to_string_native())
+            ),
+        ]);
+
+        tv.output_expert.extend(vec![
+            format!("ID : {}", deposit_proposal.id),
+            format!("Depositor : {}", deposit_proposal.depositor),
+            format!(
+                "Amount : NAM {}",
+                to_ledger_decimal(&deposit_proposal.amount.to_string_native())
+            ),
+        ]);
+    } else if code_sec.tag == Some(TX_DELEGATE_VOTE.to_string()) {
+        let vote_delegation = VoteDelegationData::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Delegate_Vote_0".to_string();
+
+        tv.output.extend(vec![
+            format!("Type : Delegate Vote"),
+            format!("Delegator : {}", vote_delegation.delegator),
+        ]);
+        if let Some(representative) = &vote_delegation.representative {
+            tv.output
+                .push(format!("Representative : {}", representative));
+        }
+
+        tv.output_expert
+            .push(format!("Delegator : {}", vote_delegation.delegator));
+        if let Some(representative) = vote_delegation.representative {
+            tv.output_expert
+                .push(format!("Representative : {}", representative));
+        }
     } else if code_sec.tag == Some(TX_REVEAL_PK.to_string()) {
         let public_key = common::PublicKey::try_from_slice(
             &tx.data()
@@ -1210,6 +1583,8 @@ pub async fn to_ledger_vector(
                 update_account.threshold.unwrap()
             )])
         }
+        tv.output
+            .extend(format_key_options(&update_account.key_options));
 
         let vp_code_data = match &update_account.vp_code_hash {
             Some(hash) => {
@@ -1243,12 +1618,67 @@ pub async fn to_ledger_vector(
             tv.output_expert
                 .extend(vec![format!("Threshold : {}", threshold,)])
         }
+        tv.output_expert
+            .extend(format_key_options(&update_account.key_options));
         if let Some((_, extra_code_hash)) = vp_code_data {
             tv.output_expert.extend(vec![format!(
                 "VP type : {}",
                 HEXLOWER.encode(&extra_code_hash.0)
             )]);
         }
+    } else if code_sec.tag == Some(TX_SPENDING_POLICY_WASM.to_string()) {
+        let update = UpdateSpendingPolicy::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Spending_Policy_0".to_string();
+        let fields = match update {
+            UpdateSpendingPolicy::SetPolicy {
+                owner,
+                policy: Some(policy),
+            } => vec![
+                format!("Owner : {}", owner),
+                format!("Action : Set Policy"),
+                format!("Delay : {}", policy.delay),
+            ],
+            UpdateSpendingPolicy::SetPolicy {
+                owner,
+                policy: None,
+            } => vec![
+                format!("Owner : {}", owner),
+                format!("Action : Clear Policy"),
+            ],
+            UpdateSpendingPolicy::ScheduleWithdrawal {
+                owner,
+                token,
+                target,
+                amount,
+            } => vec![
+                format!("Owner : {}", owner),
+                format!("Action : Schedule Withdrawal"),
+                format!("Token : {}", token),
+                format!("Target : {}", target),
+                format!("Amount : {}", amount),
+            ],
+            UpdateSpendingPolicy::ExecuteWithdrawal { owner, id } => vec![
+                format!("Owner : {}", owner),
+                format!("Action : Execute Withdrawal"),
+                format!("Withdrawal : {}", id),
+            ],
+            UpdateSpendingPolicy::CancelWithdrawal { owner, id } => vec![
+                format!("Owner : {}", owner),
+                format!("Action : Cancel Withdrawal"),
+                format!("Withdrawal : {}", id),
+            ],
+        };
+
+        tv.output.push("Type : Spending Policy".to_string());
+        tv.output.extend(fields.clone());
+        tv.output_expert.extend(fields);
     } else if code_sec.tag == Some(TX_TRANSFER_WASM.to_string()) {
         let transfer = Transfer::try_from_slice(
             &tx.data()
@@ -1725,6 +2155,52 @@ pub async fn to_ledger_vector(
             format!("Gas Token : {}", transfer.gas_fee.token),
             format!("Gas Amount : {}", transfer.gas_fee.amount),
         ]);
+    } else if code_sec.tag == Some(TX_BRIDGE_POOL_CANCEL_WASM.to_string()) {
+        let cancel = CancelPendingTransfer::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Bridge_Pool_Cancel_0".to_string();
+
+        let expiry = cancel
+            .expiry
+            .map(|epoch| epoch.to_string())
+            .unwrap_or_else(|| "Immediate".to_string());
+        tv.output.extend(vec![
+            format!("Type : Bridge Pool Cancel"),
+            format!("Transfer Hash : {}", cancel.transfer_hash),
+            format!("Expiry : {}", expiry),
+        ]);
+
+        tv.output_expert.extend(vec![
+            format!("Transfer Hash : {}", cancel.transfer_hash),
+            format!("Expiry : {}", expiry),
+        ]);
+    } else if code_sec.tag == Some(TX_BRIDGE_POOL_BUMP_FEE_WASM.to_string()) {
+        let bump = BumpPendingTransferFee::try_from_slice(
+            &tx.data()
+                .ok_or_else(|| Error::Other("Invalid Data".to_string()))?,
+        )
+        .map_err(|err| {
+            Error::from(EncodingError::Conversion(err.to_string()))
+        })?;
+
+        tv.name = "Bridge_Pool_Bump_Fee_0".to_string();
+
+        tv.output.extend(vec![
+            format!("Type : Bridge Pool Bump Fee"),
+            format!("Transfer Hash : {}", bump.transfer_hash),
+            format!("Gas Amount : {}", bump.amount),
+        ]);
+
+        tv.output_expert.extend(vec![
+            format!("Transfer Hash : {}", bump.transfer_hash),
+            format!("Gas Amount : {}", bump.amount),
+        ]);
     } else {
         tv.name = "Custom_0".to_string();
         tv.output.push("Type : Custom".to_string());
@@ -1758,3 +2234,208 @@ pub async fn to_ledger_vector(
     format_outputs(&mut tv.output_expert);
     Ok(tv)
 }
+
+#[cfg(test)]
+mod tests {
+    use namada_core::types::address::testing::{
+        established_address_1, established_address_2,
+    };
+    use namada_core::types::chain::ChainId;
+    use namada_core::types::key::testing::{
+        keypair_1, keypair_2, keypair_3, keypair_4,
+    };
+
+    use super::*;
+
+    fn test_account(
+        address: Address,
+        keys: &[common::SecretKey],
+        threshold: u8,
+    ) -> Account {
+        Account {
+            public_keys_map: AccountPublicKeysMap::from_iter(
+                keys.iter().map(|key| key.ref_to()),
+            ),
+            threshold,
+            address,
+            key_options: BTreeMap::new(),
+        }
+    }
+
+    /// Test that the signatures collected by several signers are merged and
+    /// weighted against the account, and that invalid ones are not counted.
+    #[test]
+    fn test_signing_request() {
+        let tx = Tx::new(ChainId::default(), None);
+        let mut account = Account {
+            public_keys_map: AccountPublicKeysMap::from_iter([
+                keypair_1().ref_to(),
+                keypair_2().ref_to(),
+            ]),
+            threshold: 2,
+            address: established_address_1(),
+            key_options: BTreeMap::new(),
+        };
+        let request = SigningRequest::new(&tx, &account);
+        assert_eq!(request.signers.len(), 2);
+        assert_eq!(
+            request.tx().unwrap().raw_header_hash(),
+            tx.raw_header_hash()
+        );
+
+        // Each signer signs their own copy of the request, keys outside of
+        // the account are skipped
+        let mut first = request.clone();
+        assert_eq!(
+            first.sign(&[keypair_1(), keypair_3()]).unwrap(),
+            vec![keypair_1().ref_to()]
+        );
+        assert!(first.sign(&[keypair_1()]).unwrap().is_empty());
+        assert_eq!(first.signed_weight(&account).unwrap(), 1);
+        assert!(!first.is_complete(&account).unwrap());
+
+        let mut second = request.clone();
+        second.sign(&[keypair_2()]).unwrap();
+        first.merge(second.clone()).unwrap();
+        first.merge(second).unwrap();
+        assert_eq!(first.signatures.len(), 2);
+        assert!(first.is_complete(&account).unwrap());
+
+        // A signature over another transaction is not counted
+        let other_tx = Tx::new(ChainId("other-chain".to_string()), None);
+        let mut other = SigningRequest::new(&other_tx, &account);
+        other.sign(&[keypair_2()]).unwrap();
+        assert!(request.clone().merge(other.clone()).is_err());
+        let mut forged = request.clone();
+        forged.sign(&[keypair_1()]).unwrap();
+        forged.signatures.extend(other.signatures);
+        assert_eq!(forged.signed_weight(&account).unwrap(), 1);
+
+        // The weights are those of the account at submission time
+        account.key_options.insert(
+            0,
+            AccountKeyOptions {
+                weight: 2,
+                ..Default::default()
+            },
+        );
+        assert!(forged.is_complete(&account).unwrap());
+    }
+
+    /// Test that requests over another transaction or on behalf of another
+    /// account cannot be merged.
+    #[test]
+    fn test_signing_request_merge_mismatch() {
+        let tx = Tx::new(ChainId::default(), None);
+        let account = test_account(
+            established_address_1(),
+            &[keypair_1(), keypair_2()],
+            2,
+        );
+        let mut request = SigningRequest::new(&tx, &account);
+
+        let other_tx = Tx::new(ChainId("other-chain".to_string()), None);
+        let mut other_tx_request = SigningRequest::new(&other_tx, &account);
+        other_tx_request.sign(&[keypair_2()]).unwrap();
+        assert!(request.merge(other_tx_request).is_err());
+
+        let other_account = test_account(
+            established_address_2(),
+            &[keypair_1(), keypair_2()],
+            2,
+        );
+        let mut other_owner_request = SigningRequest::new(&tx, &other_account);
+        other_owner_request.sign(&[keypair_2()]).unwrap();
+        assert!(request.merge(other_owner_request).is_err());
+
+        // Nothing was merged from the rejected requests
+        assert!(request.signatures.is_empty());
+    }
+
+    /// Test that the signatures that do not belong to the account or that
+    /// are not over the request's transaction are not weighted.
+    #[test]
+    fn test_signing_request_invalid_signatures() {
+        let tx = Tx::new(ChainId::default(), None);
+        let account = test_account(
+            established_address_1(),
+            &[keypair_1(), keypair_2()],
+            1,
+        );
+        let request = SigningRequest::new(&tx, &account);
+
+        // A key that is not in the account, at the index of one that is
+        let mut outsider = SigningRequest::new(
+            &tx,
+            &test_account(
+                established_address_1(),
+                &[keypair_3(), keypair_2()],
+                1,
+            ),
+        );
+        outsider.sign(&[keypair_3()]).unwrap();
+        let mut forged = request.clone();
+        forged.signatures.extend(outsider.signatures);
+        assert_eq!(forged.signed_weight(&account).unwrap(), 0);
+        assert!(!forged.is_complete(&account).unwrap());
+
+        // A key of the account, on behalf of another account
+        let other_account = test_account(
+            established_address_2(),
+            &[keypair_1(), keypair_2()],
+            1,
+        );
+        let mut other_owner = SigningRequest::new(&tx, &other_account);
+        other_owner.sign(&[keypair_1()]).unwrap();
+        let mut forged = request.clone();
+        forged.signatures.extend(other_owner.signatures);
+        assert_eq!(forged.signed_weight(&account).unwrap(), 0);
+
+        // A key of the account, over another transaction
+        let other_tx = Tx::new(ChainId("other-chain".to_string()), None);
+        let mut other_tx_request = SigningRequest::new(&other_tx, &account);
+        other_tx_request.sign(&[keypair_2()]).unwrap();
+        let mut forged = request.clone();
+        forged.signatures.extend(other_tx_request.signatures);
+        assert_eq!(forged.signed_weight(&account).unwrap(), 0);
+        assert!(!forged.is_complete(&account).unwrap());
+
+        // The valid signatures are still weighted next to the invalid ones
+        forged.sign(&[keypair_1()]).unwrap();
+        assert_eq!(forged.signed_weight(&account).unwrap(), 1);
+        assert!(forged.is_complete(&account).unwrap());
+    }
+
+    /// Test that a request is only complete once the weights of its valid
+    /// signatures reach the threshold of the account.
+    #[test]
+    fn test_signing_request_threshold() {
+        let tx = Tx::new(ChainId::default(), None);
+        let mut account = account(
+            established_address_1(),
+            &[keypair_1(), keypair_2(), keypair_4()],
+            4,
+        );
+        account.key_options.insert(
+            2,
+            AccountKeyOptions {
+                weight: 2,
+                ..Default::default()
+            },
+        );
+        let mut request = SigningRequest::new(&tx, &account);
+        assert!(!request.is_complete(&account).unwrap());
+
+        request.sign(&[keypair_1(), keypair_2()]).unwrap();
+        assert_eq!(request.signed_weight(&account).unwrap(), 2);
+        assert!(!request.is_complete(&account).unwrap());
+
+        request.sign(&[keypair_4()]).unwrap();
+        assert_eq!(request.signed_weight(&account).unwrap(), 4);
+        assert!(request.is_complete(&account).unwrap());
+
+        // A higher threshold than the collected weights is not met
+        account.threshold = 5;
+        assert!(!request.is_complete(&account).unwrap());
+    }
+}
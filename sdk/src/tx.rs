@@ -25,8 +25,8 @@ use namada_core::ibc::core::client::types::Height as IbcHeight;
 use namada_core::ibc::core::host::types::identifiers::{ChannelId, PortId};
 use namada_core::ibc::primitives::{Msg, Timestamp as IbcTimestamp};
 use namada_core::ledger::governance::cli::onchain::{
-    DefaultProposal, OnChainProposal, PgfFundingProposal, PgfStewardProposal,
-    ProposalVote,
+    DefaultProposal, OnChainProposal, ParameterChangeProposal,
+    PgfFundingProposal, PgfStewardProposal, ProposalVote, UpgradeProposal,
 };
 use namada_core::ledger::governance::storage::proposal::ProposalType;
 use namada_core::ledger::governance::storage::vote::StorageProposalVote;
@@ -41,9 +41,11 @@ use namada_core::types::masp::{TransferSource, TransferTarget};
 use namada_core::types::storage::Epoch;
 use namada_core::types::time::DateTimeUtc;
 use namada_core::types::token::MaspDenom;
-use namada_core::types::transaction::account::{InitAccount, UpdateAccount};
+use namada_core::types::transaction::account::{
+    InitAccount, UpdateAccount, UpdateSpendingPolicy,
+};
 use namada_core::types::transaction::governance::{
-    InitProposalData, VoteProposalData,
+    DepositProposalData, InitProposalData, VoteDelegationData, VoteProposalData,
 };
 use namada_core::types::transaction::pgf::UpdateStewardCommission;
 use namada_core::types::transaction::{pos, ResultCode, TxResult};
@@ -57,7 +59,7 @@ use crate::error::{EncodingError, Error, QueryError, Result, TxError};
 use crate::io::Io;
 use crate::masp::TransferErr::Build;
 use crate::masp::{make_asset_type, ShieldedContext, ShieldedTransfer};
-use crate::proto::{MaspBuilder, Tx};
+use crate::proto::{BatchMode, MaspBuilder, Tx};
 use crate::queries::Client;
 use crate::rpc::{
     self, query_wasm_code_hash, validate_amount, InnerTxResult,
@@ -83,10 +85,16 @@ pub const TX_REACTIVATE_VALIDATOR_WASM: &str = "tx_reactivate_validator.wasm";
 pub const TX_INIT_PROPOSAL: &str = "tx_init_proposal.wasm";
 /// Vote transaction WASM path
 pub const TX_VOTE_PROPOSAL: &str = "tx_vote_proposal.wasm";
+/// Vote delegation transaction WASM path
+pub const TX_DELEGATE_VOTE: &str = "tx_delegate_vote.wasm";
+/// Proposal deposit transaction WASM path
+pub const TX_DEPOSIT_PROPOSAL: &str = "tx_deposit_proposal.wasm";
 /// Reveal public key transaction WASM path
 pub const TX_REVEAL_PK: &str = "tx_reveal_pk.wasm";
 /// Update validity predicate WASM path
 pub const TX_UPDATE_ACCOUNT_WASM: &str = "tx_update_account.wasm";
+/// Spending policy transaction WASM path
+pub const TX_SPENDING_POLICY_WASM: &str = "tx_spending_policy.wasm";
 /// Transfer transaction WASM path
 pub const TX_TRANSFER_WASM: &str = "tx_transfer.wasm";
 /// IBC transaction WASM path
@@ -103,6 +111,10 @@ pub const TX_WITHDRAW_WASM: &str = "tx_withdraw.wasm";
 pub const TX_CLAIM_REWARDS_WASM: &str = "tx_claim_rewards.wasm";
 /// Bridge pool WASM path
 pub const TX_BRIDGE_POOL_WASM: &str = "tx_bridge_pool.wasm";
+/// Bridge pool cancel WASM path
+pub const TX_BRIDGE_POOL_CANCEL_WASM: &str = "tx_bridge_pool_cancel.wasm";
+/// Bridge pool fee bump WASM path
+pub const TX_BRIDGE_POOL_BUMP_FEE_WASM: &str = "tx_bridge_pool_bump_fee.wasm";
 /// Change commission WASM path
 pub const TX_CHANGE_COMMISSION_WASM: &str =
     "tx_change_validator_commission.wasm";
@@ -872,6 +884,72 @@ pub async fn build_update_steward_commission(
     .map(|tx| (tx, signing_data))
 }
 
+/// Craft transaction to update the spending policy of an account
+pub async fn build_spending_policy(
+    context: &impl Namada,
+    args::UpdateSpendingPolicy {
+        tx: tx_args,
+        owner,
+        action,
+        tx_code_path,
+    }: &args::UpdateSpendingPolicy,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(owner.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx_args,
+        Some(owner.clone()),
+        default_signer,
+    )
+    .await?;
+
+    let owner = owner.clone();
+    let data = match action {
+        args::SpendingPolicyAction::SetPolicy(policy) => {
+            let policy = policy
+                .as_ref()
+                .map(|policy| serde_json::from_slice(policy))
+                .transpose()
+                .map_err(|e| TxError::InvalidSpendingPolicy(e.to_string()))?;
+            UpdateSpendingPolicy::SetPolicy { owner, policy }
+        }
+        args::SpendingPolicyAction::ScheduleWithdrawal {
+            token,
+            target,
+            amount,
+        } => {
+            let amount =
+                validate_amount(context, *amount, token, tx_args.force)
+                    .await?
+                    .amount();
+            UpdateSpendingPolicy::ScheduleWithdrawal {
+                owner,
+                token: token.clone(),
+                target: target.clone(),
+                amount,
+            }
+        }
+        args::SpendingPolicyAction::ExecuteWithdrawal(id) => {
+            UpdateSpendingPolicy::ExecuteWithdrawal { owner, id: *id }
+        }
+        args::SpendingPolicyAction::CancelWithdrawal(id) => {
+            UpdateSpendingPolicy::CancelWithdrawal { owner, id: *id }
+        }
+    };
+
+    build(
+        context,
+        tx_args,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Craft transaction to resign as a steward
 pub async fn build_resign_steward(
     context: &impl Namada,
@@ -1763,6 +1841,10 @@ pub async fn build_default_proposal(
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_emergency,
+        is_parameter_change: _,
+        is_upgrade: _,
+        deposit,
         tx_code_path,
     }: &args::InitProposal,
     proposal: DefaultProposal,
@@ -1776,8 +1858,13 @@ pub async fn build_default_proposal(
     )
     .await?;
 
-    let init_proposal_data = InitProposalData::try_from(proposal.clone())
-        .map_err(|e| TxError::InvalidProposal(e.to_string()))?;
+    let mut init_proposal_data =
+        InitProposalData::try_from(proposal.clone())
+            .map_err(|e| TxError::InvalidProposal(e.to_string()))?;
+    if *is_emergency {
+        init_proposal_data.r#type = ProposalType::Emergency(None);
+    }
+    init_proposal_data.deposit = init_proposal_deposit(context, deposit).await;
 
     let push_data =
         |tx_builder: &mut Tx, init_proposal_data: &mut InitProposalData| {
@@ -1788,8 +1875,11 @@ pub async fn build_default_proposal(
             if let Some(init_proposal_code) = proposal.data {
                 let (_, extra_section_hash) =
                     tx_builder.add_extra_section(init_proposal_code, None);
-                init_proposal_data.r#type =
-                    ProposalType::Default(Some(extra_section_hash));
+                init_proposal_data.r#type = if *is_emergency {
+                    ProposalType::Emergency(Some(extra_section_hash))
+                } else {
+                    ProposalType::Default(Some(extra_section_hash))
+                };
             };
             Ok(())
         };
@@ -1806,6 +1896,113 @@ pub async fn build_default_proposal(
     .map(|tx| (tx, signing_data))
 }
 
+/// Get the initial deposit of a proposal, the minimum proposal fund if not
+/// set
+async fn init_proposal_deposit(
+    context: &impl Namada,
+    deposit: &Option<token::Amount>,
+) -> token::Amount {
+    match deposit {
+        Some(deposit) => *deposit,
+        None => {
+            rpc::query_governance_parameters(context.client())
+                .await
+                .min_proposal_fund
+        }
+    }
+}
+
+/// Build a tx to top up the deposit of a proposal
+pub async fn build_deposit_proposal(
+    context: &impl Namada,
+    args::DepositProposal {
+        tx,
+        proposal_id,
+        depositor,
+        amount,
+        tx_code_path,
+    }: &args::DepositProposal,
+    epoch: Epoch,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(depositor.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx,
+        Some(depositor.clone()),
+        default_signer,
+    )
+    .await?;
+
+    let proposal = rpc::query_proposal_by_id(context.client(), *proposal_id)
+        .await?
+        .ok_or_else(|| {
+            Error::from(TxError::ProposalDoesNotExist(*proposal_id))
+        })?;
+    let funds = rpc::query_proposal_deposits(context.client(), *proposal_id)
+        .await?
+        .into_values()
+        .sum::<token::Amount>();
+    let min_proposal_fund = rpc::query_governance_parameters(context.client())
+        .await
+        .min_proposal_fund;
+
+    let invalid_deposit = if epoch >= proposal.voting_start_epoch {
+        Some("its deposit period is over".to_string())
+    } else if funds >= min_proposal_fund {
+        Some(format!(
+            "its deposit already reached the minimum of {}",
+            min_proposal_fund.to_string_native()
+        ))
+    } else {
+        None
+    };
+    if let Some(reason) = invalid_deposit {
+        if tx.force {
+            edisplay_line!(
+                context.io(),
+                "Invalid deposit on proposal {}: {}",
+                proposal_id,
+                reason
+            );
+        } else {
+            return Err(Error::from(TxError::InvalidProposalDeposit(
+                *proposal_id,
+                reason,
+            )));
+        }
+    }
+
+    let native_token = context.native_token();
+    let balance_key = token::balance_key(&native_token, depositor);
+    check_balance_too_low_err(
+        &native_token,
+        depositor,
+        *amount,
+        balance_key,
+        tx.force,
+        context,
+    )
+    .await?;
+
+    let data = DepositProposalData {
+        id: *proposal_id,
+        depositor: depositor.clone(),
+        amount: *amount,
+    };
+
+    build(
+        context,
+        tx,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Build a proposal vote
 pub async fn build_vote_proposal(
     context: &impl Namada,
@@ -1893,6 +2090,65 @@ pub async fn build_vote_proposal(
     .map(|tx| (tx, signing_data))
 }
 
+/// Build a tx to delegate the voting power of an address to a representative,
+/// or to revoke the delegation
+pub async fn build_delegate_vote(
+    context: &impl Namada,
+    args::DelegateVote {
+        tx,
+        delegator,
+        representative,
+        tx_code_path,
+    }: &args::DelegateVote,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(delegator.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx,
+        Some(delegator.clone()),
+        default_signer,
+    )
+    .await?;
+
+    if representative.as_ref() == Some(delegator) {
+        return Err(Error::from(TxError::InvalidVoteDelegation(format!(
+            "{} can't be its own representative",
+            delegator
+        ))));
+    }
+    if rpc::is_validator(context.client(), delegator).await? {
+        if tx.force {
+            edisplay_line!(
+                context.io(),
+                "The validator {} votes with its own stake.",
+                delegator
+            );
+        } else {
+            return Err(Error::from(TxError::InvalidVoteDelegation(format!(
+                "the validator {} votes with its own stake",
+                delegator
+            ))));
+        }
+    }
+
+    let data = VoteDelegationData {
+        delegator: delegator.clone(),
+        representative: representative.clone(),
+    };
+
+    build(
+        context,
+        tx,
+        tx_code_path.clone(),
+        data,
+        do_nothing,
+        &signing_data.fee_payer,
+        None,
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
 /// Build a pgf funding proposal governance
 pub async fn build_pgf_funding_proposal(
     context: &impl Namada,
@@ -1903,6 +2159,10 @@ pub async fn build_pgf_funding_proposal(
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_emergency: _,
+        is_parameter_change: _,
+        is_upgrade: _,
+        deposit,
         tx_code_path,
     }: &args::InitProposal,
     proposal: PgfFundingProposal,
@@ -1916,8 +2176,10 @@ pub async fn build_pgf_funding_proposal(
     )
     .await?;
 
-    let init_proposal_data = InitProposalData::try_from(proposal.clone())
-        .map_err(|e| TxError::InvalidProposal(e.to_string()))?;
+    let mut init_proposal_data =
+        InitProposalData::try_from(proposal.clone())
+            .map_err(|e| TxError::InvalidProposal(e.to_string()))?;
+    init_proposal_data.deposit = init_proposal_deposit(context, deposit).await;
 
     let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
         let (_, extra_section_hash) =
@@ -1948,6 +2210,10 @@ pub async fn build_pgf_stewards_proposal(
         is_offline: _,
         is_pgf_stewards: _,
         is_pgf_funding: _,
+        is_emergency: _,
+        is_parameter_change: _,
+        is_upgrade: _,
+        deposit,
         tx_code_path,
     }: &args::InitProposal,
     proposal: PgfStewardProposal,
@@ -1961,8 +2227,114 @@ pub async fn build_pgf_stewards_proposal(
     )
     .await?;
 
-    let init_proposal_data = InitProposalData::try_from(proposal.clone())
-        .map_err(|e| TxError::InvalidProposal(e.to_string()))?;
+    let mut init_proposal_data =
+        InitProposalData::try_from(proposal.clone())
+            .map_err(|e| TxError::InvalidProposal(e.to_string()))?;
+    init_proposal_data.deposit = init_proposal_deposit(context, deposit).await;
+
+    let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
+        let (_, extra_section_hash) =
+            tx.add_extra_section(proposal_to_vec(proposal.proposal)?, None);
+        data.content = extra_section_hash;
+        Ok(())
+    };
+
+    build(
+        context,
+        tx,
+        tx_code_path.clone(),
+        init_proposal_data,
+        add_section,
+        &signing_data.fee_payer,
+        None, // TODO: need to pay the fee to submit a proposal
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Build a parameter change proposal governance
+pub async fn build_parameter_change_proposal(
+    context: &impl Namada,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        native_token: _,
+        is_offline: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_emergency: _,
+        is_parameter_change: _,
+        is_upgrade: _,
+        deposit,
+        tx_code_path,
+    }: &args::InitProposal,
+    proposal: ParameterChangeProposal,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(proposal.proposal.author.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx,
+        Some(proposal.proposal.author.clone()),
+        default_signer,
+    )
+    .await?;
+
+    let mut init_proposal_data =
+        InitProposalData::try_from(proposal.clone())
+            .map_err(|e| TxError::InvalidProposal(e.to_string()))?;
+    init_proposal_data.deposit = init_proposal_deposit(context, deposit).await;
+
+    let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
+        let (_, extra_section_hash) =
+            tx.add_extra_section(proposal_to_vec(proposal.proposal)?, None);
+        data.content = extra_section_hash;
+        Ok(())
+    };
+
+    build(
+        context,
+        tx,
+        tx_code_path.clone(),
+        init_proposal_data,
+        add_section,
+        &signing_data.fee_payer,
+        None, // TODO: need to pay the fee to submit a proposal
+    )
+    .await
+    .map(|tx| (tx, signing_data))
+}
+
+/// Build an upgrade proposal governance
+pub async fn build_upgrade_proposal(
+    context: &impl Namada,
+    args::InitProposal {
+        tx,
+        proposal_data: _,
+        native_token: _,
+        is_offline: _,
+        is_pgf_stewards: _,
+        is_pgf_funding: _,
+        is_emergency: _,
+        is_parameter_change: _,
+        is_upgrade: _,
+        deposit,
+        tx_code_path,
+    }: &args::InitProposal,
+    proposal: UpgradeProposal,
+) -> Result<(Tx, SigningTxData)> {
+    let default_signer = Some(proposal.proposal.author.clone());
+    let signing_data = signing::aux_signing_data(
+        context,
+        tx,
+        Some(proposal.proposal.author.clone()),
+        default_signer,
+    )
+    .await?;
+
+    let mut init_proposal_data =
+        InitProposalData::try_from(proposal.clone())
+            .map_err(|e| TxError::InvalidProposal(e.to_string()))?;
+    init_proposal_data.deposit = init_proposal_deposit(context, deposit).await;
 
     let add_section = |tx: &mut Tx, data: &mut InitProposalData| {
         let (_, extra_section_hash) =
@@ -2515,6 +2887,7 @@ pub async fn build_update_account(
         addr,
         public_keys,
         threshold,
+        key_options,
     }: &args::TxUpdateAccount,
 ) -> Result<(Tx, SigningTxData)> {
     let default_signer = Some(addr.clone());
@@ -2526,9 +2899,30 @@ pub async fn build_update_account(
     )
     .await?;
 
-    let addr = if let Some(account) =
-        rpc::get_account_info(context.client(), addr).await?
-    {
+    let account = rpc::get_account_info(context.client(), addr).await?;
+    let num_of_public_keys = if !public_keys.is_empty() {
+        Some(public_keys.len())
+    } else {
+        account
+            .as_ref()
+            .map(|account| account.public_keys_map.idx_to_pk.len())
+    };
+    if let Some(num_of_public_keys) = num_of_public_keys {
+        if key_options.len() > num_of_public_keys {
+            return Err(Error::from(TxError::InvalidKeyOptions(format!(
+                "{} key options are given for {} public keys",
+                key_options.len(),
+                num_of_public_keys
+            ))));
+        }
+    }
+    if key_options.iter().any(|options| options.weight == 0) {
+        return Err(Error::from(TxError::InvalidKeyOptions(
+            "the weight of a key cannot be zero".to_string(),
+        )));
+    }
+
+    let addr = if let Some(account) = account {
         account.address
     } else if tx_args.force {
         addr.clone()
@@ -2560,6 +2954,7 @@ pub async fn build_update_account(
         vp_code_hash: extra_section_hash,
         public_keys: public_keys.clone(),
         threshold: *threshold,
+        key_options: key_options.clone(),
     };
 
     let add_code_hash = |tx: &mut Tx, data: &mut UpdateAccount| {
@@ -2638,6 +3033,22 @@ pub async fn build_custom(
     Ok((tx, signing_data))
 }
 
+/// Combine transactions built with the other builders of this module into a
+/// single batch, whose inner transactions are applied in the given order under
+/// the wrapper of the first transaction. The gas limit of that wrapper must
+/// thus cover the whole batch. The returned signing data must all be used to
+/// sign the batch.
+pub fn build_batch(
+    txs: Vec<(Tx, SigningTxData)>,
+    mode: BatchMode,
+) -> Result<(Tx, Vec<SigningTxData>)> {
+    let (txs, signing_data): (Vec<_>, Vec<_>) = txs.into_iter().unzip();
+    let tx = Tx::new_batch(txs, mode).ok_or_else(|| {
+        Error::Other("Cannot build an empty batch".to_string())
+    })?;
+    Ok((tx, signing_data))
+}
+
 /// Generate IBC shielded transfer
 pub async fn gen_ibc_shielded_transfer<N: Namada>(
     context: &N,
@@ -13,6 +13,7 @@ use tiny_hderive::Error as HDeriveError;
 
 const ETH_COIN_TYPE: u32 = 60;
 const NAMADA_COIN_TYPE: u32 = 877;
+const ZIP32_PURPOSE: u32 = 32;
 
 #[derive(Error, Debug)]
 pub enum DerivationPathError {
@@ -85,6 +86,14 @@ impl DerivationPath {
         path.hardened(scheme)
     }
 
+    pub fn default_for_shielded() -> Self {
+        Self::new(vec![
+            ChildIndex::Hardened(ZIP32_PURPOSE),
+            ChildIndex::Hardened(NAMADA_COIN_TYPE),
+            ChildIndex::Hardened(0),
+        ])
+    }
+
     pub fn from_path_str(
         scheme: SchemeType,
         path: &str,
@@ -12,10 +12,12 @@ use std::str::FromStr;
 use alias::Alias;
 use bip39::{Language, Mnemonic, MnemonicType, Seed};
 use borsh::{BorshDeserialize, BorshSerialize};
+use masp_primitives::sapling::ViewingKey;
+use masp_primitives::zip32::ExtendedFullViewingKey;
 use namada_core::types::address::Address;
 use namada_core::types::key::*;
 use namada_core::types::masp::{
-    ExtendedSpendingKey, ExtendedViewingKey, PaymentAddress,
+    ExtendedSpendingKey, ExtendedViewingKey, KeyBirthday, PaymentAddress,
 };
 pub use pre_genesis::gen_key_to_store;
 use rand::CryptoRng;
@@ -27,7 +29,7 @@ use zeroize::Zeroizing;
 pub use self::derivation_path::{DerivationPath, DerivationPathError};
 pub use self::keys::{DecryptionError, StoredKeypair};
 pub use self::store::{ConfirmationResponse, ValidatorData, ValidatorKeys};
-use crate::wallet::store::derive_hd_secret_key;
+use crate::wallet::store::{derive_hd_secret_key, derive_hd_spending_key};
 
 /// Errors of key generation / recovery
 #[derive(Error, Debug)]
@@ -475,6 +477,23 @@ impl<U> Wallet<U> {
             .collect()
     }
 
+    /// Get the birthdays of all known viewing keys that have one
+    pub fn get_viewing_key_birthdays(
+        &self,
+    ) -> HashMap<ViewingKey, KeyBirthday> {
+        self.store
+            .get_birthdays()
+            .iter()
+            .filter_map(|(alias, birthday)| {
+                let viewing_key = self.store.find_viewing_key(alias)?;
+                Some((
+                    ExtendedFullViewingKey::from(*viewing_key).fvk.vk,
+                    *birthday,
+                ))
+            })
+            .collect()
+    }
+
     /// Get all known viewing keys by their alias
     pub fn get_spending_keys(
         &self,
@@ -562,18 +581,61 @@ impl<U: WalletIo> Wallet<U> {
         .map(|alias| (alias, sk))
     }
 
+    /// Restore a spending key from the user mnemonic code (read from stdin)
+    /// using a given ZIP32 derivation path and insert it into the store with
+    /// the provided alias, converted to lower case. The optional birthday
+    /// limits how far back the shielded history has to be scanned for notes
+    /// of this key.
+    /// The key is encrypted with the provided password. If no password
+    /// provided, will prompt for password from stdin.
+    /// Stores the key in decrypted key cache and returns the alias of the key
+    /// and the key itself.
+    pub fn derive_spending_key_from_mnemonic_code(
+        &mut self,
+        alias: String,
+        alias_force: bool,
+        derivation_path: DerivationPath,
+        mnemonic_passphrase: Option<(Mnemonic, Zeroizing<String>)>,
+        password: Option<Zeroizing<String>>,
+        birthday: Option<KeyBirthday>,
+    ) -> Result<(String, ExtendedSpendingKey), GenRestoreKeyError> {
+        let (mnemonic, passphrase) =
+            if let Some(mnemonic_passphrase) = mnemonic_passphrase {
+                mnemonic_passphrase
+            } else {
+                (U::read_mnemonic_code()?, U::read_mnemonic_passphrase(false))
+            };
+        let seed = Seed::new(&mnemonic, &passphrase);
+        let spendkey = derive_hd_spending_key(seed.as_bytes(), derivation_path);
+
+        self.insert_spending_key(
+            alias,
+            spendkey,
+            password,
+            birthday,
+            alias_force,
+        )
+        .map(|alias| (alias, spendkey))
+        .ok_or(GenRestoreKeyError::KeyStorageError)
+    }
+
     /// Generate a spending key similarly to how it's done for keypairs
     pub fn gen_store_spending_key(
         &mut self,
         alias: String,
         password: Option<Zeroizing<String>>,
+        birthday: Option<KeyBirthday>,
         force_alias: bool,
         csprng: &mut (impl CryptoRng + RngCore),
     ) -> (String, ExtendedSpendingKey) {
         let spendkey = gen_spending_key(csprng);
-        if let Some(alias) =
-            self.insert_spending_key(alias, spendkey, password, force_alias)
-        {
+        if let Some(alias) = self.insert_spending_key(
+            alias,
+            spendkey,
+            password,
+            birthday,
+            force_alias,
+        ) {
             (alias, spendkey)
         } else {
             panic!("Action cancelled, no changes persisted.");
@@ -942,10 +1004,16 @@ impl<U: WalletIo> Wallet<U> {
         &mut self,
         alias: String,
         view_key: ExtendedViewingKey,
+        birthday: Option<KeyBirthday>,
         force_alias: bool,
     ) -> Option<String> {
         self.store
-            .insert_viewing_key::<U>(alias.into(), view_key, force_alias)
+            .insert_viewing_key::<U>(
+                alias.into(),
+                view_key,
+                birthday,
+                force_alias,
+            )
             .map(Into::into)
     }
 
@@ -955,6 +1023,7 @@ impl<U: WalletIo> Wallet<U> {
         alias: String,
         spend_key: ExtendedSpendingKey,
         password: Option<Zeroizing<String>>,
+        birthday: Option<KeyBirthday>,
         force_alias: bool,
     ) -> Option<String> {
         self.store
@@ -962,6 +1031,7 @@ impl<U: WalletIo> Wallet<U> {
                 alias.into(),
                 spend_key,
                 password,
+                birthday,
                 force_alias,
             )
             .map(|alias| {
@@ -5,12 +5,13 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 use bimap::BiBTreeMap;
+use derivation_path::ChildIndex;
 use itertools::Itertools;
-use masp_primitives::zip32::ExtendedFullViewingKey;
+use masp_primitives::zip32::{self, ExtendedFullViewingKey};
 use namada_core::types::address::{Address, ImplicitAddress};
 use namada_core::types::key::*;
 use namada_core::types::masp::{
-    ExtendedSpendingKey, ExtendedViewingKey, PaymentAddress,
+    ExtendedSpendingKey, ExtendedViewingKey, KeyBirthday, PaymentAddress,
 };
 use serde::{Deserialize, Serialize};
 use slip10_ed25519;
@@ -80,6 +81,9 @@ pub struct Store {
     pub(crate) validator_data: Option<ValidatorData>,
     /// Namada address vp type
     address_vp_types: BTreeMap<AddressVpType, HashSet<Address>>,
+    /// Known birthdays of shielded keys
+    #[serde(default)]
+    birthdays: BTreeMap<Alias, KeyBirthday>,
 }
 
 /// Grouping of addresses by validity predicate.
@@ -143,6 +147,15 @@ impl Store {
         self.view_keys.get(&alias.into())
     }
 
+    /// Find the birthday of the shielded key with the given alias and return
+    /// it
+    pub fn find_birthday(
+        &self,
+        alias: impl AsRef<str>,
+    ) -> Option<&KeyBirthday> {
+        self.birthdays.get(&alias.into())
+    }
+
     /// Find the payment address with the given alias and return it
     pub fn find_payment_addr(
         &self,
@@ -254,6 +267,11 @@ impl Store {
         &self.view_keys
     }
 
+    /// Get all known shielded key birthdays by their alias.
+    pub fn get_birthdays(&self) -> &BTreeMap<Alias, KeyBirthday> {
+        &self.birthdays
+    }
+
     /// Get all known spending keys by their alias.
     pub fn get_spending_keys(
         &self,
@@ -366,6 +384,7 @@ impl Store {
         alias: Alias,
         spendkey: ExtendedSpendingKey,
         password: Option<Zeroizing<String>>,
+        birthday: Option<KeyBirthday>,
         force: bool,
     ) -> Option<Alias> {
         // abort if the alias is reserved
@@ -383,7 +402,7 @@ impl Store {
                 ConfirmationResponse::Replace => {}
                 ConfirmationResponse::Reselect(new_alias) => {
                     return self.insert_spending_key::<U>(
-                        new_alias, spendkey, password, false,
+                        new_alias, spendkey, password, birthday, false,
                     );
                 }
                 ConfirmationResponse::Skip => return None,
@@ -396,6 +415,9 @@ impl Store {
         // Simultaneously add the derived viewing key to ease balance viewing
         let viewkey = ExtendedFullViewingKey::from(&spendkey.into()).into();
         self.view_keys.insert(alias.clone(), viewkey);
+        if let Some(birthday) = birthday {
+            self.birthdays.insert(alias.clone(), birthday);
+        }
         Some(alias)
     }
 
@@ -404,6 +426,7 @@ impl Store {
         &mut self,
         alias: Alias,
         viewkey: ExtendedViewingKey,
+        birthday: Option<KeyBirthday>,
         force: bool,
     ) -> Option<Alias> {
         // abort if the alias is reserved
@@ -420,14 +443,18 @@ impl Store {
             match U::show_overwrite_confirmation(&alias, "a viewing key") {
                 ConfirmationResponse::Replace => {}
                 ConfirmationResponse::Reselect(new_alias) => {
-                    return self
-                        .insert_viewing_key::<U>(new_alias, viewkey, false);
+                    return self.insert_viewing_key::<U>(
+                        new_alias, viewkey, birthday, false,
+                    );
                 }
                 ConfirmationResponse::Skip => return None,
             }
         }
         self.remove_alias(&alias);
         self.view_keys.insert(alias.clone(), viewkey);
+        if let Some(birthday) = birthday {
+            self.birthdays.insert(alias.clone(), birthday);
+        }
         Some(alias)
     }
 
@@ -585,6 +612,7 @@ impl Store {
         self.pkhs.retain(|_key, val| val != alias);
         self.public_keys.remove(alias);
         self.derivation_paths.remove(alias);
+        self.birthdays.remove(alias);
     }
 
     /// Extend this store from another store (typically pre-genesis).
@@ -601,6 +629,7 @@ impl Store {
             pkhs,
             validator_data: _,
             address_vp_types,
+            birthdays,
         } = self;
         view_keys.extend(store.view_keys);
         spend_keys.extend(store.spend_keys);
@@ -611,6 +640,7 @@ impl Store {
         addresses.extend(store.addresses);
         pkhs.extend(store.pkhs);
         address_vp_types.extend(store.address_vp_types);
+        birthdays.extend(store.birthdays);
     }
 
     /// Extend this store from pre-genesis validator wallet.
@@ -732,6 +762,24 @@ pub fn derive_hd_secret_key(
     }
 }
 
+/// Generate a new spending key from the seed.
+pub fn derive_hd_spending_key(
+    seed: &[u8],
+    derivation_path: DerivationPath,
+) -> ExtendedSpendingKey {
+    let master = masp_primitives::zip32::ExtendedSpendingKey::master(seed);
+    let path = derivation_path
+        .path()
+        .iter()
+        .map(|idx| match idx {
+            ChildIndex::Hardened(idx) => zip32::ChildIndex::Hardened(*idx),
+            ChildIndex::Normal(idx) => zip32::ChildIndex::NonHardened(*idx),
+        })
+        .collect_vec();
+    masp_primitives::zip32::ExtendedSpendingKey::from_path(&master, &path)
+        .into()
+}
+
 impl Display for AddressVpType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -777,10 +825,45 @@ mod test_wallet {
     use base58::{self, FromBase58};
     use bip39::{Language, Mnemonic, Seed};
     use data_encoding::HEXLOWER;
+    use namada_core::types::storage::BlockHeight;
 
     use super::super::derivation_path::DerivationPath;
     use super::*;
 
+    #[derive(Clone)]
+    struct TestWalletUtils;
+
+    impl WalletIo for TestWalletUtils {
+        type Rng = rand_core::OsRng;
+    }
+
+    #[test]
+    fn shielded_key_birthday() {
+        let mut store = Store::default();
+        let spend_key: ExtendedSpendingKey =
+            masp_primitives::zip32::ExtendedSpendingKey::master(&[0; 32])
+                .into();
+        let birthday = KeyBirthday::Height(BlockHeight(42));
+        let alias = store
+            .insert_spending_key::<TestWalletUtils>(
+                "alice".into(),
+                spend_key,
+                None,
+                Some(birthday),
+                false,
+            )
+            .expect("Inserting the spending key cannot fail.");
+
+        // The birthday is persisted with the store
+        let decoded = Store::decode(store.encode())
+            .expect("Decoding the store cannot fail.");
+        assert_eq!(decoded.find_birthday(&alias), Some(&birthday));
+
+        // The birthday is removed together with its key
+        store.remove_alias(&alias);
+        assert_eq!(store.find_birthday(&alias), None);
+    }
+
     #[test]
     fn gen_sk_from_mnemonic_code_secp256k1() {
         const SCHEME: SchemeType = SchemeType::Secp256k1;
@@ -5,15 +5,19 @@ pub mod utils;
 use std::collections::BTreeSet;
 
 use borsh::BorshDeserialize;
+use namada_core::ledger::eth_bridge::storage::whitelist;
 use namada_core::ledger::governance::storage::keys as gov_storage;
 use namada_core::ledger::governance::storage::proposal::{
-    AddRemove, ProposalType,
+    AddRemove, ParameterChange, ProposalType,
 };
 use namada_core::ledger::governance::storage::vote::StorageProposalVote;
 use namada_core::ledger::governance::utils::is_valid_validator_voting_period;
 use namada_core::ledger::storage;
 use namada_core::ledger::storage_api::account;
-use namada_core::ledger::storage_api::governance::is_proposal_accepted;
+use namada_core::ledger::storage_api::collections::{lazy_vec, LazyCollection};
+use namada_core::ledger::storage_api::governance::{
+    get_current_vote, is_proposal_accepted,
+};
 use namada_core::ledger::vp_env::VpEnv;
 use namada_core::proto::Tx;
 use namada_proof_of_stake::is_validator;
@@ -46,6 +50,10 @@ pub enum Error {
     EmptyProposalField(String),
     #[error("Vote key is not valid: {0}")]
     InvalidVoteKey(String),
+    #[error("Representative key is not valid: {0}")]
+    InvalidRepresentativeKey(String),
+    #[error("Deposit key is not valid: {0}")]
+    InvalidDepositKey(String),
     #[error("Vote type is not compatible with proposal type.")]
     InvalidVoteType,
 }
@@ -88,8 +96,31 @@ where
             let key_type = KeyType::from_key(key, &native_token);
 
             let result = match (key_type, proposal_id) {
-                (KeyType::VOTE, Some(proposal_id)) => {
-                    self.is_valid_vote_key(proposal_id, key, verifiers)
+                (KeyType::VOTE, Some(proposal_id)) => self.is_valid_vote_key(
+                    proposal_id,
+                    key,
+                    keys_changed,
+                    verifiers,
+                ),
+                (KeyType::REPRESENTATIVE_VOTE, Some(proposal_id)) => self
+                    .is_valid_representative_vote_key(
+                        proposal_id,
+                        key,
+                        keys_changed,
+                        verifiers,
+                    ),
+                (KeyType::VOTE_HISTORY, Some(proposal_id)) => self
+                    .is_valid_vote_history_key(
+                        proposal_id,
+                        key,
+                        keys_changed,
+                        verifiers,
+                    ),
+                (KeyType::VOTER_VOTES, _) => {
+                    self.is_valid_voter_votes_key(key, keys_changed, verifiers)
+                }
+                (KeyType::REPRESENTATIVE, _) => {
+                    self.is_valid_representative_key(key, verifiers)
                 }
                 (KeyType::CONTENT, Some(proposal_id)) => {
                     self.is_valid_content_key(proposal_id)
@@ -112,6 +143,9 @@ where
                 (KeyType::FUNDS, Some(proposal_id)) => {
                     self.is_valid_funds(proposal_id, &native_token)
                 }
+                (KeyType::DEPOSIT, Some(proposal_id)) => {
+                    self.is_valid_deposit(proposal_id, key, verifiers)
+                }
                 (KeyType::AUTHOR, Some(proposal_id)) => {
                     self.is_valid_author(proposal_id, verifiers)
                 }
@@ -154,13 +188,18 @@ where
         }
 
         for counter in pre_counter..post_counter {
+            let author_key = gov_storage::get_author_key(counter);
+            let author: Address =
+                self.force_read(&author_key, ReadType::Post)?;
+
             // Construct the set of expected keys
             // NOTE: we don't check the existence of committing_epoch because
             // it's going to be checked later into the VP
             let mandatory_keys = BTreeSet::from([
                 counter_key.clone(),
                 gov_storage::get_content_key(counter),
-                gov_storage::get_author_key(counter),
+                author_key,
+                gov_storage::get_proposal_deposit_key(counter, &author),
                 gov_storage::get_proposal_type_key(counter),
                 gov_storage::get_funds_key(counter),
                 gov_storage::get_voting_start_epoch_key(counter),
@@ -181,6 +220,7 @@ where
         &self,
         proposal_id: u64,
         key: &Key,
+        keys_changed: &BTreeSet<Key>,
         verifiers: &BTreeSet<Address>,
     ) -> Result<bool> {
         let counter_key = gov_storage::get_counter_key();
@@ -203,8 +243,6 @@ where
         let voter = gov_storage::get_voter_address(key);
         let delegation_address = gov_storage::get_vote_delegation_address(key);
 
-        let vote: StorageProposalVote = self.force_read(key, ReadType::Post)?;
-
         let (voter_address, delegation_address) =
             match (voter, delegation_address) {
                 (Some(voter_address), Some(delegator_address)) => {
@@ -222,6 +260,19 @@ where
             return Ok(false);
         }
 
+        // Every vote is recorded in the history of the voter
+        if !self.is_vote_recorded(proposal_id, voter_address, keys_changed) {
+            tracing::info!("Vote not recorded in the vote history.");
+            return Ok(false);
+        }
+
+        // The vote of a delegation that a new vote of the voter doesn't
+        // cover anymore is deleted, which the vote history validates
+        if !self.ctx.has_key_post(key)? {
+            return Ok(true);
+        }
+        let vote: StorageProposalVote = self.force_read(key, ReadType::Post)?;
+
         // Voted outside of voting window. We dont check for validator because
         // if the proposal type is validator, we need to let
         // them vote for the entire voting window.
@@ -242,6 +293,14 @@ where
             return Err(Error::InvalidVoteType);
         }
 
+        if !self.has_min_funds(proposal_id)? {
+            tracing::info!(
+                "Voted on proposal {proposal_id} whose deposit is below the \
+                 minimum."
+            );
+            return Ok(false);
+        }
+
         // first check if validator, then check if delegator
         let is_validator = self
             .is_validator(
@@ -272,6 +331,244 @@ where
         Ok(is_delegator)
     }
 
+    fn is_valid_representative_vote_key(
+        &self,
+        proposal_id: u64,
+        key: &Key,
+        keys_changed: &BTreeSet<Key>,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let counter_key = gov_storage::get_counter_key();
+        let voting_start_epoch_key =
+            gov_storage::get_voting_start_epoch_key(proposal_id);
+        let voting_end_epoch_key =
+            gov_storage::get_voting_end_epoch_key(proposal_id);
+        let proposal_type_key = gov_storage::get_proposal_type_key(proposal_id);
+
+        let current_epoch = self.ctx.get_block_epoch()?;
+
+        let pre_counter: u64 = self.force_read(&counter_key, ReadType::Pre)?;
+        let pre_voting_start_epoch: Epoch =
+            self.force_read(&voting_start_epoch_key, ReadType::Pre)?;
+        let pre_voting_end_epoch: Epoch =
+            self.force_read(&voting_end_epoch_key, ReadType::Pre)?;
+        let proposal_type: ProposalType =
+            self.force_read(&proposal_type_key, ReadType::Pre)?;
+
+        let representative = gov_storage::get_representative_vote_address(key)
+            .ok_or_else(|| Error::InvalidVoteKey(key.to_string()))?;
+
+        // Invalid proposal id
+        if pre_counter <= proposal_id {
+            tracing::info!(
+                "Invalid proposal ID. Expected {pre_counter} or lower, got \
+                 {proposal_id}."
+            );
+            return Ok(false);
+        }
+
+        // Every vote is recorded in the history of the voter
+        if !self.is_vote_recorded(proposal_id, representative, keys_changed) {
+            tracing::info!("Vote not recorded in the vote history.");
+            return Ok(false);
+        }
+
+        // The vote as a representative is deleted when a new vote of the
+        // voter is cast as a validator, which the vote history validates
+        if !self.ctx.has_key_post(key)? {
+            return Ok(true);
+        }
+        let vote: StorageProposalVote = self.force_read(key, ReadType::Post)?;
+
+        if !self.is_valid_voting_window(
+            current_epoch,
+            pre_voting_start_epoch,
+            pre_voting_end_epoch,
+            false,
+        ) {
+            tracing::info!(
+                "Voted outside voting window. Current epoch: {current_epoch}, \
+                 start: {pre_voting_start_epoch}, end: {pre_voting_end_epoch}."
+            );
+            return Ok(false);
+        }
+
+        if !vote.is_compatible(&proposal_type) {
+            return Err(Error::InvalidVoteType);
+        }
+
+        if !self.has_min_funds(proposal_id)? {
+            tracing::info!(
+                "Voted on proposal {proposal_id} whose deposit is below the \
+                 minimum."
+            );
+            return Ok(false);
+        }
+
+        // Validators vote with their own stake only
+        Ok(verifiers.contains(representative)
+            && !is_validator(&self.ctx.pre(), representative)?)
+    }
+
+    /// Validate the history of the votes of a voter. The new vote must be
+    /// appended to it and match the vote written for each of its delegations
+    /// and, unless the voter is a validator, as a representative. The votes
+    /// of the previous vote that the new one doesn't cover must be deleted.
+    fn is_valid_vote_history_key(
+        &self,
+        proposal_id: u64,
+        key: &Key,
+        keys_changed: &BTreeSet<Key>,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let voter = gov_storage::get_vote_history_voter(key)
+            .ok_or_else(|| Error::InvalidVoteKey(key.to_string()))?;
+        if !verifiers.contains(voter) {
+            return Ok(false);
+        }
+
+        let history = gov_storage::vote_history_handle(proposal_id, voter);
+        let pre_len = history.len(&self.ctx.pre())?;
+        let post_len = history.len(&self.ctx.post())?;
+        let is_appended = post_len == pre_len + 1
+            && match history.is_valid_sub_key(key) {
+                Ok(Some(lazy_vec::SubKey::Len)) => true,
+                Ok(Some(lazy_vec::SubKey::Data(index))) => index == pre_len,
+                _ => false,
+            };
+        if !is_appended {
+            tracing::info!("The vote history can only be appended to.");
+            return Ok(false);
+        }
+        let record = match history.get(&self.ctx.post(), pre_len)? {
+            Some(record) => record,
+            None => return Ok(false),
+        };
+        if record.epoch != self.ctx.get_block_epoch()?
+            || record.height != self.ctx.get_block_height()?
+        {
+            return Ok(false);
+        }
+        if !gov_storage::voter_votes_handle(voter)
+            .contains(&self.ctx.post(), &proposal_id)?
+        {
+            return Ok(false);
+        }
+
+        let is_validator_vote = record.delegations.contains(voter);
+        let mut vote_keys = record
+            .delegations
+            .iter()
+            .map(|delegation| {
+                gov_storage::get_vote_proposal_key(
+                    proposal_id,
+                    voter.clone(),
+                    delegation.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        if !is_validator_vote {
+            vote_keys.push(gov_storage::get_representative_vote_key(
+                proposal_id,
+                voter,
+            ));
+        }
+        for vote_key in vote_keys {
+            if !keys_changed.contains(&vote_key) {
+                return Ok(false);
+            }
+            let vote: StorageProposalVote =
+                self.force_read(&vote_key, ReadType::Post)?;
+            if vote != record.vote {
+                return Ok(false);
+            }
+        }
+
+        let previous =
+            match get_current_vote(&self.ctx.pre(), proposal_id, voter)? {
+                Some(previous) => previous,
+                None => return Ok(true),
+            };
+        let mut stale_keys = previous
+            .delegations
+            .iter()
+            .filter(|delegation| !record.delegations.contains(delegation))
+            .map(|delegation| {
+                gov_storage::get_vote_proposal_key(
+                    proposal_id,
+                    voter.clone(),
+                    delegation.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        if !previous.delegations.contains(voter) && is_validator_vote {
+            stale_keys.push(gov_storage::get_representative_vote_key(
+                proposal_id,
+                voter,
+            ));
+        }
+        for stale_key in stale_keys {
+            if self.ctx.has_key_post(&stale_key)? {
+                tracing::info!(
+                    "Stale vote key {stale_key} of the previous vote must be \
+                     deleted."
+                );
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+
+    /// Validate the index of the proposals a voter voted on. A proposal can
+    /// only be added to it along with a vote of the voter, and never removed.
+    fn is_valid_voter_votes_key(
+        &self,
+        key: &Key,
+        keys_changed: &BTreeSet<Key>,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let (voter, proposal_id) = gov_storage::is_voter_votes_key(key)
+            .ok_or_else(|| Error::InvalidVoteKey(key.to_string()))?;
+        Ok(verifiers.contains(voter)
+            && self.ctx.has_key_post(key)?
+            && self.is_vote_recorded(proposal_id, voter, keys_changed))
+    }
+
+    /// Check if a vote of a voter on a proposal is appended to its history
+    fn is_vote_recorded(
+        &self,
+        proposal_id: u64,
+        voter: &Address,
+        keys_changed: &BTreeSet<Key>,
+    ) -> bool {
+        let prefix =
+            gov_storage::get_vote_history_prefix_key(proposal_id, voter);
+        keys_changed
+            .iter()
+            .any(|key| matches!(key.split_prefix(&prefix), Some(Some(_))))
+    }
+
+    fn is_valid_representative_key(
+        &self,
+        key: &Key,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let delegator = gov_storage::get_represented_address(key)
+            .ok_or_else(|| Error::InvalidRepresentativeKey(key.to_string()))?;
+        if !verifiers.contains(delegator) {
+            return Ok(false);
+        }
+
+        let representative: Option<Address> = self.ctx.post().read(key)?;
+        match representative {
+            // Validators vote with their own stake only
+            Some(representative) => Ok(&representative != delegator
+                && !is_validator(&self.ctx.pre(), delegator)?),
+            // The delegation can always be revoked
+            None => Ok(true),
+        }
+    }
+
     /// Validate a content key
     pub fn is_valid_content_key(&self, proposal_id: u64) -> Result<bool> {
         let content_key: Key = gov_storage::get_content_key(proposal_id);
@@ -337,9 +634,41 @@ where
                 if payments.len() > MAX_PGF_ACTIONS {
                     Ok(false)
                 } else {
-                    Ok(true)
+                    Ok(payments.iter().all(|action| action.target().is_valid()))
                 }
             }
+            ProposalType::ParameterChange(changes) => {
+                // Each parameter is changed at most once
+                let changed_parameters = changes
+                    .iter()
+                    .map(ParameterChange::name)
+                    .collect::<BTreeSet<_>>();
+                if changes.is_empty()
+                    || changed_parameters.len() != changes.len()
+                    || !changes.iter().all(ParameterChange::is_valid)
+                {
+                    return Ok(false);
+                }
+                // The ERC20 whitelist is checked again when the proposal is
+                // executed, as it may change in the meantime
+                for change in &changes {
+                    if let ParameterChange::Erc20Whitelist(erc20s) = change {
+                        for erc20 in erc20s {
+                            if !whitelist::is_valid_whitelist_change(
+                                &self.ctx.pre(),
+                                erc20,
+                            )? {
+                                return Ok(false);
+                            }
+                        }
+                    }
+                }
+                Ok(true)
+            }
+            ProposalType::Upgrade(upgrade) => {
+                let current_height = self.ctx.get_block_height()?;
+                Ok(upgrade.is_valid(current_height))
+            }
             _ => Ok(true), // default proposal
         }
     }
@@ -350,7 +679,7 @@ where
         let proposal_type: ProposalType =
             self.force_read(&proposal_type_key, ReadType::Post)?;
 
-        if !proposal_type.is_default() {
+        if !proposal_type.is_default() && !proposal_type.is_emergency() {
             return Ok(false);
         }
 
@@ -436,8 +765,6 @@ where
         let start_epoch_key =
             gov_storage::get_voting_start_epoch_key(proposal_id);
         let end_epoch_key = gov_storage::get_voting_end_epoch_key(proposal_id);
-        let min_period_parameter_key =
-            gov_storage::get_min_proposal_voting_period_key();
 
         let current_epoch = self.ctx.get_block_epoch()?;
 
@@ -452,8 +779,7 @@ where
             self.force_read(&start_epoch_key, ReadType::Post)?;
         let end_epoch: Epoch =
             self.force_read(&end_epoch_key, ReadType::Post)?;
-        let min_period: u64 =
-            self.force_read(&min_period_parameter_key, ReadType::Pre)?;
+        let min_period = self.min_voting_period(proposal_id)?;
 
         if end_epoch <= start_epoch || start_epoch <= current_epoch {
             return Ok(false);
@@ -468,8 +794,6 @@ where
         let start_epoch_key =
             gov_storage::get_voting_start_epoch_key(proposal_id);
         let end_epoch_key = gov_storage::get_voting_end_epoch_key(proposal_id);
-        let min_period_parameter_key =
-            gov_storage::get_min_proposal_voting_period_key();
         let max_period_parameter_key =
             gov_storage::get_max_proposal_period_key();
 
@@ -486,8 +810,7 @@ where
             self.force_read(&start_epoch_key, ReadType::Post)?;
         let end_epoch: Epoch =
             self.force_read(&end_epoch_key, ReadType::Post)?;
-        let min_period: u64 =
-            self.force_read(&min_period_parameter_key, ReadType::Pre)?;
+        let min_period = self.min_voting_period(proposal_id)?;
         let max_period: u64 =
             self.force_read(&max_period_parameter_key, ReadType::Pre)?;
 
@@ -504,7 +827,23 @@ where
             && (end_epoch - start_epoch).0 <= max_period)
     }
 
-    /// Validate a funds key
+    /// Read the minimum voting period of a proposal, which depends on
+    /// whether it is an emergency proposal
+    fn min_voting_period(&self, proposal_id: u64) -> Result<u64> {
+        let proposal_type_key = gov_storage::get_proposal_type_key(proposal_id);
+        let proposal_type: ProposalType =
+            self.force_read(&proposal_type_key, ReadType::Post)?;
+        let min_period_parameter_key = if proposal_type.is_emergency() {
+            gov_storage::get_min_emergency_proposal_voting_period_key()
+        } else {
+            gov_storage::get_min_proposal_voting_period_key()
+        };
+        self.force_read(&min_period_parameter_key, ReadType::Pre)
+    }
+
+    /// Validate a funds key. The funds of a new proposal are the initial
+    /// deposit of its author, which can be topped up until the start of the
+    /// voting period as long as they are below the minimum.
     pub fn is_valid_funds(
         &self,
         proposal_id: u64,
@@ -513,45 +852,109 @@ where
         let funds_key = gov_storage::get_funds_key(proposal_id);
         let balance_key =
             token::balance_key(native_token_address, self.ctx.address);
-        let min_funds_parameter_key = gov_storage::get_min_proposal_fund_key();
 
-        let min_funds_parameter: token::Amount =
-            self.force_read(&min_funds_parameter_key, ReadType::Pre)?;
-        let pre_balance: Option<token::Amount> =
-            self.ctx.pre().read(&balance_key)?;
+        let pre_balance: token::Amount =
+            self.ctx.pre().read(&balance_key)?.unwrap_or_default();
         let post_balance: token::Amount =
             self.force_read(&balance_key, ReadType::Post)?;
+        let pre_funds: Option<token::Amount> =
+            self.ctx.pre().read(&funds_key)?;
         let post_funds: token::Amount =
             self.force_read(&funds_key, ReadType::Post)?;
 
-        if let Some(pre_balance) = pre_balance {
-            Ok(post_funds >= min_funds_parameter
-                && post_balance - pre_balance == post_funds)
-        } else {
-            Ok(post_funds >= min_funds_parameter && post_balance == post_funds)
+        let deposited =
+            match post_funds.checked_sub(pre_funds.unwrap_or_default()) {
+                Some(deposited) => deposited,
+                None => return Ok(false),
+            };
+        if post_balance.checked_sub(pre_balance) != Some(deposited) {
+            return Ok(false);
+        }
+
+        match pre_funds {
+            Some(pre_funds) => {
+                let min_funds_parameter_key =
+                    gov_storage::get_min_proposal_fund_key();
+                let min_funds_parameter: token::Amount =
+                    self.force_read(&min_funds_parameter_key, ReadType::Pre)?;
+                let voting_start_epoch_key =
+                    gov_storage::get_voting_start_epoch_key(proposal_id);
+                let voting_start_epoch: Epoch =
+                    self.force_read(&voting_start_epoch_key, ReadType::Pre)?;
+                let current_epoch = self.ctx.get_block_epoch()?;
+
+                let is_valid = pre_funds < min_funds_parameter
+                    && current_epoch < voting_start_epoch;
+                if !is_valid {
+                    tracing::info!(
+                        "Deposit on proposal {proposal_id} with funds \
+                         {pre_funds:?} outside of its deposit period ending \
+                         at epoch {voting_start_epoch}."
+                    );
+                }
+                Ok(is_valid)
+            }
+            // A new proposal, whose initial deposit may be below the
+            // minimum as long as it is topped up before the voting period.
+            // Otherwise, it cannot be voted on and is rejected without a
+            // tally at the end of its voting period.
+            None => Ok(true),
         }
     }
 
-    /// Validate a balance key
-    fn is_valid_balance(&self, native_token_address: &Address) -> Result<bool> {
-        let balance_key =
-            token::balance_key(native_token_address, self.ctx.address);
-        let min_funds_parameter_key = gov_storage::get_min_proposal_fund_key();
+    /// Validate the deposit key of a backer of a proposal, which must match
+    /// the change of the funds of the proposal
+    fn is_valid_deposit(
+        &self,
+        proposal_id: u64,
+        key: &Key,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool> {
+        let depositor = gov_storage::get_proposal_depositor_address(key)
+            .ok_or_else(|| Error::InvalidDepositKey(key.to_string()))?;
+        let funds_key = gov_storage::get_funds_key(proposal_id);
 
-        let pre_balance: Option<token::Amount> =
-            self.ctx.pre().read(&balance_key)?;
+        let pre_deposit: token::Amount =
+            self.ctx.pre().read(key)?.unwrap_or_default();
+        let post_deposit: token::Amount =
+            self.force_read(key, ReadType::Post)?;
+        let pre_funds: token::Amount =
+            self.ctx.pre().read(&funds_key)?.unwrap_or_default();
+        let post_funds: token::Amount =
+            self.force_read(&funds_key, ReadType::Post)?;
+
+        let deposited = post_deposit.checked_sub(pre_deposit);
+        Ok(verifiers.contains(depositor)
+            && deposited.is_some()
+            && deposited == post_funds.checked_sub(pre_funds))
+    }
 
+    /// Check if the funds of a proposal reached the minimum, such that it can
+    /// be voted on
+    fn has_min_funds(&self, proposal_id: u64) -> Result<bool> {
+        let funds_key = gov_storage::get_funds_key(proposal_id);
+        let min_funds_parameter_key = gov_storage::get_min_proposal_fund_key();
+
+        let funds: token::Amount =
+            self.force_read(&funds_key, ReadType::Pre)?;
         let min_funds_parameter: token::Amount =
             self.force_read(&min_funds_parameter_key, ReadType::Pre)?;
+
+        Ok(funds >= min_funds_parameter)
+    }
+
+    /// Validate a balance key. The balance of governance only increases with
+    /// the deposits on the proposals, which are checked with their funds.
+    fn is_valid_balance(&self, native_token_address: &Address) -> Result<bool> {
+        let balance_key =
+            token::balance_key(native_token_address, self.ctx.address);
+
+        let pre_balance: token::Amount =
+            self.ctx.pre().read(&balance_key)?.unwrap_or_default();
         let post_balance: token::Amount =
             self.force_read(&balance_key, ReadType::Post)?;
 
-        if let Some(pre_balance) = pre_balance {
-            Ok(post_balance > pre_balance
-                && post_balance - pre_balance >= min_funds_parameter)
-        } else {
-            Ok(post_balance >= min_funds_parameter)
-        }
+        Ok(post_balance >= pre_balance)
     }
 
     /// Validate a author key
@@ -689,6 +1092,14 @@ enum KeyType {
     #[allow(non_camel_case_types)]
     VOTE,
     #[allow(non_camel_case_types)]
+    REPRESENTATIVE_VOTE,
+    #[allow(non_camel_case_types)]
+    VOTE_HISTORY,
+    #[allow(non_camel_case_types)]
+    VOTER_VOTES,
+    #[allow(non_camel_case_types)]
+    REPRESENTATIVE,
+    #[allow(non_camel_case_types)]
     CONTENT,
     #[allow(non_camel_case_types)]
     PROPOSAL_CODE,
@@ -705,6 +1116,8 @@ enum KeyType {
     #[allow(non_camel_case_types)]
     FUNDS,
     #[allow(non_camel_case_types)]
+    DEPOSIT,
+    #[allow(non_camel_case_types)]
     BALANCE,
     #[allow(non_camel_case_types)]
     AUTHOR,
@@ -720,6 +1133,14 @@ impl KeyType {
     fn from_key(key: &Key, native_token: &Address) -> Self {
         if gov_storage::is_vote_key(key) {
             Self::VOTE
+        } else if gov_storage::is_representative_vote_key(key) {
+            KeyType::REPRESENTATIVE_VOTE
+        } else if gov_storage::is_vote_history_key(key) {
+            KeyType::VOTE_HISTORY
+        } else if gov_storage::is_voter_votes_key(key).is_some() {
+            KeyType::VOTER_VOTES
+        } else if gov_storage::is_representative_key(key) {
+            KeyType::REPRESENTATIVE
         } else if gov_storage::is_content_key(key) {
             KeyType::CONTENT
         } else if gov_storage::is_proposal_type_key(key) {
@@ -736,6 +1157,8 @@ impl KeyType {
             KeyType::END_EPOCH
         } else if gov_storage::is_balance_key(key) {
             KeyType::FUNDS
+        } else if gov_storage::is_proposal_deposit_key(key) {
+            KeyType::DEPOSIT
         } else if gov_storage::is_author_key(key) {
             KeyType::AUTHOR
         } else if gov_storage::is_counter_key(key) {
@@ -751,3 +1174,178 @@ impl KeyType {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::env::temp_dir;
+
+    use namada_core::ledger::gas::TxGasMeter;
+    use namada_core::ledger::governance::storage::vote::{
+        VoteRecord, VoteType,
+    };
+    use namada_core::ledger::storage_api::governance::{
+        get_vote_history, get_voter_votes, vote_proposal,
+    };
+    use namada_core::ledger::storage_api::StorageWrite;
+    use namada_core::types::transaction::governance::VoteProposalData;
+
+    use super::*;
+    use crate::ledger::gas::VpGasMeter;
+    use crate::ledger::storage::mockdb::MockDB;
+    use crate::ledger::storage::traits::Sha256Hasher;
+    use crate::ledger::storage::WlStorage;
+    use crate::types::address::testing::{
+        established_address_1, established_address_2, established_address_3,
+    };
+    use crate::types::storage::TxIndex;
+    use crate::types::transaction::TxType;
+    use crate::vm::wasm::VpCache;
+    use crate::vm::WasmCacheRwAccess;
+
+    const PROPOSAL_ID: u64 = 0;
+
+    /// Validate the changes of the vote history keys of the current tx
+    fn is_valid_vote_history(
+        wl_storage: &WlStorage<MockDB, Sha256Hasher>,
+        voter: &Address,
+    ) -> bool {
+        let tx = Tx::from_type(TxType::Raw);
+        let keys_changed = wl_storage.write_log.get_keys();
+        let verifiers = BTreeSet::from([voter.clone()]);
+        let ctx = Ctx::<_, _, WasmCacheRwAccess>::new(
+            &ADDRESS,
+            &wl_storage.storage,
+            &wl_storage.write_log,
+            &tx,
+            &TxIndex(0),
+            VpGasMeter::new_from_tx_meter(&TxGasMeter::new_from_sub_limit(
+                u64::MAX.into(),
+            )),
+            &keys_changed,
+            &verifiers,
+            VpCache::new(temp_dir(), 100usize),
+        );
+        let vp = GovernanceVp { ctx };
+        let history_keys = keys_changed
+            .iter()
+            .filter(|key| gov_storage::is_vote_history_key(key))
+            .collect::<Vec<_>>();
+        !history_keys.is_empty()
+            && history_keys.into_iter().all(|key| {
+                vp.is_valid_vote_history_key(
+                    PROPOSAL_ID,
+                    key,
+                    &keys_changed,
+                    &verifiers,
+                )
+                .unwrap_or(false)
+            })
+    }
+
+    /// Check that a re-vote with fewer delegations deletes the votes of the
+    /// previous vote for the delegations it doesn't cover anymore, and that
+    /// the vote history accepts it.
+    #[test]
+    fn test_revote_deletes_stale_votes() {
+        let mut wl_storage = WlStorage::<MockDB, Sha256Hasher>::default();
+        let voter = established_address_1();
+        let validator_a = established_address_2();
+        let validator_b = established_address_3();
+
+        vote_proposal(
+            &mut wl_storage,
+            VoteProposalData {
+                id: PROPOSAL_ID,
+                vote: StorageProposalVote::Yay(VoteType::Default),
+                voter: voter.clone(),
+                delegations: vec![validator_a.clone(), validator_b.clone()],
+            },
+        )
+        .unwrap();
+        assert!(is_valid_vote_history(&wl_storage, &voter));
+        wl_storage.commit_tx();
+
+        vote_proposal(
+            &mut wl_storage,
+            VoteProposalData {
+                id: PROPOSAL_ID,
+                vote: StorageProposalVote::Nay,
+                voter: voter.clone(),
+                delegations: vec![validator_a.clone()],
+            },
+        )
+        .unwrap();
+        assert!(is_valid_vote_history(&wl_storage, &voter));
+
+        let vote_a = gov_storage::get_vote_proposal_key(
+            PROPOSAL_ID,
+            voter.clone(),
+            validator_a,
+        );
+        let vote_b = gov_storage::get_vote_proposal_key(
+            PROPOSAL_ID,
+            voter.clone(),
+            validator_b,
+        );
+        assert_eq!(
+            wl_storage.read::<StorageProposalVote>(&vote_a).unwrap(),
+            Some(StorageProposalVote::Nay)
+        );
+        assert!(!wl_storage.has_key(&vote_b).unwrap());
+
+        let history =
+            get_vote_history(&wl_storage, PROPOSAL_ID, &voter).unwrap();
+        assert_eq!(history.len(), 2);
+        let votes = get_voter_votes(&wl_storage, &voter).unwrap();
+        assert_eq!(votes.len(), 1);
+        assert_eq!(votes.get(&PROPOSAL_ID), history.last());
+    }
+
+    /// Check that the vote history can't be rewritten and that a re-vote
+    /// must delete the votes of the previous vote it doesn't cover.
+    #[test]
+    fn test_invalid_vote_history_rejected() {
+        let mut wl_storage = WlStorage::<MockDB, Sha256Hasher>::default();
+        let voter = established_address_1();
+        let validator_a = established_address_2();
+        let validator_b = established_address_3();
+
+        vote_proposal(
+            &mut wl_storage,
+            VoteProposalData {
+                id: PROPOSAL_ID,
+                vote: StorageProposalVote::Yay(VoteType::Default),
+                voter: voter.clone(),
+                delegations: vec![validator_a.clone(), validator_b],
+            },
+        )
+        .unwrap();
+        wl_storage.commit_tx();
+
+        // Rewrite the recorded vote
+        let history = gov_storage::vote_history_handle(PROPOSAL_ID, &voter);
+        let mut record = history.get(&wl_storage, 0).unwrap().unwrap();
+        record.vote = StorageProposalVote::Nay;
+        history.update(&mut wl_storage, 0, record.clone()).unwrap();
+        assert!(!is_valid_vote_history(&wl_storage, &voter));
+        wl_storage.drop_tx();
+
+        // Re-vote with a single delegation, keeping the other vote
+        let vote_a = gov_storage::get_vote_proposal_key(
+            PROPOSAL_ID,
+            voter.clone(),
+            validator_a.clone(),
+        );
+        wl_storage.write(&vote_a, StorageProposalVote::Nay).unwrap();
+        history
+            .push(
+                &mut wl_storage,
+                VoteRecord {
+                    delegations: vec![validator_a],
+                    ..record
+                },
+            )
+            .unwrap();
+        assert!(!is_valid_vote_history(&wl_storage, &voter));
+    }
+}
@@ -83,6 +83,17 @@ impl ProposalEvent {
         )
     }
 
+    /// Create a new proposal event for vetoed proposal
+    pub fn vetoed_proposal_event(proposal_id: u64) -> Self {
+        ProposalEvent::new(
+            EventType::Proposal.to_string(),
+            TallyResult::Vetoed,
+            proposal_id,
+            false,
+            false,
+        )
+    }
+
     /// Create a new proposal event for default proposal
     pub fn default_proposal_event(
         proposal_id: u64,
@@ -120,6 +131,31 @@ impl ProposalEvent {
         )
     }
 
+    /// Create a new proposal event for parameter change proposal
+    pub fn parameter_change_proposal_event(
+        proposal_id: u64,
+        result: bool,
+    ) -> Self {
+        ProposalEvent::new(
+            EventType::Proposal.to_string(),
+            TallyResult::Passed,
+            proposal_id,
+            false,
+            result,
+        )
+    }
+
+    /// Create a new proposal event for upgrade proposal
+    pub fn upgrade_proposal_event(proposal_id: u64, result: bool) -> Self {
+        ProposalEvent::new(
+            EventType::Proposal.to_string(),
+            TallyResult::Passed,
+            proposal_id,
+            false,
+            result,
+        )
+    }
+
     /// Create a new proposal event for eth proposal
     pub fn eth_proposal_event(proposal_id: u64, result: bool) -> Self {
         ProposalEvent::new(
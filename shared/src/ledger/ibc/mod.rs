@@ -1,14 +1,39 @@
 //! IBC integration
 
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use namada_core::ibc::apps::transfer::types::msgs::transfer::MsgTransfer;
+use namada_core::ibc::apps::transfer::types::packet::PacketData;
+use namada_core::ibc::apps::transfer::types::PrefixedCoin;
+use namada_core::ibc::core::channel::types::timeout::TimeoutHeight;
+use namada_core::ibc::primitives::Msg;
+use namada_core::ledger::governance::storage::proposal::PGFIbcTarget;
 pub use namada_core::ledger::ibc::storage;
 use namada_core::ledger::ibc::storage::{
     channel_counter_key, client_counter_key, connection_counter_key,
 };
-use namada_core::ledger::storage::WlStorage;
-use namada_core::ledger::storage_api::StorageWrite;
+use namada_core::ledger::ibc::{
+    IbcActions, IbcCommonContext, IbcStorageContext,
+};
+use namada_core::ledger::masp_utils;
+use namada_core::ledger::storage::wl_storage::WriteLogAndStorage;
+use namada_core::ledger::storage::write_log::{self, WriteLog};
+use namada_core::ledger::storage::{Storage, WlStorage};
+use namada_core::ledger::storage_api::{self, token, ResultExt, StorageWrite};
+use namada_core::tendermint::Time as TmTime;
+use namada_core::types::address::Address;
+use namada_core::types::hash::Hash;
+use namada_core::types::ibc::{IbcEvent, IbcShieldedTransfer};
+use namada_core::types::time::DurationSecs;
+use namada_core::types::token::{DenominatedAmount, Denomination};
 
 use crate::ledger::storage::{self as ledger_storage, StorageHasher};
 
+/// The timeout of the IBC transfers made by the protocol, in seconds from the
+/// time of the block in which they are sent
+pub const PROTOCOL_TRANSFER_TIMEOUT_SECS: u64 = 3600;
+
 /// Initialize storage in the genesis block.
 pub fn init_genesis_storage<DB, H>(storage: &mut WlStorage<DB, H>)
 where
@@ -39,3 +64,192 @@ where
         .write_bytes(&key, value)
         .expect("Unable to write the initial channel counter");
 }
+
+/// IBC context for the IBC operations executed by the protocol, such as the
+/// PGF payments to targets on other chains. The changes are written to the
+/// write log without going through any VP.
+#[derive(Debug)]
+pub struct IbcProtocolContext<'a, D, H>
+where
+    D: ledger_storage::DB + for<'iter> ledger_storage::DBIter<'iter>,
+    H: StorageHasher,
+{
+    wl_storage: &'a mut WlStorage<D, H>,
+}
+
+impl<D, H> WriteLogAndStorage for IbcProtocolContext<'_, D, H>
+where
+    D: ledger_storage::DB + for<'iter> ledger_storage::DBIter<'iter>,
+    H: StorageHasher,
+{
+    type D = D;
+    type H = H;
+
+    fn write_log(&self) -> &WriteLog {
+        &self.wl_storage.write_log
+    }
+
+    fn write_log_mut(&mut self) -> &mut WriteLog {
+        &mut self.wl_storage.write_log
+    }
+
+    fn storage(&self) -> &Storage<D, H> {
+        &self.wl_storage.storage
+    }
+
+    fn split_borrow(&mut self) -> (&mut WriteLog, &Storage<D, H>) {
+        self.wl_storage.split_borrow()
+    }
+
+    fn write_tx_hash(&mut self, hash: Hash) -> write_log::Result<()> {
+        self.wl_storage.write_tx_hash(hash)
+    }
+}
+
+impl<D, H> IbcStorageContext for IbcProtocolContext<'_, D, H>
+where
+    D: 'static + ledger_storage::DB + for<'iter> ledger_storage::DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    fn emit_ibc_event(&mut self, event: IbcEvent) -> storage_api::Result<()> {
+        self.wl_storage.write_log.emit_ibc_event(event);
+        Ok(())
+    }
+
+    fn get_ibc_events(
+        &self,
+        event_type: impl AsRef<str>,
+    ) -> storage_api::Result<Vec<IbcEvent>> {
+        Ok(self
+            .wl_storage
+            .write_log
+            .get_ibc_events()
+            .iter()
+            .filter(|event| event.event_type == event_type.as_ref())
+            .cloned()
+            .collect())
+    }
+
+    fn transfer_token(
+        &mut self,
+        src: &Address,
+        dest: &Address,
+        token: &Address,
+        amount: DenominatedAmount,
+    ) -> storage_api::Result<()> {
+        let amount = amount.to_amount(token, self)?;
+        token::transfer(self, token, src, dest, amount)
+    }
+
+    fn handle_masp_tx(
+        &mut self,
+        shielded: &IbcShieldedTransfer,
+    ) -> storage_api::Result<()> {
+        masp_utils::handle_masp_tx(
+            self,
+            &shielded.transfer,
+            &shielded.masp_tx,
+        )?;
+        masp_utils::update_note_commitment_tree(self, &shielded.masp_tx)
+    }
+
+    fn mint_token(
+        &mut self,
+        target: &Address,
+        token: &Address,
+        amount: DenominatedAmount,
+    ) -> storage_api::Result<()> {
+        let amount = amount.to_amount(token, self)?;
+        token::credit_tokens(self, token, target, amount)
+    }
+
+    fn burn_token(
+        &mut self,
+        target: &Address,
+        token: &Address,
+        amount: DenominatedAmount,
+    ) -> storage_api::Result<()> {
+        let amount = amount.to_amount(token, self)?;
+        token::burn(self, token, target, amount)
+    }
+
+    fn log_string(&self, message: String) {
+        tracing::trace!(message);
+    }
+}
+
+impl<D, H> IbcCommonContext for IbcProtocolContext<'_, D, H>
+where
+    D: 'static + ledger_storage::DB + for<'iter> ledger_storage::DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+}
+
+/// Transfer the given amount of token from the source address to the target
+/// on the counterparty chain with an ICS-20 transfer. If the packet fails or
+/// times out, the token is refunded to the source address by the transfer
+/// module when the acknowledgement or the timeout is relayed back.
+pub fn transfer_over_ibc<D, H>(
+    wl_storage: &mut WlStorage<D, H>,
+    token: &Address,
+    source: &Address,
+    target: &PGFIbcTarget,
+) -> storage_api::Result<()>
+where
+    D: 'static + ledger_storage::DB + for<'iter> ledger_storage::DBIter<'iter>,
+    H: 'static + StorageHasher,
+{
+    // The amount of an ICS-20 packet is an integer in the display unit of
+    // the token
+    let denom =
+        token::read_denom(wl_storage, token)?.unwrap_or(Denomination(0));
+    let amount = DenominatedAmount::new(target.amount, denom).canonical();
+    if amount.denom().0 != 0 {
+        return Err(storage_api::Error::new_const(
+            "The amount of an IBC transfer must be a whole number of tokens",
+        ));
+    }
+    let token = PrefixedCoin {
+        denom: token
+            .to_string()
+            .parse()
+            .map_err(|e| storage_api::Error::new(format!("{}", e)))?,
+        amount: amount.into(),
+    };
+    let packet_data = PacketData {
+        token,
+        sender: source.to_string().into(),
+        receiver: target.target.clone().into(),
+        memo: String::default().into(),
+    };
+    // The timeout is relative to the time of the current block, such that
+    // the message is the same on every node
+    let block_time = match &wl_storage.storage.header {
+        Some(header) => header.time,
+        None => wl_storage
+            .storage
+            .get_last_block_timestamp()
+            .into_storage_result()?,
+    };
+    let timeout_timestamp: TmTime = (block_time
+        + DurationSecs(PROTOCOL_TRANSFER_TIMEOUT_SECS))
+    .try_into()
+    .map_err(|e| storage_api::Error::new(format!("{}", e)))?;
+    let message = MsgTransfer {
+        port_id_on_a: target.port_id.clone(),
+        chan_id_on_a: target.channel_id.clone(),
+        packet_data,
+        timeout_height_on_b: TimeoutHeight::Never,
+        timeout_timestamp_on_b: timeout_timestamp.into(),
+    };
+    let mut data = vec![];
+    prost::Message::encode(&message.to_any(), &mut data)
+        .into_storage_result()?;
+
+    let ctx = IbcProtocolContext { wl_storage };
+    let mut actions = IbcActions::new(Rc::new(RefCell::new(ctx)));
+    // Nothing is written if the transfer is invalid, e.g. if the channel is
+    // closed
+    actions.validate(&data).into_storage_result()?;
+    actions.execute(&data).into_storage_result()
+}
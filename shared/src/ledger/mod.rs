@@ -15,7 +15,8 @@ pub mod vp_host_fns;
 #[cfg(feature = "wasm-runtime")]
 pub use dry_run_tx::dry_run_tx;
 pub use namada_core::ledger::{
-    gas, parameters, replay_protection, storage_api, tx_env, vp_env,
+    event_index, gas, parameters, replay_protection, storage_api, tx_env,
+    vp_env,
 };
 
 #[cfg(feature = "wasm-runtime")]
@@ -250,6 +251,7 @@ mod test {
                 vp_wasm_cache: self.vp_wasm_cache.clone(),
                 tx_wasm_cache: self.tx_wasm_cache.clone(),
                 storage_read_past_height_limit: None,
+                event_index: true,
             };
             // TODO: this is a hack to propagate errors to the caller, we should
             // really permit error types other than [`std::io::Error`]
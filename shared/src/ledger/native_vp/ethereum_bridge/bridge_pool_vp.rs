@@ -10,6 +10,16 @@
 //! correctly. This means that the appropriate data is
 //! added to the pool and gas fees are submitted appropriately
 //! and that tokens to be transferred are escrowed.
+//!
+//! The sender of a transfer may also cancel it, as long as it
+//! is not part of a signed Bridge pool root that can still be
+//! relayed, in which case the gas fees and the escrowed tokens
+//! must be refunded. Alternatively, the sender may set the
+//! epoch from which the protocol refunds the transfer.
+//!
+//! Lastly, the payer of the gas fees of a transfer may raise
+//! them, escrowing the additional fees apart from the transfer,
+//! such that its hash is left unchanged.
 
 use std::borrow::Cow;
 use std::collections::BTreeSet;
@@ -19,21 +29,30 @@ use borsh::BorshDeserialize;
 use eyre::eyre;
 use namada_core::hints;
 use namada_core::ledger::eth_bridge::storage::bridge_pool::{
-    get_pending_key, is_bridge_pool_key, BRIDGE_POOL_ADDRESS,
+    get_expiry_key, get_fee_bump_key, get_key_from_hash, get_pending_key,
+    is_bridge_pool_key, is_expiry_key, is_fee_bump_key,
+    is_pending_transfer_key, BRIDGE_POOL_ADDRESS,
 };
 use namada_core::ledger::eth_bridge::storage::whitelist;
 use namada_core::ledger::eth_bridge::ADDRESS as BRIDGE_ADDRESS;
 use namada_ethereum_bridge::storage::parameters::read_native_erc20_address;
+use namada_ethereum_bridge::storage::vp::bridge_pool::{
+    is_transfer_signed, read_transfer_fee_bump, transfer_inserted_height,
+};
 use namada_ethereum_bridge::storage::wrapped_erc20s;
 
 use crate::ledger::native_vp::{Ctx, NativeVp, StorageReader};
 use crate::ledger::storage::traits::StorageHasher;
 use crate::ledger::storage::{DBIter, DB};
+use crate::ledger::storage_api::StorageRead;
 use crate::proto::Tx;
 use crate::types::address::{Address, InternalAddress};
-use crate::types::eth_bridge_pool::{PendingTransfer, TransferToEthereumKind};
+use crate::types::eth_bridge_pool::{
+    BumpPendingTransferFee, CancelPendingTransfer, PendingTransfer,
+    TransferToEthereumKind,
+};
 use crate::types::ethereum_events::EthAddress;
-use crate::types::storage::Key;
+use crate::types::storage::{Epoch, Key};
 use crate::types::token::{balance_key, Amount};
 use crate::vm::WasmCacheAccess;
 
@@ -401,6 +420,319 @@ where
             },
         })
     }
+
+    /// Check if the tx removes a transfer from the pool or changes the
+    /// expiry of a transfer, rather than adding a new transfer.
+    fn is_cancellation(
+        &self,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool, Error> {
+        for key in keys_changed.iter().filter(|k| is_bridge_pool_key(k)) {
+            if is_expiry_key(key).is_some() {
+                return Ok(true);
+            }
+            if is_pending_transfer_key(key)
+                && !self.ctx.post().has_key(key).map_err(|e| eyre!(e))?
+            {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    /// Validate the cancellation of a transfer by its sender, or the
+    /// change of the epoch from which the protocol refunds it.
+    fn validate_cancellation(
+        &self,
+        tx_data: &[u8],
+        keys_changed: &BTreeSet<Key>,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool, Error> {
+        let cancel: CancelPendingTransfer =
+            BorshDeserialize::try_from_slice(tx_data)
+                .map_err(|e| Error(e.into()))?;
+        let pending_key = get_key_from_hash(&cancel.transfer_hash);
+        let expiry_key = get_expiry_key(&cancel.transfer_hash);
+        let fee_bump_key = get_fee_bump_key(&cancel.transfer_hash);
+        let Some(transfer) =
+            (&self.ctx).read_pre_value::<PendingTransfer>(&pending_key)?
+        else {
+            tracing::debug!(
+                hash = %cancel.transfer_hash,
+                "Rejecting the cancellation of a transfer which is not in the \
+                 Ethereum bridge pool."
+            );
+            return Ok(false);
+        };
+        if !verifiers.contains(&transfer.transfer.sender) {
+            tracing::debug!(
+                ?transfer,
+                "Rejecting the cancellation of a transfer which was not \
+                 authorized by its sender."
+            );
+            return Ok(false);
+        }
+        for key in keys_changed.iter().filter(|k| is_bridge_pool_key(k)) {
+            if *key != pending_key && *key != expiry_key && *key != fee_bump_key
+            {
+                tracing::debug!(
+                    "Rejecting transaction as it is attempting to change an \
+                     incorrect key in the Ethereum bridge pool: {}.",
+                    key,
+                );
+                return Ok(false);
+            }
+        }
+        // the bumped fees of the transfer are refunded with its gas fees
+        let fee_bump =
+            read_transfer_fee_bump(&self.ctx.pre(), &cancel.transfer_hash)
+                .map_err(|e| eyre!(e))?;
+        let mut escrowed = transfer.clone();
+        escrowed.gas_fee.amount = escrowed
+            .gas_fee
+            .amount
+            .checked_add(fee_bump)
+            .ok_or_else(|| eyre!("Overflowed adding the fee bump"))?;
+        let wnam_address = read_native_erc20_address(&self.ctx.pre())?;
+        let escrow_checks =
+            self.determine_escrow_checks(&wnam_address, &escrowed)?;
+        match cancel.expiry {
+            Some(expiry) => self.check_transfer_expiry(
+                &transfer,
+                expiry,
+                escrow_checks,
+                keys_changed,
+            ),
+            None => self.check_transfer_refund(
+                &transfer,
+                escrow_checks,
+                keys_changed,
+            ),
+        }
+    }
+
+    /// Check that the transfer stays in the pool with its escrow, until
+    /// the protocol refunds it from the given epoch.
+    fn check_transfer_expiry(
+        &self,
+        transfer: &PendingTransfer,
+        expiry: Epoch,
+        escrow_checks: EscrowCheck<'_>,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool, Error> {
+        let pending_key = get_pending_key(transfer);
+        let fee_bump_key = get_fee_bump_key(&transfer.keccak256());
+        if keys_changed.contains(&pending_key)
+            || keys_changed.contains(&fee_bump_key)
+            || !escrow_checks.check_escrow_keys_unchanged(keys_changed)
+        {
+            tracing::debug!(
+                ?transfer,
+                "Rejecting transaction as the transfer and its escrow must \
+                 stay in the Ethereum bridge pool until it expires."
+            );
+            return Ok(false);
+        }
+        let current_epoch =
+            self.ctx.pre().get_block_epoch().map_err(|e| eyre!(e))?;
+        if expiry <= current_epoch {
+            tracing::debug!(
+                ?transfer,
+                %expiry,
+                %current_epoch,
+                "The expiry of a transfer must be a future epoch."
+            );
+            return Ok(false);
+        }
+        let post_expiry: Option<Epoch> = (&self.ctx)
+            .read_post_value(&get_expiry_key(&transfer.keccak256()))?;
+        if post_expiry != Some(expiry) {
+            tracing::debug!(
+                ?transfer,
+                %expiry,
+                "An incorrect expiry was set for the transfer."
+            );
+            return Ok(false);
+        }
+        tracing::info!(
+            %expiry,
+            "The Ethereum bridge pool VP accepted the expiry of the transfer \
+             {:?}.",
+            transfer
+        );
+        Ok(true)
+    }
+
+    /// Check that the transfer was removed from the pool and that the gas
+    /// fees and the escrowed tokens were refunded, while the transfer is
+    /// not part of a signed Bridge pool root that can still be relayed.
+    fn check_transfer_refund(
+        &self,
+        transfer: &PendingTransfer,
+        escrow_checks: EscrowCheck<'_>,
+        keys_changed: &BTreeSet<Key>,
+    ) -> Result<bool, Error> {
+        let pending_key = get_pending_key(transfer);
+        let hash = transfer.keccak256();
+        for key in [
+            &pending_key,
+            &get_expiry_key(&hash),
+            &get_fee_bump_key(&hash),
+        ] {
+            if self.ctx.post().has_key(key).map_err(|e| eyre!(e))? {
+                tracing::debug!(
+                    ?transfer,
+                    "Rejecting transaction as the cancelled transfer wasn't \
+                     removed from the Ethereum bridge pool."
+                );
+                return Ok(false);
+            }
+        }
+        let inserted_height =
+            transfer_inserted_height(self.ctx.storage, &pending_key);
+        if is_transfer_signed(&self.ctx.pre(), inserted_height)
+            .map_err(|e| eyre!(e))?
+        {
+            tracing::debug!(
+                ?transfer,
+                "Rejecting the cancellation of a transfer which is part of a \
+                 signed Bridge pool root that can still be relayed."
+            );
+            return Ok(false);
+        }
+        let refund_checks = escrow_checks.reversed();
+        if !refund_checks.validate(keys_changed) {
+            tracing::debug!(
+                ?transfer,
+                "Missing storage modifications in the Bridge pool"
+            );
+            return Ok(false);
+        }
+        if !self.check_escrowed_toks(refund_checks.gas_check)? {
+            tracing::debug!(
+                ?transfer,
+                "The gas fees of the cancelled transfer were not properly \
+                 refunded."
+            );
+            return Ok(false);
+        }
+        self.check_escrowed_toks(refund_checks.token_check)
+            .map(|ok| {
+                if ok {
+                    tracing::info!(
+                        "The Ethereum bridge pool VP accepted the \
+                         cancellation of the transfer {:?}.",
+                        transfer
+                    );
+                } else {
+                    tracing::debug!(
+                        ?transfer,
+                        "The assets of the cancelled transfer were not \
+                         properly refunded."
+                    );
+                }
+                ok
+            })
+    }
+
+    /// Check if the tx raises the gas fees of a transfer in the pool.
+    #[inline]
+    fn is_fee_bump(&self, keys_changed: &BTreeSet<Key>) -> bool {
+        keys_changed
+            .iter()
+            .any(|key| is_fee_bump_key(key).is_some())
+    }
+
+    /// Validate the bump of the gas fees of a transfer by their payer,
+    /// which must escrow the additional fees in the same token.
+    fn validate_fee_bump(
+        &self,
+        tx_data: &[u8],
+        keys_changed: &BTreeSet<Key>,
+        verifiers: &BTreeSet<Address>,
+    ) -> Result<bool, Error> {
+        let bump: BumpPendingTransferFee =
+            BorshDeserialize::try_from_slice(tx_data)
+                .map_err(|e| Error(e.into()))?;
+        let pending_key = get_key_from_hash(&bump.transfer_hash);
+        let fee_bump_key = get_fee_bump_key(&bump.transfer_hash);
+        let Some(transfer) =
+            (&self.ctx).read_pre_value::<PendingTransfer>(&pending_key)?
+        else {
+            tracing::debug!(
+                hash = %bump.transfer_hash,
+                "Rejecting the fee bump of a transfer which is not in the \
+                 Ethereum bridge pool."
+            );
+            return Ok(false);
+        };
+        if bump.amount.is_zero() {
+            tracing::debug!(?transfer, "Rejecting a nil fee bump.");
+            return Ok(false);
+        }
+        if !verifiers.contains(&transfer.gas_fee.payer) {
+            tracing::debug!(
+                ?transfer,
+                "Rejecting the fee bump of a transfer which was not \
+                 authorized by its gas fee payer."
+            );
+            return Ok(false);
+        }
+        for key in keys_changed.iter().filter(|k| is_bridge_pool_key(k)) {
+            if *key != fee_bump_key {
+                tracing::debug!(
+                    "Rejecting transaction as it is attempting to change an \
+                     incorrect key in the Ethereum bridge pool: {}.\n \
+                     Expected key: {}",
+                    key,
+                    fee_bump_key
+                );
+                return Ok(false);
+            }
+        }
+        let pre_fee_bump =
+            read_transfer_fee_bump(&self.ctx.pre(), &bump.transfer_hash)
+                .map_err(|e| eyre!(e))?;
+        let post_fee_bump: Option<Amount> =
+            (&self.ctx).read_post_value(&fee_bump_key)?;
+        if post_fee_bump != pre_fee_bump.checked_add(bump.amount) {
+            tracing::debug!(
+                ?transfer,
+                ?post_fee_bump,
+                "An incorrect fee bump was written for the transfer."
+            );
+            return Ok(false);
+        }
+        let gas_check = EscrowDelta {
+            token: Cow::Borrowed(&transfer.gas_fee.token),
+            payer_account: &transfer.gas_fee.payer,
+            escrow_account: &BRIDGE_POOL_ADDRESS,
+            expected_debit: bump.amount,
+            expected_credit: bump.amount,
+            transferred_amount: &bump.amount,
+            _kind: PhantomData,
+        };
+        if !gas_check.validate(keys_changed) {
+            tracing::debug!(
+                ?transfer,
+                "Missing storage modifications in the Bridge pool"
+            );
+            return Ok(false);
+        }
+        let wnam_address = read_native_erc20_address(&self.ctx.pre())?;
+        self.check_gas_escrow(&wnam_address, &transfer, gas_check)
+            .map(|ok| {
+                if ok {
+                    tracing::info!(
+                        amount = %bump.amount.to_string_native(),
+                        "The Ethereum bridge pool VP accepted the fee bump of \
+                         the transfer {:?}.",
+                        transfer
+                    );
+                }
+                ok
+            })
+    }
 }
 
 /// Helper struct for handling the different escrow
@@ -416,6 +748,18 @@ struct EscrowDelta<'a, KIND> {
 }
 
 impl<KIND> EscrowDelta<'_, KIND> {
+    /// Get the [`EscrowDelta`] refunding the escrowed amount, i.e. with
+    /// the debited and credited accounts swapped.
+    fn reversed(self) -> Self {
+        Self {
+            payer_account: self.escrow_account,
+            escrow_account: self.payer_account,
+            expected_debit: self.expected_credit,
+            expected_credit: self.expected_debit,
+            ..self
+        }
+    }
+
     /// Validate an [`EscrowDelta`].
     ///
     /// # Conditions for validation
@@ -494,6 +838,25 @@ impl EscrowCheck<'_> {
         self.gas_check.validate(changed_keys)
             && self.token_check.validate(changed_keys)
     }
+
+    /// Get the checks of the refund of the escrowed gas fees and tokens.
+    #[inline]
+    fn reversed(self) -> Self {
+        Self {
+            gas_check: self.gas_check.reversed(),
+            token_check: self.token_check.reversed(),
+        }
+    }
+
+    /// Check that none of the escrow keys in `changed_keys` were modified.
+    #[inline]
+    fn check_escrow_keys_unchanged(
+        &self,
+        changed_keys: &BTreeSet<Key>,
+    ) -> bool {
+        self.gas_check.check_escrow_keys_unchanged(changed_keys)
+            && self.token_check.check_escrow_keys_unchanged(changed_keys)
+    }
 }
 
 /// Perform a gas check.
@@ -530,16 +893,26 @@ where
         &self,
         tx: &Tx,
         keys_changed: &BTreeSet<Key>,
-        _verifiers: &BTreeSet<Address>,
+        verifiers: &BTreeSet<Address>,
     ) -> Result<bool, Error> {
         tracing::debug!(
             keys_changed_len = keys_changed.len(),
-            verifiers_len = _verifiers.len(),
+            verifiers_len = verifiers.len(),
             "Ethereum Bridge Pool VP triggered",
         );
         let Some(tx_data) = tx.data() else {
             return Err(eyre!("No transaction data found").into());
         };
+        if self.is_cancellation(keys_changed)? {
+            return self.validate_cancellation(
+                &tx_data,
+                keys_changed,
+                verifiers,
+            );
+        }
+        if self.is_fee_bump(keys_changed) {
+            return self.validate_fee_bump(&tx_data, keys_changed, verifiers);
+        }
         let transfer: PendingTransfer =
             BorshDeserialize::try_from_slice(&tx_data[..])
                 .map_err(|e| Error(e.into()))?;
@@ -649,6 +1022,7 @@ mod test_bridge_pool_vp {
     use namada_ethereum_bridge::storage::parameters::{
         Contracts, EthereumBridgeParams, UpgradeableContract,
     };
+    use namada_ethereum_bridge::storage::proof::BridgePoolRootProof;
 
     use super::*;
     use crate::ledger::gas::VpGasMeter;
@@ -660,8 +1034,10 @@ mod test_bridge_pool_vp {
     use crate::types::address::{nam, wnam, InternalAddress};
     use crate::types::chain::ChainId;
     use crate::types::eth_bridge_pool::{GasFee, TransferToEthereum};
+    use crate::types::ethereum_events::Uint;
     use crate::types::hash::Hash;
-    use crate::types::storage::TxIndex;
+    use crate::types::keccak::KeccakHash;
+    use crate::types::storage::{BlockHeight, TxIndex};
     use crate::types::transaction::TxType;
     use crate::vm::wasm::VpCache;
     use crate::vm::WasmCacheRwAccess;
@@ -1852,6 +2228,374 @@ mod test_bridge_pool_vp {
         );
     }
 
+    /// Setup storage with a transfer in the pool added at the given
+    /// height, whose fees were bumped by the given amount, and a Bridge
+    /// pool root signed at the given height with the current nonce.
+    fn setup_pending_transfer(
+        inserted_height: BlockHeight,
+        signed_height: BlockHeight,
+        fee_bump: Amount,
+    ) -> (WlStorage<MockDB, Sha256Hasher>, PendingTransfer) {
+        let mut wl_storage = setup_storage();
+        let transfer = PendingTransfer {
+            transfer: TransferToEthereum {
+                kind: TransferToEthereumKind::Erc20,
+                asset: ASSET,
+                sender: bertha_address(),
+                recipient: EthAddress([1; 20]),
+                amount: TOKENS.into(),
+            },
+            gas_fee: GasFee {
+                token: nam(),
+                amount: GAS_FEE.into(),
+                payer: bertha_address(),
+            },
+        };
+        let root =
+            BridgePoolRootProof::new((KeccakHash([0; 32]), Uint::from(0)));
+        wl_storage
+            .write_log
+            .write(
+                &get_signed_root_key(),
+                (root, signed_height).serialize_to_vec(),
+            )
+            .expect("Test failed");
+        wl_storage
+            .write_log
+            .write(&get_pending_key(&transfer), transfer.serialize_to_vec())
+            .expect("Test failed");
+        if !fee_bump.is_zero() {
+            wl_storage
+                .write_log
+                .write(
+                    &get_fee_bump_key(&transfer.keccak256()),
+                    fee_bump.serialize_to_vec(),
+                )
+                .expect("Test failed");
+        }
+        wl_storage.storage.block.height = inserted_height;
+        wl_storage.commit_block().expect("Test failed");
+        (wl_storage, transfer)
+    }
+
+    /// Helper function that tests the cancellation of a transfer in the
+    /// pool, refunding the given amounts
+    fn assert_cancel_transfer(
+        signed_height: BlockHeight,
+        gas_refund: Amount,
+        token_refund: Amount,
+        verifiers: BTreeSet<Address>,
+        expect: Expect,
+    ) {
+        assert_cancel_bumped_transfer(
+            Amount::zero(),
+            signed_height,
+            gas_refund,
+            token_refund,
+            verifiers,
+            expect,
+        )
+    }
+
+    /// Helper function that tests the cancellation of a transfer in the
+    /// pool whose fees were bumped, refunding the given amounts
+    fn assert_cancel_bumped_transfer(
+        fee_bump: Amount,
+        signed_height: BlockHeight,
+        gas_refund: Amount,
+        token_refund: Amount,
+        verifiers: BTreeSet<Address>,
+        expect: Expect,
+    ) {
+        // setup
+        let (mut wl_storage, transfer) =
+            setup_pending_transfer(BlockHeight(1), signed_height, fee_bump);
+        let tx = Tx::from_type(TxType::Raw);
+
+        // remove the transfer from the pool
+        let pending_key = get_pending_key(&transfer);
+        wl_storage
+            .write_log
+            .delete(&pending_key)
+            .expect("Test failed");
+        let mut keys_changed = BTreeSet::from([pending_key]);
+        if !fee_bump.is_zero() {
+            let fee_bump_key = get_fee_bump_key(&transfer.keccak256());
+            wl_storage
+                .write_log
+                .delete(&fee_bump_key)
+                .expect("Test failed");
+            keys_changed.insert(fee_bump_key);
+        }
+
+        // refund Bertha
+        let mut new_keys_changed = update_balances(
+            &mut wl_storage.write_log,
+            Balance {
+                asset: ASSET,
+                kind: TransferToEthereumKind::Erc20,
+                owner: bertha_address(),
+                gas: BERTHA_WEALTH.into(),
+                token: BERTHA_TOKENS.into(),
+            },
+            SignedAmount::Positive(gas_refund),
+            SignedAmount::Positive(token_refund),
+        );
+        keys_changed.append(&mut new_keys_changed);
+
+        // release the escrow of the bridge pool
+        let mut new_keys_changed = update_balances(
+            &mut wl_storage.write_log,
+            Balance {
+                asset: ASSET,
+                kind: TransferToEthereumKind::Erc20,
+                owner: BRIDGE_POOL_ADDRESS,
+                gas: ESCROWED_AMOUNT.into(),
+                token: ESCROWED_TOKENS.into(),
+            },
+            SignedAmount::Negative(gas_refund),
+            SignedAmount::Negative(token_refund),
+        );
+        keys_changed.append(&mut new_keys_changed);
+
+        // create the data to be given to the vp
+        let vp = BridgePoolVp {
+            ctx: setup_ctx(
+                &tx,
+                &wl_storage.storage,
+                &wl_storage.write_log,
+                &keys_changed,
+                &verifiers,
+            ),
+        };
+
+        let mut tx = Tx::new(wl_storage.storage.chain_id.clone(), None);
+        tx.add_data(CancelPendingTransfer {
+            transfer_hash: transfer.keccak256(),
+            expiry: None,
+        });
+
+        let res = vp.validate_tx(&tx, &keys_changed, &verifiers);
+        match expect {
+            Expect::True => assert!(res.expect("Test failed")),
+            Expect::False => assert!(!res.expect("Test failed")),
+            Expect::Error => assert!(res.is_err()),
+        }
+    }
+
+    /// Test that the sender can cancel a transfer which is not in the
+    /// signed Bridge pool root, with a refund of the gas fees and tokens.
+    #[test]
+    fn test_cancel_transfer() {
+        assert_cancel_transfer(
+            BlockHeight(0),
+            GAS_FEE.into(),
+            TOKENS.into(),
+            BTreeSet::from([bertha_address()]),
+            Expect::True,
+        );
+    }
+
+    /// Test that only the sender of a transfer can cancel it.
+    #[test]
+    fn test_cancel_transfer_requires_sender() {
+        assert_cancel_transfer(
+            BlockHeight(0),
+            GAS_FEE.into(),
+            TOKENS.into(),
+            BTreeSet::from([established_address_1()]),
+            Expect::False,
+        );
+    }
+
+    /// Test that a transfer in a signed Bridge pool root which can still
+    /// be relayed cannot be cancelled.
+    #[test]
+    fn test_cancel_signed_transfer_rejected() {
+        assert_cancel_transfer(
+            BlockHeight(1),
+            GAS_FEE.into(),
+            TOKENS.into(),
+            BTreeSet::from([bertha_address()]),
+            Expect::False,
+        );
+    }
+
+    /// Test that the refund of a cancelled transfer must match its
+    /// amounts.
+    #[test]
+    fn test_cancel_transfer_incorrect_refund() {
+        assert_cancel_transfer(
+            BlockHeight(0),
+            GAS_FEE.into(),
+            (TOKENS + 1).into(),
+            BTreeSet::from([bertha_address()]),
+            Expect::False,
+        );
+        assert_cancel_transfer(
+            BlockHeight(0),
+            0.into(),
+            TOKENS.into(),
+            BTreeSet::from([bertha_address()]),
+            Expect::False,
+        );
+    }
+
+    /// Test that the bumped fees of a cancelled transfer are refunded
+    /// with its gas fees.
+    #[test]
+    fn test_cancel_bumped_transfer() {
+        assert_cancel_bumped_transfer(
+            10.into(),
+            BlockHeight(0),
+            (GAS_FEE + 10).into(),
+            TOKENS.into(),
+            BTreeSet::from([bertha_address()]),
+            Expect::True,
+        );
+        assert_cancel_bumped_transfer(
+            10.into(),
+            BlockHeight(0),
+            GAS_FEE.into(),
+            TOKENS.into(),
+            BTreeSet::from([bertha_address()]),
+            Expect::False,
+        );
+    }
+
+    /// Test that the payer of the gas fees of a transfer can bump them,
+    /// escrowing the additional fees, without changing the transfer.
+    #[test]
+    fn test_fee_bump() {
+        let assert_fee_bump =
+            |pre_bump: u64, post_bump: u64, debit: u64, verifier: Address| {
+                let (mut wl_storage, transfer) = setup_pending_transfer(
+                    BlockHeight(1),
+                    BlockHeight(1),
+                    pre_bump.into(),
+                );
+                let tx = Tx::from_type(TxType::Raw);
+                let fee_bump_key = get_fee_bump_key(&transfer.keccak256());
+                wl_storage
+                    .write_log
+                    .write(
+                        &fee_bump_key,
+                        Amount::from(post_bump).serialize_to_vec(),
+                    )
+                    .expect("Test failed");
+                let mut keys_changed = BTreeSet::from([fee_bump_key]);
+                // escrow the additional fees
+                let mut new_keys_changed = update_balances(
+                    &mut wl_storage.write_log,
+                    Balance {
+                        asset: ASSET,
+                        kind: TransferToEthereumKind::Erc20,
+                        owner: bertha_address(),
+                        gas: BERTHA_WEALTH.into(),
+                        token: BERTHA_TOKENS.into(),
+                    },
+                    SignedAmount::Negative(debit.into()),
+                    SignedAmount::Positive(0.into()),
+                );
+                keys_changed.append(&mut new_keys_changed);
+                let mut new_keys_changed = update_balances(
+                    &mut wl_storage.write_log,
+                    Balance {
+                        asset: ASSET,
+                        kind: TransferToEthereumKind::Erc20,
+                        owner: BRIDGE_POOL_ADDRESS,
+                        gas: ESCROWED_AMOUNT.into(),
+                        token: ESCROWED_TOKENS.into(),
+                    },
+                    SignedAmount::Positive(debit.into()),
+                    SignedAmount::Positive(0.into()),
+                );
+                keys_changed.append(&mut new_keys_changed);
+                let verifiers = BTreeSet::from([verifier]);
+                let vp = BridgePoolVp {
+                    ctx: setup_ctx(
+                        &tx,
+                        &wl_storage.storage,
+                        &wl_storage.write_log,
+                        &keys_changed,
+                        &verifiers,
+                    ),
+                };
+                let mut tx = Tx::new(wl_storage.storage.chain_id.clone(), None);
+                tx.add_data(BumpPendingTransferFee {
+                    transfer_hash: transfer.keccak256(),
+                    amount: (post_bump - pre_bump).into(),
+                });
+                vp.validate_tx(&tx, &keys_changed, &verifiers)
+                    .expect("Test failed")
+            };
+        assert!(assert_fee_bump(0, 10, 10, bertha_address()));
+        assert!(assert_fee_bump(10, 15, 5, bertha_address()));
+        // the additional fees must be escrowed
+        assert!(!assert_fee_bump(0, 10, 5, bertha_address()));
+        // only the payer of the gas fees can bump them
+        assert!(!assert_fee_bump(0, 10, 10, established_address_1()));
+        // a fee bump can't be nil
+        assert!(!assert_fee_bump(10, 10, 0, bertha_address()));
+    }
+
+    /// Test that the sender can set a future expiry to a transfer, which
+    /// must stay in the pool with its escrow.
+    #[test]
+    fn test_transfer_expiry() {
+        let assert_expiry = |expiry: Epoch, refund: bool, expect: bool| {
+            let (mut wl_storage, transfer) = setup_pending_transfer(
+                BlockHeight(1),
+                BlockHeight(1),
+                Amount::zero(),
+            );
+            let tx = Tx::from_type(TxType::Raw);
+            let expiry_key = get_expiry_key(&transfer.keccak256());
+            wl_storage
+                .write_log
+                .write(&expiry_key, expiry.serialize_to_vec())
+                .expect("Test failed");
+            let mut keys_changed = BTreeSet::from([expiry_key]);
+            if refund {
+                let mut new_keys_changed = update_balances(
+                    &mut wl_storage.write_log,
+                    Balance {
+                        asset: ASSET,
+                        kind: TransferToEthereumKind::Erc20,
+                        owner: BRIDGE_POOL_ADDRESS,
+                        gas: ESCROWED_AMOUNT.into(),
+                        token: ESCROWED_TOKENS.into(),
+                    },
+                    SignedAmount::Negative(GAS_FEE.into()),
+                    SignedAmount::Negative(TOKENS.into()),
+                );
+                keys_changed.append(&mut new_keys_changed);
+            }
+            let verifiers = BTreeSet::from([bertha_address()]);
+            let vp = BridgePoolVp {
+                ctx: setup_ctx(
+                    &tx,
+                    &wl_storage.storage,
+                    &wl_storage.write_log,
+                    &keys_changed,
+                    &verifiers,
+                ),
+            };
+            let mut tx = Tx::new(wl_storage.storage.chain_id.clone(), None);
+            tx.add_data(CancelPendingTransfer {
+                transfer_hash: transfer.keccak256(),
+                expiry: Some(expiry),
+            });
+            let res = vp.validate_tx(&tx, &keys_changed, &verifiers);
+            assert_eq!(res.expect("Test failed"), expect);
+        };
+        assert_expiry(Epoch(1), false, true);
+        // the expiry must be in the future
+        assert_expiry(Epoch(0), false, false);
+        // the escrow is kept until the transfer expires
+        assert_expiry(Epoch(1), true, false);
+    }
+
     /// Test that the Bridge pool native VP validates transfers that
     /// do not contain gas fees and no associated changed keys.
     #[test]
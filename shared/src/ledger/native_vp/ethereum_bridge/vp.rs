@@ -2,12 +2,16 @@
 use std::collections::{BTreeSet, HashSet};
 
 use eyre::{eyre, Result};
+use namada_core::ledger::eth_bridge::storage::bridge_pool::is_pending_transfer_key;
 use namada_core::ledger::eth_bridge::storage::{self, escrow_key};
 use namada_core::ledger::storage::traits::StorageHasher;
+use namada_core::ledger::storage_api::StorageRead;
 use namada_core::ledger::{eth_bridge, storage as ledger_storage};
 use namada_core::types::address::Address;
+use namada_core::types::eth_bridge_pool::PendingTransfer;
 use namada_core::types::storage::Key;
 use namada_core::types::token::{balance_key, is_balance_key, Amount};
+use namada_ethereum_bridge::storage::parameters::read_native_erc20_address;
 
 use crate::ledger::native_vp::{Ctx, NativeVp, StorageReader};
 use crate::proto::Tx;
@@ -37,9 +41,11 @@ where
 {
     /// If the Ethereum bridge's escrow key was written to, we check
     /// that the NAM balance increased and that the Bridge pool VP has
-    /// been triggered.
+    /// been triggered, or that the NAM balance decreased by the amount
+    /// refunded for wrapped NAM transfers removed from the Bridge pool.
     fn check_escrow(
         &self,
+        keys_changed: &BTreeSet<Key>,
         verifiers: &BTreeSet<Address>,
     ) -> Result<bool, Error> {
         let escrow_key =
@@ -71,6 +77,12 @@ where
             // NB: normally, we only escrow NAM under the Ethereum bridge
             // address in the context of a Bridge pool transfer
             Ok(verifiers.contains(&storage::bridge_pool::BRIDGE_POOL_ADDRESS))
+        } else if escrow_pre > escrow_post
+            && verifiers.contains(&storage::bridge_pool::BRIDGE_POOL_ADDRESS)
+        {
+            // NB: the cancellation of the transfers is validated by
+            // the Bridge pool VP
+            self.check_escrow_refund(keys_changed, escrow_pre - escrow_post)
         } else {
             tracing::info!(
                 "A normal tx cannot decrease the amount of Nam escrowed in \
@@ -79,6 +91,46 @@ where
             Ok(false)
         }
     }
+
+    /// Check that the decrease of the escrowed NAM matches the amounts of
+    /// the wrapped NAM transfers removed from the Bridge pool, which are
+    /// refunded to their senders.
+    fn check_escrow_refund(
+        &self,
+        keys_changed: &BTreeSet<Key>,
+        refunded: Amount,
+    ) -> Result<bool, Error> {
+        let wnam_address = read_native_erc20_address(&self.ctx.pre())?;
+        let mut cancelled = Amount::zero();
+        for key in keys_changed
+            .iter()
+            .filter(|key| is_pending_transfer_key(key))
+        {
+            if self.ctx.post().has_key(key).map_err(|e| eyre!(e))? {
+                continue;
+            }
+            let Some(transfer) =
+                (&self.ctx).read_pre_value::<PendingTransfer>(key)?
+            else {
+                continue;
+            };
+            if transfer.transfer.asset == wnam_address {
+                cancelled = cancelled
+                    .checked_add(transfer.transfer.amount)
+                    .ok_or_else(|| eyre!("The refunded amount overflowed"))?;
+            }
+        }
+        if cancelled != refunded {
+            tracing::debug!(
+                cancelled = %cancelled.to_string_native(),
+                refunded = %refunded.to_string_native(),
+                "The Nam released from the Ethereum bridge escrow doesn't \
+                 match the cancelled wrapped NAM transfers"
+            );
+            return Ok(false);
+        }
+        Ok(true)
+    }
 }
 
 impl<'a, DB, H, CA> NativeVp for EthBridge<'a, DB, H, CA>
@@ -93,7 +145,9 @@ where
     /// account.
     ///
     /// We only permit increasing the escrowed balance of NAM under the Ethereum
-    /// bridge address, when writing to storage from wasm transactions.
+    /// bridge address, when writing to storage from wasm transactions, or
+    /// decreasing it to refund wrapped NAM transfers cancelled from the Bridge
+    /// pool.
     ///
     /// Some other changes to the storage subspace of this account are expected
     /// to happen natively i.e. bypassing this validity predicate. For example,
@@ -116,7 +170,7 @@ where
             return Ok(false);
         }
 
-        self.check_escrow(verifiers)
+        self.check_escrow(keys_changed, verifiers)
     }
 }
 
@@ -166,7 +220,9 @@ mod tests {
 
     use borsh_ext::BorshSerializeExt;
     use namada_core::ledger::eth_bridge;
-    use namada_core::ledger::eth_bridge::storage::bridge_pool::BRIDGE_POOL_ADDRESS;
+    use namada_core::ledger::eth_bridge::storage::bridge_pool::{
+        get_pending_key, BRIDGE_POOL_ADDRESS,
+    };
     use namada_core::ledger::eth_bridge::storage::wrapped_erc20s;
     use namada_core::ledger::gas::TxGasMeter;
     use namada_core::ledger::storage_api::StorageWrite;
@@ -184,6 +240,9 @@ mod tests {
     use crate::proto::Tx;
     use crate::types::address::testing::established_address_1;
     use crate::types::address::{nam, wnam};
+    use crate::types::eth_bridge_pool::{
+        GasFee, TransferToEthereum, TransferToEthereumKind,
+    };
     use crate::types::ethereum_events;
     use crate::types::ethereum_events::EthAddress;
     use crate::types::storage::TxIndex;
@@ -448,6 +507,89 @@ mod tests {
         assert!(!res.expect("Test failed"));
     }
 
+    /// Test that the escrowed Nam can only be released to refund the
+    /// wrapped NAM transfers removed from the Bridge pool.
+    #[test]
+    fn test_escrowed_nam_refund() {
+        let mut wl_storage = setup_storage();
+        let owner =
+            Address::decode(ARBITRARY_OWNER_A_ADDRESS).expect("Test failed");
+        let transfer = PendingTransfer {
+            transfer: TransferToEthereum {
+                kind: TransferToEthereumKind::Erc20,
+                asset: wnam(),
+                recipient: EthAddress([1; 20]),
+                sender: owner.clone(),
+                amount: Amount::from(ESCROW_AMOUNT),
+            },
+            gas_fee: GasFee {
+                token: nam(),
+                amount: Amount::zero(),
+                payer: owner.clone(),
+            },
+        };
+        let pending_key = get_pending_key(&transfer);
+        let escrow_key = balance_key(&nam(), &eth_bridge::ADDRESS);
+        wl_storage
+            .write_bytes(&pending_key, transfer.serialize_to_vec())
+            .expect("Test failed");
+        wl_storage
+            .write(&escrow_key, Amount::from(ESCROW_AMOUNT))
+            .expect("Test failed");
+        wl_storage.commit_block().expect("Test failed");
+
+        // refund the escrowed Nam to the sender
+        let account_key = balance_key(&nam(), &owner);
+        wl_storage
+            .write_log
+            .write(
+                &account_key,
+                Amount::from(ARBITRARY_OWNER_A_INITIAL_BALANCE + ESCROW_AMOUNT)
+                    .serialize_to_vec(),
+            )
+            .expect("Test failed");
+        wl_storage
+            .write_log
+            .write(&escrow_key, Amount::zero().serialize_to_vec())
+            .expect("Test failed");
+        let verifiers = BTreeSet::from([BRIDGE_POOL_ADDRESS]);
+        let tx = Tx::from_type(TxType::Raw);
+
+        // the transfer was not removed from the pool
+        let keys_changed =
+            BTreeSet::from([account_key.clone(), escrow_key.clone()]);
+        let vp = EthBridge {
+            ctx: setup_ctx(
+                &tx,
+                &wl_storage.storage,
+                &wl_storage.write_log,
+                &keys_changed,
+                &verifiers,
+            ),
+        };
+        let res = vp.validate_tx(&tx, &keys_changed, &verifiers);
+        assert!(!res.expect("Test failed"));
+
+        // the transfer was cancelled
+        wl_storage
+            .write_log
+            .delete(&pending_key)
+            .expect("Test failed");
+        let keys_changed =
+            BTreeSet::from([account_key, escrow_key, pending_key]);
+        let vp = EthBridge {
+            ctx: setup_ctx(
+                &tx,
+                &wl_storage.storage,
+                &wl_storage.write_log,
+                &keys_changed,
+                &verifiers,
+            ),
+        };
+        let res = vp.validate_tx(&tx, &keys_changed, &verifiers);
+        assert!(res.expect("Test failed"));
+    }
+
     /// Test that the VP checks that the bridge pool vp will
     /// be triggered if escrowing occurs.
     #[test]
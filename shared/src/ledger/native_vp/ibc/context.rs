@@ -200,6 +200,11 @@ where
         amount: DenominatedAmount,
     ) -> Result<()> {
         let amount = amount.to_amount(token, self)?;
+        // The same as the transfer of the transaction, e.g. for the token
+        // unescrowed to the IBC account to be forwarded
+        if amount == Amount::default() || src == dest {
+            return Ok(());
+        }
         let src_key = token::balance_key(token, src);
         let dest_key = token::balance_key(token, dest);
         let src_bal: Option<Amount> = self.ctx.read(&src_key)?;
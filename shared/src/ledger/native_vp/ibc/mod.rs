@@ -11,6 +11,7 @@ use context::{PseudoExecutionContext, VpValidationContext};
 use namada_core::ledger::gas::{
     IBC_ACTION_EXECUTE_GAS, IBC_ACTION_VALIDATE_GAS,
 };
+use namada_core::ledger::ibc::rate_limit::current_usage;
 use namada_core::ledger::ibc::{
     Error as ActionError, IbcActions, TransferModule, ValidationParams,
 };
@@ -23,7 +24,9 @@ use namada_proof_of_stake::storage::read_pos_params;
 use thiserror::Error;
 
 use crate::ibc::core::host::types::identifiers::ChainId as IbcChainId;
-use crate::ledger::ibc::storage::{calc_hash, is_ibc_denom_key, is_ibc_key};
+use crate::ledger::ibc::storage::{
+    calc_hash, is_ibc_denom_key, is_ibc_key, is_rate_limit_flows_key,
+};
 use crate::ledger::native_vp::{self, Ctx, NativeVp, VpEnv};
 use crate::ledger::parameters::read_epoch_duration_parameter;
 use crate::vm::WasmCacheAccess;
@@ -43,6 +46,8 @@ pub enum Error {
     StateChange(String),
     #[error("IBC event error: {0}")]
     IbcEvent(String),
+    #[error("IBC rate limit error: {0}")]
+    RateLimit(String),
 }
 
 /// IBC functions result
@@ -85,6 +90,9 @@ where
         // Validate the denom store if a denom key has been changed
         self.validate_denom(keys_changed)?;
 
+        // Validate the flows of the rate limited tokens
+        self.validate_rate_limits(keys_changed)?;
+
         Ok(true)
     }
 }
@@ -210,6 +218,34 @@ where
         }
         Ok(())
     }
+
+    fn validate_rate_limits(
+        &self,
+        keys_changed: &BTreeSet<Key>,
+    ) -> VpResult<()> {
+        for key in keys_changed {
+            if let Some((channel_id, token)) = is_rate_limit_flows_key(key) {
+                let (limit, total) =
+                    current_usage(&self.ctx.post(), &channel_id, &token)
+                        .map_err(Error::NativeVpError)?
+                        .ok_or_else(|| {
+                            Error::RateLimit(format!(
+                                "The flows of {} on {} were recorded without \
+                                 a rate limit",
+                                token, channel_id
+                            ))
+                        })?;
+                if let Some(flow) = limit.exceeded_flow(&total) {
+                    return Err(Error::RateLimit(format!(
+                        "The {} of {} on {} exceeds its rate limit: Limit {}, \
+                         Total {:?}",
+                        flow, token, channel_id, limit, total
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 fn match_value(
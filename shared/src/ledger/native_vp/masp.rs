@@ -383,7 +383,8 @@ where
         _verifiers: &BTreeSet<Address>,
     ) -> Result<bool> {
         let epoch = self.ctx.get_block_epoch()?;
-        let (transfer, shielded_tx) = self.ctx.get_shielded_action(tx_data)?;
+        let (transfer, shielded_tx, is_native_shielding) =
+            self.ctx.get_shielded_action(tx_data)?;
         let transfer_amount = transfer
             .amount
             .to_amount(&transfer.token, &self.ctx.pre())?;
@@ -591,6 +592,12 @@ where
             _ => {}
         }
 
+        // The notes shielded natively have been built by the protocol without
+        // proofs, their commitments being checked above
+        if is_native_shielding {
+            return Ok(true);
+        }
+
         // Verify the proofs and charge the gas for the expensive execution
         self.ctx
             .charge_gas(MASP_VERIFY_SHIELDED_TX_GAS)
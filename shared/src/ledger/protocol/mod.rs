@@ -7,7 +7,7 @@ use masp_primitives::transaction::Transaction;
 use namada_core::ledger::gas::TxGasMeter;
 use namada_core::ledger::storage::wl_storage::WriteLogAndStorage;
 use namada_core::ledger::storage_api::StorageRead;
-use namada_core::proto::Section;
+use namada_core::proto::{BatchMode, Section};
 use namada_core::types::hash::Hash;
 use namada_core::types::storage::Key;
 use namada_core::types::token::Amount;
@@ -159,16 +159,19 @@ where
 {
     match tx.header().tx_type {
         TxType::Raw => Err(Error::TxTypeError),
-        TxType::Decrypted(DecryptedTx::Decrypted) => apply_wasm_tx(
-            tx,
-            &tx_index,
-            ShellParams {
+        TxType::Decrypted(DecryptedTx::Decrypted) => {
+            let shell_params = ShellParams {
                 tx_gas_meter,
                 wl_storage,
                 vp_wasm_cache,
                 tx_wasm_cache,
-            },
-        ),
+            };
+            if tx.batch().is_some() {
+                apply_wasm_batch(tx, &tx_index, shell_params)
+            } else {
+                apply_wasm_tx(tx, &tx_index, shell_params)
+            }
+        }
         TxType::Protocol(protocol_tx) => {
             apply_protocol_tx(protocol_tx.tx, tx.data(), wl_storage)
         }
@@ -195,6 +198,7 @@ where
                 initialized_accounts: vec![],
                 ibc_events: BTreeSet::default(),
                 eth_bridge_events: BTreeSet::default(),
+                batch_results: vec![],
             })
         }
         TxType::Decrypted(DecryptedTx::Undecryptable) => {
@@ -612,9 +616,103 @@ where
         initialized_accounts,
         ibc_events,
         eth_bridge_events: BTreeSet::default(),
+        batch_results: vec![],
     })
 }
 
+/// Apply the inner transactions of a batch in order via the wasm environment.
+/// The changes of every accepted inner transaction are precommitted so that
+/// the following ones can observe them.
+///
+/// In atomic mode, the batch is rejected as soon as one of the inner
+/// transactions is rejected or fails, and none of the changes must be
+/// committed. In independent mode, the changes of the rejected or failed inner
+/// transactions are dropped and the batch itself is accepted. In both modes,
+/// running out of gas, missing sections and replay attempts abort the whole
+/// batch.
+pub fn apply_wasm_batch<'a, D, H, CA, WLS>(
+    tx: Tx,
+    tx_index: &TxIndex,
+    shell_params: ShellParams<'a, CA, WLS>,
+) -> Result<TxResult>
+where
+    CA: 'static + WasmCacheAccess + Sync,
+    D: 'static + DB + for<'iter> DBIter<'iter> + Sync,
+    H: 'static + StorageHasher + Sync,
+    WLS: WriteLogAndStorage<D = D, H = H>,
+{
+    let ShellParams {
+        tx_gas_meter,
+        wl_storage,
+        vp_wasm_cache,
+        tx_wasm_cache,
+    } = shell_params;
+    let atomic = match tx.batch() {
+        Some(batch) => batch.mode == BatchMode::Atomic,
+        None => return Err(Error::TxTypeError),
+    };
+
+    let mut batch_result = TxResult::default();
+    for inner_tx in tx.batch_txs() {
+        let result = apply_wasm_tx(
+            inner_tx,
+            tx_index,
+            ShellParams {
+                tx_gas_meter: &mut *tx_gas_meter,
+                wl_storage: &mut *wl_storage,
+                vp_wasm_cache: &mut *vp_wasm_cache,
+                tx_wasm_cache: &mut *tx_wasm_cache,
+            },
+        );
+        match result {
+            Ok(result) if result.is_accepted() => {
+                wl_storage.write_log_mut().precommit_tx();
+                batch_result
+                    .changed_keys
+                    .extend(result.changed_keys.iter().cloned());
+                batch_result
+                    .vps_result
+                    .accepted_vps
+                    .extend(result.vps_result.accepted_vps.iter().cloned());
+                batch_result
+                    .initialized_accounts
+                    .extend(result.initialized_accounts.iter().cloned());
+                batch_result
+                    .ibc_events
+                    .extend(result.ibc_events.iter().cloned());
+                batch_result
+                    .eth_bridge_events
+                    .extend(result.eth_bridge_events.iter().cloned());
+                batch_result.batch_results.push(Ok(result));
+            }
+            Ok(result) => {
+                if atomic {
+                    batch_result.vps_result = result.vps_result.clone();
+                    batch_result.batch_results.push(Ok(result));
+                    break;
+                }
+                wl_storage.write_log_mut().drop_tx_keep_precommit();
+                batch_result.batch_results.push(Ok(result));
+            }
+            Err(
+                err @ (Error::GasError(_)
+                | Error::MissingSection(_)
+                | Error::ReplayAttempt(_)),
+            ) => return Err(err),
+            Err(err) => {
+                if atomic {
+                    return Err(err);
+                }
+                wl_storage.write_log_mut().drop_tx_keep_precommit();
+                batch_result.batch_results.push(Err(err.to_string()));
+            }
+        }
+    }
+    batch_result.gas_used = tx_gas_meter.get_tx_consumed_gas();
+
+    Ok(batch_result)
+}
+
 /// Apply a derived transaction to storage based on some protocol transaction.
 /// The logic here must be completely deterministic and will be executed by all
 /// full nodes every time a protocol transaction is included in a block. Storage
@@ -1103,8 +1201,9 @@ mod tests {
 
     use borsh::BorshDeserialize;
     use eyre::Result;
+    use namada_core::ledger::storage::testing::TestWlStorage;
     use namada_core::ledger::storage_api::StorageRead;
-    use namada_core::proto::{SignableEthMessage, Signed};
+    use namada_core::proto::{Code, Data, SignableEthMessage, Signed};
     use namada_core::types::ethereum_events::testing::DAI_ERC20_ETH_ADDRESS;
     use namada_core::types::ethereum_events::{
         EthereumEvent, TransferToNamada,
@@ -1123,6 +1222,8 @@ mod tests {
     use namada_ethereum_bridge::storage::proof::EthereumProof;
     use namada_ethereum_bridge::storage::{vote_tallies, vp};
     use namada_ethereum_bridge::test_utils;
+    use namada_test_utils::tx_data::TxWriteData;
+    use namada_test_utils::TestWasms;
 
     use super::*;
 
@@ -1256,4 +1357,120 @@ mod tests {
 
         Ok(())
     }
+
+    /// Build an inner transaction of a batch running the given test wasm,
+    /// which writes the given value at the given key if it is `tx_write`
+    fn batch_inner_tx(wasm: TestWasms, key: &str, value: u8) -> Tx {
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.set_code(Code::new(wasm.read_bytes(), None));
+        tx.set_data(Data::new(
+            TxWriteData {
+                key: Key::parse(key).unwrap(),
+                value: vec![value],
+            }
+            .serialize_to_vec(),
+        ));
+        tx
+    }
+
+    /// Apply a batch of the given inner transactions in the given mode,
+    /// along with the gas meter that it used
+    fn apply_batch(
+        wl_storage: &mut TestWlStorage,
+        txs: Vec<Tx>,
+        mode: BatchMode,
+    ) -> (super::Result<TxResult>, TxGasMeter) {
+        // store the test wasms
+        for wasm in [TestWasms::TxWriteStorageKey, TestWasms::TxFail] {
+            let code = wasm.read_bytes();
+            let code_hash = Hash::sha256(&code);
+            let code_len = (code.len() as u64).serialize_to_vec();
+            wl_storage
+                .write_log
+                .write(&Key::wasm_code(&code_hash), code)
+                .unwrap();
+            wl_storage
+                .write_log
+                .write(&Key::wasm_code_len(&code_hash), code_len)
+                .unwrap();
+        }
+        wl_storage.commit_tx();
+
+        let batch = Tx::new_batch(txs, mode).unwrap();
+        let mut gas_meter =
+            TxGasMeter::new_from_sub_limit(10_000_000_000.into());
+        let (mut vp_cache, _) =
+            wasm::compilation_cache::common::testing::cache();
+        let (mut tx_cache, _) =
+            wasm::compilation_cache::common::testing::cache();
+        let result = apply_wasm_batch(
+            batch,
+            &TxIndex::default(),
+            ShellParams::new(
+                &mut gas_meter,
+                wl_storage,
+                &mut vp_cache,
+                &mut tx_cache,
+            ),
+        );
+        (result, gas_meter)
+    }
+
+    #[test]
+    /// Tests that a failing inner transaction of an atomic batch fails the
+    /// whole batch, so that the changes of the preceding inner transactions
+    /// are dropped along with it.
+    fn test_apply_wasm_batch_atomic_rollback() {
+        let mut wl_storage = TestWlStorage::default();
+        let txs = vec![
+            batch_inner_tx(TestWasms::TxWriteStorageKey, "first", 1),
+            batch_inner_tx(TestWasms::TxFail, "second", 2),
+            batch_inner_tx(TestWasms::TxWriteStorageKey, "third", 3),
+        ];
+        let (result, _) = apply_batch(&mut wl_storage, txs, BatchMode::Atomic);
+        assert!(matches!(result, Err(Error::TxRunnerError(_))));
+        // the first write was precommitted and the last tx never ran
+        let first = Key::parse("first").unwrap();
+        let third = Key::parse("third").unwrap();
+        assert!(wl_storage.has_key(&first).unwrap());
+        assert!(!wl_storage.has_key(&third).unwrap());
+
+        // dropping the failed batch drops the precommitted changes as well
+        wl_storage.drop_tx();
+        assert!(!wl_storage.has_key(&first).unwrap());
+    }
+
+    #[test]
+    /// Tests that a failing inner transaction of an independent batch only
+    /// drops its own changes, and that the gas of every inner transaction
+    /// is accounted for by the batch.
+    fn test_apply_wasm_batch_independent() {
+        let mut wl_storage = TestWlStorage::default();
+        let txs = vec![
+            batch_inner_tx(TestWasms::TxWriteStorageKey, "first", 1),
+            batch_inner_tx(TestWasms::TxFail, "second", 2),
+            batch_inner_tx(TestWasms::TxWriteStorageKey, "third", 3),
+        ];
+        let (result, gas_meter) =
+            apply_batch(&mut wl_storage, txs, BatchMode::Independent);
+        let result = result.unwrap();
+        assert!(result.is_accepted());
+        let [Ok(first), Err(_), Ok(third)] = &result.batch_results[..] else {
+            panic!("unexpected inner results {:?}", result.batch_results);
+        };
+        let keys = ["first", "third"].map(|key| Key::parse(key).unwrap());
+        assert_eq!(result.changed_keys, BTreeSet::from(keys.clone()));
+
+        // the inner txs share the gas meter of the batch
+        assert!(first.gas_used > 0.into());
+        assert!(third.gas_used > first.gas_used);
+        assert_eq!(result.gas_used, third.gas_used);
+        assert_eq!(result.gas_used, gas_meter.get_tx_consumed_gas());
+
+        wl_storage.commit_tx();
+        for (key, value) in keys.iter().zip([1_u8, 3]) {
+            assert_eq!(wl_storage.read_bytes(key).unwrap(), Some(vec![value]));
+        }
+        assert!(!wl_storage.has_key(&Key::parse("second").unwrap()).unwrap());
+    }
 }
@@ -2537,6 +2537,7 @@ where
         if amount != token::Amount::default() && src != dest {
             let src_key = token::balance_key(token, src);
             let dest_key = token::balance_key(token, dest);
+            storage_api::account::record_spending(self, src, token, amount)?;
             let src_bal = self.read::<token::Amount>(&src_key)?;
             let mut src_bal = src_bal.unwrap_or_else(|| {
                 self.log_string(format!("src {} has no balance", src_key));
@@ -2603,6 +2604,7 @@ where
 
         let amount = amount.to_amount(token, self)?;
         let target_key = token::balance_key(token, target);
+        storage_api::account::record_spending(self, target, token, amount)?;
         let mut target_bal =
             self.read::<token::Amount>(&target_key)?.unwrap_or_default();
         target_bal.spend(&amount);
@@ -624,6 +624,31 @@ pub fn dummy_channel_counterparty() -> ChanCounterparty {
     ChanCounterparty::new(port_id, Some(channel_id))
 }
 
+/// Set the ID of the counterparty channel of an opened channel, e.g. to the
+/// ID of the channel on this chain
+pub fn set_counterparty_channel_id(
+    port_id: &PortId,
+    channel_id: &ChannelId,
+    counterparty_channel_id: ChannelId,
+) {
+    let key = channel_key(port_id, channel_id);
+    tx_host_env::with(|env| {
+        let (bytes, _gas) =
+            env.wl_storage.storage.read(&key).expect("read error");
+        let mut channel =
+            ChannelEnd::decode_vec(&bytes.expect("the channel should exist"))
+                .expect("invalid channel");
+        channel.remote = ChanCounterparty::new(
+            port_id.clone(),
+            Some(counterparty_channel_id),
+        );
+        env.wl_storage
+            .storage
+            .write(&key, channel.encode_vec())
+            .expect("write error");
+    });
+}
+
 pub fn unorder_channel(channel: &mut ChannelEnd) {
     channel.ordering = Order::Unordered;
 }
@@ -713,6 +738,73 @@ pub fn received_packet(
     }
 }
 
+/// Set the instruction to forward the token of the packet to the receiver
+/// through the given channel
+pub fn set_forward_memo(
+    packet: &mut Packet,
+    receiver: &str,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) {
+    let mut data: PacketData = serde_json::from_slice(&packet.data).unwrap();
+    let memo = serde_json::json!({
+        "forward": {
+            "receiver": receiver,
+            "port": port_id.to_string(),
+            "channel": channel_id.to_string(),
+        }
+    });
+    data.memo = memo.to_string().into();
+    packet.data = serde_json::to_vec(&data).unwrap();
+}
+
+/// The packet sent by the IBC account to forward the token of a packet made
+/// by [`received_packet`]
+pub fn forwarded_packet(
+    port_id: PortId,
+    channel_id: ChannelId,
+    sequence: Sequence,
+    denom: String,
+    receiver: &str,
+    timeout_timestamp: Timestamp,
+) -> Packet {
+    let amount = DenominatedAmount::native(Amount::native_whole(100));
+    let counterparty = dummy_channel_counterparty();
+    let data = PacketData {
+        token: PrefixedCoin {
+            denom: denom.parse().expect("invalid denom"),
+            amount: amount.into(),
+        },
+        sender: ADDRESS.to_string().into(),
+        receiver: receiver.to_string().into(),
+        memo: String::new().into(),
+    };
+    Packet {
+        seq_on_a: sequence,
+        port_id_on_a: port_id,
+        chan_id_on_a: channel_id,
+        port_id_on_b: counterparty.port_id().clone(),
+        chan_id_on_b: counterparty.channel_id().unwrap().clone(),
+        data: serde_json::to_vec(&data).unwrap(),
+        timeout_height_on_b: TimeoutHeight::Never,
+        timeout_timestamp_on_b: timeout_timestamp,
+    }
+}
+
+/// Update the consensus state of the counterparty chain at the proof height
+/// to the given time, e.g. to prove the timeout of a packet
+pub fn prepare_consensus_state(
+    client_id: &ClientId,
+    timestamp: Timestamp,
+) -> HashMap<storage::Key, Vec<u8>> {
+    let height = dummy_proof_height();
+    let consensus_state =
+        MockConsensusState::new(MockHeader { height, timestamp });
+    let key = consensus_state_key(client_id, height);
+    let bytes = Protobuf::<Any>::encode_vec(consensus_state);
+    HashMap::from([(key, bytes)])
+}
+
 pub fn msg_timeout(packet: Packet, next_sequence_recv: Sequence) -> MsgTimeout {
     MsgTimeout {
         packet,
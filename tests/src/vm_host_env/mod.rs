@@ -20,16 +20,28 @@ mod tests {
 
     use std::collections::BTreeSet;
     use std::panic;
+    use std::time::Duration;
 
     use borsh_ext::BorshSerializeExt;
     use itertools::Itertools;
+    use namada::core::ledger::governance::storage::proposal::{
+        PGFIbcTarget, PGFTarget, StoragePgfFunding,
+    };
+    use namada::core::ledger::parameters::storage::get_epochs_per_year_key;
+    use namada::core::ledger::pgf::inflation as pgf_inflation;
+    use namada::core::ledger::pgf::parameters::PgfParameters;
+    use namada::core::ledger::pgf::storage::keys as pgf_storage;
+    use namada::ibc::core::channel::types::commitment::compute_ack_commitment;
     use namada::ibc::primitives::Msg;
-    use namada::ledger::ibc::storage as ibc_storage;
+    use namada::ledger::ibc::forward::{self, DEFAULT_FORWARD_TIMEOUT};
+    use namada::ledger::ibc::{storage as ibc_storage, IbcCommonContext};
     use namada::ledger::native_vp::ibc::{
         get_dummy_header as tm_dummy_header, Error as IbcError,
     };
+    use namada::ledger::storage_api;
     use namada::ledger::tx_env::TxEnv;
     use namada::proto::Tx;
+    use namada::types::dec::Dec;
     use namada::types::hash::Hash;
     use namada::types::key::*;
     use namada::types::storage::{self, BlockHash, BlockHeight, Key, KeySeg};
@@ -38,6 +50,7 @@ mod tests {
     use namada::types::{address, key};
     use namada_core::ledger::ibc::context::transfer_mod::testing::DummyTransferModule;
     use namada_core::ledger::ibc::Error as IbcActionError;
+    use namada_core::types::account::SpendingPolicy;
     use namada_test_utils::TestWasms;
     use namada_tx_prelude::address::InternalAddress;
     use namada_tx_prelude::chain::ChainId;
@@ -1291,6 +1304,89 @@ mod tests {
         assert_eq!(minted, Some(Amount::from_u64(0)));
     }
 
+    #[test]
+    fn test_ibc_burn_token_with_spending_policy() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+
+        // Set the initial state before starting transactions
+        let (token, sender) = ibc::init_storage();
+        let (client_id, _client_state, mut writes) = ibc::prepare_client();
+        let (conn_id, conn_writes) = ibc::prepare_opened_connection(&client_id);
+        writes.extend(conn_writes);
+        let (port_id, channel_id, channel_writes) =
+            ibc::prepare_opened_channel(&conn_id, false);
+        writes.extend(channel_writes);
+        // the origin-specific token
+        let denom = format!("{}/{}/{}", port_id, channel_id, token);
+        let ibc_token = ibc_storage::ibc_token(&denom);
+        let balance_key = token::balance_key(&ibc_token, &sender);
+        let init_bal = Amount::from_u64(100);
+        writes.insert(balance_key, init_bal.serialize_to_vec());
+        let minted_key = token::minted_balance_key(&ibc_token);
+        writes.insert(minted_key, init_bal.serialize_to_vec());
+        let minter_key = token::minter_key(&ibc_token);
+        writes.insert(
+            minter_key,
+            Address::Internal(InternalAddress::Ibc).serialize_to_vec(),
+        );
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.wl_storage
+                    .storage
+                    .write(&key, &val)
+                    .expect("write error");
+            });
+        });
+        // The sender limits the debits of the IBC token
+        let policy = SpendingPolicy {
+            epoch_limits: [(ibc_token.clone(), init_bal)].into(),
+            ..Default::default()
+        };
+        tx_host_env::with(|env| {
+            storage_api::account::set_spending_policy(
+                &mut env.wl_storage,
+                &sender,
+                Some(policy),
+            )
+            .expect("setting the spending policy failed");
+            env.wl_storage.commit_tx();
+        });
+
+        // Start a transaction to send a packet
+        // Set this chain is the sink zone
+        let msg = ibc::msg_transfer(port_id, channel_id, denom, &sender);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+        // send the token and a packet with the data
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("sending a token failed");
+
+        // Check that the burned amount was spent by the sender
+        let spent = tx_host_env::with(|env| {
+            storage_api::account::spent_in_epoch(
+                &env.wl_storage,
+                &sender,
+                &ibc_token,
+            )
+            .expect("read error")
+        });
+        assert_eq!(spent, init_bal);
+    }
+
     #[test]
     fn test_ibc_receive_token() {
         // The environment must be initialized first
@@ -1833,4 +1929,579 @@ mod tests {
         let result = ibc::validate_multitoken_vp_from_tx(&env, &tx, &escrow);
         assert!(result.expect("token validation failed unexpectedly"));
     }
+
+    /// Prepare an opened channel to receive the packets whose token is
+    /// forwarded. Returns the token, an account, the client ID and the channel.
+    fn prepare_ibc_forward(
+    ) -> (Address, Address, ibc::ClientId, ibc::PortId, ibc::ChannelId) {
+        let (token, account) = ibc::init_storage();
+        let (client_id, _client_state, mut writes) = ibc::prepare_client();
+        let (conn_id, conn_writes) = ibc::prepare_opened_connection(&client_id);
+        writes.extend(conn_writes);
+        let (port_id, channel_id, channel_writes) =
+            ibc::prepare_opened_channel(&conn_id, false);
+        writes.extend(channel_writes);
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.wl_storage
+                    .storage
+                    .write(&key, &val)
+                    .expect("write error");
+            });
+        });
+        (token, account, client_id, port_id, channel_id)
+    }
+
+    /// Commit the transaction and the block, and begin the next block
+    fn commit_and_begin_next_block() {
+        let mut env = tx_host_env::take();
+        env.commit_tx_and_block();
+        env.wl_storage
+            .storage
+            .begin_block(BlockHash::default(), BlockHeight(2))
+            .unwrap();
+        env.wl_storage
+            .storage
+            .set_header(tm_dummy_header())
+            .unwrap();
+        tx_host_env::set(env);
+    }
+
+    /// Test that the token of a packet with a forward instruction is sent to
+    /// the next hop, and that the packet is acknowledged with the
+    /// acknowledgement of the forwarded packet.
+    #[test]
+    fn test_ibc_forward_token() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+
+        // Set the initial state before starting transactions
+        let (token, receiver, _client_id, port_id, channel_id) =
+            prepare_ibc_forward();
+
+        // packet to be forwarded over the same channel
+        let mut packet = ibc::received_packet(
+            port_id.clone(),
+            channel_id.clone(),
+            ibc::Sequence::from(1),
+            token.to_string(),
+            &receiver,
+        );
+        ibc::set_forward_memo(
+            &mut packet,
+            "cosmos1receiver",
+            &port_id,
+            &channel_id,
+        );
+        let timeout = (tx::ctx().host_timestamp().unwrap()
+            + DEFAULT_FORWARD_TIMEOUT)
+            .unwrap();
+
+        // Start a transaction to receive the packet
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs.clone(), pks_map.clone(), None)
+            .sign_wrapper(keypair.clone());
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("receiving the token failed");
+
+        // Check
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(&env, &tx);
+        assert!(result.expect("validation failed unexpectedly"));
+        // The acknowledgement waits for the forwarded packet
+        let ack_key =
+            ibc::ack_key(&port_id, &channel_id, ibc::Sequence::from(1));
+        let commitment_key =
+            ibc::commitment_key(&port_id, &channel_id, ibc::Sequence::from(1));
+        assert!(!env.wl_storage.has_key(&ack_key).expect("read error"));
+        assert!(env.wl_storage.has_key(&commitment_key).expect("read error"));
+        // The received token has been sent back to its origin
+        let denom = format!("{}/{}/{}", port_id, channel_id, token);
+        let minted_key =
+            token::minted_balance_key(&ibc::ibc_token(denom.clone()));
+        let minted: Option<Amount> =
+            env.wl_storage.read(&minted_key).expect("read error");
+        assert_eq!(minted.unwrap_or_default(), Amount::zero());
+        tx_host_env::set(env);
+
+        // Commit
+        commit_and_begin_next_block();
+
+        // Start a transaction to acknowledge the forwarded packet
+        let packet = ibc::forwarded_packet(
+            port_id.clone(),
+            channel_id.clone(),
+            ibc::Sequence::from(1),
+            denom,
+            "cosmos1receiver",
+            timeout,
+        );
+        let msg = ibc::msg_packet_ack(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("acknowledging the forwarded packet failed");
+
+        // Check
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(&env, &tx);
+        assert!(result.expect("validation failed unexpectedly"));
+        // The received packet has been acknowledged
+        let ack = env.wl_storage.read_bytes(&ack_key).expect("read error");
+        let expected = compute_ack_commitment(&forward::success_ack());
+        assert_eq!(ack, Some(expected.into_vec()));
+        let pending_key = ibc_storage::forward_ack_key(
+            &port_id,
+            &channel_id,
+            ibc::Sequence::from(1),
+        );
+        assert!(!env.wl_storage.has_key(&pending_key).expect("read error"));
+    }
+
+    /// Test that the packet whose forwarded packet timed out is acknowledged
+    /// with an error to refund the sender, reverting the receipt of the token.
+    #[test]
+    fn test_ibc_forward_token_timeout() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+
+        // Set the initial state before starting transactions
+        let (token, receiver, client_id, port_id, channel_id) =
+            prepare_ibc_forward();
+
+        // packet to be forwarded over the same channel
+        let mut packet = ibc::received_packet(
+            port_id.clone(),
+            channel_id.clone(),
+            ibc::Sequence::from(1),
+            token.to_string(),
+            &receiver,
+        );
+        ibc::set_forward_memo(
+            &mut packet,
+            "cosmos1receiver",
+            &port_id,
+            &channel_id,
+        );
+        let timeout = (tx::ctx().host_timestamp().unwrap()
+            + DEFAULT_FORWARD_TIMEOUT)
+            .unwrap();
+
+        // Start a transaction to receive the packet
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("receiving the token failed");
+
+        // Commit
+        commit_and_begin_next_block();
+        // The counterparty chain has passed the timeout
+        let writes = ibc::prepare_consensus_state(
+            &client_id,
+            (timeout + Duration::from_secs(1)).unwrap(),
+        );
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.wl_storage
+                    .storage
+                    .write(&key, &val)
+                    .expect("write error");
+            });
+        });
+
+        // Start a transaction to notify the timeout of the forwarded packet
+        let denom = format!("{}/{}/{}", port_id, channel_id, token);
+        let packet = ibc::forwarded_packet(
+            port_id.clone(),
+            channel_id.clone(),
+            ibc::Sequence::from(1),
+            denom.clone(),
+            "cosmos1receiver",
+            timeout,
+        );
+        let msg = ibc::msg_timeout(packet, ibc::Sequence::from(1));
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("timeout failed");
+
+        // Check
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(&env, &tx);
+        assert!(result.expect("validation failed unexpectedly"));
+        // The received packet has been acknowledged with an error
+        let ack_key =
+            ibc::ack_key(&port_id, &channel_id, ibc::Sequence::from(1));
+        let ack = env.wl_storage.read_bytes(&ack_key).expect("read error");
+        let expected = compute_ack_commitment(&forward::error_ack(
+            "The forwarded packet timed out".to_string(),
+        ));
+        assert_eq!(ack, Some(expected.into_vec()));
+        // The refunded token has been burned
+        let ibc_token = ibc::ibc_token(denom);
+        let minted_key = token::minted_balance_key(&ibc_token);
+        let minted: Option<Amount> =
+            env.wl_storage.read(&minted_key).expect("read error");
+        assert_eq!(minted.unwrap_or_default(), Amount::zero());
+        let balance_key = token::balance_key(
+            &ibc_token,
+            &Address::Internal(InternalAddress::Ibc),
+        );
+        let balance: Option<Amount> =
+            env.wl_storage.read(&balance_key).expect("read error");
+        assert_eq!(balance.unwrap_or_default(), Amount::zero());
+        let forwarded_key = ibc_storage::forwarded_packet_key(
+            &port_id,
+            &channel_id,
+            ibc::Sequence::from(1),
+        );
+        assert!(!env.wl_storage.has_key(&forwarded_key).expect("read error"));
+    }
+
+    /// Test that a packet whose token can't be forwarded is acknowledged with
+    /// an error in the same transaction instead of failing it.
+    #[test]
+    fn test_ibc_forward_token_failed() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        let keypair = key::testing::keypair_1();
+        let keypairs = vec![keypair.clone()];
+        let pks_map = AccountPublicKeysMap::from_iter([
+            key::testing::keypair_1().ref_to(),
+        ]);
+
+        // Set the initial state before starting transactions
+        let (token, receiver, _client_id, port_id, channel_id) =
+            prepare_ibc_forward();
+
+        // packet to be forwarded over a channel which doesn't exist
+        let mut packet = ibc::received_packet(
+            port_id.clone(),
+            channel_id.clone(),
+            ibc::Sequence::from(1),
+            token.to_string(),
+            &receiver,
+        );
+        ibc::set_forward_memo(
+            &mut packet,
+            "cosmos1receiver",
+            &port_id,
+            &ibc::ChannelId::new(5),
+        );
+
+        // Start a transaction to receive the packet
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        let mut tx = Tx::new(ChainId::default(), None);
+        tx.add_code(vec![], None)
+            .add_serialized_data(tx_data.clone())
+            .sign_raw(keypairs, pks_map, None)
+            .sign_wrapper(keypair);
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("receiving the token failed");
+
+        // Check
+        let env = tx_host_env::take();
+        let result = ibc::validate_ibc_vp_from_tx(&env, &tx);
+        assert!(result.expect("validation failed unexpectedly"));
+        // The received packet has been acknowledged with an error
+        let ack_key =
+            ibc::ack_key(&port_id, &channel_id, ibc::Sequence::from(1));
+        let ack = env
+            .wl_storage
+            .read_bytes(&ack_key)
+            .expect("read error")
+            .expect("the acknowledgement should be written");
+        let success = compute_ack_commitment(&forward::success_ack());
+        assert_ne!(ack, success.into_vec());
+        let pending_key = ibc_storage::forward_ack_key(
+            &port_id,
+            &channel_id,
+            ibc::Sequence::from(1),
+        );
+        assert!(!env.wl_storage.has_key(&pending_key).expect("read error"));
+        // Nothing has been sent and the received token has been burned
+        let commitment_key =
+            ibc::commitment_key(&port_id, &channel_id, ibc::Sequence::from(1));
+        assert!(!env.wl_storage.has_key(&commitment_key).expect("read error"));
+        let denom = format!("{}/{}/{}", port_id, channel_id, token);
+        let minted_key = token::minted_balance_key(&ibc::ibc_token(denom));
+        let minted: Option<Amount> =
+            env.wl_storage.read(&minted_key).expect("read error");
+        assert_eq!(minted.unwrap_or_default(), Amount::zero());
+    }
+
+    /// Test that the token of a packet which can't be forwarded is kept in
+    /// escrow when this chain is the source of the token, since the vouchers
+    /// on the other chains are still outstanding.
+    #[test]
+    fn test_ibc_forward_native_token_failed() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        // Set the initial state before starting transactions
+        let (token, receiver, _client_id, port_id, channel_id) =
+            prepare_ibc_forward();
+        // escrow in advance
+        let escrow_key = token::balance_key(
+            &token,
+            &Address::Internal(InternalAddress::Ibc),
+        );
+        let escrowed = Amount::from_uint(100, ibc::ANY_DENOMINATION).unwrap();
+        tx_host_env::with(|env| {
+            env.wl_storage
+                .storage
+                .write(&escrow_key, escrowed.serialize_to_vec())
+                .expect("write error");
+        });
+
+        // packet of a token whose source is this chain, to be forwarded over
+        // a channel which doesn't exist
+        let counterparty = ibc::dummy_channel_counterparty();
+        let denom = format!(
+            "{}/{}/{}",
+            counterparty.port_id(),
+            counterparty.channel_id().unwrap(),
+            token
+        );
+        let mut packet = ibc::received_packet(
+            port_id.clone(),
+            channel_id.clone(),
+            ibc::Sequence::from(1),
+            denom,
+            &receiver,
+        );
+        ibc::set_forward_memo(
+            &mut packet,
+            "cosmos1receiver",
+            &port_id,
+            &ibc::ChannelId::new(5),
+        );
+
+        // Start a transaction to receive the packet
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("receiving the token failed");
+
+        // Check
+        let env = tx_host_env::take();
+        // The received packet has been acknowledged with an error
+        let ack_key =
+            ibc::ack_key(&port_id, &channel_id, ibc::Sequence::from(1));
+        let ack = env
+            .wl_storage
+            .read_bytes(&ack_key)
+            .expect("read error")
+            .expect("the acknowledgement should be written");
+        let success = compute_ack_commitment(&forward::success_ack());
+        assert_ne!(ack, success.into_vec());
+        // The token is still escrowed
+        let escrow: Option<Amount> =
+            env.wl_storage.read(&escrow_key).expect("read error");
+        assert_eq!(escrow, Some(escrowed));
+    }
+
+    /// Test that the voucher minted for a packet which can't be forwarded is
+    /// burned when the channel IDs on both chains are the same.
+    #[test]
+    fn test_ibc_forward_token_failed_same_channel_ids() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        // Set the initial state before starting transactions
+        let (token, receiver, _client_id, port_id, channel_id) =
+            prepare_ibc_forward();
+        ibc::set_counterparty_channel_id(
+            &port_id,
+            &channel_id,
+            channel_id.clone(),
+        );
+
+        // packet sent over the channel with the same ID, to be forwarded over
+        // a channel which doesn't exist
+        let mut packet = ibc::received_packet(
+            port_id.clone(),
+            channel_id.clone(),
+            ibc::Sequence::from(1),
+            token.to_string(),
+            &receiver,
+        );
+        packet.chan_id_on_a = channel_id.clone();
+        ibc::set_forward_memo(
+            &mut packet,
+            "cosmos1receiver",
+            &port_id,
+            &ibc::ChannelId::new(5),
+        );
+
+        // Start a transaction to receive the packet
+        let msg = ibc::msg_packet_recv(packet);
+        let mut tx_data = vec![];
+        msg.to_any().encode(&mut tx_data).expect("encoding failed");
+        tx_host_env::ibc::ibc_actions(tx::ctx())
+            .execute(&tx_data)
+            .expect("receiving the token failed");
+
+        // Check
+        let env = tx_host_env::take();
+        // The received packet has been acknowledged with an error
+        let ack_key =
+            ibc::ack_key(&port_id, &channel_id, ibc::Sequence::from(1));
+        let ack = env
+            .wl_storage
+            .read_bytes(&ack_key)
+            .expect("read error")
+            .expect("the acknowledgement should be written");
+        let success = compute_ack_commitment(&forward::success_ack());
+        assert_ne!(ack, success.into_vec());
+        // The minted voucher has been burned
+        let denom = format!("{}/{}/{}", port_id, channel_id, token);
+        let ibc_token = ibc::ibc_token(denom);
+        let minted_key = token::minted_balance_key(&ibc_token);
+        let minted: Option<Amount> =
+            env.wl_storage.read(&minted_key).expect("read error");
+        assert_eq!(minted.unwrap_or_default(), Amount::zero());
+        let balance_key = token::balance_key(
+            &ibc_token,
+            &Address::Internal(InternalAddress::Ibc),
+        );
+        let balance: Option<Amount> =
+            env.wl_storage.read(&balance_key).expect("read error");
+        assert_eq!(balance.unwrap_or_default(), Amount::zero());
+    }
+
+    /// Test that the PGF inflation pays the fundings of IBC targets with an
+    /// ICS-20 transfer from the PGF account, escrowing the tokens.
+    #[test]
+    fn test_pgf_inflation_ibc_target() {
+        // The environment must be initialized first
+        tx_host_env::init();
+
+        // Set the initial state before starting transactions
+        let (_token, sender) = ibc::init_storage();
+        let (client_id, _client_state, mut writes) = ibc::prepare_client();
+        let (conn_id, conn_writes) = ibc::prepare_opened_connection(&client_id);
+        writes.extend(conn_writes);
+        let (port_id, channel_id, channel_writes) =
+            ibc::prepare_opened_channel(&conn_id, false);
+        writes.extend(channel_writes);
+        writes.into_iter().for_each(|(key, val)| {
+            tx_host_env::with(|env| {
+                env.wl_storage
+                    .storage
+                    .write(&key, &val)
+                    .expect("write error");
+            });
+        });
+
+        let nam = address::nam();
+        let pgf = Address::Internal(InternalAddress::Pgf);
+        let escrow = Address::Internal(InternalAddress::Ibc);
+        let target = PGFIbcTarget {
+            target: "cosmos1receiver".to_string(),
+            amount: Amount::native_whole(10),
+            port_id,
+            channel_id,
+        };
+        let mut env = tx_host_env::take();
+        PgfParameters {
+            pgf_inflation_rate: Dec::new(10, 2).unwrap(),
+            stewards_inflation_rate: Dec::zero(),
+            ..Default::default()
+        }
+        .init_storage(&mut env.wl_storage)
+        .unwrap();
+        env.wl_storage
+            .write(&get_epochs_per_year_key(), 365_u64)
+            .unwrap();
+        storage_api::token::write_denom(
+            &mut env.wl_storage,
+            &nam,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        )
+        .unwrap();
+        storage_api::token::credit_tokens(
+            &mut env.wl_storage,
+            &nam,
+            &sender,
+            Amount::native_whole(1_000_000),
+        )
+        .unwrap();
+
+        // A fractional amount cannot be transferred over IBC
+        let fractional_target = PGFIbcTarget {
+            amount: Amount::from_uint(10_500_000, 0).unwrap(),
+            ..target.clone()
+        };
+        assert!(namada::ledger::ibc::transfer_over_ibc(
+            &mut env.wl_storage,
+            &nam,
+            &pgf,
+            &fractional_target,
+        )
+        .is_err());
+
+        pgf_storage::fundings_handle()
+            .insert(
+                &mut env.wl_storage,
+                target.target.clone(),
+                StoragePgfFunding::new(PGFTarget::Ibc(target.clone()), 0),
+            )
+            .unwrap();
+        pgf_inflation::apply_inflation(
+            &mut env.wl_storage,
+            namada::ledger::ibc::transfer_over_ibc,
+        )
+        .unwrap();
+
+        // Check that the funding was escrowed and its packet was sent
+        let escrowed =
+            storage_api::token::read_balance(&env.wl_storage, &nam, &escrow)
+                .unwrap();
+        assert_eq!(escrowed, target.amount);
+        let commitment_key = ibc_storage::commitment_key(
+            &target.port_id,
+            &target.channel_id,
+            ibc::Sequence::from(1),
+        );
+        assert!(env.wl_storage.has_key(&commitment_key).unwrap());
+    }
 }
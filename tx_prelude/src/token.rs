@@ -18,6 +18,7 @@ pub fn transfer(
     if amount != Amount::default() && src != dest {
         let src_key = token::balance_key(token, src);
         let dest_key = token::balance_key(token, dest);
+        storage_api::account::record_spending(ctx, src, token, amount)?;
         let src_bal: Option<Amount> = ctx.read(&src_key)?;
         let mut src_bal = src_bal.unwrap_or_else(|| {
             log_string(format!("src {} has no balance", src_key));
@@ -43,6 +44,7 @@ pub fn undenominated_transfer(
     if amount != Amount::default() && src != dest {
         let src_key = token::balance_key(token, src);
         let dest_key = token::balance_key(token, dest);
+        storage_api::account::record_spending(ctx, src, token, amount)?;
         let src_bal: Option<Amount> = ctx.read(&src_key)?;
         let mut src_bal = src_bal.unwrap_or_else(|| {
             log_string(format!("src {} has no balance", src_key));
@@ -90,6 +92,7 @@ pub fn burn(
     amount: Amount,
 ) -> TxResult {
     let target_key = token::balance_key(token, target);
+    storage_api::account::record_spending(ctx, target, token, amount)?;
     let mut target_bal: Amount = ctx.read(&target_key)?.unwrap_or_default();
     target_bal.spend(&amount);
 
@@ -24,6 +24,7 @@ pub use namada_core::ledger::storage_api::{
 };
 pub use namada_core::ledger::vp_env::VpEnv;
 pub use namada_core::proto::{Section, Tx};
+use namada_core::types::account::KeyRole;
 pub use namada_core::types::address::Address;
 use namada_core::types::chain::CHAIN_ID_LENGTH;
 use namada_core::types::hash::{Hash, HASH_LENGTH};
@@ -79,13 +80,38 @@ pub fn is_proposal_accepted(ctx: &Ctx, proposal_id: u64) -> VpResult {
     ctx.has_key_pre(&proposal_execution_key)
 }
 
-/// Verify section signatures
+/// Verify section signatures of the keys of the owner that are not restricted
+/// to any role
 pub fn verify_signatures(ctx: &Ctx, tx: &Tx, owner: &Address) -> VpResult {
+    verify_signatures_with_role(ctx, tx, owner, None)
+}
+
+/// Verify section signatures of the keys of the owner that can sign for the
+/// given role
+pub fn verify_signatures_for_role(
+    ctx: &Ctx,
+    tx: &Tx,
+    owner: &Address,
+    role: KeyRole,
+) -> VpResult {
+    verify_signatures_with_role(ctx, tx, owner, Some(role))
+}
+
+fn verify_signatures_with_role(
+    ctx: &Ctx,
+    tx: &Tx,
+    owner: &Address,
+    role: Option<KeyRole>,
+) -> VpResult {
     let max_signatures_per_transaction =
         parameters::max_signatures_per_transaction(&ctx.pre())?;
 
     let public_keys_index_map =
-        storage_api::account::public_keys_index_map(&ctx.pre(), owner)?;
+        storage_api::account::public_keys_index_map_for_role(
+            &ctx.pre(),
+            owner,
+            role,
+        )?;
     let threshold =
         storage_api::account::threshold(&ctx.pre(), owner)?.unwrap_or(1);
 
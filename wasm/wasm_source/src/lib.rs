@@ -4,6 +4,10 @@ pub mod tx_become_validator;
 pub mod tx_bond;
 #[cfg(feature = "tx_bridge_pool")]
 pub mod tx_bridge_pool;
+#[cfg(feature = "tx_bridge_pool_bump_fee")]
+pub mod tx_bridge_pool_bump_fee;
+#[cfg(feature = "tx_bridge_pool_cancel")]
+pub mod tx_bridge_pool_cancel;
 #[cfg(feature = "tx_change_consensus_key")]
 pub mod tx_change_consensus_key;
 #[cfg(feature = "tx_change_validator_commission")]
@@ -14,6 +18,10 @@ pub mod tx_change_validator_metadata;
 pub mod tx_claim_rewards;
 #[cfg(feature = "tx_deactivate_validator")]
 pub mod tx_deactivate_validator;
+#[cfg(feature = "tx_delegate_vote")]
+pub mod tx_delegate_vote;
+#[cfg(feature = "tx_deposit_proposal")]
+pub mod tx_deposit_proposal;
 #[cfg(feature = "tx_ibc")]
 pub mod tx_ibc;
 #[cfg(feature = "tx_init_account")]
@@ -28,6 +36,8 @@ pub mod tx_redelegate;
 pub mod tx_resign_steward;
 #[cfg(feature = "tx_reveal_pk")]
 pub mod tx_reveal_pk;
+#[cfg(feature = "tx_spending_policy")]
+pub mod tx_spending_policy;
 #[cfg(feature = "tx_transfer")]
 pub mod tx_transfer;
 #[cfg(feature = "tx_unbond")]
@@ -0,0 +1,37 @@
+//! A tx for the payer of the gas fees of a transfer in the bridge pool to
+//! raise them, without changing the transfer.
+use eth_bridge::storage::bridge_pool;
+use eth_bridge_pool::{BumpPendingTransferFee, PendingTransfer};
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1038546)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, signed: Tx) -> TxResult {
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let bump = BumpPendingTransferFee::try_from_slice(&data[..])
+        .wrap_err("failed to decode BumpPendingTransferFee")?;
+    let transfer: PendingTransfer = ctx
+        .read(&bridge_pool::get_key_from_hash(&bump.transfer_hash))?
+        .ok_or_err_msg("The transfer is not in the bridge pool")?;
+    debug_log!(
+        "Bumping the gas fees of the transfer {} by {}",
+        bump.transfer_hash,
+        bump.amount.to_string_native()
+    );
+    // escrow the additional fees
+    token::undenominated_transfer(
+        ctx,
+        &transfer.gas_fee.payer,
+        &bridge_pool::BRIDGE_POOL_ADDRESS,
+        &transfer.gas_fee.token,
+        bump.amount,
+    )?;
+    let fee_bump_key = bridge_pool::get_fee_bump_key(&bump.transfer_hash);
+    let fee_bump: token::Amount = ctx.read(&fee_bump_key)?.unwrap_or_default();
+    let fee_bump = fee_bump
+        .checked_add(bump.amount)
+        .ok_or_err_msg("Overflowed bumping the gas fees")?;
+    ctx.write(&fee_bump_key, fee_bump)
+}
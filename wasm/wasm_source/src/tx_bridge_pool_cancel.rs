@@ -0,0 +1,75 @@
+//! A tx for the sender of a transfer in the bridge pool to cancel it, or to
+//! set the epoch from which it is refunded by the protocol.
+use eth_bridge::storage::{bridge_pool, native_erc20_key};
+use eth_bridge_pool::{CancelPendingTransfer, PendingTransfer};
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1038546)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, signed: Tx) -> TxResult {
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let cancel = CancelPendingTransfer::try_from_slice(&data[..])
+        .wrap_err("failed to decode CancelPendingTransfer")?;
+    let pending_key = bridge_pool::get_key_from_hash(&cancel.transfer_hash);
+    let expiry_key = bridge_pool::get_expiry_key(&cancel.transfer_hash);
+    let fee_bump_key = bridge_pool::get_fee_bump_key(&cancel.transfer_hash);
+    let transfer: PendingTransfer = ctx
+        .read(&pending_key)?
+        .ok_or_err_msg("The transfer is not in the bridge pool")?;
+    // the sender must authorize the cancellation
+    ctx.insert_verifier(&transfer.transfer.sender)?;
+
+    if let Some(expiry) = cancel.expiry {
+        debug_log!("Setting the expiry of the transfer to epoch {}", expiry);
+        return ctx.write(&expiry_key, expiry);
+    }
+
+    debug_log!("Cancelling the transfer {}", cancel.transfer_hash);
+    // refund the gas fees, including the bumped fees
+    let fee_bump: token::Amount = ctx.read(&fee_bump_key)?.unwrap_or_default();
+    let gas_fees = transfer
+        .gas_fee
+        .amount
+        .checked_add(fee_bump)
+        .ok_or_err_msg("Overflowed adding the fee bump")?;
+    token::undenominated_transfer(
+        ctx,
+        &bridge_pool::BRIDGE_POOL_ADDRESS,
+        &transfer.gas_fee.payer,
+        &transfer.gas_fee.token,
+        gas_fees,
+    )?;
+    // refund the escrowed tokens
+    let native_erc20: EthAddress = ctx
+        .read(&native_erc20_key())?
+        .ok_or_err_msg("Missing the wNam address")?;
+    if transfer.transfer.asset == native_erc20 {
+        let nam_addr = ctx.get_native_token()?;
+        token::undenominated_transfer(
+            ctx,
+            &eth_bridge::ADDRESS,
+            &transfer.transfer.sender,
+            &nam_addr,
+            transfer.transfer.amount,
+        )?;
+    } else {
+        let token = transfer.token_address();
+        token::undenominated_transfer(
+            ctx,
+            &bridge_pool::BRIDGE_POOL_ADDRESS,
+            &transfer.transfer.sender,
+            &token,
+            transfer.transfer.amount,
+        )?;
+    }
+    // remove the transfer from the pool
+    ctx.delete(&pending_key)?;
+    for key in [expiry_key, fee_bump_key] {
+        if ctx.has_key(&key)? {
+            ctx.delete(&key)?;
+        }
+    }
+    Ok(())
+}
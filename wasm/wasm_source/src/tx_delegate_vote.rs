@@ -0,0 +1,20 @@
+//! A tx to delegate the voting power of an address to a representative, or to
+//! revoke the delegation
+
+use namada_tx_prelude::*;
+
+#[transaction(gas = 840866)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let tx_data =
+        transaction::governance::VoteDelegationData::try_from_slice(&data[..])
+            .wrap_err("failed to decode VoteDelegationData")?;
+
+    debug_log!("apply_tx called to delegate the voting power");
+
+    governance::delegate_vote(ctx, tx_data)
+}
@@ -0,0 +1,44 @@
+//! A tx to manage the spending policy of an account and to schedule, execute
+//! or cancel withdrawals above the policy's thresholds.
+
+use namada_tx_prelude::transaction::account::UpdateSpendingPolicy;
+use namada_tx_prelude::*;
+
+#[transaction(gas = 1000000)] // TODO: needs to be benchmarked
+fn apply_tx(ctx: &mut Ctx, tx_data: Tx) -> TxResult {
+    let signed = tx_data;
+    let data = signed.data().ok_or_err_msg("Missing data").map_err(|err| {
+        ctx.set_commitment_sentinel();
+        err
+    })?;
+    let tx_data = UpdateSpendingPolicy::try_from_slice(&data[..])
+        .wrap_err("failed to decode UpdateSpendingPolicy")?;
+    debug_log!(
+        "apply_tx called with spending policy update: {:#?}",
+        tx_data
+    );
+
+    match tx_data {
+        UpdateSpendingPolicy::SetPolicy { owner, policy } => {
+            storage_api::account::set_spending_policy(ctx, &owner, policy)
+        }
+        UpdateSpendingPolicy::ScheduleWithdrawal {
+            owner,
+            token,
+            target,
+            amount,
+        } => {
+            let id = storage_api::account::schedule_withdrawal(
+                ctx, &owner, &token, &target, amount,
+            )?;
+            debug_log!("scheduled withdrawal with id {}", id);
+            Ok(())
+        }
+        UpdateSpendingPolicy::ExecuteWithdrawal { owner, id } => {
+            storage_api::account::execute_withdrawal(ctx, &owner, id)
+        }
+        UpdateSpendingPolicy::CancelWithdrawal { owner, id } => {
+            storage_api::account::cancel_withdrawal(ctx, &owner, id)
+        }
+    }
+}
@@ -48,11 +48,14 @@ fn apply_tx(ctx: &mut Ctx, tx: Tx) -> TxResult {
 
     if !tx_data.public_keys.is_empty() {
         storage_api::account::clear_public_keys(ctx, owner)?;
+        storage_api::account::clear_key_options(ctx, owner)?;
         for (index, public_key) in tx_data.public_keys.iter().enumerate() {
             let index = index as u8;
             pks_handle(owner).insert(ctx, index, public_key.clone())?;
         }
     }
 
+    storage_api::account::set_key_options(ctx, owner, tx_data.key_options)?;
+
     Ok(())
 }
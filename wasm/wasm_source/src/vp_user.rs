@@ -9,10 +9,22 @@
 //! For validator a tx to change a validator's commission rate or metadata
 //! requires a valid signature(s) only from the validator.
 //!
+//! The signatures of the account's keys count towards its threshold with the
+//! keys' weights. Keys restricted to the governance role can only sign votes,
+//! and keys restricted to the staking role can only sign bonds, unbonds,
+//! redelegations, withdrawals and reward claims. The options of the keys must
+//! have a non-zero weight and belong to one of the account's public keys.
+//!
+//! If the account has a spending policy, debits of its tokens must also abide
+//! by the policy's epoch limits, recipient allowlist and withdrawal thresholds,
+//! and changes of the policy only take effect after the policy's delay.
+//!
 //! Any other storage key changes are allowed only with a valid signature.
 
 use core::ops::Deref;
+use std::collections::BTreeMap;
 
+use namada_vp_prelude::account::{KeyRole, PendingWithdrawal, SpendingPolicy};
 use namada_vp_prelude::*;
 use once_cell::unsync::Lazy;
 use proof_of_stake::storage::{read_pos_params, validator_state_handle};
@@ -31,7 +43,10 @@ use proof_of_stake::storage_key::{
 };
 
 enum KeyType<'a> {
-    TokenBalance { owner: &'a Address },
+    TokenBalance {
+        token: &'a Address,
+        owner: &'a Address,
+    },
     TokenMinted,
     TokenMinter(&'a Address),
     PoS,
@@ -39,14 +54,16 @@ enum KeyType<'a> {
     Masp,
     PgfSteward(&'a Address),
     GovernanceVote(&'a Address),
+    SpendingPolicy(&'a Address),
+    AccountKeys(&'a Address),
     Ibc,
     Unknown,
 }
 
 impl<'a> From<&'a storage::Key> for KeyType<'a> {
     fn from(key: &'a storage::Key) -> KeyType<'a> {
-        if let Some([_, owner]) = token::is_any_token_balance_key(key) {
-            Self::TokenBalance { owner }
+        if let Some([token, owner]) = token::is_any_token_balance_key(key) {
+            Self::TokenBalance { token, owner }
         } else if token::is_any_minted_balance_key(key).is_some() {
             Self::TokenMinted
         } else if let Some(minter) = token::is_any_minter_key(key) {
@@ -60,8 +77,30 @@ impl<'a> From<&'a storage::Key> for KeyType<'a> {
             } else {
                 Self::Unknown
             }
+        } else if gov_storage::keys::is_representative_vote_key(key) {
+            let representative =
+                gov_storage::keys::get_representative_vote_address(key);
+            if let Some(address) = representative {
+                Self::GovernanceVote(address)
+            } else {
+                Self::Unknown
+            }
+        } else if gov_storage::keys::is_representative_key(key) {
+            // Delegating the voting power is signed like a vote
+            let delegator = gov_storage::keys::get_represented_address(key);
+            if let Some(address) = delegator {
+                Self::GovernanceVote(address)
+            } else {
+                Self::Unknown
+            }
         } else if let Some(address) = pgf_storage::keys::is_stewards_key(key) {
             Self::PgfSteward(address)
+        } else if let Some(owner) = key::is_spending_policy_key(key) {
+            Self::SpendingPolicy(owner)
+        } else if let Some(owner) =
+            key::is_key_options_key(key).or_else(|| key::is_pks_key(key))
+        {
+            Self::AccountKeys(owner)
         } else if let Some(address) = key.is_validity_predicate() {
             Self::Vp(address)
         } else if token::is_masp_key(key) {
@@ -92,6 +131,24 @@ fn validate_tx(
     let valid_sig = Lazy::new(|| {
         matches!(verify_signatures(ctx, &tx_data, &addr), Ok(true))
     });
+    // Keys restricted to a role can only sign for the actions of that role
+    let valid_governance_sig = Lazy::new(|| {
+        matches!(
+            verify_signatures_for_role(
+                ctx,
+                &tx_data,
+                &addr,
+                KeyRole::Governance
+            ),
+            Ok(true)
+        )
+    });
+    let valid_staking_sig = Lazy::new(|| {
+        matches!(
+            verify_signatures_for_role(ctx, &tx_data, &addr, KeyRole::Staking),
+            Ok(true)
+        )
+    });
 
     if !is_valid_tx(ctx, &tx_data)? {
         return reject();
@@ -100,15 +157,31 @@ fn validate_tx(
     for key in keys_changed.iter() {
         let key_type: KeyType = key.into();
         let is_valid = match key_type {
-            KeyType::TokenBalance { owner, .. } => {
+            KeyType::TokenBalance { token, owner } => {
                 if owner == &addr {
                     let pre: token::Amount =
                         ctx.read_pre(key)?.unwrap_or_default();
                     let post: token::Amount =
                         ctx.read_post(key)?.unwrap_or_default();
                     let change = post.change() - pre.change();
-                    // debit has to signed, credit doesn't
-                    let valid = change.non_negative() || *valid_sig;
+                    // debit has to signed and abide by the spending policy,
+                    // credit doesn't. Staking keys can only sign for bonds.
+                    let valid = change.non_negative()
+                        || ((*valid_sig
+                            || (*valid_staking_sig
+                                && is_bond_debit(
+                                    ctx,
+                                    &addr,
+                                    token,
+                                    &keys_changed,
+                                )?))
+                            && validate_spending(
+                                ctx,
+                                &addr,
+                                token,
+                                pre.checked_sub(post).unwrap_or_default(),
+                                &keys_changed,
+                            )?);
                     debug_log!(
                         "token key: {}, change: {:?}, valid_sig: {}, valid \
                          modification: {}",
@@ -132,9 +205,34 @@ fn validate_tx(
             }
             KeyType::TokenMinted => verifiers.contains(&address::MULTITOKEN),
             KeyType::TokenMinter(minter) => minter != &addr || *valid_sig,
-            KeyType::PoS => validate_pos_changes(ctx, &addr, key, &valid_sig)?,
+            KeyType::PoS => validate_pos_changes(
+                ctx,
+                &addr,
+                key,
+                &valid_sig,
+                &valid_staking_sig,
+            )?,
             KeyType::PgfSteward(address) => address != &addr || *valid_sig,
-            KeyType::GovernanceVote(voter) => voter != &addr || *valid_sig,
+            KeyType::GovernanceVote(voter) => {
+                voter != &addr || *valid_governance_sig
+            }
+            KeyType::SpendingPolicy(owner) => {
+                // Bonds record the spent amounts too
+                let is_spent_by_bond =
+                    key::is_spent_key(key).is_some() && *valid_staking_sig;
+                owner != &addr
+                    || ((*valid_sig || is_spent_by_bond)
+                        && validate_spending_policy_change(ctx, owner, key)?)
+            }
+            KeyType::AccountKeys(owner) => {
+                // The key options must match the public keys
+                owner != &addr
+                    || (*valid_sig
+                        && storage_api::account::are_key_options_valid(
+                            &ctx.post(),
+                            owner,
+                        )?)
+            }
             KeyType::Vp(owner) => {
                 let has_post: bool = ctx.has_key_post(key)?;
                 if owner == &addr {
@@ -165,13 +263,17 @@ fn validate_tx(
     accept()
 }
 
+/// Check a change of a PoS key. The keys restricted to the staking role can
+/// only sign for bonds, unbonds, redelegations, withdrawals and reward claims,
+/// while the other changes must be signed by the keys without any role.
 fn validate_pos_changes(
     ctx: &Ctx,
     owner: &Address,
     key: &storage::Key,
     valid_sig: &impl Deref<Target = bool>,
+    valid_staking_sig: &impl Deref<Target = bool>,
 ) -> VpResult {
-    // Bond or unbond
+    // Bond, unbond or withdrawal
     let is_valid_bond_or_unbond_change = || {
         let bond_id = is_bond_key(key)
             .map(|(bond_id, _)| bond_id)
@@ -179,7 +281,7 @@ fn validate_pos_changes(
             .or_else(|| is_unbond_key(key).map(|(bond_id, _, _)| bond_id));
         if let Some(bond_id) = bond_id {
             // Bonds and unbonds changes for this address must be signed
-            return &bond_id.source != owner || **valid_sig;
+            return &bond_id.source != owner || **valid_staking_sig;
         };
         // Unknown changes are not allowed
         false
@@ -284,11 +386,11 @@ fn validate_pos_changes(
     let is_valid_reward_claim = || {
         if let Some(bond_id) = is_last_pos_reward_claim_epoch_key(key) {
             // Claims for this address must be signed
-            return &bond_id.source != owner || **valid_sig;
+            return &bond_id.source != owner || **valid_staking_sig;
         }
         if let Some(bond_id) = is_rewards_counter_key(key) {
             // Claims for this address must be signed
-            return &bond_id.source != owner || **valid_sig;
+            return &bond_id.source != owner || **valid_staking_sig;
         }
         false
     };
@@ -299,11 +401,11 @@ fn validate_pos_changes(
         }
         if let Some(delegator) = is_delegator_redelegations_key(key) {
             // Redelegations for this address must be signed
-            return delegator != owner || **valid_sig;
+            return delegator != owner || **valid_staking_sig;
         }
         if let Some(bond_id) = is_rewards_counter_key(key) {
             // Redelegations auto-claim rewards
-            return &bond_id.source != owner || **valid_sig;
+            return &bond_id.source != owner || **valid_staking_sig;
         }
         false
     };
@@ -337,6 +439,214 @@ fn validate_pos_changes(
         || **valid_sig)
 }
 
+/// Check that the only account credited with the given token, other than the
+/// owner, is the PoS account
+fn is_bond_debit(
+    ctx: &Ctx,
+    owner: &Address,
+    token: &Address,
+    keys_changed: &BTreeSet<storage::Key>,
+) -> VpResult {
+    for key in keys_changed {
+        if let Some(recipient) = token::is_balance_key(token, key) {
+            if recipient != owner && recipient != &address::POS {
+                let pre: token::Amount = ctx.read_pre(key)?.unwrap_or_default();
+                let post: token::Amount =
+                    ctx.read_post(key)?.unwrap_or_default();
+                if post > pre {
+                    return reject();
+                }
+            }
+        }
+    }
+    accept()
+}
+
+/// Check that a debit of the account's tokens abides by its spending policy,
+/// if it has one
+fn validate_spending(
+    ctx: &Ctx,
+    owner: &Address,
+    token: &Address,
+    debit: token::Amount,
+    keys_changed: &BTreeSet<storage::Key>,
+) -> VpResult {
+    let policy = match storage_api::account::spending_policy(&ctx.pre(), owner)?
+    {
+        Some(policy) => policy,
+        None => return accept(),
+    };
+    let current_epoch = ctx.get_block_epoch()?;
+
+    // The accounts credited with the debited tokens
+    let mut credits = BTreeMap::new();
+    for key in keys_changed {
+        if let Some(recipient) = token::is_balance_key(token, key) {
+            if recipient == owner {
+                continue;
+            }
+            let pre: token::Amount = ctx.read_pre(key)?.unwrap_or_default();
+            let post: token::Amount = ctx.read_post(key)?.unwrap_or_default();
+            if post > pre {
+                if !policy.is_allowed_recipient(recipient) {
+                    debug_log!("Recipient {} is not allowed", recipient);
+                    return reject();
+                }
+                credits.insert(recipient.clone(), post - pre);
+            }
+        }
+    }
+
+    // With an allowlist, the whole debit must go to allowed recipients, so
+    // burns don't bypass it
+    if !policy.allowed_recipients.is_empty() {
+        let credited = credits
+            .values()
+            .fold(token::Amount::zero(), |sum, credit| sum + *credit);
+        if credited < debit {
+            debug_log!(
+                "Debit of {} is not credited to allowed recipients",
+                debit.to_string_native()
+            );
+            return reject();
+        }
+    }
+
+    // The withdrawals executed by this tx have been scheduled and are
+    // exempt from the threshold and the epoch limit. A removed withdrawal is
+    // only executed if its target is credited with its amount, otherwise it
+    // has been cancelled.
+    let withdrawals = key::pending_withdrawals_handle(owner);
+    let mut scheduled = token::Amount::zero();
+    for withdrawal in withdrawals.iter(&ctx.pre())? {
+        let (id, withdrawal) = withdrawal?;
+        if &withdrawal.token != token
+            || withdrawal.executable_from > current_epoch
+            || withdrawals.contains(&ctx.post(), &id)?
+        {
+            continue;
+        }
+        if let Some(credit) = credits.get_mut(&withdrawal.target) {
+            if *credit >= withdrawal.amount {
+                *credit -= withdrawal.amount;
+                scheduled += withdrawal.amount;
+            }
+        }
+    }
+    let regular = debit.checked_sub(scheduled).unwrap_or_default();
+
+    if let Some(threshold) = policy.withdrawal_thresholds.get(token) {
+        if regular > *threshold {
+            debug_log!(
+                "Debit of {} exceeds the withdrawal threshold {}",
+                regular.to_string_native(),
+                threshold.to_string_native()
+            );
+            return reject();
+        }
+    }
+
+    if let Some(limit) = policy.epoch_limits.get(token) {
+        let pre_spent =
+            storage_api::account::spent_in_epoch(&ctx.pre(), owner, token)?;
+        let post_spent =
+            storage_api::account::spent_in_epoch(&ctx.post(), owner, token)?;
+        let is_recorded = pre_spent
+            .checked_add(regular)
+            .map(|spent| post_spent >= spent)
+            .unwrap_or_default();
+        if !is_recorded || post_spent > *limit {
+            debug_log!(
+                "Spent amount {} is not recorded or exceeds the epoch limit {}",
+                post_spent.to_string_native(),
+                limit.to_string_native()
+            );
+            return reject();
+        }
+    }
+
+    accept()
+}
+
+/// Check a change in the spending policy subspace of the account
+fn validate_spending_policy_change(
+    ctx: &Ctx,
+    owner: &Address,
+    key: &storage::Key,
+) -> VpResult {
+    let current_epoch = ctx.get_block_epoch()?;
+    let pre_policy = storage_api::account::spending_policy(&ctx.pre(), owner)?;
+    let delay = pre_policy
+        .as_ref()
+        .map(|policy| policy.delay)
+        .unwrap_or_default();
+
+    let pending_key = key::pending_spending_policy_key(owner);
+    if *key == key::spending_policy_key(owner) || *key == pending_key {
+        if delay == 0 {
+            return accept();
+        }
+        // The policy in effect cannot change before its delay
+        let post_policy =
+            storage_api::account::spending_policy(&ctx.post(), owner)?;
+        if post_policy != pre_policy {
+            return reject();
+        }
+        if *key == pending_key {
+            let pending: Option<(storage::Epoch, Option<SpendingPolicy>)> =
+                ctx.read_post(key)?;
+            if let Some((epoch, _)) = pending {
+                return Ok(epoch >= current_epoch + delay);
+            }
+        }
+        return accept();
+    }
+
+    if key::is_spent_key(key).is_some() {
+        // The spent amounts can only grow within an epoch
+        let pre: Option<(storage::Epoch, token::Amount)> = ctx.read_pre(key)?;
+        let post: Option<(storage::Epoch, token::Amount)> =
+            ctx.read_post(key)?;
+        let pre_spent = match pre {
+            Some((epoch, amount)) if epoch == current_epoch => amount,
+            _ => token::Amount::zero(),
+        };
+        return Ok(matches!(
+            post,
+            Some((epoch, amount)) if epoch == current_epoch && amount >= pre_spent
+        ));
+    }
+
+    if *key == key::withdrawals_counter_key(owner) {
+        let pre: u64 = ctx.read_pre(key)?.unwrap_or_default();
+        let post: u64 = ctx.read_post(key)?.unwrap_or_default();
+        return Ok(post > pre);
+    }
+
+    if key
+        .split_prefix(&key::pending_withdrawals_prefix(owner))
+        .is_some()
+    {
+        let pre: Option<PendingWithdrawal> = ctx.read_pre(key)?;
+        let post: Option<PendingWithdrawal> = ctx.read_post(key)?;
+        return Ok(match (pre, post) {
+            // New withdrawals must wait for the delay of the policy
+            (None, Some(post)) => pre_policy
+                .map(|policy| {
+                    policy.is_allowed_recipient(&post.target)
+                        && post.executable_from >= current_epoch + policy.delay
+                })
+                .unwrap_or_default(),
+            // Executed or cancelled withdrawals
+            (Some(_), None) => true,
+            (pre, post) => pre == post,
+        });
+    }
+
+    // Unknown keys in the subspace are not allowed
+    reject()
+}
+
 #[cfg(test)]
 mod tests {
     use address::testing::arb_non_internal_address;
@@ -556,6 +866,351 @@ mod tests {
         );
     }
 
+    /// Run a signed debit transfer of `amount` NAM from an account with the
+    /// given spending policy to `target` and validate it with the VP.
+    fn validate_signed_debit_with_policy(
+        policy: SpendingPolicy,
+        target: &Address,
+        amount: token::Amount,
+    ) -> bool {
+        validate_signed_tx_with_policy(
+            policy,
+            target,
+            amount,
+            |_, _| {},
+            |address| {
+                tx_host_env::token::transfer(
+                    tx::ctx(),
+                    address,
+                    target,
+                    &address::nam(),
+                    token::DenominatedAmount::new(
+                        amount,
+                        token::NATIVE_MAX_DECIMAL_PLACES.into(),
+                    ),
+                )
+                .unwrap();
+            },
+        )
+    }
+
+    /// Run a signed tx applied by `apply` on an account with the given
+    /// spending policy and a balance of `balance` NAM, once `prepare` has
+    /// initialized the storage, and validate it with the VP.
+    fn validate_signed_tx_with_policy(
+        policy: SpendingPolicy,
+        target: &Address,
+        balance: token::Amount,
+        prepare: impl FnOnce(&mut TestTxEnv, &Address),
+        apply: impl FnMut(&Address),
+    ) -> bool {
+        // Initialize a tx environment
+        let mut tx_env = TestTxEnv::default();
+
+        let vp_owner = address::testing::established_address_1();
+        let keypair = key::testing::keypair_1();
+        let public_key = keypair.ref_to();
+        let token = address::nam();
+
+        // Spawn the accounts to be able to modify their storage
+        tx_env.spawn_accounts([&vp_owner, target, &token]);
+        tx_env.init_account_storage(&vp_owner, vec![public_key.clone()], 1);
+        storage_api::account::set_spending_policy(
+            &mut tx_env.wl_storage,
+            &vp_owner,
+            Some(policy),
+        )
+        .unwrap();
+
+        // Credit the tokens to the VP owner before running the transaction to
+        // be able to transfer from it
+        tx_env.credit_tokens(&vp_owner, &token, balance);
+        // write the denomination of NAM into storage
+        storage_api::token::write_denom(
+            &mut tx_env.wl_storage,
+            &token,
+            token::NATIVE_MAX_DECIMAL_PLACES.into(),
+        )
+        .unwrap();
+        prepare(&mut tx_env, &vp_owner);
+
+        // Initialize VP environment from a transaction
+        vp_host_env::init_from_tx(vp_owner.clone(), tx_env, apply);
+
+        let pks_map = AccountPublicKeysMap::from_iter(vec![public_key]);
+
+        let mut vp_env = vp_host_env::take();
+        let mut tx = vp_env.tx.clone();
+        tx.set_data(Data::new(vec![]));
+        tx.set_code(Code::new(vec![], None));
+        tx.add_section(Section::Signature(Signature::new(
+            vec![tx.raw_header_hash()],
+            pks_map.index_secret_keys(vec![keypair]),
+            None,
+        )));
+        let signed_tx = tx.clone();
+        vp_env.tx = signed_tx.clone();
+        let keys_changed: BTreeSet<storage::Key> =
+            vp_env.all_touched_storage_keys();
+        let verifiers: BTreeSet<Address> = BTreeSet::default();
+        vp_host_env::set(vp_env);
+        validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers).unwrap()
+    }
+
+    /// Test that a signed debit transfer within the limits of the account's
+    /// spending policy is accepted.
+    #[test]
+    fn test_signed_debit_within_spending_policy_accepted() {
+        let target = address::testing::established_address_2();
+        let amount = token::Amount::native_whole(10);
+        let policy = SpendingPolicy {
+            epoch_limits: [(address::nam(), amount)].into(),
+            allowed_recipients: [target.clone()].into(),
+            withdrawal_thresholds: [(address::nam(), amount)].into(),
+            delay: 1,
+        };
+        assert!(validate_signed_debit_with_policy(policy, &target, amount));
+    }
+
+    /// Test that a signed debit transfer above the epoch limit, above the
+    /// withdrawal threshold or to a recipient that is not allowed is
+    /// rejected.
+    #[test]
+    fn test_signed_debit_outside_spending_policy_rejected() {
+        let target = address::testing::established_address_2();
+        let amount = token::Amount::native_whole(10);
+        let limit = token::Amount::native_whole(5);
+
+        let policy = SpendingPolicy {
+            epoch_limits: [(address::nam(), limit)].into(),
+            ..Default::default()
+        };
+        assert!(!validate_signed_debit_with_policy(policy, &target, amount));
+
+        let policy = SpendingPolicy {
+            withdrawal_thresholds: [(address::nam(), limit)].into(),
+            ..Default::default()
+        };
+        assert!(!validate_signed_debit_with_policy(policy, &target, amount));
+
+        let policy = SpendingPolicy {
+            allowed_recipients: [address::testing::established_address_3()]
+                .into(),
+            ..Default::default()
+        };
+        assert!(!validate_signed_debit_with_policy(policy, &target, amount));
+    }
+
+    /// Schedule a withdrawal of `amount` NAM to `target` which can already be
+    /// executed
+    fn schedule_withdrawal(
+        tx_env: &mut TestTxEnv,
+        owner: &Address,
+        target: &Address,
+        amount: token::Amount,
+    ) {
+        key::pending_withdrawals_handle(owner)
+            .insert(
+                &mut tx_env.wl_storage,
+                0,
+                PendingWithdrawal {
+                    token: address::nam(),
+                    target: target.clone(),
+                    amount,
+                    executable_from: Epoch(0),
+                },
+            )
+            .unwrap();
+    }
+
+    /// Test that the execution of a scheduled withdrawal above the withdrawal
+    /// threshold is accepted.
+    #[test]
+    fn test_executed_withdrawal_accepted() {
+        let target = address::testing::established_address_2();
+        let amount = token::Amount::native_whole(10);
+        let policy = SpendingPolicy {
+            allowed_recipients: [target.clone()].into(),
+            withdrawal_thresholds: [(address::nam(), token::Amount::zero())]
+                .into(),
+            delay: 1,
+            ..Default::default()
+        };
+        assert!(validate_signed_tx_with_policy(
+            policy,
+            &target,
+            amount,
+            |tx_env, owner| schedule_withdrawal(tx_env, owner, &target, amount),
+            |address| {
+                storage_api::account::execute_withdrawal(tx::ctx(), address, 0)
+                    .unwrap();
+            },
+        ));
+    }
+
+    /// Test that cancelling a scheduled withdrawal doesn't exempt a debit of
+    /// the same amount to another recipient from the withdrawal threshold.
+    #[test]
+    fn test_cancelled_withdrawal_debit_rejected() {
+        let target = address::testing::established_address_2();
+        let other = address::testing::established_address_3();
+        let amount = token::Amount::native_whole(10);
+        let policy = SpendingPolicy {
+            withdrawal_thresholds: [(address::nam(), token::Amount::zero())]
+                .into(),
+            delay: 1,
+            ..Default::default()
+        };
+        assert!(!validate_signed_tx_with_policy(
+            policy,
+            &target,
+            amount,
+            |tx_env, owner| {
+                tx_env.spawn_accounts([&other]);
+                schedule_withdrawal(tx_env, owner, &target, amount);
+            },
+            |address| {
+                storage_api::account::cancel_withdrawal(tx::ctx(), address, 0)
+                    .unwrap();
+                tx_host_env::token::transfer(
+                    tx::ctx(),
+                    address,
+                    &other,
+                    &address::nam(),
+                    token::DenominatedAmount::new(
+                        amount,
+                        token::NATIVE_MAX_DECIMAL_PLACES.into(),
+                    ),
+                )
+                .unwrap();
+            },
+        ));
+    }
+
+    /// Test that a burn of the account's tokens is rejected when the spending
+    /// policy has a recipient allowlist.
+    #[test]
+    fn test_burn_with_allowlist_rejected() {
+        let target = address::testing::established_address_2();
+        let amount = token::Amount::native_whole(10);
+        let policy = SpendingPolicy {
+            allowed_recipients: [target.clone()].into(),
+            ..Default::default()
+        };
+        assert!(!validate_signed_tx_with_policy(
+            policy,
+            &target,
+            amount,
+            |_, _| {},
+            |address| {
+                tx_host_env::token::burn(
+                    tx::ctx(),
+                    address,
+                    &address::nam(),
+                    amount,
+                )
+                .unwrap();
+            },
+        ));
+    }
+
+    /// Test that signatures count with the weights of the keys, and that a
+    /// key restricted to governance cannot sign a debit transfer.
+    #[test]
+    fn test_signed_debit_transfer_weights_and_roles() {
+        let keypair_1 = key::testing::keypair_1();
+        let keypair_2 = key::testing::keypair_2();
+
+        for (signer, expected) in
+            [(keypair_1.clone(), true), (keypair_2.clone(), false)]
+        {
+            // Initialize a tx environment
+            let mut tx_env = TestTxEnv::default();
+
+            let vp_owner = address::testing::established_address_1();
+            let public_keys = vec![keypair_1.ref_to(), keypair_2.ref_to()];
+            let target = address::testing::established_address_2();
+            let token = address::nam();
+            let amount = token::Amount::from_uint(10_098_123, 0).unwrap();
+
+            // Spawn the accounts to be able to modify their storage
+            tx_env.spawn_accounts([&vp_owner, &target, &token]);
+            tx_env.init_account_storage(&vp_owner, public_keys.clone(), 2);
+            storage_api::account::set_key_options_at(
+                &mut tx_env.wl_storage,
+                &vp_owner,
+                account::AccountKeyOptions {
+                    weight: 2,
+                    roles: Default::default(),
+                },
+                0,
+            )
+            .unwrap();
+            storage_api::account::set_key_options_at(
+                &mut tx_env.wl_storage,
+                &vp_owner,
+                account::AccountKeyOptions {
+                    weight: 2,
+                    roles: [KeyRole::Governance].into(),
+                },
+                1,
+            )
+            .unwrap();
+
+            // Credit the tokens to the VP owner before running the
+            // transaction to be able to transfer from it
+            tx_env.credit_tokens(&vp_owner, &token, amount);
+            // write the denomination of NAM into storage
+            storage_api::token::write_denom(
+                &mut tx_env.wl_storage,
+                &token,
+                token::NATIVE_MAX_DECIMAL_PLACES.into(),
+            )
+            .unwrap();
+
+            let amount = token::DenominatedAmount::new(
+                amount,
+                token::NATIVE_MAX_DECIMAL_PLACES.into(),
+            );
+
+            // Initialize VP environment from a transaction
+            vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+                // Apply transfer in a transaction
+                tx_host_env::token::transfer(
+                    tx::ctx(),
+                    address,
+                    &target,
+                    &token,
+                    amount,
+                )
+                .unwrap();
+            });
+
+            let pks_map = AccountPublicKeysMap::from_iter(public_keys);
+
+            let mut vp_env = vp_host_env::take();
+            let mut tx = vp_env.tx.clone();
+            tx.set_data(Data::new(vec![]));
+            tx.set_code(Code::new(vec![], None));
+            tx.add_section(Section::Signature(Signature::new(
+                vec![tx.raw_header_hash()],
+                pks_map.index_secret_keys(vec![signer]),
+                None,
+            )));
+            let signed_tx = tx.clone();
+            vp_env.tx = signed_tx.clone();
+            let keys_changed: BTreeSet<storage::Key> =
+                vp_env.all_touched_storage_keys();
+            let verifiers: BTreeSet<Address> = BTreeSet::default();
+            vp_host_env::set(vp_env);
+            assert_eq!(
+                validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+                    .unwrap(),
+                expected
+            );
+        }
+    }
+
     /// Test that a non-validator PoS action that must be authorized is rejected
     /// without a valid signature.
     #[test]
@@ -1104,6 +1759,181 @@ mod tests {
         );
     }
 
+    /// Test that a key restricted to the staking role can sign bonds and
+    /// unbonds, but not the actions of a validator.
+    #[test]
+    fn test_staking_key_validator_pos_action_rejected() {
+        for (validator_action, expected) in [(false, true), (true, false)] {
+            // Init PoS genesis
+            let pos_params = PosParams::default();
+            let validator = address::testing::established_address_3();
+            let initial_stake =
+                token::Amount::from_uint(10_098_123, 0).unwrap();
+            let consensus_key = key::testing::keypair_2().ref_to();
+            let protocol_key = key::testing::keypair_1().ref_to();
+            let commission_rate = Dec::new(5, 2).unwrap();
+            let max_commission_rate_change = Dec::new(1, 2).unwrap();
+
+            let genesis_validators = [GenesisValidator {
+                address: validator.clone(),
+                tokens: initial_stake,
+                consensus_key,
+                protocol_key,
+                commission_rate,
+                max_commission_rate_change,
+                eth_hot_key: key::common::PublicKey::Secp256k1(
+                    key::testing::gen_keypair::<key::secp256k1::SigScheme>()
+                        .ref_to(),
+                ),
+                eth_cold_key: key::common::PublicKey::Secp256k1(
+                    key::testing::gen_keypair::<key::secp256k1::SigScheme>()
+                        .ref_to(),
+                ),
+                metadata: Default::default(),
+            }];
+
+            init_pos(&genesis_validators[..], &pos_params, Epoch(0));
+
+            // Initialize a tx environment
+            let mut tx_env = tx_host_env::take();
+
+            let secret_key = key::testing::keypair_1();
+            let public_key = secret_key.ref_to();
+            let token = address::nam();
+            let amount = token::Amount::from_uint(10_098_123, 0).unwrap();
+            let bond_amount = token::Amount::from_uint(5_098_123, 0).unwrap();
+            let unbond_amount = token::Amount::from_uint(3_098_123, 0).unwrap();
+
+            // Spawn the accounts to be able to modify their storage
+            tx_env.spawn_accounts([&token]);
+            tx_env.init_account_storage(
+                &validator,
+                vec![public_key.clone()],
+                1,
+            );
+            storage_api::account::set_key_options_at(
+                &mut tx_env.wl_storage,
+                &validator,
+                account::AccountKeyOptions {
+                    weight: 1,
+                    roles: [KeyRole::Staking].into(),
+                },
+                0,
+            )
+            .unwrap();
+
+            // write the denomination of NAM into storage
+            storage_api::token::write_denom(
+                &mut tx_env.wl_storage,
+                &token,
+                token::NATIVE_MAX_DECIMAL_PLACES.into(),
+            )
+            .unwrap();
+
+            // Credit the tokens to the VP owner before running the
+            // transaction to be able to transfer from it
+            tx_env.credit_tokens(&validator, &token, amount);
+
+            vp_host_env::init_from_tx(validator.clone(), tx_env, |_address| {
+                // Bond the tokens, then unbond some of them
+                tx::ctx()
+                    .bond_tokens(Some(&validator), &validator, bond_amount)
+                    .unwrap();
+                tx::ctx()
+                    .unbond_tokens(Some(&validator), &validator, unbond_amount)
+                    .unwrap();
+                if validator_action {
+                    tx::ctx().deactivate_validator(&validator).unwrap();
+                }
+            });
+
+            let pks_map = AccountPublicKeysMap::from_iter(vec![public_key]);
+
+            let mut vp_env = vp_host_env::take();
+            let mut tx = vp_env.tx.clone();
+            tx.set_data(Data::new(vec![]));
+            tx.set_code(Code::new(vec![], None));
+            tx.add_section(Section::Signature(Signature::new(
+                vec![tx.raw_header_hash()],
+                pks_map.index_secret_keys(vec![secret_key]),
+                None,
+            )));
+            let signed_tx = tx.clone();
+            vp_env.tx = signed_tx.clone();
+            let keys_changed: BTreeSet<storage::Key> =
+                vp_env.all_touched_storage_keys();
+            let verifiers: BTreeSet<Address> = BTreeSet::default();
+            vp_host_env::set(vp_env);
+            assert_eq!(
+                validate_tx(
+                    &CTX,
+                    signed_tx,
+                    validator,
+                    keys_changed,
+                    verifiers
+                )
+                .unwrap(),
+                expected
+            );
+        }
+    }
+
+    /// Test that key options without a public key at their index are
+    /// rejected, even with a valid signature.
+    #[test]
+    fn test_signed_invalid_key_options_rejected() {
+        for (index, expected) in [(0, true), (1, false)] {
+            // Initialize a tx environment
+            let mut tx_env = TestTxEnv::default();
+
+            let vp_owner = address::testing::established_address_1();
+            let secret_key = key::testing::keypair_1();
+            let public_key = secret_key.ref_to();
+
+            // Spawn the accounts to be able to modify their storage
+            tx_env.spawn_accounts([&vp_owner]);
+            tx_env.init_account_storage(&vp_owner, vec![public_key.clone()], 1);
+
+            // Initialize VP environment from a transaction
+            vp_host_env::init_from_tx(vp_owner.clone(), tx_env, |address| {
+                // Set the options of a key in a transaction
+                storage_api::account::set_key_options_at(
+                    tx::ctx(),
+                    address,
+                    account::AccountKeyOptions {
+                        weight: 1,
+                        roles: Default::default(),
+                    },
+                    index,
+                )
+                .unwrap();
+            });
+
+            let pks_map = AccountPublicKeysMap::from_iter(vec![public_key]);
+
+            let mut vp_env = vp_host_env::take();
+            let mut tx = vp_env.tx.clone();
+            tx.set_data(Data::new(vec![]));
+            tx.set_code(Code::new(vec![], None));
+            tx.add_section(Section::Signature(Signature::new(
+                vec![tx.raw_header_hash()],
+                pks_map.index_secret_keys(vec![secret_key]),
+                None,
+            )));
+            let signed_tx = tx.clone();
+            vp_env.tx = signed_tx.clone();
+            let keys_changed: BTreeSet<storage::Key> =
+                vp_env.all_touched_storage_keys();
+            let verifiers: BTreeSet<Address> = BTreeSet::default();
+            vp_host_env::set(vp_env);
+            assert_eq!(
+                validate_tx(&CTX, signed_tx, vp_owner, keys_changed, verifiers)
+                    .unwrap(),
+                expected
+            );
+        }
+    }
+
     /// Test that a transfer on with accounts other than self is accepted.
     #[test]
     fn test_transfer_between_other_parties_accepted() {